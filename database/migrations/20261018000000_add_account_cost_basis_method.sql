@@ -0,0 +1,3 @@
+ALTER TABLE account
+    ADD COLUMN cost_basis_method TEXT DEFAULT 'fifo' NOT NULL
+    CHECK (cost_basis_method IN ('fifo', 'lifo', 'hifo', 'average_cost'));
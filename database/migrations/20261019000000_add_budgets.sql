@@ -0,0 +1,15 @@
+CREATE TABLE budget (
+    id UUID DEFAULT gen_random_uuid() NOT NULL,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    category_id INT REFERENCES transaction_categories(id) ON DELETE CASCADE,
+    category_type_id INT REFERENCES transaction_category_type(id) ON DELETE CASCADE,
+    amount DECIMAL NOT NULL CHECK (amount >= 0),
+    period TEXT NOT NULL CHECK (period IN ('weekly', 'monthly', 'yearly')),
+    rollover BOOLEAN DEFAULT false NOT NULL,
+    start_date TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT now() NOT NULL,
+    updated_at TIMESTAMPTZ DEFAULT now() NOT NULL,
+    CONSTRAINT budget_pk PRIMARY KEY (id),
+    CONSTRAINT budget_single_target CHECK ((category_id IS NULL) <> (category_type_id IS NULL))
+);
+CREATE INDEX idx_budget_user_id ON budget(user_id);
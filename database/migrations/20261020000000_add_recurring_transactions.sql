@@ -0,0 +1,20 @@
+CREATE TABLE recurring_transaction (
+    id UUID DEFAULT gen_random_uuid() NOT NULL,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    account_id UUID NOT NULL REFERENCES account(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    template JSONB NOT NULL,
+    rrule TEXT NOT NULL,
+    start_date TIMESTAMPTZ NOT NULL,
+    end_date TIMESTAMPTZ,
+    next_occurrence TIMESTAMPTZ,
+    last_occurrence TIMESTAMPTZ,
+    create_as_ghost BOOLEAN DEFAULT false NOT NULL,
+    active BOOLEAN DEFAULT true NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT now() NOT NULL,
+    updated_at TIMESTAMPTZ DEFAULT now() NOT NULL,
+    CONSTRAINT recurring_transaction_pk PRIMARY KEY (id),
+    CONSTRAINT recurring_transaction_end_after_start CHECK (end_date IS NULL OR end_date >= start_date)
+);
+CREATE INDEX idx_recurring_transaction_user_id ON recurring_transaction(user_id);
+CREATE INDEX idx_recurring_transaction_due ON recurring_transaction(next_occurrence) WHERE active;
@@ -0,0 +1,13 @@
+INSERT INTO connector_provider (kind, display_name) VALUES
+    ('statement_file', 'Statement file')
+ON CONFLICT (kind) DO NOTHING;
+
+CREATE TABLE statement_csv_mapping (
+    account_id UUID NOT NULL REFERENCES account(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    mapping JSONB NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT now() NOT NULL,
+    updated_at TIMESTAMPTZ DEFAULT now() NOT NULL,
+    CONSTRAINT statement_csv_mapping_pk PRIMARY KEY (account_id)
+);
+CREATE INDEX idx_statement_csv_mapping_user_id ON statement_csv_mapping(user_id);
@@ -0,0 +1,13 @@
+CREATE TABLE account_reconciliation (
+    id UUID DEFAULT gen_random_uuid() NOT NULL,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    account_id UUID NOT NULL REFERENCES account(id) ON DELETE CASCADE,
+    asset_id INT NOT NULL REFERENCES assets(id),
+    statement_date TIMESTAMPTZ NOT NULL,
+    statement_balance DECIMAL NOT NULL,
+    source TEXT DEFAULT 'manual' NOT NULL CHECK (source IN ('manual', 'connector')),
+    binding_id UUID REFERENCES connector_binding(id) ON DELETE SET NULL,
+    created_at TIMESTAMPTZ DEFAULT now() NOT NULL,
+    CONSTRAINT account_reconciliation_pk PRIMARY KEY (id)
+);
+CREATE INDEX idx_account_reconciliation_account ON account_reconciliation(account_id, asset_id, statement_date);
@@ -0,0 +1,22 @@
+CREATE TABLE tag (
+    id UUID DEFAULT gen_random_uuid() NOT NULL,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT now() NOT NULL,
+    CONSTRAINT tag_pk PRIMARY KEY (id),
+    CONSTRAINT tag_user_name_unique UNIQUE (user_id, name)
+);
+
+CREATE TABLE transaction_tag (
+    transaction_id UUID NOT NULL REFERENCES transaction(id) ON DELETE CASCADE,
+    tag_id UUID NOT NULL REFERENCES tag(id) ON DELETE CASCADE,
+    CONSTRAINT transaction_tag_pk PRIMARY KEY (transaction_id, tag_id)
+);
+CREATE INDEX idx_transaction_tag_tag_id ON transaction_tag(tag_id);
+
+CREATE TABLE transaction_group_tag (
+    group_id UUID NOT NULL REFERENCES transaction_group(id) ON DELETE CASCADE,
+    tag_id UUID NOT NULL REFERENCES tag(id) ON DELETE CASCADE,
+    CONSTRAINT transaction_group_tag_pk PRIMARY KEY (group_id, tag_id)
+);
+CREATE INDEX idx_transaction_group_tag_tag_id ON transaction_group_tag(tag_id);
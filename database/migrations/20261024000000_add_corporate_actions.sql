@@ -0,0 +1,21 @@
+CREATE TABLE corporate_action (
+    id UUID DEFAULT gen_random_uuid() NOT NULL,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    asset_id INT NOT NULL REFERENCES assets(id),
+    action_type TEXT NOT NULL CHECK (action_type IN ('split', 'reverse_split', 'spin_off', 'ticker_change')),
+    effective_date TIMESTAMPTZ NOT NULL,
+    ratio_from DECIMAL NOT NULL CHECK (ratio_from > 0),
+    ratio_to DECIMAL NOT NULL CHECK (ratio_to > 0),
+    new_asset_id INT REFERENCES assets(id),
+    cost_basis_fraction DECIMAL CHECK (cost_basis_fraction > 0 AND cost_basis_fraction < 1),
+    created_at TIMESTAMPTZ DEFAULT now() NOT NULL,
+    CONSTRAINT corporate_action_pk PRIMARY KEY (id),
+    CONSTRAINT corporate_action_new_asset_check CHECK (
+        (action_type IN ('spin_off', 'ticker_change')) = (new_asset_id IS NOT NULL)
+        AND new_asset_id IS DISTINCT FROM asset_id
+    ),
+    CONSTRAINT corporate_action_cost_basis_fraction_check CHECK (
+        (action_type = 'spin_off') = (cost_basis_fraction IS NOT NULL)
+    )
+);
+CREATE INDEX idx_corporate_action_user_date ON corporate_action(user_id, effective_date);
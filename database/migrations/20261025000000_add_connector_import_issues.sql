@@ -0,0 +1,30 @@
+CREATE TABLE connector_import_issue (
+    id UUID DEFAULT gen_random_uuid() NOT NULL,
+    binding_id UUID NOT NULL REFERENCES connector_binding(id) ON DELETE CASCADE,
+    page_id UUID NOT NULL REFERENCES connector_raw_page(id) ON DELETE CASCADE,
+    external_id TEXT NOT NULL,
+    stage TEXT NOT NULL CHECK (stage IN ('skipped', 'unresolved')),
+    reason TEXT NOT NULL,
+    date_transacted TIMESTAMPTZ,
+    amount DECIMAL,
+    currency TEXT,
+    description TEXT,
+    asset_identifier TEXT,
+    quantity DECIMAL,
+    dismissed BOOLEAN DEFAULT false NOT NULL,
+    first_seen_at TIMESTAMPTZ DEFAULT now() NOT NULL,
+    last_seen_at TIMESTAMPTZ DEFAULT now() NOT NULL,
+    CONSTRAINT connector_import_issue_pk PRIMARY KEY (id),
+    CONSTRAINT connector_import_issue_binding_ext_key UNIQUE (binding_id, external_id)
+);
+CREATE INDEX idx_connector_import_issue_binding_id ON connector_import_issue(binding_id);
+
+CREATE TABLE user_ticker_alias (
+    id UUID DEFAULT gen_random_uuid() NOT NULL,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    provider_ticker TEXT NOT NULL,
+    asset_id INT NOT NULL REFERENCES assets(id) ON DELETE CASCADE,
+    created_at TIMESTAMPTZ DEFAULT now() NOT NULL,
+    CONSTRAINT user_ticker_alias_pk PRIMARY KEY (id),
+    CONSTRAINT user_ticker_alias_user_ticker_key UNIQUE (user_id, provider_ticker)
+);
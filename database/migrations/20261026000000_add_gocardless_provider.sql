@@ -0,0 +1,3 @@
+INSERT INTO connector_provider (kind, display_name) VALUES
+    ('gocardless', 'GoCardless')
+ON CONFLICT (kind) DO NOTHING;
@@ -0,0 +1,4 @@
+INSERT INTO connector_provider (kind, display_name) VALUES
+    ('kraken', 'Kraken'),
+    ('crypto_wallet', 'Crypto wallet')
+ON CONFLICT (kind) DO NOTHING;
@@ -0,0 +1,12 @@
+-- Imported transactions that may duplicate one the user entered by hand. The import is
+-- held back as a ghost transaction until the user merges or accepts it in the review inbox.
+CREATE TABLE connector_duplicate_candidate (
+    id UUID DEFAULT gen_random_uuid() NOT NULL,
+    transaction_id UUID NOT NULL REFERENCES transaction(id) ON DELETE CASCADE,
+    candidate_transaction_id UUID NOT NULL REFERENCES transaction(id) ON DELETE CASCADE,
+    score REAL NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT now() NOT NULL,
+    CONSTRAINT connector_duplicate_candidate_pk PRIMARY KEY (id),
+    CONSTRAINT connector_duplicate_candidate_pair_key UNIQUE (transaction_id, candidate_transaction_id)
+);
+CREATE INDEX idx_connector_duplicate_candidate_candidate_id ON connector_duplicate_candidate(candidate_transaction_id);
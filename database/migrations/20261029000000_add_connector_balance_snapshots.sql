@@ -0,0 +1,14 @@
+-- Balances a provider reported for an account, one set of rows per sync. Kept as
+-- reported, next to the Sverto asset each resolved to at the time.
+CREATE TABLE connector_balance_snapshot (
+    id UUID DEFAULT gen_random_uuid() NOT NULL,
+    provider_account_id UUID NOT NULL REFERENCES connector_provider_account(id) ON DELETE CASCADE,
+    fetched_at TIMESTAMPTZ NOT NULL,
+    kind TEXT NOT NULL CHECK (kind IN ('cash', 'holding')),
+    asset_identifier TEXT NOT NULL,
+    asset_id INTEGER REFERENCES assets(id) ON DELETE SET NULL,
+    quantity NUMERIC NOT NULL,
+    CONSTRAINT connector_balance_snapshot_pk PRIMARY KEY (id),
+    CONSTRAINT connector_balance_snapshot_key UNIQUE (provider_account_id, fetched_at, kind, asset_identifier)
+);
+CREATE INDEX idx_connector_balance_snapshot_account_fetched ON connector_balance_snapshot(provider_account_id, fetched_at);
@@ -0,0 +1,35 @@
+-- User-defined rules that categorize, tag, rename and hide imported transactions. Rules
+-- run in ascending priority: the first matching rule that sets an action wins it, tags
+-- from every matching rule are added.
+CREATE TABLE transaction_rule (
+    id UUID DEFAULT gen_random_uuid() NOT NULL,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    name TEXT NOT NULL,
+    priority INT DEFAULT 0 NOT NULL,
+    enabled BOOLEAN DEFAULT true NOT NULL,
+    description_contains TEXT,
+    description_regex TEXT,
+    amount_min DECIMAL,
+    amount_max DECIMAL,
+    account_id UUID REFERENCES account(id) ON DELETE CASCADE,
+    counterparty TEXT,
+    category_id INT REFERENCES transaction_categories(id) ON DELETE SET NULL,
+    description_rewrite TEXT,
+    visibility TEXT CHECK (visibility IN ('default', 'ghost', 'hidden')),
+    created_at TIMESTAMPTZ DEFAULT now() NOT NULL,
+    updated_at TIMESTAMPTZ DEFAULT now() NOT NULL,
+    CONSTRAINT transaction_rule_pk PRIMARY KEY (id),
+    CONSTRAINT transaction_rule_amount_range CHECK (amount_min IS NULL OR amount_max IS NULL OR amount_min <= amount_max)
+);
+CREATE INDEX idx_transaction_rule_user_id ON transaction_rule(user_id, priority);
+
+CREATE TABLE transaction_rule_tag (
+    rule_id UUID NOT NULL REFERENCES transaction_rule(id) ON DELETE CASCADE,
+    tag_id UUID NOT NULL REFERENCES tag(id) ON DELETE CASCADE,
+    CONSTRAINT transaction_rule_tag_pk PRIMARY KEY (rule_id, tag_id)
+);
+CREATE INDEX idx_transaction_rule_tag_tag_id ON transaction_rule_tag(tag_id);
+
+-- The other party a provider reported for an import, kept so rules re-applied to history
+-- can match on it.
+ALTER TABLE connector_transaction ADD COLUMN counterparty TEXT;
@@ -0,0 +1,56 @@
+-- Every stored vector records the embedding model and dimension that produced it, so
+-- searches only compare vectors of the same model. The columns lose their fixed
+-- dimension; HNSW indexes need one, so they become partial expression indexes over
+-- the default dimension. Queries cast the column to the dimension they search with.
+--
+-- Which model produced the existing vectors is not recorded anywhere, so they are left
+-- without one. That marks them stale: searches fall back to text until the re-embed
+-- job, enqueued when the worker starts, has replaced them.
+DROP INDEX IF EXISTS idx_td_embedding;
+DROP INDEX IF EXISTS idx_tg_embedding;
+DROP INDEX IF EXISTS idx_assets_embedding;
+DROP INDEX IF EXISTS idx_transaction_categories_embedding;
+
+ALTER TABLE transaction_descriptions
+    ALTER COLUMN embedding TYPE vector,
+    ADD COLUMN embedding_model TEXT,
+    ADD COLUMN embedding_dims INT;
+
+ALTER TABLE transaction_group
+    ALTER COLUMN description_embedding TYPE vector,
+    ADD COLUMN description_embedding_model TEXT,
+    ADD COLUMN description_embedding_dims INT;
+
+ALTER TABLE assets
+    ALTER COLUMN embedding TYPE vector,
+    ADD COLUMN embedding_model TEXT,
+    ADD COLUMN embedding_dims INT;
+
+ALTER TABLE transaction_categories
+    ALTER COLUMN embedding TYPE vector,
+    ADD COLUMN embedding_model TEXT,
+    ADD COLUMN embedding_dims INT;
+
+CREATE INDEX idx_td_embedding ON transaction_descriptions
+    USING hnsw ((embedding::vector(1536)) vector_cosine_ops) WHERE embedding_dims = 1536;
+CREATE INDEX idx_tg_embedding ON transaction_group
+    USING hnsw ((description_embedding::vector(1536)) vector_cosine_ops) WHERE description_embedding_dims = 1536;
+CREATE INDEX idx_assets_embedding ON assets
+    USING hnsw ((embedding::vector(1536)) vector_cosine_ops) WHERE embedding_dims = 1536;
+CREATE INDEX idx_transaction_categories_embedding ON transaction_categories
+    USING hnsw ((embedding::vector(1536)) vector_cosine_ops) WHERE embedding_dims = 1536;
+
+-- Rows still to re-embed after the active model changed.
+CREATE INDEX idx_td_embedding_model ON transaction_descriptions(embedding_model, embedding_dims);
+
+-- One row per model a re-embed run was started for, so workers starting side by side
+-- enqueue a single run. `updated_at` moves with every batch; a run that stops moving is
+-- taken over by the next worker to start.
+CREATE TABLE ai_reembed_run (
+    embedding_model TEXT NOT NULL,
+    embedding_dims INT NOT NULL,
+    started_at TIMESTAMPTZ DEFAULT now() NOT NULL,
+    updated_at TIMESTAMPTZ DEFAULT now() NOT NULL,
+    finished_at TIMESTAMPTZ,
+    CONSTRAINT ai_reembed_run_pk PRIMARY KEY (embedding_model, embedding_dims)
+);
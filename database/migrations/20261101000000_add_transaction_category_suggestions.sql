@@ -0,0 +1,13 @@
+-- Categories the categorizer suggests for transactions still in the import default
+-- category. One suggestion per transaction; a later run replaces it.
+CREATE TABLE transaction_category_suggestion (
+    transaction_id UUID NOT NULL REFERENCES transaction(id) ON DELETE CASCADE,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    category_id INT NOT NULL REFERENCES transaction_categories(id) ON DELETE CASCADE,
+    confidence REAL NOT NULL CHECK (confidence >= 0 AND confidence <= 1),
+    reason TEXT,
+    model TEXT NOT NULL,
+    created_at TIMESTAMPTZ DEFAULT now() NOT NULL,
+    CONSTRAINT transaction_category_suggestion_pk PRIMARY KEY (transaction_id)
+);
+CREATE INDEX idx_transaction_category_suggestion_user_id ON transaction_category_suggestion(user_id);
@@ -0,0 +1,23 @@
+-- Weekly and monthly digests the worker builds for every user once a period is over.
+-- `content` holds the figures, `narrative` Myra's write-up of them when the user's AI
+-- budget allowed for one.
+CREATE TABLE insight_digest (
+    id UUID DEFAULT gen_random_uuid() NOT NULL,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    period TEXT NOT NULL CHECK (period IN ('weekly', 'monthly')),
+    period_start TIMESTAMPTZ NOT NULL,
+    period_end TIMESTAMPTZ NOT NULL,
+    reference_asset_id INT NOT NULL REFERENCES assets(id),
+    content JSONB NOT NULL,
+    narrative TEXT,
+    model TEXT,
+    read_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ DEFAULT now() NOT NULL,
+    CONSTRAINT insight_digest_pk PRIMARY KEY (id),
+    CONSTRAINT insight_digest_user_period_uq UNIQUE (user_id, period, period_start),
+    CONSTRAINT insight_digest_period_range CHECK (period_start < period_end)
+);
+CREATE INDEX idx_insight_digest_user_id ON insight_digest(user_id, period_start DESC);
+
+-- Digests are only sent to the AI provider for narration once the user opts in.
+ALTER TABLE users ADD COLUMN narrate_insight_digests BOOLEAN NOT NULL DEFAULT false;
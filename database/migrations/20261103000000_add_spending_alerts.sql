@@ -0,0 +1,22 @@
+-- Alerts the anomaly detector raises on new outgoing transactions, manual or imported:
+-- amounts far above the usual for the merchant or category, the same charge twice
+-- within a day, a first purchase from a merchant in a foreign currency, and a currency the
+-- account is not normally charged in. A transaction gets at most one alert per kind.
+CREATE TABLE spending_alert (
+    id UUID DEFAULT gen_random_uuid() NOT NULL,
+    user_id UUID NOT NULL REFERENCES users(id) ON DELETE CASCADE,
+    transaction_id UUID NOT NULL REFERENCES transaction(id) ON DELETE CASCADE,
+    kind TEXT NOT NULL CHECK (kind IN ('unusual_amount', 'duplicate_charge', 'new_merchant_abroad', 'unexpected_currency')),
+    account_id UUID NOT NULL REFERENCES account(id) ON DELETE CASCADE,
+    asset_id INT NOT NULL REFERENCES assets(id),
+    amount DECIMAL NOT NULL,
+    typical_amount DECIMAL,
+    related_transaction_id UUID REFERENCES transaction(id) ON DELETE SET NULL,
+    description TEXT,
+    date_transacted TIMESTAMPTZ NOT NULL,
+    dismissed_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ DEFAULT now() NOT NULL,
+    CONSTRAINT spending_alert_pk PRIMARY KEY (id),
+    CONSTRAINT spending_alert_transaction_kind_uq UNIQUE (transaction_id, kind)
+);
+CREATE INDEX idx_spending_alert_user_id ON spending_alert(user_id, created_at DESC);
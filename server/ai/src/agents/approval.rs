@@ -9,7 +9,7 @@ use std::sync::Arc;
 
 use rig::agent::{HookAction, PromptHook, ToolCallHookAction};
 use rig::completion::message::Message;
-use rig::providers::gemini;
+use rig::completion::CompletionModel;
 use rig::tool::Tool;
 
 use crate::action_provider::AiActionProvider;
@@ -93,7 +93,7 @@ impl ApprovalHook {
     }
 }
 
-impl PromptHook<gemini::completion::CompletionModel> for ApprovalHook {
+impl<M: CompletionModel> PromptHook<M> for ApprovalHook {
     // Once a gated call has been captured, stop before the next model turn so
     // the user can approve. Skipping (rather than terminating) on the call
     // itself lets every gated call in the same turn be captured first.
@@ -1,3 +1,408 @@
-// TODO: Implement auto-categorization agent
-// This agent will analyze transaction descriptions and suggest appropriate categories.
-// It will use a limited tool set focused on category listing and matching.
+//! Batch categorizer for transactions still in the import category. Context is
+//! gathered up front instead of through tool calls: for every transaction the
+//! categories closest to its description (`search_categories`) and the user's own
+//! categorised transactions closest to it (`find_categorized_examples`). Transactions
+//! whose closest examples all share a category get that category without asking the
+//! model. The model then answers for the rest of the batch in a single completion,
+//! charged through the `RateLimitProvider` like a chat turn.
+
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use rig::client::CompletionClient;
+use rig::completion::Prompt;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::config::AiConfig;
+use crate::data_provider::AiDataProvider;
+use crate::embedding::Embedder;
+use crate::models::categorization::{
+    CategorizedExample, CategorizerOutput, CategorySuggestion, UncategorizedTransaction,
+};
+use crate::models::error::AiError;
+use crate::models::reference::CategoryResult;
+use crate::models::search::QueryEmbedding;
+use crate::provider::AiClient;
+use crate::rate_limit_provider::RateLimitProvider;
+use crate::with_ai_client;
+
+const SYSTEM_PROMPT: &str = r#"You categorize financial transactions for a personal finance app.
+
+The input is a JSON object with:
+- "categories": every category the user can choose from ({id, category, category_type}).
+- "transactions": the transactions to categorize. Each has a description, date, amount (negative = money out), asset and account, plus:
+  - "nearest_categories": ids of the categories whose names are closest to the description, closest first.
+  - "similar_transactions": transactions the user has already categorized that resemble this one, with the category they chose and how similar their description is (up to 1).
+
+Rules:
+- Pick category ids from "categories" only.
+- How the user categorized the same merchant or payee before beats the closest category name.
+- Mind the sign: income and refunds are not expenses.
+- Give every transaction a suggestion. When unsure, still suggest the best fit with a low confidence.
+- Confidence is between 0 and 1: 0.9 or more only when a similar transaction from the same merchant was categorized the same way, 0.5 or less when guessing.
+- Reason is one short sentence.
+
+Your reply MUST be ONLY a valid JSON object (no markdown, no explanation) with this structure:
+{"suggestions":[{"transaction_id":"uuid","category_id":N,"confidence":0.0,"reason":"..."}]}"#;
+
+/// Categories offered per transaction as the nearest by name.
+const NEAREST_CATEGORIES: usize = 8;
+
+/// Categorised transactions of the user shown per transaction.
+const EXAMPLES_PER_TRANSACTION: i64 = 5;
+
+/// How similar an example has to be to count towards a suggestion made without the
+/// model.
+const SIMILAR_EXAMPLE_THRESHOLD: f32 = 0.9;
+
+/// Agreeing examples needed for a suggestion made without the model.
+const MIN_SIMILAR_EXAMPLES: usize = 2;
+
+#[derive(Serialize)]
+struct CategorizerInput<'a> {
+    categories: &'a [CategoryResult],
+    transactions: Vec<TransactionContext<'a>>,
+}
+
+#[derive(Serialize)]
+struct TransactionContext<'a> {
+    transaction_id: Uuid,
+    description: &'a str,
+    date: String,
+    amount: String,
+    asset: &'a str,
+    account: &'a str,
+    nearest_categories: Vec<i32>,
+    similar_transactions: Vec<CategorizedExample>,
+}
+
+#[derive(Deserialize)]
+struct RawOutput {
+    suggestions: Vec<RawSuggestion>,
+}
+
+#[derive(Deserialize)]
+struct RawSuggestion {
+    transaction_id: Uuid,
+    category_id: i32,
+    confidence: f32,
+    #[serde(default)]
+    reason: Option<String>,
+}
+
+/// Suggests a category for each of `transactions`, from similar categorised
+/// transactions where they agree and from the model otherwise. Transactions the model
+/// skips or answers with an unknown category get no suggestion.
+#[tracing::instrument(skip_all, level = "debug", fields(model = %config.model, count = transactions.len()))]
+pub async fn categorize<D, R>(
+    config: &AiConfig,
+    data: Arc<D>,
+    rate_limit: Arc<R>,
+    transactions: Vec<UncategorizedTransaction>,
+) -> Result<CategorizerOutput, AiError>
+where
+    D: AiDataProvider,
+    R: RateLimitProvider,
+{
+    if transactions.is_empty() {
+        return Ok(CategorizerOutput {
+            suggestions: Vec::new(),
+        });
+    }
+
+    let client = AiClient::new(&config.completion)?;
+    let embedder = Embedder::new(config)?;
+
+    let categories = data.search_categories(None).await.map_err(data_error)?;
+    let category_ids: HashSet<i32> = categories.iter().map(|c| c.id).collect();
+    let vectors = embedder
+        .embed_many(transactions.iter().map(|t| t.description.clone()).collect())
+        .await?;
+
+    let mut suggestions = Vec::new();
+    let mut contexts = Vec::with_capacity(transactions.len());
+    for (transaction, vector) in transactions.iter().zip(vectors) {
+        let embedding = QueryEmbedding {
+            model: embedder.model_name().to_string(),
+            vector,
+        };
+        let nearest = data
+            .search_categories(Some(embedding.clone()))
+            .await
+            .map_err(data_error)?;
+        let examples = data
+            .find_categorized_examples(embedding, EXAMPLES_PER_TRANSACTION)
+            .await
+            .map_err(data_error)?;
+        if let Some(suggestion) = suggest_from_examples(
+            transaction.transaction_id,
+            &examples,
+            &category_ids,
+            embedder.model_name(),
+        ) {
+            suggestions.push(suggestion);
+            continue;
+        }
+        contexts.push(TransactionContext {
+            transaction_id: transaction.transaction_id,
+            description: &transaction.description,
+            date: transaction.date_transacted.date().to_string(),
+            amount: transaction.amount.to_string(),
+            asset: &transaction.asset_name,
+            account: &transaction.account_name,
+            nearest_categories: nearest
+                .iter()
+                .take(NEAREST_CATEGORIES)
+                .map(|c| c.id)
+                .collect(),
+            similar_transactions: examples,
+        });
+    }
+
+    if contexts.is_empty() {
+        return Ok(CategorizerOutput { suggestions });
+    }
+    let transaction_ids: HashSet<Uuid> = contexts.iter().map(|c| c.transaction_id).collect();
+
+    let prompt = serde_json::to_string(&CategorizerInput {
+        categories: &categories,
+        transactions: contexts,
+    })
+    .map_err(|e| AiError::unknown(format!("Failed to serialize categorizer input: {e}")))?;
+
+    rate_limit.pre_check(&prompt, &[], &[]).await?;
+    let response = with_ai_client!(client, |client| {
+        client
+            .agent(&config.model)
+            .preamble(SYSTEM_PROMPT)
+            .max_tokens(8192)
+            .build()
+            .prompt(prompt.as_str())
+            .extended_details()
+            .await
+            .map(|r| (r.output, r.usage))
+    });
+    let (output, usage) = match response {
+        Ok(r) => r,
+        Err(e) => {
+            rate_limit.release().await;
+            return Err(AiError::from(e));
+        }
+    };
+    rate_limit
+        .record_usage(usage.input_tokens, usage.output_tokens)
+        .await;
+
+    suggestions.extend(parse_suggestions(
+        &output,
+        &transaction_ids,
+        &category_ids,
+        &config.model,
+    )?);
+    Ok(CategorizerOutput { suggestions })
+}
+
+/// The category of the examples nearly identical to the transaction, when there are
+/// enough of them and they all agree. Merchants the user always categorizes the same
+/// way then need no completion.
+fn suggest_from_examples(
+    transaction_id: Uuid,
+    examples: &[CategorizedExample],
+    category_ids: &HashSet<i32>,
+    model: &str,
+) -> Option<CategorySuggestion> {
+    let close: Vec<&CategorizedExample> = examples
+        .iter()
+        .filter(|e| e.similarity >= SIMILAR_EXAMPLE_THRESHOLD)
+        .collect();
+    let category_id = close.first()?.category_id;
+    if close.len() < MIN_SIMILAR_EXAMPLES
+        || !category_ids.contains(&category_id)
+        || close.iter().any(|e| e.category_id != category_id)
+    {
+        return None;
+    }
+    Some(CategorySuggestion {
+        transaction_id,
+        category_id,
+        confidence: close.iter().map(|e| e.similarity).fold(1.0, f32::min),
+        reason: Some(format!(
+            "Categorized like {} similar transactions",
+            close.len()
+        )),
+        model: model.to_string(),
+    })
+}
+
+fn data_error(e: anyhow::Error) -> AiError {
+    AiError::unknown(format!("{e:#}"))
+}
+
+/// Parses the model's reply, keeping one suggestion per requested transaction and
+/// only categories that were offered.
+fn parse_suggestions(
+    output: &str,
+    transaction_ids: &HashSet<Uuid>,
+    category_ids: &HashSet<i32>,
+    model: &str,
+) -> Result<Vec<CategorySuggestion>, AiError> {
+    let trimmed = output.trim();
+    // Some models fence the JSON despite being told not to.
+    let trimmed = trimmed
+        .strip_prefix("```json")
+        .or_else(|| trimmed.strip_prefix("```"))
+        .and_then(|s| s.strip_suffix("```"))
+        .unwrap_or(trimmed)
+        .trim();
+
+    let parsed: RawOutput = serde_json::from_str(trimmed).map_err(|e| {
+        AiError::unknown(format!(
+            "Failed to parse categorizer output: {e}. Output was: {trimmed}"
+        ))
+    })?;
+
+    let mut seen = HashSet::new();
+    Ok(parsed
+        .suggestions
+        .into_iter()
+        .filter(|s| transaction_ids.contains(&s.transaction_id))
+        .filter(|s| category_ids.contains(&s.category_id))
+        .filter(|s| seen.insert(s.transaction_id))
+        .map(|s| CategorySuggestion {
+            transaction_id: s.transaction_id,
+            category_id: s.category_id,
+            confidence: if s.confidence.is_finite() {
+                s.confidence.clamp(0.0, 1.0)
+            } else {
+                0.0
+            },
+            reason: s.reason.filter(|r| !r.trim().is_empty()),
+            model: model.to_string(),
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ids() -> (Uuid, Uuid, HashSet<Uuid>, HashSet<i32>) {
+        let a = Uuid::from_u128(1);
+        let b = Uuid::from_u128(2);
+        (a, b, HashSet::from([a, b]), HashSet::from([10, 20]))
+    }
+
+    #[test]
+    fn test_parse_keeps_valid_suggestions() {
+        let (a, b, transactions, categories) = ids();
+        let output = format!(
+            r#"{{"suggestions":[{{"transaction_id":"{a}","category_id":10,"confidence":0.8,"reason":"Grocery store"}},{{"transaction_id":"{b}","category_id":20,"confidence":0.3}}]}}"#
+        );
+        let result = parse_suggestions(&output, &transactions, &categories, "model").unwrap();
+        assert_eq!(
+            result,
+            vec![
+                CategorySuggestion {
+                    transaction_id: a,
+                    category_id: 10,
+                    confidence: 0.8,
+                    reason: Some("Grocery store".to_string()),
+                    model: "model".to_string(),
+                },
+                CategorySuggestion {
+                    transaction_id: b,
+                    category_id: 20,
+                    confidence: 0.3,
+                    reason: None,
+                    model: "model".to_string(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_strips_code_fence() {
+        let (a, _, transactions, categories) = ids();
+        let output = format!(
+            "```json\n{{\"suggestions\":[{{\"transaction_id\":\"{a}\",\"category_id\":10,\"confidence\":0.5}}]}}\n```"
+        );
+        let result = parse_suggestions(&output, &transactions, &categories, "model").unwrap();
+        assert_eq!(result.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_drops_unknown_transactions_and_categories() {
+        let (a, b, transactions, categories) = ids();
+        let unknown = Uuid::from_u128(3);
+        let output = format!(
+            r#"{{"suggestions":[{{"transaction_id":"{unknown}","category_id":10,"confidence":0.9}},{{"transaction_id":"{a}","category_id":99,"confidence":0.9}},{{"transaction_id":"{b}","category_id":10,"confidence":0.9}}]}}"#
+        );
+        let result = parse_suggestions(&output, &transactions, &categories, "model").unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].transaction_id, b);
+    }
+
+    #[test]
+    fn test_parse_keeps_first_suggestion_per_transaction() {
+        let (a, _, transactions, categories) = ids();
+        let output = format!(
+            r#"{{"suggestions":[{{"transaction_id":"{a}","category_id":10,"confidence":0.9}},{{"transaction_id":"{a}","category_id":20,"confidence":0.9}}]}}"#
+        );
+        let result = parse_suggestions(&output, &transactions, &categories, "model").unwrap();
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].category_id, 10);
+    }
+
+    #[test]
+    fn test_parse_clamps_confidence() {
+        let (a, b, transactions, categories) = ids();
+        let output = format!(
+            r#"{{"suggestions":[{{"transaction_id":"{a}","category_id":10,"confidence":1.7}},{{"transaction_id":"{b}","category_id":10,"confidence":-0.2}}]}}"#
+        );
+        let result = parse_suggestions(&output, &transactions, &categories, "model").unwrap();
+        assert_eq!(result[0].confidence, 1.0);
+        assert_eq!(result[1].confidence, 0.0);
+    }
+
+    #[test]
+    fn test_parse_rejects_non_json() {
+        let (_, _, transactions, categories) = ids();
+        assert!(parse_suggestions("Groceries", &transactions, &categories, "model").is_err());
+    }
+
+    fn example(category_id: i32, similarity: f32) -> CategorizedExample {
+        CategorizedExample {
+            description: "TESCO STORES 3297".to_string(),
+            category_id,
+            category: "Groceries".to_string(),
+            similarity,
+        }
+    }
+
+    #[test]
+    fn test_examples_agreeing_give_suggestion() {
+        let (a, _, _, categories) = ids();
+        let examples = [example(10, 0.97), example(10, 0.93), example(20, 0.6)];
+        let suggestion = suggest_from_examples(a, &examples, &categories, "embed").unwrap();
+        assert_eq!(suggestion.category_id, 10);
+        assert_eq!(suggestion.confidence, 0.93);
+        assert_eq!(suggestion.model, "embed");
+    }
+
+    #[test]
+    fn test_examples_disagreeing_or_too_few_give_none() {
+        let (a, _, _, categories) = ids();
+        let disagreeing = [example(10, 0.97), example(20, 0.95)];
+        assert!(suggest_from_examples(a, &disagreeing, &categories, "embed").is_none());
+        let single = [example(10, 0.97), example(10, 0.5)];
+        assert!(suggest_from_examples(a, &single, &categories, "embed").is_none());
+    }
+
+    #[test]
+    fn test_examples_of_unoffered_category_give_none() {
+        let (a, _, _, categories) = ids();
+        let examples = [example(99, 0.97), example(99, 0.96)];
+        assert!(suggest_from_examples(a, &examples, &categories, "embed").is_none());
+    }
+}
@@ -1,16 +1,22 @@
 use std::sync::Arc;
 
+use futures::stream::BoxStream;
+use futures::StreamExt;
 use rig::agent::Agent;
-use rig::client::{CompletionClient, EmbeddingsClient};
-use rig::embeddings::EmbeddingModel;
+use rig::client::CompletionClient;
 use rig::providers::gemini;
 use rig::tool::{ToolDyn, ToolSet};
 
 use crate::action_provider::AiActionProvider;
-use crate::config::AiConfig;
+use crate::config::{AiConfig, AiProviderKind};
+use crate::conversation::Conversation;
+use crate::conversation_provider::ConversationProvider;
 use crate::data_provider::AiDataProvider;
-use crate::embedding::EMBEDDING_DIMS;
-use crate::provider::create_gemini_client;
+use crate::embedding::Embedder;
+use crate::models::chat::{ChatStreamEvent, ChatTurn};
+use crate::models::error::AiError;
+use crate::provider::AiClient;
+use crate::rate_limit_provider::RateLimitProvider;
 use crate::tools::aggregate_transactions::AggregateTransactionsTool;
 use crate::tools::create_custom_asset::CreateCustomAssetTool;
 use crate::tools::create_transaction::CreateTransactionTool;
@@ -19,6 +25,7 @@ use crate::tools::get_asset_price::GetAssetPriceTool;
 use crate::tools::get_holdings::GetHoldingsTool;
 use crate::tools::get_net_worth_history::GetNetWorthHistoryTool;
 use crate::tools::get_portfolio_overview::GetPortfolioOverviewTool;
+use crate::tools::get_subscriptions::GetSubscriptionsTool;
 use crate::tools::get_transaction_detail::GetTransactionDetailTool;
 use crate::tools::group_transactions::GroupTransactionsTool;
 use crate::tools::list_accounts::ListAccountsTool;
@@ -36,6 +43,7 @@ use crate::tools::search_categories::SearchCategoriesTool;
 use crate::tools::update_asset_valuation::UpdateAssetValuationTool;
 use crate::tools::update_transaction::UpdateTransactionTool;
 use crate::tools::ToolMode;
+use crate::with_ai_client;
 use gemini::completion::gemini_api_types::{
     AdditionalParameters, GenerationConfig, ThinkingConfig,
 };
@@ -80,8 +88,9 @@ const SYSTEM_PROMPT: &str = r#"You are Myra, a personal finance assistant. You h
 
 ## Choosing a Transaction Tool
 - query_transactions — find or browse individual transactions. Pass `query` to search by meaning/merchant; omit it to browse most-recent-first. Optionally filter by account, transaction_types, and dates. Each row's amount comes WITH its unit (currency code or asset ticker) — never assume a currency, read the unit.
-- aggregate_transactions — grouped spending/income totals in ONE currency (the user's default unless you pass currency_asset_id); the result states which currency. Use for "spending by category/month/account".
+- aggregate_transactions — grouped spending/income totals in ONE currency (the user's default unless you pass currency_asset_id); the result states which currency. Use for "spending by category/month/account/tag"; tags are user labels such as trips or reimbursable work expenses.
 - get_transaction_detail — expand one transaction into its full legs and fees, using a transaction_id from a prior query_transactions result.
+- get_subscriptions — recurring payments detected from transaction history, with their cadence, usual amount, and next expected charge. Use for "what am I subscribed to?" instead of searching transactions yourself.
 
 ## Custom Asset Creation
 - When the user asks to add, create, or track an asset (stock, ETF, crypto, etc.) that isn't already in their list, first call search_assets to confirm it's missing AND to find the base_pair_id (the currency the asset is denominated in — usually USD or EUR).
@@ -124,30 +133,62 @@ const SYSTEM_PROMPT: &str = r#"You are Myra, a personal finance assistant. You h
 {current_date}
 "#;
 
-pub async fn build_chat_agent_for_user<D: AiDataProvider, A: AiActionProvider>(
+/// Builds the chat agent for the configured completion provider and streams one
+/// turn of the conversation through it.
+pub async fn stream_chat<D, A, C, R>(
     config: AiConfig,
     data: Arc<D>,
     actions: Arc<A>,
-) -> Agent<gemini::completion::CompletionModel> {
-    let client = create_gemini_client(&config.api_key);
-    let embedding_model =
-        client.embedding_model_with_ndims(&config.embedding_model, EMBEDDING_DIMS);
+    conversation: &Conversation<C, R>,
+    turn: ChatTurn,
+) -> Result<BoxStream<'static, ChatStreamEvent>, AiError>
+where
+    D: AiDataProvider,
+    A: AiActionProvider,
+    C: ConversationProvider,
+    R: RateLimitProvider,
+{
+    let client = AiClient::new(&config.completion)?;
+    let embedder = Embedder::new(&config)?;
 
+    with_ai_client!(client, |client| {
+        let agent = build_chat_agent(&client, &config, embedder, data, actions.clone()).await;
+        conversation
+            .stream(agent, actions, turn)
+            .await
+            .map(|stream| stream.boxed())
+    })
+}
+
+pub async fn build_chat_agent<C, D, A>(
+    client: &C,
+    config: &AiConfig,
+    embedder: Embedder,
+    data: Arc<D>,
+    actions: Arc<A>,
+) -> Agent<C::CompletionModel>
+where
+    C: CompletionClient,
+    D: AiDataProvider,
+    A: AiActionProvider,
+{
     let current_date = time::OffsetDateTime::now_utc().date().to_string();
     let preamble = SYSTEM_PROMPT.replace("{current_date}", &current_date);
 
     let code_mode_sources: Arc<ToolSet> = Arc::new(ToolSet::from_tools_boxed(read_tools(
         &data,
-        &embedding_model,
+        &embedder,
         ToolMode::CodeMode,
     )));
 
-    client
+    let mut builder = client
         .agent(&config.model)
         .preamble(&preamble)
         .max_tokens(16384)
-        .default_max_turns(5)
-        .additional_params(
+        .default_max_turns(5);
+    // Thinking settings are Gemini request fields; other providers reject them.
+    if config.completion.provider == AiProviderKind::Gemini {
+        builder = builder.additional_params(
             serde_json::to_value(
                 AdditionalParameters::default().with_config(GenerationConfig {
                     thinking_config: Some(build_thinking_config(&config.model)),
@@ -155,8 +196,11 @@ pub async fn build_chat_agent_for_user<D: AiDataProvider, A: AiActionProvider>(
                 }),
             )
             .unwrap(),
-        )
-        .tools(read_tools(&data, &embedding_model, ToolMode::Normal))
+        );
+    }
+
+    builder
+        .tools(read_tools(&data, &embedder, ToolMode::Normal))
         .tool(CreateTransactionTool::new(actions.clone()))
         .tool(GroupTransactionsTool::new(actions.clone()))
         .tool(CreateCustomAssetTool::new(actions.clone()))
@@ -176,27 +220,26 @@ pub async fn build_chat_agent_for_user<D: AiDataProvider, A: AiActionProvider>(
 
 /// The read-only tools, built once for a given mode. `Normal` instances are
 /// registered on the agent; `CodeMode` instances back `run_script`'s datasets.
-fn read_tools<D, M>(data: &Arc<D>, embedding_model: &M, mode: ToolMode) -> Vec<Box<dyn ToolDyn>>
+fn read_tools<D>(data: &Arc<D>, embedder: &Embedder, mode: ToolMode) -> Vec<Box<dyn ToolDyn>>
 where
     D: AiDataProvider,
-    M: EmbeddingModel + Clone + Send + Sync + 'static,
 {
     vec![
         Box::new(QueryTransactionsTool::with_mode(
             data.clone(),
-            embedding_model.clone(),
+            embedder.clone(),
             mode,
         )) as Box<dyn ToolDyn>,
         Box::new(AggregateTransactionsTool::with_mode(data.clone(), mode)) as Box<dyn ToolDyn>,
         Box::new(ListAccountsTool::with_mode(data.clone(), mode)) as Box<dyn ToolDyn>,
         Box::new(SearchCategoriesTool::with_mode(
             data.clone(),
-            embedding_model.clone(),
+            embedder.clone(),
             mode,
         )) as Box<dyn ToolDyn>,
         Box::new(SearchAssetsTool::with_mode(
             data.clone(),
-            embedding_model.clone(),
+            embedder.clone(),
             mode,
         )) as Box<dyn ToolDyn>,
         Box::new(GetHoldingsTool::with_mode(data.clone(), mode)) as Box<dyn ToolDyn>,
@@ -204,6 +247,7 @@ where
         Box::new(GetPortfolioOverviewTool::with_mode(data.clone(), mode)) as Box<dyn ToolDyn>,
         Box::new(GetAssetPriceTool::with_mode(data.clone(), mode)) as Box<dyn ToolDyn>,
         Box::new(GetTransactionDetailTool::with_mode(data.clone(), mode)) as Box<dyn ToolDyn>,
+        Box::new(GetSubscriptionsTool::with_mode(data.clone(), mode)) as Box<dyn ToolDyn>,
     ]
 }
 
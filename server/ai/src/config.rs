@@ -1,33 +1,209 @@
 use std::env;
 
+/// Dimension the embedding columns were created with.
+pub const DEFAULT_EMBEDDING_DIMS: usize = 1536;
+
+const DEFAULT_OLLAMA_BASE_URL: &str = "http://localhost:11434";
+
+/// The service completions or embeddings are requested from.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AiProviderKind {
+    Gemini,
+    /// Any server speaking the OpenAI chat completions and embeddings API, such as
+    /// vLLM or the llama.cpp server.
+    OpenAiCompatible,
+    Ollama,
+}
+
+impl AiProviderKind {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "gemini" => Some(Self::Gemini),
+            "openai" | "openai_compatible" | "openai-compatible" => Some(Self::OpenAiCompatible),
+            "ollama" => Some(Self::Ollama),
+            _ => None,
+        }
+    }
+}
+
+/// Where one kind of model is served from.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AiEndpoint {
+    pub provider: AiProviderKind,
+    /// Required by Gemini; local servers usually run without one.
+    pub api_key: Option<String>,
+    /// Required for OpenAI-compatible servers; Ollama defaults to its local port.
+    pub base_url: Option<String>,
+}
+
 #[derive(Clone)]
 pub struct AiConfig {
-    pub api_key: String,
+    pub completion: AiEndpoint,
+    pub embedding: AiEndpoint,
     pub model: String,
     pub embedding_model: String,
+    /// Length of the vectors the embedding model is asked for. Stored vectors only
+    /// compare with vectors of the same model and length, so changing either means
+    /// re-embedding.
+    pub embedding_dims: usize,
 }
 
 impl AiConfig {
+    /// Reads `AI_PROVIDER` (`gemini`, `openai` or `ollama`, default `gemini`),
+    /// `AI_API_KEY`, `AI_BASE_URL`, `AI_MODEL`, `AI_EMBEDDING_MODEL` and
+    /// `AI_EMBEDDING_DIMS`. Embeddings come from the same endpoint unless
+    /// `AI_EMBEDDING_PROVIDER` names another one, configured through
+    /// `AI_EMBEDDING_API_KEY` and `AI_EMBEDDING_BASE_URL`.
     pub fn try_from_env() -> anyhow::Result<Self> {
-        let api_key = env::var("AI_API_KEY").map_err(|_| anyhow::anyhow!("AI_API_KEY not set"))?;
-        let model = env::var("AI_MODEL").map_err(|_| anyhow::anyhow!("AI_MODEL not set"))?;
-        let embedding_model = env::var("AI_EMBEDDING_MODEL")
-            .map_err(|_| anyhow::anyhow!("AI_EMBEDDING_MODEL not set"))?;
+        Self::from_lookup(|name| env::var(name).ok())
+    }
 
-        if api_key.is_empty() {
-            return Err(anyhow::anyhow!("AI_API_KEY is empty"));
-        }
-        if model.is_empty() {
-            return Err(anyhow::anyhow!("AI_MODEL is empty"));
-        }
-        if embedding_model.is_empty() {
-            return Err(anyhow::anyhow!("AI_EMBEDDING_MODEL is empty"));
-        }
+    fn from_lookup(lookup: impl Fn(&str) -> Option<String>) -> anyhow::Result<Self> {
+        let var = |name: &str| lookup(name).filter(|value| !value.trim().is_empty());
+
+        let completion = endpoint(
+            var("AI_PROVIDER").as_deref(),
+            var("AI_API_KEY"),
+            var("AI_BASE_URL"),
+            "AI",
+        )?;
+        let embedding = match var("AI_EMBEDDING_PROVIDER") {
+            Some(provider) => endpoint(
+                Some(&provider),
+                var("AI_EMBEDDING_API_KEY"),
+                var("AI_EMBEDDING_BASE_URL"),
+                "AI_EMBEDDING",
+            )?,
+            None => completion.clone(),
+        };
+
+        let model = var("AI_MODEL").ok_or_else(|| anyhow::anyhow!("AI_MODEL not set"))?;
+        let embedding_model = var("AI_EMBEDDING_MODEL")
+            .ok_or_else(|| anyhow::anyhow!("AI_EMBEDDING_MODEL not set"))?;
+        let embedding_dims = match var("AI_EMBEDDING_DIMS") {
+            Some(dims) => dims
+                .trim()
+                .parse::<usize>()
+                .ok()
+                .filter(|dims| *dims > 0)
+                .ok_or_else(|| anyhow::anyhow!("AI_EMBEDDING_DIMS must be a positive number"))?,
+            None => DEFAULT_EMBEDDING_DIMS,
+        };
 
         Ok(Self {
-            api_key,
+            completion,
+            embedding,
             model,
             embedding_model,
+            embedding_dims,
         })
     }
 }
+
+fn endpoint(
+    provider: Option<&str>,
+    api_key: Option<String>,
+    base_url: Option<String>,
+    prefix: &str,
+) -> anyhow::Result<AiEndpoint> {
+    let provider = match provider {
+        Some(value) => AiProviderKind::parse(value)
+            .ok_or_else(|| anyhow::anyhow!("{prefix}_PROVIDER has unknown provider '{value}'"))?,
+        None => AiProviderKind::Gemini,
+    };
+
+    let base_url = match provider {
+        AiProviderKind::Gemini => base_url,
+        AiProviderKind::OpenAiCompatible => {
+            Some(base_url.ok_or_else(|| anyhow::anyhow!("{prefix}_BASE_URL not set"))?)
+        }
+        AiProviderKind::Ollama => {
+            Some(base_url.unwrap_or_else(|| DEFAULT_OLLAMA_BASE_URL.to_string()))
+        }
+    };
+    if provider == AiProviderKind::Gemini && api_key.is_none() {
+        return Err(anyhow::anyhow!("{prefix}_API_KEY not set"));
+    }
+
+    Ok(AiEndpoint {
+        provider,
+        api_key,
+        base_url,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    fn config(vars: &[(&str, &str)]) -> anyhow::Result<AiConfig> {
+        let vars: HashMap<String, String> = vars
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        AiConfig::from_lookup(|name| vars.get(name).cloned())
+    }
+
+    #[test]
+    fn defaults_to_gemini_for_everything() {
+        let config = config(&[
+            ("AI_API_KEY", "key"),
+            ("AI_MODEL", "gemini-3-flash-preview"),
+            ("AI_EMBEDDING_MODEL", "gemini-embedding-2-preview"),
+        ])
+        .unwrap();
+
+        assert_eq!(config.completion.provider, AiProviderKind::Gemini);
+        assert_eq!(config.embedding, config.completion);
+        assert_eq!(config.embedding_dims, DEFAULT_EMBEDDING_DIMS);
+    }
+
+    #[test]
+    fn gemini_needs_an_api_key() {
+        let err = config(&[
+            ("AI_MODEL", "gemini-3-flash-preview"),
+            ("AI_EMBEDDING_MODEL", "gemini-embedding-2-preview"),
+        ])
+        .err()
+        .unwrap();
+
+        assert!(err.to_string().contains("AI_API_KEY"));
+    }
+
+    #[test]
+    fn local_providers_run_without_a_key() {
+        let config = config(&[
+            ("AI_PROVIDER", "openai"),
+            ("AI_BASE_URL", "http://localhost:8000/v1"),
+            ("AI_MODEL", "qwen3-14b"),
+            ("AI_EMBEDDING_PROVIDER", "ollama"),
+            ("AI_EMBEDDING_MODEL", "nomic-embed-text"),
+            ("AI_EMBEDDING_DIMS", "768"),
+        ])
+        .unwrap();
+
+        assert_eq!(config.completion.provider, AiProviderKind::OpenAiCompatible);
+        assert_eq!(config.completion.api_key, None);
+        assert_eq!(config.embedding.provider, AiProviderKind::Ollama);
+        assert_eq!(
+            config.embedding.base_url.as_deref(),
+            Some(DEFAULT_OLLAMA_BASE_URL)
+        );
+        assert_eq!(config.embedding_dims, 768);
+    }
+
+    #[test]
+    fn openai_compatible_needs_a_base_url() {
+        let err = config(&[
+            ("AI_PROVIDER", "openai"),
+            ("AI_MODEL", "qwen3-14b"),
+            ("AI_EMBEDDING_MODEL", "bge-m3"),
+        ])
+        .err()
+        .unwrap();
+
+        assert!(err.to_string().contains("AI_BASE_URL"));
+    }
+}
@@ -134,13 +134,15 @@ where
     }
 
     #[tracing::instrument(level = "debug", skip_all, fields(otel.kind = "client"))]
-    pub async fn stream<A>(
+    pub async fn stream<M, A>(
         &self,
-        agent: Agent<rig::providers::gemini::completion::CompletionModel>,
+        agent: Agent<M>,
         actions: Arc<A>,
         turn: ChatTurn,
     ) -> Result<impl Stream<Item = ChatStreamEvent>, AiError>
     where
+        M: rig::completion::CompletionModel + 'static,
+        Agent<M>: StreamingChat<M, M::StreamingResponse>,
         A: AiActionProvider,
     {
         let (message, file_ids, skip_record) = match &turn {
@@ -3,7 +3,10 @@ use uuid::Uuid;
 
 use crate::models::account::AccountResult;
 use crate::models::aggregate::{AggregateParams, AggregateResult};
+use crate::models::categorization::CategorizedExample;
 use crate::models::reference::{AssetResult, CategoryResult};
+use crate::models::search::QueryEmbedding;
+use crate::models::subscriptions::SubscriptionRow;
 use crate::models::transactions::{
     QueryTransactionsParams, QueryTransactionsResult, TransactionDetailResult,
 };
@@ -18,7 +21,7 @@ pub trait AiDataProvider: Send + Sync + 'static {
     fn query_transactions(
         &self,
         params: QueryTransactionsParams,
-        query_vector: Option<Vec<f64>>,
+        query_embedding: Option<QueryEmbedding>,
     ) -> impl std::future::Future<Output = Result<QueryTransactionsResult>> + Send;
 
     fn aggregate_transactions(
@@ -36,13 +39,21 @@ pub trait AiDataProvider: Send + Sync + 'static {
 
     fn search_categories(
         &self,
-        query_vector: Option<Vec<f64>>,
+        query_embedding: Option<QueryEmbedding>,
     ) -> impl std::future::Future<Output = Result<Vec<CategoryResult>>> + Send;
 
+    /// The user's categorised transactions closest to `query_embedding`, for the
+    /// categorizer to learn the user's habits from.
+    fn find_categorized_examples(
+        &self,
+        query_embedding: QueryEmbedding,
+        limit: i64,
+    ) -> impl std::future::Future<Output = Result<Vec<CategorizedExample>>> + Send;
+
     fn search_assets(
         &self,
         query: Option<&str>,
-        query_vector: Option<Vec<f64>>,
+        query_embedding: Option<QueryEmbedding>,
     ) -> impl std::future::Future<Output = Result<Vec<AssetResult>>> + Send;
 
     fn get_holdings(
@@ -77,4 +88,8 @@ pub trait AiDataProvider: Send + Sync + 'static {
         date_from: Option<String>,
         date_to: Option<String>,
     ) -> impl std::future::Future<Output = Result<AssetPriceResult>> + Send;
+
+    fn get_subscriptions(
+        &self,
+    ) -> impl std::future::Future<Output = Result<Vec<SubscriptionRow>>> + Send;
 }
@@ -1,11 +1,14 @@
+use std::sync::Arc;
+
+use futures::future::BoxFuture;
 use rig::client::EmbeddingsClient;
 use rig::embeddings::EmbeddingModel;
 
 use crate::config::AiConfig;
 use crate::models::error::AiError;
-use crate::provider::create_gemini_client;
-
-pub const EMBEDDING_DIMS: usize = 1536;
+use crate::models::search::QueryEmbedding;
+use crate::provider::AiClient;
+use crate::with_ai_client;
 
 #[tracing::instrument(skip_all, level = "debug", fields(text_len = text.len(), otel.kind = "client"))]
 pub async fn embed_query<M: EmbeddingModel>(model: &M, text: &str) -> Result<Vec<f64>, AiError> {
@@ -13,9 +16,112 @@ pub async fn embed_query<M: EmbeddingModel>(model: &M, text: &str) -> Result<Vec
     Ok(embedding.vec)
 }
 
+/// Object-safe view of a provider's embedding model.
+trait EmbedText: Send + Sync {
+    fn embed<'a>(&'a self, text: &'a str) -> BoxFuture<'a, Result<Vec<f64>, AiError>>;
+
+    fn embed_many(&self, texts: Vec<String>) -> BoxFuture<'_, Result<Vec<Vec<f64>>, AiError>>;
+}
+
+impl<M: EmbeddingModel + Send + Sync> EmbedText for M {
+    fn embed<'a>(&'a self, text: &'a str) -> BoxFuture<'a, Result<Vec<f64>, AiError>> {
+        Box::pin(embed_query(self, text))
+    }
+
+    fn embed_many(&self, texts: Vec<String>) -> BoxFuture<'_, Result<Vec<Vec<f64>>, AiError>> {
+        Box::pin(async move {
+            let mut vectors = Vec::with_capacity(texts.len());
+            for chunk in texts.chunks(M::MAX_DOCUMENTS.max(1)) {
+                let embeddings = self
+                    .embed_texts(chunk.to_vec())
+                    .await
+                    .map_err(AiError::from)?;
+                vectors.extend(embeddings.into_iter().map(|embedding| embedding.vec));
+            }
+            Ok(vectors)
+        })
+    }
+}
+
+/// The configured embedding model, whichever provider serves it.
+#[derive(Clone)]
+pub struct Embedder {
+    model: Arc<dyn EmbedText>,
+    model_name: String,
+    dims: usize,
+}
+
+impl Embedder {
+    pub fn new(config: &AiConfig) -> Result<Self, AiError> {
+        let client = AiClient::new(&config.embedding)?;
+        let model: Arc<dyn EmbedText> = with_ai_client!(client, |client| Arc::new(
+            client.embedding_model_with_ndims(&config.embedding_model, config.embedding_dims)
+        ));
+        Ok(Self {
+            model,
+            model_name: config.embedding_model.clone(),
+            dims: config.embedding_dims,
+        })
+    }
+
+    pub fn model_name(&self) -> &str {
+        &self.model_name
+    }
+
+    pub fn dims(&self) -> usize {
+        self.dims
+    }
+
+    /// Fails when the model answers with vectors of another length than configured,
+    /// which would not compare with the stored ones.
+    pub async fn embed(&self, text: &str) -> Result<Vec<f64>, AiError> {
+        let vector = self.model.embed(text).await?;
+        self.check_dims(&vector)?;
+        Ok(vector)
+    }
+
+    /// Embeds `text` for a similarity search, tagged with the model it came from.
+    pub async fn embed_query(&self, text: &str) -> Result<QueryEmbedding, AiError> {
+        Ok(QueryEmbedding {
+            model: self.model_name.clone(),
+            vector: self.embed(text).await?,
+        })
+    }
+
+    /// Embeds several texts, in as few requests as the model allows. Vectors come
+    /// back in the order of `texts`.
+    pub async fn embed_many(&self, texts: Vec<String>) -> Result<Vec<Vec<f64>>, AiError> {
+        let expected = texts.len();
+        let vectors = self.model.embed_many(texts).await?;
+        if vectors.len() != expected {
+            return Err(AiError::unknown(format!(
+                "embedding model {} returned {} vectors for {expected} texts",
+                self.model_name,
+                vectors.len()
+            )));
+        }
+        for vector in &vectors {
+            self.check_dims(vector)?;
+        }
+        Ok(vectors)
+    }
+
+    fn check_dims(&self, vector: &[f64]) -> Result<(), AiError> {
+        if vector.len() == self.dims {
+            return Ok(());
+        }
+        Err(AiError::Fatal {
+            detail: format!(
+                "embedding model {} returned {} dimensions, expected {}; set AI_EMBEDDING_DIMS to match",
+                self.model_name,
+                vector.len(),
+                self.dims
+            ),
+        })
+    }
+}
+
 #[tracing::instrument(skip_all, level = "debug", fields(text_len = text.len(), otel.kind = "client"))]
 pub async fn embed_text(config: &AiConfig, text: &str) -> Result<Vec<f64>, AiError> {
-    let client = create_gemini_client(&config.api_key);
-    let model = client.embedding_model_with_ndims(&config.embedding_model, EMBEDDING_DIMS);
-    embed_query(&model, text).await
+    Embedder::new(config)?.embed(text).await
 }
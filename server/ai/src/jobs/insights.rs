@@ -0,0 +1,139 @@
+//! Narration of the weekly and monthly insight digests. The worker computes the
+//! figures; the model only puts them into words, in a single completion charged
+//! through the `RateLimitProvider` like a chat turn.
+
+use std::sync::Arc;
+
+use rig::client::CompletionClient;
+use rig::completion::Prompt;
+
+use crate::config::AiConfig;
+use crate::models::error::AiError;
+use crate::models::insights::InsightDigest;
+use crate::provider::AiClient;
+use crate::rate_limit_provider::RateLimitProvider;
+use crate::with_ai_client;
+
+const NARRATIVE_SYSTEM_PROMPT: &str = r#"You are Myra, a personal finance assistant. You write the user's periodic financial digest.
+
+The input is a JSON object describing one week or month of the user's finances, amounts in their base currency ("currency"):
+- "spending": total spent this period and the period before, and the categories that changed the most.
+- "unusual_transactions": purchases well above what the user usually spends in that category.
+- "net_worth": how net worth changed over the period, when known.
+- "top_movers": held assets whose price moved the most, with the gain or loss on the units held.
+- "upcoming_charges": recurring charges and subscriptions due in the coming period.
+
+Rules:
+- Write 2 to 4 short paragraphs in plain text, addressed to the user as "you". Markdown bullet lists are allowed, headings are not.
+- Lead with what matters most this period.
+- Use only the figures given. Never invent numbers, merchants or dates.
+- Leave out sections that are empty or missing.
+- Be factual and friendly. Do not give investment advice."#;
+
+pub struct DigestNarrative {
+    pub text: String,
+    /// The completion model that wrote the text.
+    pub model: String,
+}
+
+/// Puts `digest` into words for the user.
+#[tracing::instrument(skip_all, level = "debug", fields(model = %config.model))]
+pub async fn narrate_digest<R>(
+    config: &AiConfig,
+    rate_limit: Arc<R>,
+    digest: &InsightDigest,
+) -> Result<DigestNarrative, AiError>
+where
+    R: RateLimitProvider,
+{
+    let client = AiClient::new(&config.completion)?;
+    let prompt = serde_json::to_string(digest)
+        .map_err(|e| AiError::unknown(format!("Failed to serialize insight digest: {e}")))?;
+
+    rate_limit.pre_check(&prompt, &[], &[]).await?;
+    let response = with_ai_client!(client, |client| {
+        client
+            .agent(&config.model)
+            .preamble(NARRATIVE_SYSTEM_PROMPT)
+            .max_tokens(1024)
+            .build()
+            .prompt(prompt.as_str())
+            .extended_details()
+            .await
+            .map(|r| (r.output, r.usage))
+    });
+    let (output, usage) = match response {
+        Ok(r) => r,
+        Err(e) => {
+            rate_limit.release().await;
+            return Err(AiError::from(e));
+        }
+    };
+    rate_limit
+        .record_usage(usage.input_tokens, usage.output_tokens)
+        .await;
+
+    let text = clean_narrative(&output)
+        .ok_or_else(|| AiError::unknown("Generated digest narrative was empty"))?;
+    Ok(DigestNarrative {
+        text,
+        model: config.model.clone(),
+    })
+}
+
+/// Trims the reply and unwraps it from a code fence some models add anyway.
+pub fn clean_narrative(output: &str) -> Option<String> {
+    let trimmed = output.trim();
+    let unfenced = trimmed
+        .strip_prefix("```markdown")
+        .or_else(|| trimmed.strip_prefix("```"))
+        .and_then(|s| s.strip_suffix("```"))
+        .unwrap_or(trimmed)
+        .trim();
+
+    if unfenced.is_empty() {
+        None
+    } else {
+        Some(unfenced.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clean_trims_whitespace() {
+        assert_eq!(
+            clean_narrative("\n  You spent less this week.  \n").unwrap(),
+            "You spent less this week."
+        );
+    }
+
+    #[test]
+    fn test_clean_strips_code_fence() {
+        assert_eq!(
+            clean_narrative("```markdown\nYou spent less this week.\n```").unwrap(),
+            "You spent less this week."
+        );
+        assert_eq!(
+            clean_narrative("```\nYou spent less this week.\n```").unwrap(),
+            "You spent less this week."
+        );
+    }
+
+    #[test]
+    fn test_clean_keeps_inner_lines() {
+        assert_eq!(
+            clean_narrative("First paragraph.\n\n- a point\n- another").unwrap(),
+            "First paragraph.\n\n- a point\n- another"
+        );
+    }
+
+    #[test]
+    fn test_clean_rejects_empty() {
+        assert!(clean_narrative("").is_none());
+        assert!(clean_narrative("   ").is_none());
+        assert!(clean_narrative("```\n```").is_none());
+    }
+}
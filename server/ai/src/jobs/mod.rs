@@ -1,6 +1,2 @@
+pub mod insights;
 pub mod title;
-
-// Planned jobs:
-// - Periodic insights: Weekly/monthly spending analysis triggered by cron
-// - Auto-categorization: Categorize new transactions when they arrive via integrations
-// - Embedding maintenance: Re-embed descriptions when embedding model is updated
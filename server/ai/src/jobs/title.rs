@@ -4,8 +4,9 @@ use crate::config::AiConfig;
 use crate::conversation::Conversation;
 use crate::conversation_provider::ConversationProvider;
 use crate::models::chat::{ChatTurn, Persistence};
-use crate::provider::create_gemini_client;
+use crate::provider::AiClient;
 use crate::rate_limit_provider::RateLimitProvider;
+use crate::with_ai_client;
 
 const TITLE_SYSTEM_PROMPT: &str = r#"You are a title generator for AI conversations. Given a set of messages from a chat, create a short, descriptive title (1-5 words) that captures the main topic of the conversation.
 
@@ -32,23 +33,19 @@ where
     C: ConversationProvider,
     R: RateLimitProvider,
 {
-    let client = create_gemini_client(&config.api_key);
-    let agent = client
-        .agent(&config.model)
-        .preamble(TITLE_SYSTEM_PROMPT)
-        .max_tokens(32)
-        .build();
-
-    let result = conv
-        .run(
-            agent,
-            ChatTurn::Message {
-                message: "Generate a 1-5 word title for this conversation.".to_string(),
-                file_ids: vec![],
-            },
-            Persistence::Ephemeral,
-        )
-        .await?;
+    let client = AiClient::new(&config.completion)?;
+    let turn = ChatTurn::Message {
+        message: "Generate a 1-5 word title for this conversation.".to_string(),
+        file_ids: vec![],
+    };
+    let result = with_ai_client!(client, |client| {
+        let agent = client
+            .agent(&config.model)
+            .preamble(TITLE_SYSTEM_PROMPT)
+            .max_tokens(32)
+            .build();
+        conv.run(agent, turn, Persistence::Ephemeral).await
+    })?;
 
     sanitize_title(&result.output).ok_or_else(|| anyhow::anyhow!("Generated title was empty"))
 }
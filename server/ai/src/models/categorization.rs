@@ -0,0 +1,42 @@
+use rust_decimal::Decimal;
+use serde::Serialize;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// A transaction waiting for a category.
+#[derive(Clone, Debug)]
+pub struct UncategorizedTransaction {
+    pub transaction_id: Uuid,
+    pub description: String,
+    pub date_transacted: OffsetDateTime,
+    pub amount: Decimal,
+    pub asset_name: String,
+    pub account_name: String,
+}
+
+/// One of the user's own categorised transactions that resembles the one being
+/// categorised.
+#[derive(Clone, Debug, Serialize)]
+pub struct CategorizedExample {
+    pub description: String,
+    pub category_id: i32,
+    pub category: String,
+    /// Cosine similarity of the two descriptions, from -1 to 1.
+    pub similarity: f32,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct CategorySuggestion {
+    pub transaction_id: Uuid,
+    pub category_id: i32,
+    /// How sure the model is, from 0 to 1.
+    pub confidence: f32,
+    pub reason: Option<String>,
+    /// The completion model the suggestion came from, or the embedding model when it was
+    /// taken from similar transactions without a completion.
+    pub model: String,
+}
+
+pub struct CategorizerOutput {
+    pub suggestions: Vec<CategorySuggestion>,
+}
@@ -0,0 +1,103 @@
+use rust_decimal::Decimal;
+use serde::Serialize;
+use uuid::Uuid;
+
+use super::wealth::CurrencyRef;
+
+/// A look back over one week or month of the user's finances, amounts in their base
+/// currency. Spending is positive.
+#[derive(Serialize)]
+pub struct InsightDigest {
+    pub period: String,
+    pub period_start: String,
+    /// Last day of the period.
+    pub period_end: String,
+    pub currency: CurrencyRef,
+    pub spending: SpendingSummary,
+    pub unusual_transactions: Vec<UnusualTransaction>,
+    pub net_worth: Option<NetWorthChange>,
+    pub top_movers: Vec<PortfolioMover>,
+    pub upcoming_charges: Vec<UpcomingCharge>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct SpendingSummary {
+    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    pub total: Decimal,
+    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    pub previous_total: Decimal,
+    #[serde(with = "rust_decimal::serde::arbitrary_precision_option")]
+    pub change_pct: Option<Decimal>,
+    /// Largest changes first.
+    pub categories: Vec<CategorySpending>,
+}
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct CategorySpending {
+    pub category: String,
+    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    pub amount: Decimal,
+    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    pub previous_amount: Decimal,
+    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    pub change: Decimal,
+    /// `None` when nothing was spent in the category the period before.
+    #[serde(with = "rust_decimal::serde::arbitrary_precision_option")]
+    pub change_pct: Option<Decimal>,
+}
+
+#[derive(Serialize)]
+pub struct UnusualTransaction {
+    pub transaction_id: Uuid,
+    pub date: String,
+    pub description: Option<String>,
+    pub category: String,
+    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    pub amount: Decimal,
+    /// What the user usually spends per transaction in the category.
+    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    pub typical_amount: Decimal,
+}
+
+#[derive(Serialize)]
+pub struct NetWorthChange {
+    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    pub start_value: Decimal,
+    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    pub end_value: Decimal,
+    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    pub change: Decimal,
+    #[serde(with = "rust_decimal::serde::arbitrary_precision_option")]
+    pub change_pct: Option<Decimal>,
+}
+
+/// A held asset whose price moved over the period.
+#[derive(Debug, PartialEq, Serialize)]
+pub struct PortfolioMover {
+    pub asset_id: i32,
+    pub asset_name: String,
+    pub ticker: Option<String>,
+    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    pub start_price: Decimal,
+    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    pub end_price: Decimal,
+    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    pub change_pct: Decimal,
+    /// Gain or loss on the units held now.
+    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    pub value_change: Decimal,
+}
+
+/// A charge expected in the coming period, from a recurring transaction the user set
+/// up or from a detected subscription.
+#[derive(Serialize)]
+pub struct UpcomingCharge {
+    pub description: String,
+    pub account: String,
+    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    pub amount: Decimal,
+    pub unit: String,
+    pub date: String,
+    /// `recurring` or `subscription`.
+    pub source: String,
+}
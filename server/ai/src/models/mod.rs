@@ -1,11 +1,14 @@
 pub mod account;
 pub mod action;
 pub mod aggregate;
+pub mod categorization;
 pub mod chat;
 pub mod error;
+pub mod insights;
 pub mod receipt;
 pub mod reference;
 pub mod search;
+pub mod subscriptions;
 pub mod tool_output;
 pub mod transactions;
 pub mod wealth;
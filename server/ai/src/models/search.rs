@@ -11,3 +11,11 @@ pub struct TransactionSearchResult {
     pub asset_name: String,
     pub account_name: String,
 }
+
+/// A search text embedded by the active model. Only stored vectors of the same
+/// model compare with it.
+#[derive(Clone, Debug)]
+pub struct QueryEmbedding {
+    pub model: String,
+    pub vector: Vec<f64>,
+}
@@ -0,0 +1,21 @@
+use rust_decimal::Decimal;
+use serde::Serialize;
+use uuid::Uuid;
+
+#[derive(Serialize)]
+pub struct SubscriptionRow {
+    pub description: String,
+    pub account_id: Uuid,
+    pub account: String,
+    pub period: String,
+    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    pub amount: Decimal,
+    #[serde(with = "rust_decimal::serde::arbitrary_precision")]
+    pub last_amount: Decimal,
+    pub unit: String,
+    pub first_charge_date: String,
+    pub last_charge_date: String,
+    pub next_charge_date: String,
+    pub charge_count: usize,
+    pub confidence: f64,
+}
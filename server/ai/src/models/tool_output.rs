@@ -5,6 +5,9 @@ use serde_json::Value;
 #[derive(Deserialize)]
 pub struct ListAccountsArgs {}
 
+#[derive(Deserialize)]
+pub struct GetSubscriptionsArgs {}
+
 #[derive(Deserialize)]
 pub struct QueryTransactionsArgs {
     pub query: Option<String>,
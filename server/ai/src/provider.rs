@@ -1,5 +1,67 @@
-use rig::providers::gemini;
+use rig::client::Nothing;
+use rig::providers::{gemini, ollama, openai};
 
-pub fn create_gemini_client(api_key: &str) -> gemini::Client {
-    gemini::Client::new(api_key).expect("Failed to create Gemini client")
+use crate::config::{AiEndpoint, AiProviderKind};
+use crate::models::error::AiError;
+
+/// Client of one of the supported providers. Agents and embedding models are built
+/// from the concrete client inside [`with_ai_client!`], so code using them is written
+/// once and compiled for each provider.
+pub enum AiClient {
+    Gemini(gemini::Client),
+    OpenAiCompatible(openai::CompletionsClient),
+    Ollama(ollama::Client),
+}
+
+impl AiClient {
+    pub fn new(endpoint: &AiEndpoint) -> Result<Self, AiError> {
+        let api_key = endpoint.api_key.as_deref().unwrap_or_default();
+        let base_url = endpoint.base_url.as_deref();
+        let client = match endpoint.provider {
+            AiProviderKind::Gemini => {
+                Self::Gemini(gemini::Client::new(api_key).map_err(|e| client_error("Gemini", e))?)
+            }
+            AiProviderKind::OpenAiCompatible => {
+                let base_url = base_url.ok_or_else(|| AiError::Fatal {
+                    detail: "OpenAI-compatible provider needs a base URL".to_string(),
+                })?;
+                // Local servers accept any key but the client insists on one.
+                let api_key = if api_key.is_empty() { "none" } else { api_key };
+                let client = openai::Client::builder()
+                    .api_key(api_key)
+                    .base_url(base_url)
+                    .build()
+                    .map_err(|e| client_error("OpenAI-compatible", e))?;
+                Self::OpenAiCompatible(client.completions_api())
+            }
+            AiProviderKind::Ollama => {
+                let mut builder = ollama::Client::builder().api_key(Nothing);
+                if let Some(base_url) = base_url {
+                    builder = builder.base_url(base_url);
+                }
+                Self::Ollama(builder.build().map_err(|e| client_error("Ollama", e))?)
+            }
+        };
+        Ok(client)
+    }
+}
+
+fn client_error(provider: &str, error: impl std::fmt::Display) -> AiError {
+    AiError::Fatal {
+        detail: format!("Failed to create {provider} client: {error}"),
+    }
+}
+
+/// Evaluates `$body` with `$client` bound to the concrete client inside an
+/// [`AiClient`]. The body is compiled once per provider and every arm must produce
+/// the same type.
+#[macro_export]
+macro_rules! with_ai_client {
+    ($ai_client:expr, |$client:ident| $body:expr) => {
+        match $ai_client {
+            $crate::provider::AiClient::Gemini($client) => $body,
+            $crate::provider::AiClient::OpenAiCompatible($client) => $body,
+            $crate::provider::AiClient::Ollama($client) => $body,
+        }
+    };
 }
@@ -35,8 +35,8 @@ impl<D: AiDataProvider> Tool for AggregateTransactionsTool<D> {
 
     async fn definition(&self, _prompt: String) -> ToolDefinition {
         let description = match self.mode {
-            ToolMode::Normal => "Get spending or income totals grouped by a dimension (category, description, account, or month). Use for summary questions like 'how much did I spend by category' or 'monthly spending'. Totals are in ONE currency — the user's default currency unless you pass currency_asset_id — and cover cash movements in that currency only (foreign-currency and asset-unit entries are excluded). The result states which currency it is in. Negative = spending, positive = income.",
-            ToolMode::CodeMode => "Spending/income totals grouped by a dimension, in ONE currency (the user's default unless currency_asset_id is given). args {group_by (category|description|account|month), date_from?, date_to?, description_filter?, account_id?, currency_asset_id?}. Each row: {group_name, total_amount (number, negative = spending), transaction_count}.",
+            ToolMode::Normal => "Get spending or income totals grouped by a dimension (category, description, account, month, or tag). Use for summary questions like 'how much did I spend by category', 'monthly spending' or 'what did each trip cost'. Grouping by tag only covers tagged transactions, and a transaction with several tags counts towards each of them. Totals are in ONE currency — the user's default currency unless you pass currency_asset_id — and cover cash movements in that currency only (foreign-currency and asset-unit entries are excluded). The result states which currency it is in. Negative = spending, positive = income.",
+            ToolMode::CodeMode => "Spending/income totals grouped by a dimension, in ONE currency (the user's default unless currency_asset_id is given). args {group_by (category|description|account|month|tag), date_from?, date_to?, description_filter?, account_id?, currency_asset_id?}. Each row: {group_name, total_amount (number, negative = spending), transaction_count}. group_by tag skips untagged transactions and counts multi-tagged ones under every tag.",
         };
         ToolDefinition {
             name: Self::NAME.to_string(),
@@ -46,7 +46,7 @@ impl<D: AiDataProvider> Tool for AggregateTransactionsTool<D> {
                 "properties": {
                     "group_by": {
                         "type": "string",
-                        "enum": ["category", "description", "account", "month"],
+                        "enum": ["category", "description", "account", "month", "tag"],
                         "description": "Dimension to group results by"
                     },
                     "date_from": {
@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use super::{ToolError, ToolMode};
+use crate::data_provider::AiDataProvider;
+use crate::models::tool_output::GetSubscriptionsArgs;
+use rig::{completion::request::ToolDefinition, tool::Tool};
+use serde_json::json;
+
+pub struct GetSubscriptionsTool<D: AiDataProvider> {
+    data: Arc<D>,
+    mode: ToolMode,
+}
+
+impl<D: AiDataProvider> GetSubscriptionsTool<D> {
+    pub fn new(data: Arc<D>) -> Self {
+        Self::with_mode(data, ToolMode::Normal)
+    }
+
+    pub fn with_mode(data: Arc<D>, mode: ToolMode) -> Self {
+        Self { data, mode }
+    }
+}
+
+impl<D: AiDataProvider> Tool for GetSubscriptionsTool<D> {
+    const NAME: &'static str = "get_subscriptions";
+
+    type Error = ToolError;
+    type Args = GetSubscriptionsArgs;
+    type Output = String;
+
+    async fn definition(&self, _prompt: String) -> ToolDefinition {
+        let description = match self.mode {
+            ToolMode::Normal => "Recurring payments (subscriptions) detected from the user's regular transaction history: charges to the same merchant from the same account that repeat weekly, monthly, quarterly, or yearly and are still being charged. Use for 'what am I subscribed to', 'what are my recurring bills', or 'when is my next Netflix charge'. These are inferred, not declared by the user — mention that, and treat low confidence rows as likely but unconfirmed. amount is the typical charge and last_amount the most recent one (a difference usually means a price change); both are positive and come with their unit, never assume a currency. Sorted by next_charge_date. Each row: {description, account_id, account, period, amount, last_amount, unit, first_charge_date, last_charge_date, next_charge_date, charge_count, confidence (0-1)}.",
+            ToolMode::CodeMode => "Detected recurring payments as a flat array, one row per subscription. args {}. Each row: {description, account_id, account, period ('weekly'|'monthly'|'quarterly'|'yearly'), amount (number, typical charge, positive), last_amount (number), unit, first_charge_date, last_charge_date, next_charge_date (YYYY-MM-DD), charge_count, confidence (0-1)}.",
+        };
+        ToolDefinition {
+            name: Self::NAME.to_string(),
+            description: description.to_string(),
+            parameters: json!({
+                "type": "object",
+                "properties": {},
+                "required": []
+            }),
+        }
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(tool = Self::NAME))]
+    async fn call(&self, _args: Self::Args) -> std::result::Result<Self::Output, Self::Error> {
+        let subscriptions = self
+            .data
+            .get_subscriptions()
+            .await
+            .map_err(|e| ToolError(e.to_string()))?;
+
+        serde_json::to_string(&subscriptions).map_err(Into::into)
+    }
+}
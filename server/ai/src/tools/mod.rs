@@ -6,6 +6,7 @@ pub mod get_asset_price;
 pub mod get_holdings;
 pub mod get_net_worth_history;
 pub mod get_portfolio_overview;
+pub mod get_subscriptions;
 pub mod get_transaction_detail;
 pub mod group_transactions;
 pub mod list_accounts;
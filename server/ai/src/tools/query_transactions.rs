@@ -2,10 +2,10 @@ use std::sync::Arc;
 
 use super::{ToolError, ToolMode};
 use crate::data_provider::AiDataProvider;
-use crate::embedding::embed_query;
+use crate::embedding::Embedder;
 use crate::models::tool_output::QueryTransactionsArgs;
 use crate::models::transactions::QueryTransactionsParams;
-use rig::{completion::request::ToolDefinition, embeddings::EmbeddingModel, tool::Tool};
+use rig::{completion::request::ToolDefinition, tool::Tool};
 use serde_json::json;
 use uuid::Uuid;
 
@@ -15,27 +15,27 @@ const CODE_MODE_SEMANTIC_LIMIT: i64 = 1000;
 const CODE_MODE_BROWSE_LIMIT: i64 = 50_000;
 const CODE_MODE_MAX: i64 = 50_000;
 
-pub struct QueryTransactionsTool<M: EmbeddingModel, D: AiDataProvider> {
+pub struct QueryTransactionsTool<D: AiDataProvider> {
     data: Arc<D>,
-    embedding_model: M,
+    embedder: Embedder,
     mode: ToolMode,
 }
 
-impl<M: EmbeddingModel, D: AiDataProvider> QueryTransactionsTool<M, D> {
-    pub fn new(data: Arc<D>, embedding_model: M) -> Self {
-        Self::with_mode(data, embedding_model, ToolMode::Normal)
+impl<D: AiDataProvider> QueryTransactionsTool<D> {
+    pub fn new(data: Arc<D>, embedder: Embedder) -> Self {
+        Self::with_mode(data, embedder, ToolMode::Normal)
     }
 
-    pub fn with_mode(data: Arc<D>, embedding_model: M, mode: ToolMode) -> Self {
+    pub fn with_mode(data: Arc<D>, embedder: Embedder, mode: ToolMode) -> Self {
         Self {
             data,
-            embedding_model,
+            embedder,
             mode,
         }
     }
 }
 
-impl<M: EmbeddingModel + Send + Sync, D: AiDataProvider> Tool for QueryTransactionsTool<M, D> {
+impl<D: AiDataProvider> Tool for QueryTransactionsTool<D> {
     const NAME: &'static str = "query_transactions";
 
     type Error = ToolError;
@@ -97,9 +97,10 @@ impl<M: EmbeddingModel + Send + Sync, D: AiDataProvider> Tool for QueryTransacti
         };
 
         let query = args.query.filter(|q| !q.trim().is_empty());
-        let query_vector = match &query {
+        let query_embedding = match &query {
             Some(q) => Some(
-                embed_query(&self.embedding_model, q)
+                self.embedder
+                    .embed_query(q)
                     .await
                     .map_err(|e| ToolError(e.to_string()))?,
             ),
@@ -130,7 +131,7 @@ impl<M: EmbeddingModel + Send + Sync, D: AiDataProvider> Tool for QueryTransacti
 
         let mut result = self
             .data
-            .query_transactions(params, query_vector)
+            .query_transactions(params, query_embedding)
             .await
             .map_err(|e| ToolError(e.to_string()))?;
 
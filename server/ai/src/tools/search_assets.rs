@@ -2,32 +2,32 @@ use std::sync::Arc;
 
 use super::{ToolError, ToolMode};
 use crate::data_provider::AiDataProvider;
-use crate::embedding::embed_query;
+use crate::embedding::Embedder;
 use crate::models::tool_output::SearchAssetsArgs;
-use rig::{completion::request::ToolDefinition, embeddings::EmbeddingModel, tool::Tool};
+use rig::{completion::request::ToolDefinition, tool::Tool};
 use serde_json::json;
 
-pub struct SearchAssetsTool<M: EmbeddingModel, D: AiDataProvider> {
+pub struct SearchAssetsTool<D: AiDataProvider> {
     data: Arc<D>,
-    embedding_model: M,
+    embedder: Embedder,
     mode: ToolMode,
 }
 
-impl<M: EmbeddingModel, D: AiDataProvider> SearchAssetsTool<M, D> {
-    pub fn new(data: Arc<D>, embedding_model: M) -> Self {
-        Self::with_mode(data, embedding_model, ToolMode::Normal)
+impl<D: AiDataProvider> SearchAssetsTool<D> {
+    pub fn new(data: Arc<D>, embedder: Embedder) -> Self {
+        Self::with_mode(data, embedder, ToolMode::Normal)
     }
 
-    pub fn with_mode(data: Arc<D>, embedding_model: M, mode: ToolMode) -> Self {
+    pub fn with_mode(data: Arc<D>, embedder: Embedder, mode: ToolMode) -> Self {
         Self {
             data,
-            embedding_model,
+            embedder,
             mode,
         }
     }
 }
 
-impl<M: EmbeddingModel + Send + Sync, D: AiDataProvider> Tool for SearchAssetsTool<M, D> {
+impl<D: AiDataProvider> Tool for SearchAssetsTool<D> {
     const NAME: &'static str = "search_assets";
 
     type Error = ToolError;
@@ -53,12 +53,13 @@ impl<M: EmbeddingModel + Send + Sync, D: AiDataProvider> Tool for SearchAssetsTo
 
     #[tracing::instrument(level = "debug", skip_all, fields(tool = Self::NAME))]
     async fn call(&self, args: Self::Args) -> std::result::Result<Self::Output, Self::Error> {
-        let query_vec = match self.mode {
+        let query_embedding = match self.mode {
             ToolMode::CodeMode => None,
             ToolMode::Normal => {
                 if let Some(ref q) = args.query {
                     Some(
-                        embed_query(&self.embedding_model, q)
+                        self.embedder
+                            .embed_query(q)
                             .await
                             .map_err(|e| ToolError(e.to_string()))?,
                     )
@@ -70,7 +71,7 @@ impl<M: EmbeddingModel + Send + Sync, D: AiDataProvider> Tool for SearchAssetsTo
 
         let assets = self
             .data
-            .search_assets(args.query.as_deref(), query_vec)
+            .search_assets(args.query.as_deref(), query_embedding)
             .await
             .map_err(|e| ToolError(e.to_string()))?;
 
@@ -10,16 +10,17 @@ use crate::config::AiConfig;
 use crate::conversation::Conversation;
 use crate::conversation_provider::ConversationProvider;
 use crate::data_provider::AiDataProvider;
-use crate::embedding::EMBEDDING_DIMS;
+use crate::embedding::Embedder;
 use crate::models::chat::{ChatTurn, Persistence};
 pub use crate::models::receipt::ReceiptProcessorOutput;
-use crate::provider::create_gemini_client;
+use crate::provider::AiClient;
 use crate::rate_limit_provider::RateLimitProvider;
 use crate::tools::list_accounts::ListAccountsTool;
 use crate::tools::search_assets::SearchAssetsTool;
 use crate::tools::search_categories::SearchCategoriesTool;
+use crate::with_ai_client;
 use rig::agent::Agent;
-use rig::client::{CompletionClient, EmbeddingsClient};
+use rig::client::CompletionClient;
 use uuid::Uuid;
 
 const SYSTEM_PROMPT: &str = r#"You are a receipt processing assistant. Your job is to extract transaction details from receipt images.
@@ -113,11 +114,17 @@ where
     C: ConversationProvider,
     R: RateLimitProvider,
 {
-    let agent = build_agent(&config, data);
+    let client = AiClient::new(&config.completion)?;
+    let embedder = Embedder::new(&config)?;
     let conv = Conversation::new(conversation, rate_limit);
-    let output = conv
-        .run(agent, ChatTurn::Continuation, Persistence::Persist)
-        .await?;
+    let output = with_ai_client!(client, |client| {
+        conv.run(
+            build_agent(&client, &config, data, embedder),
+            ChatTurn::Continuation,
+            Persistence::Persist,
+        )
+        .await
+    })?;
 
     parse_proposal(&output.output)
 }
@@ -136,41 +143,39 @@ where
     C: ConversationProvider,
     R: RateLimitProvider,
 {
-    let agent = build_agent(&config, data);
+    let client = AiClient::new(&config.completion)?;
+    let embedder = Embedder::new(&config)?;
     let conv = Conversation::new(conversation, rate_limit);
-    let output = conv
-        .run(
-            agent,
+    let output = with_ai_client!(client, |client| {
+        conv.run(
+            build_agent(&client, &config, data, embedder),
             ChatTurn::Message { message, file_ids },
             Persistence::Persist,
         )
-        .await?;
+        .await
+    })?;
 
     parse_proposal(&output.output)
 }
 
-fn build_agent<D>(
+fn build_agent<C, D>(
+    client: &C,
     config: &AiConfig,
     data: Arc<D>,
-) -> Agent<rig::providers::gemini::completion::CompletionModel>
+    embedder: Embedder,
+) -> Agent<C::CompletionModel>
 where
+    C: CompletionClient,
     D: AiDataProvider,
 {
-    let client = create_gemini_client(&config.api_key);
-    let embedding_model =
-        client.embedding_model_with_ndims(&config.embedding_model, EMBEDDING_DIMS);
-
     client
         .agent(&config.model)
         .preamble(SYSTEM_PROMPT)
         .max_tokens(8192)
         .default_max_turns(5)
         .tool(ListAccountsTool::new(data.clone()))
-        .tool(SearchCategoriesTool::new(
-            data.clone(),
-            embedding_model.clone(),
-        ))
-        .tool(SearchAssetsTool::new(data.clone(), embedding_model.clone()))
+        .tool(SearchCategoriesTool::new(data.clone(), embedder.clone()))
+        .tool(SearchAssetsTool::new(data, embedder))
         .build()
 }
 
@@ -39,7 +39,9 @@ use crate::{
 
 /// Get Account Net Worth History
 ///
-/// Returns net worth history scoped to a specific account.
+/// Returns net worth history scoped to a specific account. When connectors are bound to
+/// the account, the balance they reported is returned alongside for comparison; it is left
+/// empty when it cannot be loaded.
 #[utoipa::path(
     get,
     path = "/api/users/{user_id}/accounts/{account_id}/portfolio/history",
@@ -74,12 +76,25 @@ pub async fn get_account_networth_history(
             .ok_or_else(|| ApiError::Conflict("User has no base currency set".to_string()))?,
     });
 
-    let history = portfolio_service
-        .get_full_portfolio_history(user_id, default_asset, range, Some(account_id))
-        .await?;
+    let (history, provider_history) = tokio::join!(
+        portfolio_service.get_full_portfolio_history(
+            user_id,
+            default_asset.clone(),
+            range.clone(),
+            Some(account_id)
+        ),
+        portfolio_service.get_provider_balance_history(user_id, default_asset, range, account_id),
+    );
+    let history = history?;
+    // The reported balance is only an overlay; the ledger history is shown without it.
+    let provider_history = provider_history.unwrap_or_else(|e| {
+        tracing::warn!(error = ?e, "failed to load provider balance history");
+        vec![]
+    });
 
     let response = GetNetWorthHistoryResponseViewModel {
         sums: history.into_iter().map_into().collect(),
+        provider_sums: provider_history.into_iter().map_into().collect(),
         range: query_params.range.to_string(),
     };
 
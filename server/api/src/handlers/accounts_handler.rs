@@ -76,6 +76,7 @@ pub async fn get_account(
     let ret = GetAccountResponseViewModel {
         liquidity_type: account.liquidity_type.clone().into(),
         ownership_share: OwnershipShare::from_trusted(account.ownership_share),
+        cost_basis_method: account.cost_basis_method.into(),
         identifiers: account
             .identifiers
             .iter()
@@ -245,6 +246,7 @@ pub async fn add_account(
         ownership_share: body.ownership_share,
         liquidity_type: body.liquidity_type,
         identifiers: body.identifiers,
+        cost_basis_method: body.cost_basis_method,
         account: IdentifiableAccount {
             account_id: RequiredAccountId(new_id),
             account: body.account,
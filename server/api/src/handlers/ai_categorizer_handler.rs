@@ -0,0 +1,85 @@
+use std::collections::HashSet;
+
+use axum::Json;
+use business::dtos::category_suggestion_dto::CategorySuggestionDto;
+use business::service_collection::category_service::CategoryService;
+use itertools::Itertools;
+
+use crate::{
+    auth::AuthenticatedUserId,
+    errors::ApiError,
+    extractors::ValidatedJson,
+    states::{AiCategorizerServiceState, CategoryServiceState},
+    view_models::{
+        ai::category_suggestions::{
+            CategorySuggestionsResponseViewModel, SuggestCategoriesRequestViewModel,
+        },
+        transactions::validation::Validatable,
+    },
+};
+
+#[utoipa::path(
+    get,
+    path = "/api/users/{user_id}/ai/category-suggestions",
+    tag = "AI",
+    responses(
+        (status = 200, description = "Suggested categories for transactions still in the import category, most confident first.", body = CategorySuggestionsResponseViewModel),
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique identifier of the user."),
+    ),
+    security(("auth_token" = []))
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id))]
+pub async fn get_category_suggestions(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    AiCategorizerServiceState(service): AiCategorizerServiceState,
+    CategoryServiceState(category_service): CategoryServiceState,
+) -> Result<Json<CategorySuggestionsResponseViewModel>, ApiError> {
+    let dtos = service
+        .get_suggestions(user_id, None)
+        .await
+        .map_err(ApiError::from_anyhow)?;
+    Ok(Json(to_response(dtos, &category_service).await?))
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/users/{user_id}/ai/category-suggestions",
+    tag = "AI",
+    request_body(content = SuggestCategoriesRequestViewModel),
+    responses(
+        (status = 200, description = "Suggestions made by this request. Transactions the model could not place are left out.", body = CategorySuggestionsResponseViewModel),
+        (status = 429, description = "AI usage limit reached."),
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique identifier of the user."),
+    ),
+    security(("auth_token" = []))
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id))]
+pub async fn suggest_categories(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    AiCategorizerServiceState(service): AiCategorizerServiceState,
+    CategoryServiceState(category_service): CategoryServiceState,
+    ValidatedJson(request): ValidatedJson<SuggestCategoriesRequestViewModel>,
+) -> Result<Json<CategorySuggestionsResponseViewModel>, ApiError> {
+    request.validate()?;
+    let dtos = service
+        .suggest(user_id, request.transaction_ids)
+        .await
+        .map_err(ApiError::from)?;
+    Ok(Json(to_response(dtos, &category_service).await?))
+}
+
+async fn to_response(
+    dtos: Vec<CategorySuggestionDto>,
+    category_service: &CategoryService,
+) -> Result<CategorySuggestionsResponseViewModel, ApiError> {
+    let category_ids: HashSet<i32> = dtos.iter().map(|s| s.category_id).collect();
+    let categories = category_service.get_categories(category_ids).await?;
+    Ok(CategorySuggestionsResponseViewModel {
+        suggestions: dtos.into_iter().map_into().collect(),
+        categories: categories.into_iter().map_into().collect(),
+    })
+}
@@ -0,0 +1,239 @@
+use axum::{extract::Path, http::StatusCode, Json};
+use business::dtos::assets::asset_id_dto::AssetIdDto;
+use itertools::Itertools;
+use serde::Deserialize;
+use time::{OffsetDateTime, UtcOffset};
+use uuid::Uuid;
+
+#[derive(Deserialize)]
+pub(crate) struct BudgetIdPath {
+    budget_id: Uuid,
+}
+
+use crate::{
+    auth::AuthenticatedUserId,
+    errors::ApiError,
+    extractors::{ValidatedJson, ValidatedQuery},
+    states::{BudgetServiceState, UsersServiceState},
+    view_models::{
+        assets::base_models::asset_id::RequiredAssetId,
+        budgets::{
+            base_models::{BudgetViewModel, IdentifiableBudgetViewModel},
+            get_budget_actuals::{GetBudgetActualsQueryParams, GetBudgetActualsResponseViewModel},
+            get_budgets::GetBudgetsResponseViewModel,
+        },
+        errors::{CreateResponses, DeleteResponses, GetResponses, UpdateResponses},
+    },
+};
+
+/// Get Budgets
+///
+/// Lists all budgets of the user.
+#[utoipa::path(
+    get,
+    path = "/api/users/{user_id}/budgets",
+    tag = "Budgets",
+    responses(
+        (status = 200, description = "Budgets retrieved successfully.", body = GetBudgetsResponseViewModel),
+        GetResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id))]
+pub async fn get_budgets(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    BudgetServiceState(budget_service): BudgetServiceState,
+) -> Result<Json<GetBudgetsResponseViewModel>, ApiError> {
+    let budgets = budget_service.get_budgets(user_id).await?;
+
+    Ok(Json(GetBudgetsResponseViewModel {
+        budgets: budgets.into_iter().map_into().collect(),
+    }))
+}
+
+/// Create Budget
+///
+/// Creates a spending limit for a category or for every category of a category type.
+/// The amount is denominated in the user's default asset.
+#[utoipa::path(
+    post,
+    path = "/api/users/{user_id}/budgets",
+    tag = "Budgets",
+    responses(
+        (status = 201, description = "Budget created successfully.", body = IdentifiableBudgetViewModel),
+        CreateResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+    ),
+    request_body(
+        content = BudgetViewModel,
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id))]
+pub async fn create_budget(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    BudgetServiceState(budget_service): BudgetServiceState,
+    ValidatedJson(body): ValidatedJson<BudgetViewModel>,
+) -> Result<(StatusCode, Json<IdentifiableBudgetViewModel>), ApiError> {
+    let budget = budget_service
+        .create_budget(user_id, body.to_business())
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(budget.into())))
+}
+
+/// Get Budget
+///
+/// Gets a specific budget by ID.
+#[utoipa::path(
+    get,
+    path = "/api/users/{user_id}/budgets/{budget_id}",
+    tag = "Budgets",
+    responses(
+        (status = 200, description = "Budget retrieved successfully.", body = IdentifiableBudgetViewModel),
+        GetResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+        ("budget_id" = Uuid, Path, description = "Id of the budget to retrieve."),
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id, budget_id = %budget_id))]
+pub async fn get_budget(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    Path(BudgetIdPath { budget_id }): Path<BudgetIdPath>,
+    BudgetServiceState(budget_service): BudgetServiceState,
+) -> Result<Json<IdentifiableBudgetViewModel>, ApiError> {
+    let budget = budget_service.get_budget(user_id, budget_id).await?;
+
+    Ok(Json(budget.into()))
+}
+
+/// Update Budget
+///
+/// Replaces the target, amount, period and rollover settings of a budget.
+#[utoipa::path(
+    put,
+    path = "/api/users/{user_id}/budgets/{budget_id}",
+    tag = "Budgets",
+    responses(
+        (status = 200, description = "Budget updated successfully.", body = IdentifiableBudgetViewModel),
+        UpdateResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+        ("budget_id" = Uuid, Path, description = "Id of the budget to update."),
+    ),
+    request_body(
+        content = BudgetViewModel,
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id, budget_id = %budget_id))]
+pub async fn update_budget(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    Path(BudgetIdPath { budget_id }): Path<BudgetIdPath>,
+    BudgetServiceState(budget_service): BudgetServiceState,
+    ValidatedJson(body): ValidatedJson<BudgetViewModel>,
+) -> Result<Json<IdentifiableBudgetViewModel>, ApiError> {
+    let budget = budget_service
+        .update_budget(user_id, budget_id, body.to_business())
+        .await?;
+
+    Ok(Json(budget.into()))
+}
+
+/// Delete Budget
+///
+/// Deletes a budget.
+#[utoipa::path(
+    delete,
+    path = "/api/users/{user_id}/budgets/{budget_id}",
+    tag = "Budgets",
+    responses(
+        (status = 200, description = "Budget deleted successfully."),
+        DeleteResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+        ("budget_id" = Uuid, Path, description = "Id of the budget to delete."),
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id, budget_id = %budget_id))]
+pub async fn delete_budget(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    Path(BudgetIdPath { budget_id }): Path<BudgetIdPath>,
+    BudgetServiceState(budget_service): BudgetServiceState,
+) -> Result<(), ApiError> {
+    budget_service.delete_budget(user_id, budget_id).await?;
+    Ok(())
+}
+
+/// Get Budget vs Actual
+///
+/// Compares each budget with the net amount spent in its current period. Spending is
+/// summed from entry quantities and converted to the user's default asset.
+/// Budgets with rollover include the unspent amount carried from earlier periods.
+#[utoipa::path(
+    get,
+    path = "/api/users/{user_id}/budgets/actual",
+    tag = "Budgets",
+    responses(
+        (status = 200, description = "Budget vs actual retrieved successfully.", body = GetBudgetActualsResponseViewModel),
+        GetResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+        GetBudgetActualsQueryParams
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id))]
+pub async fn get_budget_actuals(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    ValidatedQuery(query_params): ValidatedQuery<GetBudgetActualsQueryParams>,
+    BudgetServiceState(budget_service): BudgetServiceState,
+    UsersServiceState(user_service): UsersServiceState,
+) -> Result<Json<GetBudgetActualsResponseViewModel>, ApiError> {
+    let default_asset = match query_params.default_asset_id {
+        Some(id) => id,
+        None => user_service
+            .get_default_asset(user_id)
+            .await?
+            .ok_or_else(|| ApiError::Conflict("User has no base currency set".to_string()))?,
+    };
+
+    let date = query_params
+        .date
+        .unwrap_or_else(OffsetDateTime::now_utc)
+        .to_offset(UtcOffset::UTC)
+        .date();
+
+    let budgets = budget_service
+        .get_budget_vs_actual(user_id, AssetIdDto(default_asset), date)
+        .await?;
+
+    Ok(Json(GetBudgetActualsResponseViewModel {
+        reference_asset_id: RequiredAssetId(default_asset),
+        budgets: budgets.into_iter().map_into().collect(),
+    }))
+}
@@ -0,0 +1,223 @@
+use std::collections::HashSet;
+
+use axum::Json;
+use business::dtos::transaction_dto::TransactionDto;
+use itertools::Itertools;
+
+use crate::{
+    auth::AuthenticatedUserId,
+    converters::{
+        transaction_dtos_to_account_ids_hashset, transaction_dtos_to_asset_ids_hashset,
+        transaction_dtos_to_category_ids_hashset,
+    },
+    errors::ApiError,
+    extractors::{ValidatedJson, ValidatedQuery},
+    states::{
+        AccountsServiceState, AssetsServiceState, CategoryServiceState, ConnectorReviewServiceState,
+    },
+    view_models::connectors::review_inbox::{
+        EditAndAcceptRequestViewModel, GetReviewInboxResponseViewModel,
+        MergeReviewTransactionsRequestViewModel, ReviewInboxQuery,
+        ReviewTransactionsRequestViewModel,
+    },
+    view_models::errors::{DeleteResponses, GetResponses, UpdateResponses},
+    view_models::transactions::{
+        base_models::metadata_lookup::MetadataLookupTables, validation::Validatable,
+    },
+};
+
+/// Get Review Inbox
+///
+/// Lists the ghost transactions imported by bindings in ghost write mode that are still
+/// waiting for review, newest first. Each comes with a suggested category, group and
+/// asset taken from the closest reviewed transactions by description embedding.
+///
+/// Imports that may duplicate a transaction the user entered by hand are held here whatever
+/// the binding's write mode, and list those manual transactions as duplicate candidates to
+/// merge into.
+#[utoipa::path(
+    get,
+    path = "/api/users/{user_id}/connectors/review-inbox",
+    tag = "Connectors",
+    responses(
+        (status = 200, description = "Review inbox retrieved successfully.", body = GetReviewInboxResponseViewModel),
+        GetResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+        ReviewInboxQuery,
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id))]
+pub async fn get_review_inbox(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    ValidatedQuery(query): ValidatedQuery<ReviewInboxQuery>,
+    ConnectorReviewServiceState(review_service): ConnectorReviewServiceState,
+    AssetsServiceState(asset_service): AssetsServiceState,
+    AccountsServiceState(accounts_service): AccountsServiceState,
+    CategoryServiceState(category_service): CategoryServiceState,
+) -> Result<Json<GetReviewInboxResponseViewModel>, ApiError> {
+    let items = review_service
+        .get_review_inbox(user_id, query.binding_id)
+        .await?;
+
+    let transactions: Vec<&TransactionDto> = items.iter().map(|item| &item.transaction).collect();
+    let mut asset_ids = transaction_dtos_to_asset_ids_hashset(&transactions);
+    asset_ids.extend(items.iter().filter_map(|item| item.suggestions.asset_id));
+    let account_ids = transaction_dtos_to_account_ids_hashset(&transactions);
+    let mut category_ids: HashSet<i32> = transaction_dtos_to_category_ids_hashset(&transactions);
+    category_ids.extend(items.iter().filter_map(|item| item.suggestions.category_id));
+
+    let (assets, accounts, categories) = tokio::try_join!(
+        asset_service.get_assets(asset_ids),
+        accounts_service.get_accounts(account_ids),
+        category_service.get_categories(category_ids),
+    )?;
+
+    Ok(Json(GetReviewInboxResponseViewModel {
+        items: items.into_iter().map(Into::into).collect(),
+        lookup_tables: MetadataLookupTables {
+            assets: assets.into_iter().map_into().collect(),
+            accounts: accounts.into_iter().map_into().collect(),
+            categories: categories.into_iter().map_into().collect(),
+        },
+    }))
+}
+
+/// Accept (bulk)
+///
+/// Accepts ghost transactions as imported, making them regular transactions.
+#[utoipa::path(
+    post,
+    path = "/api/users/{user_id}/connectors/review-inbox/accept",
+    tag = "Connectors",
+    responses(
+        (status = 200, description = "Transactions accepted successfully."),
+        UpdateResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+    ),
+    request_body(
+        content = ReviewTransactionsRequestViewModel,
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id))]
+pub async fn accept_review_transactions(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    ConnectorReviewServiceState(review_service): ConnectorReviewServiceState,
+    ValidatedJson(body): ValidatedJson<ReviewTransactionsRequestViewModel>,
+) -> Result<(), ApiError> {
+    review_service.accept(user_id, body.transaction_ids).await?;
+    Ok(())
+}
+
+/// Reject (bulk)
+///
+/// Deletes ghost transactions. The provider items stay recorded against their binding,
+/// so later syncs do not import them again.
+#[utoipa::path(
+    post,
+    path = "/api/users/{user_id}/connectors/review-inbox/reject",
+    tag = "Connectors",
+    responses(
+        (status = 200, description = "Transactions rejected successfully."),
+        DeleteResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+    ),
+    request_body(
+        content = ReviewTransactionsRequestViewModel,
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id))]
+pub async fn reject_review_transactions(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    ConnectorReviewServiceState(review_service): ConnectorReviewServiceState,
+    ValidatedJson(body): ValidatedJson<ReviewTransactionsRequestViewModel>,
+) -> Result<(), ApiError> {
+    review_service.reject(user_id, body.transaction_ids).await?;
+    Ok(())
+}
+
+/// Edit and Accept (bulk)
+///
+/// Replaces ghost transactions with the user's corrected versions and accepts them.
+/// Edited transactions are no longer updated by their provider.
+#[utoipa::path(
+    post,
+    path = "/api/users/{user_id}/connectors/review-inbox/edit",
+    tag = "Connectors",
+    responses(
+        (status = 200, description = "Transactions edited and accepted successfully."),
+        UpdateResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+    ),
+    request_body(
+        content = EditAndAcceptRequestViewModel,
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id))]
+pub async fn edit_and_accept_review_transactions(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    ConnectorReviewServiceState(review_service): ConnectorReviewServiceState,
+    ValidatedJson(body): ValidatedJson<EditAndAcceptRequestViewModel>,
+) -> Result<(), ApiError> {
+    for edit in &body.edits {
+        edit.transaction.validate()?;
+    }
+    review_service
+        .edit_and_accept(user_id, body.edits.into_iter().map(Into::into).collect())
+        .await?;
+    Ok(())
+}
+
+/// Merge (bulk)
+///
+/// Folds each ghost transaction into the transaction the user already entered by hand
+/// for the same movement. The provider item is linked to the manual transaction, which
+/// is kept as entered, and the ghost is deleted.
+#[utoipa::path(
+    post,
+    path = "/api/users/{user_id}/connectors/review-inbox/merge",
+    tag = "Connectors",
+    responses(
+        (status = 200, description = "Transactions merged successfully."),
+        UpdateResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+    ),
+    request_body(
+        content = MergeReviewTransactionsRequestViewModel,
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id))]
+pub async fn merge_review_transactions(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    ConnectorReviewServiceState(review_service): ConnectorReviewServiceState,
+    ValidatedJson(body): ValidatedJson<MergeReviewTransactionsRequestViewModel>,
+) -> Result<(), ApiError> {
+    review_service
+        .merge(user_id, body.merges.into_iter().map(Into::into).collect())
+        .await?;
+    Ok(())
+}
@@ -16,6 +16,12 @@ pub(crate) struct BindingIdPath {
     binding_id: Uuid,
 }
 
+#[derive(Deserialize)]
+pub(crate) struct BindingIssuePath {
+    binding_id: Uuid,
+    issue_id: Uuid,
+}
+
 #[derive(Deserialize)]
 pub(crate) struct ConnectionSessionPath {
     connection_id: Uuid,
@@ -31,14 +37,16 @@ pub(crate) struct ConnectionAccountPath {
 use crate::{
     auth::AuthenticatedUserId,
     errors::ApiError,
-    extractors::ValidatedJson,
-    states::{ConnectorServiceState, ConnectorSyncServiceState},
+    extractors::{ValidatedJson, ValidatedQuery},
+    states::{ConnectorBalanceServiceState, ConnectorServiceState, ConnectorSyncServiceState},
     view_models::connectors::{
+        balances::{GetBindingBalancesQueryParams, GetBindingBalancesResponseViewModel},
         base_models::ConnectorBindingViewModel,
         create_binding::{CreateBindingRequestViewModel, CreateBindingResponseViewModel},
         create_connection::{CreateConnectionRequestViewModel, CreateConnectionResponseViewModel},
         get_bindings::GetBindingsResponseViewModel,
         get_connections::GetConnectionsResponseViewModel,
+        import_issues::GetImportIssuesResponseViewModel,
         ingest::{IngestTransactionsRequestViewModel, IngestTransactionsResponseViewModel},
         list_provider_account_transactions::ListProviderAccountTransactionsResponseViewModel,
         list_provider_accounts::ListProviderAccountsResponseViewModel,
@@ -47,7 +55,9 @@ use crate::{
             CreateOAuthSessionRequestViewModel, CreateOAuthSessionResponseViewModel,
             OAuthCallbackQuery, OAuthSessionStatus,
         },
-        sync_binding::{SyncBindingRequestViewModel, SyncBindingResponseViewModel},
+        sync_binding::{
+            SyncBindingRequestViewModel, SyncBindingResponseViewModel, SyncReportViewModel,
+        },
         sync_checkpoint::GetSyncCheckpointResponseViewModel,
         update_binding::UpdateBindingRequestViewModel,
     },
@@ -498,6 +508,145 @@ pub async fn get_sync_checkpoint(
     }))
 }
 
+/// Get Import Issues
+///
+/// Lists the provider items of the binding that were not imported: items the provider
+/// mapper skips (fees, FX, non-cash rows) and transactions whose currency or instrument
+/// matches no asset. Dismissed issues are left out.
+#[utoipa::path(
+    get,
+    path = "/api/users/{user_id}/connectors/bindings/{binding_id}/import-issues",
+    tag = "Connectors",
+    responses(
+        (status = 200, description = "Import issues retrieved successfully.", body = GetImportIssuesResponseViewModel),
+        GetResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+        ("binding_id" = Uuid, Path, description = "Id of the binding."),
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id, binding_id = %binding_id))]
+pub async fn get_import_issues(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    Path(BindingIdPath { binding_id }): Path<BindingIdPath>,
+    ConnectorSyncServiceState(sync_service): ConnectorSyncServiceState,
+) -> Result<Json<GetImportIssuesResponseViewModel>, ApiError> {
+    let issues = sync_service.get_import_issues(user_id, binding_id).await?;
+
+    Ok(Json(GetImportIssuesResponseViewModel {
+        issues: issues.into_iter().map(Into::into).collect(),
+    }))
+}
+
+/// Get Binding Balances
+///
+/// Lists the balances the provider reported for the binding's account, one snapshot per
+/// sync, oldest first. Compare with the account history to spot sync gaps and missed
+/// transactions.
+#[utoipa::path(
+    get,
+    path = "/api/users/{user_id}/connectors/bindings/{binding_id}/balances",
+    tag = "Connectors",
+    responses(
+        (status = 200, description = "Binding balances retrieved successfully.", body = GetBindingBalancesResponseViewModel),
+        GetResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+        ("binding_id" = Uuid, Path, description = "Id of the binding."),
+        GetBindingBalancesQueryParams
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id, binding_id = %binding_id))]
+pub async fn get_binding_balances(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    Path(BindingIdPath { binding_id }): Path<BindingIdPath>,
+    ValidatedQuery(query_params): ValidatedQuery<GetBindingBalancesQueryParams>,
+    ConnectorBalanceServiceState(balance_service): ConnectorBalanceServiceState,
+) -> Result<Json<GetBindingBalancesResponseViewModel>, ApiError> {
+    let snapshots = balance_service
+        .get_binding_balances(user_id, binding_id, query_params.from, query_params.to)
+        .await?;
+
+    Ok(Json(GetBindingBalancesResponseViewModel {
+        snapshots: snapshots.into_iter().map(Into::into).collect(),
+    }))
+}
+
+/// Dismiss Import Issue
+///
+/// Hides an import issue the user does not intend to resolve.
+#[utoipa::path(
+    delete,
+    path = "/api/users/{user_id}/connectors/bindings/{binding_id}/import-issues/{issue_id}",
+    tag = "Connectors",
+    responses(
+        (status = 200, description = "Import issue dismissed successfully."),
+        DeleteResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+        ("binding_id" = Uuid, Path, description = "Id of the binding."),
+        ("issue_id" = Uuid, Path, description = "Id of the import issue to dismiss."),
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id, binding_id = %binding_id, issue_id = %issue_id))]
+pub async fn dismiss_import_issue(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    Path(BindingIssuePath {
+        binding_id,
+        issue_id,
+    }): Path<BindingIssuePath>,
+    ConnectorSyncServiceState(sync_service): ConnectorSyncServiceState,
+) -> Result<(), ApiError> {
+    sync_service
+        .dismiss_import_issue(user_id, binding_id, issue_id)
+        .await?;
+    Ok(())
+}
+
+/// Reproject Binding
+///
+/// Projects the binding's archived pages again from the earliest unresolved transaction,
+/// without contacting the provider, so transactions resolved by a new ticker alias or
+/// asset are imported. Returns 409 while a sync of the same provider account is running.
+#[utoipa::path(
+    post,
+    path = "/api/users/{user_id}/connectors/bindings/{binding_id}/reproject",
+    tag = "Connectors",
+    responses(
+        (status = 200, description = "Binding re-projected successfully.", body = SyncReportViewModel),
+        UpdateResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+        ("binding_id" = Uuid, Path, description = "Id of the binding to re-project."),
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id, binding_id = %binding_id))]
+pub async fn reproject_binding(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    Path(BindingIdPath { binding_id }): Path<BindingIdPath>,
+    ConnectorSyncServiceState(sync_service): ConnectorSyncServiceState,
+) -> Result<Json<SyncReportViewModel>, ApiError> {
+    let report = sync_service.reproject_binding(user_id, binding_id).await?;
+
+    Ok(Json(report.into()))
+}
+
 /// Update Binding
 ///
 /// Updates a binding's write mode and status.
@@ -719,10 +868,18 @@ pub async fn oauth_callback(
         return Err(ApiError::NotFound("unknown provider".to_string()));
     }
     let mut params: Vec<(&str, String)> = Vec::new();
-    if let Some(code) = query.code {
+    // GoCardless returns only `ref`, which is the state the session was created with. It is
+    // relayed as the state, and as the code when consent was not refused, so the app
+    // completes the session the usual way.
+    let state = query.state.or_else(|| query.reference.clone());
+    let code = match query.error {
+        Some(_) => query.code,
+        None => query.code.or(query.reference),
+    };
+    if let Some(code) = code {
         params.push(("code", code));
     }
-    if let Some(state) = query.state {
+    if let Some(state) = state {
         params.push(("state", state));
     }
     if let Some(error) = query.error {
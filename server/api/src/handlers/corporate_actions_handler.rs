@@ -0,0 +1,161 @@
+use axum::{extract::Path, http::StatusCode, Json};
+use itertools::Itertools;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    auth::AuthenticatedUserId,
+    errors::ApiError,
+    extractors::ValidatedJson,
+    states::CorporateActionServiceState,
+    view_models::{
+        corporate_actions::{
+            base_models::{CorporateActionViewModel, IdentifiableCorporateActionViewModel},
+            get_corporate_actions::GetCorporateActionsResponseViewModel,
+        },
+        errors::{CreateResponses, DeleteResponses, GetResponses},
+    },
+};
+
+#[derive(Deserialize)]
+pub(crate) struct CorporateActionIdPath {
+    corporate_action_id: Uuid,
+}
+
+/// Get Corporate Actions
+///
+/// Lists the splits, reverse splits, spin-offs and ticker changes recorded by the user,
+/// oldest effective date first.
+#[utoipa::path(
+    get,
+    path = "/api/users/{user_id}/corporate-actions",
+    tag = "Portfolio",
+    responses(
+        (status = 200, description = "Corporate actions retrieved successfully.", body = GetCorporateActionsResponseViewModel),
+        GetResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id))]
+pub async fn get_corporate_actions(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    CorporateActionServiceState(corporate_action_service): CorporateActionServiceState,
+) -> Result<Json<GetCorporateActionsResponseViewModel>, ApiError> {
+    let corporate_actions = corporate_action_service
+        .get_corporate_actions(user_id)
+        .await?;
+
+    Ok(Json(GetCorporateActionsResponseViewModel {
+        corporate_actions: corporate_actions.into_iter().map_into().collect(),
+    }))
+}
+
+/// Create Corporate Action
+///
+/// Records a corporate action on an asset. It applies to every account holding the asset
+/// before the effective date: portfolio lots, capital gains matching, holdings and net
+/// worth history are restated in post-action units. Transactions dated on or after the
+/// effective date are taken to already be in post-action units.
+#[utoipa::path(
+    post,
+    path = "/api/users/{user_id}/corporate-actions",
+    tag = "Portfolio",
+    responses(
+        (status = 201, description = "Corporate action recorded successfully.", body = IdentifiableCorporateActionViewModel),
+        CreateResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+    ),
+    request_body(
+        content = CorporateActionViewModel,
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id))]
+pub async fn create_corporate_action(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    CorporateActionServiceState(corporate_action_service): CorporateActionServiceState,
+    ValidatedJson(body): ValidatedJson<CorporateActionViewModel>,
+) -> Result<(StatusCode, Json<IdentifiableCorporateActionViewModel>), ApiError> {
+    let corporate_action = corporate_action_service
+        .create_corporate_action(user_id, body.to_business())
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(corporate_action.into())))
+}
+
+/// Get Corporate Action
+///
+/// Gets a corporate action recorded by the user.
+#[utoipa::path(
+    get,
+    path = "/api/users/{user_id}/corporate-actions/{corporate_action_id}",
+    tag = "Portfolio",
+    responses(
+        (status = 200, description = "Corporate action retrieved successfully.", body = IdentifiableCorporateActionViewModel),
+        GetResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+        ("corporate_action_id" = Uuid, Path, description = "Id of the corporate action to retrieve."),
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id, corporate_action_id = %corporate_action_id))]
+pub async fn get_corporate_action(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    Path(CorporateActionIdPath {
+        corporate_action_id,
+    }): Path<CorporateActionIdPath>,
+    CorporateActionServiceState(corporate_action_service): CorporateActionServiceState,
+) -> Result<Json<IdentifiableCorporateActionViewModel>, ApiError> {
+    let corporate_action = corporate_action_service
+        .get_corporate_action(user_id, corporate_action_id)
+        .await?;
+
+    Ok(Json(corporate_action.into()))
+}
+
+/// Delete Corporate Action
+///
+/// Deletes a corporate action. Portfolio figures go back to the units the transactions
+/// were recorded in.
+#[utoipa::path(
+    delete,
+    path = "/api/users/{user_id}/corporate-actions/{corporate_action_id}",
+    tag = "Portfolio",
+    responses(
+        (status = 200, description = "Corporate action deleted successfully."),
+        DeleteResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+        ("corporate_action_id" = Uuid, Path, description = "Id of the corporate action to delete."),
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id, corporate_action_id = %corporate_action_id))]
+pub async fn delete_corporate_action(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    Path(CorporateActionIdPath {
+        corporate_action_id,
+    }): Path<CorporateActionIdPath>,
+    CorporateActionServiceState(corporate_action_service): CorporateActionServiceState,
+) -> Result<(), ApiError> {
+    corporate_action_service
+        .delete_corporate_action(user_id, corporate_action_id)
+        .await?;
+    Ok(())
+}
@@ -20,12 +20,13 @@ use crate::{
     errors::ApiError,
     extractors::{ValidatedJson, ValidatedQuery},
     states::{
-        AccountsServiceState, AssetsServiceState, CategoryServiceState,
-        TransactionManagementServiceState,
+        AccountsServiceState, AssetsServiceState, CategoryServiceState, SpendingAlertServiceState,
+        TransactionManagementServiceState, TransactionRuleServiceState,
     },
     view_models::errors::{CreateResponses, GetResponses, UpdateResponses},
     view_models::{
         base_models::search::{CursorOrPaginatedSearchQuery, IndividualTransactionsPage},
+        tags::base_models::TagFilterQuery,
         transactions::{
             add_individual_transaction::{
                 AddIndividualTransactionRequestViewModel, AddIndividualTransactionResponseViewModel,
@@ -67,15 +68,37 @@ use crate::{
 pub async fn add_individual_transaction(
     AuthenticatedUserId(user_id): AuthenticatedUserId,
     TransactionManagementServiceState(transaction_service): TransactionManagementServiceState,
+    TransactionRuleServiceState(rule_service): TransactionRuleServiceState,
+    SpendingAlertServiceState(alert_service): SpendingAlertServiceState,
     ValidatedJson(params): ValidatedJson<AddIndividualTransactionRequestViewModel>,
 ) -> Result<Json<AddIndividualTransactionResponseViewModel>, ApiError> {
     params.transaction.validate()?;
 
     let dto: TransactionDto = params.transaction.into();
 
-    let return_dto = transaction_service
-        .add_individual_transaction(user_id, dto)
-        .await?;
+    let return_dto = if params.apply_rules {
+        rule_service
+            .add_transaction_with_rules(user_id, dto)
+            .await?
+    } else {
+        transaction_service
+            .add_individual_transaction(user_id, dto)
+            .await?
+    };
+
+    // The alert check is a follow-up; failing to enqueue it must not fail the entry.
+    if let Some(transaction_id) = return_dto.transaction_id {
+        if let Err(e) = alert_service
+            .enqueue_check(user_id, vec![transaction_id])
+            .await
+        {
+            tracing::warn!(
+                error = ?e,
+                error.type = "enqueue_spending_alert_check",
+                "failed to enqueue spending alert check"
+            );
+        }
+    }
 
     let view_model = return_dto.into();
 
@@ -160,7 +183,8 @@ pub async fn update_individual_transaction(
     ),
     params(
         ("user_id" = Uuid, Path, description = "User id for which the transactions group belongs to."),
-        CursorOrPaginatedSearchQuery
+        CursorOrPaginatedSearchQuery,
+        TagFilterQuery
     ),
     security(
         ("auth_token" = [])
@@ -171,6 +195,7 @@ pub async fn update_individual_transaction(
 pub async fn get_individual_transactions(
     AuthenticatedUserId(user_id): AuthenticatedUserId,
     ValidatedQuery(query_params): ValidatedQuery<CursorOrPaginatedSearchQuery>,
+    ValidatedQuery(tag_filter): ValidatedQuery<TagFilterQuery>,
     AssetsServiceState(asset_service): AssetsServiceState,
     TransactionManagementServiceState(transaction_service): TransactionManagementServiceState,
     AccountsServiceState(accounts_service): AccountsServiceState,
@@ -183,6 +208,7 @@ pub async fn get_individual_transactions(
             pagination,
             IndividualTransactionFiltersDto {
                 search_query: query_params.query,
+                tag_ids: tag_filter.tag_id.map(|tag_id| vec![tag_id]),
                 ..Default::default()
             },
         )
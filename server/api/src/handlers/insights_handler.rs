@@ -0,0 +1,181 @@
+use axum::{extract::Path, http::StatusCode, Json};
+use itertools::Itertools;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    auth::AuthenticatedUserId,
+    errors::ApiError,
+    extractors::{ValidatedJson, ValidatedQuery},
+    states::InsightDigestServiceState,
+    view_models::{
+        errors::{GetResponses, UpdateResponses},
+        insights::get_insight_digests::{
+            GetInsightDigestsQuery, GetInsightDigestsResponseViewModel, InsightDigestViewModel,
+            MAX_DIGESTS_PAGE_SIZE,
+        },
+        insights::insight_digest_settings::InsightDigestSettingsViewModel,
+    },
+};
+
+#[derive(Deserialize)]
+pub(crate) struct DigestIdPath {
+    digest_id: Uuid,
+}
+
+/// Get Insight Digests
+///
+/// Lists the weekly and monthly digests built for the user once each period is over,
+/// newest first. Each compares spending by category with the period before, points out
+/// unusual transactions, reports the change in net worth and the portfolio's biggest
+/// movers, and lists the charges due next, along with a narrative written by Myra when
+/// the user opted in and their AI usage allowed.
+#[utoipa::path(
+    get,
+    path = "/api/users/{user_id}/insights/digests",
+    tag = "Insights",
+    responses(
+        (status = 200, description = "Insight digests retrieved successfully.", body = GetInsightDigestsResponseViewModel),
+        GetResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+        GetInsightDigestsQuery,
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id))]
+pub async fn get_insight_digests(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    ValidatedQuery(query): ValidatedQuery<GetInsightDigestsQuery>,
+    InsightDigestServiceState(digest_service): InsightDigestServiceState,
+) -> Result<Json<GetInsightDigestsResponseViewModel>, ApiError> {
+    let digests = digest_service
+        .get_digests(
+            user_id,
+            query.period.map(Into::into),
+            query.start,
+            query.count.clamp(1, MAX_DIGESTS_PAGE_SIZE),
+        )
+        .await?;
+
+    Ok(Json(GetInsightDigestsResponseViewModel {
+        digests: digests.into_iter().map_into().collect(),
+    }))
+}
+
+/// Get Insight Digest
+#[utoipa::path(
+    get,
+    path = "/api/users/{user_id}/insights/digests/{digest_id}",
+    tag = "Insights",
+    responses(
+        (status = 200, description = "Insight digest retrieved successfully.", body = InsightDigestViewModel),
+        GetResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+        ("digest_id" = Uuid, Path, description = "Unique Identifier of the digest."),
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id, digest_id = %digest_id))]
+pub async fn get_insight_digest(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    Path(DigestIdPath { digest_id }): Path<DigestIdPath>,
+    InsightDigestServiceState(digest_service): InsightDigestServiceState,
+) -> Result<Json<InsightDigestViewModel>, ApiError> {
+    let digest = digest_service.get_digest(user_id, digest_id).await?;
+    Ok(Json(digest.into()))
+}
+
+/// Mark Insight Digest Read
+///
+/// Records when the user first opened the digest. Marking a digest read again keeps
+/// the first time.
+#[utoipa::path(
+    post,
+    path = "/api/users/{user_id}/insights/digests/{digest_id}/read",
+    tag = "Insights",
+    responses(
+        (status = 204, description = "Insight digest marked as read."),
+        UpdateResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+        ("digest_id" = Uuid, Path, description = "Unique Identifier of the digest."),
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id, digest_id = %digest_id))]
+pub async fn mark_insight_digest_read(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    Path(DigestIdPath { digest_id }): Path<DigestIdPath>,
+    InsightDigestServiceState(digest_service): InsightDigestServiceState,
+) -> Result<StatusCode, ApiError> {
+    digest_service.mark_read(user_id, digest_id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Get Insight Digest Settings
+#[utoipa::path(
+    get,
+    path = "/api/users/{user_id}/insights/settings",
+    tag = "Insights",
+    responses(
+        (status = 200, description = "Insight digest settings retrieved successfully.", body = InsightDigestSettingsViewModel),
+        GetResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id))]
+pub async fn get_insight_digest_settings(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    InsightDigestServiceState(digest_service): InsightDigestServiceState,
+) -> Result<Json<InsightDigestSettingsViewModel>, ApiError> {
+    let settings = digest_service.get_settings(user_id).await?;
+    Ok(Json(settings.into()))
+}
+
+/// Update Insight Digest Settings
+///
+/// Opts the user in to or out of narrated digests. Digests are only sent to the AI
+/// provider for a narrative after the user opted in; figures are built either way.
+#[utoipa::path(
+    put,
+    path = "/api/users/{user_id}/insights/settings",
+    tag = "Insights",
+    request_body(
+        content = InsightDigestSettingsViewModel,
+    ),
+    responses(
+        (status = 204, description = "Insight digest settings updated successfully."),
+        UpdateResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id))]
+pub async fn update_insight_digest_settings(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    InsightDigestServiceState(digest_service): InsightDigestServiceState,
+    ValidatedJson(body): ValidatedJson<InsightDigestSettingsViewModel>,
+) -> Result<StatusCode, ApiError> {
+    digest_service.update_settings(user_id, body.into()).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
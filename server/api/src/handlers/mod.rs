@@ -1,17 +1,33 @@
 pub mod account_portfolio_handler;
 pub mod accounts_handler;
+pub mod ai_categorizer_handler;
 pub mod ai_conversation_handler;
 pub mod ai_quick_upload_handler;
 pub mod ai_usage_handler;
 pub mod asset_handler;
 pub mod auth_handler;
+pub mod budgets_handler;
 pub mod category_handler;
+pub mod connector_review_handler;
 pub mod connectors_handler;
+pub mod corporate_actions_handler;
 pub mod file_handler;
 pub mod individual_transactions;
+pub mod insights_handler;
 pub mod portfolio_handler;
+pub mod reconciliations_handler;
+pub mod recurring_transactions_handler;
+pub mod reports_handler;
+pub mod spending_alerts_handler;
+pub mod statement_imports_handler;
+pub mod subscriptions_handler;
+pub mod tags_handler;
+pub mod ticker_aliases_handler;
 pub mod transaction_groups;
+pub mod transaction_rules_handler;
 pub mod transactions;
+pub mod transfers_handler;
 pub mod user_asset_handler;
 pub mod user_category_handler;
+pub mod user_data_archive_handler;
 pub mod user_handler;
@@ -156,6 +156,7 @@ pub async fn get_networth_history(
 
     let response = GetNetWorthHistoryResponseViewModel {
         sums: history.into_iter().map_into().collect(),
+        provider_sums: vec![],
         range: query_params.range.to_string(),
     };
 
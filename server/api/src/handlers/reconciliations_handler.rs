@@ -0,0 +1,173 @@
+use axum::{extract::Path, http::StatusCode, Json};
+use itertools::Itertools;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    auth::AuthenticatedUserId,
+    errors::ApiError,
+    extractors::ValidatedJson,
+    states::ReconciliationServiceState,
+    view_models::{
+        errors::{CreateResponses, DeleteResponses, GetResponses},
+        reconciliations::{
+            base_models::{ReconciliationReportViewModel, ReconciliationViewModel},
+            get_reconciliations::GetReconciliationsResponseViewModel,
+        },
+    },
+};
+
+#[derive(Deserialize)]
+pub(crate) struct AccountIdPath {
+    account_id: Uuid,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct ReconciliationIdPath {
+    account_id: Uuid,
+    reconciliation_id: Uuid,
+}
+
+/// Get Reconciliations
+///
+/// Lists the account's reconciliation checkpoints, newest first, each compared with the
+/// ledger as it stands now.
+#[utoipa::path(
+    get,
+    path = "/api/users/{user_id}/accounts/{account_id}/reconciliations",
+    tag = "Accounts",
+    responses(
+        (status = 200, description = "Reconciliations retrieved successfully.", body = GetReconciliationsResponseViewModel),
+        GetResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+        ("account_id" = Uuid, Path, description = "Id of the account."),
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id, account_id = %account_id))]
+pub async fn get_reconciliations(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    Path(AccountIdPath { account_id }): Path<AccountIdPath>,
+    ReconciliationServiceState(reconciliation_service): ReconciliationServiceState,
+) -> Result<Json<GetReconciliationsResponseViewModel>, ApiError> {
+    let reconciliations = reconciliation_service
+        .get_reconciliations(user_id, account_id)
+        .await?;
+
+    Ok(Json(GetReconciliationsResponseViewModel {
+        reconciliations: reconciliations.into_iter().map_into().collect(),
+    }))
+}
+
+/// Create Reconciliation
+///
+/// Records the balance a statement shows for one asset of the account and compares it
+/// with the sum of the account's entries up to the statement date. The response lists the
+/// transactions booked since the last checkpoint that still matches the ledger.
+#[utoipa::path(
+    post,
+    path = "/api/users/{user_id}/accounts/{account_id}/reconciliations",
+    tag = "Accounts",
+    responses(
+        (status = 201, description = "Reconciliation recorded successfully.", body = ReconciliationReportViewModel),
+        CreateResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+        ("account_id" = Uuid, Path, description = "Id of the account."),
+    ),
+    request_body(
+        content = ReconciliationViewModel,
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id, account_id = %account_id))]
+pub async fn create_reconciliation(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    Path(AccountIdPath { account_id }): Path<AccountIdPath>,
+    ReconciliationServiceState(reconciliation_service): ReconciliationServiceState,
+    ValidatedJson(body): ValidatedJson<ReconciliationViewModel>,
+) -> Result<(StatusCode, Json<ReconciliationReportViewModel>), ApiError> {
+    let report = reconciliation_service
+        .create_reconciliation(user_id, account_id, body.to_business())
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(report.into())))
+}
+
+/// Get Reconciliation
+///
+/// Gets a reconciliation checkpoint compared with the ledger as it stands now.
+#[utoipa::path(
+    get,
+    path = "/api/users/{user_id}/accounts/{account_id}/reconciliations/{reconciliation_id}",
+    tag = "Accounts",
+    responses(
+        (status = 200, description = "Reconciliation retrieved successfully.", body = ReconciliationReportViewModel),
+        GetResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+        ("account_id" = Uuid, Path, description = "Id of the account."),
+        ("reconciliation_id" = Uuid, Path, description = "Id of the reconciliation to retrieve."),
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id, reconciliation_id = %reconciliation_id))]
+pub async fn get_reconciliation(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    Path(ReconciliationIdPath {
+        account_id,
+        reconciliation_id,
+    }): Path<ReconciliationIdPath>,
+    ReconciliationServiceState(reconciliation_service): ReconciliationServiceState,
+) -> Result<Json<ReconciliationReportViewModel>, ApiError> {
+    let report = reconciliation_service
+        .get_reconciliation(user_id, account_id, reconciliation_id)
+        .await?;
+
+    Ok(Json(report.into()))
+}
+
+/// Delete Reconciliation
+///
+/// Deletes a reconciliation checkpoint.
+#[utoipa::path(
+    delete,
+    path = "/api/users/{user_id}/accounts/{account_id}/reconciliations/{reconciliation_id}",
+    tag = "Accounts",
+    responses(
+        (status = 200, description = "Reconciliation deleted successfully."),
+        DeleteResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+        ("account_id" = Uuid, Path, description = "Id of the account."),
+        ("reconciliation_id" = Uuid, Path, description = "Id of the reconciliation to delete."),
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id, reconciliation_id = %reconciliation_id))]
+pub async fn delete_reconciliation(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    Path(ReconciliationIdPath {
+        account_id,
+        reconciliation_id,
+    }): Path<ReconciliationIdPath>,
+    ReconciliationServiceState(reconciliation_service): ReconciliationServiceState,
+) -> Result<(), ApiError> {
+    reconciliation_service
+        .delete_reconciliation(user_id, account_id, reconciliation_id)
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,203 @@
+use axum::{extract::Path, http::StatusCode, Json};
+use itertools::Itertools;
+use serde::Deserialize;
+use uuid::Uuid;
+
+#[derive(Deserialize)]
+pub(crate) struct RecurringTransactionIdPath {
+    recurring_transaction_id: Uuid,
+}
+
+use crate::{
+    auth::AuthenticatedUserId,
+    errors::ApiError,
+    extractors::ValidatedJson,
+    states::RecurringTransactionServiceState,
+    view_models::{
+        errors::{CreateResponses, DeleteResponses, GetResponses, UpdateResponses},
+        recurring_transactions::{
+            base_models::{
+                IdentifiableRecurringTransactionViewModel, RecurringTransactionViewModel,
+            },
+            get_recurring_transactions::GetRecurringTransactionsResponseViewModel,
+        },
+        transactions::validation::Validatable,
+    },
+};
+
+/// Get Recurring Transactions
+///
+/// Lists all recurring transaction templates of the user.
+#[utoipa::path(
+    get,
+    path = "/api/users/{user_id}/recurring-transactions",
+    tag = "Recurring Transactions",
+    responses(
+        (status = 200, description = "Recurring transactions retrieved successfully.", body = GetRecurringTransactionsResponseViewModel),
+        GetResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id))]
+pub async fn get_recurring_transactions(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    RecurringTransactionServiceState(recurring_service): RecurringTransactionServiceState,
+) -> Result<Json<GetRecurringTransactionsResponseViewModel>, ApiError> {
+    let recurring_transactions = recurring_service
+        .get_recurring_transactions(user_id)
+        .await?;
+
+    Ok(Json(GetRecurringTransactionsResponseViewModel {
+        recurring_transactions: recurring_transactions.into_iter().map_into().collect(),
+    }))
+}
+
+/// Create Recurring Transaction
+///
+/// Creates a template that adds a transaction on every occurrence of its schedule.
+/// Occurrences are created by a background job, optionally as ghost transactions for review.
+#[utoipa::path(
+    post,
+    path = "/api/users/{user_id}/recurring-transactions",
+    tag = "Recurring Transactions",
+    responses(
+        (status = 201, description = "Recurring transaction created successfully.", body = IdentifiableRecurringTransactionViewModel),
+        CreateResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+    ),
+    request_body(
+        content = RecurringTransactionViewModel,
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id))]
+pub async fn create_recurring_transaction(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    RecurringTransactionServiceState(recurring_service): RecurringTransactionServiceState,
+    ValidatedJson(body): ValidatedJson<RecurringTransactionViewModel>,
+) -> Result<(StatusCode, Json<IdentifiableRecurringTransactionViewModel>), ApiError> {
+    body.transaction.validate()?;
+
+    let recurring = recurring_service
+        .create_recurring_transaction(user_id, body.to_business())
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(recurring.into())))
+}
+
+/// Get Recurring Transaction
+///
+/// Gets a specific recurring transaction template by ID.
+#[utoipa::path(
+    get,
+    path = "/api/users/{user_id}/recurring-transactions/{recurring_transaction_id}",
+    tag = "Recurring Transactions",
+    responses(
+        (status = 200, description = "Recurring transaction retrieved successfully.", body = IdentifiableRecurringTransactionViewModel),
+        GetResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+        ("recurring_transaction_id" = Uuid, Path, description = "Id of the recurring transaction to retrieve."),
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id, recurring_transaction_id = %recurring_transaction_id))]
+pub async fn get_recurring_transaction(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    Path(RecurringTransactionIdPath {
+        recurring_transaction_id,
+    }): Path<RecurringTransactionIdPath>,
+    RecurringTransactionServiceState(recurring_service): RecurringTransactionServiceState,
+) -> Result<Json<IdentifiableRecurringTransactionViewModel>, ApiError> {
+    let recurring = recurring_service
+        .get_recurring_transaction(user_id, recurring_transaction_id)
+        .await?;
+
+    Ok(Json(recurring.into()))
+}
+
+/// Update Recurring Transaction
+///
+/// Replaces a recurring transaction template. Its schedule restarts from today;
+/// transactions that were already created are left untouched.
+#[utoipa::path(
+    put,
+    path = "/api/users/{user_id}/recurring-transactions/{recurring_transaction_id}",
+    tag = "Recurring Transactions",
+    responses(
+        (status = 200, description = "Recurring transaction updated successfully.", body = IdentifiableRecurringTransactionViewModel),
+        UpdateResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+        ("recurring_transaction_id" = Uuid, Path, description = "Id of the recurring transaction to update."),
+    ),
+    request_body(
+        content = RecurringTransactionViewModel,
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id, recurring_transaction_id = %recurring_transaction_id))]
+pub async fn update_recurring_transaction(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    Path(RecurringTransactionIdPath {
+        recurring_transaction_id,
+    }): Path<RecurringTransactionIdPath>,
+    RecurringTransactionServiceState(recurring_service): RecurringTransactionServiceState,
+    ValidatedJson(body): ValidatedJson<RecurringTransactionViewModel>,
+) -> Result<Json<IdentifiableRecurringTransactionViewModel>, ApiError> {
+    body.transaction.validate()?;
+
+    let recurring = recurring_service
+        .update_recurring_transaction(user_id, recurring_transaction_id, body.to_business())
+        .await?;
+
+    Ok(Json(recurring.into()))
+}
+
+/// Delete Recurring Transaction
+///
+/// Deletes a recurring transaction template. Transactions it already created are kept.
+#[utoipa::path(
+    delete,
+    path = "/api/users/{user_id}/recurring-transactions/{recurring_transaction_id}",
+    tag = "Recurring Transactions",
+    responses(
+        (status = 200, description = "Recurring transaction deleted successfully."),
+        DeleteResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+        ("recurring_transaction_id" = Uuid, Path, description = "Id of the recurring transaction to delete."),
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id, recurring_transaction_id = %recurring_transaction_id))]
+pub async fn delete_recurring_transaction(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    Path(RecurringTransactionIdPath {
+        recurring_transaction_id,
+    }): Path<RecurringTransactionIdPath>,
+    RecurringTransactionServiceState(recurring_service): RecurringTransactionServiceState,
+) -> Result<(), ApiError> {
+    recurring_service
+        .delete_recurring_transaction(user_id, recurring_transaction_id)
+        .await?;
+    Ok(())
+}
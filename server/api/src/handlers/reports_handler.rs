@@ -0,0 +1,107 @@
+use std::collections::HashSet;
+
+use axum::{
+    http::header,
+    response::{IntoResponse, Response},
+    Json,
+};
+use business::dtos::assets::asset_id_dto::AssetIdDto;
+use itertools::Itertools;
+
+use crate::{
+    auth::AuthenticatedUserId,
+    errors::ApiError,
+    extractors::ValidatedQuery,
+    states::{AssetsServiceState, PortfolioOverviewServiceState, UsersServiceState},
+    view_models::{
+        assets::base_models::asset_id::RequiredAssetId,
+        errors::GetResponses,
+        reports::get_capital_gains::{
+            CapitalGainsReportLookupTables, GetCapitalGainsReportQueryParams,
+            GetCapitalGainsReportResponseViewModel, ReportFormat,
+        },
+    },
+};
+
+/// Get Capital Gains Report
+///
+/// Returns every disposal in the user's portfolio matched with the UK share identification
+/// rules (same day, 30 day bed and breakfast, Section 104 pool) across all accounts, together
+/// with dividend and withholding tax totals, grouped by UK tax year.
+#[utoipa::path(
+    get,
+    path = "/api/users/{user_id}/reports/capital-gains",
+    tag = "Reports",
+    responses(
+        (status = 200, description = "Capital gains report, as CSV when format=csv", content(
+            (GetCapitalGainsReportResponseViewModel = "application/json"),
+            (String = "text/csv")
+        )),
+        GetResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "User id"),
+        GetCapitalGainsReportQueryParams
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id, year = ?query_params.year))]
+pub async fn get_capital_gains_report(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    ValidatedQuery(query_params): ValidatedQuery<GetCapitalGainsReportQueryParams>,
+    PortfolioOverviewServiceState(portfolio_service): PortfolioOverviewServiceState,
+    UsersServiceState(user_service): UsersServiceState,
+    AssetsServiceState(asset_service): AssetsServiceState,
+) -> Result<Response, ApiError> {
+    let default_asset = match query_params.default_asset_id {
+        Some(id) => id,
+        None => user_service
+            .get_default_asset(user_id)
+            .await?
+            .ok_or_else(|| ApiError::Conflict("User has no base currency set".to_string()))?,
+    };
+
+    let tax_years: Vec<_> = portfolio_service
+        .get_capital_gains_report(AssetIdDto(default_asset), user_id)
+        .await?
+        .into_iter()
+        .filter(|x| query_params.year.is_none_or(|year| x.tax_year == year))
+        .collect();
+
+    let asset_ids: HashSet<i32> = tax_years
+        .iter()
+        .flat_map(|x| x.disposals.iter().map(|d| d.asset_id))
+        .collect();
+    let assets = asset_service.get_assets(asset_ids).await?;
+
+    let response = GetCapitalGainsReportResponseViewModel {
+        reference_asset_id: RequiredAssetId(default_asset),
+        tax_years: tax_years.into_iter().map_into().collect(),
+        lookup_tables: CapitalGainsReportLookupTables {
+            assets: assets.into_iter().map_into().collect(),
+        },
+    };
+
+    match query_params.format {
+        ReportFormat::Json => Ok(Json(response).into_response()),
+        ReportFormat::Csv => {
+            let file_name = match query_params.year {
+                Some(year) => format!("capital-gains-{year}.csv"),
+                None => "capital-gains.csv".to_string(),
+            };
+            Ok((
+                [
+                    (header::CONTENT_TYPE, "text/csv; charset=utf-8".to_string()),
+                    (
+                        header::CONTENT_DISPOSITION,
+                        format!("attachment; filename=\"{file_name}\""),
+                    ),
+                ],
+                response.to_csv(),
+            )
+                .into_response())
+        }
+    }
+}
@@ -0,0 +1,153 @@
+use std::convert::Infallible;
+
+use axum::{
+    extract::Path,
+    http::StatusCode,
+    response::sse::{Event, KeepAlive, Sse},
+    Json,
+};
+use business::dtos::spending_alert_dto::SpendingAlertDto;
+use futures::Stream;
+use itertools::Itertools;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    auth::AuthenticatedUserId,
+    errors::ApiError,
+    extractors::ValidatedQuery,
+    states::SpendingAlertServiceState,
+    view_models::{
+        errors::{GetResponses, UpdateResponses},
+        spending_alerts::get_spending_alerts::{
+            GetSpendingAlertsQuery, GetSpendingAlertsResponseViewModel, SpendingAlertViewModel,
+            MAX_ALERTS_PAGE_SIZE,
+        },
+    },
+};
+
+#[derive(Deserialize)]
+pub(crate) struct AlertIdPath {
+    alert_id: Uuid,
+}
+
+/// Get Spending Alerts
+///
+/// Lists the alerts raised on the user's recent outgoing transactions, newest first:
+/// amounts far above the usual for the merchant or category, the same charge taken twice
+/// within a day, a first purchase from a merchant in a foreign currency, and a currency
+/// the account is not normally charged in. New transactions are checked right after they
+/// are entered or imported; subscribe to hear about alerts as they are raised.
+#[utoipa::path(
+    get,
+    path = "/api/users/{user_id}/spending-alerts",
+    tag = "Spending Alerts",
+    responses(
+        (status = 200, description = "Spending alerts retrieved successfully.", body = GetSpendingAlertsResponseViewModel),
+        GetResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+        GetSpendingAlertsQuery,
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id))]
+pub async fn get_spending_alerts(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    ValidatedQuery(query): ValidatedQuery<GetSpendingAlertsQuery>,
+    SpendingAlertServiceState(alert_service): SpendingAlertServiceState,
+) -> Result<Json<GetSpendingAlertsResponseViewModel>, ApiError> {
+    let alerts = alert_service
+        .get_alerts(
+            user_id,
+            query.include_dismissed,
+            query.start,
+            query.count.clamp(1, MAX_ALERTS_PAGE_SIZE),
+        )
+        .await?;
+
+    Ok(Json(GetSpendingAlertsResponseViewModel {
+        alerts: alerts.into_iter().map_into().collect(),
+    }))
+}
+
+/// Get Spending Alert
+#[utoipa::path(
+    get,
+    path = "/api/users/{user_id}/spending-alerts/{alert_id}",
+    tag = "Spending Alerts",
+    responses(
+        (status = 200, description = "Spending alert retrieved successfully.", body = SpendingAlertViewModel),
+        GetResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+        ("alert_id" = Uuid, Path, description = "Unique Identifier of the alert."),
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id, alert_id = %alert_id))]
+pub async fn get_spending_alert(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    Path(AlertIdPath { alert_id }): Path<AlertIdPath>,
+    SpendingAlertServiceState(alert_service): SpendingAlertServiceState,
+) -> Result<Json<SpendingAlertViewModel>, ApiError> {
+    let alert = alert_service.get_alert(user_id, alert_id).await?;
+    Ok(Json(alert.into()))
+}
+
+/// Dismiss Spending Alert
+///
+/// Hides the alert from the default listing. Dismissing it again keeps the first time.
+#[utoipa::path(
+    post,
+    path = "/api/users/{user_id}/spending-alerts/{alert_id}/dismiss",
+    tag = "Spending Alerts",
+    responses(
+        (status = 204, description = "Spending alert dismissed."),
+        UpdateResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+        ("alert_id" = Uuid, Path, description = "Unique Identifier of the alert."),
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id, alert_id = %alert_id))]
+pub async fn dismiss_spending_alert(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    Path(AlertIdPath { alert_id }): Path<AlertIdPath>,
+    SpendingAlertServiceState(alert_service): SpendingAlertServiceState,
+) -> Result<StatusCode, ApiError> {
+    alert_service.dismiss(user_id, alert_id).await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Streams each alert raised for the user from now on as an `alert` event.
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id))]
+pub async fn subscribe(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    SpendingAlertServiceState(alert_service): SpendingAlertServiceState,
+) -> Result<Sse<impl Stream<Item = Result<Event, Infallible>>>, ApiError> {
+    let mut rx = alert_service.subscribe(user_id).await;
+
+    let stream = async_stream::stream! {
+        while let Ok(event) = rx.recv().await {
+            if let Ok(alert) = serde_json::from_value::<SpendingAlertDto>(event.payload) {
+                let vm = SpendingAlertViewModel::from(alert);
+                yield Ok(Event::default().event("alert").data(
+                    serde_json::to_string(&vm).unwrap_or_else(|_| "{}".to_string())
+                ));
+            }
+        }
+    };
+
+    Ok(Sse::new(stream).keep_alive(KeepAlive::default()))
+}
@@ -0,0 +1,176 @@
+use axum::{extract::Path, Json};
+use business::dtos::connectors::SyncOutcomeDto;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    auth::AuthenticatedUserId,
+    errors::ApiError,
+    extractors::ValidatedJson,
+    states::StatementImportServiceState,
+    view_models::connectors::{
+        ingest::IngestTransactionsResponseViewModel,
+        statement_import::{CsvColumnMappingViewModel, ImportStatementRequestViewModel},
+    },
+    view_models::errors::{CreateResponses, DeleteResponses, GetResponses, UpdateResponses},
+};
+
+#[derive(Deserialize)]
+pub(crate) struct BindingIdPath {
+    binding_id: Uuid,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct AccountIdPath {
+    account_id: Uuid,
+}
+
+/// Import Statement
+///
+/// Imports an uploaded bank statement (CSV, OFX/QFX, QIF or CAMT.053) into a
+/// client-supplied `statement_file` binding. Lines already imported from an earlier,
+/// overlapping statement are recognised and not duplicated. CSV uploads use the
+/// account's saved column mapping unless one is sent with the request.
+#[utoipa::path(
+    post,
+    path = "/api/users/{user_id}/connectors/bindings/{binding_id}/statements",
+    tag = "Connectors",
+    responses(
+        (status = 200, description = "Statement imported successfully.", body = IngestTransactionsResponseViewModel),
+        CreateResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+        ("binding_id" = Uuid, Path, description = "Id of the binding to import the statement into."),
+    ),
+    request_body(
+        content = ImportStatementRequestViewModel,
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id, binding_id = %binding_id))]
+pub async fn import_statement(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    Path(BindingIdPath { binding_id }): Path<BindingIdPath>,
+    StatementImportServiceState(statement_import_service): StatementImportServiceState,
+    ValidatedJson(body): ValidatedJson<ImportStatementRequestViewModel>,
+) -> Result<Json<IngestTransactionsResponseViewModel>, ApiError> {
+    let outcome = statement_import_service
+        .import_statement(user_id, binding_id, body.to_business())
+        .await?;
+
+    match outcome {
+        SyncOutcomeDto::Complete { report } => Ok(Json(IngestTransactionsResponseViewModel {
+            next_cursor: None,
+            report: Some(report.into()),
+        })),
+        SyncOutcomeDto::Partial { next_cursor, .. } => {
+            Ok(Json(IngestTransactionsResponseViewModel {
+                next_cursor,
+                report: None,
+            }))
+        }
+        SyncOutcomeDto::Failed { error } => Err(ApiError::BadRequest(error)),
+    }
+}
+
+/// Get Statement CSV Mapping
+///
+/// Gets the column mapping saved for an account's CSV statements.
+#[utoipa::path(
+    get,
+    path = "/api/users/{user_id}/accounts/{account_id}/statement-csv-mapping",
+    tag = "Connectors",
+    responses(
+        (status = 200, description = "CSV mapping retrieved successfully.", body = CsvColumnMappingViewModel),
+        GetResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+        ("account_id" = Uuid, Path, description = "Id of the account."),
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id, account_id = %account_id))]
+pub async fn get_csv_mapping(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    Path(AccountIdPath { account_id }): Path<AccountIdPath>,
+    StatementImportServiceState(statement_import_service): StatementImportServiceState,
+) -> Result<Json<CsvColumnMappingViewModel>, ApiError> {
+    let mapping = statement_import_service
+        .get_csv_mapping(user_id, account_id)
+        .await?;
+
+    Ok(Json(mapping.into()))
+}
+
+/// Save Statement CSV Mapping
+///
+/// Saves how to read an account's CSV statements, replacing any earlier mapping.
+#[utoipa::path(
+    put,
+    path = "/api/users/{user_id}/accounts/{account_id}/statement-csv-mapping",
+    tag = "Connectors",
+    responses(
+        (status = 200, description = "CSV mapping saved successfully.", body = CsvColumnMappingViewModel),
+        UpdateResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+        ("account_id" = Uuid, Path, description = "Id of the account."),
+    ),
+    request_body(
+        content = CsvColumnMappingViewModel,
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id, account_id = %account_id))]
+pub async fn save_csv_mapping(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    Path(AccountIdPath { account_id }): Path<AccountIdPath>,
+    StatementImportServiceState(statement_import_service): StatementImportServiceState,
+    ValidatedJson(body): ValidatedJson<CsvColumnMappingViewModel>,
+) -> Result<Json<CsvColumnMappingViewModel>, ApiError> {
+    let mapping = statement_import_service
+        .save_csv_mapping(user_id, account_id, body.to_business())
+        .await?;
+
+    Ok(Json(mapping.into()))
+}
+
+/// Delete Statement CSV Mapping
+///
+/// Deletes the column mapping saved for an account's CSV statements.
+#[utoipa::path(
+    delete,
+    path = "/api/users/{user_id}/accounts/{account_id}/statement-csv-mapping",
+    tag = "Connectors",
+    responses(
+        (status = 200, description = "CSV mapping deleted successfully."),
+        DeleteResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+        ("account_id" = Uuid, Path, description = "Id of the account."),
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id, account_id = %account_id))]
+pub async fn delete_csv_mapping(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    Path(AccountIdPath { account_id }): Path<AccountIdPath>,
+    StatementImportServiceState(statement_import_service): StatementImportServiceState,
+) -> Result<(), ApiError> {
+    statement_import_service
+        .delete_csv_mapping(user_id, account_id)
+        .await?;
+    Ok(())
+}
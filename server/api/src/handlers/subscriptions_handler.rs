@@ -0,0 +1,47 @@
+use axum::Json;
+use itertools::Itertools;
+use time::OffsetDateTime;
+
+use crate::{
+    auth::AuthenticatedUserId,
+    errors::ApiError,
+    states::SubscriptionServiceState,
+    view_models::{
+        errors::GetResponses, subscriptions::get_subscriptions::GetSubscriptionsResponseViewModel,
+    },
+};
+
+/// Get Subscriptions
+///
+/// Lists recurring payments detected in the user's regular transactions, such as
+/// streaming services or memberships. Charges are matched by description and amount,
+/// and only series with a weekly, monthly, quarterly or yearly rhythm that are
+/// still being charged are returned, ordered by the next expected charge.
+#[utoipa::path(
+    get,
+    path = "/api/users/{user_id}/subscriptions",
+    tag = "Subscriptions",
+    responses(
+        (status = 200, description = "Subscriptions detected successfully.", body = GetSubscriptionsResponseViewModel),
+        GetResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id))]
+pub async fn get_subscriptions(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    SubscriptionServiceState(subscription_service): SubscriptionServiceState,
+) -> Result<Json<GetSubscriptionsResponseViewModel>, ApiError> {
+    let subscriptions = subscription_service
+        .detect_subscriptions(user_id, OffsetDateTime::now_utc().date())
+        .await?;
+
+    Ok(Json(GetSubscriptionsResponseViewModel {
+        subscriptions: subscriptions.into_iter().map_into().collect(),
+    }))
+}
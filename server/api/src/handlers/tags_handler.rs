@@ -0,0 +1,339 @@
+use axum::{extract::Path, http::StatusCode, Json};
+use itertools::Itertools;
+use serde::Deserialize;
+use uuid::Uuid;
+
+#[derive(Deserialize)]
+pub(crate) struct TagIdPath {
+    tag_id: Uuid,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct TransactionIdPath {
+    transaction_id: Uuid,
+}
+
+#[derive(Deserialize)]
+pub(crate) struct GroupIdPath {
+    group_id: Uuid,
+}
+
+use crate::{
+    auth::AuthenticatedUserId,
+    errors::ApiError,
+    extractors::ValidatedJson,
+    states::TagServiceState,
+    view_models::{
+        errors::{CreateResponses, DeleteResponses, GetResponses, UpdateResponses},
+        tags::{
+            base_models::{IdentifiableTagViewModel, TagViewModel},
+            get_tags::GetTagsResponseViewModel,
+            set_tags::SetTagsRequestViewModel,
+        },
+    },
+};
+
+/// Get Tags
+///
+/// Lists all tags of the user, ordered by name.
+#[utoipa::path(
+    get,
+    path = "/api/users/{user_id}/tags",
+    tag = "Tags",
+    responses(
+        (status = 200, description = "Tags retrieved successfully.", body = GetTagsResponseViewModel),
+        GetResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id))]
+pub async fn get_tags(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    TagServiceState(tag_service): TagServiceState,
+) -> Result<Json<GetTagsResponseViewModel>, ApiError> {
+    let tags = tag_service.get_tags(user_id).await?;
+
+    Ok(Json(GetTagsResponseViewModel {
+        tags: tags.into_iter().map_into().collect(),
+    }))
+}
+
+/// Create Tag
+///
+/// Creates a label that can be attached to transactions and transaction groups.
+/// Tag names are unique per user.
+#[utoipa::path(
+    post,
+    path = "/api/users/{user_id}/tags",
+    tag = "Tags",
+    responses(
+        (status = 201, description = "Tag created successfully.", body = IdentifiableTagViewModel),
+        CreateResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+    ),
+    request_body(
+        content = TagViewModel,
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id))]
+pub async fn create_tag(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    TagServiceState(tag_service): TagServiceState,
+    ValidatedJson(body): ValidatedJson<TagViewModel>,
+) -> Result<(StatusCode, Json<IdentifiableTagViewModel>), ApiError> {
+    let tag = tag_service
+        .create_tag(user_id, body.name.into_inner())
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(tag.into())))
+}
+
+/// Get Tag
+///
+/// Gets a specific tag by ID.
+#[utoipa::path(
+    get,
+    path = "/api/users/{user_id}/tags/{tag_id}",
+    tag = "Tags",
+    responses(
+        (status = 200, description = "Tag retrieved successfully.", body = IdentifiableTagViewModel),
+        GetResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+        ("tag_id" = Uuid, Path, description = "Id of the tag to retrieve."),
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id, tag_id = %tag_id))]
+pub async fn get_tag(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    Path(TagIdPath { tag_id }): Path<TagIdPath>,
+    TagServiceState(tag_service): TagServiceState,
+) -> Result<Json<IdentifiableTagViewModel>, ApiError> {
+    let tag = tag_service.get_tag(user_id, tag_id).await?;
+
+    Ok(Json(tag.into()))
+}
+
+/// Update Tag
+///
+/// Renames a tag.
+#[utoipa::path(
+    put,
+    path = "/api/users/{user_id}/tags/{tag_id}",
+    tag = "Tags",
+    responses(
+        (status = 200, description = "Tag updated successfully.", body = IdentifiableTagViewModel),
+        UpdateResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+        ("tag_id" = Uuid, Path, description = "Id of the tag to update."),
+    ),
+    request_body(
+        content = TagViewModel,
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id, tag_id = %tag_id))]
+pub async fn update_tag(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    Path(TagIdPath { tag_id }): Path<TagIdPath>,
+    TagServiceState(tag_service): TagServiceState,
+    ValidatedJson(body): ValidatedJson<TagViewModel>,
+) -> Result<Json<IdentifiableTagViewModel>, ApiError> {
+    let tag = tag_service
+        .update_tag(user_id, tag_id, body.name.into_inner())
+        .await?;
+
+    Ok(Json(tag.into()))
+}
+
+/// Delete Tag
+///
+/// Deletes a tag and removes it from every transaction and group.
+#[utoipa::path(
+    delete,
+    path = "/api/users/{user_id}/tags/{tag_id}",
+    tag = "Tags",
+    responses(
+        (status = 200, description = "Tag deleted successfully."),
+        DeleteResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+        ("tag_id" = Uuid, Path, description = "Id of the tag to delete."),
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id, tag_id = %tag_id))]
+pub async fn delete_tag(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    Path(TagIdPath { tag_id }): Path<TagIdPath>,
+    TagServiceState(tag_service): TagServiceState,
+) -> Result<(), ApiError> {
+    tag_service.delete_tag(user_id, tag_id).await?;
+    Ok(())
+}
+
+/// Get Transaction Tags
+///
+/// Lists the tags attached directly to a transaction. Tags of its group are not included.
+#[utoipa::path(
+    get,
+    path = "/api/users/{user_id}/transactions/{transaction_id}/tags",
+    tag = "Tags",
+    responses(
+        (status = 200, description = "Transaction tags retrieved successfully.", body = GetTagsResponseViewModel),
+        GetResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+        ("transaction_id" = Uuid, Path, description = "Id of the transaction."),
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id, transaction_id = %transaction_id))]
+pub async fn get_transaction_tags(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    Path(TransactionIdPath { transaction_id }): Path<TransactionIdPath>,
+    TagServiceState(tag_service): TagServiceState,
+) -> Result<Json<GetTagsResponseViewModel>, ApiError> {
+    let tags = tag_service
+        .get_transaction_tags(user_id, transaction_id)
+        .await?;
+
+    Ok(Json(GetTagsResponseViewModel {
+        tags: tags.into_iter().map_into().collect(),
+    }))
+}
+
+/// Set Transaction Tags
+///
+/// Replaces the tags attached to a transaction. An empty list removes all of them.
+#[utoipa::path(
+    put,
+    path = "/api/users/{user_id}/transactions/{transaction_id}/tags",
+    tag = "Tags",
+    responses(
+        (status = 200, description = "Transaction tags updated successfully.", body = GetTagsResponseViewModel),
+        UpdateResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+        ("transaction_id" = Uuid, Path, description = "Id of the transaction."),
+    ),
+    request_body(
+        content = SetTagsRequestViewModel,
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id, transaction_id = %transaction_id))]
+pub async fn set_transaction_tags(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    Path(TransactionIdPath { transaction_id }): Path<TransactionIdPath>,
+    TagServiceState(tag_service): TagServiceState,
+    ValidatedJson(body): ValidatedJson<SetTagsRequestViewModel>,
+) -> Result<Json<GetTagsResponseViewModel>, ApiError> {
+    let tags = tag_service
+        .set_transaction_tags(user_id, transaction_id, body.tag_ids)
+        .await?;
+
+    Ok(Json(GetTagsResponseViewModel {
+        tags: tags.into_iter().map_into().collect(),
+    }))
+}
+
+/// Get Transaction Group Tags
+///
+/// Lists the tags attached to a transaction group.
+#[utoipa::path(
+    get,
+    path = "/api/users/{user_id}/transactions/groups/{group_id}/tags",
+    tag = "Tags",
+    responses(
+        (status = 200, description = "Transaction group tags retrieved successfully.", body = GetTagsResponseViewModel),
+        GetResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+        ("group_id" = Uuid, Path, description = "Id of the transaction group."),
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id, group_id = %group_id))]
+pub async fn get_transaction_group_tags(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    Path(GroupIdPath { group_id }): Path<GroupIdPath>,
+    TagServiceState(tag_service): TagServiceState,
+) -> Result<Json<GetTagsResponseViewModel>, ApiError> {
+    let tags = tag_service
+        .get_transaction_group_tags(user_id, group_id)
+        .await?;
+
+    Ok(Json(GetTagsResponseViewModel {
+        tags: tags.into_iter().map_into().collect(),
+    }))
+}
+
+/// Set Transaction Group Tags
+///
+/// Replaces the tags attached to a transaction group. Every transaction in the group
+/// matches the group's tags when filtering. An empty list removes all of them.
+#[utoipa::path(
+    put,
+    path = "/api/users/{user_id}/transactions/groups/{group_id}/tags",
+    tag = "Tags",
+    responses(
+        (status = 200, description = "Transaction group tags updated successfully.", body = GetTagsResponseViewModel),
+        UpdateResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+        ("group_id" = Uuid, Path, description = "Id of the transaction group."),
+    ),
+    request_body(
+        content = SetTagsRequestViewModel,
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id, group_id = %group_id))]
+pub async fn set_transaction_group_tags(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    Path(GroupIdPath { group_id }): Path<GroupIdPath>,
+    TagServiceState(tag_service): TagServiceState,
+    ValidatedJson(body): ValidatedJson<SetTagsRequestViewModel>,
+) -> Result<Json<GetTagsResponseViewModel>, ApiError> {
+    let tags = tag_service
+        .set_transaction_group_tags(user_id, group_id, body.tag_ids)
+        .await?;
+
+    Ok(Json(GetTagsResponseViewModel {
+        tags: tags.into_iter().map_into().collect(),
+    }))
+}
@@ -0,0 +1,121 @@
+use axum::{extract::Path, http::StatusCode, Json};
+use itertools::Itertools;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::{
+    auth::AuthenticatedUserId,
+    errors::ApiError,
+    extractors::ValidatedJson,
+    states::TickerAliasServiceState,
+    view_models::{
+        errors::{CreateResponses, DeleteResponses, GetResponses},
+        ticker_aliases::{
+            base_models::TickerAliasViewModel,
+            create_ticker_alias::CreateTickerAliasResponseViewModel,
+            get_ticker_aliases::GetTickerAliasesResponseViewModel,
+        },
+    },
+};
+
+#[derive(Deserialize)]
+pub(crate) struct TickerAliasIdPath {
+    ticker_alias_id: Uuid,
+}
+
+/// Get Ticker Aliases
+///
+/// Lists the user's mappings from provider tickers to assets.
+#[utoipa::path(
+    get,
+    path = "/api/users/{user_id}/ticker-aliases",
+    tag = "Connectors",
+    responses(
+        (status = 200, description = "Ticker aliases retrieved successfully.", body = GetTickerAliasesResponseViewModel),
+        GetResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id))]
+pub async fn get_ticker_aliases(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    TickerAliasServiceState(ticker_alias_service): TickerAliasServiceState,
+) -> Result<Json<GetTickerAliasesResponseViewModel>, ApiError> {
+    let ticker_aliases = ticker_alias_service.get_ticker_aliases(user_id).await?;
+
+    Ok(Json(GetTickerAliasesResponseViewModel {
+        ticker_aliases: ticker_aliases.into_iter().map_into().collect(),
+    }))
+}
+
+/// Create Ticker Alias
+///
+/// Maps a provider ticker or currency code to an asset for connector imports. Bindings
+/// holding unresolved transactions for the ticker are re-projected so the transactions
+/// are imported right away.
+#[utoipa::path(
+    post,
+    path = "/api/users/{user_id}/ticker-aliases",
+    tag = "Connectors",
+    responses(
+        (status = 201, description = "Ticker alias created successfully.", body = CreateTickerAliasResponseViewModel),
+        CreateResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+    ),
+    request_body(
+        content = TickerAliasViewModel,
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id))]
+pub async fn create_ticker_alias(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    TickerAliasServiceState(ticker_alias_service): TickerAliasServiceState,
+    ValidatedJson(body): ValidatedJson<TickerAliasViewModel>,
+) -> Result<(StatusCode, Json<CreateTickerAliasResponseViewModel>), ApiError> {
+    let created = ticker_alias_service
+        .create_ticker_alias(user_id, body.to_business())
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(created.into())))
+}
+
+/// Delete Ticker Alias
+///
+/// Deletes a ticker alias. Transactions already imported through it are kept.
+#[utoipa::path(
+    delete,
+    path = "/api/users/{user_id}/ticker-aliases/{ticker_alias_id}",
+    tag = "Connectors",
+    responses(
+        (status = 200, description = "Ticker alias deleted successfully."),
+        DeleteResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+        ("ticker_alias_id" = Uuid, Path, description = "Id of the ticker alias to delete."),
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id, ticker_alias_id = %ticker_alias_id))]
+pub async fn delete_ticker_alias(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    Path(TickerAliasIdPath { ticker_alias_id }): Path<TickerAliasIdPath>,
+    TickerAliasServiceState(ticker_alias_service): TickerAliasServiceState,
+) -> Result<(), ApiError> {
+    ticker_alias_service
+        .delete_ticker_alias(user_id, ticker_alias_id)
+        .await?;
+    Ok(())
+}
@@ -0,0 +1,212 @@
+use axum::{extract::Path, http::StatusCode, Json};
+use itertools::Itertools;
+use serde::Deserialize;
+use uuid::Uuid;
+
+#[derive(Deserialize)]
+pub(crate) struct TransactionRuleIdPath {
+    rule_id: Uuid,
+}
+
+use crate::{
+    auth::AuthenticatedUserId,
+    errors::ApiError,
+    extractors::ValidatedJson,
+    states::TransactionRuleServiceState,
+    view_models::{
+        errors::{CreateResponses, DeleteResponses, GetResponses, UpdateResponses},
+        transaction_rules::{
+            base_models::{IdentifiableTransactionRuleViewModel, TransactionRuleViewModel},
+            get_transaction_rules::GetTransactionRulesResponseViewModel,
+        },
+    },
+};
+
+/// Get Transaction Rules
+///
+/// Lists the user's transaction rules in the order they run.
+#[utoipa::path(
+    get,
+    path = "/api/users/{user_id}/transaction-rules",
+    tag = "Transaction Rules",
+    responses(
+        (status = 200, description = "Transaction rules retrieved successfully.", body = GetTransactionRulesResponseViewModel),
+        GetResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id))]
+pub async fn get_transaction_rules(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    TransactionRuleServiceState(rule_service): TransactionRuleServiceState,
+) -> Result<Json<GetTransactionRulesResponseViewModel>, ApiError> {
+    let rules = rule_service.get_rules(user_id).await?;
+
+    Ok(Json(GetTransactionRulesResponseViewModel {
+        rules: rules.into_iter().map_into().collect(),
+    }))
+}
+
+/// Create Transaction Rule
+///
+/// Creates a rule that categorizes, tags, renames or hides matching transactions. Rules
+/// run on every connector import and, when asked to, on transactions entered by hand.
+#[utoipa::path(
+    post,
+    path = "/api/users/{user_id}/transaction-rules",
+    tag = "Transaction Rules",
+    responses(
+        (status = 201, description = "Transaction rule created successfully.", body = IdentifiableTransactionRuleViewModel),
+        CreateResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+    ),
+    request_body(
+        content = TransactionRuleViewModel,
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id))]
+pub async fn create_transaction_rule(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    TransactionRuleServiceState(rule_service): TransactionRuleServiceState,
+    ValidatedJson(body): ValidatedJson<TransactionRuleViewModel>,
+) -> Result<(StatusCode, Json<IdentifiableTransactionRuleViewModel>), ApiError> {
+    let rule = rule_service
+        .create_rule(user_id, body.to_business())
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(rule.into())))
+}
+
+/// Get Transaction Rule
+///
+/// Gets a specific transaction rule by ID.
+#[utoipa::path(
+    get,
+    path = "/api/users/{user_id}/transaction-rules/{rule_id}",
+    tag = "Transaction Rules",
+    responses(
+        (status = 200, description = "Transaction rule retrieved successfully.", body = IdentifiableTransactionRuleViewModel),
+        GetResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+        ("rule_id" = Uuid, Path, description = "Id of the rule to retrieve."),
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id, rule_id = %rule_id))]
+pub async fn get_transaction_rule(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    Path(TransactionRuleIdPath { rule_id }): Path<TransactionRuleIdPath>,
+    TransactionRuleServiceState(rule_service): TransactionRuleServiceState,
+) -> Result<Json<IdentifiableTransactionRuleViewModel>, ApiError> {
+    let rule = rule_service.get_rule(user_id, rule_id).await?;
+
+    Ok(Json(rule.into()))
+}
+
+/// Update Transaction Rule
+///
+/// Replaces the conditions, actions, priority and enabled flag of a rule. Transactions
+/// the rule already changed are left as they are.
+#[utoipa::path(
+    put,
+    path = "/api/users/{user_id}/transaction-rules/{rule_id}",
+    tag = "Transaction Rules",
+    responses(
+        (status = 200, description = "Transaction rule updated successfully.", body = IdentifiableTransactionRuleViewModel),
+        UpdateResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+        ("rule_id" = Uuid, Path, description = "Id of the rule to update."),
+    ),
+    request_body(
+        content = TransactionRuleViewModel,
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id, rule_id = %rule_id))]
+pub async fn update_transaction_rule(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    Path(TransactionRuleIdPath { rule_id }): Path<TransactionRuleIdPath>,
+    TransactionRuleServiceState(rule_service): TransactionRuleServiceState,
+    ValidatedJson(body): ValidatedJson<TransactionRuleViewModel>,
+) -> Result<Json<IdentifiableTransactionRuleViewModel>, ApiError> {
+    let rule = rule_service
+        .update_rule(user_id, rule_id, body.to_business())
+        .await?;
+
+    Ok(Json(rule.into()))
+}
+
+/// Delete Transaction Rule
+///
+/// Deletes a rule. Transactions it already changed are left as they are.
+#[utoipa::path(
+    delete,
+    path = "/api/users/{user_id}/transaction-rules/{rule_id}",
+    tag = "Transaction Rules",
+    responses(
+        (status = 200, description = "Transaction rule deleted successfully."),
+        DeleteResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+        ("rule_id" = Uuid, Path, description = "Id of the rule to delete."),
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id, rule_id = %rule_id))]
+pub async fn delete_transaction_rule(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    Path(TransactionRuleIdPath { rule_id }): Path<TransactionRuleIdPath>,
+    TransactionRuleServiceState(rule_service): TransactionRuleServiceState,
+) -> Result<(), ApiError> {
+    rule_service.delete_rule(user_id, rule_id).await?;
+    Ok(())
+}
+
+/// Apply Transaction Rules
+///
+/// Queues a run of the enabled rules over the user's existing transactions. Ghost
+/// transactions waiting for review keep their visibility.
+#[utoipa::path(
+    post,
+    path = "/api/users/{user_id}/transaction-rules/apply",
+    tag = "Transaction Rules",
+    responses(
+        (status = 202, description = "Rule application enqueued."),
+        CreateResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id))]
+pub async fn apply_transaction_rules(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    TransactionRuleServiceState(rule_service): TransactionRuleServiceState,
+) -> Result<StatusCode, ApiError> {
+    rule_service.enqueue_apply_to_history(user_id).await?;
+    Ok(StatusCode::ACCEPTED)
+}
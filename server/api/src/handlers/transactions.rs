@@ -26,6 +26,7 @@ use crate::{
     view_models::{
         base_models::search::{CombinedTransactionsPage, CursorOrPaginatedSearchQuery},
         errors::{DeleteResponses, GetResponses, UpdateResponses},
+        tags::base_models::TagFilterQuery,
         transactions::{
             base_models::metadata_lookup::MetadataLookupTables,
             delete_transactions::DeleteTransactionsRequestViewModel,
@@ -269,7 +270,8 @@ pub async fn set_transactions_visibility(
     ),
     params(
         ("user_id" = Uuid, Path, description = "User id for which the transaction belongs to."),
-        CursorOrPaginatedSearchQuery
+        CursorOrPaginatedSearchQuery,
+        TagFilterQuery
     ),
     security(
         ("auth_token" = [])
@@ -279,6 +281,7 @@ pub async fn set_transactions_visibility(
 pub async fn get_transactions(
     AuthenticatedUserId(user_id): AuthenticatedUserId,
     ValidatedQuery(query_params): ValidatedQuery<CursorOrPaginatedSearchQuery>,
+    ValidatedQuery(tag_filter): ValidatedQuery<TagFilterQuery>,
     TransactionManagementServiceState(service): TransactionManagementServiceState,
     AssetsServiceState(asset_service): AssetsServiceState,
     AccountsServiceState(accounts_service): AccountsServiceState,
@@ -287,7 +290,12 @@ pub async fn get_transactions(
     let pagination = PaginationModeDto::from(&query_params);
 
     let result = service
-        .get_combined_transactions(user_id, pagination, query_params.query)
+        .get_combined_transactions(
+            user_id,
+            pagination,
+            query_params.query,
+            tag_filter.tag_id.map(|tag_id| vec![tag_id]),
+        )
         .await?;
 
     let all_tx_refs: Vec<_> = result
@@ -0,0 +1,89 @@
+use axum::Json;
+
+use crate::{
+    auth::AuthenticatedUserId,
+    errors::ApiError,
+    extractors::{ValidatedJson, ValidatedQuery},
+    states::TransferMatchingServiceState,
+    view_models::errors::{GetResponses, UpdateResponses},
+    view_models::transfers::transfer_matches::{
+        ConfirmTransfersRequestViewModel, ConfirmTransfersResponseViewModel,
+        GetTransferMatchesQueryParams, GetTransferMatchesResponseViewModel,
+    },
+};
+
+/// Get Transfer Matches
+///
+/// Lists pairs of transactions in different accounts that look like the two sides of
+/// one transfer: money out of one account and the same amount into another within three
+/// days. When the currencies differ, the amounts must agree within 3% at the latest rate.
+/// Each transaction appears in at most one pair; closest amounts and dates pair first.
+#[utoipa::path(
+    get,
+    path = "/api/users/{user_id}/transfers/matches",
+    tag = "Transactions",
+    responses(
+        (status = 200, description = "Transfer matches retrieved successfully.", body = GetTransferMatchesResponseViewModel),
+        GetResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+        GetTransferMatchesQueryParams,
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id))]
+pub async fn get_transfer_matches(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    ValidatedQuery(query_params): ValidatedQuery<GetTransferMatchesQueryParams>,
+    TransferMatchingServiceState(transfer_service): TransferMatchingServiceState,
+) -> Result<Json<GetTransferMatchesResponseViewModel>, ApiError> {
+    let matches = transfer_service
+        .get_transfer_matches(user_id, query_params.from, query_params.to)
+        .await?;
+
+    Ok(Json(GetTransferMatchesResponseViewModel {
+        matches: matches.into_iter().map(Into::into).collect(),
+    }))
+}
+
+/// Confirm Transfers (bulk)
+///
+/// Replaces each pair with a single balance transfer between the two accounts, dated at
+/// the outgoing side. Pairs in different currencies become an exchange. Provider links of
+/// both sides move to the new transaction.
+#[utoipa::path(
+    post,
+    path = "/api/users/{user_id}/transfers/matches/confirm",
+    tag = "Transactions",
+    responses(
+        (status = 200, description = "Transfers created successfully.", body = ConfirmTransfersResponseViewModel),
+        UpdateResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+    ),
+    request_body(
+        content = ConfirmTransfersRequestViewModel,
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id))]
+pub async fn confirm_transfers(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    TransferMatchingServiceState(transfer_service): TransferMatchingServiceState,
+    ValidatedJson(body): ValidatedJson<ConfirmTransfersRequestViewModel>,
+) -> Result<Json<ConfirmTransfersResponseViewModel>, ApiError> {
+    let transaction_ids = transfer_service
+        .confirm_transfers(
+            user_id,
+            body.transfers.into_iter().map(Into::into).collect(),
+        )
+        .await?;
+
+    Ok(Json(ConfirmTransfersResponseViewModel { transaction_ids }))
+}
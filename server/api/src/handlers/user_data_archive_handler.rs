@@ -0,0 +1,103 @@
+use axum::{
+    body::{Body, Bytes},
+    http::header,
+    response::{IntoResponse, Response},
+    Json,
+};
+use business::dtos::user_data_archive_dto::UserDataArchiveDto;
+
+use crate::{
+    auth::AuthenticatedUserId,
+    errors::ApiError,
+    states::UserDataArchiveServiceState,
+    view_models::{
+        errors::{CreateResponses, GetResponses},
+        users::import_user_data_view_model::ImportUserDataResponseViewModel,
+    },
+};
+
+/// Export User Data
+///
+/// Downloads a versioned JSON archive of everything the user owns: accounts and their
+/// identifiers, custom assets, pairs and rates, custom categories and category types,
+/// transaction groups, transactions with their entries, descriptions and dividends, and
+/// connector bindings. Connector credentials are never included.
+#[utoipa::path(
+    get,
+    path = "/api/users/{user_id}/export",
+    tag = "Users",
+    responses(
+        (status = 200, description = "User data archive.", content(
+            (String = "application/json")
+        )),
+        GetResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id))]
+pub async fn export_user_data(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    UserDataArchiveServiceState(archive_service): UserDataArchiveServiceState,
+) -> Result<Response, ApiError> {
+    let archive = archive_service.export_user_data(user_id).await?;
+    let file_name = format!("sverto-export-{}.json", archive.exported_at.date());
+    let body = serde_json::to_vec(&archive).map_err(anyhow::Error::from)?;
+
+    Ok((
+        [
+            (header::CONTENT_TYPE, "application/json".to_string()),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{file_name}\""),
+            ),
+        ],
+        Body::from(body),
+    )
+        .into_response())
+}
+
+/// Import User Data
+///
+/// Recreates the contents of an archive produced by the export endpoint under this user,
+/// assigning new ids. The user must not have any accounts or transactions yet. Public
+/// assets and global categories are matched by ticker and name; the import is rejected
+/// if any of them are missing on this server. Connections that stored provider
+/// credentials have to be authorised again afterwards.
+#[utoipa::path(
+    post,
+    path = "/api/users/{user_id}/import",
+    tag = "Users",
+    responses(
+        (status = 200, description = "Archive imported successfully.", body = ImportUserDataResponseViewModel),
+        CreateResponses
+    ),
+    params(
+        ("user_id" = Uuid, Path, description = "Unique Identifier of the user."),
+    ),
+    request_body(
+        content = String,
+        content_type = "application/json",
+        description = "Archive produced by the export endpoint.",
+    ),
+    security(
+        ("auth_token" = [])
+    )
+)]
+#[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id))]
+pub async fn import_user_data(
+    AuthenticatedUserId(user_id): AuthenticatedUserId,
+    UserDataArchiveServiceState(archive_service): UserDataArchiveServiceState,
+    body: Bytes,
+) -> Result<Json<ImportUserDataResponseViewModel>, ApiError> {
+    let archive: UserDataArchiveDto = serde_json::from_slice(&body)
+        .map_err(|e| ApiError::BadRequest(format!("invalid archive: {e}")))?;
+
+    let summary = archive_service.import_user_data(user_id, archive).await?;
+
+    Ok(Json(summary.into()))
+}
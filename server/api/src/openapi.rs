@@ -64,6 +64,8 @@ mod modifiers;
         super::handlers::user_handler::post_user,
         super::handlers::user_handler::post_base_asset,
         super::handlers::user_handler::post_onboarding,
+        super::handlers::user_data_archive_handler::export_user_data,
+        super::handlers::user_data_archive_handler::import_user_data,
         super::handlers::user_asset_handler::delete_asset,
         super::handlers::user_asset_handler::delete_asset_pair_rates,
         super::handlers::user_asset_handler::delete_asset_pair,
@@ -99,6 +101,53 @@ mod modifiers;
         super::handlers::account_portfolio_handler::get_account_networth_history,
         super::handlers::account_portfolio_handler::get_account_transactions,
         super::handlers::account_portfolio_handler::get_account_portfolio_overview,
+        super::handlers::reconciliations_handler::get_reconciliations,
+        super::handlers::reconciliations_handler::create_reconciliation,
+        super::handlers::reconciliations_handler::get_reconciliation,
+        super::handlers::reconciliations_handler::delete_reconciliation,
+        super::handlers::reports_handler::get_capital_gains_report,
+        super::handlers::corporate_actions_handler::get_corporate_actions,
+        super::handlers::corporate_actions_handler::create_corporate_action,
+        super::handlers::corporate_actions_handler::get_corporate_action,
+        super::handlers::corporate_actions_handler::delete_corporate_action,
+        super::handlers::budgets_handler::get_budgets,
+        super::handlers::budgets_handler::create_budget,
+        super::handlers::budgets_handler::get_budget,
+        super::handlers::budgets_handler::update_budget,
+        super::handlers::budgets_handler::delete_budget,
+        super::handlers::budgets_handler::get_budget_actuals,
+        super::handlers::recurring_transactions_handler::get_recurring_transactions,
+        super::handlers::recurring_transactions_handler::create_recurring_transaction,
+        super::handlers::recurring_transactions_handler::get_recurring_transaction,
+        super::handlers::recurring_transactions_handler::update_recurring_transaction,
+        super::handlers::recurring_transactions_handler::delete_recurring_transaction,
+        super::handlers::spending_alerts_handler::get_spending_alerts,
+        super::handlers::spending_alerts_handler::get_spending_alert,
+        super::handlers::spending_alerts_handler::dismiss_spending_alert,
+        super::handlers::subscriptions_handler::get_subscriptions,
+        super::handlers::insights_handler::get_insight_digests,
+        super::handlers::insights_handler::get_insight_digest,
+        super::handlers::insights_handler::mark_insight_digest_read,
+        super::handlers::insights_handler::get_insight_digest_settings,
+        super::handlers::insights_handler::update_insight_digest_settings,
+        super::handlers::tags_handler::get_tags,
+        super::handlers::tags_handler::create_tag,
+        super::handlers::tags_handler::get_tag,
+        super::handlers::tags_handler::update_tag,
+        super::handlers::tags_handler::delete_tag,
+        super::handlers::tags_handler::get_transaction_tags,
+        super::handlers::tags_handler::set_transaction_tags,
+        super::handlers::tags_handler::get_transaction_group_tags,
+        super::handlers::tags_handler::set_transaction_group_tags,
+        super::handlers::ticker_aliases_handler::get_ticker_aliases,
+        super::handlers::ticker_aliases_handler::create_ticker_alias,
+        super::handlers::ticker_aliases_handler::delete_ticker_alias,
+        super::handlers::transaction_rules_handler::get_transaction_rules,
+        super::handlers::transaction_rules_handler::create_transaction_rule,
+        super::handlers::transaction_rules_handler::get_transaction_rule,
+        super::handlers::transaction_rules_handler::update_transaction_rule,
+        super::handlers::transaction_rules_handler::delete_transaction_rule,
+        super::handlers::transaction_rules_handler::apply_transaction_rules,
         super::handlers::category_handler::search_categories,
         super::handlers::category_handler::get_category_types,
         super::handlers::user_category_handler::get_categories,
@@ -128,6 +177,8 @@ mod modifiers;
         super::handlers::ai_quick_upload_handler::retry_quick_upload,
         super::handlers::ai_quick_upload_handler::complete,
         super::handlers::ai_usage_handler::get_usage,
+        super::handlers::ai_categorizer_handler::get_category_suggestions,
+        super::handlers::ai_categorizer_handler::suggest_categories,
         super::handlers::connectors_handler::create_connection,
         super::handlers::connectors_handler::list_connections,
         super::handlers::connectors_handler::revoke_connection,
@@ -139,10 +190,25 @@ mod modifiers;
         super::handlers::connectors_handler::list_bindings,
         super::handlers::connectors_handler::get_binding,
         super::handlers::connectors_handler::get_sync_checkpoint,
+        super::handlers::connectors_handler::get_import_issues,
+        super::handlers::connectors_handler::get_binding_balances,
+        super::handlers::connectors_handler::dismiss_import_issue,
+        super::handlers::connectors_handler::reproject_binding,
         super::handlers::connectors_handler::update_binding,
         super::handlers::connectors_handler::delete_binding,
         super::handlers::connectors_handler::sync_binding,
         super::handlers::connectors_handler::ingest_transactions,
+        super::handlers::connector_review_handler::get_review_inbox,
+        super::handlers::connector_review_handler::accept_review_transactions,
+        super::handlers::connector_review_handler::reject_review_transactions,
+        super::handlers::connector_review_handler::edit_and_accept_review_transactions,
+        super::handlers::connector_review_handler::merge_review_transactions,
+        super::handlers::transfers_handler::get_transfer_matches,
+        super::handlers::transfers_handler::confirm_transfers,
+        super::handlers::statement_imports_handler::import_statement,
+        super::handlers::statement_imports_handler::get_csv_mapping,
+        super::handlers::statement_imports_handler::save_csv_mapping,
+        super::handlers::statement_imports_handler::delete_csv_mapping,
         super::handlers::transactions::set_transaction_visibility,
         super::handlers::transactions::set_transactions_visibility,
     ),
@@ -5,6 +5,7 @@ use crate::{
     AppState,
 };
 use axum::{
+    extract::DefaultBodyLimit,
     http::{header, HeaderValue, Method},
     response::{Html, IntoResponse},
     routing::{delete, get, post, put},
@@ -20,6 +21,9 @@ use tower_http::trace::TraceLayer;
 use utoipa_rapidoc::RapiDoc;
 use utoipa_redoc::Redoc;
 
+/// User data archives are far larger than axum's default 2 MB body limit.
+const IMPORT_BODY_LIMIT: usize = 256 * 1024 * 1024;
+
 #[rustfmt::skip]
 pub(crate) fn create_router(state: AppState) -> Router {
     let user_routes = Router::new()
@@ -31,6 +35,10 @@ pub(crate) fn create_router(state: AppState) -> Router {
         .route("/transactions/{transaction_id}",                put(handlers::transactions::update_transaction))
         .route("/transactions/{transaction_id}",                delete(handlers::transactions::delete_transaction))
         .route("/transactions/{transaction_id}/visibility",     put(handlers::transactions::set_transaction_visibility))
+        .route("/transactions/{transaction_id}/tags",           get(handlers::tags_handler::get_transaction_tags)
+                                                                    .put(handlers::tags_handler::set_transaction_tags))
+        .route("/transactions/groups/{group_id}/tags",          get(handlers::tags_handler::get_transaction_group_tags)
+                                                                    .put(handlers::tags_handler::set_transaction_group_tags))
         .route("/transactions/visibility",                      put(handlers::transactions::set_transactions_visibility))
         .route("/transactions",                                 get(handlers::transactions::get_transactions)
                                                                     .delete(handlers::transactions::delete_transactions))
@@ -63,6 +71,9 @@ pub(crate) fn create_router(state: AppState) -> Router {
         .route("/assets/{asset_id}/{reference_id}/usermetadata", put(handlers::user_asset_handler::put_custom_asset_pair))
         .route("/base-asset",                                    post(handlers::user_handler::post_base_asset))
         .route("/onboarding",                                    post(handlers::user_handler::post_onboarding))
+        .route("/export",                                       get(handlers::user_data_archive_handler::export_user_data))
+        .route("/import",                                       post(handlers::user_data_archive_handler::import_user_data)
+                                                                .layer(DefaultBodyLimit::max(IMPORT_BODY_LIMIT)))
         .route("/accounts",                                     get(handlers::accounts_handler::get_accounts)
                                                                     .post(handlers::accounts_handler::add_account))
         .route("/accounts/{account_id}",                        get(handlers::accounts_handler::get_account)
@@ -71,10 +82,57 @@ pub(crate) fn create_router(state: AppState) -> Router {
         .route("/accounts/{account_id}/portfolio/history",      get(handlers::account_portfolio_handler::get_account_networth_history))
         .route("/accounts/{account_id}/portfolio/overview",     get(handlers::account_portfolio_handler::get_account_portfolio_overview))
         .route("/accounts/{account_id}/transactions",           get(handlers::account_portfolio_handler::get_account_transactions))
+        .route("/accounts/{account_id}/statement-csv-mapping",  get(handlers::statement_imports_handler::get_csv_mapping)
+                                                                    .put(handlers::statement_imports_handler::save_csv_mapping)
+                                                                    .delete(handlers::statement_imports_handler::delete_csv_mapping))
+        .route("/accounts/{account_id}/reconciliations",        get(handlers::reconciliations_handler::get_reconciliations)
+                                                                    .post(handlers::reconciliations_handler::create_reconciliation))
+        .route("/accounts/{account_id}/reconciliations/{reconciliation_id}", get(handlers::reconciliations_handler::get_reconciliation)
+                                                                    .delete(handlers::reconciliations_handler::delete_reconciliation))
         .route("/portfolio/overview",                           get(handlers::portfolio_handler::get_portfolio_overview))
         .route("/portfolio/assets/{asset_id}/overview",       get(handlers::portfolio_handler::get_portfolio_asset_overview))
         .route("/portfolio/holdings",                           get(handlers::portfolio_handler::get_holdings))
         .route("/portfolio/history",                            get(handlers::portfolio_handler::get_networth_history))
+        .route("/reports/capital-gains",                        get(handlers::reports_handler::get_capital_gains_report))
+        .route("/corporate-actions",                            get(handlers::corporate_actions_handler::get_corporate_actions)
+                                                                    .post(handlers::corporate_actions_handler::create_corporate_action))
+        .route("/corporate-actions/{corporate_action_id}",      get(handlers::corporate_actions_handler::get_corporate_action)
+                                                                    .delete(handlers::corporate_actions_handler::delete_corporate_action))
+        .route("/budgets",                                      get(handlers::budgets_handler::get_budgets)
+                                                                    .post(handlers::budgets_handler::create_budget))
+        .route("/budgets/actual",                               get(handlers::budgets_handler::get_budget_actuals))
+        .route("/budgets/{budget_id}",                          get(handlers::budgets_handler::get_budget)
+                                                                    .put(handlers::budgets_handler::update_budget)
+                                                                    .delete(handlers::budgets_handler::delete_budget))
+        .route("/recurring-transactions",                       get(handlers::recurring_transactions_handler::get_recurring_transactions)
+                                                                    .post(handlers::recurring_transactions_handler::create_recurring_transaction))
+        .route("/recurring-transactions/{recurring_transaction_id}", get(handlers::recurring_transactions_handler::get_recurring_transaction)
+                                                                    .put(handlers::recurring_transactions_handler::update_recurring_transaction)
+                                                                    .delete(handlers::recurring_transactions_handler::delete_recurring_transaction))
+        .route("/spending-alerts",                              get(handlers::spending_alerts_handler::get_spending_alerts))
+        .route("/spending-alerts/subscribe",                    get(handlers::spending_alerts_handler::subscribe))
+        .route("/spending-alerts/{alert_id}",                   get(handlers::spending_alerts_handler::get_spending_alert))
+        .route("/spending-alerts/{alert_id}/dismiss",           post(handlers::spending_alerts_handler::dismiss_spending_alert))
+        .route("/subscriptions",                                get(handlers::subscriptions_handler::get_subscriptions))
+        .route("/insights/digests",                             get(handlers::insights_handler::get_insight_digests))
+        .route("/insights/digests/{digest_id}",                 get(handlers::insights_handler::get_insight_digest))
+        .route("/insights/digests/{digest_id}/read",            post(handlers::insights_handler::mark_insight_digest_read))
+        .route("/insights/settings",                            get(handlers::insights_handler::get_insight_digest_settings)
+                                                                    .put(handlers::insights_handler::update_insight_digest_settings))
+        .route("/tags",                                         get(handlers::tags_handler::get_tags)
+                                                                    .post(handlers::tags_handler::create_tag))
+        .route("/tags/{tag_id}",                                get(handlers::tags_handler::get_tag)
+                                                                    .put(handlers::tags_handler::update_tag)
+                                                                    .delete(handlers::tags_handler::delete_tag))
+        .route("/ticker-aliases",                               get(handlers::ticker_aliases_handler::get_ticker_aliases)
+                                                                    .post(handlers::ticker_aliases_handler::create_ticker_alias))
+        .route("/ticker-aliases/{ticker_alias_id}",             delete(handlers::ticker_aliases_handler::delete_ticker_alias))
+        .route("/transaction-rules",                            get(handlers::transaction_rules_handler::get_transaction_rules)
+                                                                    .post(handlers::transaction_rules_handler::create_transaction_rule))
+        .route("/transaction-rules/apply",                      post(handlers::transaction_rules_handler::apply_transaction_rules))
+        .route("/transaction-rules/{rule_id}",                  get(handlers::transaction_rules_handler::get_transaction_rule)
+                                                                    .put(handlers::transaction_rules_handler::update_transaction_rule)
+                                                                    .delete(handlers::transaction_rules_handler::delete_transaction_rule))
         .route("/ai/conversations",                             post(handlers::ai_conversation_handler::create_conversation)
                                                                     .get(handlers::ai_conversation_handler::list_conversations))
         .route("/ai/conversations/{conversation_id}",          get(handlers::ai_conversation_handler::get_conversation)
@@ -83,6 +141,8 @@ pub(crate) fn create_router(state: AppState) -> Router {
                                                                     .post(handlers::ai_conversation_handler::send_message))
         .route("/ai/conversations/{conversation_id}/retry",    post(handlers::ai_conversation_handler::retry_message))
         .route("/ai/usage",                                    get(handlers::ai_usage_handler::get_usage))
+        .route("/ai/category-suggestions",                     get(handlers::ai_categorizer_handler::get_category_suggestions)
+                                                                    .post(handlers::ai_categorizer_handler::suggest_categories))
         .route("/ai/quick-upload",                             post(handlers::ai_quick_upload_handler::create_quick_upload)
                                                                     .get(handlers::ai_quick_upload_handler::list_quick_uploads))
         .route("/ai/quick-upload/{quick_upload_id}",           get(handlers::ai_quick_upload_handler::get_quick_upload))
@@ -110,7 +170,19 @@ pub(crate) fn create_router(state: AppState) -> Router {
                                                                     .delete(handlers::connectors_handler::delete_binding))
         .route("/connectors/bindings/{binding_id}/sync",         post(handlers::connectors_handler::sync_binding))
         .route("/connectors/bindings/{binding_id}/sync-checkpoint", get(handlers::connectors_handler::get_sync_checkpoint))
+        .route("/connectors/bindings/{binding_id}/import-issues", get(handlers::connectors_handler::get_import_issues))
+        .route("/connectors/bindings/{binding_id}/balances",     get(handlers::connectors_handler::get_binding_balances))
+        .route("/connectors/bindings/{binding_id}/import-issues/{issue_id}", delete(handlers::connectors_handler::dismiss_import_issue))
+        .route("/connectors/bindings/{binding_id}/reproject",    post(handlers::connectors_handler::reproject_binding))
         .route("/connectors/bindings/{binding_id}/ingest",       post(handlers::connectors_handler::ingest_transactions))
+        .route("/connectors/bindings/{binding_id}/statements",   post(handlers::statement_imports_handler::import_statement))
+        .route("/connectors/review-inbox",                       get(handlers::connector_review_handler::get_review_inbox))
+        .route("/connectors/review-inbox/accept",                post(handlers::connector_review_handler::accept_review_transactions))
+        .route("/connectors/review-inbox/reject",                post(handlers::connector_review_handler::reject_review_transactions))
+        .route("/connectors/review-inbox/edit",                  post(handlers::connector_review_handler::edit_and_accept_review_transactions))
+        .route("/connectors/review-inbox/merge",                 post(handlers::connector_review_handler::merge_review_transactions))
+        .route("/transfers/matches",                             get(handlers::transfers_handler::get_transfer_matches))
+        .route("/transfers/matches/confirm",                     post(handlers::transfers_handler::confirm_transfers))
 
         .layer(axum::middleware::from_fn(enforce_user_ownership));
 
@@ -78,10 +78,55 @@ service_state!(AiConversationService);
 use business::service_collection::ai_quick_upload_service::AiQuickUploadService;
 service_state!(AiQuickUploadService);
 
+use business::service_collection::budget_service::BudgetService;
+service_state!(BudgetService);
+
+use business::service_collection::reconciliation_service::ReconciliationService;
+service_state!(ReconciliationService);
+
+use business::service_collection::transfer_matching_service::TransferMatchingService;
+service_state!(TransferMatchingService);
+
+use business::service_collection::corporate_action_service::CorporateActionService;
+service_state!(CorporateActionService);
+
+use business::service_collection::recurring_transaction_service::RecurringTransactionService;
+service_state!(RecurringTransactionService);
+
+use business::service_collection::subscription_service::SubscriptionService;
+service_state!(SubscriptionService);
+
+use business::service_collection::tag_service::TagService;
+service_state!(TagService);
+
+use business::service_collection::ticker_alias_service::TickerAliasService;
+service_state!(TickerAliasService);
+
+use business::service_collection::transaction_rule_service::TransactionRuleService;
+service_state!(TransactionRuleService);
+
 use business::service_collection::ai_usage_service::AiUsageService;
 service_state!(AiUsageService);
 
+use business::service_collection::ai_categorizer_service::AiCategorizerService;
+service_state!(AiCategorizerService);
+
 use business::service_collection::connector_service::ConnectorService;
 service_state!(ConnectorService);
 use business::service_collection::connector_sync_service::ConnectorSyncService;
 service_state!(ConnectorSyncService);
+use business::service_collection::connector_balance_service::ConnectorBalanceService;
+service_state!(ConnectorBalanceService);
+use business::service_collection::connector_review_service::ConnectorReviewService;
+service_state!(ConnectorReviewService);
+use business::service_collection::statement_import_service::StatementImportService;
+service_state!(StatementImportService);
+
+use business::service_collection::user_data_archive_service::UserDataArchiveService;
+service_state!(UserDataArchiveService);
+
+use business::service_collection::insight_digest_service::InsightDigestService;
+service_state!(InsightDigestService);
+
+use business::service_collection::spending_alert_service::SpendingAlertService;
+service_state!(SpendingAlertService);
@@ -27,7 +27,10 @@ use crate::models::CreateTransactionInput;
 
 pub fn build_request_body(input: CreateTransactionInput) -> Result<String, ApiError> {
     let transaction = build_transaction(input)?;
-    let request = AddIndividualTransactionRequestViewModel { transaction };
+    let request = AddIndividualTransactionRequestViewModel {
+        transaction,
+        apply_rules: false,
+    };
     serde_json::to_string(&request).map_err(|e| ApiError::Parse {
         reason: e.to_string(),
     })
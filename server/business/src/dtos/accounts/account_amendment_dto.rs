@@ -1,11 +1,15 @@
 use rust_decimal::Decimal;
 
-use super::account_identifier_dto::AccountIdentifierDto;
+use super::{
+    account_identifier_dto::AccountIdentifierDto, cost_basis_method_dto::CostBasisMethodDto,
+};
 
 pub struct AccountAmendmentDto {
     pub account_type: i32,
     pub account_name: String,
     pub account_liquidity_type: i32,
     pub ownership_share: Decimal,
+    /// `None` keeps an existing account's method; new accounts default to FIFO.
+    pub cost_basis_method: Option<CostBasisMethodDto>,
     pub identifiers: Vec<AccountIdentifierDto>,
 }
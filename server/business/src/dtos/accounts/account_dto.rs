@@ -2,12 +2,15 @@ use dal::models::account_models::Account;
 use rust_decimal::Decimal;
 use uuid::Uuid;
 
+use super::cost_basis_method_dto::CostBasisMethodDto;
+
 pub struct AccountDto {
     pub id: Uuid,
     pub user_id: Uuid,
     pub account_name: String,
     pub account_type: i32,
     pub ownership_share: Decimal,
+    pub cost_basis_method: CostBasisMethodDto,
 }
 
 impl From<Account> for AccountDto {
@@ -18,6 +21,8 @@ impl From<Account> for AccountDto {
             account_name: account.account_name,
             account_type: account.account_type,
             ownership_share: account.ownership_share,
+            cost_basis_method: CostBasisMethodDto::from_db_str(&account.cost_basis_method)
+                .unwrap_or_default(),
         }
     }
 }
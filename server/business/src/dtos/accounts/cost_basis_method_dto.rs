@@ -0,0 +1,35 @@
+/// How sold or transferred-out units are matched against an account's open lots.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum CostBasisMethodDto {
+    /// Oldest lot is consumed first.
+    #[default]
+    Fifo,
+    /// Newest lot is consumed first.
+    Lifo,
+    /// Lot with the highest acquisition price is consumed first.
+    Hifo,
+    /// All open lots are consumed pro rata, so every disposal is priced at the
+    /// pooled average cost of the holding.
+    AverageCost,
+}
+
+impl CostBasisMethodDto {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Fifo => "fifo",
+            Self::Lifo => "lifo",
+            Self::Hifo => "hifo",
+            Self::AverageCost => "average_cost",
+        }
+    }
+
+    pub fn from_db_str(s: &str) -> Option<Self> {
+        match s {
+            "fifo" => Some(Self::Fifo),
+            "lifo" => Some(Self::Lifo),
+            "hifo" => Some(Self::Hifo),
+            "average_cost" => Some(Self::AverageCost),
+            _ => None,
+        }
+    }
+}
@@ -5,7 +5,7 @@ use uuid::Uuid;
 use super::{
     account_identifier_dto::AccountIdentifierDto,
     account_liquidity_type_dto::AccountLiquidityTypeDto, account_type_dto::AccountTypeDto,
-    suggested_currency_dto::SuggestedCurrencyDto,
+    cost_basis_method_dto::CostBasisMethodDto, suggested_currency_dto::SuggestedCurrencyDto,
 };
 
 pub struct FullAccountDto {
@@ -15,6 +15,7 @@ pub struct FullAccountDto {
     pub account_type: AccountTypeDto,
     pub liquidity_type: AccountLiquidityTypeDto,
     pub ownership_share: Decimal,
+    pub cost_basis_method: CostBasisMethodDto,
     pub identifiers: Vec<AccountIdentifierDto>,
     pub suggested_currency: Option<SuggestedCurrencyDto>,
 }
@@ -49,6 +50,8 @@ impl From<AccountWithMetadata> for FullAccountDto {
                 name: account.liquidity_type_name,
             },
             ownership_share: account.ownership_share,
+            cost_basis_method: CostBasisMethodDto::from_db_str(&account.cost_basis_method)
+                .unwrap_or_default(),
             identifiers: Vec::new(),
             suggested_currency,
         }
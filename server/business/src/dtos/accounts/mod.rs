@@ -3,5 +3,6 @@ pub mod account_dto;
 pub mod account_identifier_dto;
 pub mod account_liquidity_type_dto;
 pub mod account_type_dto;
+pub mod cost_basis_method_dto;
 pub mod full_account_dto;
 pub mod suggested_currency_dto;
@@ -0,0 +1,35 @@
+use rust_decimal::Decimal;
+use time::Date;
+
+use super::BudgetDto;
+
+/// Budgeted and actual amounts for a single budget period, in the user's
+/// default asset.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BudgetPeriodActualDto {
+    pub start_date: Date,
+    /// Last day of the period, inclusive.
+    pub end_date: Date,
+    pub budgeted: Decimal,
+    /// Unspent amount brought forward from the previous period. Always zero
+    /// for budgets without rollover.
+    pub carried_over: Decimal,
+    /// Net outflow booked against the budget; refunds reduce it.
+    pub spent: Decimal,
+}
+
+impl BudgetPeriodActualDto {
+    pub fn available(&self) -> Decimal {
+        self.budgeted + self.carried_over
+    }
+
+    pub fn remaining(&self) -> Decimal {
+        self.available() - self.spent
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct BudgetActualDto {
+    pub budget: BudgetDto,
+    pub period: BudgetPeriodActualDto,
+}
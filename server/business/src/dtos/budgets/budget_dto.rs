@@ -0,0 +1,124 @@
+use dal::models::budget_models::{AddBudgetModel, BudgetRow, UpdateBudgetModel};
+use rust_decimal::Decimal;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BudgetPeriodDto {
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+impl BudgetPeriodDto {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Weekly => "weekly",
+            Self::Monthly => "monthly",
+            Self::Yearly => "yearly",
+        }
+    }
+
+    pub fn from_db_str(s: &str) -> Option<Self> {
+        match s {
+            "weekly" => Some(Self::Weekly),
+            "monthly" => Some(Self::Monthly),
+            "yearly" => Some(Self::Yearly),
+            _ => None,
+        }
+    }
+}
+
+/// What a budget is tracked against: a single category, or every category
+/// belonging to a category type.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BudgetTargetDto {
+    Category(i32),
+    CategoryType(i32),
+}
+
+impl BudgetTargetDto {
+    pub fn category_id(&self) -> Option<i32> {
+        match self {
+            Self::Category(id) => Some(*id),
+            Self::CategoryType(_) => None,
+        }
+    }
+
+    pub fn category_type_id(&self) -> Option<i32> {
+        match self {
+            Self::Category(_) => None,
+            Self::CategoryType(id) => Some(*id),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct BudgetDto {
+    pub id: Uuid,
+    pub target: BudgetTargetDto,
+    /// Limit per period, denominated in the user's default asset.
+    pub amount: Decimal,
+    pub period: BudgetPeriodDto,
+    pub rollover: bool,
+    pub start_date: OffsetDateTime,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+impl From<BudgetRow> for BudgetDto {
+    fn from(row: BudgetRow) -> Self {
+        // The table constraint guarantees exactly one of the two ids is set.
+        let target = match row.category_id {
+            Some(id) => BudgetTargetDto::Category(id),
+            None => BudgetTargetDto::CategoryType(row.category_type_id.unwrap_or_default()),
+        };
+
+        Self {
+            id: row.id,
+            target,
+            amount: row.amount,
+            period: BudgetPeriodDto::from_db_str(&row.period).unwrap_or(BudgetPeriodDto::Monthly),
+            rollover: row.rollover,
+            start_date: row.start_date,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AddUpdateBudgetDto {
+    pub target: BudgetTargetDto,
+    pub amount: Decimal,
+    pub period: BudgetPeriodDto,
+    pub rollover: bool,
+    pub start_date: OffsetDateTime,
+}
+
+impl AddUpdateBudgetDto {
+    pub fn into_add_model(self, user_id: Uuid) -> AddBudgetModel {
+        AddBudgetModel {
+            user_id,
+            category_id: self.target.category_id(),
+            category_type_id: self.target.category_type_id(),
+            amount: self.amount,
+            period: self.period.as_str().to_string(),
+            rollover: self.rollover,
+            start_date: self.start_date,
+        }
+    }
+}
+
+impl From<AddUpdateBudgetDto> for UpdateBudgetModel {
+    fn from(dto: AddUpdateBudgetDto) -> Self {
+        Self {
+            category_id: dto.target.category_id(),
+            category_type_id: dto.target.category_type_id(),
+            amount: dto.amount,
+            period: dto.period.as_str().to_string(),
+            rollover: dto.rollover,
+            start_date: dto.start_date,
+        }
+    }
+}
@@ -0,0 +1,5 @@
+pub mod budget_actual_dto;
+pub mod budget_dto;
+
+pub use budget_actual_dto::*;
+pub use budget_dto::*;
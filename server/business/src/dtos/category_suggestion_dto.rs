@@ -0,0 +1,30 @@
+use dal::models::ai_models::AiCategorySuggestionModel;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// The category the categorizer suggests for a transaction still in the import category.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CategorySuggestionDto {
+    pub transaction_id: Uuid,
+    pub category_id: i32,
+    /// From 0 to 1.
+    pub confidence: f32,
+    pub reason: Option<String>,
+    /// The completion model that made the suggestion, or the embedding model when it
+    /// came from similar categorised transactions.
+    pub model: String,
+    pub created_at: OffsetDateTime,
+}
+
+impl From<AiCategorySuggestionModel> for CategorySuggestionDto {
+    fn from(model: AiCategorySuggestionModel) -> Self {
+        Self {
+            transaction_id: model.transaction_id,
+            category_id: model.category_id,
+            confidence: model.confidence,
+            reason: model.reason,
+            model: model.model,
+            created_at: model.created_at,
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use dal::models::connector_models::{
+    AddConnectorBalanceSnapshotModel, ConnectorBalanceSnapshotRow,
+};
+use rust_decimal::Decimal;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ProviderBalanceKindDto {
+    Cash,
+    Holding,
+}
+
+impl ProviderBalanceKindDto {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Cash => "cash",
+            Self::Holding => "holding",
+        }
+    }
+
+    pub fn from_db_str(s: &str) -> Option<Self> {
+        match s {
+            "cash" => Some(Self::Cash),
+            "holding" => Some(Self::Holding),
+            _ => None,
+        }
+    }
+}
+
+/// One currency or instrument of a provider-reported balance. The identifier is the
+/// provider's currency code or instrument ticker; the asset is what it resolved to when
+/// the balance was fetched, if anything.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProviderBalanceLineDto {
+    pub kind: ProviderBalanceKindDto,
+    pub asset_identifier: String,
+    pub asset_id: Option<i32>,
+    pub quantity: Decimal,
+}
+
+impl ProviderBalanceLineDto {
+    pub fn into_add_model(
+        self,
+        provider_account_ref: Uuid,
+        fetched_at: OffsetDateTime,
+    ) -> AddConnectorBalanceSnapshotModel {
+        AddConnectorBalanceSnapshotModel {
+            provider_account_id: provider_account_ref,
+            fetched_at,
+            kind: self.kind.as_str().to_string(),
+            asset_identifier: self.asset_identifier,
+            asset_id: self.asset_id,
+            quantity: self.quantity,
+        }
+    }
+}
+
+/// Everything a provider account reported in one balance fetch.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProviderBalanceSnapshotDto {
+    pub provider_account_ref: Uuid,
+    pub fetched_at: OffsetDateTime,
+    pub lines: Vec<ProviderBalanceLineDto>,
+}
+
+impl ProviderBalanceSnapshotDto {
+    /// Groups rows ordered by fetch time into one snapshot per provider account and fetch.
+    pub fn from_rows(rows: Vec<ConnectorBalanceSnapshotRow>) -> anyhow::Result<Vec<Self>> {
+        let mut snapshots: Vec<Self> = Vec::new();
+        for row in rows {
+            let kind = ProviderBalanceKindDto::from_db_str(&row.kind)
+                .ok_or_else(|| anyhow::anyhow!("unknown balance snapshot kind {}", row.kind))?;
+            let line = ProviderBalanceLineDto {
+                kind,
+                asset_identifier: row.asset_identifier,
+                asset_id: row.asset_id,
+                quantity: row.quantity,
+            };
+            match snapshots.last_mut() {
+                Some(snapshot)
+                    if snapshot.provider_account_ref == row.provider_account_id
+                        && snapshot.fetched_at == row.fetched_at =>
+                {
+                    snapshot.lines.push(line)
+                }
+                _ => snapshots.push(Self {
+                    provider_account_ref: row.provider_account_id,
+                    fetched_at: row.fetched_at,
+                    lines: vec![line],
+                }),
+            }
+        }
+        Ok(snapshots)
+    }
+}
@@ -0,0 +1,75 @@
+use dal::models::connector_models::ConnectorImportIssueRow;
+use rust_decimal::Decimal;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImportIssueStageDto {
+    /// The provider mapper did not turn the item into a transaction (fees, FX, non-cash
+    /// rows and the like). Only the reason is known.
+    Skipped,
+    /// The item mapped to a transaction but its currency or instrument has no asset.
+    Unresolved,
+}
+
+impl ImportIssueStageDto {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Skipped => "skipped",
+            Self::Unresolved => "unresolved",
+        }
+    }
+
+    pub fn from_db_str(s: &str) -> Option<Self> {
+        match s {
+            "skipped" => Some(Self::Skipped),
+            "unresolved" => Some(Self::Unresolved),
+            _ => None,
+        }
+    }
+}
+
+/// A provider item a projection could not import, kept until it is imported or dismissed.
+#[derive(Clone, Debug)]
+pub struct ConnectorImportIssueDto {
+    pub id: Uuid,
+    pub binding_id: Uuid,
+    /// Archived page the item was last seen on.
+    pub page_id: Uuid,
+    pub external_id: String,
+    pub stage: ImportIssueStageDto,
+    pub reason: String,
+    pub date_transacted: Option<OffsetDateTime>,
+    pub amount: Option<Decimal>,
+    pub currency: Option<String>,
+    pub description: Option<String>,
+    pub asset_identifier: Option<String>,
+    pub quantity: Option<Decimal>,
+    pub first_seen_at: OffsetDateTime,
+    pub last_seen_at: OffsetDateTime,
+}
+
+impl TryFrom<ConnectorImportIssueRow> for ConnectorImportIssueDto {
+    type Error = anyhow::Error;
+
+    fn try_from(row: ConnectorImportIssueRow) -> Result<Self, Self::Error> {
+        let stage = ImportIssueStageDto::from_db_str(&row.stage)
+            .ok_or_else(|| anyhow::anyhow!("unknown import issue stage {}", row.stage))?;
+        Ok(Self {
+            id: row.id,
+            binding_id: row.binding_id,
+            page_id: row.page_id,
+            external_id: row.external_id,
+            stage,
+            reason: row.reason,
+            date_transacted: row.date_transacted,
+            amount: row.amount,
+            currency: row.currency,
+            description: row.description,
+            asset_identifier: row.asset_identifier,
+            quantity: row.quantity,
+            first_seen_at: row.first_seen_at,
+            last_seen_at: row.last_seen_at,
+        })
+    }
+}
@@ -0,0 +1,68 @@
+use dal::models::connector_models::{ConnectorDuplicateCandidateRow, ReviewSuggestionRow};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::dtos::transaction_dto::TransactionDto;
+
+/// What the user's closest reviewed transactions and the category and asset embeddings
+/// suggest for a ghost transaction. Empty until its description has been embedded.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ReviewSuggestionDto {
+    /// The reviewed transaction the suggestions are borrowed from.
+    pub similar_transaction_id: Option<Uuid>,
+    pub category_id: Option<i32>,
+    pub group_id: Option<Uuid>,
+    pub asset_id: Option<i32>,
+}
+
+impl From<ReviewSuggestionRow> for ReviewSuggestionDto {
+    fn from(row: ReviewSuggestionRow) -> Self {
+        Self {
+            similar_transaction_id: row.similar_transaction_id,
+            // How the user categorised something alike beats the nearest category name.
+            category_id: row.similar_category_id.or(row.nearest_category_id),
+            group_id: row.similar_group_id,
+            asset_id: row.nearest_asset_id,
+        }
+    }
+}
+
+/// A ghost transaction waiting in the review inbox.
+#[derive(Clone, Debug)]
+pub struct PendingReviewTransactionDto {
+    pub binding_id: Uuid,
+    pub external_id: String,
+    pub imported_at: OffsetDateTime,
+    pub transaction: TransactionDto,
+    pub suggestions: ReviewSuggestionDto,
+    /// Manual entries the import may duplicate, best first.
+    pub duplicate_candidates: Vec<DuplicateCandidateDto>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct DuplicateCandidateDto {
+    pub transaction_id: Uuid,
+    pub score: f32,
+}
+
+impl From<ConnectorDuplicateCandidateRow> for DuplicateCandidateDto {
+    fn from(row: ConnectorDuplicateCandidateRow) -> Self {
+        Self {
+            transaction_id: row.candidate_transaction_id,
+            score: row.score,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ReviewEditDto {
+    pub transaction_id: Uuid,
+    pub transaction: TransactionDto,
+}
+
+/// Folds a ghost transaction into a transaction the user entered by hand.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ReviewMergeDto {
+    pub ghost_transaction_id: Uuid,
+    pub manual_transaction_id: Uuid,
+}
@@ -5,7 +5,16 @@ pub struct SyncReportDto {
     pub amended: usize,
     pub conflicts: usize,
     pub unresolved: usize,
+    pub skipped: usize,
     pub duplicates: usize,
+    /// Imports linked to a transaction the user had already entered instead of imported.
+    pub linked_existing: usize,
+    /// Imports held back for review because they may duplicate a manual entry.
+    pub possible_duplicates: usize,
+    /// Imports folded into a balance transfer with the other side of the transfer.
+    pub transfers_matched: usize,
+    /// Imports at least one of the user's transaction rules matched.
+    pub rules_applied: usize,
     pub pages_projected: usize,
 }
 
@@ -1,16 +1,24 @@
+pub mod connector_balance_snapshot_dto;
 pub mod connector_binding_dto;
 pub mod connector_connection_dto;
+pub mod connector_import_issue_dto;
+pub mod connector_review_dto;
 pub mod connector_sync_dto;
 pub mod oauth_session_dto;
 pub mod provider_account_dto;
 pub mod provider_account_transaction_dto;
+pub mod statement_import_dto;
 
+pub use connector_balance_snapshot_dto::*;
 pub use connector_binding_dto::*;
 pub use connector_connection_dto::*;
+pub use connector_import_issue_dto::*;
+pub use connector_review_dto::*;
 pub use connector_sync_dto::*;
 pub use oauth_session_dto::*;
 pub use provider_account_dto::*;
 pub use provider_account_transaction_dto::*;
+pub use statement_import_dto::*;
 
 pub fn is_supported_provider(kind: &str) -> bool {
     kind.parse::<connectors::provider::ProviderKind>().is_ok()
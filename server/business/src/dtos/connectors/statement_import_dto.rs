@@ -0,0 +1,100 @@
+use connectors::statement_file::{CsvAmountColumns, CsvColumnMapping, StatementFormat};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatementFormatDto {
+    Csv,
+    Ofx,
+    Qif,
+    Camt053,
+}
+
+impl From<StatementFormatDto> for StatementFormat {
+    fn from(format: StatementFormatDto) -> Self {
+        match format {
+            StatementFormatDto::Csv => StatementFormat::Csv,
+            StatementFormatDto::Ofx => StatementFormat::Ofx,
+            StatementFormatDto::Qif => StatementFormat::Qif,
+            StatementFormatDto::Camt053 => StatementFormat::Camt053,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CsvAmountColumnsDto {
+    Signed { column: usize, negate: bool },
+    DebitCredit { debit: usize, credit: usize },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CsvColumnMappingDto {
+    pub delimiter: char,
+    pub skip_rows: usize,
+    pub date_column: usize,
+    pub date_format: String,
+    pub description_columns: Vec<usize>,
+    pub amount: CsvAmountColumnsDto,
+    pub decimal_separator: char,
+    pub currency_column: Option<usize>,
+    pub reference_column: Option<usize>,
+}
+
+impl From<CsvColumnMappingDto> for CsvColumnMapping {
+    fn from(dto: CsvColumnMappingDto) -> Self {
+        Self {
+            delimiter: dto.delimiter,
+            skip_rows: dto.skip_rows,
+            date_column: dto.date_column,
+            date_format: dto.date_format,
+            description_columns: dto.description_columns,
+            amount: match dto.amount {
+                CsvAmountColumnsDto::Signed { column, negate } => {
+                    CsvAmountColumns::Signed { column, negate }
+                }
+                CsvAmountColumnsDto::DebitCredit { debit, credit } => {
+                    CsvAmountColumns::DebitCredit { debit, credit }
+                }
+            },
+            decimal_separator: dto.decimal_separator,
+            currency_column: dto.currency_column,
+            reference_column: dto.reference_column,
+        }
+    }
+}
+
+impl From<CsvColumnMapping> for CsvColumnMappingDto {
+    fn from(mapping: CsvColumnMapping) -> Self {
+        Self {
+            delimiter: mapping.delimiter,
+            skip_rows: mapping.skip_rows,
+            date_column: mapping.date_column,
+            date_format: mapping.date_format,
+            description_columns: mapping.description_columns,
+            amount: match mapping.amount {
+                CsvAmountColumns::Signed { column, negate } => {
+                    CsvAmountColumnsDto::Signed { column, negate }
+                }
+                CsvAmountColumns::DebitCredit { debit, credit } => {
+                    CsvAmountColumnsDto::DebitCredit { debit, credit }
+                }
+            },
+            decimal_separator: mapping.decimal_separator,
+            currency_column: mapping.currency_column,
+            reference_column: mapping.reference_column,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ImportStatementDto {
+    pub format: StatementFormatDto,
+    pub content: String,
+    /// Currency code for lines whose file does not state one. Defaults to the user's
+    /// default currency.
+    pub currency: Option<String>,
+    /// Overrides the account's saved mapping for this upload.
+    pub csv_mapping: Option<CsvColumnMappingDto>,
+    /// Saves `csv_mapping` as the account's mapping for later uploads.
+    pub save_csv_mapping: bool,
+    /// QIF only: dates are written day first rather than month first.
+    pub qif_day_first: bool,
+}
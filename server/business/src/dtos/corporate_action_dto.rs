@@ -0,0 +1,113 @@
+use dal::models::corporate_action_models::{AddCorporateActionModel, CorporateActionRow};
+use rust_decimal::Decimal;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CorporateActionTypeDto {
+    /// Every `ratio_from` units become `ratio_to` units, with `ratio_to > ratio_from`.
+    Split,
+    /// Every `ratio_from` units become `ratio_to` units, with `ratio_to < ratio_from`.
+    ReverseSplit,
+    /// Holders keep their units and receive `ratio_to` units of the new asset for every
+    /// `ratio_from` units held. Part of the cost basis moves to the new asset.
+    SpinOff,
+    /// The asset is replaced by the new asset, `ratio_from` old units becoming `ratio_to`
+    /// new ones. A plain rename uses a 1:1 ratio.
+    TickerChange,
+}
+
+impl CorporateActionTypeDto {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Split => "split",
+            Self::ReverseSplit => "reverse_split",
+            Self::SpinOff => "spin_off",
+            Self::TickerChange => "ticker_change",
+        }
+    }
+
+    pub fn from_db_str(s: &str) -> Option<Self> {
+        match s {
+            "split" => Some(Self::Split),
+            "reverse_split" => Some(Self::ReverseSplit),
+            "spin_off" => Some(Self::SpinOff),
+            "ticker_change" => Some(Self::TickerChange),
+            _ => None,
+        }
+    }
+
+    /// Whether the action involves a second asset.
+    pub fn has_new_asset(&self) -> bool {
+        matches!(self, Self::SpinOff | Self::TickerChange)
+    }
+}
+
+/// An event changing the units of an asset for everyone holding it. It applies to every
+/// account holding the asset before `effective_date`.
+#[derive(Clone, Debug)]
+pub struct CorporateActionDto {
+    pub id: Uuid,
+    pub asset_id: i32,
+    pub action_type: CorporateActionTypeDto,
+    pub effective_date: OffsetDateTime,
+    pub ratio_from: Decimal,
+    pub ratio_to: Decimal,
+    pub new_asset_id: Option<i32>,
+    /// Share of the original cost basis moved to the spun-off asset.
+    pub cost_basis_fraction: Option<Decimal>,
+    pub created_at: OffsetDateTime,
+}
+
+impl CorporateActionDto {
+    /// Units after the action per unit held before it.
+    pub fn ratio(&self) -> Decimal {
+        self.ratio_to / self.ratio_from
+    }
+}
+
+impl TryFrom<CorporateActionRow> for CorporateActionDto {
+    type Error = anyhow::Error;
+
+    fn try_from(row: CorporateActionRow) -> Result<Self, Self::Error> {
+        let action_type = CorporateActionTypeDto::from_db_str(&row.action_type)
+            .ok_or_else(|| anyhow::anyhow!("unknown corporate action type {}", row.action_type))?;
+        Ok(Self {
+            id: row.id,
+            asset_id: row.asset_id,
+            action_type,
+            effective_date: row.effective_date,
+            ratio_from: row.ratio_from,
+            ratio_to: row.ratio_to,
+            new_asset_id: row.new_asset_id,
+            cost_basis_fraction: row.cost_basis_fraction,
+            created_at: row.created_at,
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AddCorporateActionDto {
+    pub asset_id: i32,
+    pub action_type: CorporateActionTypeDto,
+    pub effective_date: OffsetDateTime,
+    pub ratio_from: Decimal,
+    pub ratio_to: Decimal,
+    pub new_asset_id: Option<i32>,
+    pub cost_basis_fraction: Option<Decimal>,
+}
+
+impl AddCorporateActionDto {
+    pub fn into_add_model(self, user_id: Uuid) -> AddCorporateActionModel {
+        AddCorporateActionModel {
+            user_id,
+            asset_id: self.asset_id,
+            action_type: self.action_type.as_str().to_string(),
+            effective_date: self.effective_date,
+            ratio_from: self.ratio_from,
+            ratio_to: self.ratio_to,
+            new_asset_id: self.new_asset_id,
+            cost_basis_fraction: self.cost_basis_fraction,
+        }
+    }
+}
@@ -0,0 +1,6 @@
+/// A vector and the embedding model that produced it.
+#[derive(Debug, Clone)]
+pub struct EmbeddingDto {
+    pub model: String,
+    pub vector: Vec<f32>,
+}
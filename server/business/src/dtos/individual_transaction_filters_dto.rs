@@ -8,4 +8,6 @@ pub struct IndividualTransactionFiltersDto {
     pub transaction_type_ids: Option<Vec<i32>>,
     pub date_from: Option<OffsetDateTime>,
     pub date_to: Option<OffsetDateTime>,
+    /// Matches transactions carrying any of these tags, directly or through their group.
+    pub tag_ids: Option<Vec<Uuid>>,
 }
@@ -0,0 +1,95 @@
+use dal::models::insight_digest_models::{
+    InsightDigestCandidateRow, InsightDigestRow, InsightDigestSettingsRow,
+};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InsightPeriodDto {
+    Weekly,
+    Monthly,
+}
+
+impl InsightPeriodDto {
+    pub const ALL: [Self; 2] = [Self::Weekly, Self::Monthly];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Weekly => "weekly",
+            Self::Monthly => "monthly",
+        }
+    }
+
+    pub fn from_db_str(s: &str) -> Option<Self> {
+        match s {
+            "weekly" => Some(Self::Weekly),
+            "monthly" => Some(Self::Monthly),
+            _ => None,
+        }
+    }
+}
+
+/// A stored weekly or monthly digest. `period_end` is exclusive.
+#[derive(Clone, Debug)]
+pub struct InsightDigestDto {
+    pub id: Uuid,
+    pub period: InsightPeriodDto,
+    pub period_start: OffsetDateTime,
+    pub period_end: OffsetDateTime,
+    pub reference_asset_id: i32,
+    /// The digest figures, as serialized from `ai::models::insights::InsightDigest`.
+    pub content: serde_json::Value,
+    pub narrative: Option<String>,
+    pub model: Option<String>,
+    pub read_at: Option<OffsetDateTime>,
+    pub created_at: OffsetDateTime,
+}
+
+impl From<InsightDigestRow> for InsightDigestDto {
+    fn from(row: InsightDigestRow) -> Self {
+        Self {
+            id: row.id,
+            period: InsightPeriodDto::from_db_str(&row.period).unwrap_or(InsightPeriodDto::Weekly),
+            period_start: row.period_start,
+            period_end: row.period_end,
+            reference_asset_id: row.reference_asset_id,
+            content: row.content.0,
+            narrative: row.narrative,
+            model: row.model,
+            read_at: row.read_at,
+            created_at: row.created_at,
+        }
+    }
+}
+
+/// A user due a digest, with the asset their digest is reported in.
+#[derive(Clone, Debug)]
+pub struct InsightDigestCandidateDto {
+    pub user_id: Uuid,
+    pub reference_asset_id: i32,
+    /// Whether the user opted in to Myra narrating their digests.
+    pub narrate: bool,
+}
+
+impl From<InsightDigestCandidateRow> for InsightDigestCandidateDto {
+    fn from(row: InsightDigestCandidateRow) -> Self {
+        Self {
+            user_id: row.user_id,
+            reference_asset_id: row.reference_asset_id,
+            narrate: row.narrate,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct InsightDigestSettingsDto {
+    pub narrate: bool,
+}
+
+impl From<InsightDigestSettingsRow> for InsightDigestSettingsDto {
+    fn from(row: InsightDigestSettingsRow) -> Self {
+        Self {
+            narrate: row.narrate,
+        }
+    }
+}
@@ -26,15 +26,20 @@ pub mod assets;
 pub mod auth_dto;
 pub mod bad_gateway_error_dto;
 pub mod bad_request_error_dto;
+pub mod budgets;
 pub mod categories;
+pub mod category_suggestion_dto;
 pub mod combined_transaction_dto;
 pub mod conflict_error_dto;
 pub mod connectors;
+pub mod corporate_action_dto;
+pub mod embedding_dto;
 pub mod entry_dto;
 pub mod fee_entry_dto;
 pub mod fee_entry_types_dto;
 pub mod file_dto;
 pub mod individual_transaction_filters_dto;
+pub mod insight_digest_dto;
 pub mod net_worth;
 pub mod not_found_error_dto;
 pub mod page_of_results_dto;
@@ -42,9 +47,18 @@ pub mod paging_dto;
 pub mod portfolio;
 
 pub mod rate_limit_error_dto;
+pub mod reconciliation_dto;
+pub mod recurring_transactions;
 pub mod service_unavailable_error_dto;
+pub mod spending_alert_dto;
+pub mod subscription_dto;
+pub mod tag_dto;
+pub mod ticker_alias_dto;
 pub mod transaction_dto;
 pub mod transaction_group_dto;
+pub mod transaction_rule_dto;
+pub mod transfer_match_dto;
+pub mod user_data_archive_dto;
 pub mod user_full_dto;
 pub mod user_role_dto;
 pub mod validation_error_dto;
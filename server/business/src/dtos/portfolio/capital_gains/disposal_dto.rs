@@ -0,0 +1,52 @@
+use rust_decimal::Decimal;
+use time::Date;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShareMatchingRuleDto {
+    SameDay,
+    BedAndBreakfast,
+    Section104,
+}
+
+impl ShareMatchingRuleDto {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ShareMatchingRuleDto::SameDay => "same_day",
+            ShareMatchingRuleDto::BedAndBreakfast => "bed_and_breakfast",
+            ShareMatchingRuleDto::Section104 => "section_104",
+        }
+    }
+}
+
+/// Part of a disposal matched against acquisitions under a single rule.
+/// Section 104 matches draw from the pool and have no single acquisition date.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DisposalMatchDto {
+    pub rule: ShareMatchingRuleDto,
+    pub acquisition_date: Option<Date>,
+    pub quantity: Decimal,
+    pub cost: Decimal,
+}
+
+/// All disposals of one asset on one day, treated as a single disposal as
+/// HMRC requires. Amounts are in the report's reference asset.
+#[derive(Clone, Debug)]
+pub struct CapitalGainsDisposalDto {
+    pub asset_id: i32,
+    pub date: Date,
+    pub quantity: Decimal,
+    pub proceeds: Decimal,
+    pub fees: Decimal,
+    pub cost_basis: Decimal,
+    pub matches: Vec<DisposalMatchDto>,
+}
+
+impl CapitalGainsDisposalDto {
+    pub fn allowable_cost(&self) -> Decimal {
+        self.cost_basis + self.fees
+    }
+
+    pub fn gain(&self) -> Decimal {
+        self.proceeds - self.allowable_cost()
+    }
+}
@@ -0,0 +1,11 @@
+use rust_decimal::Decimal;
+use time::Date;
+
+/// A dividend payment, or the withholding tax taken from it, in the report's
+/// reference asset.
+#[derive(Clone, Debug)]
+pub struct DividendIncomeDto {
+    pub date: Date,
+    pub dividends: Decimal,
+    pub withholding_tax: Decimal,
+}
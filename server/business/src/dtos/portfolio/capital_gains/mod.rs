@@ -0,0 +1,21 @@
+use disposal_dto::CapitalGainsDisposalDto;
+use rust_decimal::Decimal;
+use time::Date;
+
+pub mod disposal_dto;
+pub mod dividend_income_dto;
+
+/// Disposals and dividend income falling into one UK tax year (6 April to
+/// 5 April), labelled by the calendar year the tax year starts in.
+#[derive(Clone, Debug)]
+pub struct CapitalGainsTaxYearDto {
+    pub tax_year: i32,
+    pub start_date: Date,
+    pub end_date: Date,
+    pub disposals: Vec<CapitalGainsDisposalDto>,
+    pub proceeds: Decimal,
+    pub allowable_cost: Decimal,
+    pub gain: Decimal,
+    pub dividends: Decimal,
+    pub withholding_tax: Decimal,
+}
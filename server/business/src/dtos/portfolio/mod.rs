@@ -1,2 +1,3 @@
+pub mod capital_gains;
 pub mod holding;
 pub mod overview;
@@ -0,0 +1,124 @@
+use dal::models::reconciliation_models::{
+    AccountReconciliationRow, AddAccountReconciliationModel, ReconciliationMovementRow,
+};
+use rust_decimal::Decimal;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReconciliationSourceDto {
+    /// Entered by the user from a bank or broker statement.
+    Manual,
+    /// Recorded from the balance a connector reported during a sync.
+    Connector,
+}
+
+impl ReconciliationSourceDto {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Manual => "manual",
+            Self::Connector => "connector",
+        }
+    }
+
+    pub fn from_db_str(s: &str) -> Option<Self> {
+        match s {
+            "manual" => Some(Self::Manual),
+            "connector" => Some(Self::Connector),
+            _ => None,
+        }
+    }
+}
+
+/// A balance of one asset in an account, as stated by the bank on a given date.
+#[derive(Clone, Debug)]
+pub struct AccountReconciliationDto {
+    pub id: Uuid,
+    pub account_id: Uuid,
+    pub asset_id: i32,
+    pub statement_date: OffsetDateTime,
+    pub statement_balance: Decimal,
+    pub source: ReconciliationSourceDto,
+    pub binding_id: Option<Uuid>,
+    pub created_at: OffsetDateTime,
+}
+
+impl From<AccountReconciliationRow> for AccountReconciliationDto {
+    fn from(row: AccountReconciliationRow) -> Self {
+        Self {
+            id: row.id,
+            account_id: row.account_id,
+            asset_id: row.asset_id,
+            statement_date: row.statement_date,
+            statement_balance: row.statement_balance,
+            source: ReconciliationSourceDto::from_db_str(&row.source)
+                .unwrap_or(ReconciliationSourceDto::Manual),
+            binding_id: row.binding_id,
+            created_at: row.created_at,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AddAccountReconciliationDto {
+    pub asset_id: i32,
+    pub statement_date: OffsetDateTime,
+    pub statement_balance: Decimal,
+}
+
+impl AddAccountReconciliationDto {
+    pub fn into_add_model(
+        self,
+        user_id: Uuid,
+        account_id: Uuid,
+        source: ReconciliationSourceDto,
+        binding_id: Option<Uuid>,
+    ) -> AddAccountReconciliationModel {
+        AddAccountReconciliationModel {
+            user_id,
+            account_id,
+            asset_id: self.asset_id,
+            statement_date: self.statement_date,
+            statement_balance: self.statement_balance,
+            source: source.as_str().to_string(),
+            binding_id,
+        }
+    }
+}
+
+/// Net quantity a transaction moved through the reconciled account.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LedgerMovementDto {
+    pub transaction_id: Uuid,
+    pub date_transacted: OffsetDateTime,
+    pub quantity: Decimal,
+}
+
+impl From<ReconciliationMovementRow> for LedgerMovementDto {
+    fn from(row: ReconciliationMovementRow) -> Self {
+        Self {
+            transaction_id: row.transaction_id,
+            date_transacted: row.date_transacted,
+            quantity: row.quantity,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct ReconciliationReportDto {
+    pub reconciliation: AccountReconciliationDto,
+    /// Sum of the account's entries in the asset up to the statement date.
+    pub ledger_balance: Decimal,
+    /// Statement balance minus ledger balance; zero when the account is reconciled.
+    pub discrepancy: Decimal,
+    /// The latest earlier checkpoint for the same asset that still matches the ledger.
+    pub last_reconciled: Option<AccountReconciliationDto>,
+    /// Transactions booked after `last_reconciled` up to the statement date.
+    pub unreconciled_transactions: Vec<LedgerMovementDto>,
+}
+
+impl ReconciliationReportDto {
+    pub fn is_reconciled(&self) -> bool {
+        self.discrepancy.is_zero()
+    }
+}
@@ -0,0 +1,3 @@
+pub mod recurring_transaction_dto;
+
+pub use recurring_transaction_dto::*;
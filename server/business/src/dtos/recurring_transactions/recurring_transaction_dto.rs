@@ -0,0 +1,102 @@
+use dal::models::recurring_transaction_models::{
+    AddRecurringTransactionModel, RecurringTransactionRow, UpdateRecurringTransactionModel,
+};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::dtos::transaction_dto::TransactionDto;
+use crate::entities::recurring_template::StoredRecurringTemplate;
+
+#[derive(Clone, Debug)]
+pub struct RecurringTransactionDto {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub account_id: Uuid,
+    pub name: String,
+    /// Transaction created on every occurrence, dated at the start date here. Each
+    /// occurrence gets its own date.
+    pub template: TransactionDto,
+    pub rrule: String,
+    pub start_date: OffsetDateTime,
+    pub end_date: Option<OffsetDateTime>,
+    /// `None` once the schedule has no occurrences left.
+    pub next_occurrence: Option<OffsetDateTime>,
+    pub last_occurrence: Option<OffsetDateTime>,
+    pub create_as_ghost: bool,
+    pub active: bool,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+impl TryFrom<RecurringTransactionRow> for RecurringTransactionDto {
+    type Error = anyhow::Error;
+
+    fn try_from(row: RecurringTransactionRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            id: row.id,
+            user_id: row.user_id,
+            account_id: row.account_id,
+            name: row.name,
+            template: StoredRecurringTemplate::from_json(row.template.0)?.into_dto(row.start_date),
+            rrule: row.rrule,
+            start_date: row.start_date,
+            end_date: row.end_date,
+            next_occurrence: row.next_occurrence,
+            last_occurrence: row.last_occurrence,
+            create_as_ghost: row.create_as_ghost,
+            active: row.active,
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AddUpdateRecurringTransactionDto {
+    pub account_id: Uuid,
+    pub name: String,
+    pub template: TransactionDto,
+    pub rrule: String,
+    pub start_date: OffsetDateTime,
+    pub end_date: Option<OffsetDateTime>,
+    pub create_as_ghost: bool,
+    pub active: bool,
+}
+
+impl AddUpdateRecurringTransactionDto {
+    pub fn into_add_model(
+        self,
+        user_id: Uuid,
+        next_occurrence: Option<OffsetDateTime>,
+    ) -> anyhow::Result<AddRecurringTransactionModel> {
+        Ok(AddRecurringTransactionModel {
+            user_id,
+            account_id: self.account_id,
+            name: self.name,
+            template: StoredRecurringTemplate::from_dto(&self.template).to_json()?,
+            rrule: self.rrule,
+            start_date: self.start_date,
+            end_date: self.end_date,
+            next_occurrence,
+            create_as_ghost: self.create_as_ghost,
+            active: self.active,
+        })
+    }
+
+    pub fn into_update_model(
+        self,
+        next_occurrence: Option<OffsetDateTime>,
+    ) -> anyhow::Result<UpdateRecurringTransactionModel> {
+        Ok(UpdateRecurringTransactionModel {
+            account_id: self.account_id,
+            name: self.name,
+            template: StoredRecurringTemplate::from_dto(&self.template).to_json()?,
+            rrule: self.rrule,
+            start_date: self.start_date,
+            end_date: self.end_date,
+            next_occurrence,
+            create_as_ghost: self.create_as_ghost,
+            active: self.active,
+        })
+    }
+}
@@ -0,0 +1,93 @@
+use dal::models::spending_alert_models::SpendingAlertRow;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpendingAlertKindDto {
+    /// Far above what the user usually pays the merchant, or in the category.
+    UnusualAmount,
+    /// The same amount charged by the same merchant on the same account a few days apart.
+    DuplicateCharge,
+    /// First purchase from a merchant, in a currency other than the user's base currency.
+    NewMerchantAbroad,
+    /// Charged in a currency the account has not been charged in before.
+    UnexpectedCurrency,
+}
+
+impl SpendingAlertKindDto {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::UnusualAmount => "unusual_amount",
+            Self::DuplicateCharge => "duplicate_charge",
+            Self::NewMerchantAbroad => "new_merchant_abroad",
+            Self::UnexpectedCurrency => "unexpected_currency",
+        }
+    }
+
+    pub fn from_db_str(s: &str) -> Option<Self> {
+        match s {
+            "unusual_amount" => Some(Self::UnusualAmount),
+            "duplicate_charge" => Some(Self::DuplicateCharge),
+            "new_merchant_abroad" => Some(Self::NewMerchantAbroad),
+            "unexpected_currency" => Some(Self::UnexpectedCurrency),
+            _ => None,
+        }
+    }
+}
+
+/// What the detector found about one transaction, before it is stored.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DetectedSpendingAlertDto {
+    pub transaction_id: Uuid,
+    pub kind: SpendingAlertKindDto,
+    pub account_id: Uuid,
+    pub asset_id: i32,
+    /// Amount paid, positive.
+    pub amount: Decimal,
+    /// What the user usually pays, for unusual amounts.
+    pub typical_amount: Option<Decimal>,
+    /// The earlier charge, for duplicates.
+    pub related_transaction_id: Option<Uuid>,
+    pub description: Option<String>,
+    pub date_transacted: OffsetDateTime,
+}
+
+/// A stored alert. Also the payload streamed to the user's open alert subscriptions.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SpendingAlertDto {
+    pub id: Uuid,
+    pub transaction_id: Uuid,
+    pub kind: SpendingAlertKindDto,
+    pub account_id: Uuid,
+    pub asset_id: i32,
+    pub amount: Decimal,
+    pub typical_amount: Option<Decimal>,
+    pub related_transaction_id: Option<Uuid>,
+    pub description: Option<String>,
+    pub date_transacted: OffsetDateTime,
+    pub dismissed_at: Option<OffsetDateTime>,
+    pub created_at: OffsetDateTime,
+}
+
+impl From<SpendingAlertRow> for SpendingAlertDto {
+    fn from(row: SpendingAlertRow) -> Self {
+        Self {
+            id: row.id,
+            transaction_id: row.transaction_id,
+            kind: SpendingAlertKindDto::from_db_str(&row.kind)
+                .unwrap_or(SpendingAlertKindDto::UnusualAmount),
+            account_id: row.account_id,
+            asset_id: row.asset_id,
+            amount: row.amount,
+            typical_amount: row.typical_amount,
+            related_transaction_id: row.related_transaction_id,
+            description: row.description,
+            date_transacted: row.date_transacted,
+            dismissed_at: row.dismissed_at,
+            created_at: row.created_at,
+        }
+    }
+}
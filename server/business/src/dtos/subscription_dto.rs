@@ -0,0 +1,42 @@
+use rust_decimal::Decimal;
+use time::Date;
+use uuid::Uuid;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SubscriptionPeriodDto {
+    Weekly,
+    Monthly,
+    Quarterly,
+    Yearly,
+}
+
+impl SubscriptionPeriodDto {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Weekly => "weekly",
+            Self::Monthly => "monthly",
+            Self::Quarterly => "quarterly",
+            Self::Yearly => "yearly",
+        }
+    }
+}
+
+/// A series of regular transactions that look like a recurring payment.
+#[derive(Clone, Debug)]
+pub struct DetectedSubscriptionDto {
+    /// Description of the most recent charge.
+    pub description: String,
+    pub account_id: Uuid,
+    pub asset_id: i32,
+    pub period: SubscriptionPeriodDto,
+    /// Typical (median) amount charged, as a positive quantity of `asset_id`.
+    pub amount: Decimal,
+    pub last_amount: Decimal,
+    pub first_charge_date: Date,
+    pub last_charge_date: Date,
+    pub next_charge_date: Date,
+    pub charge_count: usize,
+    /// Between 0 and 1; how regular the dates and amounts of the charges are.
+    pub confidence: f64,
+    pub transaction_ids: Vec<Uuid>,
+}
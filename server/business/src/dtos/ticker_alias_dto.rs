@@ -0,0 +1,49 @@
+use dal::models::ticker_alias_models::{AddUserTickerAliasModel, UserTickerAliasRow};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// Maps a provider's instrument identifier (or currency code) to one of the user's assets.
+/// Takes precedence over the built-in ticker matching when importing connector transactions.
+#[derive(Clone, Debug)]
+pub struct UserTickerAliasDto {
+    pub id: Uuid,
+    pub provider_ticker: String,
+    pub asset_id: i32,
+    pub created_at: OffsetDateTime,
+}
+
+impl From<UserTickerAliasRow> for UserTickerAliasDto {
+    fn from(row: UserTickerAliasRow) -> Self {
+        Self {
+            id: row.id,
+            provider_ticker: row.provider_ticker,
+            asset_id: row.asset_id,
+            created_at: row.created_at,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AddUserTickerAliasDto {
+    pub provider_ticker: String,
+    pub asset_id: i32,
+}
+
+impl AddUserTickerAliasDto {
+    pub fn into_add_model(self, user_id: Uuid) -> AddUserTickerAliasModel {
+        AddUserTickerAliasModel {
+            user_id,
+            provider_ticker: self.provider_ticker,
+            asset_id: self.asset_id,
+        }
+    }
+}
+
+/// A new alias and the bindings holding transactions it resolves: re-projected right away,
+/// or pending when another sync held the provider account.
+#[derive(Clone, Debug)]
+pub struct CreatedUserTickerAliasDto {
+    pub alias: UserTickerAliasDto,
+    pub reprojected_bindings: Vec<Uuid>,
+    pub pending_bindings: Vec<Uuid>,
+}
@@ -0,0 +1,123 @@
+use dal::models::transaction_rule_models::{AddUpdateTransactionRuleModel, TransactionRuleRow};
+use rust_decimal::Decimal;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::dtos::transaction_dto::TransactionVisibilityDto;
+
+/// What a transaction has to look like for a rule to apply. Every condition that is set
+/// must hold; a rule without conditions is rejected.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RuleConditionsDto {
+    /// Matched case-insensitively anywhere in the description.
+    pub description_contains: Option<String>,
+    pub description_regex: Option<String>,
+    /// Inclusive bounds on the signed amount, so spending is negative.
+    pub amount_min: Option<Decimal>,
+    pub amount_max: Option<Decimal>,
+    pub account_id: Option<Uuid>,
+    /// Matched case-insensitively against the name or account the provider gives for the
+    /// other party. Only imports carry one.
+    pub counterparty: Option<String>,
+}
+
+/// What a rule does to a matching transaction.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RuleActionsDto {
+    pub category_id: Option<i32>,
+    pub tag_ids: Vec<Uuid>,
+    /// Replaces the description. `$1` or `$name` expand to groups captured by the
+    /// description regex.
+    pub description_rewrite: Option<String>,
+    pub visibility: Option<TransactionVisibilityDto>,
+}
+
+impl RuleActionsDto {
+    pub fn is_empty(&self) -> bool {
+        self.category_id.is_none()
+            && self.tag_ids.is_empty()
+            && self.description_rewrite.is_none()
+            && self.visibility.is_none()
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct TransactionRuleDto {
+    pub id: Uuid,
+    pub name: String,
+    /// Rules run in ascending priority.
+    pub priority: i32,
+    pub enabled: bool,
+    pub conditions: RuleConditionsDto,
+    pub actions: RuleActionsDto,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+impl TransactionRuleDto {
+    pub fn from_row(row: TransactionRuleRow, tag_ids: Vec<Uuid>) -> Self {
+        Self {
+            id: row.id,
+            name: row.name,
+            priority: row.priority,
+            enabled: row.enabled,
+            conditions: RuleConditionsDto {
+                description_contains: row.description_contains,
+                description_regex: row.description_regex,
+                amount_min: row.amount_min,
+                amount_max: row.amount_max,
+                account_id: row.account_id,
+                counterparty: row.counterparty,
+            },
+            actions: RuleActionsDto {
+                category_id: row.category_id,
+                tag_ids,
+                description_rewrite: row.description_rewrite,
+                visibility: row
+                    .visibility
+                    .as_deref()
+                    .and_then(TransactionVisibilityDto::from_db_str),
+            },
+            created_at: row.created_at,
+            updated_at: row.updated_at,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AddUpdateTransactionRuleDto {
+    pub name: String,
+    pub priority: i32,
+    pub enabled: bool,
+    pub conditions: RuleConditionsDto,
+    pub actions: RuleActionsDto,
+}
+
+impl From<AddUpdateTransactionRuleDto> for AddUpdateTransactionRuleModel {
+    fn from(dto: AddUpdateTransactionRuleDto) -> Self {
+        Self {
+            name: dto.name,
+            priority: dto.priority,
+            enabled: dto.enabled,
+            description_contains: dto.conditions.description_contains,
+            description_regex: dto.conditions.description_regex,
+            amount_min: dto.conditions.amount_min,
+            amount_max: dto.conditions.amount_max,
+            account_id: dto.conditions.account_id,
+            counterparty: dto.conditions.counterparty,
+            category_id: dto.actions.category_id,
+            description_rewrite: dto.actions.description_rewrite,
+            visibility: dto
+                .actions
+                .visibility
+                .map(|visibility| visibility.as_str().to_string()),
+        }
+    }
+}
+
+/// Outcome of running the rules over the user's existing transactions.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RuleApplicationReportDto {
+    pub examined: usize,
+    pub updated: usize,
+}
@@ -0,0 +1,27 @@
+use dal::models::transfer_models::TransferLegRow;
+use uuid::Uuid;
+
+use crate::dtos::transaction_dto::TransactionVisibilityDto;
+use crate::entities::transfers::TransferLeg;
+
+impl From<TransferLegRow> for TransferLeg {
+    fn from(row: TransferLegRow) -> Self {
+        Self {
+            transaction_id: row.transaction_id,
+            account_id: row.account_id,
+            asset_id: row.asset_id,
+            amount: row.quantity,
+            date: row.date_transacted,
+            visibility: TransactionVisibilityDto::from_db_str(&row.visibility)
+                .unwrap_or(TransactionVisibilityDto::Default),
+            imported: row.imported,
+        }
+    }
+}
+
+/// Two transactions the user confirmed are the sides of one transfer between accounts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TransferPairDto {
+    pub outgoing_transaction_id: Uuid,
+    pub incoming_transaction_id: Uuid,
+}
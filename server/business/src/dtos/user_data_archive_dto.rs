@@ -0,0 +1,187 @@
+//! A portable copy of everything a user owns, used to move between Sverto instances.
+//!
+//! Ids inside the archive only link records to each other and are remapped on import.
+//! Shared reference data (public assets, global categories and category types) is
+//! referenced by ticker or name, because its ids differ between instances. Account types,
+//! liquidity types and transaction types are fixed by migrations and kept as ids.
+
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+/// Bumped whenever the archive layout changes incompatibly.
+pub const USER_DATA_ARCHIVE_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserDataArchiveDto {
+    pub version: u32,
+    #[serde(with = "time::serde::rfc3339")]
+    pub exported_at: OffsetDateTime,
+    pub shared_assets: Vec<ArchivedSharedAssetDto>,
+    pub shared_category_types: Vec<ArchivedSharedNameDto>,
+    pub shared_categories: Vec<ArchivedSharedNameDto>,
+    pub accounts: Vec<ArchivedAccountDto>,
+    pub custom_assets: Vec<ArchivedCustomAssetDto>,
+    pub asset_pairs: Vec<ArchivedAssetPairDto>,
+    pub category_types: Vec<ArchivedCategoryTypeDto>,
+    pub categories: Vec<ArchivedCategoryDto>,
+    pub transaction_groups: Vec<ArchivedTransactionGroupDto>,
+    pub transactions: Vec<ArchivedTransactionDto>,
+    pub connector_connections: Vec<ArchivedConnectorConnectionDto>,
+    pub connector_provider_accounts: Vec<ArchivedConnectorProviderAccountDto>,
+    pub connector_bindings: Vec<ArchivedConnectorBindingDto>,
+    pub connector_transactions: Vec<ArchivedConnectorTransactionDto>,
+}
+
+/// A public asset the archive refers to, matched by ticker on import.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedSharedAssetDto {
+    pub id: i32,
+    pub ticker: String,
+}
+
+/// A global category or category type the archive refers to, matched by name on import.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedSharedNameDto {
+    pub id: i32,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedAccountDto {
+    pub id: Uuid,
+    pub name: String,
+    pub account_type: i32,
+    pub liquidity_type: i32,
+    pub active: bool,
+    pub ownership_share: Decimal,
+    pub cost_basis_method: String,
+    pub identifiers: Vec<ArchivedAccountIdentifierDto>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedAccountIdentifierDto {
+    pub kind: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedCustomAssetDto {
+    pub id: i32,
+    pub asset_type: i32,
+    pub name: String,
+    pub ticker: String,
+    /// Asset id, shared or custom, the asset is quoted against.
+    pub base_pair_id: Option<i32>,
+}
+
+/// A pair with at least one custom side, with the rates recorded for it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedAssetPairDto {
+    pub pair1: i32,
+    pub pair2: i32,
+    pub rates: Vec<ArchivedAssetRateDto>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedAssetRateDto {
+    pub rate: Decimal,
+    #[serde(with = "time::serde::rfc3339")]
+    pub recorded_at: OffsetDateTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedCategoryTypeDto {
+    pub id: i32,
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedCategoryDto {
+    pub id: i32,
+    pub name: String,
+    pub icon: String,
+    pub category_type: i32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedTransactionGroupDto {
+    pub id: Uuid,
+    pub category_id: i32,
+    pub description: String,
+    #[serde(with = "time::serde::rfc3339")]
+    pub date_added: OffsetDateTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedTransactionDto {
+    pub id: Uuid,
+    pub group_id: Option<Uuid>,
+    pub type_id: Option<i32>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub date_transacted: OffsetDateTime,
+    pub visibility: String,
+    pub description: Option<String>,
+    pub dividend_source_asset_id: Option<i32>,
+    pub entries: Vec<ArchivedEntryDto>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedEntryDto {
+    pub account_id: Uuid,
+    pub asset_id: i32,
+    pub quantity: Decimal,
+    pub category_id: i32,
+}
+
+/// A connector connection without its credentials; stored-credential connections have
+/// to be authorised again after import.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedConnectorConnectionDto {
+    pub id: Uuid,
+    pub provider_kind: String,
+    pub credential_mode: String,
+    pub status: String,
+    #[serde(with = "time::serde::rfc3339::option")]
+    pub consent_expires_at: Option<OffsetDateTime>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedConnectorProviderAccountDto {
+    pub id: Uuid,
+    pub connection_id: Uuid,
+    pub external_account_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedConnectorBindingDto {
+    pub id: Uuid,
+    pub provider_account_id: Uuid,
+    pub account_id: Uuid,
+    pub write_mode: String,
+    pub status: String,
+}
+
+/// A binding's record of an imported provider transaction, so that the next sync after
+/// the move does not import it again.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchivedConnectorTransactionDto {
+    pub binding_id: Uuid,
+    pub transaction_id: Option<Uuid>,
+    pub external_id: String,
+    pub external_hash: String,
+    pub edited_by_user: bool,
+    #[serde(default)]
+    pub counterparty: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UserDataImportSummaryDto {
+    pub accounts: usize,
+    pub custom_assets: usize,
+    pub categories: usize,
+    pub transaction_groups: usize,
+    pub transactions: usize,
+    pub connector_bindings: usize,
+}
@@ -0,0 +1,184 @@
+use rust_decimal::Decimal;
+use time::{util, Date, Duration};
+
+use crate::dtos::budgets::{BudgetPeriodActualDto, BudgetPeriodDto};
+
+/// First day of the calendar period containing `date`. Weeks start on Monday.
+pub fn period_start(period: BudgetPeriodDto, date: Date) -> Date {
+    match period {
+        BudgetPeriodDto::Weekly => {
+            date - Duration::days(date.weekday().number_days_from_monday() as i64)
+        }
+        BudgetPeriodDto::Monthly => date - Duration::days(date.day() as i64 - 1),
+        BudgetPeriodDto::Yearly => date - Duration::days(date.ordinal() as i64 - 1),
+    }
+}
+
+/// First day of the period following the one that starts at `start`.
+pub fn next_period_start(period: BudgetPeriodDto, start: Date) -> Date {
+    let length = match period {
+        BudgetPeriodDto::Weekly => 7,
+        BudgetPeriodDto::Monthly => start.month().length(start.year()) as i64,
+        BudgetPeriodDto::Yearly => util::days_in_year(start.year()) as i64,
+    };
+    start + Duration::days(length)
+}
+
+/// Walks every period from the one containing `start_date` up to and including the
+/// one containing `through`, totalling `spending` (day, amount spent) into each.
+///
+/// With rollover enabled, whatever is left unspent at the end of a period is added
+/// to the next one. Overspending is not carried forward, so one bad month does not
+/// eat into the following month's budget.
+pub fn calculate_budget_periods(
+    amount: Decimal,
+    period: BudgetPeriodDto,
+    rollover: bool,
+    start_date: Date,
+    through: Date,
+    spending: &[(Date, Decimal)],
+) -> Vec<BudgetPeriodActualDto> {
+    let mut periods = Vec::new();
+    if through < start_date {
+        return periods;
+    }
+
+    let mut carried_over = Decimal::ZERO;
+    let mut start = period_start(period, start_date);
+    while start <= through {
+        let next = next_period_start(period, start);
+        let spent: Decimal = spending
+            .iter()
+            .filter(|(day, _)| *day >= start && *day < next)
+            .map(|(_, amount)| *amount)
+            .sum();
+
+        let actual = BudgetPeriodActualDto {
+            start_date: start,
+            end_date: next - Duration::days(1),
+            budgeted: amount,
+            carried_over,
+            spent,
+        };
+
+        carried_over = if rollover {
+            actual.remaining().max(Decimal::ZERO)
+        } else {
+            Decimal::ZERO
+        };
+
+        periods.push(actual);
+        start = next;
+    }
+
+    periods
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use time::macros::date;
+
+    use super::*;
+
+    #[test]
+    fn period_start_aligns_to_calendar() {
+        let day = date!(2024 - 02 - 15);
+
+        assert_eq!(
+            period_start(BudgetPeriodDto::Weekly, day),
+            date!(2024 - 02 - 12)
+        );
+        assert_eq!(
+            period_start(BudgetPeriodDto::Monthly, day),
+            date!(2024 - 02 - 01)
+        );
+        assert_eq!(
+            period_start(BudgetPeriodDto::Yearly, day),
+            date!(2024 - 01 - 01)
+        );
+    }
+
+    #[test]
+    fn next_period_start_handles_month_and_year_lengths() {
+        assert_eq!(
+            next_period_start(BudgetPeriodDto::Monthly, date!(2024 - 02 - 01)),
+            date!(2024 - 03 - 01)
+        );
+        assert_eq!(
+            next_period_start(BudgetPeriodDto::Monthly, date!(2024 - 12 - 01)),
+            date!(2025 - 01 - 01)
+        );
+        assert_eq!(
+            next_period_start(BudgetPeriodDto::Yearly, date!(2024 - 01 - 01)),
+            date!(2025 - 01 - 01)
+        );
+        assert_eq!(
+            next_period_start(BudgetPeriodDto::Weekly, date!(2024 - 12 - 30)),
+            date!(2025 - 01 - 06)
+        );
+    }
+
+    #[test]
+    fn spending_is_totalled_per_period() {
+        let spending = [
+            (date!(2024 - 01 - 05), dec!(40)),
+            (date!(2024 - 01 - 31), dec!(20)),
+            (date!(2024 - 02 - 01), dec!(70)),
+        ];
+
+        let periods = calculate_budget_periods(
+            dec!(100),
+            BudgetPeriodDto::Monthly,
+            false,
+            date!(2024 - 01 - 10),
+            date!(2024 - 02 - 20),
+            &spending,
+        );
+
+        assert_eq!(periods.len(), 2);
+        assert_eq!(periods[0].start_date, date!(2024 - 01 - 01));
+        assert_eq!(periods[0].end_date, date!(2024 - 01 - 31));
+        assert_eq!(periods[0].spent, dec!(60));
+        assert_eq!(periods[1].carried_over, dec!(0));
+        assert_eq!(periods[1].remaining(), dec!(30));
+    }
+
+    #[test]
+    fn rollover_carries_unspent_amounts_but_not_overspending() {
+        let spending = [
+            (date!(2024 - 01 - 05), dec!(60)),
+            (date!(2024 - 02 - 05), dec!(200)),
+            (date!(2024 - 03 - 05), dec!(50)),
+        ];
+
+        let periods = calculate_budget_periods(
+            dec!(100),
+            BudgetPeriodDto::Monthly,
+            true,
+            date!(2024 - 01 - 01),
+            date!(2024 - 03 - 31),
+            &spending,
+        );
+
+        assert_eq!(periods[1].carried_over, dec!(40));
+        assert_eq!(periods[1].available(), dec!(140));
+        assert_eq!(periods[1].remaining(), dec!(-60));
+        assert_eq!(periods[2].carried_over, dec!(0));
+        assert_eq!(periods[2].remaining(), dec!(50));
+    }
+
+    #[test]
+    fn no_periods_before_budget_start() {
+        let periods = calculate_budget_periods(
+            dec!(100),
+            BudgetPeriodDto::Weekly,
+            true,
+            date!(2024 - 05 - 01),
+            date!(2024 - 04 - 01),
+            &[],
+        );
+
+        assert!(periods.is_empty());
+    }
+}
@@ -0,0 +1,58 @@
+use rust_decimal::Decimal;
+use time::OffsetDateTime;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MatchingEventKind {
+    Acquisition,
+    Disposal,
+}
+
+/// An acquisition or disposal of an asset as seen by the share-matching rules,
+/// already converted into the reference asset. There is no account here on
+/// purpose: HMRC matches the same asset across every account the user holds.
+#[derive(Clone, Debug)]
+pub struct MatchingEvent {
+    pub date: OffsetDateTime,
+    pub asset_id: i32,
+    pub kind: MatchingEventKind,
+    pub quantity: Decimal,
+    /// Purchase cost for acquisitions, gross proceeds for disposals.
+    pub amount: Decimal,
+    pub fees: Decimal,
+}
+
+impl MatchingEvent {
+    pub fn acquisition(
+        date: OffsetDateTime,
+        asset_id: i32,
+        quantity: Decimal,
+        amount: Decimal,
+        fees: Decimal,
+    ) -> Self {
+        Self {
+            date,
+            asset_id,
+            kind: MatchingEventKind::Acquisition,
+            quantity,
+            amount,
+            fees,
+        }
+    }
+
+    pub fn disposal(
+        date: OffsetDateTime,
+        asset_id: i32,
+        quantity: Decimal,
+        amount: Decimal,
+        fees: Decimal,
+    ) -> Self {
+        Self {
+            date,
+            asset_id,
+            kind: MatchingEventKind::Disposal,
+            quantity,
+            amount,
+            fees,
+        }
+    }
+}
@@ -0,0 +1,3 @@
+pub mod matching_event;
+pub mod share_matching;
+pub mod tax_year;
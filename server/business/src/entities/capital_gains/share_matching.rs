@@ -0,0 +1,474 @@
+use std::collections::{BTreeMap, HashMap};
+
+use rust_decimal::Decimal;
+use time::{Date, Duration};
+
+use crate::dtos::portfolio::capital_gains::{
+    disposal_dto::{CapitalGainsDisposalDto, DisposalMatchDto, ShareMatchingRuleDto},
+    dividend_income_dto::DividendIncomeDto,
+    CapitalGainsTaxYearDto,
+};
+
+use super::{
+    matching_event::{MatchingEvent, MatchingEventKind},
+    tax_year::{tax_year_bounds, tax_year_of},
+};
+
+const BED_AND_BREAKFAST_DAYS: i64 = 30;
+
+/// Everything that happened to one asset on one calendar day. HMRC treats all
+/// same-day acquisitions as a single acquisition and all same-day disposals as
+/// a single disposal, so the matching rules operate on these buckets.
+#[derive(Default)]
+struct TradingDay {
+    unmatched_acquired: Decimal,
+    unmatched_cost: Decimal,
+    disposed: Decimal,
+    proceeds: Decimal,
+    disposal_fees: Decimal,
+    matches: Vec<DisposalMatchDto>,
+}
+
+impl TradingDay {
+    fn unmatched_disposed(&self) -> Decimal {
+        self.disposed - self.matches.iter().map(|x| x.quantity).sum::<Decimal>()
+    }
+
+    /// Removes `quantity` from the day's unmatched acquisitions and returns the
+    /// matching share of their cost.
+    fn take_acquired(&mut self, quantity: Decimal) -> Decimal {
+        let cost = if quantity >= self.unmatched_acquired {
+            self.unmatched_cost
+        } else {
+            self.unmatched_cost * quantity / self.unmatched_acquired
+        };
+        self.unmatched_acquired -= quantity;
+        self.unmatched_cost -= cost;
+        cost
+    }
+}
+
+/// Matches every disposal against acquisitions of the same asset using the
+/// HMRC identification rules, in order: same day, the following 30 days
+/// (bed and breakfast), then the Section 104 pool at average cost.
+pub fn match_disposals(events: Vec<MatchingEvent>) -> Vec<CapitalGainsDisposalDto> {
+    let mut assets: HashMap<i32, BTreeMap<Date, TradingDay>> = HashMap::new();
+
+    for event in events {
+        let day = assets
+            .entry(event.asset_id)
+            .or_default()
+            .entry(event.date.date())
+            .or_default();
+
+        match event.kind {
+            MatchingEventKind::Acquisition => {
+                day.unmatched_acquired += event.quantity;
+                day.unmatched_cost += event.amount + event.fees;
+            }
+            MatchingEventKind::Disposal => {
+                day.disposed += event.quantity;
+                day.proceeds += event.amount;
+                day.disposal_fees += event.fees;
+            }
+        }
+    }
+
+    let mut disposals: Vec<CapitalGainsDisposalDto> = assets
+        .into_iter()
+        .flat_map(|(asset_id, days)| match_asset_disposals(asset_id, days))
+        .collect();
+
+    disposals.sort_by_key(|x| (x.date, x.asset_id));
+    disposals
+}
+
+fn match_asset_disposals(
+    asset_id: i32,
+    days: BTreeMap<Date, TradingDay>,
+) -> Vec<CapitalGainsDisposalDto> {
+    let mut days: Vec<(Date, TradingDay)> = days.into_iter().collect();
+
+    for (date, day) in days.iter_mut() {
+        let quantity = day.disposed.min(day.unmatched_acquired);
+        if quantity <= Decimal::ZERO {
+            continue;
+        }
+
+        let cost = day.take_acquired(quantity);
+        day.matches.push(DisposalMatchDto {
+            rule: ShareMatchingRuleDto::SameDay,
+            acquisition_date: Some(*date),
+            quantity,
+            cost,
+        });
+    }
+
+    for disposal_index in 0..days.len() {
+        let disposal_date = days[disposal_index].0;
+        let window_end = disposal_date + Duration::days(BED_AND_BREAKFAST_DAYS);
+
+        for acquisition_index in disposal_index + 1..days.len() {
+            let acquisition_date = days[acquisition_index].0;
+            let remaining = days[disposal_index].1.unmatched_disposed();
+            if acquisition_date > window_end || remaining <= Decimal::ZERO {
+                break;
+            }
+
+            let quantity = remaining.min(days[acquisition_index].1.unmatched_acquired);
+            if quantity <= Decimal::ZERO {
+                continue;
+            }
+
+            let cost = days[acquisition_index].1.take_acquired(quantity);
+            days[disposal_index].1.matches.push(DisposalMatchDto {
+                rule: ShareMatchingRuleDto::BedAndBreakfast,
+                acquisition_date: Some(acquisition_date),
+                quantity,
+                cost,
+            });
+        }
+    }
+
+    let mut pool_quantity = Decimal::ZERO;
+    let mut pool_cost = Decimal::ZERO;
+    for (date, day) in days.iter_mut() {
+        pool_quantity += day.unmatched_acquired;
+        pool_cost += day.unmatched_cost;
+
+        let remaining = day.unmatched_disposed();
+        if remaining <= Decimal::ZERO {
+            continue;
+        }
+
+        let quantity = remaining.min(pool_quantity);
+        if quantity < remaining {
+            tracing::warn!(
+                asset_id,
+                date = %date,
+                unmatched = %(remaining - quantity),
+                "disposal exceeds known holdings; unmatched quantity has no allowable cost"
+            );
+        }
+
+        if quantity <= Decimal::ZERO {
+            continue;
+        }
+
+        let cost = if quantity >= pool_quantity {
+            pool_cost
+        } else {
+            pool_cost * quantity / pool_quantity
+        };
+        pool_quantity -= quantity;
+        pool_cost -= cost;
+
+        day.matches.push(DisposalMatchDto {
+            rule: ShareMatchingRuleDto::Section104,
+            acquisition_date: None,
+            quantity,
+            cost,
+        });
+    }
+
+    days.into_iter()
+        .filter(|(_, day)| day.disposed > Decimal::ZERO)
+        .map(|(date, day)| CapitalGainsDisposalDto {
+            asset_id,
+            date,
+            quantity: day.disposed,
+            proceeds: day.proceeds,
+            fees: day.disposal_fees,
+            cost_basis: day.matches.iter().map(|x| x.cost).sum(),
+            matches: day.matches,
+        })
+        .collect()
+}
+
+/// Groups matched disposals and dividend income into UK tax years, oldest
+/// first. Years with dividends but no disposals are still reported.
+pub fn build_tax_year_reports(
+    disposals: Vec<CapitalGainsDisposalDto>,
+    dividends: Vec<DividendIncomeDto>,
+) -> anyhow::Result<Vec<CapitalGainsTaxYearDto>> {
+    let mut years: BTreeMap<i32, (Vec<CapitalGainsDisposalDto>, Vec<DividendIncomeDto>)> =
+        BTreeMap::new();
+    for disposal in disposals {
+        years
+            .entry(tax_year_of(disposal.date))
+            .or_default()
+            .0
+            .push(disposal);
+    }
+    for dividend in dividends {
+        years
+            .entry(tax_year_of(dividend.date))
+            .or_default()
+            .1
+            .push(dividend);
+    }
+
+    years
+        .into_iter()
+        .map(|(tax_year, (disposals, dividends))| {
+            let (start_date, end_date) = tax_year_bounds(tax_year)?;
+            Ok(CapitalGainsTaxYearDto {
+                tax_year,
+                start_date,
+                end_date,
+                proceeds: disposals.iter().map(|x| x.proceeds).sum(),
+                allowable_cost: disposals.iter().map(|x| x.allowable_cost()).sum(),
+                gain: disposals.iter().map(|x| x.gain()).sum(),
+                dividends: dividends.iter().map(|x| x.dividends).sum(),
+                withholding_tax: dividends.iter().map(|x| x.withholding_tax).sum(),
+                disposals,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use time::macros::{date, datetime};
+
+    use super::*;
+
+    #[test]
+    fn same_day_acquisition_is_matched_before_pool() {
+        let events = vec![
+            MatchingEvent::acquisition(
+                datetime!(2024-01-10 09:00 UTC),
+                1,
+                dec!(100),
+                dec!(100),
+                dec!(0),
+            ),
+            MatchingEvent::disposal(
+                datetime!(2024-06-01 10:00 UTC),
+                1,
+                dec!(10),
+                dec!(60),
+                dec!(0),
+            ),
+            MatchingEvent::acquisition(
+                datetime!(2024-06-01 15:00 UTC),
+                1,
+                dec!(10),
+                dec!(50),
+                dec!(0),
+            ),
+        ];
+
+        let disposals = match_disposals(events);
+
+        assert_eq!(disposals.len(), 1);
+        assert_eq!(disposals[0].cost_basis, dec!(50));
+        assert_eq!(disposals[0].gain(), dec!(10));
+        assert_eq!(
+            disposals[0].matches,
+            vec![DisposalMatchDto {
+                rule: ShareMatchingRuleDto::SameDay,
+                acquisition_date: Some(date!(2024 - 06 - 01)),
+                quantity: dec!(10),
+                cost: dec!(50),
+            }]
+        );
+    }
+
+    #[test]
+    fn reacquisition_within_thirty_days_is_matched_before_pool() {
+        let events = vec![
+            MatchingEvent::acquisition(
+                datetime!(2024-01-10 00:00 UTC),
+                1,
+                dec!(100),
+                dec!(100),
+                dec!(0),
+            ),
+            MatchingEvent::disposal(
+                datetime!(2024-06-01 00:00 UTC),
+                1,
+                dec!(50),
+                dec!(100),
+                dec!(0),
+            ),
+            MatchingEvent::acquisition(
+                datetime!(2024-06-20 00:00 UTC),
+                1,
+                dec!(30),
+                dec!(90),
+                dec!(0),
+            ),
+            MatchingEvent::acquisition(
+                datetime!(2024-07-02 00:00 UTC),
+                1,
+                dec!(30),
+                dec!(300),
+                dec!(0),
+            ),
+        ];
+
+        let disposals = match_disposals(events);
+
+        assert_eq!(disposals.len(), 1);
+        let disposal = &disposals[0];
+        assert_eq!(disposal.matches.len(), 2);
+        assert_eq!(
+            disposal.matches[0].rule,
+            ShareMatchingRuleDto::BedAndBreakfast
+        );
+        assert_eq!(disposal.matches[0].quantity, dec!(30));
+        assert_eq!(disposal.matches[0].cost, dec!(90));
+        assert_eq!(disposal.matches[1].rule, ShareMatchingRuleDto::Section104);
+        assert_eq!(disposal.matches[1].quantity, dec!(20));
+        assert_eq!(disposal.matches[1].cost, dec!(20));
+        assert_eq!(disposal.gain(), dec!(-10));
+    }
+
+    #[test]
+    fn section_104_pool_uses_average_cost_including_fees() {
+        let events = vec![
+            MatchingEvent::acquisition(
+                datetime!(2023-01-10 00:00 UTC),
+                1,
+                dec!(10),
+                dec!(100),
+                dec!(0),
+            ),
+            MatchingEvent::acquisition(
+                datetime!(2023-03-10 00:00 UTC),
+                1,
+                dec!(10),
+                dec!(200),
+                dec!(10),
+            ),
+            MatchingEvent::disposal(
+                datetime!(2024-05-01 00:00 UTC),
+                1,
+                dec!(5),
+                dec!(100),
+                dec!(2),
+            ),
+            MatchingEvent::disposal(
+                datetime!(2024-08-01 00:00 UTC),
+                1,
+                dec!(15),
+                dec!(300),
+                dec!(0),
+            ),
+        ];
+
+        let disposals = match_disposals(events);
+
+        assert_eq!(disposals.len(), 2);
+        assert_eq!(disposals[0].cost_basis, dec!(77.5));
+        assert_eq!(disposals[0].allowable_cost(), dec!(79.5));
+        assert_eq!(disposals[0].gain(), dec!(20.5));
+        assert_eq!(disposals[1].cost_basis, dec!(232.5));
+        assert_eq!(disposals[1].gain(), dec!(67.5));
+    }
+
+    #[test]
+    fn assets_are_matched_independently() {
+        let events = vec![
+            MatchingEvent::acquisition(
+                datetime!(2024-01-10 00:00 UTC),
+                1,
+                dec!(10),
+                dec!(10),
+                dec!(0),
+            ),
+            MatchingEvent::acquisition(
+                datetime!(2024-01-10 00:00 UTC),
+                2,
+                dec!(10),
+                dec!(1000),
+                dec!(0),
+            ),
+            MatchingEvent::disposal(
+                datetime!(2024-01-10 00:00 UTC),
+                1,
+                dec!(10),
+                dec!(20),
+                dec!(0),
+            ),
+        ];
+
+        let disposals = match_disposals(events);
+
+        assert_eq!(disposals.len(), 1);
+        assert_eq!(disposals[0].asset_id, 1);
+        assert_eq!(disposals[0].gain(), dec!(10));
+    }
+
+    #[test]
+    fn reports_are_split_on_sixth_of_april() {
+        let events = vec![
+            MatchingEvent::acquisition(
+                datetime!(2024-01-10 00:00 UTC),
+                1,
+                dec!(20),
+                dec!(20),
+                dec!(0),
+            ),
+            MatchingEvent::disposal(
+                datetime!(2024-04-05 00:00 UTC),
+                1,
+                dec!(10),
+                dec!(30),
+                dec!(1),
+            ),
+            MatchingEvent::disposal(
+                datetime!(2024-04-06 00:00 UTC),
+                1,
+                dec!(10),
+                dec!(5),
+                dec!(0),
+            ),
+        ];
+
+        let reports = build_tax_year_reports(match_disposals(events), vec![]).unwrap();
+
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0].tax_year, 2023);
+        assert_eq!(reports[0].proceeds, dec!(30));
+        assert_eq!(reports[0].allowable_cost, dec!(11));
+        assert_eq!(reports[0].gain, dec!(19));
+        assert_eq!(reports[1].tax_year, 2024);
+        assert_eq!(reports[1].start_date, date!(2024 - 04 - 06));
+        assert_eq!(reports[1].gain, dec!(-5));
+    }
+
+    #[test]
+    fn dividends_are_totalled_per_tax_year() {
+        let dividends = vec![
+            DividendIncomeDto {
+                date: date!(2024 - 04 - 05),
+                dividends: dec!(10),
+                withholding_tax: dec!(0),
+            },
+            DividendIncomeDto {
+                date: date!(2024 - 05 - 01),
+                dividends: dec!(20),
+                withholding_tax: dec!(0),
+            },
+            DividendIncomeDto {
+                date: date!(2024 - 05 - 01),
+                dividends: dec!(0),
+                withholding_tax: dec!(3),
+            },
+        ];
+
+        let reports = build_tax_year_reports(vec![], dividends).unwrap();
+
+        assert_eq!(reports.len(), 2);
+        assert_eq!(reports[0].tax_year, 2023);
+        assert_eq!(reports[0].dividends, dec!(10));
+        assert_eq!(reports[1].tax_year, 2024);
+        assert_eq!(reports[1].dividends, dec!(20));
+        assert_eq!(reports[1].withholding_tax, dec!(3));
+        assert!(reports[1].disposals.is_empty());
+        assert_eq!(reports[1].gain, dec!(0));
+    }
+}
@@ -0,0 +1,42 @@
+use anyhow::Context;
+use time::{Date, Duration, Month};
+
+/// UK tax years run from 6 April to 5 April and are labelled by the year they
+/// start in, so 2024 covers 2024-04-06 to 2025-04-05.
+pub fn tax_year_of(date: Date) -> i32 {
+    if (u8::from(date.month()), date.day()) >= (u8::from(Month::April), 6) {
+        date.year()
+    } else {
+        date.year() - 1
+    }
+}
+
+pub fn tax_year_bounds(tax_year: i32) -> anyhow::Result<(Date, Date)> {
+    let start = Date::from_calendar_date(tax_year, Month::April, 6)
+        .with_context(|| format!("Invalid tax year {tax_year}"))?;
+    let end = Date::from_calendar_date(tax_year + 1, Month::April, 6)
+        .with_context(|| format!("Invalid tax year {tax_year}"))?
+        - Duration::days(1);
+    Ok((start, end))
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::date;
+
+    use super::*;
+
+    #[test]
+    fn tax_year_starts_on_sixth_of_april() {
+        assert_eq!(tax_year_of(date!(2024 - 04 - 05)), 2023);
+        assert_eq!(tax_year_of(date!(2024 - 04 - 06)), 2024);
+        assert_eq!(tax_year_of(date!(2025 - 01 - 31)), 2024);
+    }
+
+    #[test]
+    fn tax_year_bounds_cover_sixth_to_fifth() {
+        let (start, end) = tax_year_bounds(2024).unwrap();
+        assert_eq!(start, date!(2024 - 04 - 06));
+        assert_eq!(end, date!(2025 - 04 - 05));
+    }
+}
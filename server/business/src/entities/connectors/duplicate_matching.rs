@@ -0,0 +1,284 @@
+use std::collections::{HashMap, HashSet};
+
+use rust_decimal::Decimal;
+use time::Date;
+use uuid::Uuid;
+
+use crate::entities::subscriptions::normalize_description;
+
+/// Furthest apart, in days, a manual entry and its imported counterpart may be dated.
+/// Card payments often settle a few days after the purchase the user typed in.
+pub(crate) const DATE_WINDOW_DAYS: i64 = 4;
+/// Score from which a sole candidate is linked without asking.
+const AUTO_LINK_THRESHOLD: f32 = 0.85;
+/// Score from which a candidate is shown to the user for confirmation.
+const REVIEW_THRESHOLD: f32 = 0.55;
+/// A linked candidate must beat every rival pairing by this much.
+const AMBIGUITY_MARGIN: f32 = 0.1;
+/// Candidates kept per ambiguous import.
+const MAX_CANDIDATES: usize = 3;
+
+/// Matching amount and currency are required; the rest is weighed.
+const AMOUNT_WEIGHT: f32 = 0.3;
+const DATE_WEIGHT: f32 = 0.25;
+const ACCOUNT_WEIGHT: f32 = 0.2;
+const DESCRIPTION_WEIGHT: f32 = 0.25;
+
+/// The cash leg of a provider transaction about to be imported.
+#[derive(Clone, Debug)]
+pub(crate) struct ImportedMovement {
+    pub external_id: String,
+    pub account_id: Uuid,
+    pub asset_id: i32,
+    pub amount: Decimal,
+    pub date: Date,
+    pub description: String,
+    pub embedding: Option<Vec<f32>>,
+}
+
+/// What a transaction the user entered by hand moved in one account and asset.
+#[derive(Clone, Debug)]
+pub(crate) struct ExistingMovement {
+    pub transaction_id: Uuid,
+    pub account_id: Uuid,
+    pub asset_id: i32,
+    pub amount: Decimal,
+    pub date: Date,
+    pub description: Option<String>,
+    pub embedding: Option<Vec<f32>>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub(crate) struct DuplicateMatches {
+    /// Imports that are the same movement as an existing transaction, by external id.
+    pub linked: HashMap<String, Uuid>,
+    /// Imports that might be, with their candidates best first.
+    pub ambiguous: HashMap<String, Vec<(Uuid, f32)>>,
+}
+
+/// Whether the existing transaction can be the imported one at all: same currency asset,
+/// same amount, close enough in time.
+pub(crate) fn is_comparable(imported: &ImportedMovement, existing: &ExistingMovement) -> bool {
+    imported.asset_id == existing.asset_id
+        && imported.amount == existing.amount
+        && (imported.date - existing.date).whole_days().abs() <= DATE_WINDOW_DAYS
+}
+
+/// How likely, from 0 to 1, the existing transaction is the imported one. `None` when
+/// they are not comparable.
+pub(crate) fn score(imported: &ImportedMovement, existing: &ExistingMovement) -> Option<f32> {
+    if !is_comparable(imported, existing) {
+        return None;
+    }
+    let days = (imported.date - existing.date).whole_days().abs() as f32;
+    let date = 1.0 - days / (DATE_WINDOW_DAYS + 1) as f32;
+    let account = if imported.account_id == existing.account_id {
+        1.0
+    } else {
+        0.0
+    };
+    let description = description_similarity(imported, existing);
+
+    Some(
+        AMOUNT_WEIGHT
+            + DATE_WEIGHT * date
+            + ACCOUNT_WEIGHT * account
+            + DESCRIPTION_WEIGHT * description,
+    )
+}
+
+/// Pairs imports with existing transactions one to one, strongest first. A pairing is
+/// linked only when it clears the auto-link threshold and neither side has a close rival;
+/// two identical coffees against one manual entry are left for the user to tell apart.
+pub(crate) fn match_duplicates(
+    imported: &[ImportedMovement],
+    existing: &[ExistingMovement],
+) -> DuplicateMatches {
+    let mut pairs: Vec<(usize, usize, f32)> = Vec::new();
+    for (i, imported) in imported.iter().enumerate() {
+        for (e, existing) in existing.iter().enumerate() {
+            if let Some(score) = score(imported, existing).filter(|s| *s >= REVIEW_THRESHOLD) {
+                pairs.push((i, e, score));
+            }
+        }
+    }
+    pairs.sort_by(|a, b| b.2.total_cmp(&a.2));
+
+    let rival = |pair: &(usize, usize, f32)| {
+        pairs
+            .iter()
+            .filter(|other| (other.0 == pair.0) != (other.1 == pair.1))
+            .map(|other| other.2)
+            .fold(f32::MIN, f32::max)
+    };
+
+    let mut matches = DuplicateMatches::default();
+    let mut linked_imports: HashSet<usize> = HashSet::new();
+    let mut claimed: HashSet<usize> = HashSet::new();
+    for pair in &pairs {
+        let (i, e, score) = *pair;
+        if linked_imports.contains(&i) || claimed.contains(&e) {
+            continue;
+        }
+        if score >= AUTO_LINK_THRESHOLD && rival(pair) < score - AMBIGUITY_MARGIN {
+            linked_imports.insert(i);
+            claimed.insert(e);
+            matches
+                .linked
+                .insert(imported[i].external_id.clone(), existing[e].transaction_id);
+        }
+    }
+
+    for (i, e, score) in pairs {
+        if linked_imports.contains(&i) || claimed.contains(&e) {
+            continue;
+        }
+        let candidates = matches
+            .ambiguous
+            .entry(imported[i].external_id.clone())
+            .or_default();
+        if candidates.len() < MAX_CANDIDATES {
+            candidates.push((existing[e].transaction_id, score));
+        }
+    }
+    matches
+}
+
+/// Cosine similarity of the description embeddings, or the overlap of the normalized
+/// description words when either side has not been embedded.
+fn description_similarity(imported: &ImportedMovement, existing: &ExistingMovement) -> f32 {
+    if let (Some(a), Some(b)) = (&imported.embedding, &existing.embedding) {
+        if let Some(similarity) = cosine_similarity(a, b) {
+            return similarity.max(0.0);
+        }
+    }
+    let Some(description) = &existing.description else {
+        return 0.0;
+    };
+    let words = |text: &str| -> HashSet<String> {
+        normalize_description(text)
+            .split(' ')
+            .filter(|word| !word.is_empty())
+            .map(str::to_string)
+            .collect()
+    };
+    let (a, b) = (words(&imported.description), words(description));
+    let union = a.union(&b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(&b).count() as f32 / union as f32
+}
+
+fn cosine_similarity(a: &[f32], b: &[f32]) -> Option<f32> {
+    if a.len() != b.len() || a.is_empty() {
+        return None;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b: f32 = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return None;
+    }
+    Some(dot / (norm_a * norm_b))
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use time::macros::date;
+
+    use super::*;
+
+    const ACCOUNT: Uuid = Uuid::from_u128(1);
+    const OTHER_ACCOUNT: Uuid = Uuid::from_u128(2);
+
+    fn imported(external_id: &str, date: Date, description: &str) -> ImportedMovement {
+        ImportedMovement {
+            external_id: external_id.to_string(),
+            account_id: ACCOUNT,
+            asset_id: 45,
+            amount: dec!(-12.40),
+            date,
+            description: description.to_string(),
+            embedding: None,
+        }
+    }
+
+    fn existing(id: u128, account_id: Uuid, date: Date, description: &str) -> ExistingMovement {
+        ExistingMovement {
+            transaction_id: Uuid::from_u128(id),
+            account_id,
+            asset_id: 45,
+            amount: dec!(-12.40),
+            date,
+            description: Some(description.to_string()),
+            embedding: None,
+        }
+    }
+
+    #[test]
+    fn amount_currency_and_date_window_are_required() {
+        let import = imported("a", date!(2026 - 03 - 10), "Tesco");
+        let mut manual = existing(10, ACCOUNT, date!(2026 - 03 - 10), "Tesco");
+        assert!(score(&import, &manual).is_some());
+
+        manual.amount = dec!(-12.41);
+        assert!(score(&import, &manual).is_none());
+
+        let mut manual = existing(10, ACCOUNT, date!(2026 - 03 - 15), "Tesco");
+        assert!(score(&import, &manual).is_none());
+        manual.date = date!(2026 - 03 - 14);
+        manual.asset_id = 140;
+        assert!(score(&import, &manual).is_none());
+    }
+
+    #[test]
+    fn links_a_clear_match_and_leaves_weak_ones_for_review() {
+        let imports = [
+            imported("card-1", date!(2026 - 03 - 12), "TESCO STORES 2231"),
+            imported("card-2", date!(2026 - 03 - 12), "Pret A Manger"),
+        ];
+        let manual = [
+            existing(10, ACCOUNT, date!(2026 - 03 - 10), "Tesco stores"),
+            existing(20, OTHER_ACCOUNT, date!(2026 - 03 - 12), "Pret lunch"),
+        ];
+
+        let matches = match_duplicates(&imports, &manual);
+        assert_eq!(
+            matches.linked,
+            HashMap::from([("card-1".to_string(), Uuid::from_u128(10))])
+        );
+        assert_eq!(matches.ambiguous.len(), 1);
+        let candidates = &matches.ambiguous["card-2"];
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].0, Uuid::from_u128(20));
+    }
+
+    #[test]
+    fn identical_imports_against_one_entry_are_not_linked() {
+        let imports = [
+            imported("coffee-1", date!(2026 - 03 - 12), "Costa Coffee"),
+            imported("coffee-2", date!(2026 - 03 - 12), "Costa Coffee"),
+        ];
+        let manual = [existing(10, ACCOUNT, date!(2026 - 03 - 12), "Costa coffee")];
+
+        let matches = match_duplicates(&imports, &manual);
+        assert!(matches.linked.is_empty());
+        assert_eq!(matches.ambiguous.len(), 2);
+    }
+
+    #[test]
+    fn embeddings_take_precedence_over_words() {
+        let mut import = imported("a", date!(2026 - 03 - 12), "AMZN Mktp UK*2X4");
+        let mut manual = existing(10, ACCOUNT, date!(2026 - 03 - 12), "new headphones");
+        let without = score(&import, &manual).unwrap();
+
+        import.embedding = Some(vec![0.6, 0.8, 0.0]);
+        manual.embedding = Some(vec![0.6, 0.8, 0.1]);
+        let with = score(&import, &manual).unwrap();
+
+        assert!(without < AUTO_LINK_THRESHOLD);
+        assert!(with >= AUTO_LINK_THRESHOLD);
+    }
+}
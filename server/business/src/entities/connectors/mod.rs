@@ -1,2 +1,4 @@
 pub(crate) mod connector_transaction_batch;
+pub(crate) mod duplicate_matching;
+pub(crate) mod provider_balance_history;
 pub(crate) mod provider_transaction_import;
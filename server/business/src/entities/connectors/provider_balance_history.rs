@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use crate::dtos::{
+    connectors::{ProviderBalanceLineDto, ProviderBalanceSnapshotDto},
+    net_worth::entries_interval_sum_dto::EntriesIntervalSumDto,
+};
+
+/// Sums the lines of a balance per asset, leaving out the ones that resolved to no asset.
+pub(crate) fn resolved_balances(lines: &[ProviderBalanceLineDto]) -> HashMap<i32, Decimal> {
+    let mut balances: HashMap<i32, Decimal> = HashMap::new();
+    for line in lines {
+        if let Some(asset_id) = line.asset_id {
+            *balances.entry(asset_id).or_default() += line.quantity;
+        }
+    }
+    balances
+}
+
+/// Restates provider balance snapshots as the movements that lead from one snapshot to the
+/// next, so they can be charted like ledger entries. Each provider account is followed on
+/// its own; an asset missing from a later snapshot of the same account is taken as sold
+/// off. Lines that resolved to no asset are left out.
+///
+/// Snapshots must be ordered by fetch time. The movements come out in the same order.
+pub(crate) fn snapshot_movements(
+    snapshots: &[ProviderBalanceSnapshotDto],
+) -> Vec<EntriesIntervalSumDto> {
+    let mut previous: HashMap<Uuid, HashMap<i32, Decimal>> = HashMap::new();
+    let mut movements = Vec::new();
+
+    for snapshot in snapshots {
+        let current = resolved_balances(&snapshot.lines);
+        let held = previous.entry(snapshot.provider_account_ref).or_default();
+        let mut asset_ids: Vec<i32> = held.keys().chain(current.keys()).copied().collect();
+        asset_ids.sort_unstable();
+        asset_ids.dedup();
+
+        for asset_id in asset_ids {
+            let before = held.get(&asset_id).copied().unwrap_or_default();
+            let after = current.get(&asset_id).copied().unwrap_or_default();
+            if after != before {
+                movements.push(EntriesIntervalSumDto {
+                    asset_id,
+                    quantity: after - before,
+                    time: snapshot.fetched_at,
+                });
+            }
+        }
+        *held = current;
+    }
+
+    movements
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use time::macros::datetime;
+    use time::OffsetDateTime;
+
+    use crate::dtos::connectors::ProviderBalanceKindDto;
+
+    use super::*;
+
+    const ACCOUNT: Uuid = Uuid::from_u128(1);
+    const OTHER_ACCOUNT: Uuid = Uuid::from_u128(2);
+
+    fn snapshot(
+        provider_account_ref: Uuid,
+        fetched_at: OffsetDateTime,
+        lines: &[(Option<i32>, Decimal)],
+    ) -> ProviderBalanceSnapshotDto {
+        ProviderBalanceSnapshotDto {
+            provider_account_ref,
+            fetched_at,
+            lines: lines
+                .iter()
+                .map(|(asset_id, quantity)| ProviderBalanceLineDto {
+                    kind: ProviderBalanceKindDto::Cash,
+                    asset_identifier: "GBP".to_string(),
+                    asset_id: *asset_id,
+                    quantity: *quantity,
+                })
+                .collect(),
+        }
+    }
+
+    fn as_tuples(movements: Vec<EntriesIntervalSumDto>) -> Vec<(i32, Decimal, OffsetDateTime)> {
+        movements
+            .into_iter()
+            .map(|m| (m.asset_id, m.quantity, m.time))
+            .collect()
+    }
+
+    #[test]
+    fn first_snapshot_opens_the_balance() {
+        let at = datetime!(2026-10-01 08:00 UTC);
+        let movements = snapshot_movements(&[snapshot(ACCOUNT, at, &[(Some(1), dec!(120.50))])]);
+
+        assert_eq!(as_tuples(movements), vec![(1, dec!(120.50), at)]);
+    }
+
+    #[test]
+    fn later_snapshots_move_by_the_difference() {
+        let first = datetime!(2026-10-01 08:00 UTC);
+        let second = datetime!(2026-10-02 08:00 UTC);
+        let third = datetime!(2026-10-03 08:00 UTC);
+        let movements = snapshot_movements(&[
+            snapshot(ACCOUNT, first, &[(Some(1), dec!(100))]),
+            snapshot(ACCOUNT, second, &[(Some(1), dec!(100))]),
+            snapshot(ACCOUNT, third, &[(Some(1), dec!(75))]),
+        ]);
+
+        assert_eq!(
+            as_tuples(movements),
+            vec![(1, dec!(100), first), (1, dec!(-25), third)]
+        );
+    }
+
+    #[test]
+    fn asset_missing_from_later_snapshot_is_closed() {
+        let first = datetime!(2026-10-01 08:00 UTC);
+        let second = datetime!(2026-10-02 08:00 UTC);
+        let movements = snapshot_movements(&[
+            snapshot(ACCOUNT, first, &[(Some(1), dec!(10)), (Some(2), dec!(3))]),
+            snapshot(ACCOUNT, second, &[(Some(1), dec!(10))]),
+        ]);
+
+        assert_eq!(
+            as_tuples(movements),
+            vec![
+                (1, dec!(10), first),
+                (2, dec!(3), first),
+                (2, dec!(-3), second)
+            ]
+        );
+    }
+
+    #[test]
+    fn provider_accounts_are_followed_separately() {
+        let first = datetime!(2026-10-01 08:00 UTC);
+        let second = datetime!(2026-10-01 09:00 UTC);
+        let movements = snapshot_movements(&[
+            snapshot(ACCOUNT, first, &[(Some(1), dec!(50))]),
+            snapshot(OTHER_ACCOUNT, second, &[(Some(1), dec!(20))]),
+        ]);
+
+        assert_eq!(
+            as_tuples(movements),
+            vec![(1, dec!(50), first), (1, dec!(20), second)]
+        );
+    }
+
+    #[test]
+    fn unresolved_lines_are_left_out() {
+        let at = datetime!(2026-10-01 08:00 UTC);
+        let movements = snapshot_movements(&[snapshot(
+            ACCOUNT,
+            at,
+            &[(None, dec!(5)), (Some(1), dec!(1)), (Some(1), dec!(2))],
+        )]);
+
+        assert_eq!(as_tuples(movements), vec![(1, dec!(3), at)]);
+    }
+}
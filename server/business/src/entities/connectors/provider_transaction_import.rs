@@ -1,21 +1,86 @@
-use connectors::models::transaction::ProviderTransaction;
+use std::collections::HashMap;
+
+use connectors::models::transaction::{ProviderTransaction, ProviderTransactionKind};
 use uuid::Uuid;
 
 use crate::dtos::entry_dto::EntryDto;
 use crate::dtos::transaction_dto::{
-    AssetPurchaseMetadataDto, AssetSaleMetadataDto, CashDividendMetadataDto,
-    RegularTransactionMetadataDto, TransactionDto, TransactionTypeDto, TransactionVisibilityDto,
+    AccountFeesMetadataDto, AssetDividendMetadataDto, AssetPurchaseMetadataDto,
+    AssetSaleMetadataDto, AssetTradeMetadataDto, AssetTransferInMetadataDto,
+    AssetTransferOutMetadataDto, CashDividendMetadataDto, CashTransferInMetadataDto,
+    CashTransferOutMetadataDto, RegularTransactionMetadataDto, TransactionDto, TransactionTypeDto,
+    TransactionVisibilityDto,
 };
+use crate::entities::connectors::duplicate_matching::ImportedMovement;
+use crate::entities::transaction_rules::{AppliedRules, RuleSet};
 use crate::entities::transactions::metadata::ConnectorLinkMeta;
 use crate::entities::transactions::transaction::Transaction;
 use crate::entities::transactions::transaction_types::create_transaction_from_dto;
 
-const IMPORT_CATEGORY_ID: i32 = 76;
+/// The category imported transactions land in until a rule or the user categorizes them.
+pub(crate) const IMPORT_CATEGORY_ID: i32 = 76;
 
 const TICKER_ALIASES: &[(&str, &str)] = &[("FB_US_EQ", "META.NASDAQ"), ("JAYl_EQ", "S5WA.F")];
 
+/// Sverto tickers a provider's instrument identifier may correspond to, most likely first.
+pub(crate) fn instrument_ticker_candidates(provider_ticker: &str) -> Vec<String> {
+    if let Some((_, alias)) = TICKER_ALIASES
+        .iter()
+        .find(|(from, _)| *from == provider_ticker)
+    {
+        return vec![(*alias).to_string()];
+    }
+
+    let stripped = provider_ticker
+        .strip_suffix("_EQ")
+        .unwrap_or(provider_ticker);
+
+    if let Some(base) = stripped.strip_suffix("_US") {
+        return vec![format!("{base}.NASDAQ"), format!("{base}.NYSE")];
+    }
+    if let Some(base) = stripped.strip_suffix('l') {
+        if !base.is_empty()
+            && base
+                .chars()
+                .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '.')
+        {
+            return vec![format!("{base}.LSE")];
+        }
+    }
+    vec![stripped.to_string()]
+}
+
+/// Asset of a provider instrument identifier: the user's own alias wins over the built-in
+/// candidates, which are looked up in `resolved` (ticker to asset id).
+pub(crate) fn resolve_instrument(
+    provider_ticker: &str,
+    user_aliases: &HashMap<String, i32>,
+    resolved: &HashMap<String, i32>,
+) -> Option<i32> {
+    if let Some(asset_id) = user_aliases.get(provider_ticker) {
+        return Some(*asset_id);
+    }
+    instrument_ticker_candidates(provider_ticker)
+        .iter()
+        .find_map(|candidate| resolved.get(candidate).copied())
+}
+
+/// Asset of a provider currency code, honouring the user's aliases like
+/// [`resolve_instrument`].
+pub(crate) fn resolve_currency(
+    currency: &str,
+    user_aliases: &HashMap<String, i32>,
+    resolved: &HashMap<String, i32>,
+) -> Option<i32> {
+    user_aliases
+        .get(currency)
+        .or_else(|| resolved.get(currency))
+        .copied()
+}
+
 pub(crate) enum TransactionImportOutcome {
-    Ready(Transaction),
+    /// The transaction, and what the user's rules did to it.
+    Ready(Transaction, Option<AppliedRules>),
     Unresolvable(&'static str),
 }
 
@@ -25,6 +90,7 @@ pub(crate) struct ProviderTransactionImport {
     account_id: Uuid,
     visibility: TransactionVisibilityDto,
     binding_id: Uuid,
+    held_for_review: bool,
 }
 
 impl ProviderTransactionImport {
@@ -39,6 +105,7 @@ impl ProviderTransactionImport {
             account_id,
             visibility,
             binding_id,
+            held_for_review: false,
         }
     }
 
@@ -46,6 +113,10 @@ impl ProviderTransactionImport {
         &self.transaction.external_id
     }
 
+    pub fn transaction(&self) -> &ProviderTransaction {
+        &self.transaction
+    }
+
     pub fn external_hash(&self) -> String {
         self.transaction.external_hash()
     }
@@ -54,35 +125,49 @@ impl ProviderTransactionImport {
         &self.transaction.currency
     }
 
-    pub fn instrument_ticker_candidates(&self) -> Vec<String> {
-        let Some(provider_ticker) = self.transaction.asset_identifier.as_deref() else {
-            return vec![];
-        };
+    /// Whether the import takes coins out to somewhere the provider cannot see. Booked
+    /// as is it would be a disposal, while it is most often the user moving coins to
+    /// their own wallet, so it waits for review until it is paired with that deposit.
+    pub fn is_unpaired_withdrawal(&self) -> bool {
+        self.transaction.kind == ProviderTransactionKind::TransferOut
+    }
 
-        if let Some((_, alias)) = TICKER_ALIASES
-            .iter()
-            .find(|(from, _)| *from == provider_ticker)
-        {
-            return vec![(*alias).to_string()];
-        }
+    /// Keeps the transaction back as a ghost for the user to review, whatever the binding's
+    /// write mode. Rules do not change the visibility of a held transaction.
+    pub fn hold_for_review(&mut self) {
+        self.visibility = TransactionVisibilityDto::Ghost;
+        self.held_for_review = true;
+    }
 
-        let stripped = provider_ticker
-            .strip_suffix("_EQ")
-            .unwrap_or(provider_ticker);
+    /// Whether the import moves only cash, with no instrument involved.
+    pub fn is_cash_movement(&self) -> bool {
+        self.transaction.asset_identifier.is_none() && self.transaction.quantity.is_none()
+    }
 
-        if let Some(base) = stripped.strip_suffix("_US") {
-            return vec![format!("{base}.NASDAQ"), format!("{base}.NYSE")];
-        }
-        if let Some(base) = stripped.strip_suffix('l') {
-            if !base.is_empty()
-                && base
-                    .chars()
-                    .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '.')
-            {
-                return vec![format!("{base}.LSE")];
-            }
+    /// The import as a plain movement of cash, which is all a manual entry it duplicates
+    /// can be compared on. `None` for anything involving an instrument.
+    pub fn cash_movement(&self, cash_asset_id: i32) -> Option<ImportedMovement> {
+        if !self.is_cash_movement() {
+            return None;
         }
-        vec![stripped.to_string()]
+        let tx = &self.transaction;
+        Some(ImportedMovement {
+            external_id: tx.external_id.clone(),
+            account_id: self.account_id,
+            asset_id: cash_asset_id,
+            amount: tx.amount,
+            date: tx.date.date(),
+            description: tx.description.clone(),
+            embedding: None,
+        })
+    }
+
+    pub fn instrument_ticker_candidates(&self) -> Vec<String> {
+        self.transaction
+            .asset_identifier
+            .as_deref()
+            .map(instrument_ticker_candidates)
+            .unwrap_or_default()
     }
 
     pub fn try_into_transaction(
@@ -90,16 +175,23 @@ impl ProviderTransactionImport {
         user_id: Uuid,
         cash_asset_id: Option<i32>,
         instrument_asset_id: Option<i32>,
+        rules: &RuleSet,
     ) -> anyhow::Result<TransactionImportOutcome> {
         match self.build_dto(cash_asset_id, instrument_asset_id) {
-            Ok(dto) => {
+            Ok(mut dto) => {
+                let applied = rules.apply(
+                    &mut dto,
+                    self.transaction.counterparty.as_deref(),
+                    self.held_for_review,
+                );
                 let mut entity = create_transaction_from_dto(dto, user_id)?;
                 entity.set_connector_link(Some(ConnectorLinkMeta {
                     binding_id: self.binding_id,
                     external_id: self.transaction.external_id.clone(),
                     external_hash: self.external_hash(),
+                    counterparty: self.transaction.counterparty.clone(),
                 }));
-                Ok(TransactionImportOutcome::Ready(entity))
+                Ok(TransactionImportOutcome::Ready(entity, applied))
             }
             Err(reason) => Ok(TransactionImportOutcome::Unresolvable(reason)),
         }
@@ -115,7 +207,80 @@ impl ProviderTransactionImport {
             return Err("currency has no matching asset");
         };
 
-        let transaction_type = match (tx.quantity, tx.asset_identifier.as_deref()) {
+        let transaction_type = match tx.kind {
+            ProviderTransactionKind::Inferred => {
+                self.inferred_type(cash_asset_id, instrument_asset_id)?
+            }
+            ProviderTransactionKind::Trade => {
+                let Some(quantity) = tx.quantity.filter(|quantity| !quantity.is_zero()) else {
+                    return Err("zero-quantity trade");
+                };
+                let Some(instrument_asset_id) = instrument_asset_id else {
+                    return Err("instrument has no matching asset");
+                };
+                let instrument = EntryDto::new(instrument_asset_id, self.account_id, quantity);
+                let cash = EntryDto::new(cash_asset_id, self.account_id, tx.amount);
+                let (outgoing_entry, incoming_entry) = if quantity.is_sign_positive() {
+                    (cash, instrument)
+                } else {
+                    (instrument, cash)
+                };
+                TransactionTypeDto::AssetTrade(AssetTradeMetadataDto {
+                    outgoing_entry,
+                    incoming_entry,
+                })
+            }
+            ProviderTransactionKind::TransferIn => {
+                TransactionTypeDto::AssetTransferIn(AssetTransferInMetadataDto {
+                    entry: EntryDto::new(cash_asset_id, self.account_id, tx.amount),
+                })
+            }
+            ProviderTransactionKind::TransferOut => {
+                TransactionTypeDto::AssetTransferOut(AssetTransferOutMetadataDto {
+                    entry: EntryDto::new(cash_asset_id, self.account_id, tx.amount),
+                })
+            }
+            ProviderTransactionKind::CashTransferIn => {
+                TransactionTypeDto::CashTransferIn(CashTransferInMetadataDto {
+                    entry: EntryDto::new(cash_asset_id, self.account_id, tx.amount),
+                })
+            }
+            ProviderTransactionKind::CashTransferOut => {
+                TransactionTypeDto::CashTransferOut(CashTransferOutMetadataDto {
+                    entry: EntryDto::new(cash_asset_id, self.account_id, tx.amount),
+                })
+            }
+            ProviderTransactionKind::Reward => {
+                TransactionTypeDto::AssetDividend(AssetDividendMetadataDto {
+                    entry: EntryDto::new(cash_asset_id, self.account_id, tx.amount),
+                })
+            }
+            ProviderTransactionKind::Fee => {
+                TransactionTypeDto::AccountFees(AccountFeesMetadataDto {
+                    entry: EntryDto::new(cash_asset_id, self.account_id, tx.amount),
+                })
+            }
+        };
+
+        Ok(TransactionDto {
+            transaction_id: None,
+            date: tx.date,
+            visibility: self.visibility,
+            fee_entries: vec![],
+            transaction_type,
+        })
+    }
+
+    /// Type of a transaction whose mapper did not name one: a purchase or sale when it
+    /// carries a quantity, a cash dividend when it only names an instrument, otherwise a
+    /// regular cash movement.
+    fn inferred_type(
+        &self,
+        cash_asset_id: i32,
+        instrument_asset_id: Option<i32>,
+    ) -> Result<TransactionTypeDto, &'static str> {
+        let tx = &self.transaction;
+        Ok(match (tx.quantity, tx.asset_identifier.as_deref()) {
             (Some(quantity), Some(_)) => {
                 let Some(instrument_asset_id) = instrument_asset_id else {
                     return Err("instrument has no matching asset");
@@ -150,14 +315,135 @@ impl ProviderTransactionImport {
                 entry: EntryDto::new(cash_asset_id, self.account_id, tx.amount),
                 category_id: IMPORT_CATEGORY_ID,
             }),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal::Decimal;
+    use rust_decimal_macros::dec;
+
+    fn resolved() -> HashMap<String, i32> {
+        HashMap::from([
+            ("AAPL.NASDAQ".to_string(), 1),
+            ("VUSA.LSE".to_string(), 2),
+            ("EUR".to_string(), 3),
+        ])
+    }
+
+    #[test]
+    fn resolve_instrument_uses_builtin_candidates() {
+        let aliases = HashMap::new();
+        assert_eq!(
+            resolve_instrument("AAPL_US_EQ", &aliases, &resolved()),
+            Some(1)
+        );
+        assert_eq!(
+            resolve_instrument("VUSAl_EQ", &aliases, &resolved()),
+            Some(2)
+        );
+        assert_eq!(
+            resolve_instrument("UNKNOWN_EQ", &aliases, &resolved()),
+            None
+        );
+    }
+
+    #[test]
+    fn resolve_instrument_prefers_user_alias() {
+        let aliases = HashMap::from([
+            ("AAPL_US_EQ".to_string(), 10),
+            ("UNKNOWN_EQ".to_string(), 11),
+        ]);
+        assert_eq!(
+            resolve_instrument("AAPL_US_EQ", &aliases, &resolved()),
+            Some(10)
+        );
+        assert_eq!(
+            resolve_instrument("UNKNOWN_EQ", &aliases, &resolved()),
+            Some(11)
+        );
+    }
+
+    #[test]
+    fn resolve_currency_prefers_user_alias() {
+        let aliases = HashMap::from([("GBX".to_string(), 12)]);
+        assert_eq!(resolve_currency("EUR", &aliases, &resolved()), Some(3));
+        assert_eq!(resolve_currency("GBX", &aliases, &resolved()), Some(12));
+        assert_eq!(resolve_currency("USD", &aliases, &resolved()), None);
+    }
+
+    fn crypto_import(kind: ProviderTransactionKind, amount: Decimal) -> ProviderTransactionImport {
+        ProviderTransactionImport::new(
+            ProviderTransaction {
+                external_id: "tx-1".to_string(),
+                amount,
+                currency: "EUR".to_string(),
+                date: time::macros::datetime!(2026-03-04 0:00 UTC),
+                description: "Buy 0.5 BTC".to_string(),
+                asset_identifier: Some("BTC".to_string()),
+                quantity: Some(dec!(0.5)),
+                kind,
+                counterparty: None,
+            },
+            Uuid::nil(),
+            TransactionVisibilityDto::Default,
+            Uuid::nil(),
+        )
+    }
+
+    #[test]
+    fn crypto_trade_becomes_asset_trade() {
+        let dto = crypto_import(ProviderTransactionKind::Trade, dec!(-15000))
+            .build_dto(Some(3), Some(7))
+            .unwrap();
+        let TransactionTypeDto::AssetTrade(trade) = dto.transaction_type else {
+            panic!("expected an asset trade");
         };
+        assert_eq!(trade.outgoing_entry.asset_id, 3);
+        assert_eq!(trade.outgoing_entry.quantity, dec!(-15000));
+        assert_eq!(trade.incoming_entry.asset_id, 7);
+        assert_eq!(trade.incoming_entry.quantity, dec!(0.5));
+    }
 
-        Ok(TransactionDto {
-            transaction_id: None,
-            date: tx.date,
-            visibility: self.visibility,
-            fee_entries: vec![],
-            transaction_type,
-        })
+    #[test]
+    fn explicit_kinds_use_the_currency_asset() {
+        for (kind, amount) in [
+            (ProviderTransactionKind::TransferIn, dec!(1)),
+            (ProviderTransactionKind::TransferOut, dec!(-1)),
+            (ProviderTransactionKind::CashTransferIn, dec!(100)),
+            (ProviderTransactionKind::CashTransferOut, dec!(-100)),
+            (ProviderTransactionKind::Reward, dec!(0.1)),
+            (ProviderTransactionKind::Fee, dec!(-0.001)),
+        ] {
+            let dto = crypto_import(kind, amount)
+                .build_dto(Some(7), None)
+                .unwrap();
+            let entry = match dto.transaction_type {
+                TransactionTypeDto::AssetTransferIn(m) => m.entry,
+                TransactionTypeDto::AssetTransferOut(m) => m.entry,
+                TransactionTypeDto::CashTransferIn(m) => m.entry,
+                TransactionTypeDto::CashTransferOut(m) => m.entry,
+                TransactionTypeDto::AssetDividend(m) => m.entry,
+                TransactionTypeDto::AccountFees(m) => m.entry,
+                _ => panic!("unexpected transaction type for {kind:?}"),
+            };
+            assert_eq!((entry.asset_id, entry.quantity), (7, amount));
+        }
+    }
+
+    #[test]
+    fn only_coin_withdrawals_wait_to_be_paired() {
+        assert!(
+            crypto_import(ProviderTransactionKind::TransferOut, dec!(-1)).is_unpaired_withdrawal()
+        );
+        for kind in [
+            ProviderTransactionKind::TransferIn,
+            ProviderTransactionKind::CashTransferOut,
+            ProviderTransactionKind::Fee,
+        ] {
+            assert!(!crypto_import(kind, dec!(-1)).is_unpaired_withdrawal());
+        }
     }
 }
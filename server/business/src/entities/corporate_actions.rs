@@ -0,0 +1,375 @@
+use std::collections::HashMap;
+
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use crate::dtos::corporate_action_dto::{CorporateActionDto, CorporateActionTypeDto};
+use crate::dtos::portfolio::holding::HoldingDto;
+use crate::entities::capital_gains::matching_event::{MatchingEvent, MatchingEventKind};
+use crate::entities::portfolio_overview::investment_transaction::{
+    spin_off::SpinOff, stock_split::StockSplit, ticker_change::TickerChange,
+};
+use crate::entities::portfolio_overview::portfolio::PortfolioAction;
+
+/// The portfolio action replaying `action` over the user's lots.
+pub fn portfolio_action(action: &CorporateActionDto) -> Box<dyn PortfolioAction> {
+    match (action.action_type, action.new_asset_id) {
+        (CorporateActionTypeDto::SpinOff, Some(new_asset_id)) => Box::new(SpinOff {
+            date: action.effective_date,
+            asset_id: action.asset_id,
+            new_asset_id,
+            ratio: action.ratio(),
+            cost_basis_fraction: action.cost_basis_fraction.unwrap_or_default(),
+        }),
+        (CorporateActionTypeDto::TickerChange, Some(new_asset_id)) => Box::new(TickerChange {
+            date: action.effective_date,
+            asset_id: action.asset_id,
+            new_asset_id,
+            ratio: action.ratio(),
+        }),
+        _ => Box::new(StockSplit {
+            date: action.effective_date,
+            asset_id: action.asset_id,
+            ratio: action.ratio(),
+        }),
+    }
+}
+
+/// Restates share-matching events across corporate actions, applied in date order.
+///
+/// Events before a split are expressed in post-split units and events before a ticker
+/// change are moved to the new asset, so later disposals match them. A spin-off moves
+/// `cost_basis_fraction` of the cost still held at the effective date to acquisitions of
+/// the new asset dated like the originals. The moved cost is spread evenly across the
+/// earlier acquisitions rather than following which units were already disposed of.
+pub fn adjust_matching_events(
+    mut events: Vec<MatchingEvent>,
+    actions: &[CorporateActionDto],
+) -> Vec<MatchingEvent> {
+    let mut actions: Vec<&CorporateActionDto> = actions.iter().collect();
+    actions.sort_by_key(|action| action.effective_date);
+
+    for action in actions {
+        let ratio = action.ratio();
+        let is_before =
+            |e: &MatchingEvent| e.asset_id == action.asset_id && e.date < action.effective_date;
+
+        match (action.action_type, action.new_asset_id) {
+            (CorporateActionTypeDto::SpinOff, Some(new_asset_id)) => {
+                let fraction = action.cost_basis_fraction.unwrap_or_default();
+                let (acquired, disposed) = events.iter().filter(|e| is_before(e)).fold(
+                    (Decimal::ZERO, Decimal::ZERO),
+                    |(acquired, disposed), e| match e.kind {
+                        MatchingEventKind::Acquisition => (acquired + e.quantity, disposed),
+                        MatchingEventKind::Disposal => (acquired, disposed + e.quantity),
+                    },
+                );
+                let held = acquired - disposed;
+                if acquired <= Decimal::ZERO || held <= Decimal::ZERO {
+                    continue;
+                }
+
+                let mut spun_off = Vec::new();
+                for event in events
+                    .iter_mut()
+                    .filter(|e| is_before(e) && e.kind == MatchingEventKind::Acquisition)
+                {
+                    // Multiply before dividing to avoid Decimal rounding from the intermediate quotient.
+                    let moved_amount = event.amount * fraction * held / acquired;
+                    let moved_fees = event.fees * fraction * held / acquired;
+                    event.amount -= moved_amount;
+                    event.fees -= moved_fees;
+                    spun_off.push(MatchingEvent::acquisition(
+                        event.date,
+                        new_asset_id,
+                        event.quantity * held * ratio / acquired,
+                        moved_amount,
+                        moved_fees,
+                    ));
+                }
+                events.extend(spun_off);
+            }
+            (CorporateActionTypeDto::TickerChange, Some(new_asset_id)) => {
+                for event in events.iter_mut().filter(|e| is_before(e)) {
+                    event.asset_id = new_asset_id;
+                    event.quantity *= ratio;
+                }
+            }
+            _ => {
+                for event in events.iter_mut().filter(|e| is_before(e)) {
+                    event.quantity *= ratio;
+                }
+            }
+        }
+    }
+
+    events
+}
+
+/// Adds the units corporate actions created or removed to ledger holdings.
+///
+/// Each action comes with the ledger holdings of its asset from before its effective
+/// date. Units earlier actions already added count as held, so chained actions (a split
+/// after a ticker change) build on each other.
+pub fn adjust_holdings(
+    holdings: Vec<HoldingDto>,
+    actions: Vec<(CorporateActionDto, Vec<HoldingDto>)>,
+) -> Vec<HoldingDto> {
+    let mut adjustments: HashMap<(Uuid, i32), Decimal> = HashMap::new();
+
+    let mut actions = actions;
+    actions.sort_by_key(|(action, _)| action.effective_date);
+
+    for (action, before) in actions {
+        let mut held: HashMap<Uuid, Decimal> = adjustments
+            .iter()
+            .filter(|((_, asset_id), _)| *asset_id == action.asset_id)
+            .map(|((account_id, _), units)| (*account_id, *units))
+            .collect();
+        for holding in before
+            .into_iter()
+            .filter(|holding| holding.asset_id == action.asset_id)
+        {
+            *held.entry(holding.account_id).or_default() += holding.units;
+        }
+
+        let ratio = action.ratio();
+        for (account_id, units) in held {
+            match (action.action_type, action.new_asset_id) {
+                (CorporateActionTypeDto::SpinOff, Some(new_asset_id)) => {
+                    *adjustments.entry((account_id, new_asset_id)).or_default() += units * ratio;
+                }
+                (CorporateActionTypeDto::TickerChange, Some(new_asset_id)) => {
+                    *adjustments
+                        .entry((account_id, action.asset_id))
+                        .or_default() -= units;
+                    *adjustments.entry((account_id, new_asset_id)).or_default() += units * ratio;
+                }
+                _ => {
+                    *adjustments
+                        .entry((account_id, action.asset_id))
+                        .or_default() += units * (ratio - Decimal::ONE);
+                }
+            }
+        }
+    }
+
+    let mut adjusted: Vec<HoldingDto> = holdings
+        .into_iter()
+        .map(|mut holding| {
+            if let Some(units) = adjustments.remove(&(holding.account_id, holding.asset_id)) {
+                holding.units += units;
+            }
+            holding
+        })
+        .collect();
+    adjusted.extend(
+        adjustments
+            .into_iter()
+            .map(|((account_id, asset_id), units)| HoldingDto {
+                asset_id,
+                account_id,
+                units,
+            }),
+    );
+    adjusted.retain(|holding| !holding.units.is_zero());
+    adjusted
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use time::macros::datetime;
+    use time::OffsetDateTime;
+
+    use super::*;
+
+    fn action(
+        action_type: CorporateActionTypeDto,
+        effective_date: OffsetDateTime,
+        ratio_to: Decimal,
+        new_asset_id: Option<i32>,
+        cost_basis_fraction: Option<Decimal>,
+    ) -> CorporateActionDto {
+        CorporateActionDto {
+            id: Uuid::new_v4(),
+            asset_id: 1,
+            action_type,
+            effective_date,
+            ratio_from: dec!(1),
+            ratio_to,
+            new_asset_id,
+            cost_basis_fraction,
+            created_at: effective_date,
+        }
+    }
+
+    fn holding(account_id: Uuid, asset_id: i32, units: Decimal) -> HoldingDto {
+        HoldingDto {
+            asset_id,
+            account_id,
+            units,
+        }
+    }
+
+    fn units_of(holdings: &[HoldingDto], account_id: Uuid, asset_id: i32) -> Option<Decimal> {
+        holdings
+            .iter()
+            .find(|h| h.account_id == account_id && h.asset_id == asset_id)
+            .map(|h| h.units)
+    }
+
+    #[test]
+    fn split_restates_earlier_events_in_post_split_units() {
+        let events = vec![
+            MatchingEvent::acquisition(
+                datetime!(2024-01-02 00:00:00 UTC),
+                1,
+                dec!(10),
+                dec!(1000),
+                dec!(5),
+            ),
+            MatchingEvent::disposal(
+                datetime!(2024-09-02 00:00:00 UTC),
+                1,
+                dec!(50),
+                dec!(600),
+                dec!(0),
+            ),
+        ];
+        let split = action(
+            CorporateActionTypeDto::Split,
+            datetime!(2024-06-01 00:00:00 UTC),
+            dec!(10),
+            None,
+            None,
+        );
+
+        let adjusted = adjust_matching_events(events, &[split]);
+
+        assert_eq!(adjusted[0].quantity, dec!(100));
+        assert_eq!(adjusted[0].amount, dec!(1000));
+        assert_eq!(adjusted[1].quantity, dec!(50));
+    }
+
+    #[test]
+    fn ticker_change_moves_earlier_events_to_new_asset() {
+        let events = vec![MatchingEvent::acquisition(
+            datetime!(2024-01-02 00:00:00 UTC),
+            1,
+            dec!(4),
+            dec!(200),
+            dec!(0),
+        )];
+        let rename = action(
+            CorporateActionTypeDto::TickerChange,
+            datetime!(2024-06-01 00:00:00 UTC),
+            dec!(0.5),
+            Some(2),
+            None,
+        );
+
+        let adjusted = adjust_matching_events(events, &[rename]);
+
+        assert_eq!(adjusted[0].asset_id, 2);
+        assert_eq!(adjusted[0].quantity, dec!(2));
+        assert_eq!(adjusted[0].amount, dec!(200));
+    }
+
+    #[test]
+    fn spin_off_moves_cost_of_units_still_held_to_new_asset() {
+        let events = vec![
+            MatchingEvent::acquisition(
+                datetime!(2024-01-02 00:00:00 UTC),
+                1,
+                dec!(10),
+                dec!(1000),
+                dec!(10),
+            ),
+            MatchingEvent::disposal(
+                datetime!(2024-03-01 00:00:00 UTC),
+                1,
+                dec!(5),
+                dec!(750),
+                dec!(0),
+            ),
+        ];
+        let spin_off = action(
+            CorporateActionTypeDto::SpinOff,
+            datetime!(2024-06-01 00:00:00 UTC),
+            dec!(0.5),
+            Some(2),
+            Some(dec!(0.2)),
+        );
+
+        let adjusted = adjust_matching_events(events, &[spin_off]);
+
+        assert_eq!(adjusted.len(), 3);
+        assert_eq!(adjusted[0].amount, dec!(900));
+        assert_eq!(adjusted[0].fees, dec!(9));
+        let child = &adjusted[2];
+        assert_eq!(child.asset_id, 2);
+        assert_eq!(child.date, datetime!(2024-01-02 00:00:00 UTC));
+        assert_eq!(child.quantity, dec!(2.5));
+        assert_eq!(child.amount, dec!(100));
+        assert_eq!(child.fees, dec!(1));
+    }
+
+    #[test]
+    fn holdings_include_units_created_by_corporate_actions() {
+        let account_id = Uuid::new_v4();
+        let split_date = datetime!(2024-06-01 00:00:00 UTC);
+        let spin_off_date = datetime!(2024-09-01 00:00:00 UTC);
+
+        // 10 units held before the split, 5 more bought after it.
+        let holdings = vec![holding(account_id, 1, dec!(15))];
+        let actions = vec![
+            (
+                action(
+                    CorporateActionTypeDto::SpinOff,
+                    spin_off_date,
+                    dec!(0.5),
+                    Some(2),
+                    Some(dec!(0.1)),
+                ),
+                vec![holding(account_id, 1, dec!(15))],
+            ),
+            (
+                action(
+                    CorporateActionTypeDto::Split,
+                    split_date,
+                    dec!(2),
+                    None,
+                    None,
+                ),
+                vec![holding(account_id, 1, dec!(10))],
+            ),
+        ];
+
+        let adjusted = adjust_holdings(holdings, actions);
+
+        assert_eq!(units_of(&adjusted, account_id, 1), Some(dec!(25)));
+        assert_eq!(units_of(&adjusted, account_id, 2), Some(dec!(12.5)));
+    }
+
+    #[test]
+    fn ticker_change_replaces_holding_with_new_asset() {
+        let account_id = Uuid::new_v4();
+        let holdings = vec![holding(account_id, 1, dec!(8))];
+        let actions = vec![(
+            action(
+                CorporateActionTypeDto::TickerChange,
+                datetime!(2024-06-01 00:00:00 UTC),
+                dec!(1),
+                Some(2),
+                None,
+            ),
+            vec![holding(account_id, 1, dec!(8))],
+        )];
+
+        let adjusted = adjust_holdings(holdings, actions);
+
+        assert_eq!(units_of(&adjusted, account_id, 1), None);
+        assert_eq!(units_of(&adjusted, account_id, 2), Some(dec!(8)));
+    }
+}
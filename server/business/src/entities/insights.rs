@@ -0,0 +1,240 @@
+use std::collections::HashMap;
+
+use ai::models::insights::{CategorySpending, PortfolioMover, SpendingSummary};
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use time::{Date, Duration};
+
+use crate::dtos::budgets::BudgetPeriodDto;
+use crate::dtos::insight_digest_dto::InsightPeriodDto;
+
+use super::budgets::{next_period_start, period_start};
+
+fn calendar_period(period: InsightPeriodDto) -> BudgetPeriodDto {
+    match period {
+        InsightPeriodDto::Weekly => BudgetPeriodDto::Weekly,
+        InsightPeriodDto::Monthly => BudgetPeriodDto::Monthly,
+    }
+}
+
+/// The latest period that is over by `today`: its first day and the first day after it.
+pub fn last_completed_period(period: InsightPeriodDto, today: Date) -> (Date, Date) {
+    let end = period_start(calendar_period(period), today);
+    let start = period_start(calendar_period(period), end - Duration::days(1));
+    (start, end)
+}
+
+/// First day of the period before the one starting at `start`.
+pub fn previous_period_start(period: InsightPeriodDto, start: Date) -> Date {
+    period_start(calendar_period(period), start - Duration::days(1))
+}
+
+/// First day after the period starting at `start`.
+pub fn following_period_start(period: InsightPeriodDto, start: Date) -> Date {
+    next_period_start(calendar_period(period), start)
+}
+
+/// Percentage change from `previous` to `current`, `None` when there is nothing to
+/// compare with.
+pub fn change_pct(previous: Decimal, current: Decimal) -> Option<Decimal> {
+    if previous.is_zero() {
+        return None;
+    }
+    Some(((current - previous) / previous.abs() * dec!(100)).round_dp(2))
+}
+
+/// Compares spending per category with the period before. Both sides are net amounts
+/// per category as summed from the ledger, outflows negative; a category with a net
+/// inflow counts as no spending. Keeps the `limit` categories that changed the most.
+pub fn spending_summary(
+    current: &[(String, Decimal)],
+    previous: &[(String, Decimal)],
+    limit: usize,
+) -> SpendingSummary {
+    let spent = |net: Decimal| (-net).max(Decimal::ZERO);
+
+    let mut by_category: HashMap<&str, (Decimal, Decimal)> = HashMap::new();
+    for (category, net) in current {
+        by_category.entry(category).or_default().0 += spent(*net);
+    }
+    for (category, net) in previous {
+        by_category.entry(category).or_default().1 += spent(*net);
+    }
+
+    let total: Decimal = by_category.values().map(|(amount, _)| *amount).sum();
+    let previous_total: Decimal = by_category.values().map(|(_, amount)| *amount).sum();
+
+    let mut categories: Vec<CategorySpending> = by_category
+        .into_iter()
+        .filter(|(_, (amount, previous_amount))| !amount.is_zero() || !previous_amount.is_zero())
+        .map(|(category, (amount, previous_amount))| CategorySpending {
+            category: category.to_string(),
+            amount,
+            previous_amount,
+            change: amount - previous_amount,
+            change_pct: change_pct(previous_amount, amount),
+        })
+        .collect();
+    categories.sort_by(|a, b| {
+        b.change
+            .abs()
+            .cmp(&a.change.abs())
+            .then_with(|| b.amount.cmp(&a.amount))
+            .then_with(|| a.category.cmp(&b.category))
+    });
+    categories.truncate(limit);
+
+    SpendingSummary {
+        total,
+        previous_total,
+        change_pct: change_pct(previous_total, total),
+        categories,
+    }
+}
+
+/// The `limit` assets whose price move changed the user's holdings the most, either way.
+pub fn top_movers(mut movers: Vec<PortfolioMover>, limit: usize) -> Vec<PortfolioMover> {
+    movers.retain(|m| !m.value_change.is_zero());
+    movers.sort_by(|a, b| b.value_change.abs().cmp(&a.value_change.abs()));
+    movers.truncate(limit);
+    movers
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::date;
+
+    use super::*;
+
+    #[test]
+    fn last_completed_period_is_the_one_before_today() {
+        // A Wednesday.
+        let today = date!(2024 - 03 - 13);
+
+        assert_eq!(
+            last_completed_period(InsightPeriodDto::Weekly, today),
+            (date!(2024 - 03 - 04), date!(2024 - 03 - 11))
+        );
+        assert_eq!(
+            last_completed_period(InsightPeriodDto::Monthly, today),
+            (date!(2024 - 02 - 01), date!(2024 - 03 - 01))
+        );
+    }
+
+    #[test]
+    fn last_completed_period_on_the_first_day_of_a_period() {
+        assert_eq!(
+            last_completed_period(InsightPeriodDto::Weekly, date!(2024 - 03 - 11)),
+            (date!(2024 - 03 - 04), date!(2024 - 03 - 11))
+        );
+        assert_eq!(
+            last_completed_period(InsightPeriodDto::Monthly, date!(2024 - 01 - 01)),
+            (date!(2023 - 12 - 01), date!(2024 - 01 - 01))
+        );
+    }
+
+    #[test]
+    fn previous_and_following_periods() {
+        assert_eq!(
+            previous_period_start(InsightPeriodDto::Monthly, date!(2024 - 03 - 01)),
+            date!(2024 - 02 - 01)
+        );
+        assert_eq!(
+            following_period_start(InsightPeriodDto::Monthly, date!(2024 - 02 - 01)),
+            date!(2024 - 03 - 01)
+        );
+        assert_eq!(
+            previous_period_start(InsightPeriodDto::Weekly, date!(2024 - 01 - 01)),
+            date!(2023 - 12 - 25)
+        );
+    }
+
+    #[test]
+    fn change_pct_needs_a_previous_amount() {
+        assert_eq!(change_pct(dec!(200), dec!(250)), Some(dec!(25)));
+        assert_eq!(change_pct(dec!(-200), dec!(-100)), Some(dec!(50)));
+        assert_eq!(change_pct(Decimal::ZERO, dec!(10)), None);
+    }
+
+    #[test]
+    fn spending_summary_compares_categories() {
+        let current = [
+            ("Groceries".to_string(), dec!(-300)),
+            ("Dining".to_string(), dec!(-150)),
+            ("Salary".to_string(), dec!(3000)),
+        ];
+        let previous = [
+            ("Groceries".to_string(), dec!(-280)),
+            ("Travel".to_string(), dec!(-400)),
+        ];
+
+        let summary = spending_summary(&current, &previous, 10);
+
+        assert_eq!(summary.total, dec!(450));
+        assert_eq!(summary.previous_total, dec!(680));
+        assert_eq!(
+            summary
+                .categories
+                .iter()
+                .map(|c| (c.category.as_str(), c.change))
+                .collect::<Vec<_>>(),
+            vec![
+                ("Travel", dec!(-400)),
+                ("Dining", dec!(150)),
+                ("Groceries", dec!(20)),
+            ]
+        );
+        assert_eq!(summary.categories[1].change_pct, None);
+    }
+
+    #[test]
+    fn spending_summary_keeps_the_largest_changes() {
+        let current = [
+            ("A".to_string(), dec!(-10)),
+            ("B".to_string(), dec!(-50)),
+            ("C".to_string(), dec!(-30)),
+        ];
+
+        let summary = spending_summary(&current, &[], 2);
+
+        assert_eq!(summary.total, dec!(90));
+        assert_eq!(summary.change_pct, None);
+        assert_eq!(
+            summary
+                .categories
+                .iter()
+                .map(|c| c.category.as_str())
+                .collect::<Vec<_>>(),
+            vec!["B", "C"]
+        );
+    }
+
+    fn mover(asset_id: i32, value_change: Decimal) -> PortfolioMover {
+        PortfolioMover {
+            asset_id,
+            asset_name: format!("Asset {asset_id}"),
+            ticker: None,
+            start_price: dec!(100),
+            end_price: dec!(100),
+            change_pct: Decimal::ZERO,
+            value_change,
+        }
+    }
+
+    #[test]
+    fn top_movers_rank_by_size_of_the_move() {
+        let movers = vec![
+            mover(1, dec!(20)),
+            mover(2, dec!(-75)),
+            mover(3, Decimal::ZERO),
+            mover(4, dec!(40)),
+        ];
+
+        let ranked: Vec<i32> = top_movers(movers, 2)
+            .into_iter()
+            .map(|m| m.asset_id)
+            .collect();
+
+        assert_eq!(ranked, vec![2, 4]);
+    }
+}
@@ -1,8 +1,19 @@
+pub mod budgets;
+pub mod capital_gains;
 pub mod categories;
 pub(crate) mod connectors;
+pub mod corporate_actions;
 pub mod entries;
+pub mod insights;
 pub mod market_data;
 pub mod net_worth;
 pub mod portfolio_overview;
 pub mod range;
+pub mod reconciliation;
+pub mod recurrence_rule;
+pub(crate) mod recurring_template;
+pub mod spending_alerts;
+pub mod subscriptions;
+pub mod transaction_rules;
 pub mod transactions;
+pub mod transfers;
@@ -10,6 +10,7 @@ use crate::{
     dtos::{
         asset_rate_dto::AssetRateDto,
         assets::{asset_id_dto::AssetIdDto, asset_pair_ids_dto::AssetPairIdsDto},
+        corporate_action_dto::{CorporateActionDto, CorporateActionTypeDto},
         net_worth::entries_interval_sum_dto::EntriesIntervalSumDto,
     },
     entities::range::Range,
@@ -29,6 +30,11 @@ pub struct NetWorthHistory {
 
     last_rates: HashMap<(i32, i32), AssetRateDto>,
     cumulative_sum: HashMap<i32, Decimal>,
+
+    /// End of the first bin, which sums every entry before the range start.
+    opening_cutoff: Option<OffsetDateTime>,
+    /// Rates of an asset before a date are divided by the ratio of a split on that date.
+    split_rate_adjustments: Vec<(i32, OffsetDateTime, Decimal)>,
 }
 
 impl NetWorthHistory {
@@ -43,6 +49,9 @@ impl NetWorthHistory {
             entries_queue: VecDeque::default(),
             last_rates: HashMap::default(),
             cumulative_sum: HashMap::default(),
+            opening_cutoff: (!range.infinite_start())
+                .then(|| date_bin(range.start_time(), range.interval()) + range.interval()),
+            split_rate_adjustments: Vec::new(),
         }
     }
 
@@ -62,6 +71,103 @@ impl NetWorthHistory {
         });
     }
 
+    /// Corporate actions effective before this moment fall into the first bin, whose
+    /// entries are already summed; their effect has to be added with
+    /// [`Self::add_opening_adjustments`] instead of [`Self::add_corporate_actions`].
+    pub fn opening_cutoff(&self) -> Option<OffsetDateTime> {
+        self.opening_cutoff
+    }
+
+    /// Adds units created or removed by corporate actions before [`Self::opening_cutoff`]
+    /// to the opening balance. Call after [`Self::add_entries`].
+    pub fn add_opening_adjustments(&mut self, adjustments: impl Iterator<Item = (i32, Decimal)>) {
+        let Some(opening_cutoff) = self.opening_cutoff else {
+            return;
+        };
+        let opening_time = opening_cutoff - self.interval;
+
+        adjustments.for_each(|(asset_id, quantity)| {
+            self.add_entry_at(asset_id, quantity, opening_time);
+        });
+    }
+
+    /// Restates the history around corporate actions effective after
+    /// [`Self::opening_cutoff`], so the value of a holding does not jump on the
+    /// effective date. Before a split both the entries and the rates of the asset are
+    /// expressed in post-split units. Spin-offs and ticker changes add the units of the
+    /// new asset, and remove the old ones for a ticker change, on the effective date.
+    /// Entries are binned, so the action takes effect at the bin boundary.
+    ///
+    /// Call after [`Self::add_entries`] and before [`Self::add_asset_rates`].
+    pub fn add_corporate_actions(&mut self, actions: &[CorporateActionDto]) {
+        let mut actions: Vec<&CorporateActionDto> = actions
+            .iter()
+            .filter(|action| {
+                self.opening_cutoff
+                    .is_none_or(|cutoff| action.effective_date >= cutoff)
+            })
+            .collect();
+        actions.sort_by_key(|action| action.effective_date);
+
+        for action in actions {
+            let ratio = action.ratio();
+            let date = action.effective_date;
+            let is_before =
+                |e: &EntriesIntervalSumDto| e.asset_id == action.asset_id && e.time < date;
+
+            match (action.action_type, action.new_asset_id) {
+                (CorporateActionTypeDto::SpinOff, Some(new_asset_id)) => {
+                    let held: Decimal = self
+                        .entries_queue
+                        .iter()
+                        .filter(|e| is_before(e))
+                        .map(|e| e.quantity)
+                        .sum();
+                    self.add_entry_at(new_asset_id, held * ratio, date);
+                }
+                (CorporateActionTypeDto::TickerChange, Some(new_asset_id)) => {
+                    let held: Decimal = self
+                        .entries_queue
+                        .iter()
+                        .filter(|e| is_before(e))
+                        .map(|e| e.quantity)
+                        .sum();
+                    self.add_entry_at(action.asset_id, -held, date);
+                    self.add_entry_at(new_asset_id, held * ratio, date);
+                }
+                _ => {
+                    self.entries_queue
+                        .iter_mut()
+                        .filter(|e| is_before(e))
+                        .for_each(|e| e.quantity *= ratio);
+                    self.split_rate_adjustments
+                        .push((action.asset_id, date, ratio));
+                }
+            }
+        }
+    }
+
+    fn add_entry_at(&mut self, asset_id: i32, quantity: Decimal, time: OffsetDateTime) {
+        if quantity.is_zero() {
+            return;
+        }
+
+        self.asset_first_occurances
+            .entry(AssetIdDto(asset_id))
+            .and_modify(|first| *first = min(*first, time))
+            .or_insert(time);
+
+        let index = self.entries_queue.partition_point(|e| e.time <= time);
+        self.entries_queue.insert(
+            index,
+            EntriesIntervalSumDto {
+                asset_id,
+                quantity,
+                time,
+            },
+        );
+    }
+
     pub fn entries_exist(&self) -> bool {
         !self.entries_queue.is_empty()
     }
@@ -74,6 +180,15 @@ impl NetWorthHistory {
             .into_iter()
             .map(|(k, v)| ((k.pair1.0, k.pair2.0), v))
             .collect();
+
+        for (asset_id, date, ratio) in &self.split_rate_adjustments {
+            self.asset_pair_rates
+                .iter_mut()
+                .filter(|((base, _), _)| base == asset_id)
+                .flat_map(|(_, rates)| rates.iter_mut())
+                .filter(|rate| rate.date < *date)
+                .for_each(|rate| rate.rate /= *ratio);
+        }
     }
 
     pub fn get_asset_first_occurance_dates(&self) -> HashMap<AssetIdDto, OffsetDateTime> {
@@ -229,6 +344,17 @@ impl NetWorthHistory {
     }
 }
 
+/// Start of the bin holding `time`, matching `date_bin(interval, time, 'epoch')`.
+pub(crate) fn date_bin(time: OffsetDateTime, interval: Duration) -> OffsetDateTime {
+    let interval_seconds = interval.whole_seconds();
+    if interval_seconds <= 0 {
+        return time;
+    }
+    let seconds = time.unix_timestamp();
+    OffsetDateTime::from_unix_timestamp(seconds - seconds.rem_euclid(interval_seconds))
+        .unwrap_or(time)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::dtos::net_worth::range_dto::RangeDto;
@@ -1909,4 +2035,176 @@ mod tests {
         assert_eq!(result.len(), 1);
         assert_eq!(result[0].rate, dec!(6));
     }
+
+    fn corporate_action(
+        action_type: CorporateActionTypeDto,
+        effective_date: OffsetDateTime,
+        ratio_to: Decimal,
+        new_asset_id: Option<i32>,
+    ) -> CorporateActionDto {
+        CorporateActionDto {
+            id: uuid::Uuid::new_v4(),
+            asset_id: 1,
+            action_type,
+            effective_date,
+            ratio_from: dec!(1),
+            ratio_to,
+            new_asset_id,
+            cost_basis_fraction: (action_type == CorporateActionTypeDto::SpinOff)
+                .then_some(dec!(0.2)),
+            created_at: effective_date,
+        }
+    }
+
+    #[test]
+    fn split_inside_the_range_keeps_history_continuous() {
+        let transactions_queue = vec![EntriesIntervalSumDto {
+            asset_id: 1,
+            quantity: dec!(1),
+            time: datetime!(2023-03-22 12:00:00 UTC),
+        }];
+
+        // Quotes before the split are per old unit.
+        let mut asset_rate_queues: HashMap<(i32, i32), VecDeque<AssetRateDto>> = HashMap::new();
+        asset_rate_queues.insert(
+            (1, 2),
+            vec![
+                AssetRateDto {
+                    rate: dec!(100),
+                    date: datetime!(2023-03-22 12:00:00 UTC),
+                },
+                AssetRateDto {
+                    rate: dec!(10),
+                    date: datetime!(2023-03-22 14:00:00 UTC),
+                },
+            ]
+            .into_iter()
+            .collect(),
+        );
+
+        let mut net_worth_history = make_history(
+            2,
+            datetime!(2023-03-22 12:00:00 UTC),
+            datetime!(2023-03-22 16:00:00 UTC),
+        );
+        net_worth_history.add_entries(transactions_queue.into_iter());
+        net_worth_history.add_corporate_actions(&[corporate_action(
+            CorporateActionTypeDto::Split,
+            datetime!(2023-03-22 14:00:00 UTC),
+            dec!(10),
+            None,
+        )]);
+        add_rates(&mut net_worth_history, asset_rate_queues);
+
+        let result = net_worth_history.calculate_networth_history();
+        assert_eq!(result.len(), 5);
+        assert!(result.iter().all(|r| r.rate == dec!(100)));
+    }
+
+    #[test]
+    fn spin_off_adds_new_asset_on_effective_date() {
+        let transactions_queue = vec![EntriesIntervalSumDto {
+            asset_id: 1,
+            quantity: dec!(2),
+            time: datetime!(2023-03-22 12:00:00 UTC),
+        }];
+
+        let mut asset_rate_queues: HashMap<(i32, i32), VecDeque<AssetRateDto>> = HashMap::new();
+        asset_rate_queues.insert(
+            (1, 2),
+            vec![
+                AssetRateDto {
+                    rate: dec!(100),
+                    date: datetime!(2023-03-22 12:00:00 UTC),
+                },
+                AssetRateDto {
+                    rate: dec!(100),
+                    date: datetime!(2023-03-22 13:00:00 UTC),
+                },
+                AssetRateDto {
+                    rate: dec!(80),
+                    date: datetime!(2023-03-22 14:00:00 UTC),
+                },
+            ]
+            .into_iter()
+            .collect(),
+        );
+        asset_rate_queues.insert(
+            (3, 2),
+            vec![AssetRateDto {
+                rate: dec!(40),
+                date: datetime!(2023-03-22 14:00:00 UTC),
+            }]
+            .into_iter()
+            .collect(),
+        );
+
+        let mut net_worth_history = make_history(
+            2,
+            datetime!(2023-03-22 12:00:00 UTC),
+            datetime!(2023-03-22 15:00:00 UTC),
+        );
+        net_worth_history.add_entries(transactions_queue.into_iter());
+        net_worth_history.add_corporate_actions(&[corporate_action(
+            CorporateActionTypeDto::SpinOff,
+            datetime!(2023-03-22 14:00:00 UTC),
+            dec!(0.5),
+            Some(3),
+        )]);
+        add_rates(&mut net_worth_history, asset_rate_queues);
+
+        assert_eq!(
+            net_worth_history.get_asset_first_occurance_dates()[&AssetIdDto(3)],
+            datetime!(2023-03-22 14:00:00 UTC)
+        );
+
+        let result = net_worth_history.calculate_networth_history();
+        assert_eq!(result.len(), 4);
+        // 2 units at 100 before, 2 units at 80 plus 1 unit at 40 after.
+        assert!(result.iter().all(|r| r.rate == dec!(200)));
+    }
+
+    #[test]
+    fn actions_before_the_opening_bin_are_left_to_opening_adjustments() {
+        let transactions_queue = vec![EntriesIntervalSumDto {
+            asset_id: 1,
+            quantity: dec!(1),
+            time: datetime!(2023-03-22 12:00:00 UTC),
+        }];
+
+        let mut asset_rate_queues: HashMap<(i32, i32), VecDeque<AssetRateDto>> = HashMap::new();
+        asset_rate_queues.insert(
+            (1, 2),
+            vec![AssetRateDto {
+                rate: dec!(10),
+                date: datetime!(2023-03-22 12:00:00 UTC),
+            }]
+            .into_iter()
+            .collect(),
+        );
+
+        let mut net_worth_history = make_history(
+            2,
+            datetime!(2023-03-22 12:00:00 UTC),
+            datetime!(2023-03-22 13:00:00 UTC),
+        );
+        assert_eq!(
+            net_worth_history.opening_cutoff(),
+            Some(datetime!(2023-03-22 13:00:00 UTC))
+        );
+        net_worth_history.add_entries(transactions_queue.into_iter());
+        net_worth_history.add_corporate_actions(&[corporate_action(
+            CorporateActionTypeDto::Split,
+            datetime!(2023-03-20 00:00:00 UTC),
+            dec!(10),
+            None,
+        )]);
+        net_worth_history.add_opening_adjustments([(1, dec!(9))].into_iter());
+        add_rates(&mut net_worth_history, asset_rate_queues);
+
+        let result = net_worth_history.calculate_networth_history();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result[0].rate, dec!(100));
+        assert_eq!(result[1].rate, dec!(100));
+    }
 }
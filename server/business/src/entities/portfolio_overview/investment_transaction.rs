@@ -8,6 +8,10 @@ pub mod asset_transfer_in;
 pub mod asset_transfer_out;
 pub mod cash_balance_transfer;
 pub mod cash_dividend;
+pub mod cash_exchange_transfer;
 pub mod cash_transfer_in;
 pub mod cash_transfer_out;
 pub mod regular_cash_change;
+pub mod spin_off;
+pub mod stock_split;
+pub mod ticker_change;
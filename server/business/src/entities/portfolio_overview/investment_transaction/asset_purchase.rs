@@ -4,9 +4,12 @@ use uuid::Uuid;
 
 use crate::{
     dtos::assets::asset_id_dto::AssetIdDto,
-    entities::portfolio_overview::portfolio::{
-        portfolio_asset_position_dto::PortfolioAssetPosition, Portfolio, PortfolioAction,
-        ReferentialPortfolioAction,
+    entities::{
+        capital_gains::matching_event::MatchingEvent,
+        portfolio_overview::portfolio::{
+            portfolio_asset_position_dto::PortfolioAssetPosition, Portfolio, PortfolioAction,
+            ReferentialPortfolioAction,
+        },
     },
 };
 
@@ -43,6 +46,16 @@ impl PortfolioAction for AssetPurchase {
     fn date(&self) -> OffsetDateTime {
         self.date
     }
+
+    fn matching_events(&self) -> Vec<MatchingEvent> {
+        vec![MatchingEvent::acquisition(
+            self.date,
+            self.instrument_asset_id,
+            self.instrument_units,
+            self.instrument_price * self.instrument_units,
+            self.fees,
+        )]
+    }
 }
 
 impl ReferentialPortfolioAction for AssetPurchase {
@@ -4,8 +4,9 @@ use uuid::Uuid;
 
 use crate::{
     dtos::assets::asset_id_dto::AssetIdDto,
-    entities::portfolio_overview::portfolio::{
-        Portfolio, PortfolioAction, ReferentialPortfolioAction,
+    entities::{
+        capital_gains::matching_event::MatchingEvent,
+        portfolio_overview::portfolio::{Portfolio, PortfolioAction, ReferentialPortfolioAction},
     },
 };
 
@@ -52,6 +53,16 @@ impl PortfolioAction for AssetSale {
     fn date(&self) -> OffsetDateTime {
         self.date
     }
+
+    fn matching_events(&self) -> Vec<MatchingEvent> {
+        vec![MatchingEvent::disposal(
+            self.date,
+            self.instrument_asset_id,
+            self.instrument_units,
+            self.instrument_reference_price * self.instrument_units,
+            self.fees,
+        )]
+    }
 }
 
 #[cfg(test)]
@@ -4,9 +4,12 @@ use uuid::Uuid;
 
 use crate::{
     dtos::assets::asset_id_dto::AssetIdDto,
-    entities::portfolio_overview::portfolio::{
-        portfolio_asset_position_dto::PortfolioAssetPosition, Portfolio, PortfolioAction,
-        ReferentialPortfolioAction,
+    entities::{
+        capital_gains::matching_event::MatchingEvent,
+        portfolio_overview::portfolio::{
+            portfolio_asset_position_dto::PortfolioAssetPosition, Portfolio, PortfolioAction,
+            ReferentialPortfolioAction,
+        },
     },
 };
 
@@ -44,6 +47,28 @@ impl PortfolioAction for AssetTrade {
     fn date(&self) -> OffsetDateTime {
         self.date
     }
+
+    /// A trade disposes of the outgoing asset and acquires the incoming one at
+    /// the same market value.
+    fn matching_events(&self) -> Vec<MatchingEvent> {
+        let value = self.incoming_price * self.incoming_quantity;
+        vec![
+            MatchingEvent::disposal(
+                self.date,
+                self.outgoing_asset_id,
+                self.outgoing_quantity,
+                value,
+                self.fees,
+            ),
+            MatchingEvent::acquisition(
+                self.date,
+                self.incoming_asset_id,
+                self.incoming_quantity,
+                value,
+                Decimal::default(),
+            ),
+        ]
+    }
 }
 
 impl ReferentialPortfolioAction for AssetTrade {
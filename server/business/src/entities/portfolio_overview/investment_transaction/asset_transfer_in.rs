@@ -4,9 +4,12 @@ use uuid::Uuid;
 
 use crate::{
     dtos::assets::asset_id_dto::AssetIdDto,
-    entities::portfolio_overview::portfolio::{
-        portfolio_asset_position_dto::PortfolioAssetPosition, Portfolio, PortfolioAction,
-        ReferentialPortfolioAction,
+    entities::{
+        capital_gains::matching_event::MatchingEvent,
+        portfolio_overview::portfolio::{
+            portfolio_asset_position_dto::PortfolioAssetPosition, Portfolio, PortfolioAction,
+            ReferentialPortfolioAction,
+        },
     },
 };
 
@@ -33,6 +36,16 @@ impl PortfolioAction for AssetTransferIn {
     fn date(&self) -> OffsetDateTime {
         self.date
     }
+
+    fn matching_events(&self) -> Vec<MatchingEvent> {
+        vec![MatchingEvent::acquisition(
+            self.date,
+            self.asset_id,
+            self.quantity,
+            self.price * self.quantity,
+            self.fees,
+        )]
+    }
 }
 
 impl ReferentialPortfolioAction for AssetTransferIn {
@@ -4,8 +4,9 @@ use uuid::Uuid;
 
 use crate::{
     dtos::assets::asset_id_dto::AssetIdDto,
-    entities::portfolio_overview::portfolio::{
-        Portfolio, PortfolioAction, ReferentialPortfolioAction,
+    entities::{
+        capital_gains::matching_event::MatchingEvent,
+        portfolio_overview::portfolio::{Portfolio, PortfolioAction, ReferentialPortfolioAction},
     },
 };
 
@@ -16,6 +17,7 @@ pub struct AssetTransferOut {
     pub asset_id: i32,
     pub account_id: Uuid,
     pub quantity: Decimal,
+    pub price: Decimal,
     pub fees: Decimal,
 }
 
@@ -30,10 +32,23 @@ impl PortfolioAction for AssetTransferOut {
     fn date(&self) -> OffsetDateTime {
         self.date
     }
+
+    /// Assets leaving the user's books are treated as disposed of at their
+    /// market value on the day.
+    fn matching_events(&self) -> Vec<MatchingEvent> {
+        vec![MatchingEvent::disposal(
+            self.date,
+            self.asset_id,
+            self.quantity,
+            self.price * self.quantity,
+            self.fees,
+        )]
+    }
 }
 
 impl ReferentialPortfolioAction for AssetTransferOut {
     fn apply_conversion_rate(&mut self, price: Decimal) {
+        self.price = price;
         self.fees *= price;
     }
 
@@ -73,6 +88,7 @@ mod tests {
                 asset_id: 1,
                 account_id,
                 quantity: dec!(1),
+                price: dec!(1),
                 fees: dec!(1),
                 date: datetime!(2000-03-23 00:00:00 UTC),
             }),
@@ -121,6 +137,7 @@ mod tests {
                 asset_id: 1,
                 account_id,
                 quantity: dec!(2),
+                price: dec!(1),
                 fees: dec!(1),
                 date: datetime!(2000-03-23 00:00:00 UTC),
             }),
@@ -164,6 +181,7 @@ mod tests {
                 asset_id: 1,
                 account_id,
                 quantity: dec!(2),
+                price: dec!(1),
                 fees: dec!(1),
                 date: datetime!(2000-03-23 00:00:00 UTC),
             }),
@@ -214,6 +232,7 @@ mod tests {
                 asset_id: 1,
                 account_id,
                 quantity: dec!(2),
+                price: dec!(1),
                 fees: dec!(1),
                 date: datetime!(2000-03-23 00:00:00 UTC),
             }),
@@ -264,6 +283,7 @@ mod tests {
                 asset_id: 1,
                 account_id,
                 quantity: dec!(3),
+                price: dec!(1),
                 fees: dec!(0),
                 date: datetime!(2000-03-01 00:00:00 UTC),
             }),
@@ -309,6 +329,7 @@ mod tests {
                 asset_id: 1,
                 account_id,
                 quantity: dec!(2),
+                price: dec!(1),
                 fees: dec!(5),
                 date: datetime!(2000-03-23 00:00:00 UTC),
             }),
@@ -350,6 +371,7 @@ mod tests {
                 asset_id: 1,
                 account_id,
                 quantity: dec!(4),
+                price: dec!(1),
                 fees: dec!(2),
                 date: datetime!(2000-03-23 00:00:00 UTC),
             }),
@@ -402,6 +424,7 @@ mod tests {
                 asset_id: 1,
                 account_id,
                 quantity: dec!(5),
+                price: dec!(1),
                 fees: dec!(1),
                 date: datetime!(2000-03-23 00:00:00 UTC),
             }),
@@ -419,20 +442,43 @@ mod tests {
     }
 
     #[test]
-    fn conversion_rate_multiplies_fees_only() {
+    fn conversion_rate_sets_price_and_multiplies_fees() {
         let mut action = AssetTransferOut {
             asset_id: 7,
             account_id: Uuid::new_v4(),
             quantity: dec!(3),
+            price: dec!(1),
             fees: dec!(5),
             date: datetime!(2000-03-22 00:00:00 UTC),
         };
 
         action.apply_conversion_rate(dec!(2));
 
+        assert_eq!(action.price, dec!(2));
         assert_eq!(action.fees, dec!(10));
         assert_eq!(action.quantity, dec!(3));
         assert_eq!(action.get_conversion_asset_id(), AssetIdDto(7));
         assert_eq!(action.date(), datetime!(2000-03-22 00:00:00 UTC));
     }
+
+    #[test]
+    fn transfer_out_is_a_disposal_at_market_value() {
+        let mut action = AssetTransferOut {
+            asset_id: 7,
+            account_id: Uuid::new_v4(),
+            quantity: dec!(3),
+            price: dec!(1),
+            fees: dec!(1),
+            date: datetime!(2000-03-22 00:00:00 UTC),
+        };
+        action.apply_conversion_rate(dec!(4));
+
+        let events = action.matching_events();
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].asset_id, 7);
+        assert_eq!(events[0].quantity, dec!(3));
+        assert_eq!(events[0].amount, dec!(12));
+        assert_eq!(events[0].fees, dec!(4));
+    }
 }
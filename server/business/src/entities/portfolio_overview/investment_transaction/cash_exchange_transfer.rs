@@ -0,0 +1,84 @@
+use rust_decimal::Decimal;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::entities::portfolio_overview::portfolio::{Portfolio, PortfolioAction};
+
+/// A cash balance transfer that changes currency on the way, such as a GBP account
+/// paying into a EUR one. Fees are charged to the source account in its currency.
+#[allow(dead_code)]
+#[derive(Clone, Debug)]
+pub struct CashExchangeTransfer {
+    pub date: OffsetDateTime,
+    pub account_from: Uuid,
+    pub asset_from: i32,
+    pub units_from: Decimal,
+    pub account_to: Uuid,
+    pub asset_to: i32,
+    pub units_to: Decimal,
+    pub fees: Decimal,
+}
+
+impl PortfolioAction for CashExchangeTransfer {
+    fn update_porfolio(&self, portfolio: &mut Portfolio) {
+        let source = portfolio.get_cash_portfolio(self.account_from, self.asset_from);
+        source.add_units(-self.units_from - self.fees);
+        source.add_fees(self.fees);
+
+        let destination = portfolio.get_cash_portfolio(self.account_to, self.asset_to);
+        destination.add_units(self.units_to);
+    }
+
+    fn date(&self) -> OffsetDateTime {
+        self.date
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use time::macros::datetime;
+
+    use super::*;
+
+    #[test]
+    fn exchange_transfer_moves_each_currency_in_its_own_account() {
+        let mut portfolio = Portfolio::new();
+        let account_from = Uuid::new_v4();
+        let account_to = Uuid::new_v4();
+
+        let input: Vec<Box<dyn PortfolioAction>> = vec![Box::new(CashExchangeTransfer {
+            account_from,
+            asset_from: 1,
+            units_from: dec!(100),
+            account_to,
+            asset_to: 2,
+            units_to: dec!(116.40),
+            fees: dec!(0.5),
+            date: datetime!(2000-03-23 00:00:00 UTC),
+        })];
+
+        portfolio.process_transactions(input);
+
+        let source = portfolio
+            .account_portfolios()
+            .get(&account_from)
+            .expect("Should contain source account");
+        let destination = portfolio
+            .account_portfolios()
+            .get(&account_to)
+            .expect("Should contain destination account");
+
+        assert_eq!(
+            source.cash_portfolios.get(&1).unwrap().units(),
+            dec!(-100.5)
+        );
+        assert_eq!(source.cash_portfolios.get(&1).unwrap().fees(), dec!(0.5));
+        assert!(!source.cash_portfolios.contains_key(&2));
+        assert_eq!(
+            destination.cash_portfolios.get(&2).unwrap().units(),
+            dec!(116.40)
+        );
+        assert!(!destination.cash_portfolios.contains_key(&1));
+    }
+}
@@ -0,0 +1,180 @@
+use rust_decimal::Decimal;
+use time::OffsetDateTime;
+
+use crate::entities::portfolio_overview::portfolio::{Portfolio, PortfolioAction};
+
+/// Holders of `asset_id` receive `ratio` units of `new_asset_id` per unit held, and
+/// `cost_basis_fraction` of the cost of every open lot moves to the new asset. The new
+/// lots keep the acquisition dates of the lots they came from.
+#[derive(Clone, Debug)]
+pub struct SpinOff {
+    pub date: OffsetDateTime,
+    pub asset_id: i32,
+    pub new_asset_id: i32,
+    pub ratio: Decimal,
+    pub cost_basis_fraction: Decimal,
+}
+
+impl PortfolioAction for SpinOff {
+    fn update_porfolio(&self, portfolio: &mut Portfolio) {
+        for account_id in portfolio.accounts_with_asset(self.asset_id) {
+            let spun_off = portfolio
+                .get_asset_portfolio(account_id, self.asset_id)
+                .spin_off(self.cost_basis_fraction, self.ratio);
+
+            if !spun_off.is_empty() {
+                portfolio
+                    .get_asset_portfolio(account_id, self.new_asset_id)
+                    .add_positions(spun_off);
+            }
+        }
+    }
+
+    fn date(&self) -> OffsetDateTime {
+        self.date
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use time::macros::datetime;
+    use uuid::Uuid;
+
+    use crate::entities::portfolio_overview::investment_transaction::{
+        asset_sale::AssetSale, asset_transfer_in::AssetTransferIn,
+    };
+
+    use super::*;
+
+    fn spin_off() -> SpinOff {
+        SpinOff {
+            date: datetime!(2024-06-01 00:00:00 UTC),
+            asset_id: 1,
+            new_asset_id: 2,
+            ratio: dec!(0.5),
+            cost_basis_fraction: dec!(0.2),
+        }
+    }
+
+    #[test]
+    fn spin_off_moves_share_of_cost_basis_to_new_asset() {
+        let mut portfolio = Portfolio::new();
+        let account_id = Uuid::new_v4();
+
+        let input: Vec<Box<dyn PortfolioAction>> = vec![
+            Box::new(AssetTransferIn {
+                asset_id: 1,
+                account_id,
+                quantity: dec!(10),
+                price: dec!(100),
+                fees: dec!(10),
+                date: datetime!(2024-01-02 00:00:00 UTC),
+            }),
+            Box::new(spin_off()),
+        ];
+
+        portfolio.process_transactions(input);
+
+        let parent = portfolio.get_asset_portfolio(account_id, 1).clone();
+        assert_eq!(parent.units(), dec!(10));
+        assert_eq!(parent.positions[0].add_price(), dec!(80));
+        assert_eq!(parent.total_cost_basis(), dec!(808));
+
+        let child = portfolio.get_asset_portfolio(account_id, 2);
+        assert_eq!(child.units(), dec!(5));
+        assert_eq!(child.positions[0].add_price(), dec!(40));
+        assert_eq!(child.total_cost_basis(), dec!(202));
+        assert_eq!(
+            child.positions[0].add_date(),
+            datetime!(2024-01-02 00:00:00 UTC)
+        );
+    }
+
+    #[test]
+    fn spin_off_leaves_sold_units_and_their_gains_with_the_parent() {
+        let mut portfolio = Portfolio::new();
+        let account_id = Uuid::new_v4();
+
+        let input: Vec<Box<dyn PortfolioAction>> = vec![
+            Box::new(AssetTransferIn {
+                asset_id: 1,
+                account_id,
+                quantity: dec!(10),
+                price: dec!(100),
+                fees: dec!(10),
+                date: datetime!(2024-01-02 00:00:00 UTC),
+            }),
+            Box::new(AssetSale {
+                date: datetime!(2024-03-01 00:00:00 UTC),
+                account_id,
+                instrument_asset_id: 1,
+                instrument_units: dec!(4),
+                instrument_reference_price: dec!(150),
+                cash_asset_id: 3,
+                cash_units: dec!(600),
+                fees: dec!(0),
+            }),
+            Box::new(spin_off()),
+        ];
+
+        portfolio.process_transactions(input);
+
+        let parent = portfolio.get_asset_portfolio(account_id, 1).clone();
+        assert_eq!(parent.positions.len(), 2);
+        assert_eq!(parent.units(), dec!(10));
+        assert_eq!(parent.remaining_units(), dec!(6));
+        assert_eq!(parent.realized_gains(), dec!(196));
+
+        let child = portfolio.get_asset_portfolio(account_id, 2).clone();
+        assert_eq!(child.units(), dec!(3));
+        assert_eq!(child.total_cost_basis(), dec!(121.2));
+
+        // The open units' cost of 606 is shared between the two assets.
+        let open_parent_cost: Decimal = parent
+            .positions
+            .iter()
+            .filter(|p| p.get_amount_left() > dec!(0))
+            .map(|p| p.get_total_cost_basis())
+            .sum();
+        assert_eq!(open_parent_cost, dec!(484.8));
+        assert_eq!(open_parent_cost + child.total_cost_basis(), dec!(606));
+    }
+
+    #[test]
+    fn spin_off_skips_accounts_that_sold_everything() {
+        let mut portfolio = Portfolio::new();
+        let account_id = Uuid::new_v4();
+
+        let input: Vec<Box<dyn PortfolioAction>> = vec![
+            Box::new(AssetTransferIn {
+                asset_id: 1,
+                account_id,
+                quantity: dec!(2),
+                price: dec!(100),
+                fees: dec!(0),
+                date: datetime!(2024-01-02 00:00:00 UTC),
+            }),
+            Box::new(AssetSale {
+                date: datetime!(2024-03-01 00:00:00 UTC),
+                account_id,
+                instrument_asset_id: 1,
+                instrument_units: dec!(2),
+                instrument_reference_price: dec!(120),
+                cash_asset_id: 3,
+                cash_units: dec!(240),
+                fees: dec!(0),
+            }),
+            Box::new(spin_off()),
+        ];
+
+        portfolio.process_transactions(input);
+
+        let account_portfolio = portfolio.account_portfolios().get(&account_id).unwrap();
+        assert!(!account_portfolio.asset_portfolios.contains_key(&2));
+        assert_eq!(
+            account_portfolio.asset_portfolios[&1].realized_gains(),
+            dec!(40)
+        );
+    }
+}
@@ -0,0 +1,166 @@
+use rust_decimal::Decimal;
+use time::OffsetDateTime;
+
+use crate::entities::portfolio_overview::portfolio::{Portfolio, PortfolioAction};
+
+/// A split or reverse split: every unit of the asset held in any account becomes
+/// `ratio` units. Lots keep their cost, fees and realized gains.
+#[derive(Clone, Debug)]
+pub struct StockSplit {
+    pub date: OffsetDateTime,
+    pub asset_id: i32,
+    pub ratio: Decimal,
+}
+
+impl PortfolioAction for StockSplit {
+    fn update_porfolio(&self, portfolio: &mut Portfolio) {
+        for account_id in portfolio.accounts_with_asset(self.asset_id) {
+            portfolio
+                .get_asset_portfolio(account_id, self.asset_id)
+                .rescale(self.ratio);
+        }
+    }
+
+    fn date(&self) -> OffsetDateTime {
+        self.date
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use time::macros::datetime;
+    use uuid::Uuid;
+
+    use crate::entities::portfolio_overview::investment_transaction::{
+        asset_sale::AssetSale, asset_transfer_in::AssetTransferIn,
+    };
+
+    use super::*;
+
+    fn transfer_in(account_id: Uuid, quantity: Decimal, price: Decimal) -> AssetTransferIn {
+        AssetTransferIn {
+            asset_id: 1,
+            account_id,
+            quantity,
+            price,
+            fees: dec!(10),
+            date: datetime!(2024-01-02 00:00:00 UTC),
+        }
+    }
+
+    #[test]
+    fn split_multiplies_units_and_divides_price_keeping_cost_basis() {
+        let mut portfolio = Portfolio::new();
+        let account_id = Uuid::new_v4();
+
+        let input: Vec<Box<dyn PortfolioAction>> = vec![
+            Box::new(transfer_in(account_id, dec!(10), dec!(100))),
+            Box::new(StockSplit {
+                date: datetime!(2024-06-01 00:00:00 UTC),
+                asset_id: 1,
+                ratio: dec!(10),
+            }),
+        ];
+
+        portfolio.process_transactions(input);
+
+        let asset_portfolio = portfolio.get_asset_portfolio(account_id, 1);
+        assert_eq!(asset_portfolio.units(), dec!(100));
+        assert_eq!(asset_portfolio.positions[0].add_price(), dec!(10));
+        assert_eq!(asset_portfolio.total_cost_basis(), dec!(1010));
+        assert_eq!(
+            asset_portfolio.positions[0].add_date(),
+            datetime!(2024-01-02 00:00:00 UTC)
+        );
+    }
+
+    #[test]
+    fn reverse_split_keeps_realized_gains_of_earlier_sales() {
+        let mut portfolio = Portfolio::new();
+        let account_id = Uuid::new_v4();
+
+        let input: Vec<Box<dyn PortfolioAction>> = vec![
+            Box::new(transfer_in(account_id, dec!(10), dec!(100))),
+            Box::new(AssetSale {
+                date: datetime!(2024-03-01 00:00:00 UTC),
+                account_id,
+                instrument_asset_id: 1,
+                instrument_units: dec!(4),
+                instrument_reference_price: dec!(150),
+                cash_asset_id: 2,
+                cash_units: dec!(600),
+                fees: dec!(0),
+            }),
+            Box::new(StockSplit {
+                date: datetime!(2024-06-01 00:00:00 UTC),
+                asset_id: 1,
+                ratio: dec!(0.5),
+            }),
+        ];
+
+        portfolio.process_transactions(input);
+
+        let asset_portfolio = portfolio.get_asset_portfolio(account_id, 1);
+        assert_eq!(asset_portfolio.units(), dec!(5));
+        assert_eq!(asset_portfolio.remaining_units(), dec!(3));
+        assert_eq!(asset_portfolio.positions[0].add_price(), dec!(200));
+        assert_eq!(asset_portfolio.realized_gains(), dec!(196));
+        // 3 units at 300 are worth what 6 units at 150 were before the split.
+        assert_eq!(asset_portfolio.market_value(dec!(300)), dec!(900));
+        assert_eq!(asset_portfolio.unrealized_gains(dec!(300)), dec!(294));
+    }
+
+    #[test]
+    fn split_applies_to_every_account_and_only_to_the_split_asset() {
+        let mut portfolio = Portfolio::new();
+        let account_1 = Uuid::new_v4();
+        let account_2 = Uuid::new_v4();
+
+        let input: Vec<Box<dyn PortfolioAction>> = vec![
+            Box::new(transfer_in(account_1, dec!(1), dec!(100))),
+            Box::new(transfer_in(account_2, dec!(2), dec!(100))),
+            Box::new(AssetTransferIn {
+                asset_id: 3,
+                account_id: account_1,
+                quantity: dec!(5),
+                price: dec!(20),
+                fees: dec!(0),
+                date: datetime!(2024-01-02 00:00:00 UTC),
+            }),
+            Box::new(StockSplit {
+                date: datetime!(2024-06-01 00:00:00 UTC),
+                asset_id: 1,
+                ratio: dec!(3),
+            }),
+        ];
+
+        portfolio.process_transactions(input);
+
+        assert_eq!(portfolio.get_asset_portfolio(account_1, 1).units(), dec!(3));
+        assert_eq!(portfolio.get_asset_portfolio(account_2, 1).units(), dec!(6));
+        assert_eq!(portfolio.get_asset_portfolio(account_1, 3).units(), dec!(5));
+    }
+
+    #[test]
+    fn purchases_after_the_split_are_not_rescaled() {
+        let mut portfolio = Portfolio::new();
+        let account_id = Uuid::new_v4();
+
+        let input: Vec<Box<dyn PortfolioAction>> = vec![
+            Box::new(StockSplit {
+                date: datetime!(2023-06-01 00:00:00 UTC),
+                asset_id: 1,
+                ratio: dec!(10),
+            }),
+            Box::new(transfer_in(account_id, dec!(10), dec!(100))),
+        ];
+
+        portfolio.process_transactions(input);
+
+        assert_eq!(
+            portfolio.get_asset_portfolio(account_id, 1).units(),
+            dec!(10)
+        );
+    }
+}
@@ -0,0 +1,126 @@
+use rust_decimal::Decimal;
+use time::OffsetDateTime;
+
+use crate::entities::portfolio_overview::portfolio::{Portfolio, PortfolioAction};
+
+/// The asset is replaced by `new_asset_id`: every lot, including sold ones, moves to the
+/// new asset at `ratio` new units per old unit, together with the dividends received.
+#[derive(Clone, Debug)]
+pub struct TickerChange {
+    pub date: OffsetDateTime,
+    pub asset_id: i32,
+    pub new_asset_id: i32,
+    pub ratio: Decimal,
+}
+
+impl PortfolioAction for TickerChange {
+    fn update_porfolio(&self, portfolio: &mut Portfolio) {
+        for account_id in portfolio.accounts_with_asset(self.asset_id) {
+            let Some(mut old) = portfolio.take_asset_portfolio(account_id, self.asset_id) else {
+                continue;
+            };
+            old.rescale(self.ratio);
+
+            let new = portfolio.get_asset_portfolio(account_id, self.new_asset_id);
+            new.add_positions(old.positions);
+            new.add_cash_dividends(old.cash_dividends);
+        }
+    }
+
+    fn date(&self) -> OffsetDateTime {
+        self.date
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use time::macros::datetime;
+    use uuid::Uuid;
+
+    use crate::entities::portfolio_overview::investment_transaction::{
+        asset_transfer_in::AssetTransferIn, cash_dividend::CashDividend,
+    };
+
+    use super::*;
+
+    #[test]
+    fn ticker_change_moves_lots_and_dividends_to_new_asset() {
+        let mut portfolio = Portfolio::new();
+        let account_id = Uuid::new_v4();
+
+        let input: Vec<Box<dyn PortfolioAction>> = vec![
+            Box::new(AssetTransferIn {
+                asset_id: 1,
+                account_id,
+                quantity: dec!(10),
+                price: dec!(100),
+                fees: dec!(10),
+                date: datetime!(2024-01-02 00:00:00 UTC),
+            }),
+            Box::new(CashDividend {
+                date: datetime!(2024-02-01 00:00:00 UTC),
+                origin_asset_id: 1,
+                asset_id: 3,
+                account_id,
+                quantity: dec!(25),
+                price: dec!(1),
+                fees: dec!(0),
+            }),
+            Box::new(TickerChange {
+                date: datetime!(2024-06-01 00:00:00 UTC),
+                asset_id: 1,
+                new_asset_id: 2,
+                ratio: dec!(1),
+            }),
+        ];
+
+        portfolio.process_transactions(input);
+
+        let account_portfolio = portfolio.account_portfolios().get(&account_id).unwrap();
+        assert!(!account_portfolio.asset_portfolios.contains_key(&1));
+        let renamed = &account_portfolio.asset_portfolios[&2];
+        assert_eq!(renamed.units(), dec!(10));
+        assert_eq!(renamed.total_cost_basis(), dec!(1010));
+        assert_eq!(renamed.cash_dividends(), dec!(25));
+    }
+
+    #[test]
+    fn ticker_change_with_exchange_ratio_merges_into_existing_holding() {
+        let mut portfolio = Portfolio::new();
+        let account_id = Uuid::new_v4();
+
+        let input: Vec<Box<dyn PortfolioAction>> = vec![
+            Box::new(AssetTransferIn {
+                asset_id: 1,
+                account_id,
+                quantity: dec!(4),
+                price: dec!(50),
+                fees: dec!(0),
+                date: datetime!(2024-01-02 00:00:00 UTC),
+            }),
+            Box::new(AssetTransferIn {
+                asset_id: 2,
+                account_id,
+                quantity: dec!(1),
+                price: dec!(90),
+                fees: dec!(0),
+                date: datetime!(2024-03-01 00:00:00 UTC),
+            }),
+            Box::new(TickerChange {
+                date: datetime!(2024-06-01 00:00:00 UTC),
+                asset_id: 1,
+                new_asset_id: 2,
+                ratio: dec!(0.5),
+            }),
+        ];
+
+        portfolio.process_transactions(input);
+
+        let merged = portfolio.get_asset_portfolio(account_id, 2);
+        assert_eq!(merged.positions.len(), 2);
+        assert_eq!(merged.units(), dec!(3));
+        assert_eq!(merged.positions[1].add_price(), dec!(100));
+        assert_eq!(merged.total_cost_basis(), dec!(290));
+    }
+}
@@ -8,13 +8,18 @@ use std::{collections::HashMap, fmt::Debug};
 use rust_decimal::Decimal;
 use uuid::Uuid;
 
-use crate::dtos::{
-    assets::asset_id_dto::AssetIdDto,
-    portfolio::overview::{
-        asset_overview_dto::PortfolioAssetOverviewDto,
-        asset_position_overview_dto::PortfolioAssetOverviewPositionDto,
-        cash_overview_dto::PortfolioCashOverviewDto, PortfolioOverviewDto, PortfolioOverviewType,
+use crate::{
+    dtos::{
+        accounts::cost_basis_method_dto::CostBasisMethodDto,
+        assets::asset_id_dto::AssetIdDto,
+        portfolio::overview::{
+            asset_overview_dto::PortfolioAssetOverviewDto,
+            asset_position_overview_dto::PortfolioAssetOverviewPositionDto,
+            cash_overview_dto::PortfolioCashOverviewDto, PortfolioOverviewDto,
+            PortfolioOverviewType,
+        },
     },
+    entities::capital_gains::matching_event::MatchingEvent,
 };
 
 use self::{
@@ -25,6 +30,7 @@ use self::{
 #[derive(Clone, Debug)]
 pub struct Portfolio {
     account_portfolios: HashMap<Uuid, AccountPortfolio>,
+    cost_basis_methods: HashMap<Uuid, CostBasisMethodDto>,
 }
 
 impl Default for Portfolio {
@@ -37,6 +43,15 @@ impl Portfolio {
     pub fn new() -> Self {
         Self {
             account_portfolios: HashMap::new(),
+            cost_basis_methods: HashMap::new(),
+        }
+    }
+
+    /// Accounts missing from `cost_basis_methods` fall back to the default method.
+    pub fn with_cost_basis_methods(cost_basis_methods: HashMap<Uuid, CostBasisMethodDto>) -> Self {
+        Self {
+            account_portfolios: HashMap::new(),
+            cost_basis_methods,
         }
     }
 
@@ -94,12 +109,37 @@ impl Portfolio {
         account_id: Uuid,
         asset_id: i32,
     ) -> &mut AccountAssetPortfolio {
+        let cost_basis_method = self
+            .cost_basis_methods
+            .get(&account_id)
+            .copied()
+            .unwrap_or_default();
         self.account_portfolios
             .entry(account_id)
             .or_default()
             .asset_portfolios
             .entry(asset_id)
-            .or_default()
+            .or_insert_with(|| AccountAssetPortfolio::new(cost_basis_method))
+    }
+
+    /// Accounts with a portfolio of `asset_id`, whether or not units are still held.
+    pub fn accounts_with_asset(&self, asset_id: i32) -> Vec<Uuid> {
+        self.account_portfolios
+            .iter()
+            .filter(|(_, ap)| ap.asset_portfolios.contains_key(&asset_id))
+            .map(|(account_id, _)| *account_id)
+            .collect()
+    }
+
+    pub fn take_asset_portfolio(
+        &mut self,
+        account_id: Uuid,
+        asset_id: i32,
+    ) -> Option<AccountAssetPortfolio> {
+        self.account_portfolios
+            .get_mut(&account_id)?
+            .asset_portfolios
+            .remove(&asset_id)
     }
 
     #[allow(dead_code)]
@@ -196,6 +236,13 @@ impl Portfolio {
 pub trait PortfolioAction: Debug + Send {
     fn update_porfolio(&self, portfolio: &mut Portfolio);
     fn date(&self) -> time::OffsetDateTime;
+
+    /// Acquisitions and disposals this action contributes to share matching.
+    /// Actions that only move an asset between the user's own accounts, or
+    /// only touch cash, contribute nothing.
+    fn matching_events(&self) -> Vec<MatchingEvent> {
+        Vec::new()
+    }
 }
 
 pub trait ReferentialPortfolioAction: PortfolioAction {
@@ -1143,4 +1190,56 @@ mod tests {
         assert!(asset_ids.contains(&1));
         assert!(!asset_ids.contains(&2));
     }
+
+    #[test]
+    fn process_transactions_honours_each_accounts_cost_basis_method() {
+        let fifo_account = Uuid::new_v4();
+        let lifo_account = Uuid::new_v4();
+        let mut portfolio = Portfolio::with_cost_basis_methods(HashMap::from([(
+            lifo_account,
+            CostBasisMethodDto::Lifo,
+        )]));
+
+        let mut input: Vec<Box<dyn PortfolioAction>> = Vec::new();
+        for account_id in [fifo_account, lifo_account] {
+            input.push(Box::new(AssetPurchase {
+                instrument_asset_id: 1,
+                account_id,
+                instrument_units: dec!(10),
+                instrument_price: dec!(100),
+                fees: dec!(0),
+                cash_asset_id: 10,
+                cash_units: dec!(1000),
+                date: datetime!(2000-01-01 00:00:00 UTC),
+            }));
+            input.push(Box::new(AssetPurchase {
+                instrument_asset_id: 1,
+                account_id,
+                instrument_units: dec!(10),
+                instrument_price: dec!(200),
+                fees: dec!(0),
+                cash_asset_id: 10,
+                cash_units: dec!(2000),
+                date: datetime!(2000-02-01 00:00:00 UTC),
+            }));
+            input.push(Box::new(AssetSale {
+                instrument_asset_id: 1,
+                account_id,
+                instrument_units: dec!(5),
+                instrument_reference_price: dec!(250),
+                fees: dec!(0),
+                cash_asset_id: 10,
+                cash_units: dec!(1250),
+                date: datetime!(2000-03-01 00:00:00 UTC),
+            }));
+        }
+
+        portfolio.process_transactions(input);
+
+        let realized = |account_id: Uuid| {
+            portfolio.account_portfolios()[&account_id].asset_portfolios[&1].realized_gains()
+        };
+        assert_eq!(realized(fifo_account), dec!(750));
+        assert_eq!(realized(lifo_account), dec!(250));
+    }
 }
@@ -2,24 +2,32 @@ use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
 use tracing::warn;
 
+use crate::dtos::accounts::cost_basis_method_dto::CostBasisMethodDto;
+
 use super::portfolio_asset_position_dto::PortfolioAssetPosition;
 
 #[derive(Clone, Debug)]
 pub struct AccountAssetPortfolio {
     pub positions: Vec<PortfolioAssetPosition>,
     pub cash_dividends: Decimal,
+    pub cost_basis_method: CostBasisMethodDto,
 }
 
 impl Default for AccountAssetPortfolio {
     fn default() -> Self {
+        Self::new(CostBasisMethodDto::default())
+    }
+}
+
+impl AccountAssetPortfolio {
+    pub fn new(cost_basis_method: CostBasisMethodDto) -> Self {
         Self {
             positions: vec![],
             cash_dividends: dec!(0),
+            cost_basis_method,
         }
     }
-}
 
-impl AccountAssetPortfolio {
     fn sort(&mut self) {
         self.positions.sort_by(|a, b| b.compare_by_date(a));
     }
@@ -58,30 +66,94 @@ impl AccountAssetPortfolio {
         self.sort();
     }
 
-    pub fn sell_positions(&mut self, quantity: Decimal, price: Decimal, fees: Decimal) {
-        let mut left_to_sell = quantity;
-        while left_to_sell > dec!(0) {
-            if let Some(position) = self
-                .positions
-                .iter_mut()
-                .rev()
-                .find(|x| x.get_amount_left() > dec!(0))
-            {
-                let amount_left_in_position = position.get_amount_left();
-                let amount_selling = if amount_left_in_position < left_to_sell {
-                    amount_left_in_position
-                } else {
-                    left_to_sell
-                };
+    /// Splits `quantity` across the open lots in the order dictated by the
+    /// cost-basis method. Returns `(position index, units taken)` pairs; the
+    /// units taken sum to less than `quantity` only when not enough is held.
+    fn allocate_lots(&self, quantity: Decimal) -> Vec<(usize, Decimal)> {
+        if quantity <= dec!(0) {
+            return vec![];
+        }
 
-                // Multiply before dividing to avoid Decimal rounding from the intermediate quotient.
-                let sale_fees = fees * amount_selling / quantity;
-                position.sell(amount_selling, price, sale_fees);
-                left_to_sell -= amount_selling;
-            } else {
-                warn!(remaining = %left_to_sell, "oversell: not enough units held, selling all available");
+        // Positions are kept newest-first, so walking the indices backwards
+        // visits the oldest lot first.
+        let open_lots: Vec<usize> = (0..self.positions.len())
+            .rev()
+            .filter(|i| self.positions[*i].get_amount_left() > dec!(0))
+            .collect();
+
+        let order = match self.cost_basis_method {
+            CostBasisMethodDto::Fifo => open_lots,
+            CostBasisMethodDto::Lifo => open_lots.into_iter().rev().collect(),
+            CostBasisMethodDto::Hifo => {
+                let mut by_price = open_lots;
+                // Stable sort keeps the oldest lot first among equally priced ones.
+                by_price.sort_by(|a, b| {
+                    self.positions[*b]
+                        .add_price()
+                        .cmp(&self.positions[*a].add_price())
+                });
+                by_price
+            }
+            CostBasisMethodDto::AverageCost => return self.allocate_pro_rata(open_lots, quantity),
+        };
+
+        let mut allocations = Vec::new();
+        let mut left_to_allocate = quantity;
+        for index in order {
+            if left_to_allocate <= dec!(0) {
                 break;
             }
+            let amount_taken = self.positions[index]
+                .get_amount_left()
+                .min(left_to_allocate);
+            allocations.push((index, amount_taken));
+            left_to_allocate -= amount_taken;
+        }
+        allocations
+    }
+
+    /// Takes the same fraction of every open lot, which prices the disposal at
+    /// the pooled average cost. The last lot absorbs the rounding remainder so
+    /// the allocations sum to exactly `quantity`.
+    fn allocate_pro_rata(&self, open_lots: Vec<usize>, quantity: Decimal) -> Vec<(usize, Decimal)> {
+        let held: Decimal = open_lots
+            .iter()
+            .map(|i| self.positions[*i].get_amount_left())
+            .sum();
+
+        if quantity >= held {
+            return open_lots
+                .into_iter()
+                .map(|i| (i, self.positions[i].get_amount_left()))
+                .collect();
+        }
+
+        let mut allocations = Vec::with_capacity(open_lots.len());
+        let mut allocated = dec!(0);
+        let last = open_lots.len().saturating_sub(1);
+        for (n, index) in open_lots.into_iter().enumerate() {
+            let amount_taken = if n == last {
+                quantity - allocated
+            } else {
+                // Multiply before dividing to avoid Decimal rounding from the intermediate quotient.
+                self.positions[index].get_amount_left() * quantity / held
+            };
+            allocated += amount_taken;
+            allocations.push((index, amount_taken));
+        }
+        allocations
+    }
+
+    pub fn sell_positions(&mut self, quantity: Decimal, price: Decimal, fees: Decimal) {
+        let mut left_to_sell = quantity;
+        for (index, amount_selling) in self.allocate_lots(quantity) {
+            // Multiply before dividing to avoid Decimal rounding from the intermediate quotient.
+            let sale_fees = fees * amount_selling / quantity;
+            self.positions[index].sell(amount_selling, price, sale_fees);
+            left_to_sell -= amount_selling;
+        }
+        if left_to_sell > dec!(0) {
+            warn!(remaining = %left_to_sell, "oversell: not enough units held, selling all available");
         }
         self.sort();
     }
@@ -92,58 +164,83 @@ impl AccountAssetPortfolio {
         fees: Decimal,
     ) -> Vec<PortfolioAssetPosition> {
         let mut removed_positions: Vec<PortfolioAssetPosition> = vec![];
+        let mut emptied_positions: Vec<usize> = vec![];
         let mut left_to_remove = quantity;
-        while left_to_remove > dec!(0) {
-            if let Some(position_index) = self
-                .positions
-                .iter()
-                .rposition(|x| x.get_amount_left() > dec!(0))
-            {
-                let position = self.positions.get_mut(position_index).unwrap();
-                let amount_left_in_position = position.get_amount_left();
-                let amount_transfering = if amount_left_in_position < left_to_remove {
-                    amount_left_in_position
-                } else {
-                    left_to_remove
-                };
-
-                left_to_remove -= amount_transfering;
-
-                // A case where position does not have sold assets - we are transfeing full position
-                if amount_left_in_position == amount_transfering {
-                    let mut transfered_position = position.clone();
-                    transfered_position.add_fees(fees * amount_transfering / quantity);
-                    removed_positions.push(transfered_position);
-                    self.positions.remove(position_index);
-                    continue;
-                }
-
-                // Fee share uses units ADDED, not units left: the already-sold
-                // units' share stays with the source where realized gains already
-                // deducted it — every fee is counted exactly once.
-                // Multiply before dividing to stay exact.
-                let units_added = position.units();
-                let own_fees_moved = position.total_fees() * amount_transfering / units_added;
-                let new_fees_moved = fees * amount_transfering / quantity;
-
-                let transfered_postion = PortfolioAssetPosition::new(
-                    position.add_price(),
-                    amount_transfering,
-                    position.add_date(),
-                    own_fees_moved + new_fees_moved,
-                );
-                position.add_quantity(-amount_transfering, -own_fees_moved);
-                removed_positions.push(transfered_postion);
-            } else {
-                warn!(remaining = %left_to_remove, "over-transfer: not enough units held, transferring all available");
-                break;
+        for (position_index, amount_transfering) in self.allocate_lots(quantity) {
+            let position = &mut self.positions[position_index];
+            let amount_left_in_position = position.get_amount_left();
+
+            left_to_remove -= amount_transfering;
+
+            // A case where position does not have sold assets - we are transfeing full position
+            if amount_left_in_position == amount_transfering {
+                let mut transfered_position = position.clone();
+                transfered_position.add_fees(fees * amount_transfering / quantity);
+                removed_positions.push(transfered_position);
+                emptied_positions.push(position_index);
+                continue;
             }
+
+            // Fee share uses units ADDED, not units left: the already-sold
+            // units' share stays with the source where realized gains already
+            // deducted it — every fee is counted exactly once.
+            // Multiply before dividing to stay exact.
+            let units_added = position.units();
+            let own_fees_moved = position.total_fees() * amount_transfering / units_added;
+            let new_fees_moved = fees * amount_transfering / quantity;
+
+            let transfered_postion = PortfolioAssetPosition::new(
+                position.add_price(),
+                amount_transfering,
+                position.add_date(),
+                own_fees_moved + new_fees_moved,
+            );
+            position.add_quantity(-amount_transfering, -own_fees_moved);
+            removed_positions.push(transfered_postion);
+        }
+        if left_to_remove > dec!(0) {
+            warn!(remaining = %left_to_remove, "over-transfer: not enough units held, transferring all available");
         }
+
+        // Remove back-to-front so earlier indices stay valid.
+        emptied_positions.sort_unstable_by(|a, b| b.cmp(a));
+        for position_index in emptied_positions {
+            self.positions.remove(position_index);
+        }
+
         self.sort();
         removed_positions.sort_by(|a, b| b.compare_by_date(a));
         removed_positions
     }
 
+    /// Restates every lot after the asset was split `ratio` to one.
+    pub fn rescale(&mut self, ratio: Decimal) {
+        self.positions
+            .iter_mut()
+            .for_each(|position| position.rescale(ratio));
+    }
+
+    /// Moves `fraction` of the cost basis of every open lot to lots of the spun-off
+    /// asset and returns those lots. Units already sold keep their full cost, so a
+    /// partially sold lot is first split into its sold and open parts.
+    pub fn spin_off(&mut self, fraction: Decimal, ratio: Decimal) -> Vec<PortfolioAssetPosition> {
+        let mut sold_parts = Vec::new();
+        let mut spun_off = Vec::new();
+        for position in self.positions.iter_mut() {
+            if position.get_amount_left() <= dec!(0) {
+                continue;
+            }
+            if let Some(sold) = position.split_off_sold() {
+                sold_parts.push(sold);
+            }
+            spun_off.push(position.spin_off(fraction, ratio));
+        }
+
+        self.positions.append(&mut sold_parts);
+        self.sort();
+        spun_off
+    }
+
     #[allow(dead_code)]
     pub fn units(&self) -> Decimal {
         self.positions.iter().map(|x| x.units()).sum()
@@ -233,6 +330,7 @@ mod tests {
     use rust_decimal_macros::dec;
     use time::{macros::datetime, OffsetDateTime};
 
+    use crate::dtos::accounts::cost_basis_method_dto::CostBasisMethodDto;
     use crate::entities::portfolio_overview::portfolio::{
         account_asset_portfolio::AccountAssetPortfolio,
         portfolio_asset_position_dto::PortfolioAssetPosition,
@@ -323,6 +421,7 @@ mod tests {
     #[tokio::test]
     async fn test_sell_lifo_full() {
         let mut portfolio = AccountAssetPortfolio {
+            cost_basis_method: CostBasisMethodDto::Fifo,
             cash_dividends: dec!(0),
             positions: vec![
                 PortfolioAssetPosition::new(
@@ -353,6 +452,7 @@ mod tests {
     #[tokio::test]
     async fn test_sell_lifo_partial() {
         let mut portfolio = AccountAssetPortfolio {
+            cost_basis_method: CostBasisMethodDto::Fifo,
             cash_dividends: dec!(0),
             positions: vec![
                 PortfolioAssetPosition::new(
@@ -383,6 +483,7 @@ mod tests {
     #[tokio::test]
     async fn test_remove_lifo_full() {
         let mut portfolio = AccountAssetPortfolio {
+            cost_basis_method: CostBasisMethodDto::Fifo,
             cash_dividends: dec!(0),
             positions: vec![
                 PortfolioAssetPosition::new(
@@ -422,6 +523,7 @@ mod tests {
     #[tokio::test]
     async fn test_remove_lifo_partial() {
         let mut portfolio = AccountAssetPortfolio {
+            cost_basis_method: CostBasisMethodDto::Fifo,
             cash_dividends: dec!(0),
             positions: vec![
                 PortfolioAssetPosition::new(
@@ -472,6 +574,7 @@ mod tests {
     #[tokio::test]
     async fn test_remove_lifo_full_and_partial() {
         let mut portfolio = AccountAssetPortfolio {
+            cost_basis_method: CostBasisMethodDto::Fifo,
             cash_dividends: dec!(0),
             positions: vec![
                 PortfolioAssetPosition::new(
@@ -525,6 +628,7 @@ mod tests {
     #[test]
     fn sell_positions_consumes_oldest_lot_first() {
         let mut portfolio = AccountAssetPortfolio {
+            cost_basis_method: CostBasisMethodDto::Fifo,
             cash_dividends: dec!(0),
             positions: vec![
                 PortfolioAssetPosition::new(
@@ -561,6 +665,7 @@ mod tests {
     #[test]
     fn sell_positions_spills_over_three_lots_oldest_first_with_fee_proration_by_units_sold() {
         let mut portfolio = AccountAssetPortfolio {
+            cost_basis_method: CostBasisMethodDto::Fifo,
             cash_dividends: dec!(0),
             positions: vec![
                 PortfolioAssetPosition::new(
@@ -621,6 +726,7 @@ mod tests {
         // consumes everything held. Fee proration divides by the REQUESTED quantity, so on
         // an oversell only 8 * (5/8) = 5 of the 8 fee attaches; the rest vanishes.
         let mut portfolio = AccountAssetPortfolio {
+            cost_basis_method: CostBasisMethodDto::Fifo,
             cash_dividends: dec!(0),
             positions: vec![PortfolioAssetPosition::new(
                 dec!(10),
@@ -645,6 +751,7 @@ mod tests {
         // removes everything. Fee proration divides by the REQUESTED quantity, so on an oversell
         // only 8 * (5/8) = 5 of the 8 transfer fee attaches to the removed lot.
         let mut portfolio = AccountAssetPortfolio {
+            cost_basis_method: CostBasisMethodDto::Fifo,
             cash_dividends: dec!(0),
             positions: vec![PortfolioAssetPosition::new(
                 dec!(10),
@@ -702,6 +809,7 @@ mod tests {
     #[test]
     fn add_positions_merges_into_existing_position_with_same_date_and_price() {
         let mut portfolio = AccountAssetPortfolio {
+            cost_basis_method: CostBasisMethodDto::Fifo,
             cash_dividends: dec!(0),
             positions: vec![PortfolioAssetPosition::new(
                 dec!(10),
@@ -731,6 +839,7 @@ mod tests {
     #[test]
     fn add_positions_does_not_merge_into_existing_position_when_price_differs() {
         let mut portfolio = AccountAssetPortfolio {
+            cost_basis_method: CostBasisMethodDto::Fifo,
             cash_dividends: dec!(0),
             positions: vec![PortfolioAssetPosition::new(
                 dec!(10),
@@ -767,6 +876,7 @@ mod tests {
     #[test]
     fn remove_positions_round_trip_preserves_units_fees_dates_and_prices() {
         let mut source = AccountAssetPortfolio {
+            cost_basis_method: CostBasisMethodDto::Fifo,
             cash_dividends: dec!(0),
             positions: vec![
                 PortfolioAssetPosition::new(
@@ -821,6 +931,7 @@ mod tests {
     #[test]
     fn remove_positions_partial_removal_with_prior_sells_takes_proportional_share_of_fees() {
         let mut portfolio = AccountAssetPortfolio {
+            cost_basis_method: CostBasisMethodDto::Fifo,
             cash_dividends: dec!(0),
             positions: vec![PortfolioAssetPosition::new(
                 dec!(10),
@@ -850,6 +961,7 @@ mod tests {
         // Open question 3: today it SUMS each position's per-unit cost (10 + 20 = 30),
         // not a units-weighted average (15).
         let portfolio = AccountAssetPortfolio {
+            cost_basis_method: CostBasisMethodDto::Fifo,
             cash_dividends: dec!(0),
             positions: vec![
                 PortfolioAssetPosition::new(
@@ -873,6 +985,7 @@ mod tests {
     #[test]
     fn aggregates_sum_across_lots_and_asset_dividends_count_only_dividend_lots() {
         let mut portfolio = AccountAssetPortfolio {
+            cost_basis_method: CostBasisMethodDto::Fifo,
             cash_dividends: dec!(0),
             positions: vec![
                 PortfolioAssetPosition::new_dividend(
@@ -930,4 +1043,111 @@ mod tests {
         assert_eq!(portfolio.unrealized_gains(dec!(130)), dec!(175.80));
         assert_eq!(portfolio.total_gains(dec!(130)), dec!(253));
     }
+
+    fn three_lots(cost_basis_method: CostBasisMethodDto) -> AccountAssetPortfolio {
+        AccountAssetPortfolio {
+            cost_basis_method,
+            cash_dividends: dec!(0),
+            positions: vec![
+                PortfolioAssetPosition::new(
+                    dec!(12),
+                    dec!(5),
+                    datetime!(2000-01-03 00:00:00 UTC),
+                    dec!(0),
+                ),
+                PortfolioAssetPosition::new(
+                    dec!(30),
+                    dec!(5),
+                    datetime!(2000-01-02 00:00:00 UTC),
+                    dec!(0),
+                ),
+                PortfolioAssetPosition::new(
+                    dec!(10),
+                    dec!(10),
+                    datetime!(2000-01-01 00:00:00 UTC),
+                    dec!(0),
+                ),
+            ],
+        }
+    }
+
+    #[test]
+    fn sell_positions_lifo_consumes_newest_lot_first() {
+        let mut portfolio = three_lots(CostBasisMethodDto::Lifo);
+
+        portfolio.sell_positions(dec!(7), dec!(40), dec!(0));
+
+        assert_eq!(portfolio.positions[0].amount_sold(), dec!(5));
+        assert_eq!(portfolio.positions[1].amount_sold(), dec!(2));
+        assert_eq!(portfolio.positions[2].amount_sold(), dec!(0));
+        assert_eq!(portfolio.realized_gains(), dec!(160));
+    }
+
+    #[test]
+    fn sell_positions_hifo_consumes_most_expensive_lot_first() {
+        let mut portfolio = three_lots(CostBasisMethodDto::Hifo);
+
+        portfolio.sell_positions(dec!(7), dec!(40), dec!(0));
+
+        assert_eq!(portfolio.positions[0].amount_sold(), dec!(2));
+        assert_eq!(portfolio.positions[1].amount_sold(), dec!(5));
+        assert_eq!(portfolio.positions[2].amount_sold(), dec!(0));
+        assert_eq!(portfolio.realized_gains(), dec!(106));
+    }
+
+    #[test]
+    fn sell_positions_average_cost_realizes_gain_against_pooled_cost() {
+        let mut portfolio = three_lots(CostBasisMethodDto::AverageCost);
+
+        // Pool: 20 units costing 60 + 150 + 100 = 310, i.e. 15.5 per unit.
+        portfolio.sell_positions(dec!(10), dec!(40), dec!(0));
+
+        assert_eq!(portfolio.positions[0].amount_sold(), dec!(2.5));
+        assert_eq!(portfolio.positions[1].amount_sold(), dec!(2.5));
+        assert_eq!(portfolio.positions[2].amount_sold(), dec!(5));
+        assert_eq!(portfolio.realized_gains(), dec!(245));
+        assert_eq!(portfolio.remaining_units(), dec!(10));
+        assert_eq!(portfolio.unrealized_gains(dec!(40)), dec!(245));
+    }
+
+    #[test]
+    fn sell_positions_average_cost_allocations_sum_to_quantity_sold() {
+        let mut portfolio = three_lots(CostBasisMethodDto::AverageCost);
+
+        portfolio.sell_positions(dec!(7), dec!(40), dec!(3));
+
+        let sold: rust_decimal::Decimal = portfolio.positions.iter().map(|p| p.amount_sold()).sum();
+        assert_eq!(sold, dec!(7));
+        assert_eq!(portfolio.total_fees(), dec!(3));
+    }
+
+    #[test]
+    fn remove_positions_hifo_transfers_most_expensive_lot_first() {
+        let mut portfolio = three_lots(CostBasisMethodDto::Hifo);
+
+        let removed = portfolio.remove_positions(dec!(6), dec!(0));
+
+        assert_eq!(removed.len(), 2);
+        assert_eq!(removed[0].add_price(), dec!(12));
+        assert_eq!(removed[0].units(), dec!(1));
+        assert_eq!(removed[1].add_price(), dec!(30));
+        assert_eq!(removed[1].units(), dec!(5));
+        assert_eq!(portfolio.positions.len(), 2);
+        assert_eq!(portfolio.remaining_units(), dec!(14));
+    }
+
+    #[test]
+    fn remove_positions_average_cost_transfers_same_fraction_of_every_lot() {
+        let mut portfolio = three_lots(CostBasisMethodDto::AverageCost);
+
+        let removed = portfolio.remove_positions(dec!(10), dec!(0));
+
+        assert_eq!(removed.len(), 3);
+        assert_eq!(removed[0].units(), dec!(2.5));
+        assert_eq!(removed[1].units(), dec!(2.5));
+        assert_eq!(removed[2].units(), dec!(5));
+        assert_eq!(portfolio.positions.len(), 3);
+        assert_eq!(portfolio.remaining_units(), dec!(10));
+        assert_eq!(portfolio.total_cost_basis(), dec!(155));
+    }
 }
@@ -58,6 +58,53 @@ impl PortfolioAssetPosition {
         self.fees += fees;
     }
 
+    /// Restates the lot in units of an asset that was split `ratio` to one. The lot's
+    /// cost, fees and realized gains stay the same.
+    pub fn rescale(&mut self, ratio: Decimal) {
+        self.quantity_added *= ratio;
+        self.amount_sold *= ratio;
+        self.add_price /= ratio;
+    }
+
+    /// Turns a partially sold lot into its still-open part and returns the sold part
+    /// as a lot of its own, with the fees shared by units added. Returns `None` when
+    /// nothing was sold or nothing is left.
+    pub fn split_off_sold(&mut self) -> Option<PortfolioAssetPosition> {
+        if self.amount_sold.is_zero() || self.get_amount_left() <= dec!(0) {
+            return None;
+        }
+
+        // Multiply before dividing to avoid Decimal rounding from the intermediate quotient.
+        let sold_fees = self.fees * self.amount_sold / self.quantity_added;
+        let sold = PortfolioAssetPosition {
+            quantity_added: self.amount_sold,
+            fees: sold_fees,
+            ..self.clone()
+        };
+
+        self.quantity_added -= self.amount_sold;
+        self.fees -= sold_fees;
+        self.amount_sold = dec!(0);
+        self.sale_proceeds = dec!(0);
+        Some(sold)
+    }
+
+    /// Moves `fraction` of the lot's cost basis to a new lot of the spun-off asset,
+    /// holding `ratio` new units per unit of this lot and keeping its acquisition date.
+    /// Expects a lot without sold units (see [`Self::split_off_sold`]).
+    pub fn spin_off(&mut self, fraction: Decimal, ratio: Decimal) -> PortfolioAssetPosition {
+        let spun_off = PortfolioAssetPosition::new(
+            self.add_price * fraction / ratio,
+            self.quantity_added * ratio,
+            self.add_date,
+            self.fees * fraction,
+        );
+
+        self.add_price *= dec!(1) - fraction;
+        self.fees *= dec!(1) - fraction;
+        spun_off
+    }
+
     pub fn is_same_position(&self, other: &PortfolioAssetPosition) -> bool {
         self.add_price == other.add_price && self.add_date == other.add_date
     }
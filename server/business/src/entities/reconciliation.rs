@@ -0,0 +1,166 @@
+use rust_decimal::Decimal;
+use time::OffsetDateTime;
+
+use crate::dtos::reconciliation_dto::{
+    AccountReconciliationDto, LedgerMovementDto, ReconciliationReportDto,
+};
+
+/// Ledger balance after every movement booked up to and including `at`.
+pub fn balance_at(movements: &[LedgerMovementDto], at: OffsetDateTime) -> Decimal {
+    movements
+        .iter()
+        .filter(|movement| movement.date_transacted <= at)
+        .map(|movement| movement.quantity)
+        .sum()
+}
+
+/// Compares a checkpoint with the ledger.
+///
+/// `checkpoints` are the other checkpoints recorded for the same account and asset, and
+/// `movements` must cover the account's history up to the latest of them. The report
+/// lists every transaction since the most recent earlier checkpoint that still agrees
+/// with the ledger, which is where a discrepancy has to come from.
+pub fn build_reconciliation_report(
+    reconciliation: AccountReconciliationDto,
+    checkpoints: &[AccountReconciliationDto],
+    movements: &[LedgerMovementDto],
+) -> ReconciliationReportDto {
+    let ledger_balance = balance_at(movements, reconciliation.statement_date);
+
+    let last_reconciled = checkpoints
+        .iter()
+        .filter(|checkpoint| {
+            checkpoint.id != reconciliation.id
+                && checkpoint.asset_id == reconciliation.asset_id
+                && checkpoint.statement_date < reconciliation.statement_date
+                && balance_at(movements, checkpoint.statement_date) == checkpoint.statement_balance
+        })
+        .max_by_key(|checkpoint| checkpoint.statement_date)
+        .cloned();
+
+    let since = last_reconciled
+        .as_ref()
+        .map(|checkpoint| checkpoint.statement_date);
+    let unreconciled_transactions = movements
+        .iter()
+        .filter(|movement| {
+            movement.date_transacted <= reconciliation.statement_date
+                && since.is_none_or(|since| movement.date_transacted > since)
+        })
+        .cloned()
+        .collect();
+
+    ReconciliationReportDto {
+        discrepancy: reconciliation.statement_balance - ledger_balance,
+        ledger_balance,
+        reconciliation,
+        last_reconciled,
+        unreconciled_transactions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use time::macros::datetime;
+    use uuid::Uuid;
+
+    use crate::dtos::reconciliation_dto::ReconciliationSourceDto;
+
+    use super::*;
+
+    fn checkpoint(statement_date: OffsetDateTime, balance: Decimal) -> AccountReconciliationDto {
+        AccountReconciliationDto {
+            id: Uuid::new_v4(),
+            account_id: Uuid::nil(),
+            asset_id: 1,
+            statement_date,
+            statement_balance: balance,
+            source: ReconciliationSourceDto::Manual,
+            binding_id: None,
+            created_at: statement_date,
+        }
+    }
+
+    fn movement(date_transacted: OffsetDateTime, quantity: Decimal) -> LedgerMovementDto {
+        LedgerMovementDto {
+            transaction_id: Uuid::new_v4(),
+            date_transacted,
+            quantity,
+        }
+    }
+
+    #[test]
+    fn balance_at_includes_movements_on_the_statement_date() {
+        let movements = vec![
+            movement(datetime!(2024-01-01 0:00 UTC), dec!(100)),
+            movement(datetime!(2024-01-31 0:00 UTC), dec!(-20)),
+            movement(datetime!(2024-02-01 0:00 UTC), dec!(-5)),
+        ];
+
+        assert_eq!(
+            balance_at(&movements, datetime!(2024-01-31 0:00 UTC)),
+            dec!(80)
+        );
+    }
+
+    #[test]
+    fn matching_statement_has_no_discrepancy() {
+        let movements = vec![
+            movement(datetime!(2024-01-05 0:00 UTC), dec!(250)),
+            movement(datetime!(2024-01-20 0:00 UTC), dec!(-50)),
+        ];
+
+        let report = build_reconciliation_report(
+            checkpoint(datetime!(2024-01-31 0:00 UTC), dec!(200)),
+            &[],
+            &movements,
+        );
+
+        assert!(report.is_reconciled());
+        assert_eq!(report.ledger_balance, dec!(200));
+        assert_eq!(report.unreconciled_transactions.len(), 2);
+    }
+
+    #[test]
+    fn lists_transactions_since_last_reconciled_checkpoint() {
+        let movements = vec![
+            movement(datetime!(2024-01-05 0:00 UTC), dec!(250)),
+            movement(datetime!(2024-02-03 0:00 UTC), dec!(-30)),
+            movement(datetime!(2024-02-10 0:00 UTC), dec!(-15)),
+        ];
+        let january = checkpoint(datetime!(2024-01-31 0:00 UTC), dec!(250));
+        let february = checkpoint(datetime!(2024-02-29 0:00 UTC), dec!(190));
+
+        let report = build_reconciliation_report(february, &[january.clone()], &movements);
+
+        assert_eq!(report.ledger_balance, dec!(205));
+        assert_eq!(report.discrepancy, dec!(-15));
+        assert_eq!(
+            report.last_reconciled.map(|checkpoint| checkpoint.id),
+            Some(january.id)
+        );
+        assert_eq!(report.unreconciled_transactions, movements[1..].to_vec());
+    }
+
+    #[test]
+    fn skips_earlier_checkpoints_that_no_longer_match() {
+        let movements = vec![
+            movement(datetime!(2024-01-05 0:00 UTC), dec!(100)),
+            movement(datetime!(2024-02-05 0:00 UTC), dec!(10)),
+            movement(datetime!(2024-03-05 0:00 UTC), dec!(10)),
+        ];
+        let january = checkpoint(datetime!(2024-01-31 0:00 UTC), dec!(100));
+        let february = checkpoint(datetime!(2024-02-29 0:00 UTC), dec!(999));
+        let march = checkpoint(datetime!(2024-03-31 0:00 UTC), dec!(120));
+
+        let report = build_reconciliation_report(march, &[january.clone(), february], &movements);
+
+        assert!(report.is_reconciled());
+        assert_eq!(
+            report.last_reconciled.map(|checkpoint| checkpoint.id),
+            Some(january.id)
+        );
+        assert_eq!(report.unreconciled_transactions.len(), 2);
+    }
+}
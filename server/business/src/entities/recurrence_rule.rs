@@ -0,0 +1,337 @@
+use std::str::FromStr;
+
+use thiserror::Error;
+use time::{Date, Duration, Month, OffsetDateTime, Weekday};
+
+/// Upper bound on schedule periods walked when searching for an occurrence, so a
+/// rule that never produces a date (e.g. past year 9999) cannot loop forever.
+const MAX_PERIODS: u32 = 100_000;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum RecurrenceRuleError {
+    #[error("Recurrence rule must specify FREQ.")]
+    MissingFrequency,
+
+    #[error("Malformed recurrence rule part '{0}'.")]
+    MalformedPart(String),
+
+    #[error("Unsupported recurrence rule part '{0}'.")]
+    UnsupportedPart(String),
+
+    #[error("Invalid value '{value}' for {part}.")]
+    InvalidValue { part: String, value: String },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RecurrenceFrequency {
+    Daily,
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+/// A subset of the iCalendar RRULE grammar: `FREQ`, `INTERVAL`, `BYDAY` (weekly
+/// rules only) and `BYMONTHDAY` (monthly rules only), e.g.
+/// `FREQ=MONTHLY;INTERVAL=1;BYMONTHDAY=-1` for the last day of every month.
+///
+/// Month days past the end of a shorter month are clamped to its last day, so a
+/// rule on the 31st still fires in February. The schedule ends at the template's
+/// end date rather than through `COUNT` or `UNTIL`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RecurrenceRule {
+    pub frequency: RecurrenceFrequency,
+    pub interval: u32,
+    pub by_day: Vec<Weekday>,
+    pub by_month_day: Option<i8>,
+}
+
+impl FromStr for RecurrenceRule {
+    type Err = RecurrenceRuleError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut frequency = None;
+        let mut interval = 1;
+        let mut by_day = Vec::new();
+        let mut by_month_day = None;
+
+        for part in s.trim().trim_start_matches("RRULE:").split(';') {
+            if part.is_empty() {
+                continue;
+            }
+            let (key, value) = part
+                .split_once('=')
+                .ok_or_else(|| RecurrenceRuleError::MalformedPart(part.to_string()))?;
+            let key = key.trim().to_ascii_uppercase();
+            let value = value.trim().to_ascii_uppercase();
+            let invalid = || RecurrenceRuleError::InvalidValue {
+                part: key.clone(),
+                value: value.clone(),
+            };
+
+            match key.as_str() {
+                "FREQ" => {
+                    frequency = Some(match value.as_str() {
+                        "DAILY" => RecurrenceFrequency::Daily,
+                        "WEEKLY" => RecurrenceFrequency::Weekly,
+                        "MONTHLY" => RecurrenceFrequency::Monthly,
+                        "YEARLY" => RecurrenceFrequency::Yearly,
+                        _ => return Err(invalid()),
+                    })
+                }
+                "INTERVAL" => {
+                    interval = value
+                        .parse::<u32>()
+                        .ok()
+                        .filter(|x| *x > 0)
+                        .ok_or_else(invalid)?;
+                }
+                "BYDAY" => {
+                    by_day = value
+                        .split(',')
+                        .map(|day| parse_weekday(day.trim()).ok_or_else(invalid))
+                        .collect::<Result<Vec<_>, _>>()?;
+                    by_day.sort_by_key(|x| x.number_days_from_monday());
+                    by_day.dedup();
+                }
+                "BYMONTHDAY" => {
+                    by_month_day = Some(
+                        value
+                            .parse::<i8>()
+                            .ok()
+                            .filter(|x| *x != 0 && (-31..=31).contains(x))
+                            .ok_or_else(invalid)?,
+                    );
+                }
+                _ => return Err(RecurrenceRuleError::UnsupportedPart(key)),
+            }
+        }
+
+        let frequency = frequency.ok_or(RecurrenceRuleError::MissingFrequency)?;
+
+        if !by_day.is_empty() && frequency != RecurrenceFrequency::Weekly {
+            return Err(RecurrenceRuleError::UnsupportedPart(
+                "BYDAY without FREQ=WEEKLY".to_string(),
+            ));
+        }
+        if by_month_day.is_some() && frequency != RecurrenceFrequency::Monthly {
+            return Err(RecurrenceRuleError::UnsupportedPart(
+                "BYMONTHDAY without FREQ=MONTHLY".to_string(),
+            ));
+        }
+
+        Ok(Self {
+            frequency,
+            interval,
+            by_day,
+            by_month_day,
+        })
+    }
+}
+
+impl RecurrenceRule {
+    /// First occurrence of the schedule starting at `dtstart` that falls on or after `from`.
+    /// Every occurrence keeps the time of day and offset of `dtstart`.
+    pub fn next_occurrence(
+        &self,
+        dtstart: OffsetDateTime,
+        from: OffsetDateTime,
+    ) -> Option<OffsetDateTime> {
+        for index in 0..MAX_PERIODS {
+            let candidates = self.period_dates(dtstart.date(), index)?;
+            for date in candidates {
+                let occurrence = date
+                    .with_time(dtstart.time())
+                    .assume_offset(dtstart.offset());
+                if occurrence >= dtstart && occurrence >= from {
+                    return Some(occurrence);
+                }
+            }
+        }
+        None
+    }
+
+    /// All occurrences in `[from, until]`, at most `limit` of them.
+    pub fn occurrences_between(
+        &self,
+        dtstart: OffsetDateTime,
+        from: OffsetDateTime,
+        until: OffsetDateTime,
+        limit: usize,
+    ) -> Vec<OffsetDateTime> {
+        let mut occurrences = Vec::new();
+        let mut cursor = from;
+        while occurrences.len() < limit {
+            match self.next_occurrence(dtstart, cursor) {
+                Some(occurrence) if occurrence <= until => {
+                    occurrences.push(occurrence);
+                    cursor = occurrence + Duration::SECOND;
+                }
+                _ => break,
+            }
+        }
+        occurrences
+    }
+
+    /// Candidate dates of the `index`-th period after the one containing `start`,
+    /// in ascending order. `None` once the calendar runs out.
+    fn period_dates(&self, start: Date, index: u32) -> Option<Vec<Date>> {
+        let step = index.checked_mul(self.interval)? as i64;
+
+        match self.frequency {
+            RecurrenceFrequency::Daily => Some(vec![start.checked_add(Duration::days(step))?]),
+            RecurrenceFrequency::Weekly => {
+                if self.by_day.is_empty() {
+                    return Some(vec![start.checked_add(Duration::weeks(step))?]);
+                }
+                let week_start = start
+                    .checked_sub(Duration::days(
+                        start.weekday().number_days_from_monday() as i64
+                    ))?
+                    .checked_add(Duration::weeks(step))?;
+                self.by_day
+                    .iter()
+                    .map(|day| {
+                        week_start.checked_add(Duration::days(day.number_days_from_monday() as i64))
+                    })
+                    .collect()
+            }
+            RecurrenceFrequency::Monthly => {
+                let months = start.year() as i64 * 12 + start.month() as i64 - 1 + step;
+                let year = i32::try_from(months.div_euclid(12)).ok()?;
+                let month = Month::try_from((months.rem_euclid(12) + 1) as u8).ok()?;
+                let length = month.length(year) as i8;
+                let day = match self.by_month_day.unwrap_or(start.day() as i8) {
+                    day if day > 0 => day.min(length),
+                    day => (length + day + 1).max(1),
+                };
+                Date::from_calendar_date(year, month, day as u8)
+                    .ok()
+                    .map(|x| vec![x])
+            }
+            RecurrenceFrequency::Yearly => {
+                let year = start.year().checked_add(i32::try_from(step).ok()?)?;
+                let day = start.day().min(start.month().length(year));
+                Date::from_calendar_date(year, start.month(), day)
+                    .ok()
+                    .map(|x| vec![x])
+            }
+        }
+    }
+}
+
+fn parse_weekday(value: &str) -> Option<Weekday> {
+    match value {
+        "MO" => Some(Weekday::Monday),
+        "TU" => Some(Weekday::Tuesday),
+        "WE" => Some(Weekday::Wednesday),
+        "TH" => Some(Weekday::Thursday),
+        "FR" => Some(Weekday::Friday),
+        "SA" => Some(Weekday::Saturday),
+        "SU" => Some(Weekday::Sunday),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+
+    #[test]
+    fn parses_supported_parts() {
+        let rule: RecurrenceRule = "FREQ=WEEKLY;INTERVAL=2;BYDAY=FR,MO".parse().unwrap();
+
+        assert_eq!(rule.frequency, RecurrenceFrequency::Weekly);
+        assert_eq!(rule.interval, 2);
+        assert_eq!(rule.by_day, vec![Weekday::Monday, Weekday::Friday]);
+    }
+
+    #[test]
+    fn rejects_unsupported_or_invalid_parts() {
+        assert_eq!(
+            "INTERVAL=1".parse::<RecurrenceRule>(),
+            Err(RecurrenceRuleError::MissingFrequency)
+        );
+        assert_eq!(
+            "FREQ=MONTHLY;COUNT=3".parse::<RecurrenceRule>(),
+            Err(RecurrenceRuleError::UnsupportedPart("COUNT".to_string()))
+        );
+        assert!("FREQ=MONTHLY;INTERVAL=0".parse::<RecurrenceRule>().is_err());
+        assert!("FREQ=DAILY;BYMONTHDAY=3".parse::<RecurrenceRule>().is_err());
+    }
+
+    #[test]
+    fn monthly_rule_clamps_to_month_end() {
+        let rule: RecurrenceRule = "FREQ=MONTHLY;BYMONTHDAY=31".parse().unwrap();
+        let start = datetime!(2024-01-31 09:00 UTC);
+
+        let occurrences =
+            rule.occurrences_between(start, start, datetime!(2024-04-30 23:59 UTC), 10);
+
+        assert_eq!(
+            occurrences,
+            vec![
+                datetime!(2024-01-31 09:00 UTC),
+                datetime!(2024-02-29 09:00 UTC),
+                datetime!(2024-03-31 09:00 UTC),
+                datetime!(2024-04-30 09:00 UTC),
+            ]
+        );
+    }
+
+    #[test]
+    fn last_day_of_month_with_negative_month_day() {
+        let rule: RecurrenceRule = "FREQ=MONTHLY;BYMONTHDAY=-1".parse().unwrap();
+        let start = datetime!(2023-02-10 00:00 UTC);
+
+        assert_eq!(
+            rule.next_occurrence(start, start),
+            Some(datetime!(2023-02-28 00:00 UTC))
+        );
+    }
+
+    #[test]
+    fn weekly_rule_with_days_skips_dates_before_start() {
+        let rule: RecurrenceRule = "FREQ=WEEKLY;INTERVAL=2;BYDAY=MO,TH".parse().unwrap();
+        // A Wednesday, so Monday of the first week is already past.
+        let start = datetime!(2024-05-01 12:00 UTC);
+
+        let occurrences =
+            rule.occurrences_between(start, start, datetime!(2024-05-31 00:00 UTC), 10);
+
+        assert_eq!(
+            occurrences,
+            vec![
+                datetime!(2024-05-02 12:00 UTC),
+                datetime!(2024-05-13 12:00 UTC),
+                datetime!(2024-05-16 12:00 UTC),
+                datetime!(2024-05-27 12:00 UTC),
+                datetime!(2024-05-30 12:00 UTC),
+            ]
+        );
+    }
+
+    #[test]
+    fn next_occurrence_respects_from() {
+        let rule: RecurrenceRule = "FREQ=YEARLY".parse().unwrap();
+        let start = datetime!(2020-02-29 00:00 UTC);
+
+        assert_eq!(
+            rule.next_occurrence(start, datetime!(2021-01-01 00:00 UTC)),
+            Some(datetime!(2021-02-28 00:00 UTC))
+        );
+    }
+
+    #[test]
+    fn occurrences_are_capped_by_limit() {
+        let rule: RecurrenceRule = "FREQ=DAILY".parse().unwrap();
+        let start = datetime!(2024-01-01 00:00 UTC);
+
+        let occurrences =
+            rule.occurrences_between(start, start, datetime!(2024-12-31 00:00 UTC), 3);
+
+        assert_eq!(occurrences.len(), 3);
+        assert_eq!(occurrences[2], datetime!(2024-01-03 00:00 UTC));
+    }
+}
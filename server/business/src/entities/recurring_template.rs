@@ -0,0 +1,378 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::dtos::entry_dto::EntryDto;
+use crate::dtos::fee_entry_dto::FeeEntryDto;
+use crate::dtos::fee_entry_types_dto::FeeEntryTypesDto;
+use crate::dtos::transaction_dto::{
+    AccountFeesMetadataDto, AssetBalanceTransferMetadataDto, AssetDividendMetadataDto,
+    AssetPurchaseMetadataDto, AssetSaleMetadataDto, AssetTradeMetadataDto,
+    AssetTransferInMetadataDto, AssetTransferOutMetadataDto, CashBalanceTransferMetadataDto,
+    CashDividendMetadataDto, CashTransferInMetadataDto, CashTransferOutMetadataDto,
+    RegularTransactionMetadataDto, TransactionDto, TransactionTypeDto, TransactionVisibilityDto,
+};
+
+/// Version written by [`StoredRecurringTemplate::from_dto`]. A change to the stored shape
+/// gets a new version, and reading keeps understanding the old ones.
+const CURRENT_VERSION: u32 = 1;
+
+/// The JSON a recurring transaction's template is persisted as. It is kept apart from
+/// [`TransactionDto`] so that the DTO can change without breaking templates already in
+/// the database.
+///
+/// Only what every occurrence copies is stored: no ids, no date (each occurrence gets
+/// its own) and no visibility (the template's ghost flag decides it).
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) struct StoredRecurringTemplate {
+    version: u32,
+    transaction: TemplateTransaction,
+    fees: Vec<TemplateFee>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum TemplateTransaction {
+    Regular {
+        description: Option<String>,
+        category_id: i32,
+        entry: TemplateEntry,
+    },
+    AssetPurchase {
+        purchase: TemplateEntry,
+        sale: TemplateEntry,
+    },
+    AssetSale {
+        sale: TemplateEntry,
+        proceeds: TemplateEntry,
+    },
+    CashTransferIn {
+        entry: TemplateEntry,
+    },
+    CashTransferOut {
+        entry: TemplateEntry,
+    },
+    CashDividend {
+        entry: TemplateEntry,
+        origin_asset_id: i32,
+    },
+    AssetDividend {
+        entry: TemplateEntry,
+    },
+    AssetTransferOut {
+        entry: TemplateEntry,
+    },
+    AssetTransferIn {
+        entry: TemplateEntry,
+    },
+    AssetTrade {
+        outgoing: TemplateEntry,
+        incoming: TemplateEntry,
+    },
+    AssetBalanceTransfer {
+        outgoing: TemplateEntry,
+        incoming: TemplateEntry,
+    },
+    AccountFees {
+        entry: TemplateEntry,
+    },
+    CashBalanceTransfer {
+        outgoing: TemplateEntry,
+        incoming: TemplateEntry,
+    },
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct TemplateEntry {
+    asset_id: i32,
+    account_id: Uuid,
+    quantity: Decimal,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct TemplateFee {
+    fee_type: TemplateFeeType,
+    entry: TemplateEntry,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum TemplateFeeType {
+    Transaction,
+    Exchange,
+    WithholdingTax,
+}
+
+impl StoredRecurringTemplate {
+    pub fn from_dto(dto: &TransactionDto) -> Self {
+        let transaction = match &dto.transaction_type {
+            TransactionTypeDto::Regular(m) => TemplateTransaction::Regular {
+                description: m.description.clone(),
+                category_id: m.category_id,
+                entry: (&m.entry).into(),
+            },
+            TransactionTypeDto::AssetPurchase(m) => TemplateTransaction::AssetPurchase {
+                purchase: (&m.purchase).into(),
+                sale: (&m.sale).into(),
+            },
+            TransactionTypeDto::AssetSale(m) => TemplateTransaction::AssetSale {
+                sale: (&m.sale).into(),
+                proceeds: (&m.proceeds).into(),
+            },
+            TransactionTypeDto::CashTransferIn(m) => TemplateTransaction::CashTransferIn {
+                entry: (&m.entry).into(),
+            },
+            TransactionTypeDto::CashTransferOut(m) => TemplateTransaction::CashTransferOut {
+                entry: (&m.entry).into(),
+            },
+            TransactionTypeDto::CashDividend(m) => TemplateTransaction::CashDividend {
+                entry: (&m.entry).into(),
+                origin_asset_id: m.origin_asset_id,
+            },
+            TransactionTypeDto::AssetDividend(m) => TemplateTransaction::AssetDividend {
+                entry: (&m.entry).into(),
+            },
+            TransactionTypeDto::AssetTransferOut(m) => TemplateTransaction::AssetTransferOut {
+                entry: (&m.entry).into(),
+            },
+            TransactionTypeDto::AssetTransferIn(m) => TemplateTransaction::AssetTransferIn {
+                entry: (&m.entry).into(),
+            },
+            TransactionTypeDto::AssetTrade(m) => TemplateTransaction::AssetTrade {
+                outgoing: (&m.outgoing_entry).into(),
+                incoming: (&m.incoming_entry).into(),
+            },
+            TransactionTypeDto::AssetBalanceTransfer(m) => {
+                TemplateTransaction::AssetBalanceTransfer {
+                    outgoing: (&m.outgoing_change).into(),
+                    incoming: (&m.incoming_change).into(),
+                }
+            }
+            TransactionTypeDto::AccountFees(m) => TemplateTransaction::AccountFees {
+                entry: (&m.entry).into(),
+            },
+            TransactionTypeDto::CashBalanceTransfer(m) => {
+                TemplateTransaction::CashBalanceTransfer {
+                    outgoing: (&m.outgoing_change).into(),
+                    incoming: (&m.incoming_change).into(),
+                }
+            }
+        };
+
+        Self {
+            version: CURRENT_VERSION,
+            transaction,
+            fees: dto
+                .fee_entries
+                .iter()
+                .map(|fee| TemplateFee {
+                    fee_type: (&fee.entry_type).into(),
+                    entry: (&fee.entry).into(),
+                })
+                .collect(),
+        }
+    }
+
+    /// The template as a new transaction dated `date`.
+    pub fn into_dto(self, date: OffsetDateTime) -> TransactionDto {
+        let transaction_type = match self.transaction {
+            TemplateTransaction::Regular {
+                description,
+                category_id,
+                entry,
+            } => TransactionTypeDto::Regular(RegularTransactionMetadataDto {
+                description,
+                entry: entry.into(),
+                category_id,
+            }),
+            TemplateTransaction::AssetPurchase { purchase, sale } => {
+                TransactionTypeDto::AssetPurchase(AssetPurchaseMetadataDto {
+                    purchase: purchase.into(),
+                    sale: sale.into(),
+                })
+            }
+            TemplateTransaction::AssetSale { sale, proceeds } => {
+                TransactionTypeDto::AssetSale(AssetSaleMetadataDto {
+                    sale: sale.into(),
+                    proceeds: proceeds.into(),
+                })
+            }
+            TemplateTransaction::CashTransferIn { entry } => {
+                TransactionTypeDto::CashTransferIn(CashTransferInMetadataDto {
+                    entry: entry.into(),
+                })
+            }
+            TemplateTransaction::CashTransferOut { entry } => {
+                TransactionTypeDto::CashTransferOut(CashTransferOutMetadataDto {
+                    entry: entry.into(),
+                })
+            }
+            TemplateTransaction::CashDividend {
+                entry,
+                origin_asset_id,
+            } => TransactionTypeDto::CashDividend(CashDividendMetadataDto {
+                entry: entry.into(),
+                origin_asset_id,
+            }),
+            TemplateTransaction::AssetDividend { entry } => {
+                TransactionTypeDto::AssetDividend(AssetDividendMetadataDto {
+                    entry: entry.into(),
+                })
+            }
+            TemplateTransaction::AssetTransferOut { entry } => {
+                TransactionTypeDto::AssetTransferOut(AssetTransferOutMetadataDto {
+                    entry: entry.into(),
+                })
+            }
+            TemplateTransaction::AssetTransferIn { entry } => {
+                TransactionTypeDto::AssetTransferIn(AssetTransferInMetadataDto {
+                    entry: entry.into(),
+                })
+            }
+            TemplateTransaction::AssetTrade { outgoing, incoming } => {
+                TransactionTypeDto::AssetTrade(AssetTradeMetadataDto {
+                    outgoing_entry: outgoing.into(),
+                    incoming_entry: incoming.into(),
+                })
+            }
+            TemplateTransaction::AssetBalanceTransfer { outgoing, incoming } => {
+                TransactionTypeDto::AssetBalanceTransfer(AssetBalanceTransferMetadataDto {
+                    outgoing_change: outgoing.into(),
+                    incoming_change: incoming.into(),
+                })
+            }
+            TemplateTransaction::AccountFees { entry } => {
+                TransactionTypeDto::AccountFees(AccountFeesMetadataDto {
+                    entry: entry.into(),
+                })
+            }
+            TemplateTransaction::CashBalanceTransfer { outgoing, incoming } => {
+                TransactionTypeDto::CashBalanceTransfer(CashBalanceTransferMetadataDto {
+                    outgoing_change: outgoing.into(),
+                    incoming_change: incoming.into(),
+                })
+            }
+        };
+
+        TransactionDto {
+            transaction_id: None,
+            date,
+            visibility: TransactionVisibilityDto::Default,
+            fee_entries: self
+                .fees
+                .into_iter()
+                .map(|fee| FeeEntryDto {
+                    entry: fee.entry.into(),
+                    entry_type: fee.fee_type.into(),
+                })
+                .collect(),
+            transaction_type,
+        }
+    }
+
+    pub fn to_json(&self) -> anyhow::Result<serde_json::Value> {
+        Ok(serde_json::to_value(self)?)
+    }
+
+    pub fn from_json(value: serde_json::Value) -> anyhow::Result<Self> {
+        let version = value.get("version").and_then(|v| v.as_u64());
+        if version != Some(u64::from(CURRENT_VERSION)) {
+            anyhow::bail!("Unsupported recurring transaction template version {version:?}");
+        }
+        Ok(serde_json::from_value(value)?)
+    }
+}
+
+impl From<&EntryDto> for TemplateEntry {
+    fn from(entry: &EntryDto) -> Self {
+        Self {
+            asset_id: entry.asset_id,
+            account_id: entry.account_id,
+            quantity: entry.quantity,
+        }
+    }
+}
+
+impl From<TemplateEntry> for EntryDto {
+    fn from(entry: TemplateEntry) -> Self {
+        EntryDto::new(entry.asset_id, entry.account_id, entry.quantity)
+    }
+}
+
+impl From<&FeeEntryTypesDto> for TemplateFeeType {
+    fn from(fee_type: &FeeEntryTypesDto) -> Self {
+        match fee_type {
+            FeeEntryTypesDto::Transaction => Self::Transaction,
+            FeeEntryTypesDto::Exchange => Self::Exchange,
+            FeeEntryTypesDto::WithholdingTax => Self::WithholdingTax,
+        }
+    }
+}
+
+impl From<TemplateFeeType> for FeeEntryTypesDto {
+    fn from(fee_type: TemplateFeeType) -> Self {
+        match fee_type {
+            TemplateFeeType::Transaction => Self::Transaction,
+            TemplateFeeType::Exchange => Self::Exchange,
+            TemplateFeeType::WithholdingTax => Self::WithholdingTax,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use serde_json::json;
+    use time::macros::datetime;
+
+    use super::*;
+
+    fn purchase_with_fee() -> TransactionDto {
+        let account_id = Uuid::from_u128(1);
+        TransactionDto {
+            transaction_id: Some(Uuid::from_u128(2)),
+            date: datetime!(2026-01-01 0:00 UTC),
+            visibility: TransactionVisibilityDto::Hidden,
+            fee_entries: vec![FeeEntryDto {
+                entry: EntryDto::new(1, account_id, dec!(-1.5)),
+                entry_type: FeeEntryTypesDto::Transaction,
+            }],
+            transaction_type: TransactionTypeDto::AssetPurchase(AssetPurchaseMetadataDto {
+                purchase: EntryDto::new(7, account_id, dec!(0.25)),
+                sale: EntryDto::new(1, account_id, dec!(-100)),
+            }),
+        }
+    }
+
+    #[test]
+    fn round_trips_through_json() {
+        let stored = StoredRecurringTemplate::from_dto(&purchase_with_fee());
+        let json = stored.to_json().unwrap();
+        assert_eq!(json["version"], json!(1));
+        assert_eq!(StoredRecurringTemplate::from_json(json).unwrap(), stored);
+
+        let dto = stored.into_dto(datetime!(2026-02-01 0:00 UTC));
+        assert_eq!(dto.transaction_id, None);
+        assert_eq!(dto.date, datetime!(2026-02-01 0:00 UTC));
+        assert_eq!(dto.visibility, TransactionVisibilityDto::Default);
+        assert_eq!(dto.fee_entries.len(), 1);
+        assert_eq!(dto.fee_entries[0].entry.quantity, dec!(-1.5));
+        let TransactionTypeDto::AssetPurchase(purchase) = dto.transaction_type else {
+            panic!("expected an asset purchase");
+        };
+        assert_eq!(purchase.purchase.asset_id, 7);
+        assert_eq!(purchase.purchase.quantity, dec!(0.25));
+        assert_eq!(purchase.sale.quantity, dec!(-100));
+    }
+
+    #[test]
+    fn rejects_unknown_versions() {
+        let mut json = StoredRecurringTemplate::from_dto(&purchase_with_fee())
+            .to_json()
+            .unwrap();
+        json["version"] = json!(2);
+        assert!(StoredRecurringTemplate::from_json(json).is_err());
+        assert!(StoredRecurringTemplate::from_json(json!({})).is_err());
+    }
+}
@@ -0,0 +1,397 @@
+use std::collections::{HashMap, HashSet};
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use time::{Duration, OffsetDateTime};
+use uuid::Uuid;
+
+use crate::dtos::spending_alert_dto::{DetectedSpendingAlertDto, SpendingAlertKindDto};
+
+use super::subscriptions::normalize_description;
+
+/// Earlier charges needed before a merchant, a category or an account has a "usual".
+const MIN_SAMPLES: usize = 5;
+/// How many standard deviations above the mean an unusual amount lies at least.
+const DEVIATIONS: Decimal = dec!(3);
+/// And how many times the mean, so tight distributions do not flag small rises.
+const MIN_RATIO: Decimal = dec!(2);
+/// How close together two identical charges must be to count as a duplicate.
+const DUPLICATE_WINDOW: Duration = Duration::days(1);
+/// Identical charges the user paid this often before are a habit, not a duplicate.
+const HABITUAL_REPEATS: usize = 3;
+
+/// One outgoing regular transaction in a single account, asset and category, as seen
+/// by the detector.
+#[derive(Clone, Debug)]
+pub struct AlertCharge {
+    pub transaction_id: Uuid,
+    pub date: OffsetDateTime,
+    pub description: Option<String>,
+    pub account_id: Uuid,
+    pub asset_id: i32,
+    pub category_id: i32,
+    /// Amount paid, positive.
+    pub amount: Decimal,
+}
+
+impl AlertCharge {
+    fn merchant(&self) -> Option<String> {
+        self.description
+            .as_deref()
+            .map(normalize_description)
+            .filter(|key| !key.is_empty())
+    }
+
+    fn is_before(&self, other: &AlertCharge) -> bool {
+        (self.date, self.transaction_id) < (other.date, other.transaction_id)
+    }
+}
+
+/// `history` in date order, each charge with its merchant normalized once, indexed
+/// the ways the checks look charges up. Every index lists charges in date order, so
+/// the ones before a charge are a prefix of it.
+struct ChargeHistory<'a> {
+    charges: Vec<(&'a AlertCharge, Option<String>)>,
+    /// By account, asset and merchant.
+    by_account_merchant: HashMap<(Uuid, i32, String), Vec<usize>>,
+    /// By asset and merchant, across accounts.
+    by_merchant: HashMap<(i32, String), Vec<usize>>,
+    /// By asset and category.
+    by_category: HashMap<(i32, i32), Vec<usize>>,
+    by_account: HashMap<Uuid, Vec<usize>>,
+    /// The first charge from each merchant.
+    first_by_merchant: HashMap<String, usize>,
+}
+
+impl<'a> ChargeHistory<'a> {
+    fn new(history: &'a [AlertCharge]) -> Self {
+        let mut charges: Vec<(&AlertCharge, Option<String>)> =
+            history.iter().map(|h| (h, h.merchant())).collect();
+        charges.sort_by_key(|(h, _)| (h.date, h.transaction_id));
+
+        let mut by_account_merchant: HashMap<(Uuid, i32, String), Vec<usize>> = HashMap::new();
+        let mut by_merchant: HashMap<(i32, String), Vec<usize>> = HashMap::new();
+        let mut by_category: HashMap<(i32, i32), Vec<usize>> = HashMap::new();
+        let mut by_account: HashMap<Uuid, Vec<usize>> = HashMap::new();
+        let mut first_by_merchant: HashMap<String, usize> = HashMap::new();
+        for (i, (h, merchant)) in charges.iter().enumerate() {
+            if let Some(merchant) = merchant {
+                by_account_merchant
+                    .entry((h.account_id, h.asset_id, merchant.clone()))
+                    .or_default()
+                    .push(i);
+                by_merchant
+                    .entry((h.asset_id, merchant.clone()))
+                    .or_default()
+                    .push(i);
+                first_by_merchant.entry(merchant.clone()).or_insert(i);
+            }
+            by_category
+                .entry((h.asset_id, h.category_id))
+                .or_default()
+                .push(i);
+            by_account.entry(h.account_id).or_default().push(i);
+        }
+
+        Self {
+            charges,
+            by_account_merchant,
+            by_merchant,
+            by_category,
+            by_account,
+            first_by_merchant,
+        }
+    }
+
+    /// The charges of `index` that came before `charge`.
+    fn before<'s>(
+        &'s self,
+        index: Option<&'s Vec<usize>>,
+        charge: &AlertCharge,
+    ) -> impl Iterator<Item = &'a AlertCharge> + Clone + 's {
+        let index = index.map(Vec::as_slice).unwrap_or_default();
+        let end = index.partition_point(|&i| self.charges[i].0.is_before(charge));
+        index[..end].iter().map(|&i| self.charges[i].0)
+    }
+
+    fn count_before(&self, charge: &AlertCharge) -> usize {
+        self.charges.partition_point(|(h, _)| h.is_before(charge))
+    }
+
+    fn merchant_seen_before(&self, merchant: &str, charge: &AlertCharge) -> bool {
+        self.first_by_merchant
+            .get(merchant)
+            .is_some_and(|&i| self.charges[i].0.is_before(charge))
+    }
+}
+
+/// Checks each of `checked` against the charges that came before it in `history`,
+/// which may include the checked charges themselves. Flags:
+///
+/// - amounts far above the usual for the merchant, or for the category when the
+///   merchant has too few earlier charges;
+/// - the same amount charged by the same merchant on the same account within a day of
+///   an earlier charge, unless the user pays that amount there habitually;
+/// - a first purchase from a merchant in a currency other than `base_asset_id`. The
+///   providers give no merchant location, so the currency stands in for "abroad";
+/// - a currency the account has not been charged in before.
+///
+/// A transaction gets at most one alert of each kind.
+pub fn detect_spending_alerts(
+    checked: &[AlertCharge],
+    history: &[AlertCharge],
+    base_asset_id: Option<i32>,
+) -> Vec<DetectedSpendingAlertDto> {
+    let mut alerts: Vec<DetectedSpendingAlertDto> = Vec::new();
+    let mut seen: HashSet<(Uuid, SpendingAlertKindDto)> = HashSet::new();
+    let history = ChargeHistory::new(history);
+
+    for charge in checked {
+        let merchant = charge.merchant();
+
+        let found =
+            [
+                unusual_amount(charge, merchant.as_deref(), &history)
+                    .map(|typical| (SpendingAlertKindDto::UnusualAmount, Some(typical), None)),
+                duplicate_of(charge, merchant.as_deref(), &history)
+                    .map(|related| (SpendingAlertKindDto::DuplicateCharge, None, Some(related))),
+                new_merchant_abroad(charge, merchant.as_deref(), &history, base_asset_id)
+                    .then_some((SpendingAlertKindDto::NewMerchantAbroad, None, None)),
+                unexpected_currency(charge, &history).then_some((
+                    SpendingAlertKindDto::UnexpectedCurrency,
+                    None,
+                    None,
+                )),
+            ];
+
+        for (kind, typical_amount, related_transaction_id) in found.into_iter().flatten() {
+            if !seen.insert((charge.transaction_id, kind)) {
+                continue;
+            }
+            alerts.push(DetectedSpendingAlertDto {
+                transaction_id: charge.transaction_id,
+                kind,
+                account_id: charge.account_id,
+                asset_id: charge.asset_id,
+                amount: charge.amount,
+                typical_amount,
+                related_transaction_id,
+                description: charge.description.clone(),
+                date_transacted: charge.date,
+            });
+        }
+    }
+
+    alerts
+}
+
+/// The usual amount, when `charge` lies far above it.
+fn unusual_amount(
+    charge: &AlertCharge,
+    merchant: Option<&str>,
+    history: &ChargeHistory,
+) -> Option<Decimal> {
+    let by_merchant: Vec<Decimal> = merchant
+        .map(|merchant| {
+            let index = history
+                .by_merchant
+                .get(&(charge.asset_id, merchant.to_string()));
+            history.before(index, charge).map(|h| h.amount).collect()
+        })
+        .unwrap_or_default();
+    let samples: Vec<Decimal> = if by_merchant.len() >= MIN_SAMPLES {
+        by_merchant
+    } else {
+        let index = history
+            .by_category
+            .get(&(charge.asset_id, charge.category_id));
+        history.before(index, charge).map(|h| h.amount).collect()
+    };
+    if samples.len() < MIN_SAMPLES {
+        return None;
+    }
+
+    let count = Decimal::from(samples.len());
+    let mean = samples.iter().sum::<Decimal>() / count;
+    let variance = samples
+        .iter()
+        .map(|x| (*x - mean) * (*x - mean))
+        .sum::<Decimal>()
+        / (count - Decimal::ONE);
+
+    // Compared squared so no square root is needed.
+    let excess = charge.amount - mean;
+    let far_out = excess > Decimal::ZERO && excess * excess > DEVIATIONS * DEVIATIONS * variance;
+    (far_out && charge.amount >= mean * MIN_RATIO).then(|| mean.round_dp(2))
+}
+
+/// The latest earlier charge `charge` repeats.
+fn duplicate_of(
+    charge: &AlertCharge,
+    merchant: Option<&str>,
+    history: &ChargeHistory,
+) -> Option<Uuid> {
+    let index = history.by_account_merchant.get(&(
+        charge.account_id,
+        charge.asset_id,
+        merchant?.to_string(),
+    ));
+    let (recent, earlier): (Vec<&AlertCharge>, Vec<&AlertCharge>) = history
+        .before(index, charge)
+        .filter(|h| h.amount == charge.amount && h.transaction_id != charge.transaction_id)
+        .partition(|h| charge.date - h.date <= DUPLICATE_WINDOW);
+    if earlier.len() >= HABITUAL_REPEATS {
+        return None;
+    }
+    recent.last().map(|h| h.transaction_id)
+}
+
+fn new_merchant_abroad(
+    charge: &AlertCharge,
+    merchant: Option<&str>,
+    history: &ChargeHistory,
+    base_asset_id: Option<i32>,
+) -> bool {
+    let (Some(merchant), Some(base_asset_id)) = (merchant, base_asset_id) else {
+        return false;
+    };
+    // Everything is new to a user without history, e.g. on their first import.
+    charge.asset_id != base_asset_id
+        && history.count_before(charge) >= MIN_SAMPLES
+        && !history.merchant_seen_before(merchant, charge)
+}
+
+fn unexpected_currency(charge: &AlertCharge, history: &ChargeHistory) -> bool {
+    let mut on_account = history.before(history.by_account.get(&charge.account_id), charge);
+    on_account.clone().count() >= MIN_SAMPLES && !on_account.any(|h| h.asset_id == charge.asset_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+
+    const EUR: i32 = 1;
+    const USD: i32 = 2;
+    const GROCERIES: i32 = 10;
+
+    fn charge(date: OffsetDateTime, description: &str, amount: Decimal) -> AlertCharge {
+        AlertCharge {
+            transaction_id: Uuid::new_v4(),
+            date,
+            description: Some(description.to_string()),
+            account_id: Uuid::nil(),
+            asset_id: EUR,
+            category_id: GROCERIES,
+            amount,
+        }
+    }
+
+    fn weekly_shopping() -> Vec<AlertCharge> {
+        (0..8)
+            .map(|week| {
+                charge(
+                    datetime!(2024-01-01 12:00 UTC) + Duration::weeks(week),
+                    "SUPERMARKT 42",
+                    dec!(60) + Decimal::from(week),
+                )
+            })
+            .collect()
+    }
+
+    fn kinds(alerts: &[DetectedSpendingAlertDto]) -> Vec<SpendingAlertKindDto> {
+        alerts.iter().map(|a| a.kind).collect()
+    }
+
+    #[test]
+    fn flags_amount_far_above_the_merchant_usual() {
+        let history = weekly_shopping();
+        let big = charge(datetime!(2024-03-01 12:00 UTC), "Supermarkt 43", dec!(480));
+        let usual = charge(datetime!(2024-03-02 12:00 UTC), "Supermarkt 44", dec!(70));
+
+        let alerts = detect_spending_alerts(&[big.clone(), usual], &history, Some(EUR));
+
+        assert_eq!(kinds(&alerts), vec![SpendingAlertKindDto::UnusualAmount]);
+        assert_eq!(alerts[0].transaction_id, big.transaction_id);
+        assert_eq!(alerts[0].typical_amount, Some(dec!(63.50)));
+    }
+
+    #[test]
+    fn falls_back_to_the_category_for_unknown_merchants() {
+        let history = weekly_shopping();
+        let big = charge(datetime!(2024-03-01 12:00 UTC), "Deli corner", dec!(400));
+
+        let alerts = detect_spending_alerts(&[big], &history, Some(EUR));
+
+        assert_eq!(kinds(&alerts), vec![SpendingAlertKindDto::UnusualAmount]);
+    }
+
+    #[test]
+    fn flags_the_second_of_two_identical_charges() {
+        let mut history = weekly_shopping();
+        let first = charge(datetime!(2024-03-01 09:00 UTC), "Electro Shop", dec!(64.99));
+        let second = charge(datetime!(2024-03-01 09:05 UTC), "ELECTRO SHOP", dec!(64.99));
+        history.extend([first.clone(), second.clone()]);
+
+        let alerts = detect_spending_alerts(&[first.clone(), second.clone()], &history, Some(EUR));
+
+        assert_eq!(kinds(&alerts), vec![SpendingAlertKindDto::DuplicateCharge]);
+        assert_eq!(alerts[0].transaction_id, second.transaction_id);
+        assert_eq!(alerts[0].related_transaction_id, Some(first.transaction_id));
+    }
+
+    #[test]
+    fn habitual_purchases_are_not_duplicates() {
+        let mut history: Vec<AlertCharge> = (0..10)
+            .map(|day| {
+                charge(
+                    datetime!(2024-02-01 08:00 UTC) + Duration::days(day),
+                    "Coffee house",
+                    dec!(3.50),
+                )
+            })
+            .collect();
+        let today = charge(datetime!(2024-02-11 08:00 UTC), "Coffee house", dec!(3.50));
+        history.push(today.clone());
+
+        assert!(detect_spending_alerts(&[today], &history, Some(EUR)).is_empty());
+    }
+
+    #[test]
+    fn flags_first_purchase_from_a_merchant_in_a_foreign_currency() {
+        let history = weekly_shopping();
+        let mut abroad = charge(datetime!(2024-03-01 12:00 UTC), "NYC Souvenirs", dec!(25));
+        abroad.asset_id = USD;
+
+        let alerts = detect_spending_alerts(&[abroad], &history, Some(EUR));
+
+        assert_eq!(
+            kinds(&alerts),
+            vec![
+                SpendingAlertKindDto::NewMerchantAbroad,
+                SpendingAlertKindDto::UnexpectedCurrency
+            ]
+        );
+    }
+
+    #[test]
+    fn known_currency_on_the_account_is_expected() {
+        let mut history = weekly_shopping();
+        for x in history.iter_mut().take(2) {
+            x.asset_id = USD;
+        }
+        let mut again = charge(datetime!(2024-03-01 12:00 UTC), "SUPERMARKT 99", dec!(62));
+        again.asset_id = USD;
+
+        assert!(detect_spending_alerts(&[again], &history, Some(EUR)).is_empty());
+    }
+
+    #[test]
+    fn nothing_is_flagged_without_history() {
+        let mut first = charge(datetime!(2024-03-01 12:00 UTC), "Hotel Roma", dec!(900));
+        first.asset_id = USD;
+
+        assert!(detect_spending_alerts(&[first.clone()], &[first], Some(EUR)).is_empty());
+    }
+}
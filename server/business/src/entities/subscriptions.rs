@@ -0,0 +1,365 @@
+use std::collections::HashMap;
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use time::{Date, Duration, Month};
+use uuid::Uuid;
+
+use crate::dtos::subscription_dto::{DetectedSubscriptionDto, SubscriptionPeriodDto};
+
+/// Cosine similarity above which two descriptions are treated as the same merchant.
+const EMBEDDING_SIMILARITY_THRESHOLD: f32 = 0.92;
+/// How far a charge may drift from the previous one in the series, e.g. after a price rise.
+const AMOUNT_TOLERANCE: Decimal = dec!(0.3);
+/// Amounts within this share of the median count as "the usual amount" for confidence.
+const STABLE_AMOUNT_TOLERANCE: Decimal = dec!(0.1);
+/// Share of gaps between charges that must match the detected period.
+const MIN_REGULAR_INTERVALS: f64 = 0.6;
+
+/// One outgoing regular transaction, as seen by the detector.
+#[derive(Clone, Debug)]
+pub struct SubscriptionCharge {
+    pub transaction_id: Uuid,
+    pub date: Date,
+    pub description: String,
+    pub embedding: Option<Vec<f32>>,
+    pub account_id: Uuid,
+    pub asset_id: i32,
+    /// Amount paid, positive.
+    pub amount: Decimal,
+}
+
+/// Lowercases a description and drops punctuation and any token containing a digit,
+/// so `NETFLIX.COM 8841` and `Netflix.com 1203` compare equal.
+pub fn normalize_description(description: &str) -> String {
+    description
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty() && !token.chars().any(|c| c.is_ascii_digit()))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Groups `charges` into series paid from the same account in the same asset to the
+/// same merchant, and reports the series that repeat on a weekly, monthly, quarterly
+/// or yearly cadence.
+///
+/// Charges belong to the same merchant when their normalized descriptions match or,
+/// failing that, their description embedding is close to that of the series' first
+/// charge. Series whose next expected charge is overdue by more than half a period are
+/// treated as cancelled and left out.
+pub fn detect_subscriptions(
+    mut charges: Vec<SubscriptionCharge>,
+    today: Date,
+) -> Vec<DetectedSubscriptionDto> {
+    charges.sort_by_key(|x| x.date);
+
+    let mut clusters: Vec<Vec<SubscriptionCharge>> = Vec::new();
+    // Clusters by account, asset and the normalized descriptions of their charges.
+    let mut by_description: HashMap<(Uuid, i32, String), Vec<usize>> = HashMap::new();
+    // Clusters by account and asset whose first charge has an embedding, for charges
+    // whose description matches no cluster.
+    let mut embedded_seeds: HashMap<(Uuid, i32), Vec<usize>> = HashMap::new();
+    for charge in charges {
+        let account_asset = (charge.account_id, charge.asset_id);
+        let key = (
+            charge.account_id,
+            charge.asset_id,
+            normalize_description(&charge.description),
+        );
+        let amount_fits = |cluster: &[SubscriptionCharge]| {
+            amounts_close(
+                cluster[cluster.len() - 1].amount,
+                charge.amount,
+                AMOUNT_TOLERANCE,
+            )
+        };
+        let matching = by_description
+            .get(&key)
+            .and_then(|indices| {
+                indices
+                    .iter()
+                    .copied()
+                    .find(|&index| amount_fits(&clusters[index]))
+            })
+            .or_else(|| {
+                charge.embedding.as_ref()?;
+                embedded_seeds
+                    .get(&account_asset)?
+                    .iter()
+                    .copied()
+                    .find(|&index| {
+                        amount_fits(&clusters[index])
+                            && embeddings_similar(&clusters[index][0].embedding, &charge.embedding)
+                    })
+            });
+
+        match matching {
+            Some(index) => {
+                let indices = by_description.entry(key).or_default();
+                if !indices.contains(&index) {
+                    indices.push(index);
+                }
+                clusters[index].push(charge);
+            }
+            None => {
+                let index = clusters.len();
+                if charge.embedding.is_some() {
+                    embedded_seeds.entry(account_asset).or_default().push(index);
+                }
+                by_description.entry(key).or_default().push(index);
+                clusters.push(vec![charge]);
+            }
+        }
+    }
+
+    let mut detected: Vec<DetectedSubscriptionDto> = clusters
+        .into_iter()
+        .filter_map(|cluster| summarize_cluster(cluster, today))
+        .collect();
+    detected.sort_by_key(|x| x.next_charge_date);
+    detected
+}
+
+fn summarize_cluster(
+    cluster: Vec<SubscriptionCharge>,
+    today: Date,
+) -> Option<DetectedSubscriptionDto> {
+    if cluster.len() < 2 {
+        return None;
+    }
+
+    let intervals: Vec<i64> = cluster
+        .windows(2)
+        .map(|pair| (pair[1].date - pair[0].date).whole_days())
+        .collect();
+    let period = classify_period(median_interval(&intervals))?;
+
+    let min_charges = match period {
+        SubscriptionPeriodDto::Yearly => 2,
+        _ => 3,
+    };
+    if cluster.len() < min_charges {
+        return None;
+    }
+
+    let regular_intervals = intervals
+        .iter()
+        .filter(|x| classify_period(**x) == Some(period))
+        .count() as f64
+        / intervals.len() as f64;
+    if regular_intervals < MIN_REGULAR_INTERVALS {
+        return None;
+    }
+
+    let last = &cluster[cluster.len() - 1];
+    let next_charge_date = next_charge(period, last.date)?;
+    if today > next_charge_date + grace_period(period) {
+        return None;
+    }
+
+    let mut amounts: Vec<Decimal> = cluster.iter().map(|x| x.amount).collect();
+    amounts.sort();
+    let amount = amounts[amounts.len() / 2];
+    let stable_amounts = amounts
+        .iter()
+        .filter(|x| amounts_close(amount, **x, STABLE_AMOUNT_TOLERANCE))
+        .count() as f64
+        / amounts.len() as f64;
+
+    // More charges make a coincidence less likely; six or more count fully.
+    let history = (cluster.len() as f64 / 6.0).min(1.0);
+    let confidence = 0.5 * regular_intervals + 0.3 * stable_amounts + 0.2 * history;
+
+    Some(DetectedSubscriptionDto {
+        description: last.description.clone(),
+        account_id: last.account_id,
+        asset_id: last.asset_id,
+        period,
+        amount,
+        last_amount: last.amount,
+        first_charge_date: cluster[0].date,
+        last_charge_date: last.date,
+        next_charge_date,
+        charge_count: cluster.len(),
+        confidence: (confidence * 100.0).round() / 100.0,
+        transaction_ids: cluster.iter().map(|x| x.transaction_id).collect(),
+    })
+}
+
+fn median_interval(intervals: &[i64]) -> i64 {
+    let mut sorted = intervals.to_vec();
+    sorted.sort_unstable();
+    sorted[sorted.len() / 2]
+}
+
+/// Maps a gap in days to the cadence it fits, allowing for months of different
+/// lengths and charges that land a few days early or late.
+fn classify_period(days: i64) -> Option<SubscriptionPeriodDto> {
+    match days {
+        6..=8 => Some(SubscriptionPeriodDto::Weekly),
+        26..=35 => Some(SubscriptionPeriodDto::Monthly),
+        85..=98 => Some(SubscriptionPeriodDto::Quarterly),
+        350..=380 => Some(SubscriptionPeriodDto::Yearly),
+        _ => None,
+    }
+}
+
+fn next_charge(period: SubscriptionPeriodDto, last: Date) -> Option<Date> {
+    match period {
+        SubscriptionPeriodDto::Weekly => last.checked_add(Duration::weeks(1)),
+        SubscriptionPeriodDto::Monthly => add_months(last, 1),
+        SubscriptionPeriodDto::Quarterly => add_months(last, 3),
+        SubscriptionPeriodDto::Yearly => add_months(last, 12),
+    }
+}
+
+fn grace_period(period: SubscriptionPeriodDto) -> Duration {
+    match period {
+        SubscriptionPeriodDto::Weekly => Duration::days(3),
+        SubscriptionPeriodDto::Monthly => Duration::days(15),
+        SubscriptionPeriodDto::Quarterly => Duration::days(45),
+        SubscriptionPeriodDto::Yearly => Duration::days(180),
+    }
+}
+
+/// Same day `months` later, clamped to the end of shorter months.
+fn add_months(date: Date, months: i32) -> Option<Date> {
+    let index = date.year() * 12 + date.month() as i32 - 1 + months;
+    let year = index.div_euclid(12);
+    let month = Month::try_from((index.rem_euclid(12) + 1) as u8).ok()?;
+    let day = date.day().min(month.length(year));
+    Date::from_calendar_date(year, month, day).ok()
+}
+
+fn amounts_close(reference: Decimal, amount: Decimal, tolerance: Decimal) -> bool {
+    if reference.is_zero() {
+        return amount.is_zero();
+    }
+    ((amount - reference) / reference).abs() <= tolerance
+}
+
+fn embeddings_similar(a: &Option<Vec<f32>>, b: &Option<Vec<f32>>) -> bool {
+    let (Some(a), Some(b)) = (a, b) else {
+        return false;
+    };
+    if a.len() != b.len() || a.is_empty() {
+        return false;
+    }
+
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b: f32 = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return false;
+    }
+    dot / (norm_a * norm_b) >= EMBEDDING_SIMILARITY_THRESHOLD
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use time::macros::date;
+
+    use super::*;
+
+    fn charge(date: Date, description: &str, amount: Decimal) -> SubscriptionCharge {
+        SubscriptionCharge {
+            transaction_id: Uuid::new_v4(),
+            date,
+            description: description.to_string(),
+            embedding: None,
+            account_id: Uuid::nil(),
+            asset_id: 1,
+            amount,
+        }
+    }
+
+    #[test]
+    fn normalize_description_drops_reference_numbers() {
+        assert_eq!(
+            normalize_description("NETFLIX.COM 8841*AB12"),
+            normalize_description("Netflix.com  1203")
+        );
+        assert_eq!(normalize_description("Spotify P1A2B3"), "spotify");
+    }
+
+    #[test]
+    fn detects_monthly_subscription_with_varying_references() {
+        let charges = vec![
+            charge(date!(2024 - 01 - 15), "NETFLIX.COM 1111", dec!(15.99)),
+            charge(date!(2024 - 02 - 15), "NETFLIX.COM 2222", dec!(15.99)),
+            charge(date!(2024 - 03 - 16), "NETFLIX.COM 3333", dec!(15.99)),
+            charge(date!(2024 - 04 - 15), "NETFLIX.COM 4444", dec!(17.99)),
+            charge(date!(2024 - 03 - 02), "Corner shop", dec!(8.20)),
+        ];
+
+        let detected = detect_subscriptions(charges, date!(2024 - 05 - 01));
+
+        assert_eq!(detected.len(), 1);
+        let netflix = &detected[0];
+        assert_eq!(netflix.period, SubscriptionPeriodDto::Monthly);
+        assert_eq!(netflix.charge_count, 4);
+        assert_eq!(netflix.amount, dec!(15.99));
+        assert_eq!(netflix.last_amount, dec!(17.99));
+        assert_eq!(netflix.next_charge_date, date!(2024 - 05 - 15));
+    }
+
+    #[test]
+    fn merges_differently_worded_descriptions_by_embedding() {
+        let mut charges = vec![
+            charge(date!(2024 - 01 - 03), "Gym membership", dec!(30)),
+            charge(date!(2024 - 02 - 03), "PureGym Ltd", dec!(30)),
+            charge(date!(2024 - 03 - 03), "PUREGYM DD", dec!(30)),
+        ];
+        for x in &mut charges {
+            x.embedding = Some(vec![0.9, 0.1, 0.0]);
+        }
+
+        let detected = detect_subscriptions(charges, date!(2024 - 03 - 20));
+
+        assert_eq!(detected.len(), 1);
+        assert_eq!(detected[0].description, "PUREGYM DD");
+    }
+
+    #[test]
+    fn irregular_purchases_are_not_subscriptions() {
+        let charges = vec![
+            charge(date!(2024 - 01 - 02), "Coffee house", dec!(3.5)),
+            charge(date!(2024 - 01 - 04), "Coffee house", dec!(3.5)),
+            charge(date!(2024 - 01 - 19), "Coffee house", dec!(3.5)),
+            charge(date!(2024 - 03 - 01), "Coffee house", dec!(3.5)),
+        ];
+
+        assert!(detect_subscriptions(charges, date!(2024 - 03 - 05)).is_empty());
+    }
+
+    #[test]
+    fn cancelled_subscription_is_left_out() {
+        let charges = vec![
+            charge(date!(2023 - 01 - 10), "Streaming+", dec!(9.99)),
+            charge(date!(2023 - 02 - 10), "Streaming+", dec!(9.99)),
+            charge(date!(2023 - 03 - 10), "Streaming+", dec!(9.99)),
+        ];
+
+        assert_eq!(
+            detect_subscriptions(charges.clone(), date!(2023 - 04 - 20)).len(),
+            1
+        );
+        assert!(detect_subscriptions(charges, date!(2023 - 07 - 01)).is_empty());
+    }
+
+    #[test]
+    fn yearly_subscription_needs_two_charges() {
+        let charges = vec![
+            charge(date!(2023 - 06 - 01), "Domain renewal", dec!(12)),
+            charge(date!(2024 - 06 - 01), "Domain renewal", dec!(12)),
+        ];
+
+        let detected = detect_subscriptions(charges, date!(2024 - 08 - 01));
+
+        assert_eq!(detected.len(), 1);
+        assert_eq!(detected[0].period, SubscriptionPeriodDto::Yearly);
+        assert_eq!(detected[0].next_charge_date, date!(2025 - 06 - 01));
+    }
+}
@@ -0,0 +1,447 @@
+use regex::{Regex, RegexBuilder};
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use crate::dtos::transaction_dto::{TransactionDto, TransactionTypeDto, TransactionVisibilityDto};
+use crate::dtos::transaction_rule_dto::{RuleActionsDto, RuleConditionsDto, TransactionRuleDto};
+
+/// Upper bound on a compiled description regex, so a user cannot make every import pay
+/// for a huge automaton.
+const REGEX_SIZE_LIMIT: usize = 1 << 20;
+
+pub fn compile_description_regex(pattern: &str) -> Result<Regex, regex::Error> {
+    RegexBuilder::new(pattern)
+        .size_limit(REGEX_SIZE_LIMIT)
+        .build()
+}
+
+/// The parts of a transaction rules look at.
+#[derive(Clone, Copy, Debug)]
+pub struct RuleSubject<'a> {
+    pub description: &'a str,
+    pub amount: Decimal,
+    pub account_id: Uuid,
+    pub counterparty: Option<&'a str>,
+}
+
+/// The combined effect of every rule matching a transaction.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RuleOutcome {
+    pub rule_ids: Vec<Uuid>,
+    pub category_id: Option<i32>,
+    pub tag_ids: Vec<Uuid>,
+    pub description: Option<String>,
+    pub visibility: Option<TransactionVisibilityDto>,
+}
+
+/// What applying the rules did to a transaction.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AppliedRules {
+    pub rule_ids: Vec<Uuid>,
+    /// Tags to add; they live outside the transaction.
+    pub tag_ids: Vec<Uuid>,
+    /// The category, description or visibility was changed.
+    pub changed: bool,
+}
+
+struct CompiledRule {
+    id: Uuid,
+    conditions: RuleConditionsDto,
+    regex: Option<Regex>,
+    actions: RuleActionsDto,
+}
+
+impl CompiledRule {
+    fn matches(&self, subject: &RuleSubject) -> bool {
+        let conditions = &self.conditions;
+        conditions
+            .description_contains
+            .as_deref()
+            .is_none_or(|needle| contains_ignore_case(subject.description, needle))
+            && self
+                .regex
+                .as_ref()
+                .is_none_or(|regex| regex.is_match(subject.description))
+            && conditions
+                .amount_min
+                .is_none_or(|min| subject.amount >= min)
+            && conditions
+                .amount_max
+                .is_none_or(|max| subject.amount <= max)
+            && conditions
+                .account_id
+                .is_none_or(|account_id| subject.account_id == account_id)
+            && conditions.counterparty.as_deref().is_none_or(|needle| {
+                subject
+                    .counterparty
+                    .is_some_and(|counterparty| contains_ignore_case(counterparty, needle))
+            })
+    }
+
+    fn rewrite(&self, description: &str, template: &str) -> String {
+        match self
+            .regex
+            .as_ref()
+            .and_then(|regex| regex.captures(description))
+        {
+            Some(captures) => {
+                let mut rewritten = String::new();
+                captures.expand(template, &mut rewritten);
+                rewritten
+            }
+            None => template.to_string(),
+        }
+    }
+}
+
+fn contains_ignore_case(haystack: &str, needle: &str) -> bool {
+    haystack.to_lowercase().contains(&needle.to_lowercase())
+}
+
+/// The user's enabled rules, ready to run. Rules run in ascending priority: for the
+/// category, description and visibility the first matching rule that sets one wins, and
+/// the tags of every matching rule are added. Every rule sees the description as it came
+/// in, not as an earlier rule rewrote it.
+///
+/// Rules only categorize; a transaction no rule matches is left for the description
+/// embedding suggestions, and after those for the categorizer agent.
+#[derive(Default)]
+pub struct RuleSet {
+    rules: Vec<CompiledRule>,
+}
+
+impl RuleSet {
+    /// Disabled rules, and rules whose regex no longer compiles, are left out.
+    pub fn new(mut rules: Vec<TransactionRuleDto>) -> Self {
+        rules.sort_by_key(|rule| rule.priority);
+        let rules = rules
+            .into_iter()
+            .filter(|rule| rule.enabled)
+            .filter_map(|rule| {
+                let regex = match rule.conditions.description_regex.as_deref() {
+                    Some(pattern) => match compile_description_regex(pattern) {
+                        Ok(regex) => Some(regex),
+                        Err(e) => {
+                            tracing::warn!(rule_id = %rule.id, error = %e, "skipping rule with invalid regex");
+                            return None;
+                        }
+                    },
+                    None => None,
+                };
+                Some(CompiledRule {
+                    id: rule.id,
+                    conditions: rule.conditions,
+                    regex,
+                    actions: rule.actions,
+                })
+            })
+            .collect();
+        Self { rules }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// `None` when no rule matches.
+    pub fn evaluate(&self, subject: &RuleSubject) -> Option<RuleOutcome> {
+        let mut outcome = RuleOutcome::default();
+        for rule in self.rules.iter().filter(|rule| rule.matches(subject)) {
+            outcome.rule_ids.push(rule.id);
+            let actions = &rule.actions;
+            outcome.category_id = outcome.category_id.or(actions.category_id);
+            outcome.visibility = outcome.visibility.or(actions.visibility);
+            if outcome.description.is_none() {
+                outcome.description = actions
+                    .description_rewrite
+                    .as_deref()
+                    .map(|template| rule.rewrite(subject.description, template));
+            }
+            for tag_id in &actions.tag_ids {
+                if !outcome.tag_ids.contains(tag_id) {
+                    outcome.tag_ids.push(*tag_id);
+                }
+            }
+        }
+        (!outcome.rule_ids.is_empty()).then_some(outcome)
+    }
+
+    /// Runs the rules over a regular transaction and applies the outcome to it. Other
+    /// transaction types have no category or description for rules to set, and are left
+    /// alone. The visibility is kept when `keep_visibility` is set.
+    pub fn apply(
+        &self,
+        transaction: &mut TransactionDto,
+        counterparty: Option<&str>,
+        keep_visibility: bool,
+    ) -> Option<AppliedRules> {
+        let TransactionTypeDto::Regular(metadata) = &mut transaction.transaction_type else {
+            return None;
+        };
+        let outcome = self.evaluate(&RuleSubject {
+            description: metadata.description.as_deref().unwrap_or_default(),
+            amount: metadata.entry.quantity,
+            account_id: metadata.entry.account_id,
+            counterparty,
+        })?;
+
+        let mut changed = false;
+        if let Some(category_id) = outcome.category_id {
+            changed |= metadata.category_id != category_id;
+            metadata.category_id = category_id;
+        }
+        if let Some(description) = outcome.description {
+            changed |= metadata.description.as_deref() != Some(description.as_str());
+            metadata.description = Some(description);
+        }
+        if let Some(visibility) = outcome.visibility.filter(|_| !keep_visibility) {
+            changed |= transaction.visibility != visibility;
+            transaction.visibility = visibility;
+        }
+
+        Some(AppliedRules {
+            rule_ids: outcome.rule_ids,
+            tag_ids: outcome.tag_ids,
+            changed,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use time::macros::datetime;
+
+    use crate::dtos::entry_dto::EntryDto;
+    use crate::dtos::transaction_dto::RegularTransactionMetadataDto;
+
+    use super::*;
+
+    const CURRENT: Uuid = Uuid::from_u128(1);
+    const SAVINGS: Uuid = Uuid::from_u128(2);
+    const GROCERIES: i32 = 10;
+    const COFFEE: i32 = 11;
+    const FOOD_TAG: Uuid = Uuid::from_u128(100);
+    const WORK_TAG: Uuid = Uuid::from_u128(101);
+
+    fn rule(
+        id: u128,
+        priority: i32,
+        conditions: RuleConditionsDto,
+        actions: RuleActionsDto,
+    ) -> TransactionRuleDto {
+        TransactionRuleDto {
+            id: Uuid::from_u128(id),
+            name: format!("rule {id}"),
+            priority,
+            enabled: true,
+            conditions,
+            actions,
+            created_at: datetime!(2026-10-01 0:00 UTC),
+            updated_at: datetime!(2026-10-01 0:00 UTC),
+        }
+    }
+
+    fn subject(description: &str, amount: Decimal) -> RuleSubject<'_> {
+        RuleSubject {
+            description,
+            amount,
+            account_id: CURRENT,
+            counterparty: None,
+        }
+    }
+
+    fn contains(needle: &str) -> RuleConditionsDto {
+        RuleConditionsDto {
+            description_contains: Some(needle.to_string()),
+            ..Default::default()
+        }
+    }
+
+    fn categorize(category_id: i32) -> RuleActionsDto {
+        RuleActionsDto {
+            category_id: Some(category_id),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn every_condition_must_hold() {
+        let rules = RuleSet::new(vec![rule(
+            1,
+            0,
+            RuleConditionsDto {
+                description_contains: Some("tesco".to_string()),
+                amount_min: Some(dec!(-100)),
+                amount_max: Some(dec!(0)),
+                account_id: Some(CURRENT),
+                ..Default::default()
+            },
+            categorize(GROCERIES),
+        )]);
+
+        assert!(rules
+            .evaluate(&subject("TESCO STORES 2041", dec!(-23.10)))
+            .is_some());
+        assert!(rules
+            .evaluate(&subject("TESCO STORES 2041", dec!(-150)))
+            .is_none());
+        assert!(rules
+            .evaluate(&subject("TESCO STORES 2041", dec!(5)))
+            .is_none());
+        assert!(rules
+            .evaluate(&subject("Sainsbury's", dec!(-23.10)))
+            .is_none());
+        assert!(rules
+            .evaluate(&RuleSubject {
+                account_id: SAVINGS,
+                ..subject("TESCO STORES 2041", dec!(-23.10))
+            })
+            .is_none());
+    }
+
+    #[test]
+    fn counterparty_condition_needs_a_counterparty() {
+        let rules = RuleSet::new(vec![rule(
+            1,
+            0,
+            RuleConditionsDto {
+                counterparty: Some("acme ltd".to_string()),
+                ..Default::default()
+            },
+            categorize(GROCERIES),
+        )]);
+
+        assert!(rules.evaluate(&subject("Salary", dec!(2500))).is_none());
+        assert!(rules
+            .evaluate(&RuleSubject {
+                counterparty: Some("ACME Ltd"),
+                ..subject("Salary", dec!(2500))
+            })
+            .is_some());
+    }
+
+    #[test]
+    fn first_rule_by_priority_wins_and_tags_accumulate() {
+        let rules = RuleSet::new(vec![
+            rule(
+                1,
+                20,
+                contains("coffee"),
+                RuleActionsDto {
+                    category_id: Some(GROCERIES),
+                    tag_ids: vec![FOOD_TAG],
+                    ..Default::default()
+                },
+            ),
+            rule(
+                2,
+                10,
+                contains("pret"),
+                RuleActionsDto {
+                    category_id: Some(COFFEE),
+                    tag_ids: vec![WORK_TAG, FOOD_TAG],
+                    ..Default::default()
+                },
+            ),
+        ]);
+
+        let outcome = rules
+            .evaluate(&subject("PRET A MANGER coffee", dec!(-3.2)))
+            .unwrap();
+
+        assert_eq!(
+            outcome.rule_ids,
+            vec![Uuid::from_u128(2), Uuid::from_u128(1)]
+        );
+        assert_eq!(outcome.category_id, Some(COFFEE));
+        assert_eq!(outcome.tag_ids, vec![WORK_TAG, FOOD_TAG]);
+    }
+
+    #[test]
+    fn disabled_and_invalid_rules_are_skipped() {
+        let mut disabled = rule(1, 0, contains("tesco"), categorize(GROCERIES));
+        disabled.enabled = false;
+        let invalid = rule(
+            2,
+            0,
+            RuleConditionsDto {
+                description_regex: Some("(unclosed".to_string()),
+                ..Default::default()
+            },
+            categorize(GROCERIES),
+        );
+
+        let rules = RuleSet::new(vec![disabled, invalid]);
+
+        assert!(rules.is_empty());
+    }
+
+    #[test]
+    fn rewrite_expands_regex_captures() {
+        let rules = RuleSet::new(vec![rule(
+            1,
+            0,
+            RuleConditionsDto {
+                description_regex: Some(r"^CARD PAYMENT TO (?<merchant>.+?),".to_string()),
+                ..Default::default()
+            },
+            RuleActionsDto {
+                description_rewrite: Some("$merchant".to_string()),
+                visibility: Some(TransactionVisibilityDto::Hidden),
+                ..Default::default()
+            },
+        )]);
+
+        let outcome = rules
+            .evaluate(&subject("CARD PAYMENT TO Blue Bottle,4.20 GBP", dec!(-4.2)))
+            .unwrap();
+
+        assert_eq!(outcome.description.as_deref(), Some("Blue Bottle"));
+        assert_eq!(outcome.visibility, Some(TransactionVisibilityDto::Hidden));
+    }
+
+    fn regular(description: &str, category_id: i32) -> TransactionDto {
+        TransactionDto {
+            transaction_id: None,
+            date: datetime!(2026-10-01 9:00 UTC),
+            visibility: TransactionVisibilityDto::Ghost,
+            fee_entries: vec![],
+            transaction_type: TransactionTypeDto::Regular(RegularTransactionMetadataDto {
+                description: Some(description.to_string()),
+                entry: EntryDto::new(1, CURRENT, dec!(-12)),
+                category_id,
+            }),
+        }
+    }
+
+    #[test]
+    fn apply_updates_regular_transactions() {
+        let rules = RuleSet::new(vec![rule(
+            1,
+            0,
+            contains("tesco"),
+            RuleActionsDto {
+                category_id: Some(GROCERIES),
+                tag_ids: vec![FOOD_TAG],
+                visibility: Some(TransactionVisibilityDto::Default),
+                ..Default::default()
+            },
+        )]);
+
+        let mut transaction = regular("Tesco", 76);
+        let applied = rules.apply(&mut transaction, None, false).unwrap();
+        assert!(applied.changed);
+        assert_eq!(applied.tag_ids, vec![FOOD_TAG]);
+        assert_eq!(transaction.visibility, TransactionVisibilityDto::Default);
+        let TransactionTypeDto::Regular(metadata) = &transaction.transaction_type else {
+            unreachable!();
+        };
+        assert_eq!(metadata.category_id, GROCERIES);
+
+        let mut held = regular("Tesco", GROCERIES);
+        let applied = rules.apply(&mut held, None, true).unwrap();
+        assert!(!applied.changed);
+        assert_eq!(held.visibility, TransactionVisibilityDto::Ghost);
+    }
+}
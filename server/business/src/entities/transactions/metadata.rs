@@ -13,6 +13,7 @@ pub struct ConnectorLinkMeta {
     pub binding_id: Uuid,
     pub external_id: String,
     pub external_hash: String,
+    pub counterparty: Option<String>,
 }
 
 bitflags! {
@@ -120,6 +120,7 @@ impl TransactionProcessor for AssetTransferOutTransaction {
                 asset_id: entry.asset_id,
                 account_id: entry.account_id,
                 quantity: entry.quantity.abs(),
+                price: dec!(1),
                 fees: self.base.fee_entries_total().abs(),
                 date: self.base.date(),
             },
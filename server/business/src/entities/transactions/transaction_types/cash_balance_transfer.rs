@@ -16,7 +16,10 @@ use crate::{
     dynamic_enums::{transaction_type_categories::TransactionTypeCategories, DynamicEnum},
     entities::{
         entries::entry::Entry,
-        portfolio_overview::investment_transaction::cash_balance_transfer::CashBalanceTransfer,
+        portfolio_overview::investment_transaction::{
+            cash_balance_transfer::CashBalanceTransfer,
+            cash_exchange_transfer::CashExchangeTransfer,
+        },
         transactions::{
             base_transaction::BaseTransaction,
             transaction::{Transaction, TransactionPortfolioAction},
@@ -83,14 +86,20 @@ impl TransactionProcessor for CashBalanceTransferTransaction {
             _ => panic!("Invalid transaction type"),
         };
 
-        ensure!(
-            metadata.outgoing_change.asset_id == metadata.incoming_change.asset_id,
-            "Cash balance transfer entries must use the same asset"
-        );
-        ensure!(
-            metadata.outgoing_change.quantity == -metadata.incoming_change.quantity,
-            "Cash balance transfer entries must have equal magnitude and opposite signs"
-        );
+        // A transfer between accounts in different currencies is an exchange, so only the
+        // signs have to agree.
+        if metadata.outgoing_change.asset_id == metadata.incoming_change.asset_id {
+            ensure!(
+                metadata.outgoing_change.quantity == -metadata.incoming_change.quantity,
+                "Cash balance transfer entries must have equal magnitude and opposite signs"
+            );
+        } else {
+            ensure!(
+                metadata.outgoing_change.quantity < dec!(0)
+                    && metadata.incoming_change.quantity > dec!(0),
+                "Cash balance transfer entries must have opposite signs"
+            );
+        }
         ensure!(
             metadata.outgoing_change.account_id != metadata.incoming_change.account_id,
             "Cash balance transfer entries must use distinct accounts"
@@ -139,6 +148,21 @@ impl TransactionProcessor for CashBalanceTransferTransaction {
         let outgoing_entry = self.base.entry(|x| x.quantity < dec!(0))?;
         let incoming_entry = self.base.entry(|x| x.quantity > dec!(0))?;
 
+        if outgoing_entry.asset_id != incoming_entry.asset_id {
+            return Ok(TransactionPortfolioAction::Regular(Box::new(
+                CashExchangeTransfer {
+                    account_from: outgoing_entry.account_id,
+                    asset_from: outgoing_entry.asset_id,
+                    units_from: -outgoing_entry.quantity,
+                    account_to: incoming_entry.account_id,
+                    asset_to: incoming_entry.asset_id,
+                    units_to: incoming_entry.quantity,
+                    fees: -self.base.fee_entries_total(),
+                    date: self.base.date(),
+                },
+            )));
+        }
+
         Ok(TransactionPortfolioAction::Regular(Box::new(
             CashBalanceTransfer {
                 asset_id: outgoing_entry.asset_id,
@@ -197,21 +221,40 @@ mod tests {
     }
 
     #[test]
-    fn try_from_dto_rejects_mismatched_assets() {
+    fn try_from_dto_accepts_exchange_between_currencies() {
         load_dynamic_enums();
         let dto = transfer_dto(
             EntryDto::new(1, Uuid::new_v4(), dec!(-100)),
-            EntryDto::new(2, Uuid::new_v4(), dec!(100)),
+            EntryDto::new(2, Uuid::new_v4(), dec!(116.40)),
+            vec![],
+        );
+
+        let transaction = CashBalanceTransferTransaction::try_from_dto(dto, Uuid::new_v4())
+            .expect("an exchange between currencies should be accepted");
+
+        let entries = transaction.get_entries();
+        assert_eq!(entries[0].asset_id, 1);
+        assert_eq!(entries[0].quantity, dec!(-100));
+        assert_eq!(entries[1].asset_id, 2);
+        assert_eq!(entries[1].quantity, dec!(116.40));
+    }
+
+    #[test]
+    fn try_from_dto_rejects_same_sign_exchange_legs() {
+        load_dynamic_enums();
+        let dto = transfer_dto(
+            EntryDto::new(1, Uuid::new_v4(), dec!(100)),
+            EntryDto::new(2, Uuid::new_v4(), dec!(116.40)),
             vec![],
         );
 
         let error = CashBalanceTransferTransaction::try_from_dto(dto, Uuid::new_v4())
             .err()
-            .expect("mismatched assets should be rejected");
+            .expect("same-sign exchange legs should be rejected");
 
         assert_eq!(
             error.to_string(),
-            "Cash balance transfer entries must use the same asset"
+            "Cash balance transfer entries must have opposite signs"
         );
     }
 
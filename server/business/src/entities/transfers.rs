@@ -0,0 +1,274 @@
+use std::collections::HashMap;
+
+use rust_decimal::Decimal;
+use rust_decimal_macros::dec;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::dtos::transaction_dto::TransactionVisibilityDto;
+
+/// Furthest apart, in days, the two legs of a transfer may be dated. Payments between
+/// banks usually land the same or the next working day.
+pub const DATE_WINDOW_DAYS: i64 = 3;
+/// How far the incoming leg of an exchange may stray from the outgoing amount converted
+/// at the latest rate, to allow for the provider's spread and the rate moving since.
+const EXCHANGE_TOLERANCE: Decimal = dec!(0.03);
+
+/// A single-entry cash movement that may be one side of a transfer between the user's
+/// accounts. Outgoing legs have a negative amount.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TransferLeg {
+    pub transaction_id: Uuid,
+    pub account_id: Uuid,
+    pub asset_id: i32,
+    pub amount: Decimal,
+    pub date: OffsetDateTime,
+    pub visibility: TransactionVisibilityDto,
+    /// Whether a connector imported the transaction.
+    pub imported: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TransferMatch {
+    pub outgoing: TransferLeg,
+    pub incoming: TransferLeg,
+    /// Neither leg could have been paired with any other leg.
+    pub unambiguous: bool,
+}
+
+impl TransferMatch {
+    /// Whether the money changed currency on the way.
+    pub fn is_exchange(&self) -> bool {
+        self.outgoing.asset_id != self.incoming.asset_id
+    }
+}
+
+/// How far the incoming leg is from what the outgoing leg should have paid in, as a
+/// fraction of the expected amount. `None` when the two cannot be one transfer. Rates are
+/// keyed by (outgoing asset, incoming asset) and give units of the incoming asset per unit
+/// of the outgoing one.
+fn deviation(
+    outgoing: &TransferLeg,
+    incoming: &TransferLeg,
+    rates: &HashMap<(i32, i32), Decimal>,
+) -> Option<Decimal> {
+    if outgoing.amount >= Decimal::ZERO
+        || incoming.amount <= Decimal::ZERO
+        || outgoing.account_id == incoming.account_id
+        || (incoming.date.date() - outgoing.date.date())
+            .whole_days()
+            .abs()
+            > DATE_WINDOW_DAYS
+    {
+        return None;
+    }
+
+    if outgoing.asset_id == incoming.asset_id {
+        return (incoming.amount == -outgoing.amount).then_some(Decimal::ZERO);
+    }
+
+    let rate = rates.get(&(outgoing.asset_id, incoming.asset_id))?;
+    let expected = -outgoing.amount * rate;
+    if expected <= Decimal::ZERO {
+        return None;
+    }
+    let deviation = (incoming.amount - expected).abs() / expected;
+    (deviation <= EXCHANGE_TOLERANCE).then_some(deviation)
+}
+
+/// Pairs outgoing legs with incoming legs in another account, one to one. Same-currency
+/// legs must carry the same amount; legs in different currencies must agree within the
+/// exchange tolerance, so an exchange is only found when its rate is given. Closest amounts
+/// pair first, then closest dates.
+pub fn match_transfers(
+    legs: &[TransferLeg],
+    rates: &HashMap<(i32, i32), Decimal>,
+) -> Vec<TransferMatch> {
+    let mut pairs: Vec<(usize, usize, Decimal, i64)> = Vec::new();
+    for (o, outgoing) in legs.iter().enumerate() {
+        for (i, incoming) in legs.iter().enumerate() {
+            if let Some(deviation) = deviation(outgoing, incoming, rates) {
+                let seconds = (incoming.date - outgoing.date).whole_seconds().abs();
+                pairs.push((o, i, deviation, seconds));
+            }
+        }
+    }
+    pairs.sort_by(|a, b| a.2.cmp(&b.2).then(a.3.cmp(&b.3)));
+
+    let mut options: HashMap<usize, usize> = HashMap::new();
+    for (o, i, _, _) in &pairs {
+        *options.entry(*o).or_default() += 1;
+        *options.entry(*i).or_default() += 1;
+    }
+
+    let mut used: Vec<bool> = vec![false; legs.len()];
+    let mut matches = Vec::new();
+    for (o, i, _, _) in pairs {
+        if used[o] || used[i] {
+            continue;
+        }
+        used[o] = true;
+        used[i] = true;
+        matches.push(TransferMatch {
+            outgoing: legs[o].clone(),
+            incoming: legs[i].clone(),
+            unambiguous: options[&o] == 1 && options[&i] == 1,
+        });
+    }
+    matches
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::datetime;
+
+    use super::*;
+
+    const CURRENT: Uuid = Uuid::from_u128(1);
+    const SAVINGS: Uuid = Uuid::from_u128(2);
+    const EUR_ACCOUNT: Uuid = Uuid::from_u128(3);
+    const GBP: i32 = 1;
+    const EUR: i32 = 2;
+
+    fn leg(
+        id: u128,
+        account_id: Uuid,
+        asset_id: i32,
+        amount: Decimal,
+        date: OffsetDateTime,
+    ) -> TransferLeg {
+        TransferLeg {
+            transaction_id: Uuid::from_u128(id),
+            account_id,
+            asset_id,
+            amount,
+            date,
+            visibility: TransactionVisibilityDto::Default,
+            imported: true,
+        }
+    }
+
+    fn ids(matches: &[TransferMatch]) -> Vec<(u128, u128, bool)> {
+        matches
+            .iter()
+            .map(|m| {
+                (
+                    m.outgoing.transaction_id.as_u128(),
+                    m.incoming.transaction_id.as_u128(),
+                    m.unambiguous,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn pairs_equal_amounts_across_accounts() {
+        let legs = [
+            leg(
+                10,
+                CURRENT,
+                GBP,
+                dec!(-250),
+                datetime!(2026-10-01 09:00 UTC),
+            ),
+            leg(20, SAVINGS, GBP, dec!(250), datetime!(2026-10-02 07:00 UTC)),
+        ];
+
+        let matches = match_transfers(&legs, &HashMap::new());
+
+        assert_eq!(ids(&matches), vec![(10, 20, true)]);
+        assert!(!matches[0].is_exchange());
+    }
+
+    #[test]
+    fn ignores_different_amounts_same_account_and_distant_dates() {
+        let legs = [
+            leg(
+                10,
+                CURRENT,
+                GBP,
+                dec!(-250),
+                datetime!(2026-10-01 09:00 UTC),
+            ),
+            leg(
+                20,
+                SAVINGS,
+                GBP,
+                dec!(249.99),
+                datetime!(2026-10-01 09:00 UTC),
+            ),
+            leg(30, CURRENT, GBP, dec!(250), datetime!(2026-10-01 09:00 UTC)),
+            leg(40, SAVINGS, GBP, dec!(250), datetime!(2026-10-05 09:00 UTC)),
+        ];
+
+        assert!(match_transfers(&legs, &HashMap::new()).is_empty());
+    }
+
+    #[test]
+    fn pairs_exchange_within_tolerance_of_rate() {
+        let legs = [
+            leg(
+                10,
+                CURRENT,
+                GBP,
+                dec!(-100),
+                datetime!(2026-10-01 09:00 UTC),
+            ),
+            leg(
+                20,
+                EUR_ACCOUNT,
+                EUR,
+                dec!(114.20),
+                datetime!(2026-10-01 10:00 UTC),
+            ),
+            leg(
+                30,
+                EUR_ACCOUNT,
+                EUR,
+                dec!(100),
+                datetime!(2026-10-01 10:00 UTC),
+            ),
+        ];
+        let rates = HashMap::from([((GBP, EUR), dec!(1.16))]);
+
+        let matches = match_transfers(&legs, &rates);
+
+        assert_eq!(ids(&matches), vec![(10, 20, true)]);
+        assert!(matches[0].is_exchange());
+    }
+
+    #[test]
+    fn exchange_without_rate_is_not_paired() {
+        let legs = [
+            leg(
+                10,
+                CURRENT,
+                GBP,
+                dec!(-100),
+                datetime!(2026-10-01 09:00 UTC),
+            ),
+            leg(
+                20,
+                EUR_ACCOUNT,
+                EUR,
+                dec!(116),
+                datetime!(2026-10-01 10:00 UTC),
+            ),
+        ];
+
+        assert!(match_transfers(&legs, &HashMap::new()).is_empty());
+    }
+
+    #[test]
+    fn closest_date_wins_and_rivals_make_it_ambiguous() {
+        let legs = [
+            leg(10, CURRENT, GBP, dec!(-50), datetime!(2026-10-01 09:00 UTC)),
+            leg(11, CURRENT, GBP, dec!(-50), datetime!(2026-10-03 09:00 UTC)),
+            leg(20, SAVINGS, GBP, dec!(50), datetime!(2026-10-03 12:00 UTC)),
+        ];
+
+        let matches = match_transfers(&legs, &HashMap::new());
+
+        assert_eq!(ids(&matches), vec![(11, 20, false)]);
+    }
+}
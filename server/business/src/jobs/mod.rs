@@ -9,6 +9,17 @@ pub enum EmbeddingJob {
     Category { category_id: i32, text: String },
 }
 
+/// Re-embeds, one batch per run, every vector the active embedding model did not produce:
+/// categories, then assets, groups and transactions. Each run enqueues the next batch, so
+/// a restarted worker picks the migration up where it stopped.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ReembedJob {
+    Categories { after: Option<i32> },
+    Assets { after: Option<i32> },
+    Groups { after: Option<Uuid> },
+    Transactions { after: Option<Uuid> },
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileProcessingJob {
     pub file_id: Uuid,
@@ -37,3 +48,24 @@ pub struct SyncConnectorBindingJob {
     pub binding_id: Uuid,
     pub user_id: Uuid,
 }
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApplyTransactionRulesJob {
+    pub user_id: Uuid,
+}
+
+/// Suggests categories for the user's transactions still in the import category, only
+/// those imported by `binding_id` when set. Enqueued after each connector sync.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategorizeTransactionsJob {
+    pub user_id: Uuid,
+    pub binding_id: Option<Uuid>,
+}
+
+/// Checks newly added transactions for spending the user should hear about. Enqueued
+/// after manual entry and after each connector import.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckSpendingAlertsJob {
+    pub user_id: Uuid,
+    pub transaction_ids: Vec<Uuid>,
+}
@@ -5,7 +5,10 @@
 use ai::data_provider::AiDataProvider;
 use ai::models::account::AccountResult;
 use ai::models::aggregate::{AggregateParams, AggregateResult};
+use ai::models::categorization::CategorizedExample;
 use ai::models::reference::{AssetResult, CategoryResult};
+use ai::models::search::QueryEmbedding;
+use ai::models::subscriptions::SubscriptionRow;
 use ai::models::transactions::{
     QueryTransactionsParams, QueryTransactionsResult, TransactionDetailResult,
 };
@@ -32,10 +35,10 @@ impl AiDataProvider for UserDataProvider {
     async fn query_transactions(
         &self,
         params: QueryTransactionsParams,
-        query_vector: Option<Vec<f64>>,
+        query_embedding: Option<QueryEmbedding>,
     ) -> Result<QueryTransactionsResult> {
         self.service
-            .query_transactions(self.user_id, params, query_vector)
+            .query_transactions(self.user_id, params, query_embedding)
             .await
     }
 
@@ -69,20 +72,30 @@ impl AiDataProvider for UserDataProvider {
 
     async fn search_categories(
         &self,
-        query_vector: Option<Vec<f64>>,
+        query_embedding: Option<QueryEmbedding>,
     ) -> Result<Vec<CategoryResult>> {
         self.service
-            .search_categories(self.user_id, query_vector)
+            .search_categories(self.user_id, query_embedding)
+            .await
+    }
+
+    async fn find_categorized_examples(
+        &self,
+        query_embedding: QueryEmbedding,
+        limit: i64,
+    ) -> Result<Vec<CategorizedExample>> {
+        self.service
+            .find_categorized_examples(self.user_id, query_embedding, limit)
             .await
     }
 
     async fn search_assets(
         &self,
         query: Option<&str>,
-        query_vector: Option<Vec<f64>>,
+        query_embedding: Option<QueryEmbedding>,
     ) -> Result<Vec<AssetResult>> {
         self.service
-            .search_assets(self.user_id, query, query_vector)
+            .search_assets(self.user_id, query, query_embedding)
             .await
     }
 
@@ -154,4 +167,8 @@ impl AiDataProvider for UserDataProvider {
             )
             .await
     }
+
+    async fn get_subscriptions(&self) -> Result<Vec<SubscriptionRow>> {
+        self.service.get_subscriptions(self.user_id).await
+    }
 }
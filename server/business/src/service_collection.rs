@@ -3,6 +3,11 @@ use dal::database_connection::MyraDbConnection;
 use dal::database_context::MyraDb;
 use dal::file_provider::FileProvider;
 use dal::job_queue::JobQueueHandle;
+use dal::market_data_client::{AssetClass, MarketDataClient};
+use dal::market_data_provider::{
+    EcbMarketDataProvider, MarketDataProvider, PriceFileMarketDataProvider,
+    RoutingMarketDataProvider, StubMarketDataProvider,
+};
 use dal::noop_file_provider::NoOpFileProvider;
 use dal::pg_notify_connection::PgNotifyConnection;
 #[mockall_double::double]
@@ -15,6 +20,7 @@ use std::sync::Arc;
 
 pub mod accounts_service;
 pub mod ai_action_service;
+pub mod ai_categorizer_service;
 pub mod ai_chat_service;
 pub mod ai_conversation_service;
 pub mod ai_data_service;
@@ -24,19 +30,35 @@ pub mod ai_usage_service;
 pub mod asset_rates_service;
 pub mod asset_service;
 pub mod auth_service;
+pub mod budget_service;
 pub mod category_service;
 pub mod category_type_service;
 pub mod category_validation_service;
+pub mod connector_balance_service;
+pub mod connector_duplicate_service;
+pub mod connector_review_service;
 pub mod connector_service;
 pub mod connector_sync_service;
+pub mod corporate_action_service;
 pub mod entries_service;
 pub mod file_service;
+pub mod insight_digest_service;
 pub mod portfolio_overview_service;
 pub mod portfolio_service;
+pub mod reconciliation_service;
+pub mod recurring_transaction_service;
+pub mod spending_alert_service;
+pub mod statement_import_service;
+pub mod subscription_service;
+pub mod tag_service;
+pub mod ticker_alias_service;
 pub mod transaction_group_service;
 pub mod transaction_management_service;
 pub mod transaction_metadata_service;
+pub mod transaction_rule_service;
 pub mod transaction_service;
+pub mod transfer_matching_service;
+pub mod user_data_archive_service;
 pub mod user_service;
 
 #[derive(Clone)]
@@ -47,6 +69,7 @@ pub struct Services {
     pub job_queue: JobQueueHandle,
     pub pg_notify: PgNotifyConnection,
     pub secret_provider: Arc<dyn SecretProvider>,
+    pub market_data: Arc<dyn MarketDataProvider>,
 }
 
 #[derive(Clone)]
@@ -57,6 +80,7 @@ pub struct ServiceProviders {
     pub redis: RedisConnection,
     pub pg_notify: PgNotifyConnection,
     pub secret_provider: Arc<dyn SecretProvider>,
+    pub market_data: Arc<dyn MarketDataProvider>,
     pub services: Services,
 }
 
@@ -107,6 +131,8 @@ impl Services {
             }
         };
 
+        let market_data = market_data_provider_from_env();
+
         let redis = RedisConnection::new().await;
         let job_queue = JobQueueHandle::new(connection.pool.clone());
         let pg_notify = PgNotifyConnection::new(connection.pool.clone());
@@ -118,6 +144,7 @@ impl Services {
             job_queue,
             pg_notify,
             secret_provider,
+            market_data,
         })
     }
 
@@ -129,6 +156,7 @@ impl Services {
             redis: self.redis.clone(),
             pg_notify: self.pg_notify.clone(),
             secret_provider: self.secret_provider.clone(),
+            market_data: self.market_data.clone(),
             services: self.clone(),
         }
     }
@@ -137,3 +165,60 @@ impl Services {
         self.job_queue.clone()
     }
 }
+
+/// `MARKET_DATA_PROVIDER` picks the default source (`http` unless set);
+/// `MARKET_DATA_PROVIDER_CURRENCY`, `_CRYPTO` and `_OTHER` override it per asset class.
+fn market_data_provider_from_env() -> Arc<dyn MarketDataProvider> {
+    let default = market_data_source("MARKET_DATA_PROVIDER")
+        .unwrap_or_else(|| Arc::new(MarketDataClient::new()));
+
+    let mut router = RoutingMarketDataProvider::new(default);
+    for (class, var) in [
+        (AssetClass::Currency, "MARKET_DATA_PROVIDER_CURRENCY"),
+        (AssetClass::Crypto, "MARKET_DATA_PROVIDER_CRYPTO"),
+        (AssetClass::Other, "MARKET_DATA_PROVIDER_OTHER"),
+    ] {
+        if let Some(provider) = market_data_source(var) {
+            router = router.with_class(class, provider);
+        }
+    }
+    Arc::new(router)
+}
+
+fn market_data_source(var: &str) -> Option<Arc<dyn MarketDataProvider>> {
+    let value = std::env::var(var).ok().filter(|v| !v.is_empty())?;
+    match value.as_str() {
+        "http" => Some(Arc::new(MarketDataClient::new())),
+        "ecb" => match EcbMarketDataProvider::new() {
+            Ok(provider) => Some(Arc::new(provider)),
+            Err(e) => {
+                tracing::warn!(
+                    error = ?e,
+                    var,
+                    "ECB market data provider not configured, ignoring"
+                );
+                None
+            }
+        },
+        "price_files" => match PriceFileMarketDataProvider::new() {
+            Ok(provider) => Some(Arc::new(provider)),
+            Err(e) => {
+                tracing::warn!(
+                    error = ?e,
+                    var,
+                    "Price file market data provider not configured, ignoring"
+                );
+                None
+            }
+        },
+        "stub" => Some(Arc::new(StubMarketDataProvider::new())),
+        other => {
+            tracing::warn!(
+                provider = other,
+                var,
+                "Unknown market data provider, ignoring"
+            );
+            None
+        }
+    }
+}
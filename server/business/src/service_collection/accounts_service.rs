@@ -21,6 +21,7 @@ use crate::dtos::accounts::{
     account_identifier_dto::{AccountIdentifierDto, AccountIdentifierKind},
     account_liquidity_type_dto::AccountLiquidityTypeDto,
     account_type_dto::AccountTypeDto,
+    cost_basis_method_dto::CostBasisMethodDto,
     full_account_dto::FullAccountDto,
 };
 
@@ -142,6 +143,22 @@ impl AccountsService {
         Ok(ret)
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id))]
+    pub async fn get_cost_basis_methods(
+        &self,
+        user_id: Uuid,
+    ) -> anyhow::Result<HashMap<Uuid, CostBasisMethodDto>> {
+        let query = account_queries::get_accounts(GetAccountsParams::all_by_user_id(user_id));
+        let models = self.db.fetch_all::<Account>(query).await?;
+        Ok(models
+            .into_iter()
+            .map(|model| {
+                let dto: AccountDto = model.into();
+                (dto.id, dto.cost_basis_method)
+            })
+            .collect())
+    }
+
     #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id))]
     pub async fn get_user_accounts_with_metadata(
         &self,
@@ -176,6 +193,9 @@ impl AccountsService {
             account_type: amendment.account_type,
             liquidity_type: amendment.account_liquidity_type,
             ownership_share: amendment.ownership_share,
+            cost_basis_method: amendment
+                .cost_basis_method
+                .map(|method| method.as_str().to_string()),
         };
 
         self.db.start_transaction().await?;
@@ -215,6 +235,11 @@ impl AccountsService {
             account_type: amendment.account_type,
             liquidity_type: amendment.account_liquidity_type,
             ownership_share: amendment.ownership_share,
+            cost_basis_method: amendment
+                .cost_basis_method
+                .unwrap_or_default()
+                .as_str()
+                .to_string(),
         };
 
         self.db.start_transaction().await?;
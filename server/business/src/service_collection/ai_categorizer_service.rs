@@ -0,0 +1,205 @@
+//! Category suggestions for transactions still in the import category — connector
+//! imports above all, ghost ones included. The categorizer agent runs after every
+//! connector sync and on demand; its suggestions are stored until the transaction
+//! leaves the import category.
+
+use std::sync::Arc;
+
+use ai::models::categorization::{CategorizerOutput, UncategorizedTransaction};
+use ai::models::error::AiError;
+#[mockall_double::double]
+use dal::database_context::MyraDb;
+use dal::job_queue::JobQueueHandle;
+use dal::models::ai_models::{
+    AddCategorySuggestionModel, AiCategorySuggestionModel, AiTransactionSearchModel,
+};
+use dal::queries::ai_queries;
+use dal::query_params::ai_search_params::UncategorizedTransactionsParams;
+use uuid::Uuid;
+
+use crate::dtos::ai_chat_error_dto::AiChatError;
+use crate::dtos::ai_error_dto::AiErrorDto;
+use crate::dtos::category_suggestion_dto::CategorySuggestionDto;
+use crate::entities::connectors::provider_transaction_import::IMPORT_CATEGORY_ID;
+use crate::jobs::CategorizeTransactionsJob;
+use crate::providers::user_data_provider::UserDataProvider;
+use crate::providers::user_rate_limiter::UserRateLimiter;
+use crate::rate_limiting::rate_limiter::RateLimiter;
+
+use super::ai_data_service::AiDataService;
+
+/// Transactions sent to the model in one completion.
+pub const CATEGORIZE_BATCH_SIZE: usize = 25;
+
+/// Batches one background run goes through at most; the next sync picks up the rest.
+const MAX_BATCHES_PER_RUN: usize = 8;
+
+pub struct AiCategorizerService {
+    services: super::Services,
+    db: MyraDb,
+    queue: JobQueueHandle,
+    rate_limiter: RateLimiter,
+}
+
+impl AiCategorizerService {
+    pub fn new(providers: &super::ServiceProviders) -> Self {
+        Self {
+            services: providers.services.clone(),
+            db: providers.db.clone(),
+            queue: providers.job_queue.clone(),
+            rate_limiter: RateLimiter::new(providers.redis.clone(), providers.db.clone()),
+        }
+    }
+
+    pub async fn enqueue_categorize(
+        &self,
+        user_id: Uuid,
+        binding_id: Option<Uuid>,
+    ) -> anyhow::Result<()> {
+        self.queue
+            .push(CategorizeTransactionsJob {
+                user_id,
+                binding_id,
+            })
+            .await
+    }
+
+    /// Stored suggestions for the user's transactions still in the import category,
+    /// most confident first.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id))]
+    pub async fn get_suggestions(
+        &self,
+        user_id: Uuid,
+        transaction_ids: Option<Vec<Uuid>>,
+    ) -> anyhow::Result<Vec<CategorySuggestionDto>> {
+        let rows = self
+            .db
+            .fetch_all::<AiCategorySuggestionModel>(ai_queries::get_category_suggestions(
+                user_id,
+                IMPORT_CATEGORY_ID,
+                transaction_ids,
+            ))
+            .await?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    /// Suggests categories right away: for `transaction_ids`, replacing earlier
+    /// suggestions, or else for the next batch of transactions without one.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id))]
+    pub async fn suggest(
+        &self,
+        user_id: Uuid,
+        transaction_ids: Option<Vec<Uuid>>,
+    ) -> Result<Vec<CategorySuggestionDto>, AiChatError> {
+        let params = UncategorizedTransactionsParams {
+            user_id,
+            category_id: IMPORT_CATEGORY_ID,
+            binding_id: None,
+            skip_suggested: transaction_ids.is_none(),
+            transaction_ids,
+            limit: CATEGORIZE_BATCH_SIZE as u64,
+        };
+        let (_, stored) =
+            self.run_batch(&params)
+                .await
+                .map_err(|e| match e.downcast::<AiError>() {
+                    Ok(ai_error) => AiChatError::Ai(AiErrorDto::from(ai_error)),
+                    Err(e) => AiChatError::Internal(e),
+                })?;
+        if stored.is_empty() {
+            return Ok(Vec::new());
+        }
+        Ok(self.get_suggestions(user_id, Some(stored)).await?)
+    }
+
+    /// Background run after a connector sync. Returns how many transactions got a
+    /// suggestion. AI failures stay `AiError`s so the worker can classify them.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id))]
+    pub async fn categorize_pending(
+        &self,
+        user_id: Uuid,
+        binding_id: Option<Uuid>,
+    ) -> anyhow::Result<usize> {
+        let params = UncategorizedTransactionsParams {
+            user_id,
+            category_id: IMPORT_CATEGORY_ID,
+            binding_id,
+            transaction_ids: None,
+            skip_suggested: true,
+            limit: CATEGORIZE_BATCH_SIZE as u64,
+        };
+        let mut suggested = 0;
+        for _ in 0..MAX_BATCHES_PER_RUN {
+            let (fetched, stored) = self.run_batch(&params).await?;
+            suggested += stored.len();
+            // Transactions the model passed over would come back in every batch.
+            if fetched < CATEGORIZE_BATCH_SIZE || stored.is_empty() {
+                break;
+            }
+        }
+        Ok(suggested)
+    }
+
+    /// Categorizes one batch and stores the suggestions. Returns how many transactions
+    /// were sent and the ids of those that got a suggestion.
+    async fn run_batch(
+        &self,
+        params: &UncategorizedTransactionsParams,
+    ) -> anyhow::Result<(usize, Vec<Uuid>)> {
+        let transactions: Vec<UncategorizedTransaction> = self
+            .db
+            .fetch_all::<AiTransactionSearchModel>(ai_queries::get_uncategorized_transactions(
+                params,
+            ))
+            .await?
+            .into_iter()
+            .map(|row| UncategorizedTransaction {
+                transaction_id: row.transaction_id,
+                description: row.description,
+                date_transacted: row.date_transacted,
+                amount: row.quantity,
+                asset_name: row.asset_name,
+                account_name: row.account_name,
+            })
+            .collect();
+        let fetched = transactions.len();
+        if transactions.is_empty() {
+            return Ok((0, Vec::new()));
+        }
+
+        let config = ai::config::AiConfig::try_from_env()?;
+        let providers = self.services.create_providers();
+        let data = Arc::new(UserDataProvider::new(
+            AiDataService::new(&providers),
+            params.user_id,
+        ));
+        let rate_limit = Arc::new(UserRateLimiter::new(
+            self.rate_limiter.clone(),
+            params.user_id,
+        ));
+
+        let CategorizerOutput { suggestions } =
+            ai::agents::categorizer::categorize(&config, data, rate_limit, transactions).await?;
+        if suggestions.is_empty() {
+            return Ok((fetched, Vec::new()));
+        }
+
+        let stored: Vec<Uuid> = suggestions.iter().map(|s| s.transaction_id).collect();
+        self.db
+            .execute(ai_queries::upsert_category_suggestions(
+                suggestions
+                    .into_iter()
+                    .map(|s| AddCategorySuggestionModel {
+                        transaction_id: s.transaction_id,
+                        user_id: params.user_id,
+                        category_id: s.category_id,
+                        confidence: s.confidence,
+                        reason: s.reason,
+                        model: s.model,
+                    })
+                    .collect(),
+            ))
+            .await?;
+        Ok((fetched, stored))
+    }
+}
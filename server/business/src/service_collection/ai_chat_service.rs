@@ -48,13 +48,12 @@ impl AiChatService {
         );
 
         let config = ai::config::AiConfig::try_from_env()?;
-        let agent =
-            ai::agents::chat::build_chat_agent_for_user(config, data, actions.clone()).await;
 
         let rate_limit = Arc::new(UserRateLimiter::new(self.rate_limiter.clone(), user_id));
         let conv = ai::conversation::Conversation::new(conv_agent.clone(), rate_limit);
 
-        let stream_result = conv.stream(agent, actions, turn.into()).await;
+        let stream_result =
+            ai::agents::chat::stream_chat(config, data, actions, &conv, turn.into()).await;
         let rig_stream = match stream_result {
             Ok(s) => s.map(ChatStreamEventDto::from),
             Err(e) => return Err(AiChatError::Ai(AiErrorDto::from(e))),
@@ -7,8 +7,11 @@ use std::collections::{HashMap, HashSet};
 
 use ai::models::account::{AccountIdentifierResult, AccountResult};
 use ai::models::aggregate::{AggregateGroupResult, AggregateResult};
+use ai::models::categorization::CategorizedExample;
+use ai::models::insights::{NetWorthChange, PortfolioMover, UnusualTransaction, UpcomingCharge};
 use ai::models::reference::{AssetResult, CategoryResult};
-use ai::models::search::TransactionSearchResult;
+use ai::models::search::{QueryEmbedding, TransactionSearchResult};
+use ai::models::subscriptions::SubscriptionRow;
 use ai::models::transactions::{
     QueryTransactionsParams, QueryTransactionsResult, TransactionDetailEntry,
     TransactionDetailResult, TransactionRow,
@@ -21,13 +24,16 @@ use ai::models::wealth::{
 use anyhow::Result;
 #[mockall_double::double]
 use dal::database_context::MyraDb;
-use dal::models::ai_models::{AiAssetModel, AiCategoryModel, AiTransactionSearchModel};
+use dal::models::ai_models::{
+    AiAssetModel, AiCategoryModel, AiTransactionCategoryModel, AiTransactionSearchModel,
+    AiUnusualTransactionModel,
+};
 use dal::queries::{account_identifier_queries, ai_queries};
 use dal::query_params::ai_search_params;
 use pgvector::Vector;
 use rust_decimal::Decimal;
 use rust_decimal_macros::dec;
-use time::OffsetDateTime;
+use time::{Duration, OffsetDateTime};
 use uuid::Uuid;
 
 use crate::dtos::asset_rate_dto::AssetRateDto;
@@ -43,6 +49,8 @@ use crate::dtos::portfolio::overview::asset_overview_dto::PortfolioAssetOverview
 use crate::dtos::portfolio::overview::cash_overview_dto::PortfolioCashOverviewDto;
 use crate::dtos::portfolio::overview::PortfolioOverviewType;
 use crate::dtos::transaction_dto::{TransactionDto, TransactionTypeDto};
+use crate::entities::connectors::provider_transaction_import::IMPORT_CATEGORY_ID;
+use crate::entities::insights::change_pct;
 
 use super::accounts_service::AccountsService;
 use super::asset_rates_service::AssetRatesService;
@@ -50,9 +58,18 @@ use super::asset_service::AssetsService;
 use super::category_service::CategoryService;
 use super::portfolio_overview_service::PortfolioOverviewService;
 use super::portfolio_service::PortfolioService;
+use super::recurring_transaction_service::RecurringTransactionService;
+use super::subscription_service::SubscriptionService;
 use super::transaction_management_service::TransactionManagementService;
 use super::user_service::UsersService;
 
+/// Window the usual spend per category is taken from when looking for unusual
+/// transactions.
+const UNUSUAL_HISTORY: Duration = Duration::days(180);
+
+/// Past transactions a category needs before any of its spending counts as unusual.
+const UNUSUAL_MIN_SAMPLES: i64 = 5;
+
 pub struct AiDataService {
     db: MyraDb,
     portfolio_overview_service: PortfolioOverviewService,
@@ -61,6 +78,8 @@ pub struct AiDataService {
     accounts_service: AccountsService,
     assets_service: AssetsService,
     category_service: CategoryService,
+    recurring_transaction_service: RecurringTransactionService,
+    subscription_service: SubscriptionService,
     users_service: UsersService,
     transaction_service: TransactionManagementService,
 }
@@ -75,6 +94,8 @@ impl AiDataService {
             accounts_service: AccountsService::new(providers),
             assets_service: AssetsService::new(providers),
             category_service: CategoryService::new(providers),
+            recurring_transaction_service: RecurringTransactionService::new(providers),
+            subscription_service: SubscriptionService::new(providers),
             users_service: UsersService::new(providers),
             transaction_service: TransactionManagementService::new(providers),
         }
@@ -122,16 +143,15 @@ impl AiDataService {
     pub async fn search_transactions_by_vector(
         &self,
         user_id: Uuid,
-        query_vector: Vec<f64>,
+        query_embedding: QueryEmbedding,
         date_from: Option<&str>,
         date_to: Option<&str>,
         account_ids: Option<Vec<Uuid>>,
         limit: i64,
     ) -> Result<Vec<TransactionSearchResult>> {
-        let vector = Vector::from(query_vector.iter().map(|&x| x as f32).collect::<Vec<f32>>());
         let q = ai_queries::search_transactions_by_embedding(
             user_id,
-            vector,
+            &to_embedding_search(query_embedding),
             date_from,
             date_to,
             account_ids,
@@ -141,6 +161,58 @@ impl AiDataService {
         Ok(rows.into_iter().map(to_search_result).collect())
     }
 
+    /// The user's categorised transactions nearest to `query_embedding`. Imports still
+    /// in the import category are no example of how the user categorises.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, limit))]
+    pub async fn find_categorized_examples(
+        &self,
+        user_id: Uuid,
+        query_embedding: QueryEmbedding,
+        limit: i64,
+    ) -> Result<Vec<CategorizedExample>> {
+        // Over-fetch: some neighbours are uncategorised and get dropped below.
+        let neighbours = self
+            .db
+            .fetch_all::<AiTransactionSearchModel>(ai_queries::search_transactions_by_embedding(
+                user_id,
+                &to_embedding_search(query_embedding),
+                None,
+                None,
+                None,
+                limit * 3,
+            ))
+            .await?;
+        if neighbours.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut categories: HashMap<Uuid, AiTransactionCategoryModel> = self
+            .db
+            .fetch_all::<AiTransactionCategoryModel>(ai_queries::get_transaction_categories(
+                user_id,
+                neighbours.iter().map(|n| n.transaction_id).collect(),
+                IMPORT_CATEGORY_ID,
+            ))
+            .await?
+            .into_iter()
+            .map(|row| (row.transaction_id, row))
+            .collect();
+
+        Ok(neighbours
+            .into_iter()
+            .filter_map(|n| {
+                let row = categories.remove(&n.transaction_id)?;
+                Some(CategorizedExample {
+                    description: n.description,
+                    category_id: row.category_id,
+                    category: row.category,
+                    similarity: n.distance.map_or(0.0, |distance| (1.0 - distance) as f32),
+                })
+            })
+            .take(limit as usize)
+            .collect())
+    }
+
     #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id))]
     pub async fn aggregate_transactions(
         &self,
@@ -236,10 +308,9 @@ impl AiDataService {
     pub async fn search_categories(
         &self,
         user_id: Uuid,
-        query_vector: Option<Vec<f64>>,
+        query_embedding: Option<QueryEmbedding>,
     ) -> Result<Vec<CategoryResult>> {
-        let embedding =
-            query_vector.map(|qv| Vector::from(qv.iter().map(|&x| x as f32).collect::<Vec<f32>>()));
+        let embedding = query_embedding.map(to_embedding_search);
         let params = ai_search_params::SearchCategoriesParams {
             user_id,
             limit: embedding.as_ref().map(|_| 20_i64),
@@ -257,10 +328,9 @@ impl AiDataService {
         &self,
         user_id: Uuid,
         query: Option<&str>,
-        query_vector: Option<Vec<f64>>,
+        query_embedding: Option<QueryEmbedding>,
     ) -> Result<Vec<AssetResult>> {
-        let embedding =
-            query_vector.map(|qv| Vector::from(qv.iter().map(|&x| x as f32).collect::<Vec<f32>>()));
+        let embedding = query_embedding.map(to_embedding_search);
         let params = ai_search_params::SearchAssetsParams {
             user_id,
             query: query.map(|s| s.to_string()),
@@ -754,7 +824,7 @@ impl AiDataService {
         &self,
         user_id: Uuid,
         params: QueryTransactionsParams,
-        query_vector: Option<Vec<f64>>,
+        query_embedding: Option<QueryEmbedding>,
     ) -> Result<QueryTransactionsResult> {
         let type_ids = params.transaction_types.as_ref().map(|types| {
             types
@@ -787,7 +857,9 @@ impl AiDataService {
                 )
                 .await
                 .unwrap_or_default();
-            let vector = match query_vector {
+            // Only vectors of the active model are compared; descriptions not re-embedded
+            // yet after a model change are still found by the text search.
+            let vector = match query_embedding {
                 Some(qv) => self
                     .search_transactions_by_vector(
                         user_id,
@@ -985,6 +1057,263 @@ impl AiDataService {
         })
     }
 
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id))]
+    pub async fn get_subscriptions(&self, user_id: Uuid) -> Result<Vec<SubscriptionRow>> {
+        let today = OffsetDateTime::now_utc().date();
+        let detected = self
+            .subscription_service
+            .detect_subscriptions(user_id, today)
+            .await?;
+
+        let tickers = self
+            .asset_ticker_map(detected.iter().map(|x| x.asset_id).collect())
+            .await?;
+        let account_names = self
+            .account_name_map(detected.iter().map(|x| x.account_id).collect())
+            .await?;
+
+        Ok(detected
+            .into_iter()
+            .map(|x| SubscriptionRow {
+                account: account_names
+                    .get(&x.account_id)
+                    .cloned()
+                    .unwrap_or_default(),
+                unit: tickers.get(&x.asset_id).cloned().unwrap_or_default(),
+                description: x.description,
+                account_id: x.account_id,
+                period: x.period.as_str().to_string(),
+                amount: x.amount,
+                last_amount: x.last_amount,
+                first_charge_date: x.first_charge_date.to_string(),
+                last_charge_date: x.last_charge_date.to_string(),
+                next_charge_date: x.next_charge_date.to_string(),
+                charge_count: x.charge_count,
+                confidence: x.confidence,
+            })
+            .collect())
+    }
+
+    /// Outflows in `from..to` at least three standard deviations and twice the mean
+    /// above the user's usual spend in the same category. Most unusual first.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id))]
+    pub async fn get_unusual_transactions(
+        &self,
+        user_id: Uuid,
+        from: OffsetDateTime,
+        to: OffsetDateTime,
+        reference_asset_id: i32,
+        limit: i64,
+    ) -> Result<Vec<UnusualTransaction>> {
+        let params = ai_search_params::UnusualTransactionsParams {
+            user_id,
+            currency_asset_id: reference_asset_id,
+            date_from: from,
+            date_to: to,
+            history_from: from - UNUSUAL_HISTORY,
+            min_samples: UNUSUAL_MIN_SAMPLES,
+            deviations: dec!(3),
+            min_ratio: dec!(2),
+            limit,
+        };
+        let rows = self
+            .db
+            .fetch_all::<AiUnusualTransactionModel>(ai_queries::get_unusual_transactions(&params))
+            .await?;
+
+        Ok(rows
+            .into_iter()
+            .map(|r| UnusualTransaction {
+                transaction_id: r.transaction_id,
+                date: format_rfc3339(r.date_transacted),
+                description: r.description,
+                category: r.category,
+                amount: r.amount,
+                typical_amount: r.typical_amount.round_dp(2),
+            })
+            .collect())
+    }
+
+    /// Net worth at the start and end of `from..to`, `None` without any history.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id))]
+    pub async fn get_net_worth_change(
+        &self,
+        user_id: Uuid,
+        from: OffsetDateTime,
+        to: OffsetDateTime,
+        reference_asset_id: i32,
+    ) -> Result<Option<NetWorthChange>> {
+        let history = self
+            .portfolio_service
+            .get_full_portfolio_history(
+                user_id,
+                AssetIdDto(reference_asset_id),
+                RangeDto::Custom(Some(from), Some(to), None),
+                None,
+            )
+            .await?;
+
+        let (Some(first), Some(last)) = (history.first(), history.last()) else {
+            return Ok(None);
+        };
+        Ok(Some(NetWorthChange {
+            start_value: first.rate,
+            end_value: last.rate,
+            change: last.rate - first.rate,
+            change_pct: change_pct(first.rate, last.rate),
+        }))
+    }
+
+    /// Price moves over `from..to` of every non-currency asset the user holds now,
+    /// with the gain or loss on the units held.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id))]
+    pub async fn get_portfolio_movers(
+        &self,
+        user_id: Uuid,
+        from: OffsetDateTime,
+        to: OffsetDateTime,
+        reference_asset_id: i32,
+    ) -> Result<Vec<PortfolioMover>> {
+        let mut units: HashMap<i32, Decimal> = HashMap::new();
+        for h in self
+            .portfolio_overview_service
+            .get_holdings(user_id, true)
+            .await?
+        {
+            *units.entry(h.asset_id).or_default() += h.units;
+        }
+        units.retain(|id, u| !u.is_zero() && *id != reference_asset_id);
+
+        let asset_map = self.asset_map(units.keys().copied().collect()).await?;
+        let mut movers = Vec::new();
+        for (asset_id, held) in units {
+            let Some(asset) = asset_map.get(&asset_id) else {
+                continue;
+            };
+            if asset.asset_type.id == 1 {
+                continue;
+            }
+
+            let rates = self
+                .asset_rates_service
+                .get_market_pair_rates_by_range_converted(
+                    AssetPairIdsDto::new(AssetIdDto(asset_id), AssetIdDto(reference_asset_id)),
+                    RangeDto::Custom(Some(from), Some(to), None),
+                )
+                .await?;
+            let in_period = || rates.iter().filter(|r| r.date >= from && r.date < to);
+            let (Some(first), Some(last)) = (
+                in_period().min_by_key(|r| r.date),
+                in_period().max_by_key(|r| r.date),
+            ) else {
+                continue;
+            };
+            let Some(pct) = change_pct(first.rate, last.rate) else {
+                continue;
+            };
+
+            movers.push(PortfolioMover {
+                asset_id,
+                asset_name: asset.name.clone(),
+                ticker: Some(asset.ticker.clone()),
+                start_price: first.rate,
+                end_price: last.rate,
+                change_pct: pct,
+                value_change: (held * (last.rate - first.rate)).round_dp(2),
+            });
+        }
+        Ok(movers)
+    }
+
+    /// Outflows due in `from..to`: the next occurrence of the user's active recurring
+    /// transactions and the next charge of detected subscriptions. A subscription
+    /// that a recurring transaction already covers is listed once.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id))]
+    pub async fn get_upcoming_charges(
+        &self,
+        user_id: Uuid,
+        from: OffsetDateTime,
+        to: OffsetDateTime,
+    ) -> Result<Vec<UpcomingCharge>> {
+        let recurring: Vec<_> = self
+            .recurring_transaction_service
+            .get_recurring_transactions(user_id)
+            .await?
+            .into_iter()
+            .filter(|r| r.active)
+            .filter_map(|r| {
+                let next = r.next_occurrence.filter(|d| *d >= from && *d < to)?;
+                let (amount, asset_id, account_id, description) = primary_leg(&r.template);
+                (amount < Decimal::ZERO).then(|| {
+                    (
+                        description.unwrap_or(r.name),
+                        account_id,
+                        -amount,
+                        asset_id,
+                        next.date(),
+                    )
+                })
+            })
+            .collect();
+        let subscriptions: Vec<_> = self
+            .subscription_service
+            .detect_subscriptions(user_id, from.date())
+            .await?
+            .into_iter()
+            .filter(|x| x.next_charge_date >= from.date() && x.next_charge_date < to.date())
+            .map(|x| {
+                (
+                    x.description,
+                    x.account_id,
+                    x.amount,
+                    x.asset_id,
+                    x.next_charge_date,
+                )
+            })
+            .collect();
+
+        let tickers = self
+            .asset_ticker_map(
+                recurring
+                    .iter()
+                    .chain(&subscriptions)
+                    .map(|c| c.3)
+                    .collect(),
+            )
+            .await?;
+        let account_names = self
+            .account_name_map(
+                recurring
+                    .iter()
+                    .chain(&subscriptions)
+                    .map(|c| c.1)
+                    .collect(),
+            )
+            .await?;
+
+        let mut seen: HashSet<(String, Uuid)> = HashSet::new();
+        let mut charges: Vec<UpcomingCharge> = recurring
+            .into_iter()
+            .map(|c| (c, "recurring"))
+            .chain(subscriptions.into_iter().map(|c| (c, "subscription")))
+            .filter(|((description, account_id, ..), _)| {
+                seen.insert((description.to_lowercase(), *account_id))
+            })
+            .map(
+                |((description, account_id, amount, asset_id, date), source)| UpcomingCharge {
+                    description,
+                    account: account_names.get(&account_id).cloned().unwrap_or_default(),
+                    amount,
+                    unit: tickers.get(&asset_id).cloned().unwrap_or_default(),
+                    date: date.to_string(),
+                    source: source.to_string(),
+                },
+            )
+            .collect();
+        charges.sort_by(|a, b| a.date.cmp(&b.date));
+        Ok(charges)
+    }
+
     async fn asset_map(&self, ids: HashSet<i32>) -> Result<HashMap<i32, AssetDto>> {
         if ids.is_empty() {
             return Ok(HashMap::new());
@@ -1199,6 +1528,19 @@ fn to_asset_result(r: AiAssetModel) -> AssetResult {
     }
 }
 
+fn to_embedding_search(embedding: QueryEmbedding) -> ai_search_params::EmbeddingSearch {
+    ai_search_params::EmbeddingSearch {
+        model: embedding.model,
+        vector: Vector::from(
+            embedding
+                .vector
+                .iter()
+                .map(|&x| x as f32)
+                .collect::<Vec<f32>>(),
+        ),
+    }
+}
+
 fn to_search_result(m: AiTransactionSearchModel) -> TransactionSearchResult {
     TransactionSearchResult {
         transaction_id: m.transaction_id,
@@ -1,11 +1,23 @@
+use ai::config::AiConfig;
+use ai::embedding::Embedder;
 #[mockall_double::double]
 use dal::database_context::MyraDb;
 use dal::job_queue::JobQueueHandle;
+use dal::models::ai_models::{
+    AiStaleAssetEmbeddingModel, AiStaleCategoryEmbeddingModel, AiStaleDescriptionEmbeddingModel,
+};
 use dal::queries::ai_queries;
 use pgvector::Vector;
+use time::{Duration, OffsetDateTime};
 use uuid::Uuid;
 
-use crate::jobs::EmbeddingJob;
+use crate::dtos::embedding_dto::EmbeddingDto;
+use crate::jobs::{EmbeddingJob, ReembedJob};
+
+const REEMBED_BATCH_SIZE: u64 = 100;
+/// How long a re-embed run may go without finishing a batch before a starting worker
+/// takes it over, e.g. after its job failed for good.
+const REEMBED_ABANDONED_AFTER: Duration = Duration::hours(1);
 
 #[derive(Clone)]
 pub struct AiEmbeddingService {
@@ -21,6 +33,44 @@ impl AiEmbeddingService {
         }
     }
 
+    /// Embeds `text` right away, for callers that cannot wait for the queue.
+    #[tracing::instrument(level = "debug", skip_all, fields(text_len = text.len()))]
+    pub async fn generate_embedding(&self, text: &str) -> anyhow::Result<EmbeddingDto> {
+        let config = AiConfig::try_from_env()?;
+        let vec_f64 = Embedder::new(&config)?.embed(text).await?;
+        Ok(EmbeddingDto {
+            model: config.embedding_model,
+            vector: vec_f64.iter().map(|&x| x as f32).collect(),
+        })
+    }
+
+    /// Embeds all of `texts` in as few requests as the model allows. Embeddings come back
+    /// in the order of `texts`.
+    #[tracing::instrument(level = "debug", skip_all, fields(count = texts.len()))]
+    pub async fn generate_embeddings(
+        &self,
+        texts: Vec<String>,
+    ) -> anyhow::Result<Vec<EmbeddingDto>> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+        let config = AiConfig::try_from_env()?;
+        let vectors = Embedder::new(&config)?.embed_many(texts).await?;
+        Ok(vectors
+            .into_iter()
+            .map(|vector| EmbeddingDto {
+                model: config.embedding_model.clone(),
+                vector: vector.iter().map(|&x| x as f32).collect(),
+            })
+            .collect())
+    }
+
+    /// The model new embeddings come from. Stored vectors of other models do not
+    /// compare with them.
+    pub fn active_model(&self) -> anyhow::Result<String> {
+        Ok(AiConfig::try_from_env()?.embedding_model)
+    }
+
     pub async fn enqueue_embed_transaction(
         &self,
         transaction_id: Uuid,
@@ -56,51 +106,302 @@ impl AiEmbeddingService {
             .await
     }
 
-    #[tracing::instrument(level = "debug", skip_all, fields(transaction_id = %transaction_id, dimensions = embedding.len()))]
+    /// Starts re-embedding everything not embedded by the active model, e.g. after
+    /// `AI_EMBEDDING_MODEL` or `AI_EMBEDDING_DIMS` changed.
+    pub async fn enqueue_reembed(&self) -> anyhow::Result<()> {
+        self.queue
+            .push(ReembedJob::Categories { after: None })
+            .await
+    }
+
+    /// Enqueues the re-embed job when any stored vector is missing or came from another
+    /// model than the active one. Called when the worker starts, so changing the model
+    /// only takes a restart. Does nothing while no embedding model is configured, or
+    /// while another worker's run for the same model is still making progress.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn enqueue_reembed_if_stale(&self) -> anyhow::Result<()> {
+        let Ok(config) = AiConfig::try_from_env() else {
+            return Ok(());
+        };
+        let model = config.embedding_model;
+        let dims = config.embedding_dims as i32;
+
+        let stale = !self
+            .db
+            .fetch_all::<AiStaleCategoryEmbeddingModel>(ai_queries::get_stale_category_embeddings(
+                &model, dims, None, 1,
+            ))
+            .await?
+            .is_empty()
+            || !self
+                .db
+                .fetch_all::<AiStaleAssetEmbeddingModel>(ai_queries::get_stale_asset_embeddings(
+                    &model, dims, None, 1,
+                ))
+                .await?
+                .is_empty()
+            || !self
+                .db
+                .fetch_all::<AiStaleDescriptionEmbeddingModel>(
+                    ai_queries::get_stale_group_embeddings(&model, dims, None, 1),
+                )
+                .await?
+                .is_empty()
+            || !self
+                .db
+                .fetch_all::<AiStaleDescriptionEmbeddingModel>(
+                    ai_queries::get_stale_transaction_embeddings(&model, dims, None, 1),
+                )
+                .await?
+                .is_empty();
+        if !stale {
+            return Ok(());
+        }
+
+        let claimed = self
+            .db
+            .execute_with_rows_affected(ai_queries::claim_reembed_run(
+                &model,
+                dims,
+                OffsetDateTime::now_utc() - REEMBED_ABANDONED_AFTER,
+            ))
+            .await?
+            > 0;
+        if !claimed {
+            tracing::debug!(model = %model, dims, "re-embedding already under way");
+            return Ok(());
+        }
+
+        tracing::info!(model = %model, dims, "stored embeddings are stale, re-embedding queued");
+        self.enqueue_reembed().await
+    }
+
+    /// Re-embeds one batch of `job` and enqueues the batch after it.
+    #[tracing::instrument(level = "debug", skip_all, fields(job = ?job))]
+    pub async fn reembed_batch(&self, job: &ReembedJob) -> anyhow::Result<()> {
+        let config = AiConfig::try_from_env()?;
+        let embedder = Embedder::new(&config)?;
+        let model = embedder.model_name().to_string();
+        let dims = embedder.dims() as i32;
+
+        let next = match job {
+            ReembedJob::Categories { after } => {
+                let rows = self
+                    .db
+                    .fetch_all::<AiStaleCategoryEmbeddingModel>(
+                        ai_queries::get_stale_category_embeddings(
+                            &model,
+                            dims,
+                            *after,
+                            REEMBED_BATCH_SIZE,
+                        ),
+                    )
+                    .await?;
+                let texts = rows
+                    .iter()
+                    .map(|row| category_embedding_text(&row.category, &row.category_type_name))
+                    .collect();
+                let vectors = embed_batch(&embedder, texts).await?;
+                for (row, vector) in rows.iter().zip(vectors) {
+                    self.db
+                        .execute(ai_queries::update_category_embedding(
+                            row.id, vector, &model,
+                        ))
+                        .await?;
+                }
+                match rows.last() {
+                    Some(last) if rows.len() as u64 == REEMBED_BATCH_SIZE => {
+                        Some(ReembedJob::Categories {
+                            after: Some(last.id),
+                        })
+                    }
+                    _ => Some(ReembedJob::Assets { after: None }),
+                }
+            }
+            ReembedJob::Assets { after } => {
+                let rows = self
+                    .db
+                    .fetch_all::<AiStaleAssetEmbeddingModel>(
+                        ai_queries::get_stale_asset_embeddings(
+                            &model,
+                            dims,
+                            *after,
+                            REEMBED_BATCH_SIZE,
+                        ),
+                    )
+                    .await?;
+                let texts = rows
+                    .iter()
+                    .map(|row| {
+                        asset_embedding_text(&row.asset_name, row.ticker.as_deref().unwrap_or(""))
+                    })
+                    .collect();
+                let vectors = embed_batch(&embedder, texts).await?;
+                for (row, vector) in rows.iter().zip(vectors) {
+                    self.db
+                        .execute(ai_queries::update_asset_embedding(row.id, vector, &model))
+                        .await?;
+                }
+                match rows.last() {
+                    Some(last) if rows.len() as u64 == REEMBED_BATCH_SIZE => {
+                        Some(ReembedJob::Assets {
+                            after: Some(last.id),
+                        })
+                    }
+                    _ => Some(ReembedJob::Groups { after: None }),
+                }
+            }
+            ReembedJob::Groups { after } => {
+                let rows = self
+                    .db
+                    .fetch_all::<AiStaleDescriptionEmbeddingModel>(
+                        ai_queries::get_stale_group_embeddings(
+                            &model,
+                            dims,
+                            *after,
+                            REEMBED_BATCH_SIZE,
+                        ),
+                    )
+                    .await?;
+                let texts = rows.iter().map(|row| row.description.clone()).collect();
+                let vectors = embed_batch(&embedder, texts).await?;
+                for (row, vector) in rows.iter().zip(vectors) {
+                    self.db
+                        .execute(ai_queries::update_transaction_group_embedding(
+                            row.id, vector, &model,
+                        ))
+                        .await?;
+                }
+                match rows.last() {
+                    Some(last) if rows.len() as u64 == REEMBED_BATCH_SIZE => {
+                        Some(ReembedJob::Groups {
+                            after: Some(last.id),
+                        })
+                    }
+                    _ => Some(ReembedJob::Transactions { after: None }),
+                }
+            }
+            ReembedJob::Transactions { after } => {
+                let rows = self
+                    .db
+                    .fetch_all::<AiStaleDescriptionEmbeddingModel>(
+                        ai_queries::get_stale_transaction_embeddings(
+                            &model,
+                            dims,
+                            *after,
+                            REEMBED_BATCH_SIZE,
+                        ),
+                    )
+                    .await?;
+                let texts = rows.iter().map(|row| row.description.clone()).collect();
+                let vectors = embed_batch(&embedder, texts).await?;
+                for (row, vector) in rows.iter().zip(vectors) {
+                    self.db
+                        .execute(ai_queries::update_transaction_description_embedding(
+                            row.id, vector, &model,
+                        ))
+                        .await?;
+                }
+                match rows.last() {
+                    Some(last) if rows.len() as u64 == REEMBED_BATCH_SIZE => {
+                        Some(ReembedJob::Transactions {
+                            after: Some(last.id),
+                        })
+                    }
+                    _ => None,
+                }
+            }
+        };
+
+        self.db
+            .execute(ai_queries::update_reembed_run(&model, dims, next.is_none()))
+            .await?;
+        match next {
+            Some(next) => self.queue.push(next).await,
+            None => {
+                tracing::info!(model = %model, dims, "re-embedding finished");
+                Ok(())
+            }
+        }
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(transaction_id = %transaction_id, dimensions = embedding.vector.len()))]
     pub async fn store_transaction_embedding(
         &self,
         transaction_id: Uuid,
-        embedding: Vec<f32>,
+        embedding: EmbeddingDto,
     ) -> anyhow::Result<()> {
         let query = ai_queries::update_transaction_description_embedding(
             transaction_id,
-            Vector::from(embedding),
+            Vector::from(embedding.vector),
+            &embedding.model,
         );
         self.db.execute(query).await?;
         Ok(())
     }
 
-    #[tracing::instrument(level = "debug", skip_all, fields(group_id = %group_id, dimensions = embedding.len()))]
+    #[tracing::instrument(level = "debug", skip_all, fields(group_id = %group_id, dimensions = embedding.vector.len()))]
     pub async fn store_group_embedding(
         &self,
         group_id: Uuid,
-        embedding: Vec<f32>,
+        embedding: EmbeddingDto,
     ) -> anyhow::Result<()> {
-        let query =
-            ai_queries::update_transaction_group_embedding(group_id, Vector::from(embedding));
+        let query = ai_queries::update_transaction_group_embedding(
+            group_id,
+            Vector::from(embedding.vector),
+            &embedding.model,
+        );
         self.db.execute(query).await?;
         Ok(())
     }
 
-    #[tracing::instrument(level = "debug", skip_all, fields(asset_id = %asset_id, dimensions = embedding.len()))]
+    #[tracing::instrument(level = "debug", skip_all, fields(asset_id = %asset_id, dimensions = embedding.vector.len()))]
     pub async fn store_asset_embedding(
         &self,
         asset_id: i32,
-        embedding: Vec<f32>,
+        embedding: EmbeddingDto,
     ) -> anyhow::Result<()> {
-        let query = ai_queries::update_asset_embedding(asset_id, Vector::from(embedding));
+        let query = ai_queries::update_asset_embedding(
+            asset_id,
+            Vector::from(embedding.vector),
+            &embedding.model,
+        );
         self.db.execute(query).await?;
         Ok(())
     }
 
-    #[tracing::instrument(level = "debug", skip_all, fields(category_id = %category_id, dimensions = embedding.len()))]
+    #[tracing::instrument(level = "debug", skip_all, fields(category_id = %category_id, dimensions = embedding.vector.len()))]
     pub async fn store_category_embedding(
         &self,
         category_id: i32,
-        embedding: Vec<f32>,
+        embedding: EmbeddingDto,
     ) -> anyhow::Result<()> {
-        let query = ai_queries::update_category_embedding(category_id, Vector::from(embedding));
+        let query = ai_queries::update_category_embedding(
+            category_id,
+            Vector::from(embedding.vector),
+            &embedding.model,
+        );
         self.db.execute(query).await?;
         Ok(())
     }
 }
+
+pub(crate) fn asset_embedding_text(name: &str, ticker: &str) -> String {
+    format!("Asset: {name} | Ticker: {ticker}")
+}
+
+pub(crate) fn category_embedding_text(category: &str, category_type_name: &str) -> String {
+    format!("Category: {category} | Type: {category_type_name}")
+}
+
+async fn embed_batch(embedder: &Embedder, texts: Vec<String>) -> anyhow::Result<Vec<Vector>> {
+    if texts.is_empty() {
+        return Ok(Vec::new());
+    }
+    Ok(embedder
+        .embed_many(texts)
+        .await?
+        .into_iter()
+        .map(|vector| Vector::from(vector.iter().map(|&x| x as f32).collect::<Vec<f32>>()))
+        .collect())
+}
@@ -1,8 +1,10 @@
 use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Arc;
 
 #[mockall_double::double]
 use dal::database_context::MyraDb;
-use dal::market_data_client::{AssetClass, MarketDataClient, PairRequest};
+use dal::market_data_client::{AssetClass, PairRequest};
+use dal::market_data_provider::MarketDataProvider;
 use dal::{
     models::asset_models::{
         AssetPair, AssetPairRate, AssetPairRateDate, AssetPairRateOption, AssetRate,
@@ -29,6 +31,7 @@ use time::OffsetDateTime;
 
 pub struct AssetRatesService {
     db: MyraDb,
+    market_data: Arc<dyn MarketDataProvider>,
 }
 
 #[automock]
@@ -36,6 +39,7 @@ impl AssetRatesService {
     pub fn new(providers: &super::ServiceProviders) -> Self {
         Self {
             db: providers.db.clone(),
+            market_data: providers.market_data.clone(),
         }
     }
 
@@ -352,7 +356,8 @@ impl AssetRatesService {
             quote_type: AssetClass::from_asset_type_id(info.asset_type2),
         };
 
-        let entries = MarketDataClient::new()
+        let entries = self
+            .market_data
             .get_history(std::slice::from_ref(&request), from)
             .await
             .map_err(|e| anyhow::anyhow!("market data history fetch failed: {}", e))?;
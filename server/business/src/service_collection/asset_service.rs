@@ -18,7 +18,7 @@ use dal::database_context::MyraDb;
 use mockall::automock;
 use uuid::Uuid;
 
-use super::ai_embedding_service::AiEmbeddingService;
+use super::ai_embedding_service::{asset_embedding_text, AiEmbeddingService};
 
 use crate::dtos::{
     self,
@@ -284,7 +284,7 @@ impl AssetsService {
 
         self.db.commit_transaction().await?;
 
-        let embed_text = format!("Asset: {} | Ticker: {}", asset_dto.name, asset_dto.ticker);
+        let embed_text = asset_embedding_text(&asset_dto.name, &asset_dto.ticker);
         self.embedding_service
             .enqueue_embed_asset(asset_id, embed_text)
             .await?;
@@ -378,7 +378,7 @@ impl AssetsService {
         self.db.execute(query).await?;
 
         if name_or_ticker_changed {
-            let embed_text = format!("Asset: {} | Ticker: {}", name, ticker);
+            let embed_text = asset_embedding_text(&name, &ticker);
             self.embedding_service
                 .enqueue_embed_asset(asset_id, embed_text)
                 .await?;
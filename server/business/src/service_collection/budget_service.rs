@@ -0,0 +1,252 @@
+#[mockall_double::double]
+use dal::database_context::MyraDb;
+use dal::models::budget_models::{BudgetRow, BudgetSpendingRow};
+use dal::queries::budget_queries;
+use dal::query_params::get_budgets_params::{GetBudgetSpendingParams, GetBudgetsParams};
+use rust_decimal::Decimal;
+use time::{Date, Time, UtcOffset};
+use uuid::Uuid;
+
+use crate::dtos::asset_id_date_dto::AssetIdDateDto;
+use crate::dtos::assets::asset_id_dto::AssetIdDto;
+use crate::dtos::bad_request_error_dto::BusinessBadRequestError;
+use crate::dtos::budgets::{AddUpdateBudgetDto, BudgetActualDto, BudgetDto, BudgetTargetDto};
+use crate::dtos::categories::CategoryError;
+use crate::dtos::not_found_error_dto::BusinessNotFoundError;
+use crate::entities::budgets::{calculate_budget_periods, next_period_start, period_start};
+
+#[mockall_double::double]
+use super::asset_rates_service::AssetRatesService;
+use super::category_service::CategoryService;
+use super::category_type_service::CategoryTypeService;
+
+pub struct BudgetService {
+    db: MyraDb,
+    asset_rates_service: AssetRatesService,
+    category_service: CategoryService,
+    category_type_service: CategoryTypeService,
+}
+
+impl BudgetService {
+    pub fn new(providers: &super::ServiceProviders) -> Self {
+        Self {
+            db: providers.db.clone(),
+            asset_rates_service: AssetRatesService::new(providers),
+            category_service: CategoryService::new(providers),
+            category_type_service: CategoryTypeService::new(providers),
+        }
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id))]
+    pub async fn get_budgets(&self, user_id: Uuid) -> anyhow::Result<Vec<BudgetDto>> {
+        let query = budget_queries::get_budgets(GetBudgetsParams::all(user_id));
+        let rows = self.db.fetch_all::<BudgetRow>(query).await?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, budget_id = %budget_id))]
+    pub async fn get_budget(&self, user_id: Uuid, budget_id: Uuid) -> anyhow::Result<BudgetDto> {
+        let query = budget_queries::get_budgets(GetBudgetsParams::by_id(user_id, budget_id));
+        let row = self
+            .db
+            .fetch_optional::<BudgetRow>(query)
+            .await?
+            .ok_or_else(|| budget_not_found(budget_id))?;
+        Ok(row.into())
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id))]
+    pub async fn create_budget(
+        &self,
+        user_id: Uuid,
+        budget: AddUpdateBudgetDto,
+    ) -> anyhow::Result<BudgetDto> {
+        self.validate_budget(user_id, &budget).await?;
+
+        let query = budget_queries::insert_budget(budget.into_add_model(user_id));
+        let row = self.db.fetch_one::<BudgetRow>(query).await?;
+        Ok(row.into())
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, budget_id = %budget_id))]
+    pub async fn update_budget(
+        &self,
+        user_id: Uuid,
+        budget_id: Uuid,
+        budget: AddUpdateBudgetDto,
+    ) -> anyhow::Result<BudgetDto> {
+        self.validate_budget(user_id, &budget).await?;
+
+        let query = budget_queries::update_budget(budget_id, user_id, budget.into());
+        let row = self
+            .db
+            .fetch_optional::<BudgetRow>(query)
+            .await?
+            .ok_or_else(|| budget_not_found(budget_id))?;
+        Ok(row.into())
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, budget_id = %budget_id))]
+    pub async fn delete_budget(&self, user_id: Uuid, budget_id: Uuid) -> anyhow::Result<()> {
+        self.get_budget(user_id, budget_id).await?;
+
+        let query = budget_queries::delete_budget(budget_id, user_id);
+        self.db.execute(query).await?;
+        Ok(())
+    }
+
+    /// Compares every budget with what was actually spent in the period containing `date`.
+    /// Spending is converted to `reference_asset_id` at the rate of the day it was booked.
+    /// Budgets starting after `date` are left out.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, date = %date))]
+    pub async fn get_budget_vs_actual(
+        &self,
+        user_id: Uuid,
+        reference_asset_id: AssetIdDto,
+        date: Date,
+    ) -> anyhow::Result<Vec<BudgetActualDto>> {
+        let budgets = self.get_budgets(user_id).await?;
+
+        let mut result = Vec::with_capacity(budgets.len());
+        for budget in budgets {
+            let budget_start = budget.start_date.to_offset(UtcOffset::UTC).date();
+            if budget_start > date {
+                continue;
+            }
+
+            // Rollover needs the full history to know what was carried into this period.
+            let spending_from = if budget.rollover {
+                period_start(budget.period, budget_start)
+            } else {
+                period_start(budget.period, date)
+            };
+            let spending_to = next_period_start(budget.period, period_start(budget.period, date));
+
+            let spending = self
+                .get_converted_spending(
+                    user_id,
+                    &budget.target,
+                    spending_from,
+                    spending_to,
+                    &reference_asset_id,
+                )
+                .await?;
+
+            let periods = calculate_budget_periods(
+                budget.amount,
+                budget.period,
+                budget.rollover,
+                spending_from,
+                date,
+                &spending,
+            );
+
+            if let Some(period) = periods.into_iter().last() {
+                result.push(BudgetActualDto { budget, period });
+            }
+        }
+
+        Ok(result)
+    }
+
+    async fn get_converted_spending(
+        &self,
+        user_id: Uuid,
+        target: &BudgetTargetDto,
+        from: Date,
+        to: Date,
+        reference_asset_id: &AssetIdDto,
+    ) -> anyhow::Result<Vec<(Date, Decimal)>> {
+        let query = budget_queries::get_budget_spending(GetBudgetSpendingParams {
+            user_id,
+            category_id: target.category_id(),
+            category_type_id: target.category_type_id(),
+            start: from.with_time(Time::MIDNIGHT).assume_utc(),
+            end: to.with_time(Time::MIDNIGHT).assume_utc(),
+        });
+        let rows = self.db.fetch_all::<BudgetSpendingRow>(query).await?;
+
+        let asset_id_dates: Vec<AssetIdDateDto> = rows
+            .iter()
+            .filter(|row| row.asset_id != reference_asset_id.0)
+            .map(|row| AssetIdDateDto {
+                asset_id: row.asset_id,
+                date: row.day,
+            })
+            .collect();
+
+        let rates = self
+            .asset_rates_service
+            .get_pairs_by_dates_converted(asset_id_dates, reference_asset_id.clone())
+            .await?;
+
+        let mut rate_iter = rates.into_iter();
+        let mut spending = Vec::with_capacity(rows.len());
+        for row in rows {
+            let mut quantity = row.quantity;
+            if row.asset_id != reference_asset_id.0 {
+                match rate_iter.next().flatten() {
+                    Some(rate) => quantity *= rate.rate,
+                    None => {
+                        tracing::warn!(
+                            asset_id = row.asset_id,
+                            date = %row.day,
+                            "no conversion rate available for budget spending; excluding"
+                        );
+                        continue;
+                    }
+                }
+            }
+            // Expenses are booked as negative entries, so spending is the negated sum.
+            spending.push((row.day.to_offset(UtcOffset::UTC).date(), -quantity));
+        }
+
+        Ok(spending)
+    }
+
+    async fn validate_budget(
+        &self,
+        user_id: Uuid,
+        budget: &AddUpdateBudgetDto,
+    ) -> anyhow::Result<()> {
+        if budget.amount < Decimal::ZERO {
+            return Err(anyhow::Error::new(BusinessBadRequestError {
+                message: "Budget amount cannot be negative".to_string(),
+            }));
+        }
+
+        match budget.target {
+            BudgetTargetDto::Category(category_id) => {
+                self.category_service
+                    .get_category(category_id, user_id)
+                    .await
+                    .map_err(|err| match err.downcast_ref::<CategoryError>() {
+                        Some(_) => anyhow::Error::new(BusinessNotFoundError {
+                            message: format!("category {category_id} not found"),
+                        }),
+                        None => err,
+                    })?;
+            }
+            BudgetTargetDto::CategoryType(type_id) => {
+                let exists = self
+                    .category_type_service
+                    .get_user_category_types(user_id)
+                    .await?
+                    .any(|x| x.id == type_id);
+                if !exists {
+                    return Err(anyhow::Error::new(BusinessNotFoundError {
+                        message: format!("category type {type_id} not found"),
+                    }));
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn budget_not_found(budget_id: Uuid) -> anyhow::Error {
+    anyhow::Error::new(BusinessNotFoundError {
+        message: format!("budget {budget_id} not found"),
+    })
+}
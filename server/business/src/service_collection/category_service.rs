@@ -10,7 +10,7 @@ use dal::query_params::get_categories_params::GetCategoriesParams;
 use itertools::Itertools;
 use uuid::Uuid;
 
-use super::ai_embedding_service::AiEmbeddingService;
+use super::ai_embedding_service::{category_embedding_text, AiEmbeddingService};
 use super::category_validation_service::CategoryValidationService;
 use crate::dtos::{
     categories::{CategoryDto, CategoryError, CreateCategoryDto, UpdateCategoryDto},
@@ -143,10 +143,7 @@ impl CategoryService {
             .context("Failed to create category")?;
 
         let category = self.get_category(created_id, user_id).await?;
-        let embed_text = format!(
-            "Category: {} | Type: {}",
-            category.category, category.category_type_name
-        );
+        let embed_text = category_embedding_text(&category.category, &category.category_type_name);
         self.embedding_service
             .enqueue_embed_category(created_id, embed_text)
             .await?;
@@ -189,10 +186,8 @@ impl CategoryService {
         let category = self.get_category(category_id, user_id).await?;
 
         if name_or_type_changed {
-            let embed_text = format!(
-                "Category: {} | Type: {}",
-                category.category, category.category_type_name
-            );
+            let embed_text =
+                category_embedding_text(&category.category, &category.category_type_name);
             self.embedding_service
                 .enqueue_embed_category(category_id, embed_text)
                 .await?;
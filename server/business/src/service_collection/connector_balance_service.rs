@@ -0,0 +1,138 @@
+#[mockall_double::double]
+use dal::database_context::MyraDb;
+use dal::models::connector_models::ConnectorBalanceSnapshotRow;
+use dal::queries::connector_queries;
+
+use itertools::Itertools;
+use std::collections::HashMap;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::dtos::connectors::{ProviderBalanceLineDto, ProviderBalanceSnapshotDto};
+use crate::dtos::net_worth::entries_interval_sum_dto::EntriesIntervalSumDto;
+use crate::entities::connectors::provider_balance_history::snapshot_movements;
+
+use super::connector_service::ConnectorService;
+use super::ServiceProviders;
+
+/// Keeps the balances providers report on each sync, so they can be compared with the
+/// balance Sverto computes from the ledger.
+pub struct ConnectorBalanceService {
+    db: MyraDb,
+    connectors: ConnectorService,
+}
+
+impl ConnectorBalanceService {
+    pub fn new(providers: &ServiceProviders) -> Self {
+        Self {
+            db: providers.db.clone(),
+            connectors: ConnectorService::new(providers),
+        }
+    }
+
+    /// Stores one fetch of a provider account's balance. Lines reported more than once
+    /// for the same currency or instrument are summed.
+    #[tracing::instrument(level = "debug", skip_all, fields(provider_account_ref = %provider_account_ref))]
+    pub(crate) async fn record_snapshot(
+        &self,
+        provider_account_ref: Uuid,
+        fetched_at: OffsetDateTime,
+        lines: Vec<ProviderBalanceLineDto>,
+    ) -> anyhow::Result<()> {
+        let mut merged: HashMap<_, ProviderBalanceLineDto> = HashMap::new();
+        for line in lines {
+            merged
+                .entry((line.kind, line.asset_identifier.clone()))
+                .and_modify(|existing| existing.quantity += line.quantity)
+                .or_insert(line);
+        }
+        if merged.is_empty() {
+            return Ok(());
+        }
+
+        let models = merged
+            .into_values()
+            .map(|line| line.into_add_model(provider_account_ref, fetched_at))
+            .collect();
+        self.db
+            .execute(connector_queries::insert_connector_balance_snapshots(
+                models,
+            ))
+            .await?;
+        Ok(())
+    }
+
+    /// Balances the binding's provider account reported, oldest first.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, binding_id = %binding_id))]
+    pub async fn get_binding_balances(
+        &self,
+        user_id: Uuid,
+        binding_id: Uuid,
+        from: Option<OffsetDateTime>,
+        to: Option<OffsetDateTime>,
+    ) -> anyhow::Result<Vec<ProviderBalanceSnapshotDto>> {
+        let binding = self.connectors.get_binding(user_id, binding_id).await?;
+
+        self.get_snapshots(vec![binding.provider_account_ref], from, to)
+            .await
+    }
+
+    /// The balances reported by every provider account bound to the account between `from`
+    /// and `to`, restated as movements. The last snapshot before `from` is included, so
+    /// the movements open on the balance held at `from`. Empty when the account has no
+    /// bindings or none has reported a balance.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, account_id = %account_id))]
+    pub(crate) async fn get_account_balance_movements(
+        &self,
+        user_id: Uuid,
+        account_id: Uuid,
+        from: Option<OffsetDateTime>,
+        to: Option<OffsetDateTime>,
+    ) -> anyhow::Result<Vec<EntriesIntervalSumDto>> {
+        let provider_account_refs: Vec<Uuid> = self
+            .connectors
+            .list_bindings(user_id)
+            .await?
+            .into_iter()
+            .filter(|binding| binding.sverto_account_id == account_id)
+            .map(|binding| binding.provider_account_ref)
+            .unique()
+            .collect();
+        if provider_account_refs.is_empty() {
+            return Ok(vec![]);
+        }
+
+        let mut snapshots = match from {
+            Some(from) => {
+                let query = connector_queries::get_latest_connector_balance_snapshots_before(
+                    provider_account_refs.clone(),
+                    from,
+                );
+                let rows = self
+                    .db
+                    .fetch_all::<ConnectorBalanceSnapshotRow>(query)
+                    .await?;
+                ProviderBalanceSnapshotDto::from_rows(rows)?
+            }
+            None => vec![],
+        };
+        snapshots.extend(self.get_snapshots(provider_account_refs, from, to).await?);
+        Ok(snapshot_movements(&snapshots))
+    }
+
+    async fn get_snapshots(
+        &self,
+        provider_account_refs: Vec<Uuid>,
+        from: Option<OffsetDateTime>,
+        to: Option<OffsetDateTime>,
+    ) -> anyhow::Result<Vec<ProviderBalanceSnapshotDto>> {
+        let query =
+            connector_queries::get_connector_balance_snapshots(provider_account_refs, from, to);
+        let rows = self
+            .db
+            .fetch_all::<ConnectorBalanceSnapshotRow>(query)
+            .await?;
+
+        ProviderBalanceSnapshotDto::from_rows(rows)
+    }
+}
@@ -0,0 +1,146 @@
+use std::collections::HashMap;
+
+#[mockall_double::double]
+use dal::database_context::MyraDb;
+use dal::models::connector_models::{AddConnectorDuplicateCandidateModel, DuplicateCandidateRow};
+use dal::queries::connector_queries;
+
+use itertools::Itertools;
+use time::Duration;
+use uuid::Uuid;
+
+use crate::entities::connectors::duplicate_matching::{
+    is_comparable, match_duplicates, DuplicateMatches, ExistingMovement, ImportedMovement,
+    DATE_WINDOW_DAYS,
+};
+
+use super::ai_embedding_service::AiEmbeddingService;
+use super::ServiceProviders;
+
+/// Finds the transactions the user already entered by hand among what a sync is about
+/// to import.
+pub struct ConnectorDuplicateService {
+    db: MyraDb,
+    embeddings: AiEmbeddingService,
+}
+
+impl ConnectorDuplicateService {
+    pub fn new(providers: &ServiceProviders) -> Self {
+        Self {
+            db: providers.db.clone(),
+            embeddings: AiEmbeddingService::new(providers),
+        }
+    }
+
+    /// Embeds the distinct import descriptions in a single request. Syncs call this before
+    /// opening their database transaction so a slow provider holds no locks. When no model
+    /// is configured or the request fails the map is empty, and duplicates are matched on
+    /// description words.
+    #[tracing::instrument(level = "debug", skip_all, fields(count = descriptions.len()))]
+    pub(crate) async fn embed_descriptions(
+        &self,
+        descriptions: Vec<String>,
+    ) -> HashMap<String, Vec<f32>> {
+        if descriptions.is_empty() || self.embeddings.active_model().is_err() {
+            return HashMap::new();
+        }
+        let descriptions: Vec<String> = descriptions.into_iter().unique().collect();
+        match self
+            .embeddings
+            .generate_embeddings(descriptions.clone())
+            .await
+        {
+            Ok(embeddings) => descriptions
+                .into_iter()
+                .zip(embeddings)
+                .map(|(description, embedding)| (description, embedding.vector))
+                .collect(),
+            Err(e) => {
+                tracing::warn!(
+                    error = %e,
+                    "import descriptions not embedded — matching duplicates on words"
+                );
+                HashMap::new()
+            }
+        }
+    }
+
+    /// Matches the imports against the user's unlinked transactions. `embeddings` holds
+    /// the import descriptions embedded by [`Self::embed_descriptions`]; they are only
+    /// used for imports with a candidate embedded by the active model to compare to.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, count = imported.len()))]
+    pub(crate) async fn match_imports(
+        &self,
+        user_id: Uuid,
+        mut imported: Vec<ImportedMovement>,
+        embeddings: &HashMap<String, Vec<f32>>,
+    ) -> anyhow::Result<DuplicateMatches> {
+        let (Some(first), Some(last)) = (
+            imported.iter().map(|movement| movement.date).min(),
+            imported.iter().map(|movement| movement.date).max(),
+        ) else {
+            return Ok(DuplicateMatches::default());
+        };
+
+        let window = Duration::days(DATE_WINDOW_DAYS);
+        let active_model = self.embeddings.active_model().ok();
+        let existing: Vec<ExistingMovement> = self
+            .db
+            .fetch_all::<DuplicateCandidateRow>(
+                connector_queries::get_duplicate_candidate_transactions(
+                    user_id,
+                    imported
+                        .iter()
+                        .map(|movement| movement.asset_id)
+                        .unique()
+                        .collect(),
+                    (first - window).midnight().assume_utc(),
+                    (last + window + Duration::days(1)).midnight().assume_utc(),
+                ),
+            )
+            .await?
+            .into_iter()
+            .map(|row| ExistingMovement {
+                transaction_id: row.transaction_id,
+                account_id: row.account_id,
+                asset_id: row.asset_id,
+                amount: row.quantity,
+                date: row.date_transacted.date(),
+                description: row.description,
+                embedding: row
+                    .embedding
+                    .filter(|_| active_model.is_some() && row.embedding_model == active_model)
+                    .map(|x| x.to_vec()),
+            })
+            .collect();
+        if existing.is_empty() {
+            return Ok(DuplicateMatches::default());
+        }
+
+        for movement in imported.iter_mut() {
+            let worth_embedding = existing.iter().any(|candidate| {
+                candidate.embedding.is_some() && is_comparable(movement, candidate)
+            });
+            if worth_embedding {
+                movement.embedding = embeddings.get(&movement.description).cloned();
+            }
+        }
+
+        Ok(match_duplicates(&imported, &existing))
+    }
+
+    pub(crate) async fn record_candidates(
+        &self,
+        candidates: Vec<AddConnectorDuplicateCandidateModel>,
+    ) -> anyhow::Result<()> {
+        if candidates.is_empty() {
+            return Ok(());
+        }
+        self.db
+            .execute(connector_queries::insert_connector_duplicate_candidates(
+                candidates,
+            ))
+            .await?;
+        Ok(())
+    }
+}
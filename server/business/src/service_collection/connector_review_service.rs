@@ -0,0 +1,363 @@
+#[mockall_double::double]
+use dal::database_context::MyraDb;
+use dal::models::connector_models::{
+    ConnectorDuplicateCandidateRow, ConnectorTransactionRow, PendingReviewTransactionRow,
+    ReviewSuggestionRow,
+};
+use dal::queries::{connector_queries, transaction_data_queries};
+
+use std::collections::{HashMap, HashSet};
+use uuid::Uuid;
+
+use crate::dtos::bad_request_error_dto::BusinessBadRequestError;
+use crate::dtos::conflict_error_dto::BusinessConflictError;
+use crate::dtos::connectors::{
+    DuplicateCandidateDto, PendingReviewTransactionDto, ReviewEditDto, ReviewMergeDto,
+    ReviewSuggestionDto,
+};
+use crate::dtos::not_found_error_dto::BusinessNotFoundError;
+use crate::dtos::transaction_dto::TransactionVisibilityDto;
+
+use super::spending_alert_service::SpendingAlertService;
+use super::transaction_management_service::TransactionManagementService;
+use super::ServiceProviders;
+
+/// Triage of the ghost transactions imported by bindings in ghost write mode.
+pub struct ConnectorReviewService {
+    db: MyraDb,
+    transaction_management: TransactionManagementService,
+    alerts: SpendingAlertService,
+}
+
+impl ConnectorReviewService {
+    pub fn new(providers: &ServiceProviders) -> Self {
+        Self {
+            db: providers.db.clone(),
+            transaction_management: TransactionManagementService::new(providers),
+            alerts: SpendingAlertService::new(providers),
+        }
+    }
+
+    /// Pending ghost transactions across the user's bindings, or of one binding, with
+    /// suggestions from their description embeddings and the manual entries they may
+    /// duplicate.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id))]
+    pub async fn get_review_inbox(
+        &self,
+        user_id: Uuid,
+        binding_id: Option<Uuid>,
+    ) -> anyhow::Result<Vec<PendingReviewTransactionDto>> {
+        let pending: Vec<PendingReviewTransactionRow> = self
+            .db
+            .fetch_all(connector_queries::get_pending_review_transactions(
+                user_id, binding_id, None,
+            ))
+            .await?;
+        if pending.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let transaction_ids: Vec<Uuid> = pending.iter().map(|row| row.transaction_id).collect();
+        let mut transactions: HashMap<Uuid, _> = self
+            .transaction_management
+            .get_transactions_by_ids(user_id, transaction_ids.clone())
+            .await?
+            .into_iter()
+            .filter_map(|dto| Some((dto.transaction_id?, dto)))
+            .collect();
+        let mut suggestions: HashMap<Uuid, ReviewSuggestionDto> = self
+            .db
+            .fetch_all::<ReviewSuggestionRow>(connector_queries::get_review_suggestions(
+                user_id,
+                transaction_ids.clone(),
+            ))
+            .await?
+            .into_iter()
+            .map(|row| (row.transaction_id, row.into()))
+            .collect();
+        let mut duplicate_candidates: HashMap<Uuid, Vec<DuplicateCandidateDto>> = HashMap::new();
+        for row in self
+            .db
+            .fetch_all::<ConnectorDuplicateCandidateRow>(
+                connector_queries::get_connector_duplicate_candidates(transaction_ids),
+            )
+            .await?
+        {
+            duplicate_candidates
+                .entry(row.transaction_id)
+                .or_default()
+                .push(row.into());
+        }
+
+        Ok(pending
+            .into_iter()
+            .filter_map(|row| {
+                Some(PendingReviewTransactionDto {
+                    transaction: transactions.remove(&row.transaction_id)?,
+                    suggestions: suggestions.remove(&row.transaction_id).unwrap_or_default(),
+                    duplicate_candidates: duplicate_candidates
+                        .remove(&row.transaction_id)
+                        .unwrap_or_default(),
+                    binding_id: row.binding_id,
+                    external_id: row.external_id,
+                    imported_at: row.imported_at,
+                })
+            })
+            .collect())
+    }
+
+    /// Makes the ghost transactions regular ones as imported. Accepting an import flagged
+    /// as a possible duplicate settles it as a distinct movement.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, count = transaction_ids.len()))]
+    pub async fn accept(&self, user_id: Uuid, transaction_ids: Vec<Uuid>) -> anyhow::Result<()> {
+        let transaction_ids = self.ensure_pending(user_id, transaction_ids).await?;
+        self.transaction_management
+            .set_transactions_visibility(
+                user_id,
+                transaction_ids.clone(),
+                TransactionVisibilityDto::Default,
+            )
+            .await?;
+        self.db
+            .execute(connector_queries::delete_connector_duplicate_candidates(
+                transaction_ids.clone(),
+            ))
+            .await?;
+        self.enqueue_alert_check(user_id, transaction_ids).await;
+        Ok(())
+    }
+
+    /// Deletes the ghost transactions. Their provider items stay recorded, so later syncs
+    /// do not import them again.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, count = transaction_ids.len()))]
+    pub async fn reject(&self, user_id: Uuid, transaction_ids: Vec<Uuid>) -> anyhow::Result<()> {
+        let transaction_ids = self.ensure_pending(user_id, transaction_ids).await?;
+        self.transaction_management
+            .delete_transactions(user_id, transaction_ids)
+            .await
+    }
+
+    /// Applies the user's corrections and accepts the transactions in one go. The edits
+    /// mark the provider items as user-edited, so provider amendments no longer apply.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, count = edits.len()))]
+    pub async fn edit_and_accept(
+        &self,
+        user_id: Uuid,
+        edits: Vec<ReviewEditDto>,
+    ) -> anyhow::Result<()> {
+        let transaction_ids = self
+            .ensure_pending(
+                user_id,
+                edits.iter().map(|edit| edit.transaction_id).collect(),
+            )
+            .await?;
+
+        self.db.start_transaction().await?;
+        for edit in edits {
+            self.transaction_management
+                .update_individual_transaction_inner(user_id, edit.transaction_id, edit.transaction)
+                .await?;
+        }
+        self.db
+            .execute(transaction_data_queries::update_transactions_visibility(
+                user_id,
+                transaction_ids.clone(),
+                TransactionVisibilityDto::Default.as_str().to_string(),
+            ))
+            .await?;
+        self.db
+            .execute(connector_queries::delete_connector_duplicate_candidates(
+                transaction_ids.clone(),
+            ))
+            .await?;
+        self.db.commit_transaction().await?;
+        self.enqueue_alert_check(user_id, transaction_ids).await;
+        Ok(())
+    }
+
+    /// Accepted ghosts enter the ledger only now, so this is when they are checked for
+    /// spending alerts. The check is a follow-up and must not fail the review.
+    async fn enqueue_alert_check(&self, user_id: Uuid, transaction_ids: Vec<Uuid>) {
+        if let Err(e) = self.alerts.enqueue_check(user_id, transaction_ids).await {
+            tracing::warn!(
+                error = ?e,
+                error.type = "enqueue_spending_alert_check",
+                "failed to enqueue spending alert check"
+            );
+        }
+    }
+
+    /// Moves each ghost transaction's provider link onto the manual transaction the user
+    /// already entered for it, then deletes the ghost. The manual transaction is kept as
+    /// entered and is from then on treated as user-edited by syncs.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, count = merges.len()))]
+    pub async fn merge(&self, user_id: Uuid, merges: Vec<ReviewMergeDto>) -> anyhow::Result<()> {
+        validate_merges(&merges)?;
+        let ghost_ids = self
+            .ensure_pending(
+                user_id,
+                merges
+                    .iter()
+                    .map(|merge| merge.ghost_transaction_id)
+                    .collect(),
+            )
+            .await?;
+
+        let manual_ids: Vec<Uuid> = merges
+            .iter()
+            .map(|merge| merge.manual_transaction_id)
+            .collect();
+        let manual = self
+            .transaction_management
+            .get_transactions_by_ids(user_id, manual_ids.clone())
+            .await?;
+        if manual.len() != manual_ids.len() {
+            return Err(anyhow::Error::new(BusinessNotFoundError {
+                message: "manual transaction not found".to_string(),
+            }));
+        }
+        if manual
+            .iter()
+            .any(|dto| dto.visibility == TransactionVisibilityDto::Ghost)
+        {
+            return Err(anyhow::Error::new(BusinessBadRequestError {
+                message: "a ghost transaction can only be merged into a reviewed one".to_string(),
+            }));
+        }
+        let linked: Vec<ConnectorTransactionRow> = self
+            .db
+            .fetch_all(connector_queries::get_connector_transactions_by_transaction_ids(manual_ids))
+            .await?;
+        if let Some(row) = linked.first() {
+            return Err(anyhow::Error::new(BusinessConflictError {
+                message: format!(
+                    "a manual transaction is already linked to provider item {}",
+                    row.external_id
+                ),
+            }));
+        }
+
+        self.db.start_transaction().await?;
+        for merge in &merges {
+            self.db
+                .execute(connector_queries::relink_connector_transaction(
+                    merge.ghost_transaction_id,
+                    merge.manual_transaction_id,
+                ))
+                .await?;
+        }
+        self.transaction_management
+            .delete_transactions_inner(user_id, ghost_ids)
+            .await?;
+        self.db.commit_transaction().await?;
+        Ok(())
+    }
+
+    /// Checks every id is a pending ghost transaction of the user.
+    async fn ensure_pending(
+        &self,
+        user_id: Uuid,
+        transaction_ids: Vec<Uuid>,
+    ) -> anyhow::Result<Vec<Uuid>> {
+        let requested: HashSet<Uuid> = transaction_ids.into_iter().collect();
+        if requested.is_empty() {
+            return Err(anyhow::Error::new(BusinessBadRequestError {
+                message: "no transactions given".to_string(),
+            }));
+        }
+        let pending: HashSet<Uuid> = self
+            .db
+            .fetch_all::<PendingReviewTransactionRow>(
+                connector_queries::get_pending_review_transactions(
+                    user_id,
+                    None,
+                    Some(requested.iter().copied().collect()),
+                ),
+            )
+            .await?
+            .into_iter()
+            .map(|row| row.transaction_id)
+            .collect();
+
+        if let Some(missing) = requested.difference(&pending).next() {
+            return Err(anyhow::Error::new(BusinessNotFoundError {
+                message: format!("transaction {missing} is not awaiting review"),
+            }));
+        }
+        Ok(requested.into_iter().collect())
+    }
+}
+
+/// Each ghost folds into one manual transaction and no manual transaction takes two.
+fn validate_merges(merges: &[ReviewMergeDto]) -> anyhow::Result<()> {
+    let mut ghosts = HashSet::new();
+    let mut manual = HashSet::new();
+    for merge in merges {
+        let message = if merge.ghost_transaction_id == merge.manual_transaction_id {
+            "a transaction cannot be merged into itself"
+        } else if !ghosts.insert(merge.ghost_transaction_id) {
+            "a ghost transaction is merged more than once"
+        } else if !manual.insert(merge.manual_transaction_id) {
+            "a manual transaction can absorb only one ghost transaction"
+        } else {
+            continue;
+        };
+        return Err(anyhow::Error::new(BusinessBadRequestError {
+            message: message.to_string(),
+        }));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn merge(ghost: u128, manual: u128) -> ReviewMergeDto {
+        ReviewMergeDto {
+            ghost_transaction_id: Uuid::from_u128(ghost),
+            manual_transaction_id: Uuid::from_u128(manual),
+        }
+    }
+
+    #[test]
+    fn merges_pair_each_ghost_with_its_own_manual_transaction() {
+        assert!(validate_merges(&[merge(1, 10), merge(2, 20)]).is_ok());
+
+        for (merges, expected) in [
+            (
+                vec![merge(1, 1)],
+                "a transaction cannot be merged into itself",
+            ),
+            (
+                vec![merge(1, 10), merge(1, 20)],
+                "a ghost transaction is merged more than once",
+            ),
+            (
+                vec![merge(1, 10), merge(2, 10)],
+                "a manual transaction can absorb only one ghost transaction",
+            ),
+        ] {
+            let err = validate_merges(&merges).unwrap_err();
+            assert!(err.is::<BusinessBadRequestError>());
+            assert_eq!(err.to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn suggestion_prefers_the_similar_transactions_category() {
+        let row = |similar_category_id| ReviewSuggestionRow {
+            transaction_id: Uuid::nil(),
+            similar_transaction_id: Some(Uuid::from_u128(7)),
+            similar_group_id: None,
+            similar_category_id,
+            nearest_category_id: Some(3),
+            nearest_asset_id: Some(45),
+        };
+
+        let dto = ReviewSuggestionDto::from(row(Some(12)));
+        assert_eq!(dto.category_id, Some(12));
+        assert_eq!(dto.asset_id, Some(45));
+        assert_eq!(ReviewSuggestionDto::from(row(None)).category_id, Some(3));
+    }
+}
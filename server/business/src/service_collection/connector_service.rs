@@ -187,7 +187,7 @@ impl ConnectorService {
             })
             .collect();
 
-        let mut transactions = map_pages(kind, &pages);
+        let mut transactions = map_pages(kind, &pages).transactions;
         transactions.sort_by(|a, b| b.date.cmp(&a.date));
 
         Ok(transactions.into_iter().map(Into::into).collect())
@@ -345,6 +345,7 @@ impl ConnectorService {
             .provider_kind()
             .provider()
             .begin_oauth(&store, &state, redirect_uri.as_deref())
+            .await
             .map_err(|e| {
                 anyhow::Error::new(BusinessBadRequestError {
                     message: e.to_string(),
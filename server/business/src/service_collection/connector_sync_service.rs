@@ -2,17 +2,23 @@
 use dal::database_context::MyraDb;
 use dal::job_queue::JobQueueHandle;
 use dal::models::connector_models::{
-    ActiveStoredBindingRow, AddConnectorProviderAccountModel, ConnectorBindingRow,
+    ActiveStoredBindingRow, AddConnectorDuplicateCandidateModel, AddConnectorProviderAccountModel,
+    AddConnectorTransactionModel, ConnectorBindingRow, ConnectorImportIssueRow,
     ConnectorRawPageRow, ConnectorTransactionRow, UpdateProviderAccountSyncResultModel,
+    UpsertConnectorImportIssueModel,
+};
+use dal::models::ticker_alias_models::UserTickerAliasRow;
+use dal::queries::{connector_queries, tag_queries, ticker_alias_queries};
+use dal::query_params::connector_params::{
+    GetConnectorBindingsParams, GetConnectorImportIssuesParams, GetRawPagesParams,
 };
-use dal::queries::connector_queries;
-use dal::query_params::connector_params::{GetConnectorBindingsParams, GetRawPagesParams};
 #[mockall_double::double]
 use dal::redis_connection::RedisConnection;
 use dal::secrets::SecretProvider;
 
 use connectors::client_supplied::{ClientSuppliedConnector, ClientSuppliedStream};
 use connectors::models::sync::RawPage;
+use connectors::models::transaction::{MappedPages, ProviderTransaction, SkippedTransaction};
 use connectors::port::{Connector, ConnectorStore, SyncParams, SyncRunOutcome};
 use connectors::provider::{CredentialSource, ProviderKind};
 
@@ -21,22 +27,32 @@ use crate::dtos::bad_request_error_dto::BusinessBadRequestError;
 use crate::dtos::conflict_error_dto::BusinessConflictError;
 use crate::dtos::connectors::{
     ActiveStoredBinding, BindingStatusDto, ConnectionStatusDto, ConnectorBindingDto,
-    ConnectorConnectionDto, CredentialModeDto, SyncDispatchDto, SyncOutcomeDto, SyncReportDto,
+    ConnectorConnectionDto, ConnectorImportIssueDto, CredentialModeDto, ImportIssueStageDto,
+    ProviderBalanceKindDto, ProviderBalanceLineDto, SyncDispatchDto, SyncOutcomeDto, SyncReportDto,
     TransientSyncCredentialDto,
 };
+use crate::dtos::not_found_error_dto::BusinessNotFoundError;
 use crate::dtos::transaction_dto::TransactionVisibilityDto;
 use crate::jobs::SyncConnectorBindingJob;
 use crate::providers::connector_store::BusinessConnectorStore;
 
 use crate::entities::connectors::connector_transaction_batch::ConnectorTransactionBatch;
+use crate::entities::connectors::provider_balance_history::resolved_balances;
 use crate::entities::connectors::provider_transaction_import::{
-    ProviderTransactionImport, TransactionImportOutcome,
+    instrument_ticker_candidates, resolve_currency, resolve_instrument, ProviderTransactionImport,
+    TransactionImportOutcome,
 };
 use crate::entities::transactions::transaction::Transaction;
 
 use super::asset_service::AssetsService;
+use super::connector_balance_service::ConnectorBalanceService;
+use super::connector_duplicate_service::ConnectorDuplicateService;
 use super::connector_service::ConnectorService;
+use super::reconciliation_service::ReconciliationService;
+use super::spending_alert_service::SpendingAlertService;
 use super::transaction_management_service::TransactionManagementService;
+use super::transaction_rule_service::TransactionRuleService;
+use super::transfer_matching_service::TransferMatchingService;
 use super::ServiceProviders;
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
@@ -60,6 +76,12 @@ pub struct ConnectorSyncService {
     connectors: ConnectorService,
     transaction_management: TransactionManagementService,
     assets: AssetsService,
+    reconciliations: ReconciliationService,
+    duplicates: ConnectorDuplicateService,
+    balances: ConnectorBalanceService,
+    transfers: TransferMatchingService,
+    rules: TransactionRuleService,
+    alerts: SpendingAlertService,
 }
 
 impl ConnectorSyncService {
@@ -72,6 +94,12 @@ impl ConnectorSyncService {
             connectors: ConnectorService::new(providers),
             transaction_management: TransactionManagementService::new(providers),
             assets: AssetsService::new(providers),
+            reconciliations: ReconciliationService::new(providers),
+            duplicates: ConnectorDuplicateService::new(providers),
+            balances: ConnectorBalanceService::new(providers),
+            transfers: TransferMatchingService::new(providers),
+            rules: TransactionRuleService::new(providers),
+            alerts: SpendingAlertService::new(providers),
         }
     }
 
@@ -274,7 +302,10 @@ impl ConnectorSyncService {
             FetchPhaseOutcome::Failed(e) => Some(e),
         };
 
-        let report = match self.reconcile_and_commit(user_id, binding, connector).await {
+        let report = match self
+            .reconcile_and_commit(user_id, binding, &|pages| connector.map_pages(pages))
+            .await
+        {
             Ok(report) => report,
             Err(e) => {
                 let _ = self.db.rollback_transaction().await;
@@ -291,11 +322,100 @@ impl ConnectorSyncService {
                 pages_projected = report.pages_projected,
                 "fetch failed; projected existing archive instead"
             );
+        } else {
+            self.record_provider_balance(user_id, binding, connector)
+                .await;
         }
 
         Ok(SyncOutcomeDto::Complete { report })
     }
 
+    /// Stores the balance the provider reports as a snapshot and records it as
+    /// reconciliation checkpoints on the bound account. A balance that cannot be fetched or
+    /// resolved does not fail the sync.
+    async fn record_provider_balance(
+        &self,
+        user_id: Uuid,
+        binding: &ConnectorBindingDto,
+        connector: &dyn Connector,
+    ) {
+        if let Err(e) = self
+            .try_record_provider_balance(user_id, binding, connector)
+            .await
+        {
+            tracing::warn!(
+                binding_id = %binding.id,
+                error = %e,
+                "provider balance not recorded"
+            );
+        }
+    }
+
+    async fn try_record_provider_balance(
+        &self,
+        user_id: Uuid,
+        binding: &ConnectorBindingDto,
+        connector: &dyn Connector,
+    ) -> anyhow::Result<()> {
+        let balance = connector.fetch_balance().await?;
+        let fetched_at = OffsetDateTime::now_utc();
+
+        let mut needed_tickers: HashSet<String> = HashSet::new();
+        needed_tickers.extend(balance.cash.iter().map(|cash| cash.currency.clone()));
+        for holding in &balance.quantities {
+            needed_tickers.extend(instrument_ticker_candidates(&holding.asset_identifier));
+        }
+        if needed_tickers.is_empty() {
+            return Ok(());
+        }
+        let resolved = self.assets.resolve_tickers(user_id, needed_tickers).await?;
+        let aliases = self.get_ticker_alias_map(user_id).await?;
+
+        let mut lines = Vec::new();
+        for cash in balance.cash {
+            let asset_id = resolve_currency(&cash.currency, &aliases, &resolved);
+            if asset_id.is_none() {
+                tracing::debug!(currency = %cash.currency, "balance currency has no matching asset")
+            }
+            lines.push(ProviderBalanceLineDto {
+                kind: ProviderBalanceKindDto::Cash,
+                asset_identifier: cash.currency,
+                asset_id,
+                quantity: cash.amount,
+            });
+        }
+        for holding in balance.quantities {
+            let asset_id = resolve_instrument(&holding.asset_identifier, &aliases, &resolved);
+            if asset_id.is_none() {
+                tracing::debug!(
+                    asset_identifier = %holding.asset_identifier,
+                    "balance holding has no matching asset"
+                )
+            }
+            lines.push(ProviderBalanceLineDto {
+                kind: ProviderBalanceKindDto::Holding,
+                asset_identifier: holding.asset_identifier,
+                asset_id,
+                quantity: holding.quantity,
+            });
+        }
+
+        let balances = resolved_balances(&lines);
+        self.balances
+            .record_snapshot(binding.provider_account_ref, fetched_at, lines)
+            .await?;
+
+        self.reconciliations
+            .record_provider_balances(
+                user_id,
+                binding.sverto_account_id,
+                binding.id,
+                balances,
+                fetched_at,
+            )
+            .await
+    }
+
     async fn run_fetch_phase(
         &self,
         provider_account_ref: Uuid,
@@ -536,12 +656,152 @@ impl ConnectorSyncService {
         Ok(results.into_iter().map(Into::into).collect())
     }
 
+    /// The user's ticker aliases, provider ticker to asset id.
+    async fn get_ticker_alias_map(&self, user_id: Uuid) -> anyhow::Result<HashMap<String, i32>> {
+        let query = ticker_alias_queries::get_user_ticker_aliases(user_id);
+        Ok(self
+            .db
+            .fetch_all::<UserTickerAliasRow>(query)
+            .await?
+            .into_iter()
+            .map(|row| (row.provider_ticker, row.asset_id))
+            .collect())
+    }
+
+    /// Open items of the binding's archive that projection could not import.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, binding_id = %binding_id))]
+    pub async fn get_import_issues(
+        &self,
+        user_id: Uuid,
+        binding_id: Uuid,
+    ) -> anyhow::Result<Vec<ConnectorImportIssueDto>> {
+        self.connectors.get_binding(user_id, binding_id).await?;
+
+        let query = connector_queries::get_connector_import_issues(
+            GetConnectorImportIssuesParams::by_binding(user_id, binding_id),
+        );
+        self.db
+            .fetch_all::<ConnectorImportIssueRow>(query)
+            .await?
+            .into_iter()
+            .map(ConnectorImportIssueDto::try_from)
+            .collect()
+    }
+
+    /// Hides an issue the user does not intend to resolve. A dismissed issue stays hidden
+    /// on later projections of the same item.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, binding_id = %binding_id, issue_id = %issue_id))]
+    pub async fn dismiss_import_issue(
+        &self,
+        user_id: Uuid,
+        binding_id: Uuid,
+        issue_id: Uuid,
+    ) -> anyhow::Result<()> {
+        let query = connector_queries::get_connector_import_issues(
+            GetConnectorImportIssuesParams::by_id(user_id, issue_id),
+        );
+        let issue = self
+            .db
+            .fetch_optional::<ConnectorImportIssueRow>(query)
+            .await?
+            .filter(|issue| issue.binding_id == binding_id);
+        if issue.is_none() {
+            return Err(anyhow::Error::new(BusinessNotFoundError {
+                message: format!("import issue {issue_id} not found"),
+            }));
+        }
+
+        self.db
+            .execute(connector_queries::dismiss_connector_import_issue(
+                user_id, issue_id,
+            ))
+            .await?;
+        Ok(())
+    }
+
+    /// Projects the binding's archive again from the earliest page holding an unresolved
+    /// transaction, without fetching from the provider. Transactions imported earlier come
+    /// out unchanged, so only the ones that now resolve are added.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, binding_id = %binding_id))]
+    pub async fn reproject_binding(
+        &self,
+        user_id: Uuid,
+        binding_id: Uuid,
+    ) -> anyhow::Result<SyncReportDto> {
+        let (binding, connection) = self.load_active_binding(user_id, binding_id).await?;
+        let provider_kind: ProviderKind = connection.provider_kind.parse().map_err(|_| {
+            anyhow::Error::new(BusinessBadRequestError {
+                message: format!(
+                    "provider {} cannot be re-projected",
+                    connection.provider_kind
+                ),
+            })
+        })?;
+
+        let earliest_page_id = self
+            .get_import_issues(user_id, binding_id)
+            .await?
+            .into_iter()
+            .filter(|issue| issue.stage == ImportIssueStageDto::Unresolved)
+            .map(|issue| issue.page_id)
+            .min();
+        let Some(earliest_page_id) = earliest_page_id else {
+            return Ok(SyncReportDto::default());
+        };
+
+        self.claim_provider_account(binding.provider_account_ref)
+            .await?;
+        let result = self
+            .rewind_and_reproject(user_id, &binding, provider_kind, earliest_page_id)
+            .await;
+        self.db
+            .execute(connector_queries::release_provider_account_fetch_claim(
+                binding.provider_account_ref,
+            ))
+            .await?;
+        result
+    }
+
+    async fn rewind_and_reproject(
+        &self,
+        user_id: Uuid,
+        binding: &ConnectorBindingDto,
+        provider_kind: ProviderKind,
+        from_page_id: Uuid,
+    ) -> anyhow::Result<SyncReportDto> {
+        let checkpoint: Option<Uuid> = self
+            .db
+            .fetch_one_scalar(connector_queries::get_previous_raw_page_id(
+                binding.provider_account_ref,
+                from_page_id,
+            ))
+            .await?;
+        self.db
+            .execute(connector_queries::update_binding_projection(
+                binding.id, checkpoint,
+            ))
+            .await?;
+
+        match self
+            .reconcile_and_commit(user_id, binding, &|pages| {
+                connectors::provider::map_pages(provider_kind, pages)
+            })
+            .await
+        {
+            Ok(report) => Ok(report),
+            Err(e) => {
+                let _ = self.db.rollback_transaction().await;
+                Err(e)
+            }
+        }
+    }
+
     #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, binding_id = %binding.id))]
     async fn reconcile_and_commit(
         &self,
         user_id: Uuid,
         binding: &ConnectorBindingDto,
-        connector: &dyn Connector,
+        map_pages: &(dyn Fn(&[RawPage]) -> MappedPages + Sync),
     ) -> anyhow::Result<SyncReportDto> {
         let binding_id = binding.id;
         let provider_account_ref = binding.provider_account_ref;
@@ -562,15 +822,28 @@ impl ConnectorSyncService {
             .await?;
         let max_page_id = raw_pages.last().map(|page| page.id);
 
-        let pages: Vec<RawPage> = raw_pages
-            .iter()
-            .map(|page| RawPage {
+        // Pages are mapped one at a time so every issue can point at the page to re-project.
+        let mut mapped_transactions: Vec<ProviderTransaction> = Vec::new();
+        let mut page_ids: HashMap<String, Uuid> = HashMap::new();
+        let mut issues: Vec<UpsertConnectorImportIssueModel> = Vec::new();
+        for page in &raw_pages {
+            let mapped = map_pages(&[RawPage {
                 stream: page.stream.clone(),
                 payload: page.payload.0.clone(),
-            })
-            .collect();
-        let map_pages = connector.map_pages(&pages);
-        let batch = ConnectorTransactionBatch::from_mapped(map_pages);
+            }]);
+            for tx in &mapped.transactions {
+                page_ids.insert(tx.external_id.clone(), page.id);
+            }
+            mapped_transactions.extend(mapped.transactions);
+            issues.extend(
+                mapped
+                    .skipped
+                    .into_iter()
+                    .map(|skipped| skipped_issue(binding_id, page.id, skipped)),
+            );
+        }
+        let skipped = issues.len();
+        let batch = ConnectorTransactionBatch::from_mapped(mapped_transactions);
 
         let existing_rows: HashMap<String, ConnectorTransactionRow> = if batch.is_empty() {
             HashMap::new()
@@ -592,16 +865,44 @@ impl ConnectorSyncService {
             "trusted" => TransactionVisibilityDto::Default,
             _ => TransactionVisibilityDto::Ghost,
         };
+        let rules = self.rules.rule_set(user_id).await?;
+
+        let classified = batch.classify(&existing_rows);
+        let imports: Vec<ProviderTransactionImport> = classified
+            .new
+            .into_iter()
+            .map(|tx| {
+                ProviderTransactionImport::new(
+                    tx.clone(),
+                    binding_row.sverto_account_id,
+                    visibility,
+                    binding_id,
+                )
+            })
+            .collect();
+
+        // Embedding requests go out before the transaction opens so a slow provider does
+        // not hold its locks.
+        let description_embeddings = self
+            .duplicates
+            .embed_descriptions(
+                imports
+                    .iter()
+                    .filter(|import| import.is_cash_movement())
+                    .map(|import| import.transaction().description.clone())
+                    .collect(),
+            )
+            .await;
 
         self.db.start_transaction().await?;
 
         let mut report = SyncReportDto {
+            skipped,
             duplicates: batch.duplicates(),
             pages_projected: raw_pages.len(),
             ..SyncReportDto::default()
         };
 
-        let classified = batch.classify(&existing_rows);
         report.unchanged = classified.unchanged;
         report.conflicts = classified.conflicts.len();
         report.amended = classified.amended.len();
@@ -622,37 +923,76 @@ impl ConnectorSyncService {
             );
         }
 
-        let imports: Vec<ProviderTransactionImport> = classified
-            .new
-            .into_iter()
-            .map(|tx| {
-                ProviderTransactionImport::new(
-                    tx.clone(),
-                    binding_row.sverto_account_id,
-                    visibility,
-                    binding_id,
-                )
-            })
-            .collect();
-
         let mut needed_tickers: HashSet<String> = HashSet::new();
         for import in &imports {
             needed_tickers.insert(import.currency().to_string());
             needed_tickers.extend(import.instrument_ticker_candidates());
         }
         let resolved = self.assets.resolve_tickers(user_id, needed_tickers).await?;
+        let aliases = self.get_ticker_alias_map(user_id).await?;
+
+        let resolved_imports: Vec<(ProviderTransactionImport, Option<i32>, Option<i32>)> = imports
+            .into_iter()
+            .map(|import| {
+                let cash_asset_id = resolve_currency(import.currency(), &aliases, &resolved);
+                let instrument_asset_id = import
+                    .transaction()
+                    .asset_identifier
+                    .as_deref()
+                    .and_then(|identifier| resolve_instrument(identifier, &aliases, &resolved));
+                (import, cash_asset_id, instrument_asset_id)
+            })
+            .collect();
+
+        // Purchases the user already typed in are linked instead of imported a second time;
+        // the ones that only might be are held back for review next to their candidates.
+        let duplicates = self
+            .duplicates
+            .match_imports(
+                user_id,
+                resolved_imports
+                    .iter()
+                    .filter_map(|(import, cash_asset_id, _)| {
+                        import.cash_movement((*cash_asset_id)?)
+                    })
+                    .collect(),
+                &description_embeddings,
+            )
+            .await?;
 
         let mut entities: Vec<Transaction> = Vec::new();
-        for import in imports {
-            let cash_asset_id = resolved.get(import.currency()).copied();
-            let instrument_asset_id = import
-                .instrument_ticker_candidates()
-                .iter()
-                .find_map(|candidate| resolved.get(candidate).copied());
+        let mut rule_tags: Vec<Vec<Uuid>> = Vec::new();
+        let mut linked: Vec<AddConnectorTransactionModel> = Vec::new();
+        let mut unresolved_ids: HashSet<String> = HashSet::new();
+        for (mut import, cash_asset_id, instrument_asset_id) in resolved_imports {
+            if let Some(transaction_id) = duplicates.linked.get(import.external_id()) {
+                linked.push(AddConnectorTransactionModel {
+                    binding_id,
+                    transaction_id: Some(*transaction_id),
+                    external_id: import.external_id().to_string(),
+                    external_hash: import.external_hash(),
+                    counterparty: import.transaction().counterparty.clone(),
+                });
+                continue;
+            }
+            if duplicates.ambiguous.contains_key(import.external_id())
+                || import.is_unpaired_withdrawal()
+            {
+                import.hold_for_review();
+            }
 
-            match import.try_into_transaction(user_id, cash_asset_id, instrument_asset_id)? {
-                TransactionImportOutcome::Ready(entity) => {
+            match import.try_into_transaction(
+                user_id,
+                cash_asset_id,
+                instrument_asset_id,
+                &rules,
+            )? {
+                TransactionImportOutcome::Ready(entity, applied) => {
+                    if applied.is_some() {
+                        report.rules_applied += 1;
+                    }
                     entities.push(entity);
+                    rule_tags.push(applied.map(|applied| applied.tag_ids).unwrap_or_default());
                 }
                 TransactionImportOutcome::Unresolvable(reason) => {
                     report.unresolved += 1;
@@ -662,10 +1002,37 @@ impl ConnectorSyncService {
                         reason,
                         "provider transaction not imported — unresolvable"
                     );
+                    if let Some(page_id) = page_ids.get(import.external_id()) {
+                        issues.push(unresolved_issue(
+                            binding_id,
+                            *page_id,
+                            import.transaction(),
+                            reason,
+                        ));
+                    }
+                    unresolved_ids.insert(import.external_id().to_string());
                 }
             }
         }
 
+        if !linked.is_empty() {
+            let transaction_ids: Vec<Uuid> = linked
+                .iter()
+                .filter_map(|link| link.transaction_id)
+                .collect();
+            report.linked_existing = linked.len();
+            self.db
+                .execute(connector_queries::insert_connector_transactions(linked))
+                .await?;
+            // The manual entry is what the user wants to keep, so provider amendments
+            // must not overwrite it.
+            self.db
+                .execute(connector_queries::mark_connector_transactions_edited(
+                    transaction_ids,
+                ))
+                .await?;
+        }
+
         if !entities.is_empty() {
             self.transaction_management
                 .add_transactions(&mut entities)
@@ -673,6 +1040,65 @@ impl ConnectorSyncService {
             report.new_transactions = entities.len();
         }
 
+        for (entity, tag_ids) in entities.iter().zip(rule_tags) {
+            let Some(transaction_id) = entity.get_transaction_id() else {
+                continue;
+            };
+            if !tag_ids.is_empty() {
+                self.db
+                    .execute(tag_queries::add_transaction_tags(transaction_id, tag_ids))
+                    .await?;
+            }
+        }
+
+        let mut candidates: Vec<AddConnectorDuplicateCandidateModel> = Vec::new();
+        for entity in &entities {
+            let (Some(link), Some(transaction_id)) =
+                (entity.connector_link(), entity.get_transaction_id())
+            else {
+                continue;
+            };
+            let Some(matches) = duplicates.ambiguous.get(&link.external_id) else {
+                continue;
+            };
+            report.possible_duplicates += 1;
+            candidates.extend(matches.iter().map(|(candidate_transaction_id, score)| {
+                AddConnectorDuplicateCandidateModel {
+                    transaction_id,
+                    candidate_transaction_id: *candidate_transaction_id,
+                    score: *score,
+                }
+            }));
+        }
+        self.duplicates.record_candidates(candidates).await?;
+
+        // Anything the batch carries that did not come out unresolved is now in the ledger
+        // (or was already), so earlier issues about it are settled.
+        let settled: Vec<String> = page_ids
+            .into_keys()
+            .filter(|external_id| !unresolved_ids.contains(external_id))
+            .collect();
+        if !settled.is_empty() {
+            self.db
+                .execute(
+                    connector_queries::delete_connector_import_issues_by_external_ids(
+                        binding_id, settled,
+                    ),
+                )
+                .await?;
+        }
+        if !issues.is_empty() {
+            let issues: Vec<UpsertConnectorImportIssueModel> = issues
+                .into_iter()
+                .map(|issue| (issue.external_id.clone(), issue))
+                .collect::<HashMap<_, _>>()
+                .into_values()
+                .collect();
+            self.db
+                .execute(connector_queries::upsert_connector_import_issues(issues))
+                .await?;
+        }
+
         // Projection checkpoint advanced on the binding (the fetch outcome is recorded on the
         // provider account by the caller's fetch phase).
         self.db
@@ -684,6 +1110,35 @@ impl ConnectorSyncService {
 
         self.db.commit_transaction().await?;
 
+        // Transfers are paired after the import is committed, so a failure here leaves the
+        // legs as they were for the user to confirm later.
+        if visibility == TransactionVisibilityDto::Default && !entities.is_empty() {
+            let transaction_ids: Vec<Uuid> = entities
+                .iter()
+                .filter_map(|entity| entity.get_transaction_id())
+                .collect();
+            match self
+                .transfers
+                .pair_imported(user_id, transaction_ids.clone())
+                .await
+            {
+                Ok(matched) => report.transfers_matched = matched,
+                Err(e) => tracing::warn!(
+                    binding_id = %binding_id,
+                    error = %e,
+                    "failed to pair imported transfers"
+                ),
+            }
+            // Checked after pairing, so the legs of a transfer are not taken for spending.
+            if let Err(e) = self.alerts.enqueue_check(user_id, transaction_ids).await {
+                tracing::warn!(
+                    binding_id = %binding_id,
+                    error = %e,
+                    "failed to enqueue spending alert check"
+                );
+            }
+        }
+
         tracing::info!(
             binding_id = %binding_id,
             pages_projected = report.pages_projected,
@@ -692,9 +1147,54 @@ impl ConnectorSyncService {
             amended = report.amended,
             conflicts = report.conflicts,
             unresolved = report.unresolved,
+            skipped = report.skipped,
             duplicates = report.duplicates,
+            linked_existing = report.linked_existing,
+            possible_duplicates = report.possible_duplicates,
+            transfers_matched = report.transfers_matched,
             "projection committed"
         );
         Ok(report)
     }
 }
+
+fn skipped_issue(
+    binding_id: Uuid,
+    page_id: Uuid,
+    skipped: SkippedTransaction,
+) -> UpsertConnectorImportIssueModel {
+    UpsertConnectorImportIssueModel {
+        binding_id,
+        page_id,
+        external_id: skipped.external_id,
+        stage: ImportIssueStageDto::Skipped.as_str().to_string(),
+        reason: skipped.reason,
+        date_transacted: None,
+        amount: None,
+        currency: None,
+        description: None,
+        asset_identifier: None,
+        quantity: None,
+    }
+}
+
+fn unresolved_issue(
+    binding_id: Uuid,
+    page_id: Uuid,
+    tx: &ProviderTransaction,
+    reason: &str,
+) -> UpsertConnectorImportIssueModel {
+    UpsertConnectorImportIssueModel {
+        binding_id,
+        page_id,
+        external_id: tx.external_id.clone(),
+        stage: ImportIssueStageDto::Unresolved.as_str().to_string(),
+        reason: reason.to_string(),
+        date_transacted: Some(tx.date),
+        amount: Some(tx.amount),
+        currency: Some(tx.currency.clone()),
+        description: Some(tx.description.clone()),
+        asset_identifier: tx.asset_identifier.clone(),
+        quantity: tx.quantity,
+    }
+}
@@ -0,0 +1,198 @@
+use std::collections::HashSet;
+
+#[mockall_double::double]
+use dal::database_context::MyraDb;
+use dal::models::corporate_action_models::CorporateActionRow;
+use dal::models::portfolio_models::Holding;
+use dal::queries::{corporate_action_queries, entries_queries};
+use dal::query_params::get_corporate_actions_params::GetCorporateActionsParams;
+use itertools::Itertools;
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use crate::dtos::bad_request_error_dto::BusinessBadRequestError;
+use crate::dtos::corporate_action_dto::{
+    AddCorporateActionDto, CorporateActionDto, CorporateActionTypeDto,
+};
+use crate::dtos::not_found_error_dto::BusinessNotFoundError;
+use crate::dtos::portfolio::holding::HoldingDto;
+
+use super::asset_service::AssetsService;
+
+pub struct CorporateActionService {
+    db: MyraDb,
+    assets_service: AssetsService,
+}
+
+impl CorporateActionService {
+    pub fn new(providers: &super::ServiceProviders) -> Self {
+        Self {
+            db: providers.db.clone(),
+            assets_service: AssetsService::new(providers),
+        }
+    }
+
+    /// Every corporate action the user recorded, oldest effective date first.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id))]
+    pub async fn get_corporate_actions(
+        &self,
+        user_id: Uuid,
+    ) -> anyhow::Result<Vec<CorporateActionDto>> {
+        let query = corporate_action_queries::get_corporate_actions(
+            GetCorporateActionsParams::all(user_id),
+        );
+        self.db
+            .fetch_all::<CorporateActionRow>(query)
+            .await?
+            .into_iter()
+            .map(CorporateActionDto::try_from)
+            .collect()
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, corporate_action_id = %corporate_action_id))]
+    pub async fn get_corporate_action(
+        &self,
+        user_id: Uuid,
+        corporate_action_id: Uuid,
+    ) -> anyhow::Result<CorporateActionDto> {
+        let query = corporate_action_queries::get_corporate_actions(
+            GetCorporateActionsParams::by_id(user_id, corporate_action_id),
+        );
+        self.db
+            .fetch_optional::<CorporateActionRow>(query)
+            .await?
+            .ok_or_else(|| {
+                anyhow::Error::new(BusinessNotFoundError {
+                    message: format!("corporate action {corporate_action_id} not found"),
+                })
+            })?
+            .try_into()
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, asset_id = action.asset_id))]
+    pub async fn create_corporate_action(
+        &self,
+        user_id: Uuid,
+        action: AddCorporateActionDto,
+    ) -> anyhow::Result<CorporateActionDto> {
+        validate_corporate_action(&action)
+            .map_err(|message| anyhow::Error::new(BusinessBadRequestError { message }))?;
+
+        let asset_ids: HashSet<i32> = [Some(action.asset_id), action.new_asset_id]
+            .into_iter()
+            .flatten()
+            .collect();
+        let existing: HashSet<i32> = self
+            .assets_service
+            .get_assets(asset_ids.clone())
+            .await?
+            .into_iter()
+            .map(|asset| asset.id.0)
+            .collect();
+        if let Some(missing) = asset_ids.difference(&existing).sorted().next() {
+            return Err(anyhow::Error::new(BusinessBadRequestError {
+                message: format!("asset {missing} does not exist"),
+            }));
+        }
+
+        let query =
+            corporate_action_queries::insert_corporate_action(action.into_add_model(user_id));
+        self.db
+            .fetch_one::<CorporateActionRow>(query)
+            .await?
+            .try_into()
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, corporate_action_id = %corporate_action_id))]
+    pub async fn delete_corporate_action(
+        &self,
+        user_id: Uuid,
+        corporate_action_id: Uuid,
+    ) -> anyhow::Result<()> {
+        self.get_corporate_action(user_id, corporate_action_id)
+            .await?;
+
+        let query = corporate_action_queries::delete_corporate_action(corporate_action_id, user_id);
+        self.db.execute(query).await?;
+        Ok(())
+    }
+
+    /// Pairs each action with the ledger holdings of its asset from before its effective
+    /// date, as [`crate::entities::corporate_actions::adjust_holdings`] expects.
+    pub async fn get_holdings_before_actions(
+        &self,
+        user_id: Uuid,
+        apply_ownership_share: bool,
+        actions: Vec<CorporateActionDto>,
+    ) -> anyhow::Result<Vec<(CorporateActionDto, Vec<HoldingDto>)>> {
+        let mut with_holdings = Vec::with_capacity(actions.len());
+        for action in actions {
+            let query = entries_queries::get_asset_holdings_before(
+                user_id,
+                apply_ownership_share,
+                action.asset_id,
+                action.effective_date,
+            );
+            let holdings: Vec<HoldingDto> = self
+                .db
+                .fetch_all::<Holding>(query)
+                .await?
+                .into_iter()
+                .map_into()
+                .collect();
+            with_holdings.push((action, holdings));
+        }
+        Ok(with_holdings)
+    }
+}
+
+fn validate_corporate_action(action: &AddCorporateActionDto) -> Result<(), String> {
+    if action.ratio_from <= Decimal::ZERO || action.ratio_to <= Decimal::ZERO {
+        return Err("ratio_from and ratio_to must be positive".to_string());
+    }
+
+    match action.action_type {
+        CorporateActionTypeDto::Split if action.ratio_to <= action.ratio_from => {
+            return Err("a split must increase the number of units".to_string());
+        }
+        CorporateActionTypeDto::ReverseSplit if action.ratio_to >= action.ratio_from => {
+            return Err("a reverse split must decrease the number of units".to_string());
+        }
+        _ => {}
+    }
+
+    match (action.action_type.has_new_asset(), action.new_asset_id) {
+        (true, None) => {
+            return Err(format!(
+                "{} requires new_asset_id",
+                action.action_type.as_str()
+            ));
+        }
+        (true, Some(new_asset_id)) if new_asset_id == action.asset_id => {
+            return Err("new_asset_id must differ from asset_id".to_string());
+        }
+        (false, Some(_)) => {
+            return Err(format!(
+                "{} does not take new_asset_id",
+                action.action_type.as_str()
+            ));
+        }
+        _ => {}
+    }
+
+    match (action.action_type, action.cost_basis_fraction) {
+        (CorporateActionTypeDto::SpinOff, None) => {
+            Err("spin_off requires cost_basis_fraction".to_string())
+        }
+        (CorporateActionTypeDto::SpinOff, Some(fraction))
+            if fraction <= Decimal::ZERO || fraction >= Decimal::ONE =>
+        {
+            Err("cost_basis_fraction must be between 0 and 1".to_string())
+        }
+        (CorporateActionTypeDto::SpinOff, Some(_)) | (_, None) => Ok(()),
+        (_, Some(_)) => Err(format!(
+            "{} does not take cost_basis_fraction",
+            action.action_type.as_str()
+        )),
+    }
+}
@@ -0,0 +1,310 @@
+//! Weekly and monthly insight digests. Once a period is over the worker builds one
+//! per user from the same data the chat tools read (`AiDataService`) and, for users
+//! who opted in, has Myra narrate it when their AI budget allows; a digest without a
+//! narrative is still stored.
+
+use std::sync::Arc;
+
+use ai::jobs::insights::DigestNarrative;
+use ai::models::error::AiError;
+use ai::models::insights::InsightDigest;
+use ai::models::wealth::CurrencyRef;
+#[mockall_double::double]
+use dal::database_context::MyraDb;
+use dal::models::insight_digest_models::{
+    AddInsightDigestModel, InsightDigestCandidateRow, InsightDigestRow, InsightDigestSettingsRow,
+};
+use dal::queries::insight_digest_queries;
+use dal::query_params::get_insight_digests_params::{
+    GetInsightDigestCandidatesParams, GetInsightDigestsParams,
+};
+use rust_decimal::Decimal;
+use time::{Date, Duration, OffsetDateTime, Time};
+use uuid::Uuid;
+
+use crate::dtos::insight_digest_dto::{
+    InsightDigestCandidateDto, InsightDigestDto, InsightDigestSettingsDto, InsightPeriodDto,
+};
+use crate::dtos::not_found_error_dto::BusinessNotFoundError;
+use crate::entities::insights::{
+    following_period_start, last_completed_period, previous_period_start, spending_summary,
+    top_movers,
+};
+use crate::providers::user_rate_limiter::UserRateLimiter;
+use crate::rate_limiting::rate_limiter::RateLimiter;
+
+use super::ai_data_service::AiDataService;
+
+/// Categories listed in the spending comparison.
+const SPENDING_CATEGORIES: usize = 8;
+
+/// Category groups fetched per period; more than any user has in practice.
+const CATEGORY_GROUP_LIMIT: i64 = 500;
+
+const UNUSUAL_TRANSACTIONS: i64 = 5;
+
+const PORTFOLIO_MOVERS: usize = 5;
+
+pub struct InsightDigestService {
+    db: MyraDb,
+    ai_data: AiDataService,
+    rate_limiter: RateLimiter,
+}
+
+impl InsightDigestService {
+    pub fn new(providers: &super::ServiceProviders) -> Self {
+        Self {
+            db: providers.db.clone(),
+            ai_data: AiDataService::new(providers),
+            rate_limiter: RateLimiter::new(providers.redis.clone(), providers.db.clone()),
+        }
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id))]
+    pub async fn get_digests(
+        &self,
+        user_id: Uuid,
+        period: Option<InsightPeriodDto>,
+        start: u64,
+        count: u64,
+    ) -> anyhow::Result<Vec<InsightDigestDto>> {
+        let query = insight_digest_queries::get_insight_digests(GetInsightDigestsParams::all(
+            user_id,
+            period.map(|p| p.as_str().to_string()),
+            start,
+            count,
+        ));
+        let rows = self.db.fetch_all::<InsightDigestRow>(query).await?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id))]
+    pub async fn get_digest(
+        &self,
+        user_id: Uuid,
+        digest_id: Uuid,
+    ) -> anyhow::Result<InsightDigestDto> {
+        let query = insight_digest_queries::get_insight_digests(GetInsightDigestsParams::by_id(
+            user_id, digest_id,
+        ));
+        let row = self
+            .db
+            .fetch_optional::<InsightDigestRow>(query)
+            .await?
+            .ok_or_else(|| digest_not_found(digest_id))?;
+        Ok(row.into())
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id))]
+    pub async fn mark_read(&self, user_id: Uuid, digest_id: Uuid) -> anyhow::Result<()> {
+        self.get_digest(user_id, digest_id).await?;
+
+        let query = insight_digest_queries::mark_insight_digest_read(user_id, digest_id);
+        self.db.execute(query).await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id))]
+    pub async fn get_settings(&self, user_id: Uuid) -> anyhow::Result<InsightDigestSettingsDto> {
+        let query = insight_digest_queries::get_insight_digest_settings(user_id);
+        let row = self
+            .db
+            .fetch_optional::<InsightDigestSettingsRow>(query)
+            .await?;
+        Ok(row.map(Into::into).unwrap_or_default())
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id))]
+    pub async fn update_settings(
+        &self,
+        user_id: Uuid,
+        settings: InsightDigestSettingsDto,
+    ) -> anyhow::Result<()> {
+        let query =
+            insight_digest_queries::update_insight_digest_settings(user_id, settings.narrate);
+        self.db.execute(query).await?;
+        Ok(())
+    }
+
+    /// Users still without a digest for the last `period` completed by `today`, a page
+    /// of `limit` of them after the user `after`.
+    #[tracing::instrument(level = "debug", skip_all, fields(period = period.as_str()))]
+    pub async fn get_candidates(
+        &self,
+        period: InsightPeriodDto,
+        today: Date,
+        after: Option<Uuid>,
+        limit: u64,
+    ) -> anyhow::Result<Vec<InsightDigestCandidateDto>> {
+        let (start, end) = last_completed_period(period, today);
+        let query = insight_digest_queries::get_insight_digest_candidates(
+            GetInsightDigestCandidatesParams {
+                period: period.as_str().to_string(),
+                period_start: midnight(start),
+                period_end: midnight(end),
+                after,
+                limit,
+            },
+        );
+        let rows = self
+            .db
+            .fetch_all::<InsightDigestCandidateRow>(query)
+            .await?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    /// Builds and stores the candidate's digest for the last `period` completed by
+    /// `today`, narrated when the user opted in. `None` when the user already had one.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %candidate.user_id, period = period.as_str()))]
+    pub async fn generate_digest(
+        &self,
+        candidate: &InsightDigestCandidateDto,
+        period: InsightPeriodDto,
+        today: Date,
+    ) -> anyhow::Result<Option<InsightDigestDto>> {
+        let (start, end) = last_completed_period(period, today);
+        let digest = self.build_digest(candidate, period, start, end).await?;
+        // The digest only goes to the AI provider once the user asked for narratives.
+        let narrative = if candidate.narrate {
+            self.narrate(candidate.user_id, &digest).await
+        } else {
+            None
+        };
+
+        let query = insight_digest_queries::insert_insight_digest(AddInsightDigestModel {
+            user_id: candidate.user_id,
+            period: period.as_str().to_string(),
+            period_start: midnight(start),
+            period_end: midnight(end),
+            reference_asset_id: candidate.reference_asset_id,
+            content: serde_json::to_value(&digest)?,
+            model: narrative.as_ref().map(|n| n.model.clone()),
+            narrative: narrative.map(|n| n.text),
+        });
+        let row = self.db.fetch_optional::<InsightDigestRow>(query).await?;
+        Ok(row.map(Into::into))
+    }
+
+    async fn build_digest(
+        &self,
+        candidate: &InsightDigestCandidateDto,
+        period: InsightPeriodDto,
+        start: Date,
+        end: Date,
+    ) -> anyhow::Result<InsightDigest> {
+        let user_id = candidate.user_id;
+        let reference_asset_id = candidate.reference_asset_id;
+        let from = midnight(start);
+        let to = midnight(end);
+        let previous_from = midnight(previous_period_start(period, start));
+        let upcoming_to = midnight(following_period_start(period, end));
+
+        let current = self.spending_by_category(candidate, from, to).await?;
+        let previous = self
+            .spending_by_category(candidate, previous_from, from)
+            .await?;
+        let unusual_transactions = self
+            .ai_data
+            .get_unusual_transactions(user_id, from, to, reference_asset_id, UNUSUAL_TRANSACTIONS)
+            .await?;
+        let net_worth = self
+            .ai_data
+            .get_net_worth_change(user_id, from, to, reference_asset_id)
+            .await?;
+        let movers = self
+            .ai_data
+            .get_portfolio_movers(user_id, from, to, reference_asset_id)
+            .await?;
+        let upcoming_charges = self
+            .ai_data
+            .get_upcoming_charges(user_id, to, upcoming_to)
+            .await?;
+
+        Ok(InsightDigest {
+            period: period.as_str().to_string(),
+            period_start: start.to_string(),
+            period_end: (end - Duration::days(1)).to_string(),
+            currency: CurrencyRef {
+                asset_id: reference_asset_id,
+                code: current.0,
+            },
+            spending: spending_summary(&current.1, &previous.1, SPENDING_CATEGORIES),
+            unusual_transactions,
+            net_worth,
+            top_movers: top_movers(movers, PORTFOLIO_MOVERS),
+            upcoming_charges,
+        })
+    }
+
+    /// Net amount per category in `from..to` in the reference asset, with its ticker.
+    async fn spending_by_category(
+        &self,
+        candidate: &InsightDigestCandidateDto,
+        from: OffsetDateTime,
+        to: OffsetDateTime,
+    ) -> anyhow::Result<(String, Vec<(String, Decimal)>)> {
+        let result = self
+            .ai_data
+            .aggregate_transactions(
+                candidate.user_id,
+                "category",
+                Some(&format_rfc3339(from)),
+                // The aggregate's upper bound is inclusive.
+                Some(&format_rfc3339(to - Duration::microseconds(1))),
+                None,
+                None,
+                Some(candidate.reference_asset_id),
+                CATEGORY_GROUP_LIMIT,
+            )
+            .await?;
+        Ok((
+            result.currency,
+            result
+                .groups
+                .into_iter()
+                .map(|g| (g.group_name, g.total_amount))
+                .collect(),
+        ))
+    }
+
+    async fn narrate(&self, user_id: Uuid, digest: &InsightDigest) -> Option<DigestNarrative> {
+        // Without an AI provider configured digests go out as figures only.
+        let config = ai::config::AiConfig::try_from_env().ok()?;
+        let rate_limit = Arc::new(UserRateLimiter::new(self.rate_limiter.clone(), user_id));
+
+        match ai::jobs::insights::narrate_digest(&config, rate_limit, digest).await {
+            Ok(narrative) => Some(narrative),
+            Err(AiError::RateLimited { .. }) => {
+                tracing::info!(
+                    user_id = %user_id,
+                    "AI usage limit reached; storing digest without narrative"
+                );
+                None
+            }
+            Err(e) => {
+                tracing::warn!(
+                    user_id = %user_id,
+                    error = ?e,
+                    error.type = "narrate_digest",
+                    "failed to narrate insight digest"
+                );
+                None
+            }
+        }
+    }
+}
+
+fn midnight(date: Date) -> OffsetDateTime {
+    date.with_time(Time::MIDNIGHT).assume_utc()
+}
+
+fn format_rfc3339(dt: OffsetDateTime) -> String {
+    dt.format(&time::format_description::well_known::Rfc3339)
+        .unwrap_or_default()
+}
+
+fn digest_not_found(digest_id: Uuid) -> anyhow::Error {
+    anyhow::Error::new(BusinessNotFoundError {
+        message: format!("insight digest {digest_id} not found"),
+    })
+}
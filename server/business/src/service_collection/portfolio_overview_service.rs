@@ -11,8 +11,17 @@ use uuid::Uuid;
 
 use crate::dtos::asset_id_date_dto::AssetIdDateDto;
 use crate::dtos::assets::asset_id_dto::AssetIdDto;
+use crate::dtos::fee_entry_types_dto::FeeEntryTypesDto;
+use crate::dtos::portfolio::capital_gains::dividend_income_dto::DividendIncomeDto;
+use crate::dtos::portfolio::capital_gains::CapitalGainsTaxYearDto;
 use crate::dtos::portfolio::holding::HoldingDto;
 use crate::dtos::portfolio::overview::PortfolioOverviewDto;
+use crate::dtos::transaction_dto::TransactionTypeDto;
+use crate::entities::capital_gains::matching_event::MatchingEvent;
+use crate::entities::capital_gains::share_matching::{build_tax_year_reports, match_disposals};
+use crate::entities::corporate_actions::{
+    adjust_holdings, adjust_matching_events, portfolio_action,
+};
 use crate::entities::portfolio_overview::portfolio::{
     Portfolio, PortfolioAction, ReferentialPortfolioAction,
 };
@@ -20,8 +29,10 @@ use crate::entities::transactions::metadata::MetadataKinds;
 use crate::entities::transactions::transaction::{Transaction, TransactionPortfolioAction};
 use crate::entities::transactions::transaction_types::create_transactions_from_transaction_with_entries_models;
 
+use super::accounts_service::AccountsService;
 #[mockall_double::double]
 use super::asset_rates_service::AssetRatesService;
+use super::corporate_action_service::CorporateActionService;
 use super::transaction_metadata_service::TransactionMetadataService;
 #[mockall_double::double]
 use super::transaction_service::TransactionService;
@@ -30,8 +41,10 @@ pub struct PortfolioOverviewService {
     #[allow(dead_code)]
     db: MyraDb,
     _transaction_service: TransactionService,
+    accounts_service: AccountsService,
     asset_rates_service: AssetRatesService,
     transaction_metadata_service: TransactionMetadataService,
+    corporate_action_service: CorporateActionService,
 }
 
 impl PortfolioOverviewService {
@@ -39,8 +52,10 @@ impl PortfolioOverviewService {
         Self {
             db: providers.db.clone(),
             _transaction_service: TransactionService::new(providers),
+            accounts_service: AccountsService::new(providers),
             asset_rates_service: AssetRatesService::new(providers),
             transaction_metadata_service: TransactionMetadataService::new(providers),
+            corporate_action_service: CorporateActionService::new(providers),
         }
     }
 
@@ -52,7 +67,21 @@ impl PortfolioOverviewService {
     ) -> anyhow::Result<Vec<HoldingDto>> {
         let query = entries_queries::get_holdings(user_id, apply_ownership_share);
         let ret = self.db.fetch_all::<Holding>(query).await?;
-        Ok(ret.into_iter().map(|h| h.into()).collect())
+        let holdings: Vec<HoldingDto> = ret.into_iter().map(|h| h.into()).collect();
+
+        let actions = self
+            .corporate_action_service
+            .get_corporate_actions(user_id)
+            .await?;
+        if actions.is_empty() {
+            return Ok(holdings);
+        }
+
+        let actions = self
+            .corporate_action_service
+            .get_holdings_before_actions(user_id, apply_ownership_share, actions)
+            .await?;
+        Ok(adjust_holdings(holdings, actions))
     }
 
     #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, account_id = ?account_id, asset_id = ?asset_id))]
@@ -68,6 +97,187 @@ impl PortfolioOverviewService {
             None => GetTransactionWithEntriesParams::by_user_id_with_ownership(user_id),
         };
 
+        let cost_basis_methods = self
+            .accounts_service
+            .get_cost_basis_methods(user_id)
+            .await?;
+        let mut portfolio = Portfolio::with_cost_basis_methods(cost_basis_methods);
+
+        let transactions = self.load_transactions(query_params).await?;
+        let converted_actions = self
+            .get_converted_portfolio_actions(transactions, reference_asset_id.clone())
+            .await?;
+
+        // Corporate actions go first so that, after the stable sort by date, they apply
+        // before any transaction on their effective date.
+        let mut final_vec: Vec<Box<dyn PortfolioAction>> = self
+            .corporate_action_service
+            .get_corporate_actions(user_id)
+            .await?
+            .iter()
+            .map(portfolio_action)
+            .collect();
+        final_vec.extend(converted_actions);
+
+        tracing::trace!(count = final_vec.len(), "processing portfolio actions");
+        portfolio.process_transactions(final_vec);
+
+        if let Some(account_id) = account_id {
+            portfolio.retain_account(account_id);
+        }
+
+        if let Some(asset_id) = asset_id {
+            portfolio.retain_asset(asset_id);
+        }
+
+        let held_asset_ids: HashSet<AssetIdDto> = portfolio
+            .account_portfolios()
+            .iter()
+            .flat_map(|(_, ap)| ap.asset_portfolios.keys().map(|id| AssetIdDto(*id)))
+            .collect();
+
+        let current_rates = self
+            .asset_rates_service
+            .get_pairs_latest_converted(held_asset_ids, reference_asset_id.clone())
+            .await?;
+
+        let current_rates: HashMap<AssetIdDto, Decimal> = current_rates
+            .into_iter()
+            .filter(|(ids, _)| ids.pair2 == reference_asset_id)
+            .map(|(ids, rate)| (ids.pair1, rate.rate))
+            .collect();
+
+        let dto = portfolio.try_into_dto(current_rates)?;
+        Ok(dto)
+    }
+
+    /// Realized gains across all of the user's accounts, matched with the HMRC
+    /// share identification rules and grouped by UK tax year.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id))]
+    pub async fn get_capital_gains_report(
+        &self,
+        reference_asset_id: AssetIdDto,
+        user_id: Uuid,
+    ) -> anyhow::Result<Vec<CapitalGainsTaxYearDto>> {
+        let query_params = GetTransactionWithEntriesParams::by_user_id_with_ownership(user_id);
+        let transactions = self.load_transactions(query_params).await?;
+
+        let dividends = self
+            .get_dividend_income(&transactions, reference_asset_id.clone())
+            .await?;
+
+        let actions = self
+            .get_converted_portfolio_actions(transactions, reference_asset_id.clone())
+            .await?;
+
+        let corporate_actions = self
+            .corporate_action_service
+            .get_corporate_actions(user_id)
+            .await?;
+
+        let events: Vec<MatchingEvent> = adjust_matching_events(
+            actions
+                .iter()
+                .flat_map(|action| action.matching_events())
+                .collect(),
+            &corporate_actions,
+        )
+        .into_iter()
+        .filter(|event| event.asset_id != reference_asset_id.0)
+        .collect();
+
+        tracing::trace!(count = events.len(), "matching disposals");
+        build_tax_year_reports(match_disposals(events), dividends)
+    }
+
+    /// Gross dividends and the withholding tax taken from them, converted into
+    /// `reference_asset_id` at the rate on the payment date.
+    async fn get_dividend_income(
+        &self,
+        transactions: &[Transaction],
+        reference_asset_id: AssetIdDto,
+    ) -> anyhow::Result<Vec<DividendIncomeDto>> {
+        let mut income: Vec<(AssetIdDateDto, DividendIncomeDto)> = Vec::new();
+
+        for transaction in transactions {
+            let dto = transaction.try_into_dto()?;
+            let entry = match &dto.transaction_type {
+                TransactionTypeDto::AssetDividend(metadata) => &metadata.entry,
+                TransactionTypeDto::CashDividend(metadata) => &metadata.entry,
+                _ => continue,
+            };
+
+            income.push((
+                AssetIdDateDto {
+                    asset_id: entry.asset_id,
+                    date: dto.date,
+                },
+                DividendIncomeDto {
+                    date: dto.date.date(),
+                    dividends: entry.quantity,
+                    withholding_tax: Decimal::ZERO,
+                },
+            ));
+
+            for fee in dto
+                .fee_entries
+                .iter()
+                .filter(|x| x.entry_type == FeeEntryTypesDto::WithholdingTax)
+            {
+                income.push((
+                    AssetIdDateDto {
+                        asset_id: fee.entry.asset_id,
+                        date: dto.date,
+                    },
+                    DividendIncomeDto {
+                        date: dto.date.date(),
+                        dividends: Decimal::ZERO,
+                        withholding_tax: fee.entry.quantity.abs(),
+                    },
+                ));
+            }
+        }
+
+        let asset_id_dates: Vec<AssetIdDateDto> = income
+            .iter()
+            .filter(|(asset_date, _)| asset_date.asset_id != reference_asset_id.0)
+            .map(|(asset_date, _)| asset_date.clone())
+            .collect();
+
+        let rates = self
+            .asset_rates_service
+            .get_pairs_by_dates_converted(asset_id_dates, reference_asset_id.clone())
+            .await?;
+
+        let mut rate_iter = rates.into_iter();
+        let mut converted = Vec::with_capacity(income.len());
+        for (asset_date, mut dividend) in income {
+            if asset_date.asset_id != reference_asset_id.0 {
+                match rate_iter.next().flatten() {
+                    Some(rate) => {
+                        dividend.dividends *= rate.rate;
+                        dividend.withholding_tax *= rate.rate;
+                    }
+                    None => {
+                        tracing::warn!(
+                            asset_id = asset_date.asset_id,
+                            date = %asset_date.date,
+                            "no conversion rate available for dividend; excluding from report"
+                        );
+                        continue;
+                    }
+                }
+            }
+            converted.push(dividend);
+        }
+
+        Ok(converted)
+    }
+
+    async fn load_transactions(
+        &self,
+        query_params: GetTransactionWithEntriesParams,
+    ) -> anyhow::Result<Vec<Transaction>> {
         let query = transaction_queries::get_transaction_with_entries(query_params);
         let models = self
             .db
@@ -82,8 +292,16 @@ impl PortfolioOverviewService {
             .load_metadata(&mut transactions, MetadataKinds::DIVIDENDS)
             .await?;
 
-        let mut portfolio = Portfolio::new();
+        Ok(transactions)
+    }
 
+    /// Turns transactions into portfolio actions with every referential action
+    /// converted into `reference_asset_id`.
+    async fn get_converted_portfolio_actions(
+        &self,
+        transactions: Vec<Transaction>,
+        reference_asset_id: AssetIdDto,
+    ) -> anyhow::Result<Vec<Box<dyn PortfolioAction>>> {
         let mut regular_actions: Vec<Box<dyn PortfolioAction>> = Vec::new();
         let mut referential_actions: Vec<Box<dyn ReferentialPortfolioAction>> = Vec::new();
 
@@ -141,36 +359,6 @@ impl PortfolioOverviewService {
                 .into_iter()
                 .map(|a| a as Box<dyn PortfolioAction>),
         );
-
-        tracing::trace!(count = final_vec.len(), "processing portfolio actions");
-        portfolio.process_transactions(final_vec);
-
-        if let Some(account_id) = account_id {
-            portfolio.retain_account(account_id);
-        }
-
-        if let Some(asset_id) = asset_id {
-            portfolio.retain_asset(asset_id);
-        }
-
-        let held_asset_ids: HashSet<AssetIdDto> = portfolio
-            .account_portfolios()
-            .iter()
-            .flat_map(|(_, ap)| ap.asset_portfolios.keys().map(|id| AssetIdDto(*id)))
-            .collect();
-
-        let current_rates = self
-            .asset_rates_service
-            .get_pairs_latest_converted(held_asset_ids, reference_asset_id.clone())
-            .await?;
-
-        let current_rates: HashMap<AssetIdDto, Decimal> = current_rates
-            .into_iter()
-            .filter(|(ids, _)| ids.pair2 == reference_asset_id)
-            .map(|(ids, rate)| (ids.pair1, rate.rate))
-            .collect();
-
-        let dto = portfolio.try_into_dto(current_rates)?;
-        Ok(dto)
+        Ok(final_vec)
     }
 }
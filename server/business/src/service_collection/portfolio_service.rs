@@ -1,20 +1,28 @@
+use std::collections::HashMap;
+
 #[mockall_double::double]
 use dal::database_context::MyraDb;
+use rust_decimal::Decimal;
 use uuid::Uuid;
 
 use crate::dtos::asset_rate_dto::AssetRateDto;
 use crate::dtos::assets::asset_id_dto::AssetIdDto;
 use crate::dtos::net_worth::range_dto::RangeDto;
-use crate::entities::net_worth::net_wroth_history::NetWorthHistory;
+use crate::entities::corporate_actions::adjust_holdings;
+use crate::entities::net_worth::net_wroth_history::{date_bin, NetWorthHistory};
 use crate::entities::range::{Range, RangeError};
 
 use super::asset_rates_service::AssetRatesService;
+use super::connector_balance_service::ConnectorBalanceService;
+use super::corporate_action_service::CorporateActionService;
 use super::entries_service::EntriesService;
 
 pub struct PortfolioService {
     _db_context: MyraDb,
     entries_service: EntriesService,
     asset_rates_service: AssetRatesService,
+    corporate_action_service: CorporateActionService,
+    connector_balance_service: ConnectorBalanceService,
 }
 
 impl PortfolioService {
@@ -23,6 +31,8 @@ impl PortfolioService {
             _db_context: providers.db.clone(),
             entries_service: EntriesService::new(providers),
             asset_rates_service: AssetRatesService::new(providers),
+            corporate_action_service: CorporateActionService::new(providers),
+            connector_balance_service: ConnectorBalanceService::new(providers),
         }
     }
 
@@ -62,6 +72,9 @@ impl PortfolioService {
             return Ok(vec![]);
         }
 
+        self.add_corporate_actions(&mut net_worth_history, user_id, account_id)
+            .await?;
+
         let asset_first_occurances = net_worth_history.get_asset_first_occurance_dates();
         let asset_rate_queues = self
             .asset_rates_service
@@ -78,4 +91,119 @@ impl PortfolioService {
 
         Ok(history)
     }
+
+    /// History of the balance the account's connectors reported, valued like
+    /// [`Self::get_full_portfolio_history`] and on the same bins, so the two can be
+    /// overlaid. Snapshots are binned like ledger entries, and points before the bin of
+    /// the first reported balance are left out. Providers report holdings as they stand,
+    /// so corporate actions are not applied.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, account_id = %account_id))]
+    pub async fn get_provider_balance_history(
+        &self,
+        user_id: Uuid,
+        reference_asset: AssetIdDto,
+        range_dto: RangeDto,
+        account_id: Uuid,
+    ) -> anyhow::Result<Vec<AssetRateDto>> {
+        let bounded_range = match Range::try_from(range_dto.clone()) {
+            Ok(r) => Some(r),
+            Err(RangeError::StartDateNotSpecified) => None,
+            Err(err) => return Err(err.into()),
+        };
+        let movements = self
+            .connector_balance_service
+            .get_account_balance_movements(
+                user_id,
+                account_id,
+                bounded_range.map(|r| r.start_time()),
+                bounded_range.map(|r| r.end_time()),
+            )
+            .await?;
+        let Some(first_reported) = movements.first().map(|movement| movement.time) else {
+            return Ok(vec![]);
+        };
+
+        let range = match bounded_range {
+            Some(r) => r,
+            None => {
+                let oldest_date = self
+                    .entries_service
+                    .get_oldest_entry_date(user_id, Some(account_id))
+                    .await?;
+                Range::try_from_with_time(range_dto, oldest_date.unwrap_or(first_reported))?
+            }
+        };
+
+        let interval = range.interval();
+        let first_bin = date_bin(first_reported, interval);
+        let mut balance_history = NetWorthHistory::new(reference_asset.clone(), range);
+        balance_history.add_entries(movements.into_iter().map(|mut movement| {
+            movement.time = date_bin(movement.time, interval);
+            movement
+        }));
+
+        let asset_first_occurances = balance_history.get_asset_first_occurance_dates();
+        let asset_rate_queues = self
+            .asset_rates_service
+            .get_assets_rates_default_from_date(
+                reference_asset,
+                asset_first_occurances,
+                range.interval(),
+            )
+            .await?;
+        balance_history.add_asset_rates(asset_rate_queues);
+
+        Ok(balance_history
+            .calculate_networth_history()
+            .into_iter()
+            .filter(|point| point.date >= first_bin)
+            .collect())
+    }
+
+    /// Restates the history for the user's corporate actions. Actions before the first
+    /// bin are applied to the opening balance from the ledger holdings they affected.
+    async fn add_corporate_actions(
+        &self,
+        net_worth_history: &mut NetWorthHistory,
+        user_id: Uuid,
+        account_id: Option<Uuid>,
+    ) -> anyhow::Result<()> {
+        let actions = self
+            .corporate_action_service
+            .get_corporate_actions(user_id)
+            .await?;
+        if actions.is_empty() {
+            return Ok(());
+        }
+
+        if let Some(opening_cutoff) = net_worth_history.opening_cutoff() {
+            let opening_actions = actions
+                .iter()
+                .filter(|action| action.effective_date < opening_cutoff)
+                .cloned()
+                .collect();
+            let opening_actions = self
+                .corporate_action_service
+                .get_holdings_before_actions(user_id, account_id.is_none(), opening_actions)
+                .await?
+                .into_iter()
+                .map(|(action, holdings)| {
+                    let holdings = holdings
+                        .into_iter()
+                        .filter(|holding| account_id.is_none_or(|id| holding.account_id == id))
+                        .collect();
+                    (action, holdings)
+                })
+                .collect();
+
+            let mut adjustments: HashMap<i32, Decimal> = HashMap::new();
+            for holding in adjust_holdings(vec![], opening_actions) {
+                *adjustments.entry(holding.asset_id).or_default() += holding.units;
+            }
+            net_worth_history.add_opening_adjustments(adjustments.into_iter());
+        }
+
+        net_worth_history.add_corporate_actions(&actions);
+        Ok(())
+    }
 }
@@ -0,0 +1,303 @@
+use std::collections::{HashMap, HashSet};
+
+#[mockall_double::double]
+use dal::database_context::MyraDb;
+use dal::models::reconciliation_models::{AccountReconciliationRow, ReconciliationMovementRow};
+use dal::queries::reconciliation_queries;
+use dal::query_params::get_account_reconciliations_params::GetAccountReconciliationsParams;
+use itertools::Itertools;
+use rust_decimal::Decimal;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::dtos::bad_request_error_dto::BusinessBadRequestError;
+use crate::dtos::not_found_error_dto::BusinessNotFoundError;
+use crate::dtos::reconciliation_dto::{
+    AccountReconciliationDto, AddAccountReconciliationDto, LedgerMovementDto,
+    ReconciliationReportDto, ReconciliationSourceDto,
+};
+use crate::entities::reconciliation::build_reconciliation_report;
+
+use super::accounts_service::AccountsService;
+use super::asset_service::AssetsService;
+
+pub struct ReconciliationService {
+    db: MyraDb,
+    accounts_service: AccountsService,
+    assets_service: AssetsService,
+}
+
+impl ReconciliationService {
+    pub fn new(providers: &super::ServiceProviders) -> Self {
+        Self {
+            db: providers.db.clone(),
+            accounts_service: AccountsService::new(providers),
+            assets_service: AssetsService::new(providers),
+        }
+    }
+
+    /// Every checkpoint of the account, newest first, each compared with the ledger.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, account_id = %account_id))]
+    pub async fn get_reconciliations(
+        &self,
+        user_id: Uuid,
+        account_id: Uuid,
+    ) -> anyhow::Result<Vec<ReconciliationReportDto>> {
+        self.ensure_account_owned(user_id, account_id).await?;
+
+        let query = reconciliation_queries::get_account_reconciliations(
+            GetAccountReconciliationsParams::by_account(user_id, account_id),
+        );
+        let checkpoints: Vec<AccountReconciliationDto> = self
+            .db
+            .fetch_all::<AccountReconciliationRow>(query)
+            .await?
+            .into_iter()
+            .map_into()
+            .collect();
+
+        let by_asset = checkpoints
+            .iter()
+            .cloned()
+            .into_group_map_by(|checkpoint| checkpoint.asset_id);
+        let mut movements: HashMap<i32, Vec<LedgerMovementDto>> = HashMap::new();
+        for (asset_id, asset_checkpoints) in &by_asset {
+            let up_to = asset_checkpoints
+                .iter()
+                .map(|checkpoint| checkpoint.statement_date)
+                .max()
+                .unwrap_or_else(OffsetDateTime::now_utc);
+            movements.insert(
+                *asset_id,
+                self.get_movements(user_id, account_id, *asset_id, up_to)
+                    .await?,
+            );
+        }
+
+        Ok(checkpoints
+            .into_iter()
+            .map(|checkpoint| {
+                let asset_id = checkpoint.asset_id;
+                build_reconciliation_report(
+                    checkpoint,
+                    by_asset
+                        .get(&asset_id)
+                        .map(Vec::as_slice)
+                        .unwrap_or_default(),
+                    movements
+                        .get(&asset_id)
+                        .map(Vec::as_slice)
+                        .unwrap_or_default(),
+                )
+            })
+            .collect())
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, reconciliation_id = %reconciliation_id))]
+    pub async fn get_reconciliation(
+        &self,
+        user_id: Uuid,
+        account_id: Uuid,
+        reconciliation_id: Uuid,
+    ) -> anyhow::Result<ReconciliationReportDto> {
+        let query = reconciliation_queries::get_account_reconciliations(
+            GetAccountReconciliationsParams::by_id(user_id, account_id, reconciliation_id),
+        );
+        let checkpoint: AccountReconciliationDto = self
+            .db
+            .fetch_optional::<AccountReconciliationRow>(query)
+            .await?
+            .ok_or_else(|| reconciliation_not_found(reconciliation_id))?
+            .into();
+
+        self.build_report(user_id, checkpoint).await
+    }
+
+    /// Records a statement balance and reports how far the ledger is from it.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, account_id = %account_id))]
+    pub async fn create_reconciliation(
+        &self,
+        user_id: Uuid,
+        account_id: Uuid,
+        reconciliation: AddAccountReconciliationDto,
+    ) -> anyhow::Result<ReconciliationReportDto> {
+        self.ensure_account_owned(user_id, account_id).await?;
+
+        let assets = self
+            .assets_service
+            .get_assets(HashSet::from([reconciliation.asset_id]))
+            .await?;
+        if assets.is_empty() {
+            return Err(anyhow::Error::new(BusinessBadRequestError {
+                message: format!("asset {} does not exist", reconciliation.asset_id),
+            }));
+        }
+
+        let query = reconciliation_queries::insert_account_reconciliations(vec![reconciliation
+            .into_add_model(user_id, account_id, ReconciliationSourceDto::Manual, None)]);
+        let checkpoint: AccountReconciliationDto = self
+            .db
+            .fetch_one::<AccountReconciliationRow>(query)
+            .await?
+            .into();
+
+        self.build_report(user_id, checkpoint).await
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, reconciliation_id = %reconciliation_id))]
+    pub async fn delete_reconciliation(
+        &self,
+        user_id: Uuid,
+        account_id: Uuid,
+        reconciliation_id: Uuid,
+    ) -> anyhow::Result<()> {
+        self.get_reconciliation(user_id, account_id, reconciliation_id)
+            .await?;
+
+        let query =
+            reconciliation_queries::delete_account_reconciliation(reconciliation_id, user_id);
+        self.db.execute(query).await?;
+        Ok(())
+    }
+
+    /// Turns the balances a connector reported for a bound account into checkpoints.
+    /// When the provider reports the same balance as on the binding's previous checkpoint
+    /// for that asset, the existing checkpoint is moved forward instead of adding another.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, account_id = %account_id, binding_id = %binding_id))]
+    pub async fn record_provider_balances(
+        &self,
+        user_id: Uuid,
+        account_id: Uuid,
+        binding_id: Uuid,
+        balances: HashMap<i32, Decimal>,
+        at: OffsetDateTime,
+    ) -> anyhow::Result<()> {
+        if balances.is_empty() {
+            return Ok(());
+        }
+
+        let query = reconciliation_queries::get_account_reconciliations(
+            GetAccountReconciliationsParams::by_account(user_id, account_id),
+        );
+        let previous: HashMap<i32, AccountReconciliationDto> = self
+            .db
+            .fetch_all::<AccountReconciliationRow>(query)
+            .await?
+            .into_iter()
+            .map(AccountReconciliationDto::from)
+            .filter(|checkpoint| checkpoint.binding_id == Some(binding_id))
+            .into_grouping_map_by(|checkpoint| checkpoint.asset_id)
+            .max_by_key(|_, checkpoint| checkpoint.statement_date);
+
+        let mut new_checkpoints = Vec::new();
+        for (asset_id, balance) in balances {
+            match previous.get(&asset_id) {
+                Some(checkpoint) if checkpoint.statement_balance == balance => {
+                    self.db
+                        .execute(
+                            reconciliation_queries::update_reconciliation_statement_date(
+                                checkpoint.id,
+                                at,
+                            ),
+                        )
+                        .await?;
+                }
+                _ => new_checkpoints.push(
+                    AddAccountReconciliationDto {
+                        asset_id,
+                        statement_date: at,
+                        statement_balance: balance,
+                    }
+                    .into_add_model(
+                        user_id,
+                        account_id,
+                        ReconciliationSourceDto::Connector,
+                        Some(binding_id),
+                    ),
+                ),
+            }
+        }
+
+        if !new_checkpoints.is_empty() {
+            self.db
+                .execute(reconciliation_queries::insert_account_reconciliations(
+                    new_checkpoints,
+                ))
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn build_report(
+        &self,
+        user_id: Uuid,
+        checkpoint: AccountReconciliationDto,
+    ) -> anyhow::Result<ReconciliationReportDto> {
+        let query = reconciliation_queries::get_account_reconciliations(
+            GetAccountReconciliationsParams::by_asset(
+                user_id,
+                checkpoint.account_id,
+                checkpoint.asset_id,
+            ),
+        );
+        let earlier: Vec<AccountReconciliationDto> = self
+            .db
+            .fetch_all::<AccountReconciliationRow>(query)
+            .await?
+            .into_iter()
+            .map(AccountReconciliationDto::from)
+            .filter(|other| other.statement_date < checkpoint.statement_date)
+            .collect();
+
+        let movements = self
+            .get_movements(
+                user_id,
+                checkpoint.account_id,
+                checkpoint.asset_id,
+                checkpoint.statement_date,
+            )
+            .await?;
+
+        Ok(build_reconciliation_report(
+            checkpoint, &earlier, &movements,
+        ))
+    }
+
+    async fn get_movements(
+        &self,
+        user_id: Uuid,
+        account_id: Uuid,
+        asset_id: i32,
+        up_to: OffsetDateTime,
+    ) -> anyhow::Result<Vec<LedgerMovementDto>> {
+        let query = reconciliation_queries::get_reconciliation_movements(
+            user_id, account_id, asset_id, up_to,
+        );
+        let rows = self
+            .db
+            .fetch_all::<ReconciliationMovementRow>(query)
+            .await?;
+        Ok(rows.into_iter().map_into().collect())
+    }
+
+    async fn ensure_account_owned(&self, user_id: Uuid, account_id: Uuid) -> anyhow::Result<()> {
+        let owned = self
+            .accounts_service
+            .get_accounts(HashSet::from([account_id]))
+            .await?
+            .into_iter()
+            .any(|account| account.user_id == user_id);
+        if !owned {
+            return Err(anyhow::Error::new(BusinessNotFoundError {
+                message: "account not found".to_string(),
+            }));
+        }
+        Ok(())
+    }
+}
+
+fn reconciliation_not_found(id: Uuid) -> anyhow::Error {
+    anyhow::Error::new(BusinessNotFoundError {
+        message: format!("reconciliation {id} not found"),
+    })
+}
@@ -0,0 +1,328 @@
+use std::collections::HashSet;
+
+#[mockall_double::double]
+use dal::database_context::MyraDb;
+use dal::models::recurring_transaction_models::RecurringTransactionRow;
+use dal::queries::recurring_transaction_queries;
+use dal::query_params::get_recurring_transactions_params::{
+    AdvanceRecurringTransactionParams, GetRecurringTransactionsParams,
+};
+use time::{Duration, OffsetDateTime, Time};
+use uuid::Uuid;
+
+use crate::dtos::bad_request_error_dto::BusinessBadRequestError;
+use crate::dtos::not_found_error_dto::BusinessNotFoundError;
+use crate::dtos::recurring_transactions::{
+    AddUpdateRecurringTransactionDto, RecurringTransactionDto,
+};
+use crate::dtos::transaction_dto::TransactionVisibilityDto;
+use crate::entities::recurrence_rule::RecurrenceRule;
+use crate::entities::transactions::transaction::Transaction;
+use crate::entities::transactions::transaction_types::create_transaction_from_dto;
+
+use super::accounts_service::AccountsService;
+use super::transaction_management_service::TransactionManagementService;
+
+/// Caps how many occurrences a single run creates for one template, so a template
+/// that fell far behind catches up over several runs instead of one huge insert.
+const MAX_OCCURRENCES_PER_RUN: usize = 100;
+
+pub struct RecurringTransactionService {
+    db: MyraDb,
+    accounts_service: AccountsService,
+    transaction_management: TransactionManagementService,
+}
+
+impl RecurringTransactionService {
+    pub fn new(providers: &super::ServiceProviders) -> Self {
+        Self {
+            db: providers.db.clone(),
+            accounts_service: AccountsService::new(providers),
+            transaction_management: TransactionManagementService::new(providers),
+        }
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id))]
+    pub async fn get_recurring_transactions(
+        &self,
+        user_id: Uuid,
+    ) -> anyhow::Result<Vec<RecurringTransactionDto>> {
+        let query = recurring_transaction_queries::get_recurring_transactions(
+            GetRecurringTransactionsParams::all(user_id),
+        );
+        let rows = self.db.fetch_all::<RecurringTransactionRow>(query).await?;
+        rows.into_iter().map(TryInto::try_into).collect()
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, recurring_transaction_id = %id))]
+    pub async fn get_recurring_transaction(
+        &self,
+        user_id: Uuid,
+        id: Uuid,
+    ) -> anyhow::Result<RecurringTransactionDto> {
+        let query = recurring_transaction_queries::get_recurring_transactions(
+            GetRecurringTransactionsParams::by_id(user_id, id),
+        );
+        let row = self
+            .db
+            .fetch_optional::<RecurringTransactionRow>(query)
+            .await?
+            .ok_or_else(|| recurring_transaction_not_found(id))?;
+        row.try_into()
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id))]
+    pub async fn create_recurring_transaction(
+        &self,
+        user_id: Uuid,
+        recurring: AddUpdateRecurringTransactionDto,
+    ) -> anyhow::Result<RecurringTransactionDto> {
+        let rule = self
+            .validate_recurring_transaction(user_id, &recurring)
+            .await?;
+        let next_occurrence = first_occurrence(&rule, &recurring, None, OffsetDateTime::now_utc());
+
+        let query = recurring_transaction_queries::insert_recurring_transaction(
+            recurring.into_add_model(user_id, next_occurrence)?,
+        );
+        let row = self.db.fetch_one::<RecurringTransactionRow>(query).await?;
+        row.try_into()
+    }
+
+    /// Replaces a template. The schedule restarts from today, but occurrences that were
+    /// already created are never repeated.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, recurring_transaction_id = %id))]
+    pub async fn update_recurring_transaction(
+        &self,
+        user_id: Uuid,
+        id: Uuid,
+        recurring: AddUpdateRecurringTransactionDto,
+    ) -> anyhow::Result<RecurringTransactionDto> {
+        let existing = self.get_recurring_transaction(user_id, id).await?;
+        let rule = self
+            .validate_recurring_transaction(user_id, &recurring)
+            .await?;
+        let next_occurrence = first_occurrence(
+            &rule,
+            &recurring,
+            existing.last_occurrence,
+            OffsetDateTime::now_utc(),
+        );
+
+        let query = recurring_transaction_queries::update_recurring_transaction(
+            id,
+            user_id,
+            recurring.into_update_model(next_occurrence)?,
+        );
+        let row = self
+            .db
+            .fetch_optional::<RecurringTransactionRow>(query)
+            .await?
+            .ok_or_else(|| recurring_transaction_not_found(id))?;
+        row.try_into()
+    }
+
+    /// Deletes the template only; transactions it already created are kept.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, recurring_transaction_id = %id))]
+    pub async fn delete_recurring_transaction(
+        &self,
+        user_id: Uuid,
+        id: Uuid,
+    ) -> anyhow::Result<()> {
+        self.get_recurring_transaction(user_id, id).await?;
+
+        let query = recurring_transaction_queries::delete_recurring_transaction(id, user_id);
+        self.db.execute(query).await?;
+        Ok(())
+    }
+
+    /// Active templates of all users with at least one occurrence at or before `now`, by
+    /// id and starting after `after`.
+    #[tracing::instrument(level = "debug", skip_all)]
+    pub async fn get_due_recurring_transactions(
+        &self,
+        now: OffsetDateTime,
+        after: Option<Uuid>,
+        limit: u64,
+    ) -> anyhow::Result<Vec<RecurringTransactionDto>> {
+        let query = recurring_transaction_queries::get_recurring_transactions(
+            GetRecurringTransactionsParams::due(now, after, limit),
+        );
+        let rows = self.db.fetch_all::<RecurringTransactionRow>(query).await?;
+        rows.into_iter().map(TryInto::try_into).collect()
+    }
+
+    /// Creates a transaction for every occurrence of `recurring` between its next
+    /// occurrence and `now`, then moves the schedule forward. Returns how many
+    /// transactions were created.
+    ///
+    /// Both happen in one database transaction, and the schedule only moves if nobody
+    /// else moved it first, so running this concurrently never duplicates an occurrence.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %recurring.user_id, recurring_transaction_id = %recurring.id))]
+    pub async fn materialize_recurring_transaction(
+        &self,
+        recurring: &RecurringTransactionDto,
+        now: OffsetDateTime,
+    ) -> anyhow::Result<usize> {
+        let Some(next_occurrence) = recurring.next_occurrence else {
+            return Ok(0);
+        };
+        let rule: RecurrenceRule = recurring.rrule.parse()?;
+
+        let until = recurring.end_date.map_or(now, |end| end.min(now));
+        let occurrences = rule.occurrences_between(
+            recurring.start_date,
+            next_occurrence,
+            until,
+            MAX_OCCURRENCES_PER_RUN,
+        );
+        let Some(last_occurrence) = occurrences.last().copied() else {
+            return Ok(0);
+        };
+
+        let following = rule
+            .next_occurrence(recurring.start_date, last_occurrence + Duration::SECOND)
+            .filter(|x| recurring.end_date.is_none_or(|end| *x <= end));
+
+        let visibility = if recurring.create_as_ghost {
+            TransactionVisibilityDto::Ghost
+        } else {
+            TransactionVisibilityDto::Default
+        };
+
+        let mut entities = occurrences
+            .into_iter()
+            .map(|date| {
+                let mut dto = recurring.template.clone();
+                dto.transaction_id = None;
+                dto.date = date;
+                dto.visibility = visibility;
+                create_transaction_from_dto(dto, recurring.user_id)
+            })
+            .collect::<anyhow::Result<Vec<Transaction>>>()?;
+
+        let params = AdvanceRecurringTransactionParams {
+            id: recurring.id,
+            expected_next_occurrence: next_occurrence,
+            next_occurrence: following,
+            last_occurrence,
+        };
+
+        self.db.start_transaction().await?;
+        match self.advance_and_add(params, &mut entities).await {
+            Ok(true) => {
+                self.db.commit_transaction().await?;
+                Ok(entities.len())
+            }
+            Ok(false) => {
+                self.db.rollback_transaction().await?;
+                Ok(0)
+            }
+            Err(e) => {
+                let _ = self.db.rollback_transaction().await;
+                Err(e)
+            }
+        }
+    }
+
+    async fn advance_and_add(
+        &self,
+        params: AdvanceRecurringTransactionParams,
+        entities: &mut [Transaction],
+    ) -> anyhow::Result<bool> {
+        let claimed = self
+            .db
+            .execute_with_rows_affected(
+                recurring_transaction_queries::advance_recurring_transaction(params),
+            )
+            .await?;
+        if claimed == 0 {
+            return Ok(false);
+        }
+
+        self.transaction_management
+            .add_transactions(entities)
+            .await?;
+        Ok(true)
+    }
+
+    async fn validate_recurring_transaction(
+        &self,
+        user_id: Uuid,
+        recurring: &AddUpdateRecurringTransactionDto,
+    ) -> anyhow::Result<RecurrenceRule> {
+        let rule: RecurrenceRule = recurring.rrule.parse().map_err(|e| {
+            anyhow::Error::new(BusinessBadRequestError {
+                message: format!("Invalid recurrence rule: {e}"),
+            })
+        })?;
+
+        if recurring
+            .end_date
+            .is_some_and(|end| end < recurring.start_date)
+        {
+            return Err(anyhow::Error::new(BusinessBadRequestError {
+                message: "End date cannot be before start date".to_string(),
+            }));
+        }
+
+        // Building the entity runs the same checks as creating the transaction directly.
+        let entity =
+            create_transaction_from_dto(recurring.template.clone(), user_id).map_err(|e| {
+                anyhow::Error::new(BusinessBadRequestError {
+                    message: format!("Invalid transaction template: {e}"),
+                })
+            })?;
+
+        let entry_accounts: HashSet<Uuid> = entity
+            .get_entries()
+            .iter()
+            .map(|entry| entry.account_id)
+            .collect();
+        if !entry_accounts.contains(&recurring.account_id) {
+            return Err(anyhow::Error::new(BusinessBadRequestError {
+                message: "Transaction template does not book to the target account".to_string(),
+            }));
+        }
+
+        let owned = self
+            .accounts_service
+            .get_accounts(entry_accounts.clone())
+            .await?
+            .into_iter()
+            .filter(|account| account.user_id == user_id)
+            .count();
+        if owned != entry_accounts.len() {
+            return Err(anyhow::Error::new(BusinessNotFoundError {
+                message: "account not found".to_string(),
+            }));
+        }
+
+        Ok(rule)
+    }
+}
+
+/// First occurrence from the start of today (UTC) onwards, so templates starting in
+/// the past are not backfilled. Never returns an occurrence at or before
+/// `last_occurrence`.
+fn first_occurrence(
+    rule: &RecurrenceRule,
+    recurring: &AddUpdateRecurringTransactionDto,
+    last_occurrence: Option<OffsetDateTime>,
+    now: OffsetDateTime,
+) -> Option<OffsetDateTime> {
+    let today = now.date().with_time(Time::MIDNIGHT).assume_utc();
+    let mut from = recurring.start_date.max(today);
+    if let Some(last) = last_occurrence {
+        from = from.max(last + Duration::SECOND);
+    }
+
+    rule.next_occurrence(recurring.start_date, from)
+        .filter(|x| recurring.end_date.is_none_or(|end| *x <= end))
+}
+
+fn recurring_transaction_not_found(id: Uuid) -> anyhow::Error {
+    anyhow::Error::new(BusinessNotFoundError {
+        message: format!("recurring transaction {id} not found"),
+    })
+}
@@ -0,0 +1,235 @@
+//! Alerts on spending that looks wrong: amounts far outside the usual, charges taken
+//! twice, first purchases abroad and unexpected currencies. New transactions are
+//! checked by the worker right after they are entered or imported, and each alert is
+//! streamed to the user's open subscriptions as soon as it is stored.
+
+#[mockall_double::double]
+use dal::database_context::MyraDb;
+use dal::job_queue::JobQueueHandle;
+use dal::models::spending_alert_models::{
+    AddSpendingAlertModel, SpendingAlertChargeRow, SpendingAlertRow,
+};
+use dal::pg_notify_connection::{PgNotifyConnection, PgNotifyEvent};
+use dal::queries::spending_alert_queries;
+use dal::query_params::get_spending_alerts_params::{
+    GetSpendingAlertChargesParams, GetSpendingAlertChargesParamsSearchType, GetSpendingAlertsParams,
+};
+use rust_decimal::Decimal;
+use time::{Duration, OffsetDateTime};
+use tokio::sync::broadcast;
+use uuid::Uuid;
+
+use crate::dtos::not_found_error_dto::BusinessNotFoundError;
+use crate::dtos::spending_alert_dto::SpendingAlertDto;
+use crate::entities::spending_alerts::{detect_spending_alerts, AlertCharge};
+use crate::jobs::CheckSpendingAlertsJob;
+
+use super::user_service::UsersService;
+
+/// History a new charge is compared with.
+const LOOKBACK: Duration = Duration::days(365);
+/// Older transactions are not alerted on, so a first import of years of history or a
+/// backdated manual entry does not flood the user.
+const RECENT: Duration = Duration::days(14);
+
+pub struct SpendingAlertService {
+    db: MyraDb,
+    queue: JobQueueHandle,
+    pg_notify: PgNotifyConnection,
+    users_service: UsersService,
+}
+
+impl SpendingAlertService {
+    pub fn new(providers: &super::ServiceProviders) -> Self {
+        Self {
+            db: providers.db.clone(),
+            queue: providers.job_queue.clone(),
+            pg_notify: providers.pg_notify.clone(),
+            users_service: UsersService::new(providers),
+        }
+    }
+
+    pub async fn enqueue_check(
+        &self,
+        user_id: Uuid,
+        transaction_ids: Vec<Uuid>,
+    ) -> anyhow::Result<()> {
+        if transaction_ids.is_empty() {
+            return Ok(());
+        }
+        self.queue
+            .push(CheckSpendingAlertsJob {
+                user_id,
+                transaction_ids,
+            })
+            .await
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id))]
+    pub async fn get_alerts(
+        &self,
+        user_id: Uuid,
+        include_dismissed: bool,
+        start: u64,
+        count: u64,
+    ) -> anyhow::Result<Vec<SpendingAlertDto>> {
+        let query = spending_alert_queries::get_spending_alerts(GetSpendingAlertsParams::all(
+            user_id,
+            include_dismissed,
+            start,
+            count,
+        ));
+        let rows = self.db.fetch_all::<SpendingAlertRow>(query).await?;
+        Ok(rows.into_iter().map(Into::into).collect())
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id))]
+    pub async fn get_alert(
+        &self,
+        user_id: Uuid,
+        alert_id: Uuid,
+    ) -> anyhow::Result<SpendingAlertDto> {
+        let query = spending_alert_queries::get_spending_alerts(GetSpendingAlertsParams::by_id(
+            user_id, alert_id,
+        ));
+        let row = self
+            .db
+            .fetch_optional::<SpendingAlertRow>(query)
+            .await?
+            .ok_or_else(|| {
+                anyhow::Error::new(BusinessNotFoundError {
+                    message: format!("spending alert {alert_id} not found"),
+                })
+            })?;
+        Ok(row.into())
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id))]
+    pub async fn dismiss(&self, user_id: Uuid, alert_id: Uuid) -> anyhow::Result<()> {
+        self.get_alert(user_id, alert_id).await?;
+
+        let query = spending_alert_queries::dismiss_spending_alert(user_id, alert_id);
+        self.db.execute(query).await?;
+        Ok(())
+    }
+
+    /// Alerts stored for the user from now on, as `SpendingAlertDto` payloads.
+    pub async fn subscribe(&self, user_id: Uuid) -> broadcast::Receiver<PgNotifyEvent> {
+        self.pg_notify.subscribe(&entity_id(user_id)).await
+    }
+
+    /// Checks the user's recent outgoing transactions among `transaction_ids` against
+    /// their history, stores what is found and streams the new alerts. Transactions
+    /// checked before only yield alerts they did not have yet.
+    #[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id, transactions = transaction_ids.len()))]
+    pub async fn check_transactions(
+        &self,
+        user_id: Uuid,
+        transaction_ids: Vec<Uuid>,
+    ) -> anyhow::Result<Vec<SpendingAlertDto>> {
+        if transaction_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let recent_from = OffsetDateTime::now_utc() - RECENT;
+        let checked: Vec<AlertCharge> = self
+            .get_charges(
+                user_id,
+                GetSpendingAlertChargesParamsSearchType::ByIds(transaction_ids),
+            )
+            .await?
+            .into_iter()
+            .filter(|charge| charge.date >= recent_from)
+            .collect();
+        let (Some(first), Some(last)) = (
+            checked.iter().map(|c| c.date).min(),
+            checked.iter().map(|c| c.date).max(),
+        ) else {
+            return Ok(Vec::new());
+        };
+
+        let history = self
+            .get_charges(
+                user_id,
+                GetSpendingAlertChargesParamsSearchType::Range {
+                    from: first - LOOKBACK,
+                    to: last + Duration::days(1),
+                },
+            )
+            .await?;
+        let base_asset_id = self.users_service.get_default_asset(user_id).await?;
+
+        let detected = detect_spending_alerts(&checked, &history, base_asset_id);
+        if detected.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let models = detected
+            .into_iter()
+            .map(|alert| AddSpendingAlertModel {
+                user_id,
+                transaction_id: alert.transaction_id,
+                kind: alert.kind.as_str().to_string(),
+                account_id: alert.account_id,
+                asset_id: alert.asset_id,
+                amount: alert.amount,
+                typical_amount: alert.typical_amount,
+                related_transaction_id: alert.related_transaction_id,
+                description: alert.description,
+                date_transacted: alert.date_transacted,
+            })
+            .collect();
+        let rows = self
+            .db
+            .fetch_all::<SpendingAlertRow>(spending_alert_queries::insert_spending_alerts(models))
+            .await?;
+        let alerts: Vec<SpendingAlertDto> = rows.into_iter().map(Into::into).collect();
+
+        // The alerts are stored either way; a missed notification shows on the next list.
+        for alert in &alerts {
+            if let Err(e) = self.pg_notify.notify(&entity_id(user_id), alert).await {
+                tracing::warn!(
+                    alert_id = %alert.id,
+                    error = ?e,
+                    error.type = "sqlx::Error",
+                    "failed to stream spending alert"
+                );
+            }
+        }
+
+        Ok(alerts)
+    }
+
+    /// Outgoing charges only; a transaction split over several categories or accounts
+    /// gives one charge for each.
+    async fn get_charges(
+        &self,
+        user_id: Uuid,
+        search_type: GetSpendingAlertChargesParamsSearchType,
+    ) -> anyhow::Result<Vec<AlertCharge>> {
+        let query =
+            spending_alert_queries::get_spending_alert_charges(GetSpendingAlertChargesParams {
+                user_id,
+                search_type,
+            });
+        let rows = self.db.fetch_all::<SpendingAlertChargeRow>(query).await?;
+
+        Ok(rows
+            .into_iter()
+            .filter(|row| row.quantity < Decimal::ZERO)
+            .map(|row| AlertCharge {
+                transaction_id: row.transaction_id,
+                date: row.date_transacted,
+                description: row.description,
+                account_id: row.account_id,
+                asset_id: row.asset_id,
+                category_id: row.category_id,
+                amount: -row.quantity,
+            })
+            .collect())
+    }
+}
+
+fn entity_id(user_id: Uuid) -> String {
+    format!("spending_alerts:{user_id}")
+}
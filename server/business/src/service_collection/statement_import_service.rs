@@ -0,0 +1,233 @@
+use std::collections::HashSet;
+
+use connectors::statement_file::{
+    into_stream_items, parse_statement, CsvColumnMapping, QifDateOrder, StatementParseOptions,
+    TRANSACTIONS_STREAM,
+};
+#[mockall_double::double]
+use dal::database_context::MyraDb;
+use dal::models::statement_csv_mapping_models::{
+    StatementCsvMappingRow, UpsertStatementCsvMappingModel,
+};
+use dal::queries::statement_csv_mapping_queries;
+use uuid::Uuid;
+
+use crate::dtos::bad_request_error_dto::BusinessBadRequestError;
+use crate::dtos::connectors::{
+    ClientSuppliedStreamDto, CredentialModeDto, CsvColumnMappingDto, ImportStatementDto,
+    StatementFormatDto, SyncOutcomeDto, TransientSyncCredentialDto,
+};
+use crate::dtos::not_found_error_dto::BusinessNotFoundError;
+
+use super::accounts_service::AccountsService;
+use super::asset_service::AssetsService;
+use super::connector_service::ConnectorService;
+use super::connector_sync_service::ConnectorSyncService;
+use super::user_service::UsersService;
+
+const STATEMENT_FILE_PROVIDER: &str = "statement_file";
+
+pub struct StatementImportService {
+    db: MyraDb,
+    accounts: AccountsService,
+    assets: AssetsService,
+    users: UsersService,
+    connectors: ConnectorService,
+    connector_sync: ConnectorSyncService,
+}
+
+impl StatementImportService {
+    pub fn new(providers: &super::ServiceProviders) -> Self {
+        Self {
+            db: providers.db.clone(),
+            accounts: AccountsService::new(providers),
+            assets: AssetsService::new(providers),
+            users: UsersService::new(providers),
+            connectors: ConnectorService::new(providers),
+            connector_sync: ConnectorSyncService::new(providers),
+        }
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, account_id = %account_id))]
+    pub async fn get_csv_mapping(
+        &self,
+        user_id: Uuid,
+        account_id: Uuid,
+    ) -> anyhow::Result<CsvColumnMappingDto> {
+        self.find_csv_mapping(user_id, account_id)
+            .await?
+            .ok_or_else(|| {
+                anyhow::Error::new(BusinessNotFoundError {
+                    message: format!("no CSV mapping saved for account {account_id}"),
+                })
+            })
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, account_id = %account_id))]
+    pub async fn save_csv_mapping(
+        &self,
+        user_id: Uuid,
+        account_id: Uuid,
+        mapping: CsvColumnMappingDto,
+    ) -> anyhow::Result<CsvColumnMappingDto> {
+        self.ensure_account_owned(user_id, account_id).await?;
+
+        let mapping: CsvColumnMapping = mapping.into();
+        mapping.validate().map_err(|e| {
+            anyhow::Error::new(BusinessBadRequestError {
+                message: format!("Invalid CSV mapping: {e}"),
+            })
+        })?;
+
+        let query = statement_csv_mapping_queries::upsert_statement_csv_mapping(
+            UpsertStatementCsvMappingModel {
+                account_id,
+                user_id,
+                mapping: serde_json::to_value(&mapping)?,
+            },
+        );
+        let row = self.db.fetch_one::<StatementCsvMappingRow>(query).await?;
+        Ok(serde_json::from_value::<CsvColumnMapping>(row.mapping.0)?.into())
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, account_id = %account_id))]
+    pub async fn delete_csv_mapping(&self, user_id: Uuid, account_id: Uuid) -> anyhow::Result<()> {
+        self.get_csv_mapping(user_id, account_id).await?;
+
+        let query =
+            statement_csv_mapping_queries::delete_statement_csv_mapping(user_id, account_id);
+        self.db.execute(query).await?;
+        Ok(())
+    }
+
+    /// Parses an uploaded statement and ingests it into a `statement_file` binding through
+    /// the client-supplied connector. Lines already imported from an earlier, overlapping
+    /// statement are recognised by their external id and left alone.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, binding_id = %binding_id, format = ?statement.format))]
+    pub async fn import_statement(
+        &self,
+        user_id: Uuid,
+        binding_id: Uuid,
+        statement: ImportStatementDto,
+    ) -> anyhow::Result<SyncOutcomeDto> {
+        let binding = self.connectors.get_binding(user_id, binding_id).await?;
+        let connection = self
+            .connectors
+            .get_connection(user_id, binding.connection_id)
+            .await?;
+        if connection.provider_kind != STATEMENT_FILE_PROVIDER
+            || connection.credential_mode != CredentialModeDto::ClientSupplied
+        {
+            return Err(anyhow::Error::new(BusinessBadRequestError {
+                message:
+                    "statements can only be imported into client_supplied statement_file bindings"
+                        .to_string(),
+            }));
+        }
+
+        let csv_mapping = match (statement.format, statement.csv_mapping) {
+            (StatementFormatDto::Csv, Some(mapping)) if statement.save_csv_mapping => Some(
+                self.save_csv_mapping(user_id, binding.sverto_account_id, mapping)
+                    .await?,
+            ),
+            (StatementFormatDto::Csv, Some(mapping)) => Some(mapping),
+            (StatementFormatDto::Csv, None) => Some(
+                self.find_csv_mapping(user_id, binding.sverto_account_id)
+                    .await?
+                    .ok_or_else(|| {
+                        anyhow::Error::new(BusinessBadRequestError {
+                            message: "no CSV mapping saved for this account — send csv_mapping"
+                                .to_string(),
+                        })
+                    })?,
+            ),
+            (_, _) => None,
+        };
+
+        let options = StatementParseOptions {
+            csv_mapping: csv_mapping.map(Into::into),
+            qif_date_order: if statement.qif_day_first {
+                QifDateOrder::DayFirst
+            } else {
+                QifDateOrder::MonthFirst
+            },
+        };
+        let lines = parse_statement(statement.format.into(), &statement.content, &options)
+            .map_err(|e| {
+                anyhow::Error::new(BusinessBadRequestError {
+                    message: format!("Could not read statement: {e}"),
+                })
+            })?;
+        if lines.is_empty() {
+            return Err(anyhow::Error::new(BusinessBadRequestError {
+                message: "statement contains no booked transactions".to_string(),
+            }));
+        }
+
+        let currency = match statement.currency {
+            Some(currency) => currency,
+            None => self.default_currency(user_id).await?,
+        };
+        let items = into_stream_items(lines, &currency)
+            .into_iter()
+            .map(serde_json::to_value)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.connector_sync
+            .sync_binding_transient(
+                user_id,
+                binding_id,
+                TransientSyncCredentialDto::ClientSupplied {
+                    streams: vec![ClientSuppliedStreamDto {
+                        stream: TRANSACTIONS_STREAM.to_string(),
+                        items,
+                    }],
+                    raw_balance: serde_json::Value::Null,
+                },
+            )
+            .await
+    }
+
+    async fn find_csv_mapping(
+        &self,
+        user_id: Uuid,
+        account_id: Uuid,
+    ) -> anyhow::Result<Option<CsvColumnMappingDto>> {
+        let query = statement_csv_mapping_queries::get_statement_csv_mapping(user_id, account_id);
+        let Some(row) = self
+            .db
+            .fetch_optional::<StatementCsvMappingRow>(query)
+            .await?
+        else {
+            return Ok(None);
+        };
+        Ok(Some(
+            serde_json::from_value::<CsvColumnMapping>(row.mapping.0)?.into(),
+        ))
+    }
+
+    async fn default_currency(&self, user_id: Uuid) -> anyhow::Result<String> {
+        let asset_id = self.users.get_default_asset(user_id).await?.ok_or_else(|| {
+            anyhow::Error::new(BusinessBadRequestError {
+                message: "statement has no currency and the user has no default currency — send currency"
+                    .to_string(),
+            })
+        })?;
+        Ok(self.assets.get_asset(asset_id).await?.ticker)
+    }
+
+    async fn ensure_account_owned(&self, user_id: Uuid, account_id: Uuid) -> anyhow::Result<()> {
+        let owned = self
+            .accounts
+            .get_accounts(HashSet::from([account_id]))
+            .await?
+            .iter()
+            .any(|account| account.user_id == user_id);
+        if !owned {
+            return Err(anyhow::Error::new(BusinessNotFoundError {
+                message: format!("account {account_id} not found"),
+            }));
+        }
+        Ok(())
+    }
+}
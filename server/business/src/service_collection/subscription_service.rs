@@ -0,0 +1,58 @@
+#[mockall_double::double]
+use dal::database_context::MyraDb;
+use dal::models::subscription_models::SubscriptionChargeRow;
+use dal::queries::subscription_queries;
+use dal::query_params::get_subscription_charges_params::GetSubscriptionChargesParams;
+use rust_decimal::Decimal;
+use time::{Date, Duration, Time, UtcOffset};
+use uuid::Uuid;
+
+use crate::dtos::subscription_dto::DetectedSubscriptionDto;
+use crate::entities::subscriptions::{detect_subscriptions, SubscriptionCharge};
+
+/// Long enough to see a yearly subscription charged twice, with some slack.
+const LOOKBACK: Duration = Duration::days(800);
+
+pub struct SubscriptionService {
+    db: MyraDb,
+}
+
+impl SubscriptionService {
+    pub fn new(providers: &super::ServiceProviders) -> Self {
+        Self {
+            db: providers.db.clone(),
+        }
+    }
+
+    /// Scans the user's regular transactions for recurring payments that are still
+    /// being charged as of `today`. Amounts stay in the asset they were paid in.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, today = %today))]
+    pub async fn detect_subscriptions(
+        &self,
+        user_id: Uuid,
+        today: Date,
+    ) -> anyhow::Result<Vec<DetectedSubscriptionDto>> {
+        let since = (today - LOOKBACK).with_time(Time::MIDNIGHT).assume_utc();
+        let query = subscription_queries::get_subscription_charges(GetSubscriptionChargesParams {
+            user_id,
+            since,
+        });
+        let rows = self.db.fetch_all::<SubscriptionChargeRow>(query).await?;
+
+        let charges = rows
+            .into_iter()
+            .filter(|row| row.quantity < Decimal::ZERO)
+            .map(|row| SubscriptionCharge {
+                transaction_id: row.transaction_id,
+                date: row.date_transacted.to_offset(UtcOffset::UTC).date(),
+                description: row.description,
+                embedding: row.embedding.map(|x| x.to_vec()),
+                account_id: row.account_id,
+                asset_id: row.asset_id,
+                amount: -row.quantity,
+            })
+            .collect();
+
+        Ok(detect_subscriptions(charges, today))
+    }
+}
@@ -0,0 +1,242 @@
+use std::collections::HashSet;
+
+#[mockall_double::double]
+use dal::database_context::MyraDb;
+use dal::models::base::Exsists;
+use dal::models::tag_models::{AddTagModel, TagRow};
+use dal::queries::tag_queries;
+use dal::query_params::get_tags_params::GetTagsParams;
+use itertools::Itertools;
+use uuid::Uuid;
+
+use crate::dtos::bad_request_error_dto::BusinessBadRequestError;
+use crate::dtos::conflict_error_dto::BusinessConflictError;
+use crate::dtos::not_found_error_dto::BusinessNotFoundError;
+use crate::dtos::tag_dto::TagDto;
+
+pub struct TagService {
+    db: MyraDb,
+}
+
+impl TagService {
+    pub fn new(providers: &super::ServiceProviders) -> Self {
+        Self {
+            db: providers.db.clone(),
+        }
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id))]
+    pub async fn get_tags(&self, user_id: Uuid) -> anyhow::Result<Vec<TagDto>> {
+        let query = tag_queries::get_tags(GetTagsParams::all(user_id));
+        let rows = self.db.fetch_all::<TagRow>(query).await?;
+        Ok(rows.into_iter().map_into().collect())
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, tag_id = %tag_id))]
+    pub async fn get_tag(&self, user_id: Uuid, tag_id: Uuid) -> anyhow::Result<TagDto> {
+        let query = tag_queries::get_tags(GetTagsParams::by_id(user_id, tag_id));
+        let row = self
+            .db
+            .fetch_optional::<TagRow>(query)
+            .await?
+            .ok_or_else(|| tag_not_found(tag_id))?;
+        Ok(row.into())
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id))]
+    pub async fn create_tag(&self, user_id: Uuid, name: String) -> anyhow::Result<TagDto> {
+        let query = tag_queries::insert_tag(AddTagModel { user_id, name });
+        let row = self
+            .db
+            .fetch_one::<TagRow>(query)
+            .await
+            .map_err(map_duplicate_name)?;
+        Ok(row.into())
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, tag_id = %tag_id))]
+    pub async fn update_tag(
+        &self,
+        user_id: Uuid,
+        tag_id: Uuid,
+        name: String,
+    ) -> anyhow::Result<TagDto> {
+        let query = tag_queries::update_tag(tag_id, user_id, name);
+        let row = self
+            .db
+            .fetch_optional::<TagRow>(query)
+            .await
+            .map_err(map_duplicate_name)?
+            .ok_or_else(|| tag_not_found(tag_id))?;
+        Ok(row.into())
+    }
+
+    /// Deletes a tag and removes it from every transaction and group carrying it.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, tag_id = %tag_id))]
+    pub async fn delete_tag(&self, user_id: Uuid, tag_id: Uuid) -> anyhow::Result<()> {
+        self.get_tag(user_id, tag_id).await?;
+
+        let query = tag_queries::delete_tag(tag_id, user_id);
+        self.db.execute(query).await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, transaction_id = %transaction_id))]
+    pub async fn get_transaction_tags(
+        &self,
+        user_id: Uuid,
+        transaction_id: Uuid,
+    ) -> anyhow::Result<Vec<TagDto>> {
+        self.ensure_transaction_owned(user_id, transaction_id)
+            .await?;
+
+        let query = tag_queries::get_tags(GetTagsParams::by_transaction(user_id, transaction_id));
+        let rows = self.db.fetch_all::<TagRow>(query).await?;
+        Ok(rows.into_iter().map_into().collect())
+    }
+
+    /// Replaces the tags of a transaction. Tags inherited from its group are not affected.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, transaction_id = %transaction_id))]
+    pub async fn set_transaction_tags(
+        &self,
+        user_id: Uuid,
+        transaction_id: Uuid,
+        tag_ids: Vec<Uuid>,
+    ) -> anyhow::Result<Vec<TagDto>> {
+        self.ensure_transaction_owned(user_id, transaction_id)
+            .await?;
+        let tags = self.get_owned_tags(user_id, tag_ids).await?;
+
+        self.db.start_transaction().await?;
+        self.db
+            .execute(tag_queries::delete_transaction_tags(transaction_id))
+            .await?;
+        if !tags.is_empty() {
+            self.db
+                .execute(tag_queries::insert_transaction_tags(
+                    transaction_id,
+                    tags.iter().map(|tag| tag.id).collect(),
+                ))
+                .await?;
+        }
+        self.db.commit_transaction().await?;
+
+        Ok(tags)
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, group_id = %group_id))]
+    pub async fn get_transaction_group_tags(
+        &self,
+        user_id: Uuid,
+        group_id: Uuid,
+    ) -> anyhow::Result<Vec<TagDto>> {
+        self.ensure_group_owned(user_id, group_id).await?;
+
+        let query = tag_queries::get_tags(GetTagsParams::by_group(user_id, group_id));
+        let rows = self.db.fetch_all::<TagRow>(query).await?;
+        Ok(rows.into_iter().map_into().collect())
+    }
+
+    /// Replaces the tags of a transaction group. Every transaction in the group is
+    /// treated as carrying them when filtering and aggregating.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, group_id = %group_id))]
+    pub async fn set_transaction_group_tags(
+        &self,
+        user_id: Uuid,
+        group_id: Uuid,
+        tag_ids: Vec<Uuid>,
+    ) -> anyhow::Result<Vec<TagDto>> {
+        self.ensure_group_owned(user_id, group_id).await?;
+        let tags = self.get_owned_tags(user_id, tag_ids).await?;
+
+        self.db.start_transaction().await?;
+        self.db
+            .execute(tag_queries::delete_transaction_group_tags(group_id))
+            .await?;
+        if !tags.is_empty() {
+            self.db
+                .execute(tag_queries::insert_transaction_group_tags(
+                    group_id,
+                    tags.iter().map(|tag| tag.id).collect(),
+                ))
+                .await?;
+        }
+        self.db.commit_transaction().await?;
+
+        Ok(tags)
+    }
+
+    pub(crate) async fn get_owned_tags(
+        &self,
+        user_id: Uuid,
+        tag_ids: Vec<Uuid>,
+    ) -> anyhow::Result<Vec<TagDto>> {
+        let requested: HashSet<Uuid> = tag_ids.into_iter().collect();
+        if requested.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let query = tag_queries::get_tags(GetTagsParams::by_ids(
+            user_id,
+            requested.iter().copied().collect(),
+        ));
+        let tags: Vec<TagDto> = self
+            .db
+            .fetch_all::<TagRow>(query)
+            .await?
+            .into_iter()
+            .map_into()
+            .collect();
+
+        if tags.len() != requested.len() {
+            let found: HashSet<Uuid> = tags.iter().map(|tag| tag.id).collect();
+            let missing = requested.difference(&found).join(", ");
+            return Err(anyhow::Error::new(BusinessBadRequestError {
+                message: format!("unknown tags: {missing}"),
+            }));
+        }
+        Ok(tags)
+    }
+
+    async fn ensure_transaction_owned(
+        &self,
+        user_id: Uuid,
+        transaction_id: Uuid,
+    ) -> anyhow::Result<()> {
+        let query = tag_queries::transaction_exists_for_user(transaction_id, user_id);
+        if !self.db.fetch_one::<Exsists>(query).await?.exists {
+            return Err(anyhow::Error::new(BusinessNotFoundError {
+                message: format!("transaction {transaction_id} not found"),
+            }));
+        }
+        Ok(())
+    }
+
+    async fn ensure_group_owned(&self, user_id: Uuid, group_id: Uuid) -> anyhow::Result<()> {
+        let query = tag_queries::transaction_group_exists_for_user(group_id, user_id);
+        if !self.db.fetch_one::<Exsists>(query).await?.exists {
+            return Err(anyhow::Error::new(BusinessNotFoundError {
+                message: format!("transaction group {group_id} not found"),
+            }));
+        }
+        Ok(())
+    }
+}
+
+fn tag_not_found(id: Uuid) -> anyhow::Error {
+    anyhow::Error::new(BusinessNotFoundError {
+        message: format!("tag {id} not found"),
+    })
+}
+
+fn map_duplicate_name(e: sqlx::Error) -> anyhow::Error {
+    if e.as_database_error()
+        .is_some_and(|d| d.is_unique_violation())
+    {
+        anyhow::Error::new(BusinessConflictError {
+            message: "A tag with this name already exists.".to_string(),
+        })
+    } else {
+        anyhow::Error::new(e)
+    }
+}
@@ -0,0 +1,150 @@
+use std::collections::{BTreeSet, HashSet};
+
+#[mockall_double::double]
+use dal::database_context::MyraDb;
+use dal::models::connector_models::ConnectorImportIssueRow;
+use dal::models::ticker_alias_models::UserTickerAliasRow;
+use dal::queries::{connector_queries, ticker_alias_queries};
+use dal::query_params::connector_params::GetConnectorImportIssuesParams;
+use itertools::Itertools;
+use uuid::Uuid;
+
+use crate::dtos::bad_request_error_dto::BusinessBadRequestError;
+use crate::dtos::conflict_error_dto::BusinessConflictError;
+use crate::dtos::not_found_error_dto::BusinessNotFoundError;
+use crate::dtos::ticker_alias_dto::{
+    AddUserTickerAliasDto, CreatedUserTickerAliasDto, UserTickerAliasDto,
+};
+
+use super::asset_service::AssetsService;
+use super::connector_sync_service::ConnectorSyncService;
+
+pub struct TickerAliasService {
+    db: MyraDb,
+    assets_service: AssetsService,
+    connector_sync_service: ConnectorSyncService,
+}
+
+impl TickerAliasService {
+    pub fn new(providers: &super::ServiceProviders) -> Self {
+        Self {
+            db: providers.db.clone(),
+            assets_service: AssetsService::new(providers),
+            connector_sync_service: ConnectorSyncService::new(providers),
+        }
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id))]
+    pub async fn get_ticker_aliases(
+        &self,
+        user_id: Uuid,
+    ) -> anyhow::Result<Vec<UserTickerAliasDto>> {
+        let query = ticker_alias_queries::get_user_ticker_aliases(user_id);
+        Ok(self
+            .db
+            .fetch_all::<UserTickerAliasRow>(query)
+            .await?
+            .into_iter()
+            .map_into()
+            .collect())
+    }
+
+    /// Stores the alias, then re-projects every binding holding unresolved transactions for
+    /// the ticker so they are imported right away. A binding that cannot be re-projected
+    /// now (e.g. a sync is running) is left for `reproject_binding` to retry.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, provider_ticker = %alias.provider_ticker))]
+    pub async fn create_ticker_alias(
+        &self,
+        user_id: Uuid,
+        mut alias: AddUserTickerAliasDto,
+    ) -> anyhow::Result<CreatedUserTickerAliasDto> {
+        alias.provider_ticker = alias.provider_ticker.trim().to_string();
+        if alias.provider_ticker.is_empty() {
+            return Err(anyhow::Error::new(BusinessBadRequestError {
+                message: "provider_ticker must not be empty".to_string(),
+            }));
+        }
+
+        let asset_exists = !self
+            .assets_service
+            .get_assets(HashSet::from([alias.asset_id]))
+            .await?
+            .is_empty();
+        if !asset_exists {
+            return Err(anyhow::Error::new(BusinessBadRequestError {
+                message: format!("asset {} does not exist", alias.asset_id),
+            }));
+        }
+
+        let taken = self
+            .get_ticker_aliases(user_id)
+            .await?
+            .into_iter()
+            .any(|existing| existing.provider_ticker == alias.provider_ticker);
+        if taken {
+            return Err(anyhow::Error::new(BusinessConflictError {
+                message: format!("an alias for {} already exists", alias.provider_ticker),
+            }));
+        }
+
+        let provider_ticker = alias.provider_ticker.clone();
+        let query = ticker_alias_queries::insert_user_ticker_alias(alias.into_add_model(user_id));
+        let alias: UserTickerAliasDto =
+            self.db.fetch_one::<UserTickerAliasRow>(query).await?.into();
+
+        let query = connector_queries::get_connector_import_issues(
+            GetConnectorImportIssuesParams::unresolved_by_identifier(user_id, provider_ticker),
+        );
+        let binding_ids: BTreeSet<Uuid> = self
+            .db
+            .fetch_all::<ConnectorImportIssueRow>(query)
+            .await?
+            .into_iter()
+            .map(|issue| issue.binding_id)
+            .collect();
+
+        let mut created = CreatedUserTickerAliasDto {
+            alias,
+            reprojected_bindings: Vec::new(),
+            pending_bindings: Vec::new(),
+        };
+        for binding_id in binding_ids {
+            match self
+                .connector_sync_service
+                .reproject_binding(user_id, binding_id)
+                .await
+            {
+                Ok(_) => created.reprojected_bindings.push(binding_id),
+                Err(e) => {
+                    tracing::warn!(
+                        binding_id = %binding_id,
+                        error = %e,
+                        "binding not re-projected after alias creation"
+                    );
+                    created.pending_bindings.push(binding_id);
+                }
+            }
+        }
+        Ok(created)
+    }
+
+    /// Removes the alias. Transactions already imported through it stay as they are.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, alias_id = %alias_id))]
+    pub async fn delete_ticker_alias(&self, user_id: Uuid, alias_id: Uuid) -> anyhow::Result<()> {
+        let query = ticker_alias_queries::get_user_ticker_alias(alias_id, user_id);
+        if self
+            .db
+            .fetch_optional::<UserTickerAliasRow>(query)
+            .await?
+            .is_none()
+        {
+            return Err(anyhow::Error::new(BusinessNotFoundError {
+                message: format!("ticker alias {alias_id} not found"),
+            }));
+        }
+
+        let query = ticker_alias_queries::delete_user_ticker_alias(alias_id, user_id);
+        self.db.execute(query).await?;
+        Ok(())
+    }
+}
@@ -252,6 +252,7 @@ impl TransactionManagementService {
         query_params.transaction_type_ids = filters.transaction_type_ids;
         query_params.date_from = filters.date_from;
         query_params.date_to = filters.date_to;
+        query_params.tag_ids = filters.tag_ids;
 
         // Map pagination mode, adding +1 for has_more detection
         match &pagination {
@@ -332,6 +333,7 @@ impl TransactionManagementService {
         user_id: Uuid,
         pagination: PaginationModeDto,
         search_query: Option<String>,
+        tag_ids: Option<Vec<Uuid>>,
     ) -> anyhow::Result<CursorPageOfResultsDto<CombinedTransactionItem>> {
         let limit = pagination.page_size();
 
@@ -340,6 +342,7 @@ impl TransactionManagementService {
             user_id,
             pagination: pagination.into(),
             search_query,
+            tag_ids,
         };
         let combined_query =
             transaction_queries::get_combined_transaction_ids_for_user(combined_params);
@@ -557,11 +560,38 @@ impl TransactionManagementService {
         Ok(dto)
     }
 
+    /// Applies a change the user made, so a linked connector transaction is no longer
+    /// overwritten by its provider.
     pub(crate) async fn update_individual_transaction_inner(
         &self,
         user_id: Uuid,
         transaction_id: Uuid,
         transaction_dto: TransactionDto,
+    ) -> anyhow::Result<()> {
+        self.apply_transaction_update(user_id, transaction_id, transaction_dto)
+            .await?;
+        self.transaction_metadata_service
+            .mark_connector_links_edited(&[transaction_id])
+            .await
+    }
+
+    /// Applies a change Sverto made on the user's behalf, such as a rule firing. A linked
+    /// connector transaction keeps following its provider.
+    pub(crate) async fn update_individual_transaction_by_rules_inner(
+        &self,
+        user_id: Uuid,
+        transaction_id: Uuid,
+        transaction_dto: TransactionDto,
+    ) -> anyhow::Result<()> {
+        self.apply_transaction_update(user_id, transaction_id, transaction_dto)
+            .await
+    }
+
+    async fn apply_transaction_update(
+        &self,
+        user_id: Uuid,
+        transaction_id: Uuid,
+        transaction_dto: TransactionDto,
     ) -> anyhow::Result<()> {
         // Step 1: Verify ownership (reuse fetched data)
         let query_params = GetTransactionWithEntriesParams::by_transaction_id(transaction_id);
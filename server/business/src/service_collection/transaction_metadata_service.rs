@@ -53,6 +53,7 @@ impl TransactionMetadataService {
                     transaction_id: Some(transaction_id),
                     external_id: link.external_id.clone(),
                     external_hash: link.external_hash.clone(),
+                    counterparty: link.counterparty.clone(),
                 })
             })
             .collect();
@@ -111,8 +112,6 @@ impl TransactionMetadataService {
         self.diff_dividends(transaction_id, old_transaction, new_transaction)
             .await?;
 
-        self.mark_connector_links_edited(&[transaction_id]).await?;
-
         Ok(())
     }
 
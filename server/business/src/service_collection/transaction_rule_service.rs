@@ -0,0 +1,433 @@
+use std::collections::{HashMap, HashSet};
+
+#[mockall_double::double]
+use dal::database_context::MyraDb;
+use dal::job_queue::JobQueueHandle;
+use dal::models::transaction_rule_models::{
+    RuleCandidateRow, TransactionRuleRow, TransactionRuleTagRow,
+};
+use dal::queries::{tag_queries, transaction_rule_queries};
+use dal::query_params::get_transaction_rules_params::GetTransactionRulesParams;
+use itertools::Itertools;
+use uuid::Uuid;
+
+use crate::dtos::bad_request_error_dto::BusinessBadRequestError;
+use crate::dtos::categories::CategoryError;
+use crate::dtos::not_found_error_dto::BusinessNotFoundError;
+use crate::dtos::transaction_dto::{TransactionDto, TransactionVisibilityDto};
+use crate::dtos::transaction_rule_dto::{
+    AddUpdateTransactionRuleDto, RuleApplicationReportDto, TransactionRuleDto,
+};
+use crate::entities::transaction_rules::{compile_description_regex, RuleSet};
+use crate::jobs::ApplyTransactionRulesJob;
+
+use super::accounts_service::AccountsService;
+use super::category_service::CategoryService;
+use super::tag_service::TagService;
+use super::transaction_management_service::TransactionManagementService;
+
+/// Transactions loaded at a time when running the rules over history.
+const HISTORY_BATCH_SIZE: u64 = 200;
+
+pub struct TransactionRuleService {
+    db: MyraDb,
+    queue: JobQueueHandle,
+    accounts_service: AccountsService,
+    tag_service: TagService,
+    category_service: CategoryService,
+    transaction_management: TransactionManagementService,
+}
+
+impl TransactionRuleService {
+    pub fn new(providers: &super::ServiceProviders) -> Self {
+        Self {
+            db: providers.db.clone(),
+            queue: providers.job_queue.clone(),
+            accounts_service: AccountsService::new(providers),
+            tag_service: TagService::new(providers),
+            category_service: CategoryService::new(providers),
+            transaction_management: TransactionManagementService::new(providers),
+        }
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id))]
+    pub async fn get_rules(&self, user_id: Uuid) -> anyhow::Result<Vec<TransactionRuleDto>> {
+        self.load_rules(GetTransactionRulesParams::all(user_id))
+            .await
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, rule_id = %rule_id))]
+    pub async fn get_rule(
+        &self,
+        user_id: Uuid,
+        rule_id: Uuid,
+    ) -> anyhow::Result<TransactionRuleDto> {
+        self.load_rules(GetTransactionRulesParams::by_id(user_id, rule_id))
+            .await?
+            .pop()
+            .ok_or_else(|| rule_not_found(rule_id))
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id))]
+    pub async fn create_rule(
+        &self,
+        user_id: Uuid,
+        rule: AddUpdateTransactionRuleDto,
+    ) -> anyhow::Result<TransactionRuleDto> {
+        self.validate_rule(user_id, &rule).await?;
+        let tag_ids: Vec<Uuid> = rule.actions.tag_ids.iter().copied().unique().collect();
+
+        self.db.start_transaction().await?;
+        let query = transaction_rule_queries::insert_transaction_rule(user_id, rule.into());
+        let row = match self.db.fetch_one::<TransactionRuleRow>(query).await {
+            Ok(row) => row,
+            Err(e) => {
+                let _ = self.db.rollback_transaction().await;
+                return Err(e.into());
+            }
+        };
+        if let Err(e) = self.set_rule_tags(row.id, tag_ids.clone()).await {
+            let _ = self.db.rollback_transaction().await;
+            return Err(e);
+        }
+        self.db.commit_transaction().await?;
+
+        Ok(TransactionRuleDto::from_row(row, tag_ids))
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, rule_id = %rule_id))]
+    pub async fn update_rule(
+        &self,
+        user_id: Uuid,
+        rule_id: Uuid,
+        rule: AddUpdateTransactionRuleDto,
+    ) -> anyhow::Result<TransactionRuleDto> {
+        self.validate_rule(user_id, &rule).await?;
+        let tag_ids: Vec<Uuid> = rule.actions.tag_ids.iter().copied().unique().collect();
+
+        self.db.start_transaction().await?;
+        let query =
+            transaction_rule_queries::update_transaction_rule(rule_id, user_id, rule.into());
+        let row = match self.db.fetch_optional::<TransactionRuleRow>(query).await {
+            Ok(Some(row)) => row,
+            Ok(None) => {
+                let _ = self.db.rollback_transaction().await;
+                return Err(rule_not_found(rule_id));
+            }
+            Err(e) => {
+                let _ = self.db.rollback_transaction().await;
+                return Err(e.into());
+            }
+        };
+        if let Err(e) = self.set_rule_tags(row.id, tag_ids.clone()).await {
+            let _ = self.db.rollback_transaction().await;
+            return Err(e);
+        }
+        self.db.commit_transaction().await?;
+
+        Ok(TransactionRuleDto::from_row(row, tag_ids))
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, rule_id = %rule_id))]
+    pub async fn delete_rule(&self, user_id: Uuid, rule_id: Uuid) -> anyhow::Result<()> {
+        self.get_rule(user_id, rule_id).await?;
+
+        let query = transaction_rule_queries::delete_transaction_rule(rule_id, user_id);
+        self.db.execute(query).await?;
+        Ok(())
+    }
+
+    /// The user's enabled rules, ready to run over transactions.
+    pub(crate) async fn rule_set(&self, user_id: Uuid) -> anyhow::Result<RuleSet> {
+        let rules = self
+            .load_rules(GetTransactionRulesParams::enabled(user_id))
+            .await?;
+        Ok(RuleSet::new(rules))
+    }
+
+    /// Adds a transaction the user entered by hand, after running their rules over it.
+    /// Manual entries have no counterparty, so rules conditioned on one do not match.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id))]
+    pub async fn add_transaction_with_rules(
+        &self,
+        user_id: Uuid,
+        mut transaction: TransactionDto,
+    ) -> anyhow::Result<TransactionDto> {
+        let rules = self.rule_set(user_id).await?;
+        let tag_ids = rules
+            .apply(&mut transaction, None, false)
+            .map(|applied| applied.tag_ids)
+            .unwrap_or_default();
+
+        // The rule's tags go in with the transaction, so it is never created without them.
+        self.db.start_transaction().await?;
+        match self
+            .add_tagged_transaction(user_id, transaction, tag_ids)
+            .await
+        {
+            Ok(transaction) => {
+                self.db.commit_transaction().await?;
+                Ok(transaction)
+            }
+            Err(e) => {
+                let _ = self.db.rollback_transaction().await;
+                Err(e)
+            }
+        }
+    }
+
+    async fn add_tagged_transaction(
+        &self,
+        user_id: Uuid,
+        transaction: TransactionDto,
+        tag_ids: Vec<Uuid>,
+    ) -> anyhow::Result<TransactionDto> {
+        let transaction = self
+            .transaction_management
+            .add_individual_transaction_inner(user_id, transaction)
+            .await?;
+        if let Some(transaction_id) = transaction.transaction_id.filter(|_| !tag_ids.is_empty()) {
+            self.db
+                .execute(tag_queries::add_transaction_tags(transaction_id, tag_ids))
+                .await?;
+        }
+        Ok(transaction)
+    }
+
+    /// Queues a run of the rules over the user's existing transactions.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id))]
+    pub async fn enqueue_apply_to_history(&self, user_id: Uuid) -> anyhow::Result<()> {
+        self.queue.push(ApplyTransactionRulesJob { user_id }).await
+    }
+
+    /// Runs the rules over every regular transaction the user has. Visibility actions are
+    /// applied too, except to ghosts still waiting for review. Each batch is committed on
+    /// its own; a failure leaves earlier batches applied.
+    #[tracing::instrument(level = "info", skip_all, fields(user_id = %user_id))]
+    pub async fn apply_to_history(
+        &self,
+        user_id: Uuid,
+    ) -> anyhow::Result<RuleApplicationReportDto> {
+        let rules = self.rule_set(user_id).await?;
+        let mut report = RuleApplicationReportDto::default();
+        if rules.is_empty() {
+            return Ok(report);
+        }
+
+        let mut after: Option<Uuid> = None;
+        loop {
+            let rows = self
+                .db
+                .fetch_all::<RuleCandidateRow>(transaction_rule_queries::get_rule_candidates(
+                    user_id,
+                    after,
+                    HISTORY_BATCH_SIZE,
+                ))
+                .await?;
+            let Some(last) = rows.last().map(|row| row.transaction_id) else {
+                break;
+            };
+            after = Some(last);
+            let batch_len = rows.len();
+
+            let candidates: Vec<Uuid> = rows.iter().map(|row| row.transaction_id).collect();
+            let counterparties: HashMap<Uuid, String> = rows
+                .into_iter()
+                .filter_map(|row| Some((row.transaction_id, row.counterparty?)))
+                .collect();
+
+            let transactions = self
+                .transaction_management
+                .get_transactions_by_ids(user_id, candidates)
+                .await?;
+            report.examined += transactions.len();
+
+            self.db.start_transaction().await?;
+            match self
+                .apply_to_batch(user_id, &rules, transactions, &counterparties)
+                .await
+            {
+                Ok(updated) => report.updated += updated,
+                Err(e) => {
+                    let _ = self.db.rollback_transaction().await;
+                    return Err(e);
+                }
+            }
+            self.db.commit_transaction().await?;
+
+            if (batch_len as u64) < HISTORY_BATCH_SIZE {
+                break;
+            }
+        }
+        Ok(report)
+    }
+
+    async fn apply_to_batch(
+        &self,
+        user_id: Uuid,
+        rules: &RuleSet,
+        transactions: Vec<TransactionDto>,
+        counterparties: &HashMap<Uuid, String>,
+    ) -> anyhow::Result<usize> {
+        let mut updated = 0;
+        for mut transaction in transactions {
+            let Some(transaction_id) = transaction.transaction_id else {
+                continue;
+            };
+            // Ghosts wait in the review inbox for the user to decide on them.
+            let keep_visibility = transaction.visibility == TransactionVisibilityDto::Ghost;
+            let counterparty = counterparties.get(&transaction_id).map(String::as_str);
+            let Some(applied) = rules.apply(&mut transaction, counterparty, keep_visibility) else {
+                continue;
+            };
+
+            if applied.changed {
+                self.transaction_management
+                    .update_individual_transaction_by_rules_inner(
+                        user_id,
+                        transaction_id,
+                        transaction,
+                    )
+                    .await?;
+            }
+            if !applied.tag_ids.is_empty() {
+                self.db
+                    .execute(tag_queries::add_transaction_tags(
+                        transaction_id,
+                        applied.tag_ids,
+                    ))
+                    .await?;
+            }
+            updated += 1;
+        }
+        Ok(updated)
+    }
+
+    async fn load_rules(
+        &self,
+        params: GetTransactionRulesParams,
+    ) -> anyhow::Result<Vec<TransactionRuleDto>> {
+        let user_id = params.user_id;
+        let rows = self
+            .db
+            .fetch_all::<TransactionRuleRow>(transaction_rule_queries::get_transaction_rules(
+                params,
+            ))
+            .await?;
+        if rows.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut tags: HashMap<Uuid, Vec<Uuid>> = HashMap::new();
+        for row in self
+            .db
+            .fetch_all::<TransactionRuleTagRow>(
+                transaction_rule_queries::get_transaction_rule_tags(user_id),
+            )
+            .await?
+        {
+            tags.entry(row.rule_id).or_default().push(row.tag_id);
+        }
+
+        Ok(rows
+            .into_iter()
+            .map(|row| {
+                let tag_ids = tags.remove(&row.id).unwrap_or_default();
+                TransactionRuleDto::from_row(row, tag_ids)
+            })
+            .collect())
+    }
+
+    async fn set_rule_tags(&self, rule_id: Uuid, tag_ids: Vec<Uuid>) -> anyhow::Result<()> {
+        self.db
+            .execute(transaction_rule_queries::delete_transaction_rule_tags(
+                rule_id,
+            ))
+            .await?;
+        if !tag_ids.is_empty() {
+            self.db
+                .execute(transaction_rule_queries::insert_transaction_rule_tags(
+                    rule_id, tag_ids,
+                ))
+                .await?;
+        }
+        Ok(())
+    }
+
+    async fn validate_rule(
+        &self,
+        user_id: Uuid,
+        rule: &AddUpdateTransactionRuleDto,
+    ) -> anyhow::Result<()> {
+        let conditions = &rule.conditions;
+        let actions = &rule.actions;
+
+        if rule.name.trim().is_empty() {
+            return Err(bad_request("Rule name cannot be empty"));
+        }
+        if conditions.description_contains.is_none()
+            && conditions.description_regex.is_none()
+            && conditions.amount_min.is_none()
+            && conditions.amount_max.is_none()
+            && conditions.account_id.is_none()
+            && conditions.counterparty.is_none()
+        {
+            return Err(bad_request("A rule needs at least one condition"));
+        }
+        if actions.is_empty() {
+            return Err(bad_request("A rule needs at least one action"));
+        }
+        if let Some(pattern) = conditions.description_regex.as_deref() {
+            if let Err(e) = compile_description_regex(pattern) {
+                return Err(bad_request(&format!("Invalid description regex: {e}")));
+            }
+        }
+        if let (Some(min), Some(max)) = (conditions.amount_min, conditions.amount_max) {
+            if min > max {
+                return Err(bad_request("Minimum amount cannot exceed the maximum"));
+            }
+        }
+
+        if let Some(account_id) = conditions.account_id {
+            let owned = self
+                .accounts_service
+                .get_accounts(HashSet::from([account_id]))
+                .await?
+                .iter()
+                .any(|account| account.user_id == user_id);
+            if !owned {
+                return Err(anyhow::Error::new(BusinessNotFoundError {
+                    message: format!("account {account_id} not found"),
+                }));
+            }
+        }
+        if let Some(category_id) = actions.category_id {
+            self.category_service
+                .get_category(category_id, user_id)
+                .await
+                .map_err(|err| match err.downcast_ref::<CategoryError>() {
+                    Some(_) => anyhow::Error::new(BusinessNotFoundError {
+                        message: format!("category {category_id} not found"),
+                    }),
+                    None => err,
+                })?;
+        }
+        self.tag_service
+            .get_owned_tags(user_id, actions.tag_ids.clone())
+            .await?;
+
+        Ok(())
+    }
+}
+
+fn bad_request(message: &str) -> anyhow::Error {
+    anyhow::Error::new(BusinessBadRequestError {
+        message: message.to_string(),
+    })
+}
+
+fn rule_not_found(rule_id: Uuid) -> anyhow::Error {
+    anyhow::Error::new(BusinessNotFoundError {
+        message: format!("transaction rule {rule_id} not found"),
+    })
+}
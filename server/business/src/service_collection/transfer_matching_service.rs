@@ -0,0 +1,385 @@
+use std::collections::{HashMap, HashSet};
+
+#[mockall_double::double]
+use dal::database_context::MyraDb;
+use dal::models::transfer_models::TransferLegRow;
+use dal::queries::{connector_queries, transfer_queries};
+use dal::query_params::get_transfer_legs_params::GetTransferLegsParams;
+use rust_decimal::Decimal;
+use time::{Duration, OffsetDateTime};
+use uuid::Uuid;
+
+use crate::dtos::assets::asset_id_dto::AssetIdDto;
+use crate::dtos::bad_request_error_dto::BusinessBadRequestError;
+use crate::dtos::entry_dto::EntryDto;
+use crate::dtos::not_found_error_dto::BusinessNotFoundError;
+use crate::dtos::transaction_dto::{
+    CashBalanceTransferMetadataDto, TransactionDto, TransactionTypeDto, TransactionVisibilityDto,
+};
+use crate::dtos::transfer_match_dto::TransferPairDto;
+use crate::entities::transfers::{match_transfers, TransferLeg, TransferMatch, DATE_WINDOW_DAYS};
+
+#[mockall_double::double]
+use super::asset_rates_service::AssetRatesService;
+use super::transaction_management_service::TransactionManagementService;
+use super::ServiceProviders;
+
+/// How far back matches are looked for when no range is given.
+const DEFAULT_LOOKBACK_DAYS: i64 = 90;
+
+/// Finds the two sides of transfers between the user's accounts that were recorded as
+/// unrelated transactions and folds each pair into one balance transfer.
+pub struct TransferMatchingService {
+    db: MyraDb,
+    transaction_management: TransactionManagementService,
+    asset_rates: AssetRatesService,
+}
+
+impl TransferMatchingService {
+    pub fn new(providers: &ServiceProviders) -> Self {
+        Self {
+            db: providers.db.clone(),
+            transaction_management: TransactionManagementService::new(providers),
+            asset_rates: AssetRatesService::new(providers),
+        }
+    }
+
+    /// Likely transfers dated within the range, which defaults to the last 90 days.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id))]
+    pub async fn get_transfer_matches(
+        &self,
+        user_id: Uuid,
+        from: Option<OffsetDateTime>,
+        to: Option<OffsetDateTime>,
+    ) -> anyhow::Result<Vec<TransferMatch>> {
+        let to = to.unwrap_or_else(OffsetDateTime::now_utc);
+        let from = from.unwrap_or(to - Duration::days(DEFAULT_LOOKBACK_DAYS));
+        if from > to {
+            return Err(anyhow::Error::new(BusinessBadRequestError {
+                message: "from must not be after to".to_string(),
+            }));
+        }
+
+        let legs = self
+            .get_legs(GetTransferLegsParams::by_date_range(user_id, from, to))
+            .await?;
+        let rates = self.get_exchange_rates(&legs).await?;
+        Ok(match_transfers(&legs, &rates))
+    }
+
+    /// Replaces each confirmed pair with a balance transfer and returns the ids of the
+    /// new transactions. The pair is taken as the user gave it, so legs in different
+    /// currencies need not agree with the latest rate.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, count = pairs.len()))]
+    pub async fn confirm_transfers(
+        &self,
+        user_id: Uuid,
+        pairs: Vec<TransferPairDto>,
+    ) -> anyhow::Result<Vec<Uuid>> {
+        validate_pairs(&pairs)?;
+        let legs: HashMap<Uuid, TransferLeg> = self
+            .get_legs(GetTransferLegsParams::by_transaction_ids(
+                user_id,
+                pairs
+                    .iter()
+                    .flat_map(|pair| [pair.outgoing_transaction_id, pair.incoming_transaction_id])
+                    .collect(),
+            ))
+            .await?
+            .into_iter()
+            .map(|leg| (leg.transaction_id, leg))
+            .collect();
+
+        let mut matches = Vec::with_capacity(pairs.len());
+        for pair in &pairs {
+            let leg = |transaction_id: Uuid| {
+                legs.get(&transaction_id).cloned().ok_or_else(|| {
+                    anyhow::Error::new(BusinessNotFoundError {
+                        message: format!(
+                            "transaction {transaction_id} cannot be one side of a transfer"
+                        ),
+                    })
+                })
+            };
+            let outgoing = leg(pair.outgoing_transaction_id)?;
+            let incoming = leg(pair.incoming_transaction_id)?;
+            validate_sides(&outgoing, &incoming)?;
+            matches.push(TransferMatch {
+                outgoing,
+                incoming,
+                unambiguous: false,
+            });
+        }
+
+        self.db.start_transaction().await?;
+        let mut transaction_ids = Vec::with_capacity(matches.len());
+        for transfer in &matches {
+            match self.convert(user_id, transfer).await {
+                Ok(transaction_id) => transaction_ids.push(transaction_id),
+                Err(e) => {
+                    let _ = self.db.rollback_transaction().await;
+                    return Err(e);
+                }
+            }
+        }
+        self.db.commit_transaction().await?;
+        Ok(transaction_ids)
+    }
+
+    /// Converts the transfers a sync can settle without asking: both sides imported and
+    /// already in the ledger, in one currency, and neither side with another candidate.
+    /// Legs of the given transactions are paired with any leg inside the date window.
+    /// Returns how many transfers were made.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, count = transaction_ids.len()))]
+    pub(crate) async fn pair_imported(
+        &self,
+        user_id: Uuid,
+        transaction_ids: Vec<Uuid>,
+    ) -> anyhow::Result<usize> {
+        let imported = self
+            .get_legs(GetTransferLegsParams::by_transaction_ids(
+                user_id,
+                transaction_ids,
+            ))
+            .await?;
+        let (Some(from), Some(to)) = (
+            imported.iter().map(|leg| leg.date).min(),
+            imported.iter().map(|leg| leg.date).max(),
+        ) else {
+            return Ok(0);
+        };
+        let window = Duration::days(DATE_WINDOW_DAYS + 1);
+        let legs = self
+            .get_legs(GetTransferLegsParams::by_date_range(
+                user_id,
+                from - window,
+                to + window,
+            ))
+            .await?;
+
+        // Exchanges are never paired here, but their rates still count towards ambiguity.
+        let rates = self.get_exchange_rates(&legs).await?;
+
+        let new_ids: HashSet<Uuid> = imported.iter().map(|leg| leg.transaction_id).collect();
+        let mut converted = 0;
+        for transfer in match_transfers(&legs, &rates) {
+            let settled = transfer.unambiguous
+                && !transfer.is_exchange()
+                && [&transfer.outgoing, &transfer.incoming]
+                    .iter()
+                    .all(|leg| leg.imported && leg.visibility == TransactionVisibilityDto::Default)
+                && (new_ids.contains(&transfer.outgoing.transaction_id)
+                    || new_ids.contains(&transfer.incoming.transaction_id));
+            if !settled {
+                continue;
+            }
+
+            self.db.start_transaction().await?;
+            if let Err(e) = self.convert(user_id, &transfer).await {
+                let _ = self.db.rollback_transaction().await;
+                return Err(e);
+            }
+            self.db.commit_transaction().await?;
+            converted += 1;
+        }
+        Ok(converted)
+    }
+
+    async fn get_legs(&self, params: GetTransferLegsParams) -> anyhow::Result<Vec<TransferLeg>> {
+        Ok(self
+            .db
+            .fetch_all::<TransferLegRow>(transfer_queries::get_transfer_legs(params))
+            .await?
+            .into_iter()
+            .map(TransferLeg::from)
+            .collect())
+    }
+
+    /// Latest rates from every outgoing currency to every other incoming one among the legs.
+    async fn get_exchange_rates(
+        &self,
+        legs: &[TransferLeg],
+    ) -> anyhow::Result<HashMap<(i32, i32), Decimal>> {
+        let outgoing: HashSet<i32> = legs
+            .iter()
+            .filter(|leg| leg.amount < Decimal::ZERO)
+            .map(|leg| leg.asset_id)
+            .collect();
+        let incoming: HashSet<i32> = legs
+            .iter()
+            .filter(|leg| leg.amount > Decimal::ZERO)
+            .map(|leg| leg.asset_id)
+            .collect();
+
+        let mut rates = HashMap::new();
+        for &from in &outgoing {
+            for &to in incoming.iter().filter(|to| **to != from) {
+                if let Some(rate) = self
+                    .asset_rates
+                    .get_pair_latest_converted(AssetIdDto(from), AssetIdDto(to))
+                    .await?
+                {
+                    rates.insert((from, to), rate.rate);
+                }
+            }
+        }
+        Ok(rates)
+    }
+
+    /// Adds the balance transfer, moves the provider links of both sides onto it and
+    /// deletes the sides. Runs inside the caller's database transaction.
+    async fn convert(&self, user_id: Uuid, transfer: &TransferMatch) -> anyhow::Result<Uuid> {
+        let outgoing = &transfer.outgoing;
+        let incoming = &transfer.incoming;
+        let transaction = self
+            .transaction_management
+            .add_individual_transaction_inner(
+                user_id,
+                TransactionDto {
+                    transaction_id: None,
+                    date: outgoing.date,
+                    visibility: TransactionVisibilityDto::Default,
+                    fee_entries: vec![],
+                    transaction_type: TransactionTypeDto::CashBalanceTransfer(
+                        CashBalanceTransferMetadataDto {
+                            outgoing_change: EntryDto::new(
+                                outgoing.asset_id,
+                                outgoing.account_id,
+                                outgoing.amount,
+                            ),
+                            incoming_change: EntryDto::new(
+                                incoming.asset_id,
+                                incoming.account_id,
+                                incoming.amount,
+                            ),
+                        },
+                    ),
+                },
+            )
+            .await?;
+        let transaction_id = transaction
+            .transaction_id
+            .ok_or_else(|| anyhow::anyhow!("balance transfer was not assigned an id"))?;
+
+        for leg in [outgoing, incoming] {
+            self.db
+                .execute(connector_queries::relink_connector_transaction(
+                    leg.transaction_id,
+                    transaction_id,
+                ))
+                .await?;
+        }
+        self.transaction_management
+            .delete_transactions_inner(
+                user_id,
+                vec![outgoing.transaction_id, incoming.transaction_id],
+            )
+            .await?;
+        Ok(transaction_id)
+    }
+}
+
+/// No transaction is paired with itself or used in more than one pair.
+fn validate_pairs(pairs: &[TransferPairDto]) -> anyhow::Result<()> {
+    if pairs.is_empty() {
+        return Err(anyhow::Error::new(BusinessBadRequestError {
+            message: "no transfers given".to_string(),
+        }));
+    }
+    let mut seen = HashSet::new();
+    for pair in pairs {
+        let message = if pair.outgoing_transaction_id == pair.incoming_transaction_id {
+            "a transaction cannot be both sides of a transfer"
+        } else if !seen.insert(pair.outgoing_transaction_id)
+            || !seen.insert(pair.incoming_transaction_id)
+        {
+            "a transaction can be part of only one transfer"
+        } else {
+            continue;
+        };
+        return Err(anyhow::Error::new(BusinessBadRequestError {
+            message: message.to_string(),
+        }));
+    }
+    Ok(())
+}
+
+/// Money leaves one account and arrives in another; in one currency it arrives whole.
+fn validate_sides(outgoing: &TransferLeg, incoming: &TransferLeg) -> anyhow::Result<()> {
+    let message = if outgoing.amount >= Decimal::ZERO {
+        format!("transaction {} is not outgoing", outgoing.transaction_id)
+    } else if incoming.amount <= Decimal::ZERO {
+        format!("transaction {} is not incoming", incoming.transaction_id)
+    } else if outgoing.account_id == incoming.account_id {
+        "both sides of a transfer are in the same account".to_string()
+    } else if outgoing.asset_id == incoming.asset_id && incoming.amount != -outgoing.amount {
+        "both sides of a transfer in one currency must have the same amount".to_string()
+    } else {
+        return Ok(());
+    };
+    Err(anyhow::Error::new(BusinessBadRequestError { message }))
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use time::macros::datetime;
+
+    use super::*;
+
+    fn pair(outgoing: u128, incoming: u128) -> TransferPairDto {
+        TransferPairDto {
+            outgoing_transaction_id: Uuid::from_u128(outgoing),
+            incoming_transaction_id: Uuid::from_u128(incoming),
+        }
+    }
+
+    fn leg(id: u128, account: u128, asset_id: i32, amount: Decimal) -> TransferLeg {
+        TransferLeg {
+            transaction_id: Uuid::from_u128(id),
+            account_id: Uuid::from_u128(account),
+            asset_id,
+            amount,
+            date: datetime!(2026-10-01 09:00 UTC),
+            visibility: TransactionVisibilityDto::Default,
+            imported: false,
+        }
+    }
+
+    #[test]
+    fn pairs_use_each_transaction_once() {
+        assert!(validate_pairs(&[pair(1, 2), pair(3, 4)]).is_ok());
+
+        for (pairs, expected) in [
+            (vec![], "no transfers given"),
+            (
+                vec![pair(1, 1)],
+                "a transaction cannot be both sides of a transfer",
+            ),
+            (
+                vec![pair(1, 2), pair(3, 1)],
+                "a transaction can be part of only one transfer",
+            ),
+        ] {
+            let err = validate_pairs(&pairs).unwrap_err();
+            assert!(err.is::<BusinessBadRequestError>());
+            assert_eq!(err.to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn sides_must_leave_one_account_and_reach_another() {
+        assert!(validate_sides(&leg(1, 10, 1, dec!(-20)), &leg(2, 20, 1, dec!(20))).is_ok());
+        assert!(validate_sides(&leg(1, 10, 1, dec!(-20)), &leg(2, 20, 2, dec!(23))).is_ok());
+
+        for (outgoing, incoming) in [
+            (leg(1, 10, 1, dec!(20)), leg(2, 20, 1, dec!(20))),
+            (leg(1, 10, 1, dec!(-20)), leg(2, 20, 1, dec!(-20))),
+            (leg(1, 10, 1, dec!(-20)), leg(2, 10, 1, dec!(20))),
+            (leg(1, 10, 1, dec!(-20)), leg(2, 20, 1, dec!(19))),
+        ] {
+            let err = validate_sides(&outgoing, &incoming).unwrap_err();
+            assert!(err.is::<BusinessBadRequestError>());
+        }
+    }
+}
@@ -0,0 +1,985 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt::Display;
+use std::hash::Hash;
+
+#[mockall_double::double]
+use dal::database_context::MyraDb;
+use dal::models::account_models::{
+    AccountCreationModel, AccountIdentifierInsert, AccountIdentifierRow,
+};
+use dal::models::asset_models::{
+    AssetId, AssetPair, AssetPairId, AssetPairRateInsert, InsertAsset,
+};
+use dal::models::base::Exsists;
+use dal::models::category_models::{
+    CategoryTypeModel, InsertCategoryModel, InsertCategoryTypeModel,
+};
+use dal::models::connector_models::{
+    AddConnectorBindingModel, AddConnectorConnectionModel, AddConnectorProviderAccountModel,
+    AddConnectorTransactionModel,
+};
+use dal::models::entry_models::AddEntryModel;
+use dal::models::transaction_models::{
+    AddTransactionDescriptionModel, AddTransactionDividendModel, AddTransactionGroupModel,
+};
+use dal::models::user_data_archive_models::{
+    ArchiveAccountRow, ArchiveAssetPairRow, ArchiveAssetRateRow, ArchiveAssetRow,
+    ArchiveConnectorBindingRow, ArchiveConnectorConnectionRow, ArchiveConnectorProviderAccountRow,
+    ArchiveConnectorTransactionRow, ArchiveEntryRow, ArchiveTransactionGroupRow,
+    ArchiveTransactionRow, InsertArchivedTransactionModel,
+};
+use dal::queries::{
+    account_identifier_queries, account_queries, asset_queries, category_queries,
+    category_type_queries, connector_queries, entries_queries, transaction_data_queries,
+    transaction_group_queries, user_data_archive_queries,
+};
+use itertools::Itertools;
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::dtos::bad_request_error_dto::BusinessBadRequestError;
+use crate::dtos::conflict_error_dto::BusinessConflictError;
+use crate::dtos::user_data_archive_dto::{
+    ArchivedAccountDto, ArchivedAccountIdentifierDto, ArchivedAssetPairDto, ArchivedAssetRateDto,
+    ArchivedCategoryDto, ArchivedCategoryTypeDto, ArchivedConnectorBindingDto,
+    ArchivedConnectorConnectionDto, ArchivedConnectorProviderAccountDto,
+    ArchivedConnectorTransactionDto, ArchivedCustomAssetDto, ArchivedEntryDto,
+    ArchivedSharedAssetDto, ArchivedSharedNameDto, ArchivedTransactionDto,
+    ArchivedTransactionGroupDto, UserDataArchiveDto, UserDataImportSummaryDto,
+    USER_DATA_ARCHIVE_VERSION,
+};
+
+use super::ai_embedding_service::AiEmbeddingService;
+use super::asset_service::AssetsService;
+use super::category_service::CategoryService;
+use super::category_type_service::CategoryTypeService;
+
+/// Rows per multi-row insert, keeping each statement well under Postgres' bind limit.
+const INSERT_CHUNK_SIZE: usize = 1000;
+
+/// Text queued for embedding once the import is committed.
+enum PendingEmbedding {
+    Asset(i32, String),
+    Category(i32, String),
+    Group(Uuid, String),
+    Transaction(Uuid, String),
+}
+
+/// Ids of shared reference data on this instance, keyed by the archive's ids.
+struct SharedIdMaps {
+    assets: HashMap<i32, i32>,
+    category_types: HashMap<i32, i32>,
+    categories: HashMap<i32, i32>,
+    category_type_names: HashMap<i32, String>,
+}
+
+pub struct UserDataArchiveService {
+    db: MyraDb,
+    assets: AssetsService,
+    categories: CategoryService,
+    category_types: CategoryTypeService,
+    embedding_service: AiEmbeddingService,
+}
+
+impl UserDataArchiveService {
+    pub fn new(providers: &super::ServiceProviders) -> Self {
+        Self {
+            db: providers.db.clone(),
+            assets: AssetsService::new(providers),
+            categories: CategoryService::new(providers),
+            category_types: CategoryTypeService::new(providers),
+            embedding_service: AiEmbeddingService::new(providers),
+        }
+    }
+
+    /// Collects everything the user owns into a versioned archive. Connector credentials
+    /// are left out.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id))]
+    pub async fn export_user_data(&self, user_id: Uuid) -> anyhow::Result<UserDataArchiveDto> {
+        let account_rows = self
+            .db
+            .fetch_all::<ArchiveAccountRow>(user_data_archive_queries::get_archive_accounts(
+                user_id,
+            ))
+            .await?;
+        let mut identifiers: HashMap<Uuid, Vec<ArchivedAccountIdentifierDto>> = HashMap::new();
+        if !account_rows.is_empty() {
+            let query = account_identifier_queries::get_identifiers_for_accounts(
+                account_rows.iter().map(|account| account.id).collect(),
+            );
+            for row in self.db.fetch_all::<AccountIdentifierRow>(query).await? {
+                identifiers
+                    .entry(row.account_id)
+                    .or_default()
+                    .push(ArchivedAccountIdentifierDto {
+                        kind: row.kind,
+                        value: row.value,
+                    });
+            }
+        }
+
+        let custom_assets = self
+            .db
+            .fetch_all::<ArchiveAssetRow>(user_data_archive_queries::get_archive_custom_assets(
+                user_id,
+            ))
+            .await?;
+        let custom_asset_ids: HashSet<i32> = custom_assets.iter().map(|asset| asset.id).collect();
+        let pairs = if custom_asset_ids.is_empty() {
+            Vec::new()
+        } else {
+            self.db
+                .fetch_all::<ArchiveAssetPairRow>(
+                    user_data_archive_queries::get_archive_asset_pairs(
+                        custom_asset_ids.iter().copied().collect(),
+                    ),
+                )
+                .await?
+        };
+        let mut rates: HashMap<i32, Vec<ArchivedAssetRateDto>> = HashMap::new();
+        if !pairs.is_empty() {
+            let query = user_data_archive_queries::get_archive_asset_rates(
+                pairs.iter().map(|pair| pair.id).collect(),
+            );
+            for row in self.db.fetch_all::<ArchiveAssetRateRow>(query).await? {
+                rates
+                    .entry(row.pair_id)
+                    .or_default()
+                    .push(ArchivedAssetRateDto {
+                        rate: row.rate,
+                        recorded_at: row.recorded_at,
+                    });
+            }
+        }
+
+        let all_category_types = self
+            .category_types
+            .get_user_category_types(user_id)
+            .await?
+            .collect_vec();
+        let category_types = all_category_types
+            .iter()
+            .filter(|category_type| !category_type.is_global)
+            .map(|category_type| ArchivedCategoryTypeDto {
+                id: category_type.id,
+                name: category_type.category_type_name.clone(),
+            })
+            .collect_vec();
+        let categories = self
+            .categories
+            .get_all_user_categories(user_id)
+            .await?
+            .into_iter()
+            .filter(|category| category.user_id == Some(user_id))
+            .map(|category| ArchivedCategoryDto {
+                id: category.id,
+                name: category.category,
+                icon: category.icon,
+                category_type: category.category_type,
+            })
+            .collect_vec();
+
+        let groups = self
+            .db
+            .fetch_all::<ArchiveTransactionGroupRow>(
+                user_data_archive_queries::get_archive_transaction_groups(user_id),
+            )
+            .await?;
+        let transaction_rows = self
+            .db
+            .fetch_all::<ArchiveTransactionRow>(
+                user_data_archive_queries::get_archive_transactions(user_id),
+            )
+            .await?;
+        let mut entries: HashMap<Uuid, Vec<ArchivedEntryDto>> = HashMap::new();
+        for row in self
+            .db
+            .fetch_all::<ArchiveEntryRow>(user_data_archive_queries::get_archive_entries(user_id))
+            .await?
+        {
+            entries
+                .entry(row.transaction_id)
+                .or_default()
+                .push(ArchivedEntryDto {
+                    account_id: row.account_id,
+                    asset_id: row.asset_id,
+                    quantity: row.quantity,
+                    category_id: row.category_id,
+                });
+        }
+
+        let connections = self
+            .db
+            .fetch_all::<ArchiveConnectorConnectionRow>(
+                user_data_archive_queries::get_archive_connector_connections(user_id),
+            )
+            .await?;
+        let provider_accounts = self
+            .db
+            .fetch_all::<ArchiveConnectorProviderAccountRow>(
+                user_data_archive_queries::get_archive_connector_provider_accounts(user_id),
+            )
+            .await?;
+        let bindings = self
+            .db
+            .fetch_all::<ArchiveConnectorBindingRow>(
+                user_data_archive_queries::get_archive_connector_bindings(user_id),
+            )
+            .await?;
+        let connector_transactions = self
+            .db
+            .fetch_all::<ArchiveConnectorTransactionRow>(
+                user_data_archive_queries::get_archive_connector_transactions(user_id),
+            )
+            .await?;
+
+        let transactions = transaction_rows
+            .into_iter()
+            .map(|row| ArchivedTransactionDto {
+                entries: entries.remove(&row.id).unwrap_or_default(),
+                id: row.id,
+                group_id: row.group_id,
+                type_id: row.type_id,
+                date_transacted: row.date_transacted,
+                visibility: row.visibility,
+                description: row.description,
+                dividend_source_asset_id: row.source_asset_id,
+            })
+            .collect_vec();
+
+        // Shared reference data is written by ticker or name so another instance can
+        // resolve it to its own ids.
+        let shared_asset_ids: HashSet<i32> = transactions
+            .iter()
+            .flat_map(|transaction| {
+                transaction
+                    .entries
+                    .iter()
+                    .map(|entry| entry.asset_id)
+                    .chain(transaction.dividend_source_asset_id)
+            })
+            .chain(custom_assets.iter().filter_map(|asset| asset.base_pair_id))
+            .chain(pairs.iter().flat_map(|pair| [pair.pair1, pair.pair2]))
+            .filter(|id| !custom_asset_ids.contains(id))
+            .collect();
+        let shared_assets = if shared_asset_ids.is_empty() {
+            Vec::new()
+        } else {
+            self.assets
+                .get_assets(shared_asset_ids)
+                .await?
+                .into_iter()
+                .map(|asset| ArchivedSharedAssetDto {
+                    id: asset.asset_id,
+                    ticker: asset.ticker,
+                })
+                .sorted_by_key(|asset| asset.id)
+                .collect()
+        };
+
+        let custom_category_ids: HashSet<i32> =
+            categories.iter().map(|category| category.id).collect();
+        let shared_category_ids: HashSet<i32> = transactions
+            .iter()
+            .flat_map(|transaction| transaction.entries.iter().map(|entry| entry.category_id))
+            .chain(groups.iter().map(|group| group.category_id))
+            .filter(|id| !custom_category_ids.contains(id))
+            .collect();
+        let shared_categories = self
+            .categories
+            .get_categories(shared_category_ids)
+            .await?
+            .into_iter()
+            .map(|category| ArchivedSharedNameDto {
+                id: category.id,
+                name: category.category,
+            })
+            .sorted_by_key(|category| category.id)
+            .collect();
+
+        let custom_category_type_ids: HashSet<i32> = category_types
+            .iter()
+            .map(|category_type| category_type.id)
+            .collect();
+        let shared_category_type_ids: HashSet<i32> = categories
+            .iter()
+            .map(|category| category.category_type)
+            .filter(|id| !custom_category_type_ids.contains(id))
+            .collect();
+        let shared_category_types = all_category_types
+            .iter()
+            .filter(|category_type| shared_category_type_ids.contains(&category_type.id))
+            .map(|category_type| ArchivedSharedNameDto {
+                id: category_type.id,
+                name: category_type.category_type_name.clone(),
+            })
+            .collect();
+
+        Ok(UserDataArchiveDto {
+            version: USER_DATA_ARCHIVE_VERSION,
+            exported_at: OffsetDateTime::now_utc(),
+            shared_assets,
+            shared_category_types,
+            shared_categories,
+            accounts: account_rows
+                .into_iter()
+                .map(|row| ArchivedAccountDto {
+                    identifiers: identifiers.remove(&row.id).unwrap_or_default(),
+                    id: row.id,
+                    name: row.account_name,
+                    account_type: row.account_type,
+                    liquidity_type: row.liquidity_type,
+                    active: row.active,
+                    ownership_share: row.ownership_share,
+                    cost_basis_method: row.cost_basis_method,
+                })
+                .collect(),
+            custom_assets: custom_assets
+                .into_iter()
+                .map(|row| ArchivedCustomAssetDto {
+                    id: row.id,
+                    asset_type: row.asset_type,
+                    name: row.asset_name,
+                    ticker: row.ticker,
+                    base_pair_id: row.base_pair_id,
+                })
+                .collect(),
+            asset_pairs: pairs
+                .into_iter()
+                .map(|pair| ArchivedAssetPairDto {
+                    rates: rates.remove(&pair.id).unwrap_or_default(),
+                    pair1: pair.pair1,
+                    pair2: pair.pair2,
+                })
+                .collect(),
+            category_types,
+            categories,
+            transaction_groups: groups
+                .into_iter()
+                .map(|row| ArchivedTransactionGroupDto {
+                    id: row.id,
+                    category_id: row.category_id,
+                    description: row.description,
+                    date_added: row.date_added,
+                })
+                .collect(),
+            transactions,
+            connector_connections: connections
+                .into_iter()
+                .map(|row| ArchivedConnectorConnectionDto {
+                    id: row.id,
+                    provider_kind: row.provider_kind,
+                    credential_mode: row.credential_mode,
+                    status: row.status,
+                    consent_expires_at: row.consent_expires_at,
+                })
+                .collect(),
+            connector_provider_accounts: provider_accounts
+                .into_iter()
+                .map(|row| ArchivedConnectorProviderAccountDto {
+                    id: row.id,
+                    connection_id: row.connection_id,
+                    external_account_id: row.external_account_id,
+                })
+                .collect(),
+            connector_bindings: bindings
+                .into_iter()
+                .map(|row| ArchivedConnectorBindingDto {
+                    id: row.id,
+                    provider_account_id: row.provider_account_id,
+                    account_id: row.sverto_account_id,
+                    write_mode: row.write_mode,
+                    status: row.status,
+                })
+                .collect(),
+            connector_transactions: connector_transactions
+                .into_iter()
+                .map(|row| ArchivedConnectorTransactionDto {
+                    binding_id: row.binding_id,
+                    transaction_id: row.transaction_id,
+                    external_id: row.external_id,
+                    external_hash: row.external_hash,
+                    edited_by_user: row.edited_by_user,
+                    counterparty: row.counterparty,
+                })
+                .collect(),
+        })
+    }
+
+    /// Recreates an archive's contents under `user_id`, giving every record a new id.
+    /// The user must not have any accounts or transactions yet; the whole import runs in
+    /// one database transaction.
+    #[tracing::instrument(level = "debug", skip_all, fields(user_id = %user_id, version = archive.version))]
+    pub async fn import_user_data(
+        &self,
+        user_id: Uuid,
+        archive: UserDataArchiveDto,
+    ) -> anyhow::Result<UserDataImportSummaryDto> {
+        if archive.version != USER_DATA_ARCHIVE_VERSION {
+            return Err(bad_request(format!(
+                "unsupported archive version {} (expected {USER_DATA_ARCHIVE_VERSION})",
+                archive.version
+            )));
+        }
+
+        let has_data = self
+            .db
+            .fetch_one::<Exsists>(user_data_archive_queries::user_has_ledger_data(user_id))
+            .await?;
+        if has_data.exists {
+            return Err(anyhow::Error::new(BusinessConflictError {
+                message:
+                    "archives can only be imported into a user without accounts or transactions"
+                        .to_string(),
+            }));
+        }
+
+        let shared = self.resolve_shared_ids(user_id, &archive).await?;
+
+        self.db.start_transaction().await?;
+        let (summary, embeddings) = match self.write_archive(user_id, archive, shared).await {
+            Ok(result) => result,
+            Err(e) => {
+                let _ = self.db.rollback_transaction().await;
+                return Err(e);
+            }
+        };
+        self.db.commit_transaction().await?;
+
+        for embedding in embeddings {
+            match embedding {
+                PendingEmbedding::Asset(id, text) => {
+                    self.embedding_service.enqueue_embed_asset(id, text).await?
+                }
+                PendingEmbedding::Category(id, text) => {
+                    self.embedding_service
+                        .enqueue_embed_category(id, text)
+                        .await?
+                }
+                PendingEmbedding::Group(id, text) => {
+                    self.embedding_service.enqueue_embed_group(id, text).await?
+                }
+                PendingEmbedding::Transaction(id, text) => {
+                    self.embedding_service
+                        .enqueue_embed_transaction(id, text)
+                        .await?
+                }
+            }
+        }
+
+        Ok(summary)
+    }
+
+    async fn resolve_shared_ids(
+        &self,
+        user_id: Uuid,
+        archive: &UserDataArchiveDto,
+    ) -> anyhow::Result<SharedIdMaps> {
+        let by_ticker = self
+            .assets
+            .resolve_tickers(
+                user_id,
+                archive
+                    .shared_assets
+                    .iter()
+                    .map(|asset| asset.ticker.clone())
+                    .collect(),
+            )
+            .await?;
+        let assets = resolve_by_name(
+            "assets",
+            archive
+                .shared_assets
+                .iter()
+                .map(|asset| (asset.id, asset.ticker.as_str())),
+            |ticker| by_ticker.get(ticker).copied(),
+        )?;
+
+        let global_types = self
+            .category_types
+            .get_category_types()
+            .await?
+            .map(|category_type| {
+                (
+                    category_type.category_type_name.to_lowercase(),
+                    category_type,
+                )
+            })
+            .collect::<HashMap<_, _>>();
+        let category_types = resolve_by_name(
+            "category types",
+            archive
+                .shared_category_types
+                .iter()
+                .map(|category_type| (category_type.id, category_type.name.as_str())),
+            |name| global_types.get(&name.to_lowercase()).map(|t| t.id),
+        )?;
+        let category_type_names: HashMap<i32, String> = global_types
+            .into_values()
+            .map(|category_type| (category_type.id, category_type.category_type_name))
+            .collect();
+
+        let global_categories = self
+            .categories
+            .get_all_user_categories(user_id)
+            .await?
+            .into_iter()
+            .filter(|category| category.is_global)
+            .map(|category| (category.category.to_lowercase(), category.id))
+            .collect::<HashMap<_, _>>();
+        let categories = resolve_by_name(
+            "categories",
+            archive
+                .shared_categories
+                .iter()
+                .map(|category| (category.id, category.name.as_str())),
+            |name| global_categories.get(&name.to_lowercase()).copied(),
+        )?;
+
+        Ok(SharedIdMaps {
+            assets,
+            category_types,
+            categories,
+            category_type_names,
+        })
+    }
+
+    async fn write_archive(
+        &self,
+        user_id: Uuid,
+        archive: UserDataArchiveDto,
+        shared: SharedIdMaps,
+    ) -> anyhow::Result<(UserDataImportSummaryDto, Vec<PendingEmbedding>)> {
+        let mut summary = UserDataImportSummaryDto::default();
+        let mut embeddings = Vec::new();
+
+        // Accounts
+        let mut account_ids: HashMap<Uuid, Uuid> = HashMap::new();
+        let mut identifiers = Vec::new();
+        for account in archive.accounts {
+            let query = account_queries::insert_account(AccountCreationModel {
+                user_id,
+                account_name: account.name,
+                account_type: account.account_type,
+                liquidity_type: account.liquidity_type,
+                ownership_share: account.ownership_share,
+                cost_basis_method: account.cost_basis_method,
+            });
+            let id: Uuid = self.db.fetch_one_scalar(query).await?;
+            if !account.active {
+                self.db
+                    .execute(account_queries::deactivate_account(user_id, id))
+                    .await?;
+            }
+            identifiers.extend(account.identifiers.into_iter().map(|identifier| {
+                AccountIdentifierInsert {
+                    account_id: id,
+                    kind: identifier.kind,
+                    value: identifier.value,
+                }
+            }));
+            account_ids.insert(account.id, id);
+        }
+        if !identifiers.is_empty() {
+            self.db
+                .execute(account_identifier_queries::insert_account_identifiers(
+                    identifiers,
+                ))
+                .await?;
+        }
+        summary.accounts = account_ids.len();
+
+        // Custom assets, inserted once the asset they are quoted against exists
+        let mut asset_ids = shared.assets;
+        let mut pending_assets = archive.custom_assets;
+        while !pending_assets.is_empty() {
+            let (ready, waiting): (Vec<_>, Vec<_>) =
+                pending_assets.into_iter().partition(|asset| {
+                    asset
+                        .base_pair_id
+                        .is_none_or(|base| asset_ids.contains_key(&base))
+                });
+            if ready.is_empty() {
+                return Err(bad_request(format!(
+                    "custom assets {} are quoted against assets missing from the archive",
+                    waiting.iter().map(|asset| &asset.ticker).join(", ")
+                )));
+            }
+            for asset in ready {
+                let query = asset_queries::insert_asset(InsertAsset {
+                    ticker: asset.ticker.clone(),
+                    asset_name: asset.name.clone(),
+                    asset_type: asset.asset_type,
+                    base_pair_id: asset
+                        .base_pair_id
+                        .and_then(|base| asset_ids.get(&base).copied()),
+                    user_id: Some(user_id),
+                });
+                let id = self
+                    .db
+                    .fetch_one::<AssetId>(query)
+                    .await
+                    .map_err(|e| {
+                        if e.as_database_error()
+                            .is_some_and(|d| d.is_unique_violation())
+                        {
+                            anyhow::Error::new(BusinessConflictError {
+                                message: format!(
+                                    "an asset with ticker {} already exists on this server",
+                                    asset.ticker
+                                ),
+                            })
+                        } else {
+                            anyhow::Error::new(e)
+                        }
+                    })?
+                    .id;
+                asset_ids.insert(asset.id, id);
+                summary.custom_assets += 1;
+                embeddings.push(PendingEmbedding::Asset(
+                    id,
+                    format!("Asset: {} | Ticker: {}", asset.name, asset.ticker),
+                ));
+            }
+            pending_assets = waiting;
+        }
+
+        let mut rates = Vec::new();
+        for pair in archive.asset_pairs {
+            let query = asset_queries::inser_pair(AssetPair {
+                pair1: remap(&asset_ids, pair.pair1, "asset")?,
+                pair2: remap(&asset_ids, pair.pair2, "asset")?,
+            });
+            let pair_id = self.db.fetch_one::<AssetPairId>(query).await?.id;
+            rates.extend(pair.rates.into_iter().map(|rate| AssetPairRateInsert {
+                pair_id,
+                rate: rate.rate,
+                recorded_at: rate.recorded_at,
+            }));
+        }
+        if !rates.is_empty() {
+            self.db
+                .copy_in(asset_queries::copy_in_pair_rates(rates))
+                .await?;
+        }
+
+        // Categories
+        let mut category_type_ids = shared.category_types;
+        let mut category_type_names = shared.category_type_names;
+        for category_type in archive.category_types {
+            let query = category_type_queries::insert_category_type(
+                user_id,
+                InsertCategoryTypeModel {
+                    category_type_name: category_type.name,
+                },
+            );
+            let created = self.db.fetch_one::<CategoryTypeModel>(query).await?;
+            category_type_ids.insert(category_type.id, created.id);
+            category_type_names.insert(created.id, created.category_type_name);
+        }
+
+        let mut category_ids = shared.categories;
+        for category in archive.categories {
+            let category_type = remap(&category_type_ids, category.category_type, "category type")?;
+            let query = category_queries::insert_category(
+                user_id,
+                InsertCategoryModel {
+                    category: category.name.clone(),
+                    icon: category.icon,
+                    category_type,
+                },
+            );
+            let id: i32 = self.db.fetch_one_scalar(query).await?;
+            category_ids.insert(category.id, id);
+            summary.categories += 1;
+            embeddings.push(PendingEmbedding::Category(
+                id,
+                format!(
+                    "Category: {} | Type: {}",
+                    category.name,
+                    category_type_names
+                        .get(&category_type)
+                        .map(String::as_str)
+                        .unwrap_or_default()
+                ),
+            ));
+        }
+
+        // Transactions
+        let mut group_ids: HashMap<Uuid, Uuid> = HashMap::new();
+        for group in archive.transaction_groups {
+            let query =
+                transaction_group_queries::insert_transaction_group(AddTransactionGroupModel {
+                    category_id: remap(&category_ids, group.category_id, "category")?,
+                    description: group.description.clone(),
+                    date_added: group.date_added,
+                });
+            let id: Uuid = self.db.fetch_one_scalar(query).await?;
+            group_ids.insert(group.id, id);
+            embeddings.push(PendingEmbedding::Group(id, group.description));
+        }
+        summary.transaction_groups = group_ids.len();
+
+        let mut transaction_ids: HashMap<Uuid, Uuid> = HashMap::new();
+        for chunk in archive.transactions.chunks(INSERT_CHUNK_SIZE) {
+            let models = chunk
+                .iter()
+                .map(|transaction| {
+                    Ok(InsertArchivedTransactionModel {
+                        user_id,
+                        group_id: transaction
+                            .group_id
+                            .map(|id| remap(&group_ids, id, "transaction group"))
+                            .transpose()?,
+                        type_id: transaction.type_id,
+                        date_transacted: transaction.date_transacted,
+                        visibility: transaction.visibility.clone(),
+                    })
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?;
+            let ids: Vec<Uuid> = self
+                .db
+                .fetch_all_scalar(user_data_archive_queries::insert_archived_transactions(
+                    models,
+                ))
+                .await?;
+
+            let mut entries = Vec::new();
+            let mut descriptions = Vec::new();
+            let mut dividends = Vec::new();
+            for (transaction, id) in chunk.iter().zip(ids) {
+                transaction_ids.insert(transaction.id, id);
+                for entry in &transaction.entries {
+                    entries.push(AddEntryModel {
+                        asset_id: remap(&asset_ids, entry.asset_id, "asset")?,
+                        account_id: remap(&account_ids, entry.account_id, "account")?,
+                        quantity: entry.quantity,
+                        category_id: remap(&category_ids, entry.category_id, "category")?,
+                        transaction_id: id,
+                    });
+                }
+                if let Some(description) = &transaction.description {
+                    descriptions.push(AddTransactionDescriptionModel {
+                        transaction_id: id,
+                        description: description.clone(),
+                    });
+                    embeddings.push(PendingEmbedding::Transaction(id, description.clone()));
+                }
+                if let Some(source_asset_id) = transaction.dividend_source_asset_id {
+                    dividends.push(AddTransactionDividendModel {
+                        transaction_id: id,
+                        source_asset_id: remap(&asset_ids, source_asset_id, "asset")?,
+                    });
+                }
+            }
+
+            for entries in into_chunks(entries) {
+                self.db
+                    .execute(entries_queries::insert_entries(entries))
+                    .await?;
+            }
+            if !descriptions.is_empty() {
+                self.db
+                    .execute(transaction_data_queries::insert_descriptions(descriptions))
+                    .await?;
+            }
+            if !dividends.is_empty() {
+                self.db
+                    .execute(transaction_data_queries::insert_dividends(dividends))
+                    .await?;
+            }
+        }
+        summary.transactions = transaction_ids.len();
+
+        // Connectors. Stored credentials are not exported, so those connections wait
+        // for the user to authorise them again.
+        let mut connection_ids: HashMap<Uuid, Uuid> = HashMap::new();
+        for connection in archive.connector_connections {
+            let provider_id: Uuid = self
+                .db
+                .fetch_one_scalar(connector_queries::get_connector_provider_id_by_kind(
+                    connection.provider_kind.clone(),
+                ))
+                .await
+                .map_err(|_| {
+                    bad_request(format!(
+                        "unknown connector provider: {}",
+                        connection.provider_kind
+                    ))
+                })?;
+            let needs_consent = connection.credential_mode == "stored";
+            let query =
+                connector_queries::insert_connector_connection(AddConnectorConnectionModel {
+                    user_id,
+                    provider_id,
+                    credential_mode: connection.credential_mode,
+                    provider_key_id: None,
+                    status: if needs_consent {
+                        "pending_oauth".to_string()
+                    } else {
+                        connection.status
+                    },
+                    consent_expires_at: if needs_consent {
+                        None
+                    } else {
+                        connection.consent_expires_at
+                    },
+                });
+            let id: Uuid = self.db.fetch_one_scalar(query).await?;
+            connection_ids.insert(connection.id, id);
+        }
+
+        let mut provider_account_ids: HashMap<Uuid, Uuid> = HashMap::new();
+        for provider_account in archive.connector_provider_accounts {
+            let query = connector_queries::get_or_create_provider_account(
+                AddConnectorProviderAccountModel {
+                    connection_id: remap(
+                        &connection_ids,
+                        provider_account.connection_id,
+                        "connector connection",
+                    )?,
+                    external_account_id: provider_account.external_account_id,
+                },
+            );
+            let id: Uuid = self.db.fetch_one_scalar(query).await?;
+            provider_account_ids.insert(provider_account.id, id);
+        }
+
+        let mut binding_ids: HashMap<Uuid, Uuid> = HashMap::new();
+        for binding in archive.connector_bindings {
+            let query = connector_queries::insert_connector_binding(AddConnectorBindingModel {
+                provider_account_ref: remap(
+                    &provider_account_ids,
+                    binding.provider_account_id,
+                    "connector provider account",
+                )?,
+                sverto_account_id: remap(&account_ids, binding.account_id, "account")?,
+                write_mode: binding.write_mode,
+                status: binding.status,
+            });
+            let id: Uuid = self.db.fetch_one_scalar(query).await?;
+            binding_ids.insert(binding.id, id);
+        }
+        summary.connector_bindings = binding_ids.len();
+
+        let mut edited_transaction_ids = Vec::new();
+        let ledger = archive
+            .connector_transactions
+            .into_iter()
+            .map(|entry| {
+                let transaction_id = entry
+                    .transaction_id
+                    .and_then(|id| transaction_ids.get(&id).copied());
+                if entry.edited_by_user {
+                    edited_transaction_ids.extend(transaction_id);
+                }
+                Ok(AddConnectorTransactionModel {
+                    binding_id: remap(&binding_ids, entry.binding_id, "connector binding")?,
+                    transaction_id,
+                    external_id: entry.external_id,
+                    external_hash: entry.external_hash,
+                    counterparty: entry.counterparty,
+                })
+            })
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        for chunk in into_chunks(ledger) {
+            self.db
+                .execute(connector_queries::insert_connector_transactions(chunk))
+                .await?;
+        }
+        for chunk in into_chunks(edited_transaction_ids) {
+            self.db
+                .execute(connector_queries::mark_connector_transactions_edited(chunk))
+                .await?;
+        }
+
+        Ok((summary, embeddings))
+    }
+}
+
+fn bad_request(message: String) -> anyhow::Error {
+    anyhow::Error::new(BusinessBadRequestError { message })
+}
+
+fn into_chunks<T>(mut items: Vec<T>) -> Vec<Vec<T>> {
+    let mut chunks = Vec::new();
+    while items.len() > INSERT_CHUNK_SIZE {
+        let rest = items.split_off(INSERT_CHUNK_SIZE);
+        chunks.push(items);
+        items = rest;
+    }
+    if !items.is_empty() {
+        chunks.push(items);
+    }
+    chunks
+}
+
+fn remap<K, V>(ids: &HashMap<K, V>, id: K, kind: &str) -> anyhow::Result<V>
+where
+    K: Eq + Hash + Display,
+    V: Copy,
+{
+    ids.get(&id)
+        .copied()
+        .ok_or_else(|| bad_request(format!("archive refers to unknown {kind} {id}")))
+}
+
+/// Maps archive ids of shared reference data to ids on this instance, failing with the
+/// full list of names this instance does not know.
+fn resolve_by_name<'a>(
+    kind: &str,
+    references: impl Iterator<Item = (i32, &'a str)>,
+    lookup: impl Fn(&str) -> Option<i32>,
+) -> anyhow::Result<HashMap<i32, i32>> {
+    let mut ids = HashMap::new();
+    let mut missing = Vec::new();
+    for (archive_id, name) in references {
+        match lookup(name) {
+            Some(id) => {
+                ids.insert(archive_id, id);
+            }
+            None => missing.push(name),
+        }
+    }
+    if !missing.is_empty() {
+        return Err(bad_request(format!(
+            "archive uses {kind} this server does not have: {}",
+            missing.join(", ")
+        )));
+    }
+    Ok(ids)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_by_name_lists_every_missing_reference() {
+        let known = HashMap::from([("GBP", 45)]);
+
+        let err = resolve_by_name(
+            "assets",
+            [(1, "GBP"), (2, "XYZ"), (3, "ABC")].into_iter(),
+            |ticker| known.get(ticker).copied(),
+        )
+        .unwrap_err();
+
+        assert_eq!(
+            err.to_string(),
+            "archive uses assets this server does not have: XYZ, ABC"
+        );
+    }
+
+    #[test]
+    fn resolve_by_name_maps_archive_ids() {
+        let known = HashMap::from([("GBP", 45), ("USD", 140)]);
+
+        let ids = resolve_by_name("assets", [(7, "USD"), (9, "GBP")].into_iter(), |ticker| {
+            known.get(ticker).copied()
+        })
+        .unwrap();
+
+        assert_eq!(ids, HashMap::from([(7, 140), (9, 45)]));
+    }
+}
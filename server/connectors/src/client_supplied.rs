@@ -1,6 +1,6 @@
 use crate::models::balance::ProviderBalance;
 use crate::models::sync::{FetchedPage, RawPage, SyncCursor};
-use crate::models::transaction::{ProviderTransaction, SkippedTransaction};
+use crate::models::transaction::{MappedPages, SkippedTransaction};
 use crate::port::Connector;
 use crate::provider::ProviderKind;
 use crate::Result;
@@ -63,11 +63,18 @@ impl Connector for ClientSuppliedConnector {
     }
 
     async fn fetch_balance(&self) -> Result<ProviderBalance> {
+        // Statement files and clients that only push transactions send no balance.
+        if self.raw_balance.is_null() {
+            return Ok(ProviderBalance {
+                quantities: Vec::new(),
+                cash: Vec::new(),
+            });
+        }
         let balance: ProviderBalance = serde_json::from_value(self.raw_balance.clone())?;
         Ok(balance)
     }
 
-    fn map_pages(&self, pages: &[RawPage]) -> Vec<ProviderTransaction> {
+    fn map_pages(&self, pages: &[RawPage]) -> MappedPages {
         if let Ok(kind) = self.provider_kind.parse::<ProviderKind>() {
             return crate::provider::map_pages(kind, pages);
         }
@@ -95,6 +102,9 @@ impl Connector for ClientSuppliedConnector {
         }
 
         crate::models::transaction::log_skipped(&self.provider_kind, &skipped);
-        Vec::new()
+        MappedPages {
+            transactions: Vec::new(),
+            skipped,
+        }
     }
 }
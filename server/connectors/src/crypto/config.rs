@@ -0,0 +1,29 @@
+const DEFAULT_KRAKEN_API_BASE: &str = "https://api.kraken.com";
+const DEFAULT_BITCOIN_EXPLORER_URL: &str = "https://blockstream.info/api";
+const DEFAULT_ETHEREUM_EXPLORER_URL: &str = "https://api.etherscan.io/api";
+
+/// Endpoints of the crypto connectors. The explorers only need to speak the Esplora
+/// (Bitcoin) and Etherscan (Ethereum) APIs, so self-hosted instances work too.
+#[derive(Clone, Debug)]
+pub struct CryptoConfig {
+    pub kraken_api_base: String,
+    pub bitcoin_explorer_url: String,
+    pub ethereum_explorer_url: String,
+    pub ethereum_explorer_api_key: Option<String>,
+}
+
+impl CryptoConfig {
+    pub(crate) fn from_env() -> Self {
+        let url = |name: &str, default: &str| {
+            std::env::var(name)
+                .map(|v| v.trim_end_matches('/').to_string())
+                .unwrap_or_else(|_| default.to_string())
+        };
+        Self {
+            kraken_api_base: url("KRAKEN_API_BASE", DEFAULT_KRAKEN_API_BASE),
+            bitcoin_explorer_url: url("BITCOIN_EXPLORER_URL", DEFAULT_BITCOIN_EXPLORER_URL),
+            ethereum_explorer_url: url("ETHEREUM_EXPLORER_URL", DEFAULT_ETHEREUM_EXPLORER_URL),
+            ethereum_explorer_api_key: std::env::var("ETHEREUM_EXPLORER_API_KEY").ok(),
+        }
+    }
+}
@@ -0,0 +1,354 @@
+use rust_decimal::Decimal;
+use serde_json::Value;
+
+use crate::models::{
+    MappedTransaction, ProviderTransaction, ProviderTransactionKind, SkippedTransaction,
+};
+
+/// Kraken's four-letter codes from before it dropped the `X` (crypto) / `Z` (fiat) prefix.
+const LEGACY_ASSETS: &[&str] = &[
+    "XXBT", "XETH", "XLTC", "XXRP", "XXLM", "XETC", "XREP", "XZEC", "XXMR", "XMLN", "XXDG", "ZEUR",
+    "ZUSD", "ZGBP", "ZCAD", "ZJPY", "ZAUD", "ZCHF",
+];
+
+/// Quote assets a trading pair can end with, longest first so `USDT` wins over `USD`.
+const QUOTE_ASSETS: &[&str] = &[
+    "USDT", "USDC", "ZEUR", "ZUSD", "ZGBP", "ZCAD", "ZJPY", "ZAUD", "ZCHF", "XXBT", "XETH", "EUR",
+    "USD", "GBP", "CAD", "JPY", "AUD", "CHF", "XBT", "ETH", "DAI",
+];
+
+const FIAT_ASSETS: &[&str] = &["EUR", "USD", "GBP", "CAD", "JPY", "AUD", "CHF"];
+
+fn skipped(external_id: impl Into<String>, reason: impl Into<String>) -> MappedTransaction {
+    MappedTransaction::Skipped(SkippedTransaction {
+        external_id: external_id.into(),
+        reason: reason.into(),
+    })
+}
+
+/// Kraken's asset code as the ticker Sverto knows it: the legacy prefix and any
+/// staking/earn suffix (`DOT.S`, `ETH2.S`, `XBT.M`) are dropped, `XBT`/`XDG` renamed.
+pub fn normalize_asset(code: &str) -> String {
+    let base = code.split('.').next().unwrap_or(code);
+    let base = if LEGACY_ASSETS.contains(&base) {
+        &base[1..]
+    } else {
+        base
+    };
+    match base {
+        "XBT" => "BTC",
+        "XDG" => "DOGE",
+        "ETH2" => "ETH",
+        other => other,
+    }
+    .to_string()
+}
+
+pub fn is_fiat(asset: &str) -> bool {
+    FIAT_ASSETS.contains(&asset)
+}
+
+/// Base and quote of a trading pair (`XXBTZEUR`, `DOTEUR`, `XBT/USDT`), normalised.
+pub fn split_pair(pair: &str) -> Option<(String, String)> {
+    if let Some((base, quote)) = pair.split_once('/') {
+        return Some((normalize_asset(base), normalize_asset(quote)));
+    }
+    if pair.len() == 8 && LEGACY_ASSETS.contains(&&pair[..4]) && LEGACY_ASSETS.contains(&&pair[4..])
+    {
+        return Some((normalize_asset(&pair[..4]), normalize_asset(&pair[4..])));
+    }
+    QUOTE_ASSETS.iter().find_map(|quote| {
+        let base = pair.strip_suffix(quote)?;
+        (!base.is_empty()).then(|| (normalize_asset(base), normalize_asset(quote)))
+    })
+}
+
+fn parse_time(raw: &Value) -> Option<time::OffsetDateTime> {
+    let secs = raw
+        .get("time")
+        .and_then(|v| v.as_f64().or_else(|| v.as_str()?.parse().ok()))?;
+    time::OffsetDateTime::from_unix_timestamp_nanos((secs * 1e9).round() as i128).ok()
+}
+
+fn decimal(raw: &Value, key: &str) -> Option<Decimal> {
+    raw.get(key).and_then(crate::util::parse_decimal)
+}
+
+/// A filled trade from `TradesHistory`. The fee, charged in the quote asset, is folded into
+/// the quote amount like Trading212's net fill value.
+pub fn map_trade(raw: &Value) -> MappedTransaction {
+    let Some(external_id) = raw.get("txid").and_then(|v| v.as_str()).map(str::to_string) else {
+        return skipped("<missing txid>", "missing txid — cannot identify trade");
+    };
+    let Some(pair) = raw.get("pair").and_then(|v| v.as_str()) else {
+        return skipped(external_id, "missing pair");
+    };
+    let Some((base, quote)) = split_pair(pair) else {
+        return skipped(external_id, format!("unrecognised pair {pair}"));
+    };
+    let buy = match raw.get("type").and_then(|v| v.as_str()) {
+        Some("buy") => true,
+        Some("sell") => false,
+        other => {
+            return skipped(
+                external_id,
+                format!("unknown trade type {}", other.unwrap_or("<missing>")),
+            )
+        }
+    };
+    let (Some(volume), Some(cost)) = (decimal(raw, "vol"), decimal(raw, "cost")) else {
+        return skipped(external_id, "missing or unparseable volume or cost");
+    };
+    let fee = decimal(raw, "fee").unwrap_or_default();
+    let Some(date) = parse_time(raw) else {
+        return skipped(external_id, "missing or unparseable time");
+    };
+
+    let (quantity, amount) = if buy {
+        (volume.abs(), -(cost.abs() + fee))
+    } else {
+        (-volume.abs(), cost.abs() - fee)
+    };
+
+    MappedTransaction::Provider(ProviderTransaction {
+        external_id,
+        amount,
+        currency: quote,
+        date,
+        description: format!(
+            "{} {} {base}",
+            if buy { "Buy" } else { "Sell" },
+            volume.abs()
+        ),
+        asset_identifier: Some(base),
+        quantity: Some(quantity),
+        kind: ProviderTransactionKind::Trade,
+        counterparty: None,
+    })
+}
+
+/// A `Ledgers` entry. Trades are imported from their own stream and internal transfers
+/// (spot to staking and back) net to zero, so both map to nothing.
+pub fn map_ledger_entry(raw: &Value) -> Vec<MappedTransaction> {
+    let Some(external_id) = raw
+        .get("ledger_id")
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+    else {
+        return vec![skipped(
+            "<missing ledger id>",
+            "missing ledger id — cannot identify entry",
+        )];
+    };
+    let entry_type = raw
+        .get("type")
+        .and_then(|v| v.as_str())
+        .unwrap_or("unknown");
+    let subtype = raw.get("subtype").and_then(|v| v.as_str()).unwrap_or("");
+    let Some(amount) = decimal(raw, "amount") else {
+        return vec![skipped(external_id, "missing or unparseable amount")];
+    };
+
+    let asset = raw
+        .get("asset")
+        .and_then(|v| v.as_str())
+        .map(normalize_asset);
+    // Fiat only ever moves between Kraken and a bank account; coins move between wallets.
+    let fiat = asset.as_deref().is_some_and(is_fiat);
+
+    let kind = match (entry_type, subtype) {
+        ("trade", _) | ("transfer", _) => return vec![],
+        ("deposit", _) if fiat => ProviderTransactionKind::CashTransferIn,
+        ("deposit", _) => ProviderTransactionKind::TransferIn,
+        ("withdrawal", _) if fiat => ProviderTransactionKind::CashTransferOut,
+        ("withdrawal", _) => ProviderTransactionKind::TransferOut,
+        ("staking", _) | ("earn", "reward") if amount.is_sign_positive() => {
+            ProviderTransactionKind::Reward
+        }
+        ("staking", _) | ("earn", _) => return vec![],
+        (other, _) => {
+            return vec![skipped(
+                external_id,
+                format!("{other} ledger entries are not imported"),
+            )]
+        }
+    };
+
+    let Some(asset) = asset else {
+        return vec![skipped(external_id, "missing asset")];
+    };
+    let Some(date) = parse_time(raw) else {
+        return vec![skipped(external_id, "missing or unparseable time")];
+    };
+    let fee = decimal(raw, "fee").unwrap_or_default();
+
+    let label = match kind {
+        ProviderTransactionKind::TransferIn | ProviderTransactionKind::CashTransferIn => "Deposit",
+        ProviderTransactionKind::TransferOut | ProviderTransactionKind::CashTransferOut => {
+            "Withdrawal"
+        }
+        _ => "Staking reward",
+    };
+    let mut mapped = Vec::with_capacity(2);
+    if !amount.is_zero() {
+        mapped.push(MappedTransaction::Provider(ProviderTransaction {
+            external_id: external_id.clone(),
+            amount,
+            currency: asset.clone(),
+            date,
+            description: format!("{label} {asset}"),
+            asset_identifier: None,
+            quantity: None,
+            kind,
+            counterparty: None,
+        }));
+    }
+    if !fee.is_zero() {
+        mapped.push(MappedTransaction::Provider(ProviderTransaction {
+            external_id: format!("{external_id}:fee"),
+            amount: -fee.abs(),
+            currency: asset.clone(),
+            date,
+            description: format!("{label} fee {asset}"),
+            asset_identifier: None,
+            quantity: None,
+            kind: ProviderTransactionKind::Fee,
+            counterparty: None,
+        }));
+    }
+    mapped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+    use serde_json::json;
+
+    fn provider(mapped: MappedTransaction) -> ProviderTransaction {
+        match mapped {
+            MappedTransaction::Provider(tx) => tx,
+            MappedTransaction::Skipped(s) => panic!("unexpectedly skipped: {}", s.reason),
+        }
+    }
+
+    #[test]
+    fn normalizes_assets_and_pairs() {
+        assert_eq!(normalize_asset("XXBT"), "BTC");
+        assert_eq!(normalize_asset("ZEUR"), "EUR");
+        assert_eq!(normalize_asset("DOT.S"), "DOT");
+        assert_eq!(normalize_asset("ETH2.S"), "ETH");
+        assert_eq!(normalize_asset("ZEUS"), "ZEUS");
+        assert_eq!(
+            split_pair("XXBTZEUR"),
+            Some(("BTC".to_string(), "EUR".to_string()))
+        );
+        assert_eq!(
+            split_pair("SOLUSDT"),
+            Some(("SOL".to_string(), "USDT".to_string()))
+        );
+        assert_eq!(
+            split_pair("XETHXXBT"),
+            Some(("ETH".to_string(), "BTC".to_string()))
+        );
+        assert_eq!(split_pair("EUR"), None);
+    }
+
+    #[test]
+    fn maps_buy_with_fee_folded_into_quote_amount() {
+        let tx = provider(map_trade(&json!({
+            "txid": "TX-1",
+            "pair": "XXBTZEUR",
+            "type": "buy",
+            "time": 1_700_000_000.25,
+            "vol": "0.50000000",
+            "cost": "15000.00",
+            "fee": "24.00",
+        })));
+        assert_eq!(tx.kind, ProviderTransactionKind::Trade);
+        assert_eq!(tx.asset_identifier.as_deref(), Some("BTC"));
+        assert_eq!(tx.quantity, Some(dec!(0.5)));
+        assert_eq!(tx.currency, "EUR");
+        assert_eq!(tx.amount, dec!(-15024.00));
+        assert_eq!(tx.date.unix_timestamp(), 1_700_000_000);
+    }
+
+    #[test]
+    fn maps_withdrawal_with_network_fee() {
+        let mapped = map_ledger_entry(&json!({
+            "ledger_id": "L-1",
+            "refid": "R-1",
+            "type": "withdrawal",
+            "subtype": "",
+            "asset": "XETH",
+            "time": 1_700_000_000.0,
+            "amount": "-1.2500000000",
+            "fee": "0.0035000000",
+        }));
+        let [withdrawal, fee] = <[MappedTransaction; 2]>::try_from(mapped)
+            .unwrap_or_else(|_| panic!("expected withdrawal and fee"));
+        let withdrawal = provider(withdrawal);
+        let fee = provider(fee);
+        assert_eq!(withdrawal.kind, ProviderTransactionKind::TransferOut);
+        assert_eq!(withdrawal.amount, dec!(-1.25));
+        assert_eq!(withdrawal.currency, "ETH");
+        assert_eq!(fee.kind, ProviderTransactionKind::Fee);
+        assert_eq!(fee.external_id, "L-1:fee");
+        assert_eq!(fee.amount, dec!(-0.0035));
+    }
+
+    #[test]
+    fn maps_fiat_funding_as_cash_transfers() {
+        let deposit = map_ledger_entry(&json!({
+            "ledger_id": "L-5", "type": "deposit", "subtype": "", "asset": "ZEUR",
+            "time": 1_700_000_000.0, "amount": "500.0000", "fee": "0",
+        }));
+        let deposit = provider(deposit.into_iter().next().unwrap());
+        assert_eq!(deposit.kind, ProviderTransactionKind::CashTransferIn);
+        assert_eq!(deposit.currency, "EUR");
+
+        let withdrawal = map_ledger_entry(&json!({
+            "ledger_id": "L-6", "type": "withdrawal", "subtype": "", "asset": "ZEUR",
+            "time": 1_700_000_000.0, "amount": "-200.0000", "fee": "0.0900",
+        }));
+        let kinds: Vec<ProviderTransactionKind> = withdrawal
+            .into_iter()
+            .map(|mapped| provider(mapped).kind)
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ProviderTransactionKind::CashTransferOut,
+                ProviderTransactionKind::Fee
+            ]
+        );
+    }
+
+    #[test]
+    fn maps_staking_rewards_and_ignores_internal_movements() {
+        let reward = map_ledger_entry(&json!({
+            "ledger_id": "L-2", "type": "earn", "subtype": "reward", "asset": "DOT.S",
+            "time": 1_700_000_000.0, "amount": "0.0421", "fee": "0",
+        }));
+        assert_eq!(reward.len(), 1);
+        let reward = provider(reward.into_iter().next().unwrap());
+        assert_eq!(reward.kind, ProviderTransactionKind::Reward);
+        assert_eq!(reward.currency, "DOT");
+
+        for entry_type in ["trade", "transfer"] {
+            assert!(map_ledger_entry(&json!({
+                "ledger_id": "L-3", "type": entry_type, "asset": "XXBT",
+                "time": 1_700_000_000.0, "amount": "0.1", "fee": "0",
+            }))
+            .is_empty());
+        }
+        assert!(matches!(
+            map_ledger_entry(&json!({
+                "ledger_id": "L-4", "type": "margin", "asset": "XXBT",
+                "time": 1_700_000_000.0, "amount": "0.1", "fee": "0",
+            }))
+            .as_slice(),
+            [MappedTransaction::Skipped(_)]
+        ));
+    }
+}
@@ -0,0 +1,233 @@
+use std::sync::OnceLock;
+
+use async_trait::async_trait;
+
+use crate::crypto::config::CryptoConfig;
+use crate::crypto::kraken::client::KrakenClient;
+use crate::models::account::ProviderAccount;
+use crate::port::{Connector, ConnectorStore};
+use crate::provider::{CredentialSource, Provider, ProviderKind};
+use crate::Result;
+
+/// Kraken through a read-only API key: the connection's `provider_key_id` is the key, its
+/// credential the base64 private key. Keys only need the query funds and query
+/// ledger/trade history permissions.
+pub struct KrakenProvider {
+    config: OnceLock<CryptoConfig>,
+}
+
+impl KrakenProvider {
+    pub const fn from_env() -> Self {
+        Self {
+            config: OnceLock::new(),
+        }
+    }
+
+    pub fn with_config(config: CryptoConfig) -> Self {
+        Self {
+            config: OnceLock::from(config),
+        }
+    }
+
+    fn config(&self) -> &CryptoConfig {
+        self.config.get_or_init(CryptoConfig::from_env)
+    }
+}
+
+#[async_trait]
+impl Provider for KrakenProvider {
+    fn kind(&self) -> ProviderKind {
+        ProviderKind::Kraken
+    }
+
+    async fn build_connector(
+        &self,
+        _provider_account_id: &str,
+        credential: CredentialSource,
+        store: &dyn ConnectorStore,
+    ) -> Result<Box<dyn Connector>> {
+        let api_key = store
+            .provider_key_id()
+            .ok_or_else(|| anyhow::anyhow!("kraken connection has no provider_key_id"))?;
+        let api_secret = match credential {
+            CredentialSource::Transient(secret) => secret,
+            CredentialSource::Stored => {
+                let bytes = store
+                    .get_credential()
+                    .await?
+                    .ok_or_else(|| anyhow::anyhow!("Credential not found"))?;
+                String::from_utf8(bytes)?
+            }
+        };
+        Ok(Box::new(KrakenClient::new(
+            self.config(),
+            api_key,
+            api_secret,
+        )))
+    }
+
+    fn resolve_provider_account_id(
+        &self,
+        _client_value: Option<String>,
+        store: &dyn ConnectorStore,
+    ) -> Result<String> {
+        Ok(store
+            .provider_key_id()
+            .unwrap_or_else(|| "default".to_string()))
+    }
+
+    async fn list_accounts(&self, store: &dyn ConnectorStore) -> Result<Vec<ProviderAccount>> {
+        Ok(vec![ProviderAccount {
+            provider_account_id: store
+                .provider_key_id()
+                .unwrap_or_else(|| "default".to_string()),
+            display_name: "Kraken".to_string(),
+            currency: None,
+            account_type: None,
+        }])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use rust_decimal_macros::dec;
+    use serde_json::json;
+
+    use super::*;
+    use crate::mock_http::{MockHttpServer, MockRoute};
+    use crate::models::sync::{FetchedPage, RawPage};
+    use crate::models::ProviderTransactionKind;
+    use crate::port::{MockConnectorStore, SyncParams, SyncRunOutcome};
+
+    fn config(server: &MockHttpServer) -> CryptoConfig {
+        CryptoConfig {
+            kraken_api_base: server.url().to_string(),
+            bitcoin_explorer_url: server.url().to_string(),
+            ethereum_explorer_url: server.url().to_string(),
+            ethereum_explorer_api_key: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn syncs_trades_and_ledgers_from_mock_exchange() {
+        let server = MockHttpServer::start(vec![
+            MockRoute::post(
+                "/0/private/TradesHistory",
+                json!({ "error": [], "result": { "count": 1, "trades": {
+                    "TX-1": { "pair": "XXBTZEUR", "type": "buy", "time": 1_700_000_000.0, "vol": "0.1", "cost": "3000.0", "fee": "4.8" }
+                } } }),
+            ),
+            MockRoute::post(
+                "/0/private/Ledgers",
+                json!({ "error": [], "result": { "count": 3, "ledger": {
+                    "L-1": { "refid": "TX-1", "type": "trade", "asset": "XXBT", "time": 1_700_000_000.0, "amount": "0.1", "fee": "0" },
+                    "L-2": { "refid": "W-1", "type": "withdrawal", "asset": "XXBT", "time": 1_700_100_000.0, "amount": "-0.05", "fee": "0.0001" },
+                    "L-3": { "refid": "S-1", "type": "staking", "asset": "DOT.S", "time": 1_700_200_000.0, "amount": "0.5", "fee": "0" }
+                } } }),
+            ),
+            MockRoute::post(
+                "/0/private/Balance",
+                json!({ "error": [], "result": { "XXBT": "0.0499", "ZEUR": "120.50", "DOT.S": "0.5", "XETH": "0.0" } }),
+            ),
+        ])
+        .await;
+        let provider = KrakenProvider::with_config(config(&server));
+
+        let pages = Arc::new(Mutex::new(Vec::<FetchedPage>::new()));
+        let mut store = MockConnectorStore::new();
+        store
+            .expect_provider_key_id()
+            .returning(|| Some("api-key".to_string()));
+        store
+            .expect_get_credential()
+            .returning(|| Ok(Some(b"c2VjcmV0".to_vec())));
+        store.expect_latest_cursor().returning(|| Ok(None));
+        let appended = pages.clone();
+        store.expect_append_page().returning(move |page| {
+            appended.lock().unwrap().push(page.clone());
+            Ok(())
+        });
+
+        let connector = provider
+            .build_connector("api-key", CredentialSource::Stored, &store)
+            .await
+            .unwrap();
+        let outcome = connector
+            .sync(
+                &store,
+                SyncParams {
+                    synced_through: None,
+                    budget: None,
+                    inline_retries: 0,
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(outcome, SyncRunOutcome::Complete { pages_fetched: 2 });
+
+        let trades_request = &server.requests_to("POST", "/0/private/TradesHistory")[0];
+        assert_eq!(trades_request.header("api-key"), Some("api-key"));
+        assert!(trades_request.header("api-sign").is_some());
+        assert!(trades_request.body.starts_with("nonce="));
+
+        let raw_pages: Vec<RawPage> = pages
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|page| RawPage {
+                stream: page.stream.clone(),
+                payload: page.payload.clone(),
+            })
+            .collect();
+        let mapped = connector.map_pages(&raw_pages);
+        let kinds: Vec<ProviderTransactionKind> =
+            mapped.transactions.iter().map(|tx| tx.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ProviderTransactionKind::Trade,
+                ProviderTransactionKind::TransferOut,
+                ProviderTransactionKind::Fee,
+                ProviderTransactionKind::Reward,
+            ]
+        );
+        assert_eq!(mapped.transactions[0].amount, dec!(-3004.8));
+        assert!(mapped.skipped.is_empty());
+
+        let balance = connector.fetch_balance().await.unwrap();
+        assert_eq!(balance.cash.len(), 1);
+        assert_eq!(balance.cash[0].currency, "EUR");
+        let mut holdings: Vec<(String, rust_decimal::Decimal)> = balance
+            .quantities
+            .into_iter()
+            .map(|q| (q.asset_identifier, q.quantity))
+            .collect();
+        holdings.sort();
+        assert_eq!(
+            holdings,
+            vec![
+                ("BTC".to_string(), dec!(0.0499)),
+                ("DOT".to_string(), dec!(0.5)),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn surfaces_kraken_errors() {
+        let server = MockHttpServer::start(vec![MockRoute::post(
+            "/0/private/TradesHistory",
+            json!({ "error": ["EAPI:Invalid key"] }),
+        )])
+        .await;
+        let connector = KrakenClient::new(
+            &config(&server),
+            "api-key".to_string(),
+            "c2VjcmV0".to_string(),
+        );
+
+        let err = connector.fetch_page(None, None).await.unwrap_err();
+        assert!(err.to_string().contains("EAPI:Invalid key"));
+    }
+}
@@ -0,0 +1,7 @@
+//! Crypto connectors: exchange accounts read through read-only API keys, and public
+//! addresses watched through a block explorer. Both report movements in the coin itself,
+//! so their mappers set an explicit [`crate::models::ProviderTransactionKind`].
+
+pub mod config;
+pub mod kraken;
+pub mod wallet;
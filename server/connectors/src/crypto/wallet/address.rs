@@ -0,0 +1,124 @@
+use std::fmt;
+use std::str::FromStr;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Chain {
+    Bitcoin,
+    Ethereum,
+}
+
+impl Chain {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Chain::Bitcoin => "btc",
+            Chain::Ethereum => "eth",
+        }
+    }
+
+    /// Raw page stream the chain's transactions are archived under.
+    pub fn stream(self) -> &'static str {
+        match self {
+            Chain::Bitcoin => "bitcoin",
+            Chain::Ethereum => "ethereum",
+        }
+    }
+
+    /// Ticker of the chain's native coin.
+    pub fn coin(self) -> &'static str {
+        match self {
+            Chain::Bitcoin => "BTC",
+            Chain::Ethereum => "ETH",
+        }
+    }
+}
+
+/// A public address being watched, written `btc:<address>` or `eth:<address>` as a binding's
+/// provider account id. The prefix may be left out when the address format is unambiguous.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WatchedAddress {
+    pub chain: Chain,
+    pub address: String,
+}
+
+impl FromStr for WatchedAddress {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.trim();
+        let (chain, address) = match value.split_once(':') {
+            Some(("btc", address)) => (Chain::Bitcoin, address),
+            Some(("eth", address)) => (Chain::Ethereum, address),
+            Some((other, _)) => anyhow::bail!("unsupported chain: {other}"),
+            None if value.starts_with("0x") => (Chain::Ethereum, value),
+            None => (Chain::Bitcoin, value),
+        };
+
+        match chain {
+            Chain::Bitcoin => {
+                let bech32 = address.to_ascii_lowercase().starts_with("bc1");
+                let base58 = address.starts_with('1') || address.starts_with('3');
+                if !(bech32 || base58)
+                    || !(26..=62).contains(&address.len())
+                    || !address.chars().all(|c| c.is_ascii_alphanumeric())
+                {
+                    anyhow::bail!("not a bitcoin mainnet address: {address}");
+                }
+                // Bech32 is case-insensitive; base58 is not.
+                let address = if bech32 {
+                    address.to_ascii_lowercase()
+                } else {
+                    address.to_string()
+                };
+                Ok(Self { chain, address })
+            }
+            Chain::Ethereum => {
+                let hex = address
+                    .strip_prefix("0x")
+                    .filter(|hex| hex.len() == 40 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+                    .ok_or_else(|| anyhow::anyhow!("not an ethereum address: {address}"))?;
+                Ok(Self {
+                    chain,
+                    address: format!("0x{}", hex.to_ascii_lowercase()),
+                })
+            }
+        }
+    }
+}
+
+impl fmt::Display for WatchedAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.chain.as_str(), self.address)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_and_canonicalises_addresses() {
+        let btc: WatchedAddress = "BC1QAR0SRRR7XFKVY5L643LYDNW9RE59GTZZWF5MDQ"
+            .parse()
+            .unwrap();
+        assert_eq!(btc.chain, Chain::Bitcoin);
+        assert_eq!(
+            btc.to_string(),
+            "btc:bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq"
+        );
+
+        let eth: WatchedAddress = "0xDE0B295669A9FD93D5F28D9EC85E40F4CB697BAE"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            eth.to_string(),
+            "eth:0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae"
+        );
+        assert_eq!(eth.to_string().parse::<WatchedAddress>().unwrap(), eth);
+
+        assert!("eth:0x1234".parse::<WatchedAddress>().is_err());
+        assert!("sol:abc".parse::<WatchedAddress>().is_err());
+        assert!("tb1qtestnet000000000000000000000"
+            .parse::<WatchedAddress>()
+            .is_err());
+    }
+}
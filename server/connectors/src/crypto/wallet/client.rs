@@ -0,0 +1,216 @@
+use observability::{create_http_client, TracedHttpClient};
+use rust_decimal::Decimal;
+use serde_json::Value;
+use url::Url;
+
+use crate::crypto::config::CryptoConfig;
+use crate::crypto::wallet::address::{Chain, WatchedAddress};
+use crate::models::balance::{ProviderAssetBalance, ProviderBalance};
+use crate::models::sync::{FetchedPage, RawPage, SyncCursor};
+use crate::models::transaction::MappedPages;
+use crate::port::Connector;
+use crate::Result;
+
+/// Confirmed transactions per Esplora `txs/chain` page; fixed by the API.
+const ESPLORA_PAGE_SIZE: usize = 25;
+/// Etherscan caps `page * offset` at 10,000, which bounds how far back an address is read.
+const ETHERSCAN_PAGE_SIZE: usize = 100;
+
+pub struct WalletClient {
+    http: TracedHttpClient,
+    watched: WatchedAddress,
+    bitcoin_explorer_url: String,
+    ethereum_explorer_url: String,
+    ethereum_explorer_api_key: Option<String>,
+}
+
+impl WalletClient {
+    pub fn new(config: &CryptoConfig, watched: WatchedAddress) -> Self {
+        Self {
+            http: create_http_client(),
+            watched,
+            bitcoin_explorer_url: config.bitcoin_explorer_url.clone(),
+            ethereum_explorer_url: config.ethereum_explorer_url.clone(),
+            ethereum_explorer_api_key: config.ethereum_explorer_api_key.clone(),
+        }
+    }
+
+    async fn get_json(&self, url: &str, query: &[(&str, String)]) -> Result<Value> {
+        let mut url = Url::parse(url)?;
+        if !query.is_empty() {
+            url.query_pairs_mut().extend_pairs(query);
+        }
+        let resp = crate::util::ensure_success(self.http.get(url).send().await?).await?;
+        Ok(resp.json().await?)
+    }
+
+    /// Calls the Etherscan-style API. It answers status `0` both for errors and for an
+    /// address without transactions.
+    async fn etherscan(&self, params: &[(&str, String)]) -> Result<Value> {
+        let mut query: Vec<(&str, String)> = params.to_vec();
+        query.push(("address", self.watched.address.clone()));
+        if let Some(key) = &self.ethereum_explorer_api_key {
+            query.push(("apikey", key.clone()));
+        }
+        let mut body = self.get_json(&self.ethereum_explorer_url, &query).await?;
+        let status = body.get("status").and_then(|v| v.as_str()).unwrap_or("0");
+        let message = body.get("message").and_then(|v| v.as_str()).unwrap_or("");
+        if status != "1" && message != "No transactions found" {
+            anyhow::bail!(
+                "explorer returned {message}: {}",
+                body.get("result").cloned().unwrap_or_default()
+            );
+        }
+        Ok(body.get_mut("result").map(Value::take).unwrap_or_default())
+    }
+
+    async fn fetch_bitcoin_page(
+        &self,
+        cursor: Option<&Value>,
+    ) -> Result<(Vec<Value>, Option<Value>)> {
+        let mut url = format!(
+            "{}/address/{}/txs/chain",
+            self.bitcoin_explorer_url, self.watched.address
+        );
+        if let Some(last_txid) = cursor
+            .and_then(|c| c.get("last_txid"))
+            .and_then(|v| v.as_str())
+        {
+            url = format!("{url}/{last_txid}");
+        }
+        let txs: Vec<Value> = self
+            .get_json(&url, &[])
+            .await?
+            .as_array()
+            .cloned()
+            .unwrap_or_default();
+        let next = (txs.len() >= ESPLORA_PAGE_SIZE)
+            .then(|| txs.last().and_then(|tx| tx.get("txid")).cloned())
+            .flatten()
+            .map(|txid| serde_json::json!({ "last_txid": txid }));
+        Ok((txs, next))
+    }
+
+    async fn fetch_ethereum_page(
+        &self,
+        cursor: Option<&Value>,
+    ) -> Result<(Vec<Value>, Option<Value>)> {
+        let page = cursor
+            .and_then(|c| c.get("page"))
+            .and_then(|v| v.as_u64())
+            .unwrap_or(1);
+        let txs: Vec<Value> = self
+            .etherscan(&[
+                ("module", "account".to_string()),
+                ("action", "txlist".to_string()),
+                ("startblock", "0".to_string()),
+                ("endblock", "99999999".to_string()),
+                ("page", page.to_string()),
+                ("offset", ETHERSCAN_PAGE_SIZE.to_string()),
+                ("sort", "desc".to_string()),
+            ])
+            .await?
+            .as_array()
+            .cloned()
+            .unwrap_or_default();
+        let next =
+            (txs.len() >= ETHERSCAN_PAGE_SIZE).then(|| serde_json::json!({ "page": page + 1 }));
+        Ok((txs, next))
+    }
+}
+
+fn tx_time(chain: Chain, tx: &Value) -> Option<i64> {
+    match chain {
+        Chain::Bitcoin => tx.get("status")?.get("block_time")?.as_i64(),
+        Chain::Ethereum => tx.get("timeStamp")?.as_str()?.parse().ok(),
+    }
+}
+
+#[async_trait::async_trait]
+impl Connector for WalletClient {
+    /// Walks the address history newest first until a page reaches past `from`.
+    async fn fetch_page(
+        &self,
+        from: Option<time::OffsetDateTime>,
+        cursor: Option<SyncCursor>,
+    ) -> Result<FetchedPage> {
+        let chain = self.watched.chain;
+        let (txs, next) = match chain {
+            Chain::Bitcoin => {
+                self.fetch_bitcoin_page(cursor.as_ref().map(|c| c.as_value()))
+                    .await?
+            }
+            Chain::Ethereum => {
+                self.fetch_ethereum_page(cursor.as_ref().map(|c| c.as_value()))
+                    .await?
+            }
+        };
+        let covers_from = from.is_some_and(|from| {
+            txs.iter()
+                .any(|tx| tx_time(chain, tx).is_some_and(|ts| ts < from.unix_timestamp()))
+        });
+
+        let items = txs
+            .into_iter()
+            .map(|tx| serde_json::json!({ "address": self.watched.address, "tx": tx }))
+            .collect();
+        Ok(FetchedPage {
+            stream: chain.stream().to_string(),
+            payload: Value::Array(items),
+            next_cursor: if covers_from {
+                None
+            } else {
+                next.map(SyncCursor::new)
+            },
+        })
+    }
+
+    fn map_pages(&self, pages: &[RawPage]) -> MappedPages {
+        crate::provider::map_pages(crate::provider::ProviderKind::CryptoWallet, pages)
+    }
+
+    async fn fetch_balance(&self) -> Result<ProviderBalance> {
+        let quantity = match self.watched.chain {
+            Chain::Bitcoin => {
+                let body = self
+                    .get_json(
+                        &format!(
+                            "{}/address/{}",
+                            self.bitcoin_explorer_url, self.watched.address
+                        ),
+                        &[],
+                    )
+                    .await?;
+                let stat = |key: &str| {
+                    body.get("chain_stats")
+                        .and_then(|s| s.get(key))
+                        .and_then(|v| v.as_i64())
+                        .unwrap_or(0)
+                };
+                Decimal::new(stat("funded_txo_sum") - stat("spent_txo_sum"), 8)
+            }
+            Chain::Ethereum => {
+                let result = self
+                    .etherscan(&[
+                        ("module", "account".to_string()),
+                        ("action", "balance".to_string()),
+                        ("tag", "latest".to_string()),
+                    ])
+                    .await?;
+                let wei: i128 = result
+                    .as_str()
+                    .and_then(|s| s.parse().ok())
+                    .ok_or_else(|| anyhow::anyhow!("explorer returned no balance"))?;
+                Decimal::try_from_i128_with_scale(wei, 18)?
+            }
+        };
+
+        Ok(ProviderBalance {
+            quantities: vec![ProviderAssetBalance {
+                asset_identifier: self.watched.chain.coin().to_string(),
+                quantity: quantity.normalize(),
+            }],
+            cash: vec![],
+        })
+    }
+}
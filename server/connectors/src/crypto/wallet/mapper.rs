@@ -0,0 +1,324 @@
+use rust_decimal::Decimal;
+use serde_json::Value;
+
+use crate::models::{
+    MappedTransaction, ProviderTransaction, ProviderTransactionKind, SkippedTransaction,
+};
+
+const SATS_SCALE: u32 = 8;
+const WEI_SCALE: u32 = 18;
+
+fn skipped(external_id: impl Into<String>, reason: impl Into<String>) -> MappedTransaction {
+    MappedTransaction::Skipped(SkippedTransaction {
+        external_id: external_id.into(),
+        reason: reason.into(),
+    })
+}
+
+fn movement(
+    external_id: String,
+    kind: ProviderTransactionKind,
+    amount: Decimal,
+    coin: &str,
+    date: time::OffsetDateTime,
+    description: String,
+) -> MappedTransaction {
+    MappedTransaction::Provider(ProviderTransaction {
+        external_id,
+        amount,
+        currency: coin.to_string(),
+        date,
+        description,
+        asset_identifier: None,
+        quantity: None,
+        kind,
+        counterparty: None,
+    })
+}
+
+/// Archived items pair the explorer's transaction with the watched address, since the
+/// direction of a transfer depends on which side of it the address is.
+fn unwrap_item(item: &Value) -> Option<(&str, &Value)> {
+    Some((item.get("address")?.as_str()?, item.get("tx")?))
+}
+
+fn sats(value: Option<&Value>) -> i64 {
+    value.and_then(|v| v.as_i64()).unwrap_or(0)
+}
+
+fn wei(value: Option<&Value>) -> Option<i128> {
+    value?.as_str()?.parse().ok()
+}
+
+/// An Esplora transaction. When the address funded it, the whole fee is attributed to the
+/// address and what left it beyond change and fee is the transfer out.
+pub fn map_bitcoin_transaction(item: &Value) -> Vec<MappedTransaction> {
+    let Some((address, tx)) = unwrap_item(item) else {
+        return vec![skipped("<missing txid>", "malformed archived item")];
+    };
+    let Some(txid) = tx.get("txid").and_then(|v| v.as_str()).map(str::to_string) else {
+        return vec![skipped("<missing txid>", "missing txid")];
+    };
+    let Some(date) = tx
+        .get("status")
+        .filter(|s| s.get("confirmed").and_then(|v| v.as_bool()) == Some(true))
+        .and_then(|s| s.get("block_time"))
+        .and_then(|v| v.as_i64())
+        .and_then(|ts| time::OffsetDateTime::from_unix_timestamp(ts).ok())
+    else {
+        return vec![skipped(txid, "unconfirmed transaction")];
+    };
+
+    let touches =
+        |entry: &Value| entry.get("scriptpubkey_address").and_then(|v| v.as_str()) == Some(address);
+    let received: i64 = tx
+        .get("vout")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter(|out| touches(out))
+        .map(|out| sats(out.get("value")))
+        .sum();
+    let spent: i64 = tx
+        .get("vin")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter_map(|input| input.get("prevout"))
+        .filter(|prevout| touches(prevout))
+        .map(|prevout| sats(prevout.get("value")))
+        .sum();
+
+    let coin = "BTC";
+    if spent == 0 {
+        if received == 0 {
+            return vec![skipped(
+                txid,
+                "transaction does not touch the watched address",
+            )];
+        }
+        return vec![movement(
+            txid,
+            ProviderTransactionKind::TransferIn,
+            Decimal::new(received, SATS_SCALE).normalize(),
+            coin,
+            date,
+            format!("Received {coin}"),
+        )];
+    }
+    if received >= spent {
+        return vec![movement(
+            txid,
+            ProviderTransactionKind::TransferIn,
+            Decimal::new(received - spent, SATS_SCALE).normalize(),
+            coin,
+            date,
+            format!("Received {coin}"),
+        )];
+    }
+
+    let fee = sats(tx.get("fee")).min(spent - received);
+    let sent = spent - received - fee;
+    let mut mapped = Vec::with_capacity(2);
+    if sent > 0 {
+        mapped.push(movement(
+            txid.clone(),
+            ProviderTransactionKind::TransferOut,
+            -Decimal::new(sent, SATS_SCALE).normalize(),
+            coin,
+            date,
+            format!("Sent {coin}"),
+        ));
+    }
+    if fee > 0 {
+        mapped.push(movement(
+            format!("{txid}:fee"),
+            ProviderTransactionKind::Fee,
+            -Decimal::new(fee, SATS_SCALE).normalize(),
+            coin,
+            date,
+            format!("{coin} network fee"),
+        ));
+    }
+    mapped
+}
+
+/// An Etherscan `txlist` entry. The sender pays gas even when the call reverted, in which
+/// case no value moved.
+pub fn map_ethereum_transaction(item: &Value) -> Vec<MappedTransaction> {
+    let Some((address, tx)) = unwrap_item(item) else {
+        return vec![skipped("<missing hash>", "malformed archived item")];
+    };
+    let Some(hash) = tx.get("hash").and_then(|v| v.as_str()).map(str::to_string) else {
+        return vec![skipped("<missing hash>", "missing hash")];
+    };
+    let Some(date) = tx
+        .get("timeStamp")
+        .and_then(|v| v.as_str())
+        .and_then(|s| s.parse().ok())
+        .and_then(|ts| time::OffsetDateTime::from_unix_timestamp(ts).ok())
+    else {
+        return vec![skipped(hash, "missing or unparseable timeStamp")];
+    };
+    let party = |key: &str| {
+        tx.get(key)
+            .and_then(|v| v.as_str())
+            .is_some_and(|a| a.eq_ignore_ascii_case(address))
+    };
+    let (outgoing, incoming) = (party("from"), party("to"));
+    let failed = tx.get("isError").and_then(|v| v.as_str()) == Some("1");
+    let Some(value) = wei(tx.get("value")) else {
+        return vec![skipped(hash, "missing or unparseable value")];
+    };
+    let value = if failed { 0 } else { value };
+    let to_eth = |wei: i128| {
+        Decimal::try_from_i128_with_scale(wei, WEI_SCALE)
+            .map(|d| d.normalize())
+            .ok()
+    };
+
+    let coin = "ETH";
+    let mut mapped = Vec::with_capacity(2);
+    if value > 0 && incoming != outgoing {
+        let Some(amount) = to_eth(value) else {
+            return vec![skipped(hash, "value out of range")];
+        };
+        mapped.push(if incoming {
+            movement(
+                hash.clone(),
+                ProviderTransactionKind::TransferIn,
+                amount,
+                coin,
+                date,
+                format!("Received {coin}"),
+            )
+        } else {
+            movement(
+                hash.clone(),
+                ProviderTransactionKind::TransferOut,
+                -amount,
+                coin,
+                date,
+                format!("Sent {coin}"),
+            )
+        });
+    }
+    if outgoing {
+        let gas = wei(tx.get("gasUsed"))
+            .zip(wei(tx.get("gasPrice")))
+            .map(|(used, price)| used * price)
+            .unwrap_or(0);
+        if let Some(fee) = to_eth(gas).filter(|fee| !fee.is_zero()) {
+            mapped.push(movement(
+                format!("{hash}:fee"),
+                ProviderTransactionKind::Fee,
+                -fee,
+                coin,
+                date,
+                format!("{coin} network fee"),
+            ));
+        }
+    }
+    mapped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+    use serde_json::json;
+
+    const BTC: &str = "bc1qwatched";
+    const ETH: &str = "0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae";
+
+    fn provider(mapped: &MappedTransaction) -> &ProviderTransaction {
+        match mapped {
+            MappedTransaction::Provider(tx) => tx,
+            MappedTransaction::Skipped(s) => panic!("unexpectedly skipped: {}", s.reason),
+        }
+    }
+
+    #[test]
+    fn bitcoin_spend_splits_transfer_change_and_fee() {
+        let mapped = map_bitcoin_transaction(&json!({
+            "address": BTC,
+            "tx": {
+                "txid": "abc",
+                "fee": 1500,
+                "status": { "confirmed": true, "block_time": 1_700_000_000 },
+                "vin": [{ "prevout": { "scriptpubkey_address": BTC, "value": 100_000_000 } }],
+                "vout": [
+                    { "scriptpubkey_address": "bc1qelsewhere", "value": 25_000_000 },
+                    { "scriptpubkey_address": BTC, "value": 74_998_500 }
+                ]
+            }
+        }));
+        assert_eq!(mapped.len(), 2);
+        let (sent, fee) = (provider(&mapped[0]), provider(&mapped[1]));
+        assert_eq!(sent.kind, ProviderTransactionKind::TransferOut);
+        assert_eq!(sent.amount, dec!(-0.25));
+        assert_eq!(fee.kind, ProviderTransactionKind::Fee);
+        assert_eq!(fee.amount, dec!(-0.000015));
+        assert_eq!(fee.external_id, "abc:fee");
+    }
+
+    #[test]
+    fn bitcoin_receive_is_transfer_in() {
+        let mapped = map_bitcoin_transaction(&json!({
+            "address": BTC,
+            "tx": {
+                "txid": "def",
+                "fee": 900,
+                "status": { "confirmed": true, "block_time": 1_700_000_000 },
+                "vin": [{ "prevout": { "scriptpubkey_address": "bc1qsender", "value": 60_000 } }],
+                "vout": [{ "scriptpubkey_address": BTC, "value": 50_000 }]
+            }
+        }));
+        assert_eq!(mapped.len(), 1);
+        let received = provider(&mapped[0]);
+        assert_eq!(received.kind, ProviderTransactionKind::TransferIn);
+        assert_eq!(received.amount, dec!(0.0005));
+        assert_eq!(received.currency, "BTC");
+    }
+
+    #[test]
+    fn ethereum_send_pays_gas_and_failed_call_only_gas() {
+        let sent = map_ethereum_transaction(&json!({
+            "address": ETH,
+            "tx": {
+                "hash": "0xaaa", "timeStamp": "1700000000", "isError": "0",
+                "from": ETH.to_uppercase().replace("0X", "0x"), "to": "0x0000000000000000000000000000000000000001",
+                "value": "1500000000000000000", "gasUsed": "21000", "gasPrice": "20000000000"
+            }
+        }));
+        assert_eq!(sent.len(), 2);
+        assert_eq!(provider(&sent[0]).amount, dec!(-1.5));
+        assert_eq!(provider(&sent[1]).amount, dec!(-0.00042));
+
+        let failed = map_ethereum_transaction(&json!({
+            "address": ETH,
+            "tx": {
+                "hash": "0xbbb", "timeStamp": "1700000000", "isError": "1",
+                "from": ETH, "to": "0x0000000000000000000000000000000000000001",
+                "value": "1000", "gasUsed": "50000", "gasPrice": "10000000000"
+            }
+        }));
+        assert_eq!(failed.len(), 1);
+        assert_eq!(provider(&failed[0]).kind, ProviderTransactionKind::Fee);
+
+        let received = map_ethereum_transaction(&json!({
+            "address": ETH,
+            "tx": {
+                "hash": "0xccc", "timeStamp": "1700000000", "isError": "0",
+                "from": "0x0000000000000000000000000000000000000001", "to": ETH,
+                "value": "20000000000000000000", "gasUsed": "21000", "gasPrice": "1"
+            }
+        }));
+        assert_eq!(received.len(), 1);
+        assert_eq!(provider(&received[0]).amount, dec!(20));
+        assert_eq!(
+            provider(&received[0]).kind,
+            ProviderTransactionKind::TransferIn
+        );
+    }
+}
@@ -0,0 +1,4 @@
+pub mod address;
+pub mod client;
+pub mod mapper;
+pub mod provider;
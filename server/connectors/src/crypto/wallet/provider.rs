@@ -0,0 +1,165 @@
+use std::sync::OnceLock;
+
+use async_trait::async_trait;
+
+use crate::crypto::config::CryptoConfig;
+use crate::crypto::wallet::address::WatchedAddress;
+use crate::crypto::wallet::client::WalletClient;
+use crate::models::account::ProviderAccount;
+use crate::port::{Connector, ConnectorStore};
+use crate::provider::{CredentialSource, Provider, ProviderKind};
+use crate::Result;
+
+/// Watch-only BTC and ETH addresses read from a public block explorer. There is no
+/// credential; each binding names the address it follows as its provider account id.
+pub struct CryptoWalletProvider {
+    config: OnceLock<CryptoConfig>,
+}
+
+impl CryptoWalletProvider {
+    pub const fn from_env() -> Self {
+        Self {
+            config: OnceLock::new(),
+        }
+    }
+
+    pub fn with_config(config: CryptoConfig) -> Self {
+        Self {
+            config: OnceLock::from(config),
+        }
+    }
+
+    fn config(&self) -> &CryptoConfig {
+        self.config.get_or_init(CryptoConfig::from_env)
+    }
+}
+
+#[async_trait]
+impl Provider for CryptoWalletProvider {
+    fn kind(&self) -> ProviderKind {
+        ProviderKind::CryptoWallet
+    }
+
+    async fn build_connector(
+        &self,
+        provider_account_id: &str,
+        _credential: CredentialSource,
+        _store: &dyn ConnectorStore,
+    ) -> Result<Box<dyn Connector>> {
+        let watched: WatchedAddress = provider_account_id.parse()?;
+        Ok(Box::new(WalletClient::new(self.config(), watched)))
+    }
+
+    fn resolve_provider_account_id(
+        &self,
+        client_value: Option<String>,
+        _store: &dyn ConnectorStore,
+    ) -> Result<String> {
+        let value = client_value.ok_or_else(|| {
+            anyhow::anyhow!("provider_account_id is required for crypto_wallet bindings")
+        })?;
+        Ok(value.parse::<WatchedAddress>()?.to_string())
+    }
+
+    async fn list_accounts(&self, _store: &dyn ConnectorStore) -> Result<Vec<ProviderAccount>> {
+        Ok(Vec::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use rust_decimal_macros::dec;
+    use serde_json::json;
+
+    use super::*;
+    use crate::mock_http::{MockHttpServer, MockRoute};
+    use crate::models::sync::{FetchedPage, RawPage};
+    use crate::models::ProviderTransactionKind;
+    use crate::port::{MockConnectorStore, SyncParams, SyncRunOutcome};
+
+    const ADDRESS: &str = "0xde0b295669a9fd93d5f28d9ec85e40f4cb697bae";
+
+    #[tokio::test]
+    async fn syncs_ethereum_address_from_mock_explorer() {
+        let api = "/api";
+        // The Etherscan API multiplexes on query parameters; `txlist` is the only call a
+        // sync makes.
+        let server = MockHttpServer::start(vec![MockRoute::get(
+            api,
+            json!({ "status": "1", "message": "OK", "result": [
+                { "hash": "0xin", "timeStamp": "1700000000", "isError": "0", "from": "0x0000000000000000000000000000000000000001", "to": ADDRESS, "value": "2000000000000000000", "gasUsed": "21000", "gasPrice": "1000000000" },
+                { "hash": "0xout", "timeStamp": "1700000100", "isError": "0", "from": ADDRESS, "to": "0x0000000000000000000000000000000000000002", "value": "500000000000000000", "gasUsed": "21000", "gasPrice": "1000000000" }
+            ] }),
+        )])
+        .await;
+        let provider = CryptoWalletProvider::with_config(CryptoConfig {
+            kraken_api_base: server.url().to_string(),
+            bitcoin_explorer_url: server.url().to_string(),
+            ethereum_explorer_url: format!("{}{api}", server.url()),
+            ethereum_explorer_api_key: Some("explorer-key".to_string()),
+        });
+
+        let store = MockConnectorStore::new();
+        let account_id = provider
+            .resolve_provider_account_id(Some(ADDRESS.to_uppercase().replace("0X", "0x")), &store)
+            .unwrap();
+        assert_eq!(account_id, format!("eth:{ADDRESS}"));
+
+        let pages = Arc::new(Mutex::new(Vec::<FetchedPage>::new()));
+        let mut store = MockConnectorStore::new();
+        store.expect_latest_cursor().returning(|| Ok(None));
+        let appended = pages.clone();
+        store.expect_append_page().returning(move |page| {
+            appended.lock().unwrap().push(page.clone());
+            Ok(())
+        });
+
+        let connector = provider
+            .build_connector(&account_id, CredentialSource::Stored, &store)
+            .await
+            .unwrap();
+        let outcome = connector
+            .sync(
+                &store,
+                SyncParams {
+                    synced_through: None,
+                    budget: None,
+                    inline_retries: 0,
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(outcome, SyncRunOutcome::Complete { pages_fetched: 1 });
+
+        let request = &server.requests_to("GET", api)[0];
+        assert!(request.query.contains("action=txlist"));
+        assert!(request.query.contains(&format!("address={ADDRESS}")));
+        assert!(request.query.contains("apikey=explorer-key"));
+
+        let raw_pages: Vec<RawPage> = pages
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|page| RawPage {
+                stream: page.stream.clone(),
+                payload: page.payload.clone(),
+            })
+            .collect();
+        let mapped = connector.map_pages(&raw_pages);
+        let movements: Vec<(ProviderTransactionKind, rust_decimal::Decimal)> = mapped
+            .transactions
+            .iter()
+            .map(|tx| (tx.kind, tx.amount))
+            .collect();
+        assert_eq!(
+            movements,
+            vec![
+                (ProviderTransactionKind::TransferIn, dec!(2)),
+                (ProviderTransactionKind::TransferOut, dec!(-0.5)),
+                (ProviderTransactionKind::Fee, dec!(-0.000021)),
+            ]
+        );
+    }
+}
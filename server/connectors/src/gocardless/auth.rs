@@ -0,0 +1,179 @@
+use crate::gocardless::config::GoCardlessConfig;
+use crate::port::ConnectorStore;
+use crate::Result;
+use observability::create_http_client;
+use serde::{Deserialize, Serialize};
+
+const ACCESS_TOKEN_CACHE_KEY: &str = "gocardless:access_token";
+const ACCESS_TOKEN_EXPIRY_MARGIN_SECS: u64 = 60;
+
+/// Requisition status once the user has completed consent at their bank.
+pub const REQUISITION_LINKED: &str = "LN";
+
+#[derive(Debug, Deserialize)]
+struct TokenResponse {
+    access: String,
+    access_expires: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct NewTokenRequest<'a> {
+    secret_id: &'a str,
+    secret_key: &'a str,
+}
+
+#[derive(Debug, Serialize)]
+struct AgreementRequest<'a> {
+    institution_id: &'a str,
+    max_historical_days: u32,
+    access_valid_for_days: u32,
+    access_scope: [&'a str; 3],
+}
+
+#[derive(Debug, Deserialize)]
+struct AgreementResponse {
+    id: String,
+}
+
+#[derive(Debug, Serialize)]
+struct RequisitionRequest<'a> {
+    redirect: &'a str,
+    institution_id: &'a str,
+    reference: &'a str,
+    agreement: &'a str,
+}
+
+/// A GoCardless requisition: one user's consent at one institution, covering every account
+/// they linked during it.
+#[derive(Debug, Deserialize)]
+pub struct Requisition {
+    pub id: String,
+    pub status: String,
+    pub link: String,
+    #[serde(default)]
+    pub reference: Option<String>,
+    #[serde(default)]
+    pub accounts: Vec<String>,
+}
+
+/// App-level access token. Unlike TrueLayer's, it is minted from the secret pair rather than
+/// a rotating refresh token, so concurrent callers may each mint one without a lock.
+pub async fn access_token(config: &GoCardlessConfig, store: &dyn ConnectorStore) -> Result<String> {
+    if let Some(cached) = store.cache_get(ACCESS_TOKEN_CACHE_KEY).await {
+        return Ok(cached);
+    }
+
+    let secret_id = config
+        .secret_id
+        .as_deref()
+        .ok_or_else(|| anyhow::anyhow!("GoCardless not configured"))?;
+    let secret_key = config
+        .secret_key
+        .as_deref()
+        .ok_or_else(|| anyhow::anyhow!("GoCardless not configured"))?;
+
+    let token: TokenResponse = post_json(
+        config,
+        None,
+        "/api/v2/token/new/",
+        &NewTokenRequest {
+            secret_id,
+            secret_key,
+        },
+    )
+    .await?;
+
+    store
+        .cache_put(
+            ACCESS_TOKEN_CACHE_KEY,
+            &token.access,
+            token
+                .access_expires
+                .saturating_sub(ACCESS_TOKEN_EXPIRY_MARGIN_SECS)
+                .max(1),
+        )
+        .await;
+
+    Ok(token.access)
+}
+
+pub fn resolve_redirect_uri(config: &GoCardlessConfig, requested: Option<&str>) -> Result<String> {
+    let uri = requested.ok_or_else(|| anyhow::anyhow!("redirect_uri is required"))?;
+    if config.redirect_uri_allowlist.iter().any(|a| a == uri) {
+        Ok(uri.to_string())
+    } else {
+        anyhow::bail!("redirect_uri is not allowlisted")
+    }
+}
+
+/// Creates the end-user agreement and the requisition the user completes at their bank.
+/// `reference` comes back as the `ref` query parameter of the redirect.
+pub async fn create_requisition(
+    config: &GoCardlessConfig,
+    access_token: &str,
+    institution_id: &str,
+    reference: &str,
+    redirect_uri: &str,
+) -> Result<Requisition> {
+    let agreement: AgreementResponse = post_json(
+        config,
+        Some(access_token),
+        "/api/v2/agreements/enduser/",
+        &AgreementRequest {
+            institution_id,
+            max_historical_days: config.max_historical_days,
+            access_valid_for_days: config.access_valid_for_days,
+            access_scope: ["balances", "details", "transactions"],
+        },
+    )
+    .await?;
+
+    post_json(
+        config,
+        Some(access_token),
+        "/api/v2/requisitions/",
+        &RequisitionRequest {
+            redirect: redirect_uri,
+            institution_id,
+            reference,
+            agreement: &agreement.id,
+        },
+    )
+    .await
+}
+
+pub async fn get_requisition(
+    config: &GoCardlessConfig,
+    access_token: &str,
+    requisition_id: &str,
+) -> Result<Requisition> {
+    let resp = crate::util::ensure_success(
+        create_http_client()
+            .get(format!(
+                "{}/api/v2/requisitions/{}/",
+                config.api_base, requisition_id
+            ))
+            .header("Authorization", format!("Bearer {}", access_token))
+            .send()
+            .await?,
+    )
+    .await?;
+    Ok(resp.json().await?)
+}
+
+async fn post_json<B: Serialize, T: serde::de::DeserializeOwned>(
+    config: &GoCardlessConfig,
+    access_token: Option<&str>,
+    path: &str,
+    body: &B,
+) -> Result<T> {
+    let mut request = create_http_client()
+        .post(format!("{}{}", config.api_base, path))
+        .header("Content-Type", "application/json")
+        .body(serde_json::to_string(body)?);
+    if let Some(token) = access_token {
+        request = request.header("Authorization", format!("Bearer {}", token));
+    }
+    let resp = crate::util::ensure_success(request.send().await?).await?;
+    Ok(resp.json().await?)
+}
@@ -0,0 +1,217 @@
+use crate::gocardless::config::GoCardlessConfig;
+use crate::models::account::ProviderAccount;
+use crate::models::balance::{ProviderBalance, ProviderCashBalance};
+use crate::models::sync::{FetchedPage, RawPage, SyncCursor};
+use crate::models::transaction::MappedPages;
+use crate::port::{Connector, ProviderCapabilities};
+use crate::Result;
+use observability::{create_http_client, TracedHttpClient};
+use url::Url;
+
+// Balance types in order of preference. PSD2 banks report different subsets; the interim
+// ones include today's bookings, the closing ones are as of the last statement.
+const BALANCE_TYPE_PREFERENCE: &[&str] = &[
+    "interimAvailable",
+    "interimBooked",
+    "closingAvailable",
+    "closingBooked",
+    "expected",
+];
+
+pub struct GoCardlessClient {
+    http: TracedHttpClient,
+    api_base: String,
+    access_token: String,
+    account_id: String,
+    max_historical_days: u32,
+}
+
+impl GoCardlessClient {
+    pub fn new(config: &GoCardlessConfig, access_token: String, account_id: String) -> Self {
+        Self {
+            http: create_http_client(),
+            api_base: config.api_base.clone(),
+            access_token,
+            account_id,
+            max_historical_days: config.max_historical_days,
+        }
+    }
+
+    /// Accounts the user linked in the requisition, with their details. An account whose
+    /// details cannot be read (some banks rate-limit the endpoint) is still listed by id.
+    pub async fn list_accounts(
+        config: &GoCardlessConfig,
+        access_token: &str,
+        requisition_id: &str,
+    ) -> Result<Vec<ProviderAccount>> {
+        let requisition =
+            crate::gocardless::auth::get_requisition(config, access_token, requisition_id).await?;
+        let http = create_http_client();
+
+        let mut accounts = Vec::with_capacity(requisition.accounts.len());
+        for account_id in requisition.accounts {
+            let details = crate::util::ensure_success(
+                http.get(format!(
+                    "{}/api/v2/accounts/{}/details/",
+                    config.api_base, account_id
+                ))
+                .header("Authorization", format!("Bearer {}", access_token))
+                .send()
+                .await?,
+            )
+            .await;
+            let details = match details {
+                Ok(resp) => resp.json::<serde_json::Value>().await?,
+                Err(e) => {
+                    tracing::warn!(account_id = %account_id, error = %e, "gocardless account details fetch failed — listing by id");
+                    serde_json::Value::Null
+                }
+            };
+            accounts.push(parse_account(&account_id, &details));
+        }
+        Ok(accounts)
+    }
+
+    fn bearer(&self) -> String {
+        format!("Bearer {}", self.access_token)
+    }
+
+    fn transactions_url(&self, from: Option<time::OffsetDateTime>) -> Result<Url> {
+        let mut url = Url::parse(&format!(
+            "{}/api/v2/accounts/{}/transactions/",
+            self.api_base, self.account_id
+        ))?;
+        if let Some(from) = from {
+            let date_from = from
+                .date()
+                .format(&time::macros::format_description!("[year]-[month]-[day]"))?;
+            url.query_pairs_mut().append_pair("date_from", &date_from);
+        }
+        Ok(url)
+    }
+}
+
+fn parse_account(account_id: &str, details: &serde_json::Value) -> ProviderAccount {
+    let account = details.get("account");
+    let field = |name: &str| {
+        account
+            .and_then(|a| a.get(name))
+            .and_then(|v| v.as_str())
+            .map(str::to_string)
+    };
+    ProviderAccount {
+        provider_account_id: account_id.to_string(),
+        display_name: field("name")
+            .or_else(|| field("product"))
+            .or_else(|| field("iban"))
+            .unwrap_or_else(|| account_id.to_string()),
+        currency: field("currency"),
+        account_type: field("cashAccountType"),
+    }
+}
+
+#[async_trait::async_trait]
+impl Connector for GoCardlessClient {
+    /// GoCardless returns the whole window in one response, so a sync is a single page.
+    /// Only booked transactions are archived — pending ones change id once booked.
+    async fn fetch_page(
+        &self,
+        from: Option<time::OffsetDateTime>,
+        _cursor: Option<SyncCursor>,
+    ) -> Result<FetchedPage> {
+        let resp = crate::util::ensure_success(
+            self.http
+                .get(self.transactions_url(from)?)
+                .header("Authorization", self.bearer())
+                .send()
+                .await?,
+        )
+        .await?;
+        let body: serde_json::Value = resp.json().await?;
+
+        let booked: Vec<serde_json::Value> = body
+            .get("transactions")
+            .and_then(|t| t.get("booked"))
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default();
+
+        Ok(FetchedPage {
+            stream: "transactions".to_string(),
+            payload: serde_json::Value::Array(booked),
+            next_cursor: None,
+        })
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            max_history: Some(time::Duration::days(self.max_historical_days.into())),
+        }
+    }
+
+    fn map_pages(&self, pages: &[RawPage]) -> MappedPages {
+        crate::provider::map_pages(crate::provider::ProviderKind::GoCardless, pages)
+    }
+
+    async fn fetch_balance(&self) -> Result<ProviderBalance> {
+        let url = format!(
+            "{}/api/v2/accounts/{}/balances/",
+            self.api_base, self.account_id
+        );
+        let resp = crate::util::ensure_success(
+            self.http
+                .get(&url)
+                .header("Authorization", self.bearer())
+                .send()
+                .await?,
+        )
+        .await?;
+        let body: serde_json::Value = resp.json().await?;
+
+        Ok(ProviderBalance {
+            quantities: vec![],
+            cash: parse_cash_balance(&body),
+        })
+    }
+}
+
+/// One balance per currency, picking the most current balance type the bank reports.
+fn parse_cash_balance(body: &serde_json::Value) -> Vec<ProviderCashBalance> {
+    let balances: Vec<(usize, ProviderCashBalance)> = body
+        .get("balances")
+        .and_then(|v| v.as_array())
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| {
+                    let balance_type = item.get("balanceType").and_then(|v| v.as_str())?;
+                    let rank = BALANCE_TYPE_PREFERENCE
+                        .iter()
+                        .position(|t| *t == balance_type)
+                        .unwrap_or(BALANCE_TYPE_PREFERENCE.len());
+                    let amount = item.get("balanceAmount")?;
+                    Some((
+                        rank,
+                        ProviderCashBalance {
+                            currency: amount.get("currency")?.as_str()?.to_string(),
+                            amount: amount.get("amount").and_then(crate::util::parse_decimal)?,
+                        },
+                    ))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut best: Vec<(usize, ProviderCashBalance)> = Vec::new();
+    for (rank, balance) in balances {
+        match best
+            .iter_mut()
+            .find(|(_, b)| b.currency == balance.currency)
+        {
+            Some(existing) if rank < existing.0 => *existing = (rank, balance),
+            Some(_) => {}
+            None => best.push((rank, balance)),
+        }
+    }
+    best.into_iter().map(|(_, balance)| balance).collect()
+}
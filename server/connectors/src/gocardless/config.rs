@@ -0,0 +1,45 @@
+const DEFAULT_API_BASE: &str = "https://bankaccountdata.gocardless.com";
+const DEFAULT_MAX_HISTORICAL_DAYS: u32 = 90;
+const DEFAULT_ACCESS_VALID_FOR_DAYS: u32 = 90;
+
+/// Settings of the GoCardless Bank Account Data aggregator. `api_base` points at any
+/// deployment speaking the same API, including the test fixture's mock server.
+#[derive(Clone, Debug)]
+pub struct GoCardlessConfig {
+    pub secret_id: Option<String>,
+    pub secret_key: Option<String>,
+    pub api_base: String,
+    pub redirect_uri_allowlist: Vec<String>,
+    /// History requested in the end-user agreement. Most banks cap it at 90 days.
+    pub max_historical_days: u32,
+    /// Lifetime of the end-user agreement, after which the user must re-consent.
+    pub access_valid_for_days: u32,
+}
+
+impl GoCardlessConfig {
+    pub(crate) fn from_env() -> Self {
+        Self {
+            secret_id: std::env::var("GOCARDLESS_SECRET_ID").ok(),
+            secret_key: std::env::var("GOCARDLESS_SECRET_KEY").ok(),
+            api_base: std::env::var("GOCARDLESS_API_BASE")
+                .map(|v| v.trim_end_matches('/').to_string())
+                .unwrap_or_else(|_| DEFAULT_API_BASE.to_string()),
+            redirect_uri_allowlist: std::env::var("GOCARDLESS_REDIRECT_URI_ALLOWLIST")
+                .map(|v| {
+                    v.split(',')
+                        .map(|s| s.trim().to_string())
+                        .filter(|s| !s.is_empty())
+                        .collect()
+                })
+                .unwrap_or_default(),
+            max_historical_days: std::env::var("GOCARDLESS_MAX_HISTORICAL_DAYS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_MAX_HISTORICAL_DAYS),
+            access_valid_for_days: std::env::var("GOCARDLESS_ACCESS_VALID_FOR_DAYS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(DEFAULT_ACCESS_VALID_FOR_DAYS),
+        }
+    }
+}
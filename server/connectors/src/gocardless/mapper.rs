@@ -0,0 +1,176 @@
+use crate::models::{
+    MappedTransaction, ProviderTransaction, ProviderTransactionKind, SkippedTransaction,
+};
+use serde_json::Value;
+
+fn parse_date(s: &str) -> Option<time::OffsetDateTime> {
+    if let Ok(dt) = time::OffsetDateTime::parse(s, &time::format_description::well_known::Rfc3339) {
+        return Some(dt);
+    }
+    let date = time::Date::parse(
+        s,
+        &time::macros::format_description!("[year]-[month]-[day]"),
+    )
+    .ok()?;
+    Some(date.midnight().assume_utc())
+}
+
+/// Berlin Group `transactionId` when the bank provides one, otherwise the aggregator's own
+/// `internalTransactionId`. Both are stable across fetches of a booked transaction.
+pub fn derive_external_id(tx: &Value) -> Option<String> {
+    ["transactionId", "internalTransactionId", "entryReference"]
+        .iter()
+        .find_map(|key| tx.get(*key).and_then(|v| v.as_str()))
+        .filter(|id| !id.is_empty())
+        .map(str::to_string)
+}
+
+fn text(value: Option<&Value>) -> Option<String> {
+    value
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string)
+}
+
+/// The creditor of a debit or the debtor of a credit, by name or else by IBAN.
+fn counterparty(tx: &Value, outgoing: bool) -> Option<String> {
+    let (name, account) = if outgoing {
+        ("creditorName", "creditorAccount")
+    } else {
+        ("debtorName", "debtorAccount")
+    };
+    text(tx.get(name)).or_else(|| text(tx.get(account).and_then(|v| v.get("iban"))))
+}
+
+fn description(tx: &Value, outgoing: bool) -> String {
+    let text = |key: &str| text(tx.get(key));
+    let counterparty_name = if outgoing {
+        text("creditorName")
+    } else {
+        text("debtorName")
+    };
+    text("remittanceInformationUnstructured")
+        .or_else(|| {
+            tx.get("remittanceInformationUnstructuredArray")
+                .and_then(|v| v.as_array())
+                .map(|parts| {
+                    parts
+                        .iter()
+                        .filter_map(|p| p.as_str())
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                })
+                .filter(|s| !s.trim().is_empty())
+        })
+        .or(counterparty_name)
+        .or_else(|| text("additionalInformation"))
+        .unwrap_or_default()
+}
+
+fn skipped(external_id: impl Into<String>, reason: impl Into<String>) -> MappedTransaction {
+    MappedTransaction::Skipped(SkippedTransaction {
+        external_id: external_id.into(),
+        reason: reason.into(),
+    })
+}
+
+pub fn map_transaction(tx: &Value) -> MappedTransaction {
+    let Some(external_id) = derive_external_id(tx) else {
+        return skipped("<missing id>", "no usable transaction identifier");
+    };
+    let transaction_amount = tx.get("transactionAmount");
+    let Some(amount) = transaction_amount
+        .and_then(|v| v.get("amount"))
+        .and_then(crate::util::parse_decimal)
+    else {
+        return skipped(external_id, "missing or unparseable amount");
+    };
+    let Some(currency) = transaction_amount
+        .and_then(|v| v.get("currency"))
+        .and_then(|v| v.as_str())
+        .map(str::to_string)
+    else {
+        return skipped(external_id, "missing currency");
+    };
+    let Some(date) = [
+        "bookingDateTime",
+        "bookingDate",
+        "valueDateTime",
+        "valueDate",
+    ]
+    .iter()
+    .filter_map(|key| tx.get(*key).and_then(|v| v.as_str()))
+    .find_map(parse_date) else {
+        return skipped(external_id, "missing or unparseable booking date");
+    };
+
+    // Amounts are already signed from the account holder's perspective: debits negative.
+    let outgoing = amount.is_sign_negative();
+    MappedTransaction::Provider(ProviderTransaction {
+        description: description(tx, outgoing),
+        counterparty: counterparty(tx, outgoing),
+        external_id,
+        amount,
+        currency,
+        date,
+        asset_identifier: None,
+        quantity: None,
+        kind: ProviderTransactionKind::Inferred,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rust_decimal_macros::dec;
+    use serde_json::json;
+
+    #[test]
+    fn maps_booked_transaction() {
+        let tx = json!({
+            "transactionId": "tx-1",
+            "bookingDate": "2026-03-04",
+            "transactionAmount": { "amount": "-12.30", "currency": "EUR" },
+            "creditorName": "Coffee Bar",
+        });
+        let MappedTransaction::Provider(mapped) = map_transaction(&tx) else {
+            panic!("expected a provider transaction");
+        };
+        assert_eq!(mapped.external_id, "tx-1");
+        assert_eq!(mapped.amount, dec!(-12.30));
+        assert_eq!(mapped.currency, "EUR");
+        assert_eq!(mapped.date, time::macros::datetime!(2026-03-04 0:00 UTC));
+        assert_eq!(mapped.description, "Coffee Bar");
+        assert_eq!(mapped.counterparty.as_deref(), Some("Coffee Bar"));
+    }
+
+    #[test]
+    fn falls_back_to_internal_id_and_remittance_array() {
+        let tx = json!({
+            "internalTransactionId": "int-9",
+            "bookingDateTime": "2026-03-05T10:15:00Z",
+            "transactionAmount": { "amount": "250.00", "currency": "EUR" },
+            "remittanceInformationUnstructuredArray": ["Salary", "March"],
+            "debtorName": "Employer",
+        });
+        let MappedTransaction::Provider(mapped) = map_transaction(&tx) else {
+            panic!("expected a provider transaction");
+        };
+        assert_eq!(mapped.external_id, "int-9");
+        assert_eq!(mapped.description, "Salary March");
+        assert_eq!(mapped.counterparty.as_deref(), Some("Employer"));
+    }
+
+    #[test]
+    fn skips_transaction_without_identifier() {
+        let tx = json!({
+            "bookingDate": "2026-03-04",
+            "transactionAmount": { "amount": "1.00", "currency": "EUR" },
+        });
+        assert!(matches!(
+            map_transaction(&tx),
+            MappedTransaction::Skipped(_)
+        ));
+    }
+}
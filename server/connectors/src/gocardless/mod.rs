@@ -0,0 +1,5 @@
+pub mod auth;
+pub mod client;
+pub mod config;
+pub mod mapper;
+pub mod provider;
@@ -0,0 +1,377 @@
+use std::sync::OnceLock;
+
+use async_trait::async_trait;
+
+use crate::gocardless::auth::REQUISITION_LINKED;
+use crate::gocardless::client::GoCardlessClient;
+use crate::gocardless::config::GoCardlessConfig;
+use crate::models::account::ProviderAccount;
+use crate::port::{Connector, ConnectorStore};
+use crate::provider::{CredentialSource, Provider, ProviderKind};
+use crate::Result;
+
+/// PSD2 bank access through GoCardless Bank Account Data. The connection's
+/// `provider_key_id` is the institution id (e.g. `SANDBOXFINANCE_SFIN0000`), and its stored
+/// credential is the requisition id created when the OAuth session begins. API access
+/// itself uses the app-level secret pair.
+pub struct GoCardlessProvider {
+    config: OnceLock<GoCardlessConfig>,
+}
+
+impl GoCardlessProvider {
+    pub const fn from_env() -> Self {
+        Self {
+            config: OnceLock::new(),
+        }
+    }
+
+    pub fn with_config(config: GoCardlessConfig) -> Self {
+        Self {
+            config: OnceLock::from(config),
+        }
+    }
+
+    fn config(&self) -> &GoCardlessConfig {
+        self.config.get_or_init(GoCardlessConfig::from_env)
+    }
+
+    async fn requisition_id(store: &dyn ConnectorStore) -> Result<String> {
+        let bytes = store.get_credential().await?.ok_or_else(|| {
+            anyhow::anyhow!("GoCardless requisition not found — begin an OAuth session first")
+        })?;
+        Ok(String::from_utf8(bytes)?)
+    }
+}
+
+#[async_trait]
+impl Provider for GoCardlessProvider {
+    fn kind(&self) -> ProviderKind {
+        ProviderKind::GoCardless
+    }
+
+    fn needs_attended_backfill(&self) -> bool {
+        true
+    }
+
+    async fn build_connector(
+        &self,
+        provider_account_id: &str,
+        credential: CredentialSource,
+        store: &dyn ConnectorStore,
+    ) -> Result<Box<dyn Connector>> {
+        let access_token = match credential {
+            CredentialSource::Transient(token) => token,
+            CredentialSource::Stored => {
+                crate::gocardless::auth::access_token(self.config(), store).await?
+            }
+        };
+        Ok(Box::new(GoCardlessClient::new(
+            self.config(),
+            access_token,
+            provider_account_id.to_string(),
+        )))
+    }
+
+    fn resolve_provider_account_id(
+        &self,
+        client_value: Option<String>,
+        _store: &dyn ConnectorStore,
+    ) -> Result<String> {
+        client_value.ok_or_else(|| {
+            anyhow::anyhow!("provider_account_id is required for gocardless bindings")
+        })
+    }
+
+    async fn list_accounts(&self, store: &dyn ConnectorStore) -> Result<Vec<ProviderAccount>> {
+        let requisition_id = Self::requisition_id(store).await?;
+        let access_token = crate::gocardless::auth::access_token(self.config(), store).await?;
+        GoCardlessClient::list_accounts(self.config(), &access_token, &requisition_id).await
+    }
+
+    /// Creates a requisition with `state` as its reference. GoCardless redirects back with
+    /// only `ref=<state>`; there is no authorization code to exchange.
+    async fn begin_oauth(
+        &self,
+        store: &dyn ConnectorStore,
+        state: &str,
+        redirect_uri: Option<&str>,
+    ) -> Result<String> {
+        let config = self.config();
+        let uri = crate::gocardless::auth::resolve_redirect_uri(config, redirect_uri)?;
+        let institution_id = store.provider_key_id().ok_or_else(|| {
+            anyhow::anyhow!("gocardless connections need the institution id as provider_key_id")
+        })?;
+
+        let access_token = crate::gocardless::auth::access_token(config, store).await?;
+        let requisition = crate::gocardless::auth::create_requisition(
+            config,
+            &access_token,
+            &institution_id,
+            state,
+            &uri,
+        )
+        .await?;
+        store.put_credential(requisition.id.as_bytes()).await?;
+
+        Ok(requisition.link)
+    }
+
+    /// `code` is the `ref` the redirect carried. Consent is complete once the requisition
+    /// is linked.
+    async fn complete_oauth(
+        &self,
+        store: &dyn ConnectorStore,
+        code: &str,
+        _redirect_uri: Option<&str>,
+    ) -> Result<Option<time::OffsetDateTime>> {
+        let config = self.config();
+        let requisition_id = Self::requisition_id(store).await?;
+        let access_token = crate::gocardless::auth::access_token(config, store).await?;
+        let requisition =
+            crate::gocardless::auth::get_requisition(config, &access_token, &requisition_id)
+                .await?;
+
+        if requisition.reference.as_deref() != Some(code) {
+            anyhow::bail!("redirect reference does not match the pending requisition");
+        }
+        if requisition.status != REQUISITION_LINKED {
+            anyhow::bail!(
+                "requisition is {} — consent was not completed",
+                requisition.status
+            );
+        }
+
+        Ok(Some(
+            time::OffsetDateTime::now_utc()
+                + time::Duration::days(config.access_valid_for_days.into()),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{Arc, Mutex};
+
+    use rust_decimal_macros::dec;
+    use serde_json::json;
+
+    use super::*;
+    use crate::mock_http::{MockHttpServer, MockRoute};
+    use crate::models::balance::ProviderCashBalance;
+    use crate::models::sync::{FetchedPage, RawPage};
+    use crate::port::{MockConnectorStore, SyncParams, SyncRunOutcome};
+
+    const REDIRECT: &str = "https://app.example/connectors/gocardless/callback";
+
+    fn aggregator_routes() -> Vec<MockRoute> {
+        vec![
+            MockRoute::post(
+                "/api/v2/token/new/",
+                json!({ "access": "app-token", "access_expires": 86400, "refresh": "r", "refresh_expires": 2592000 }),
+            ),
+            MockRoute::post("/api/v2/agreements/enduser/", json!({ "id": "agr-1" })),
+            MockRoute::post(
+                "/api/v2/requisitions/",
+                json!({ "id": "req-1", "status": "CR", "reference": "state-1", "link": "https://ob.example/start/req-1", "accounts": [] }),
+            ),
+            MockRoute::get(
+                "/api/v2/requisitions/req-1/",
+                json!({ "id": "req-1", "status": "LN", "reference": "state-1", "link": "https://ob.example/start/req-1", "accounts": ["acc-1"] }),
+            ),
+            MockRoute::get(
+                "/api/v2/accounts/acc-1/details/",
+                json!({ "account": { "currency": "EUR", "name": "Main account", "cashAccountType": "CACC" } }),
+            ),
+            MockRoute::get(
+                "/api/v2/accounts/acc-1/transactions/",
+                json!({ "transactions": {
+                    "booked": [
+                        { "transactionId": "tx-1", "bookingDate": "2026-03-04", "transactionAmount": { "amount": "-12.30", "currency": "EUR" }, "creditorName": "Coffee Bar" },
+                        { "transactionId": "tx-2", "bookingDate": "2026-03-05", "transactionAmount": { "amount": "2500.00", "currency": "EUR" }, "remittanceInformationUnstructured": "Salary" },
+                        { "bookingDate": "2026-03-06", "transactionAmount": { "amount": "-1.00", "currency": "EUR" } }
+                    ],
+                    "pending": [
+                        { "transactionAmount": { "amount": "-5.00", "currency": "EUR" }, "valueDate": "2026-03-07" }
+                    ]
+                } }),
+            ),
+            MockRoute::get(
+                "/api/v2/accounts/acc-1/balances/",
+                json!({ "balances": [
+                    { "balanceAmount": { "amount": "2400.00", "currency": "EUR" }, "balanceType": "closingBooked" },
+                    { "balanceAmount": { "amount": "2487.70", "currency": "EUR" }, "balanceType": "interimAvailable" }
+                ] }),
+            ),
+        ]
+    }
+
+    fn config(server: &MockHttpServer) -> GoCardlessConfig {
+        GoCardlessConfig {
+            secret_id: Some("id".to_string()),
+            secret_key: Some("key".to_string()),
+            api_base: server.url().to_string(),
+            redirect_uri_allowlist: vec![REDIRECT.to_string()],
+            max_historical_days: 90,
+            access_valid_for_days: 90,
+        }
+    }
+
+    /// Store double holding the credential and appended pages in memory.
+    fn store(
+        credential: Arc<Mutex<Option<Vec<u8>>>>,
+        pages: Arc<Mutex<Vec<FetchedPage>>>,
+    ) -> MockConnectorStore {
+        let mut store = MockConnectorStore::new();
+        store
+            .expect_provider_key_id()
+            .returning(|| Some("SANDBOXFINANCE_SFIN0000".to_string()));
+        store.expect_cache_get().returning(|_| None);
+        store.expect_cache_put().returning(|_, _, _| ());
+        store.expect_latest_cursor().returning(|| Ok(None));
+        let stored = credential.clone();
+        store.expect_put_credential().returning(move |value| {
+            *stored.lock().unwrap() = Some(value.to_vec());
+            Ok(())
+        });
+        store
+            .expect_get_credential()
+            .returning(move || Ok(credential.lock().unwrap().clone()));
+        store.expect_append_page().returning(move |page| {
+            pages.lock().unwrap().push(page.clone());
+            Ok(())
+        });
+        store
+    }
+
+    #[tokio::test]
+    async fn consent_and_sync_against_mock_aggregator() {
+        let server = MockHttpServer::start(aggregator_routes()).await;
+        let provider = GoCardlessProvider::with_config(config(&server));
+        let credential = Arc::new(Mutex::new(None));
+        let pages = Arc::new(Mutex::new(Vec::new()));
+        let store = store(credential.clone(), pages.clone());
+
+        let link = provider
+            .begin_oauth(&store, "state-1", Some(REDIRECT))
+            .await
+            .unwrap();
+        assert_eq!(link, "https://ob.example/start/req-1");
+        assert_eq!(credential.lock().unwrap().as_deref(), Some(&b"req-1"[..]));
+        let requisition = &server.requests_to("POST", "/api/v2/requisitions/")[0];
+        assert_eq!(
+            requisition.header("authorization"),
+            Some("Bearer app-token")
+        );
+        assert_eq!(requisition.json()["reference"], "state-1");
+        assert_eq!(requisition.json()["agreement"], "agr-1");
+        assert_eq!(
+            requisition.json()["institution_id"],
+            "SANDBOXFINANCE_SFIN0000"
+        );
+
+        let expires = provider
+            .complete_oauth(&store, "state-1", Some(REDIRECT))
+            .await
+            .unwrap();
+        assert!(expires.is_some());
+
+        let accounts = provider.list_accounts(&store).await.unwrap();
+        assert_eq!(accounts.len(), 1);
+        assert_eq!(accounts[0].provider_account_id, "acc-1");
+        assert_eq!(accounts[0].display_name, "Main account");
+        assert_eq!(accounts[0].currency.as_deref(), Some("EUR"));
+
+        let connector = provider
+            .build_connector("acc-1", CredentialSource::Stored, &store)
+            .await
+            .unwrap();
+        let outcome = connector
+            .sync(
+                &store,
+                SyncParams {
+                    synced_through: None,
+                    budget: None,
+                    inline_retries: 0,
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(outcome, SyncRunOutcome::Complete { pages_fetched: 1 });
+        let transactions_request =
+            &server.requests_to("GET", "/api/v2/accounts/acc-1/transactions/")[0];
+        assert!(transactions_request.query.starts_with("date_from="));
+
+        let raw_pages: Vec<RawPage> = pages
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|page| RawPage {
+                stream: page.stream.clone(),
+                payload: page.payload.clone(),
+            })
+            .collect();
+        let mapped = connector.map_pages(&raw_pages);
+        assert_eq!(mapped.transactions.len(), 2);
+        assert_eq!(mapped.transactions[0].amount, dec!(-12.30));
+        assert_eq!(mapped.transactions[0].description, "Coffee Bar");
+        assert_eq!(mapped.transactions[1].description, "Salary");
+        assert_eq!(mapped.skipped.len(), 1);
+
+        let balance = connector.fetch_balance().await.unwrap();
+        assert_eq!(
+            balance.cash,
+            vec![ProviderCashBalance {
+                currency: "EUR".to_string(),
+                amount: dec!(2487.70),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn unlinked_requisition_does_not_complete() {
+        let mut routes = aggregator_routes();
+        routes.retain(|route| route.path != "/api/v2/requisitions/req-1/");
+        routes.push(MockRoute::get(
+            "/api/v2/requisitions/req-1/",
+            json!({ "id": "req-1", "status": "RJ", "reference": "state-1", "link": "https://ob.example/start/req-1", "accounts": [] }),
+        ));
+        let server = MockHttpServer::start(routes).await;
+        let provider = GoCardlessProvider::with_config(config(&server));
+        let store = store(
+            Arc::new(Mutex::new(Some(b"req-1".to_vec()))),
+            Arc::new(Mutex::new(Vec::new())),
+        );
+
+        let err = provider
+            .complete_oauth(&store, "state-1", Some(REDIRECT))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("RJ"));
+    }
+
+    #[tokio::test]
+    async fn aggregator_errors_fail_the_fetch() {
+        let mut routes = aggregator_routes();
+        routes.retain(|route| route.path != "/api/v2/accounts/acc-1/transactions/");
+        routes.push(
+            MockRoute::get(
+                "/api/v2/accounts/acc-1/transactions/",
+                json!({ "summary": "Rate limit exceeded" }),
+            )
+            .with_status(429),
+        );
+        let server = MockHttpServer::start(routes).await;
+        let provider = GoCardlessProvider::with_config(config(&server));
+        let store = store(
+            Arc::new(Mutex::new(Some(b"req-1".to_vec()))),
+            Arc::new(Mutex::new(Vec::new())),
+        );
+
+        let connector = provider
+            .build_connector("acc-1", CredentialSource::Stored, &store)
+            .await
+            .unwrap();
+        let err = connector.fetch_page(None, None).await.unwrap_err();
+        assert!(err.to_string().contains("429"));
+    }
+}
@@ -1,8 +1,13 @@
 pub mod client_supplied;
+pub mod crypto;
 pub mod dedup;
+pub mod gocardless;
+#[cfg(test)]
+pub(crate) mod mock_http;
 pub mod models;
 pub mod port;
 pub mod provider;
+pub mod statement_file;
 pub mod trading212;
 pub mod truelayer;
 mod util;
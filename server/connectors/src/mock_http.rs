@@ -0,0 +1,193 @@
+//! Minimal HTTP/1.1 server for exercising provider clients offline. Each route answers a
+//! method and path (query string ignored) with a canned JSON body; every request is recorded
+//! so tests can assert on what a client sent.
+
+use std::sync::{Arc, Mutex};
+
+use serde_json::Value;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+#[derive(Clone, Debug)]
+pub(crate) struct MockRoute {
+    pub method: &'static str,
+    pub path: String,
+    pub status: u16,
+    pub body: Value,
+}
+
+impl MockRoute {
+    pub fn get(path: impl Into<String>, body: Value) -> Self {
+        Self {
+            method: "GET",
+            path: path.into(),
+            status: 200,
+            body,
+        }
+    }
+
+    pub fn post(path: impl Into<String>, body: Value) -> Self {
+        Self {
+            method: "POST",
+            path: path.into(),
+            status: 200,
+            body,
+        }
+    }
+
+    pub fn with_status(mut self, status: u16) -> Self {
+        self.status = status;
+        self
+    }
+}
+
+#[derive(Clone, Debug)]
+pub(crate) struct RecordedRequest {
+    pub method: String,
+    pub path: String,
+    pub query: String,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl RecordedRequest {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn json(&self) -> Value {
+        serde_json::from_str(&self.body).unwrap_or(Value::Null)
+    }
+}
+
+pub(crate) struct MockHttpServer {
+    base_url: String,
+    requests: Arc<Mutex<Vec<RecordedRequest>>>,
+    accept_loop: tokio::task::JoinHandle<()>,
+}
+
+impl MockHttpServer {
+    pub async fn start(routes: Vec<MockRoute>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0")
+            .await
+            .expect("bind mock server");
+        let base_url = format!("http://{}", listener.local_addr().expect("local addr"));
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let routes = Arc::new(routes);
+
+        let recorded = requests.clone();
+        let accept_loop = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let routes = routes.clone();
+                let recorded = recorded.clone();
+                tokio::spawn(async move {
+                    let _ = serve_connection(stream, &routes, &recorded).await;
+                });
+            }
+        });
+
+        Self {
+            base_url,
+            requests,
+            accept_loop,
+        }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub fn requests(&self) -> Vec<RecordedRequest> {
+        self.requests.lock().expect("requests lock").clone()
+    }
+
+    pub fn requests_to(&self, method: &str, path: &str) -> Vec<RecordedRequest> {
+        self.requests()
+            .into_iter()
+            .filter(|request| request.method == method && request.path == path)
+            .collect()
+    }
+}
+
+impl Drop for MockHttpServer {
+    fn drop(&mut self) {
+        self.accept_loop.abort();
+    }
+}
+
+async fn serve_connection(
+    mut stream: TcpStream,
+    routes: &[MockRoute],
+    recorded: &Mutex<Vec<RecordedRequest>>,
+) -> std::io::Result<()> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let header_end = loop {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            return Ok(());
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+        if let Some(pos) = buffer.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos + 4;
+        }
+    };
+
+    let head = String::from_utf8_lossy(&buffer[..header_end]).to_string();
+    let mut lines = head.split("\r\n");
+    let mut request_line = lines.next().unwrap_or_default().split_whitespace();
+    let method = request_line.next().unwrap_or_default().to_string();
+    let target = request_line.next().unwrap_or_default();
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let headers: Vec<(String, String)> = lines
+        .filter_map(|line| line.split_once(':'))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect();
+
+    let content_length = headers
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case("content-length"))
+        .and_then(|(_, value)| value.parse::<usize>().ok())
+        .unwrap_or(0);
+    while buffer.len() < header_end + content_length {
+        let read = stream.read(&mut chunk).await?;
+        if read == 0 {
+            break;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    }
+    let body = String::from_utf8_lossy(&buffer[header_end..]).to_string();
+
+    let (status, response_body) = match routes
+        .iter()
+        .find(|route| route.method == method && route.path == path)
+    {
+        Some(route) => (route.status, route.body.to_string()),
+        None => (
+            404,
+            serde_json::json!({ "detail": format!("no mock route for {method} {path}") })
+                .to_string(),
+        ),
+    };
+
+    recorded
+        .lock()
+        .expect("requests lock")
+        .push(RecordedRequest {
+            method,
+            path: path.to_string(),
+            query: query.to_string(),
+            headers,
+            body,
+        });
+
+    let response = format!(
+        "HTTP/1.1 {status} MOCK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{response_body}",
+        response_body.len()
+    );
+    stream.write_all(response.as_bytes()).await?;
+    stream.shutdown().await
+}
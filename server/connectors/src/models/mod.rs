@@ -5,4 +5,7 @@ pub mod transaction;
 
 pub use account::ProviderAccount;
 pub use sync::{FetchedPage, RawPage, SyncCursor};
-pub use transaction::{MappedTransaction, ProviderTransaction, SkippedTransaction};
+pub use transaction::{
+    MappedPages, MappedTransaction, ProviderTransaction, ProviderTransactionKind,
+    SkippedTransaction,
+};
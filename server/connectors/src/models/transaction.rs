@@ -2,6 +2,49 @@ use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 
+/// How the importer classifies a provider transaction. Mappers for cash and brokerage
+/// providers leave it `Inferred`; crypto mappers name the movement explicitly because a
+/// coin moving in or out of a wallet is not a purchase or a dividend.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProviderTransactionKind {
+    /// Regular cash movement, purchase/sale or cash dividend, depending on whether
+    /// `asset_identifier` and `quantity` are set.
+    #[default]
+    Inferred,
+    /// Exchange of `quantity` of `asset_identifier` against `amount` of `currency`.
+    Trade,
+    /// `amount` of the `currency` asset deposited into the account.
+    TransferIn,
+    /// `amount` (negative) of the `currency` asset withdrawn from the account. The coins
+    /// usually land in another wallet, so the importer holds these for review rather than
+    /// booking them as a disposal.
+    TransferOut,
+    /// `amount` of a fiat `currency` paid into the account from a bank.
+    CashTransferIn,
+    /// `amount` (negative) of a fiat `currency` paid out of the account to a bank.
+    CashTransferOut,
+    /// Staking or similar reward paid in the `currency` asset itself.
+    Reward,
+    /// Network or account fee (negative `amount`) paid in the `currency` asset.
+    Fee,
+}
+
+impl ProviderTransactionKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ProviderTransactionKind::Inferred => "inferred",
+            ProviderTransactionKind::Trade => "trade",
+            ProviderTransactionKind::TransferIn => "transfer_in",
+            ProviderTransactionKind::TransferOut => "transfer_out",
+            ProviderTransactionKind::CashTransferIn => "cash_transfer_in",
+            ProviderTransactionKind::CashTransferOut => "cash_transfer_out",
+            ProviderTransactionKind::Reward => "reward",
+            ProviderTransactionKind::Fee => "fee",
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProviderTransaction {
     pub external_id: String,
@@ -11,6 +54,12 @@ pub struct ProviderTransaction {
     pub description: String,
     pub asset_identifier: Option<String>,
     pub quantity: Option<Decimal>,
+    #[serde(default)]
+    pub kind: ProviderTransactionKind,
+    /// Name or account of the other party, when the provider gives one. Not part of the
+    /// external hash, so it can be filled in without re-importing anything.
+    #[serde(default)]
+    pub counterparty: Option<String>,
 }
 
 impl ProviderTransaction {
@@ -31,6 +80,11 @@ impl ProviderTransaction {
             hasher.update(part.as_bytes());
             hasher.update(b":");
         }
+        // Left out for inferred transactions so hashes of existing imports stay stable.
+        if self.kind != ProviderTransactionKind::Inferred {
+            hasher.update(self.kind.as_str().as_bytes());
+            hasher.update(b":");
+        }
         format!("{:x}", hasher.finalize())
     }
 }
@@ -46,6 +100,14 @@ pub enum MappedTransaction {
     Skipped(SkippedTransaction),
 }
 
+/// Result of mapping archived pages: the transactions to project and the items the
+/// mapper could not turn into one.
+#[derive(Debug, Default)]
+pub struct MappedPages {
+    pub transactions: Vec<ProviderTransaction>,
+    pub skipped: Vec<SkippedTransaction>,
+}
+
 pub(crate) fn log_skipped(provider: &str, skipped: &[SkippedTransaction]) {
     if skipped.is_empty() {
         return;
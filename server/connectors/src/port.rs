@@ -1,6 +1,6 @@
 use crate::models::balance::ProviderBalance;
 use crate::models::sync::{FetchedPage, RawPage, SyncCursor};
-use crate::models::transaction::ProviderTransaction;
+use crate::models::transaction::MappedPages;
 use crate::provider::ProviderKind;
 use async_trait::async_trait;
 use time::OffsetDateTime;
@@ -64,7 +64,7 @@ pub trait Connector: Send + Sync + 'static {
 
     async fn fetch_balance(&self) -> anyhow::Result<ProviderBalance>;
 
-    fn map_pages(&self, pages: &[RawPage]) -> Vec<ProviderTransaction>;
+    fn map_pages(&self, pages: &[RawPage]) -> MappedPages;
 
     fn capabilities(&self) -> ProviderCapabilities {
         ProviderCapabilities::default()
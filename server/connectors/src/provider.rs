@@ -3,9 +3,13 @@ use std::str::FromStr;
 use async_trait::async_trait;
 use serde_json::Value;
 
+use crate::crypto::kraken::provider::KrakenProvider;
+use crate::crypto::wallet::provider::CryptoWalletProvider;
+use crate::gocardless::provider::GoCardlessProvider;
 use crate::models::account::ProviderAccount;
-use crate::models::transaction::MappedTransaction;
+use crate::models::transaction::{MappedPages, MappedTransaction};
 use crate::port::{Connector, ConnectorStore};
+use crate::statement_file::provider::StatementFileProvider;
 use crate::trading212::provider::Trading212Provider;
 use crate::truelayer::provider::TrueLayerProvider;
 use crate::Result;
@@ -14,6 +18,10 @@ use crate::Result;
 pub enum ProviderKind {
     Trading212,
     TrueLayer,
+    GoCardless,
+    StatementFile,
+    Kraken,
+    CryptoWallet,
 }
 
 impl ProviderKind {
@@ -21,20 +29,44 @@ impl ProviderKind {
         match self {
             ProviderKind::Trading212 => "trading212",
             ProviderKind::TrueLayer => "truelayer",
+            ProviderKind::GoCardless => "gocardless",
+            ProviderKind::StatementFile => "statement_file",
+            ProviderKind::Kraken => "kraken",
+            ProviderKind::CryptoWallet => "crypto_wallet",
         }
     }
 
-    pub fn map_item(self, stream: &str, item: &Value) -> Option<MappedTransaction> {
+    /// Transactions of one archived item. Most items map to exactly one; a crypto withdrawal
+    /// also yields the network fee it paid.
+    pub fn map_item(self, stream: &str, item: &Value) -> Vec<MappedTransaction> {
         match self {
             ProviderKind::Trading212 => match stream {
-                "transactions" => Some(crate::trading212::mapper::map_transaction(item)),
-                "orders" => Some(crate::trading212::mapper::map_order(item)),
-                "dividends" => Some(crate::trading212::mapper::map_dividend(item)),
-                _ => None,
+                "transactions" => vec![crate::trading212::mapper::map_transaction(item)],
+                "orders" => vec![crate::trading212::mapper::map_order(item)],
+                "dividends" => vec![crate::trading212::mapper::map_dividend(item)],
+                _ => vec![],
             },
             ProviderKind::TrueLayer => match stream {
-                "transactions" => Some(crate::truelayer::mapper::map_transaction(item)),
-                _ => None,
+                "transactions" => vec![crate::truelayer::mapper::map_transaction(item)],
+                _ => vec![],
+            },
+            ProviderKind::GoCardless => match stream {
+                "transactions" => vec![crate::gocardless::mapper::map_transaction(item)],
+                _ => vec![],
+            },
+            ProviderKind::StatementFile => match stream {
+                "transactions" => vec![crate::statement_file::mapper::map_transaction(item)],
+                _ => vec![],
+            },
+            ProviderKind::Kraken => match stream {
+                "trades" => vec![crate::crypto::kraken::mapper::map_trade(item)],
+                "ledgers" => crate::crypto::kraken::mapper::map_ledger_entry(item),
+                _ => vec![],
+            },
+            ProviderKind::CryptoWallet => match stream {
+                "bitcoin" => crate::crypto::wallet::mapper::map_bitcoin_transaction(item),
+                "ethereum" => crate::crypto::wallet::mapper::map_ethereum_transaction(item),
+                _ => vec![],
             },
         }
     }
@@ -47,6 +79,10 @@ impl FromStr for ProviderKind {
         match value {
             "trading212" => Ok(ProviderKind::Trading212),
             "truelayer" => Ok(ProviderKind::TrueLayer),
+            "gocardless" => Ok(ProviderKind::GoCardless),
+            "statement_file" => Ok(ProviderKind::StatementFile),
+            "kraken" => Ok(ProviderKind::Kraken),
+            "crypto_wallet" => Ok(ProviderKind::CryptoWallet),
             other => anyhow::bail!("unknown provider kind: {other}"),
         }
     }
@@ -80,7 +116,7 @@ pub trait Provider: Send + Sync {
         false
     }
 
-    fn begin_oauth(
+    async fn begin_oauth(
         &self,
         _store: &dyn ConnectorStore,
         _state: &str,
@@ -99,35 +135,40 @@ pub trait Provider: Send + Sync {
     }
 }
 
-pub fn map_pages(
-    kind: ProviderKind,
-    pages: &[crate::models::sync::RawPage],
-) -> Vec<crate::models::transaction::ProviderTransaction> {
-    let mut transactions = Vec::new();
-    let mut skipped = Vec::new();
+pub fn map_pages(kind: ProviderKind, pages: &[crate::models::sync::RawPage]) -> MappedPages {
+    let mut mapped = MappedPages::default();
     for page in pages {
         let Some(items) = page.payload.as_array() else {
             continue;
         };
         for item in items {
-            match kind.map_item(&page.stream, item) {
-                Some(MappedTransaction::Provider(p)) => transactions.push(p),
-                Some(MappedTransaction::Skipped(s)) => skipped.push(s),
-                None => {}
+            for transaction in kind.map_item(&page.stream, item) {
+                match transaction {
+                    MappedTransaction::Provider(p) => mapped.transactions.push(p),
+                    MappedTransaction::Skipped(s) => mapped.skipped.push(s),
+                }
             }
         }
     }
-    crate::models::transaction::log_skipped(kind.as_str(), &skipped);
-    transactions
+    crate::models::transaction::log_skipped(kind.as_str(), &mapped.skipped);
+    mapped
 }
 
 impl ProviderKind {
     pub fn provider(self) -> &'static dyn Provider {
         static TRADING212: Trading212Provider = Trading212Provider;
         static TRUELAYER: TrueLayerProvider = TrueLayerProvider;
+        static GOCARDLESS: GoCardlessProvider = GoCardlessProvider::from_env();
+        static STATEMENT_FILE: StatementFileProvider = StatementFileProvider;
+        static KRAKEN: KrakenProvider = KrakenProvider::from_env();
+        static CRYPTO_WALLET: CryptoWalletProvider = CryptoWalletProvider::from_env();
         match self {
             ProviderKind::Trading212 => &TRADING212,
             ProviderKind::TrueLayer => &TRUELAYER,
+            ProviderKind::GoCardless => &GOCARDLESS,
+            ProviderKind::StatementFile => &STATEMENT_FILE,
+            ProviderKind::Kraken => &KRAKEN,
+            ProviderKind::CryptoWallet => &CRYPTO_WALLET,
         }
     }
 }
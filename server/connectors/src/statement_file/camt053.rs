@@ -0,0 +1,201 @@
+use roxmltree::{Document, Node};
+use time::Date;
+
+use super::{parse_amount, StatementLine};
+use crate::Result;
+
+/// Parses the booked entries (`Ntry`) of an ISO 20022 `camt.053` bank-to-customer
+/// statement. Namespaces are ignored so any schema version from `camt.053.001.02`
+/// onwards is accepted. Batch entries are imported as one line for the entry total.
+pub fn parse(content: &str) -> Result<Vec<StatementLine>> {
+    let document = Document::parse(content)?;
+    let root = document.root_element();
+    if descendant(root, "BkToCstmrStmt").is_none() {
+        anyhow::bail!("not a camt.053 statement");
+    }
+
+    let mut lines = Vec::new();
+    for statement in root.descendants().filter(|n| is(n, "Stmt")) {
+        for entry in statement.children().filter(|n| is(n, "Ntry")) {
+            if let Some(line) = to_line(entry)? {
+                lines.push(line);
+            }
+        }
+    }
+    Ok(lines)
+}
+
+fn to_line(entry: Node) -> Result<Option<StatementLine>> {
+    let status = child(entry, "Sts")
+        .map(|sts| child(sts, "Cd").unwrap_or(sts))
+        .and_then(|n| n.text())
+        .map(str::trim);
+    if matches!(status, Some("PDNG") | Some("INFO")) {
+        return Ok(None);
+    }
+
+    let reference = child_text(entry, "AcctSvcrRef")
+        .or_else(|| child_text(entry, "NtryRef"))
+        .or_else(|| {
+            single_transaction(entry)
+                .and_then(|tx| child(tx, "Refs"))
+                .and_then(|refs| {
+                    child_text(refs, "AcctSvcrRef").or_else(|| child_text(refs, "EndToEndId"))
+                })
+                .filter(|id| *id != "NOTPROVIDED")
+        })
+        .map(str::to_string);
+    let context = reference.as_deref().unwrap_or("<no reference>");
+
+    let amount_node =
+        child(entry, "Amt").ok_or_else(|| anyhow::anyhow!("entry {context}: missing Amt"))?;
+    let amount = amount_node
+        .text()
+        .and_then(|value| parse_amount(value, '.'))
+        .ok_or_else(|| anyhow::anyhow!("entry {context}: unparseable Amt"))?;
+    let debit = match child_text(entry, "CdtDbtInd") {
+        Some("DBIT") => true,
+        Some("CRDT") => false,
+        _ => anyhow::bail!("entry {context}: missing or invalid CdtDbtInd"),
+    };
+
+    let date = ["BookgDt", "ValDt"]
+        .iter()
+        .filter_map(|name| child(entry, name))
+        .find_map(|n| child_text(n, "Dt").or_else(|| child_text(n, "DtTm")))
+        .and_then(|value| value.get(0..10))
+        .and_then(parse_iso_date)
+        .ok_or_else(|| anyhow::anyhow!("entry {context}: missing or unparseable booking date"))?;
+
+    Ok(Some(StatementLine {
+        reference,
+        date,
+        amount: if debit { -amount.abs() } else { amount.abs() },
+        currency: amount_node.attribute("Ccy").map(str::to_string),
+        description: describe(entry, debit),
+    }))
+}
+
+/// The counterparty name followed by the unstructured remittance information, falling
+/// back to the entry's free-text note.
+fn describe(entry: Node, debit: bool) -> String {
+    let mut parts: Vec<&str> = Vec::new();
+    if let Some(tx) = single_transaction(entry) {
+        let party = if debit { "Cdtr" } else { "Dbtr" };
+        if let Some(name) = child(tx, "RltdPties")
+            .and_then(|parties| child(parties, party))
+            .and_then(|p| child(p, "Pty").or(Some(p)))
+            .and_then(|p| child_text(p, "Nm"))
+        {
+            parts.push(name);
+        }
+        if let Some(remittance) = child(tx, "RmtInf") {
+            parts.extend(
+                remittance
+                    .children()
+                    .filter(|n| is(n, "Ustrd"))
+                    .filter_map(|n| n.text())
+                    .map(str::trim),
+            );
+        }
+    }
+    if parts.is_empty() {
+        if let Some(info) = child_text(entry, "AddtlNtryInf") {
+            parts.push(info);
+        }
+    }
+    parts.join(" ")
+}
+
+fn single_transaction<'a, 'input>(entry: Node<'a, 'input>) -> Option<Node<'a, 'input>> {
+    let mut transactions = child(entry, "NtryDtls")?
+        .children()
+        .filter(|n| is(n, "TxDtls"));
+    let first = transactions.next()?;
+    transactions.next().is_none().then_some(first)
+}
+
+fn parse_iso_date(value: &str) -> Option<Date> {
+    let format = time::macros::format_description!("[year]-[month]-[day]");
+    Date::parse(value, &format).ok()
+}
+
+fn is(node: &Node, name: &str) -> bool {
+    node.is_element() && node.tag_name().name() == name
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.children().find(|n| is(n, name))
+}
+
+fn child_text<'a>(node: Node<'a, '_>, name: &str) -> Option<&'a str> {
+    child(node, name)
+        .and_then(|n| n.text())
+        .map(str::trim)
+        .filter(|text| !text.is_empty())
+}
+
+fn descendant<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    node.descendants().find(|n| is(n, name))
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use time::macros::date;
+
+    use super::*;
+
+    const STATEMENT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Document xmlns="urn:iso:std:iso:20022:tech:xsd:camt.053.001.02">
+  <BkToCstmrStmt>
+    <Stmt>
+      <Ntry>
+        <Amt Ccy="EUR">49.90</Amt>
+        <CdtDbtInd>DBIT</CdtDbtInd>
+        <Sts>BOOK</Sts>
+        <BookgDt><Dt>2024-03-04</Dt></BookgDt>
+        <AcctSvcrRef>2024030400123</AcctSvcrRef>
+        <NtryDtls><TxDtls>
+          <RltdPties><Cdtr><Nm>Stadtwerke</Nm></Cdtr></RltdPties>
+          <RmtInf><Ustrd>Abschlag Maerz</Ustrd></RmtInf>
+        </TxDtls></NtryDtls>
+      </Ntry>
+      <Ntry>
+        <Amt Ccy="EUR">1200.00</Amt>
+        <CdtDbtInd>CRDT</CdtDbtInd>
+        <Sts>PDNG</Sts>
+        <BookgDt><Dt>2024-03-05</Dt></BookgDt>
+      </Ntry>
+      <Ntry>
+        <Amt Ccy="EUR">15.00</Amt>
+        <CdtDbtInd>CRDT</CdtDbtInd>
+        <Sts><Cd>BOOK</Cd></Sts>
+        <BookgDt><DtTm>2024-03-06T10:00:00+01:00</DtTm></BookgDt>
+        <AddtlNtryInf>Gutschrift</AddtlNtryInf>
+      </Ntry>
+    </Stmt>
+  </BkToCstmrStmt>
+</Document>"#;
+
+    #[test]
+    fn parses_booked_entries() {
+        let lines = parse(STATEMENT).unwrap();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].amount, dec!(-49.90));
+        assert_eq!(lines[0].currency.as_deref(), Some("EUR"));
+        assert_eq!(lines[0].date, date!(2024 - 03 - 04));
+        assert_eq!(lines[0].reference.as_deref(), Some("2024030400123"));
+        assert_eq!(lines[0].description, "Stadtwerke Abschlag Maerz");
+        assert_eq!(lines[1].amount, dec!(15.00));
+        assert_eq!(lines[1].date, date!(2024 - 03 - 06));
+        assert_eq!(lines[1].reference, None);
+        assert_eq!(lines[1].description, "Gutschrift");
+    }
+
+    #[test]
+    fn rejects_other_xml() {
+        assert!(parse("<Document><Other/></Document>").is_err());
+    }
+}
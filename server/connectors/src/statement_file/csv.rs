@@ -0,0 +1,203 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use time::format_description::BorrowedFormatItem;
+use time::Date;
+
+use super::{parse_amount, StatementLine};
+use crate::Result;
+
+/// Where the amount of a CSV line comes from. Columns are zero-based.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CsvAmountColumns {
+    /// One signed column. Set `negate` for banks that print money going out as positive.
+    Signed { column: usize, negate: bool },
+    /// Separate money-out and money-in columns, both printed as positive numbers.
+    DebitCredit { debit: usize, credit: usize },
+}
+
+/// How to read one bank's CSV export. Saved per account so that the next upload from the
+/// same bank needs no setup. Columns are zero-based.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CsvColumnMapping {
+    pub delimiter: char,
+    /// Rows to skip before the first transaction, including the header row.
+    pub skip_rows: usize,
+    pub date_column: usize,
+    /// A `time` format description, e.g. `[day]/[month]/[year]`.
+    pub date_format: String,
+    /// Joined with a space to form the description.
+    pub description_columns: Vec<usize>,
+    pub amount: CsvAmountColumns,
+    pub decimal_separator: char,
+    pub currency_column: Option<usize>,
+    pub reference_column: Option<usize>,
+}
+
+impl CsvColumnMapping {
+    pub fn validate(&self) -> Result<()> {
+        if !self.delimiter.is_ascii() {
+            anyhow::bail!("delimiter must be a single ASCII character");
+        }
+        if self.decimal_separator != '.' && self.decimal_separator != ',' {
+            anyhow::bail!("decimal separator must be '.' or ','");
+        }
+        if self.description_columns.is_empty() {
+            anyhow::bail!("at least one description column is required");
+        }
+        self.date_items()?;
+        Ok(())
+    }
+
+    fn date_items(&self) -> Result<Vec<BorrowedFormatItem<'_>>> {
+        time::format_description::parse_borrowed::<2>(&self.date_format)
+            .map_err(|e| anyhow::anyhow!("invalid date format '{}': {e}", self.date_format))
+    }
+}
+
+pub fn parse(content: &str, mapping: &CsvColumnMapping) -> Result<Vec<StatementLine>> {
+    mapping.validate()?;
+    let date_items = mapping.date_items()?;
+
+    let mut reader = ::csv::ReaderBuilder::new()
+        .delimiter(mapping.delimiter as u8)
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(content.as_bytes());
+
+    let mut lines = Vec::new();
+    for (index, record) in reader.records().enumerate().skip(mapping.skip_rows) {
+        let record = record?;
+        if record.iter().all(|field| field.trim().is_empty()) {
+            continue;
+        }
+        let row = index + 1;
+        let field = |column: usize| record.get(column).map(str::trim).unwrap_or("");
+
+        let date = Date::parse(field(mapping.date_column), &date_items)
+            .map_err(|e| anyhow::anyhow!("row {row}: unparseable date: {e}"))?;
+
+        let amount = match mapping.amount {
+            CsvAmountColumns::Signed { column, negate } => {
+                let amount = parse_amount(field(column), mapping.decimal_separator)
+                    .ok_or_else(|| anyhow::anyhow!("row {row}: unparseable amount"))?;
+                if negate {
+                    -amount
+                } else {
+                    amount
+                }
+            }
+            CsvAmountColumns::DebitCredit { debit, credit } => {
+                let debit = parse_amount(field(debit), mapping.decimal_separator);
+                let credit = parse_amount(field(credit), mapping.decimal_separator);
+                if debit.is_none() && credit.is_none() {
+                    anyhow::bail!("row {row}: neither debit nor credit amount present");
+                }
+                credit.unwrap_or(Decimal::ZERO).abs() - debit.unwrap_or(Decimal::ZERO).abs()
+            }
+        };
+
+        let description = mapping
+            .description_columns
+            .iter()
+            .map(|column| field(*column))
+            .filter(|value| !value.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        lines.push(StatementLine {
+            reference: mapping
+                .reference_column
+                .map(field)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string),
+            date,
+            amount,
+            currency: mapping
+                .currency_column
+                .map(field)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string),
+            description,
+        });
+    }
+    Ok(lines)
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use time::macros::date;
+
+    use super::*;
+
+    fn mapping(amount: CsvAmountColumns) -> CsvColumnMapping {
+        CsvColumnMapping {
+            delimiter: ';',
+            skip_rows: 1,
+            date_column: 0,
+            date_format: "[day].[month].[year]".to_string(),
+            description_columns: vec![1, 2],
+            amount,
+            decimal_separator: ',',
+            currency_column: None,
+            reference_column: None,
+        }
+    }
+
+    #[test]
+    fn parses_signed_amounts() {
+        let content = "Date;Payee;Memo;Amount\n\
+                       01.03.2024;Bakery;;-4,20\n\
+                       \n\
+                       02.03.2024;Employer;March salary;2.500,00\n";
+
+        let lines = parse(
+            content,
+            &mapping(CsvAmountColumns::Signed {
+                column: 3,
+                negate: false,
+            }),
+        )
+        .unwrap();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].date, date!(2024 - 03 - 01));
+        assert_eq!(lines[0].amount, dec!(-4.20));
+        assert_eq!(lines[0].description, "Bakery");
+        assert_eq!(lines[1].amount, dec!(2500.00));
+        assert_eq!(lines[1].description, "Employer March salary");
+    }
+
+    #[test]
+    fn parses_debit_and_credit_columns() {
+        let content = "Date;Payee;Memo;Out;In\n01.03.2024;Rent;;950,00;\n";
+
+        let lines = parse(
+            content,
+            &mapping(CsvAmountColumns::DebitCredit {
+                debit: 3,
+                credit: 4,
+            }),
+        )
+        .unwrap();
+
+        assert_eq!(lines[0].amount, dec!(-950.00));
+    }
+
+    #[test]
+    fn reports_the_failing_row() {
+        let content = "Date;Payee;Memo;Amount\n2024-03-01;Bakery;;-4,20\n";
+
+        let err = parse(
+            content,
+            &mapping(CsvAmountColumns::Signed {
+                column: 3,
+                negate: false,
+            }),
+        )
+        .unwrap_err();
+
+        assert!(err.to_string().starts_with("row 2:"));
+    }
+}
@@ -0,0 +1,48 @@
+use serde_json::Value;
+
+use super::StatementItem;
+use crate::models::{
+    MappedTransaction, ProviderTransaction, ProviderTransactionKind, SkippedTransaction,
+};
+
+fn parse_date(s: &str) -> Option<time::OffsetDateTime> {
+    let format = time::macros::format_description!("[year]-[month]-[day]");
+    time::Date::parse(s, &format)
+        .ok()
+        .map(|date| date.midnight().assume_utc())
+}
+
+pub fn map_transaction(raw: &Value) -> MappedTransaction {
+    let item: StatementItem = match serde_json::from_value(raw.clone()) {
+        Ok(item) => item,
+        Err(e) => {
+            let external_id = raw
+                .get("external_id")
+                .and_then(|v| v.as_str())
+                .unwrap_or("<missing external_id>");
+            return MappedTransaction::Skipped(SkippedTransaction {
+                external_id: external_id.to_string(),
+                reason: format!("malformed statement item: {e}"),
+            });
+        }
+    };
+
+    let Some(date) = parse_date(&item.date) else {
+        return MappedTransaction::Skipped(SkippedTransaction {
+            external_id: item.external_id,
+            reason: "unparseable date".to_string(),
+        });
+    };
+
+    MappedTransaction::Provider(ProviderTransaction {
+        external_id: item.external_id,
+        amount: item.amount,
+        currency: item.currency,
+        date,
+        description: item.description,
+        asset_identifier: None,
+        quantity: None,
+        kind: ProviderTransactionKind::Inferred,
+        counterparty: None,
+    })
+}
@@ -0,0 +1,183 @@
+use time::{Date, Month};
+
+use super::{parse_amount, StatementLine};
+use crate::Result;
+
+/// Parses `STMTTRN` records from bank and credit card statements. Works on both the
+/// SGML flavour of OFX 1.x, where leaf elements are never closed, and the XML of OFX 2.x.
+pub fn parse(content: &str) -> Result<Vec<StatementLine>> {
+    let mut lines = Vec::new();
+    let mut currency: Option<String> = None;
+    let mut record: Option<Vec<(String, String)>> = None;
+
+    for token in tokenize(content) {
+        match token {
+            Token::Open(tag) if tag == "STMTTRN" => record = Some(Vec::new()),
+            Token::Close(tag) if tag == "STMTTRN" => {
+                if let Some(fields) = record.take() {
+                    lines.push(to_line(&fields, currency.as_deref())?);
+                }
+            }
+            Token::Leaf(tag, value) => match record.as_mut() {
+                Some(fields) => fields.push((tag, value)),
+                None if tag == "CURDEF" => currency = Some(value),
+                None => {}
+            },
+            _ => {}
+        }
+    }
+
+    if lines.is_empty() && !content.to_ascii_uppercase().contains("<OFX") {
+        anyhow::bail!("not an OFX document");
+    }
+    Ok(lines)
+}
+
+fn to_line(fields: &[(String, String)], currency: Option<&str>) -> Result<StatementLine> {
+    let get = |name: &str| {
+        fields
+            .iter()
+            .find(|(tag, _)| tag == name)
+            .map(|(_, value)| value.as_str())
+    };
+
+    let reference = get("FITID").map(str::to_string);
+    let context = reference.as_deref().unwrap_or("<no FITID>");
+
+    let date = get("DTPOSTED")
+        .and_then(parse_ofx_date)
+        .ok_or_else(|| anyhow::anyhow!("transaction {context}: missing or unparseable DTPOSTED"))?;
+    let amount = get("TRNAMT")
+        .and_then(|value| parse_amount(value, '.'))
+        .ok_or_else(|| anyhow::anyhow!("transaction {context}: missing or unparseable TRNAMT"))?;
+
+    let description = match (get("NAME"), get("MEMO")) {
+        (Some(name), Some(memo)) if !name.contains(memo) => format!("{name} {memo}"),
+        (Some(name), _) => name.to_string(),
+        (None, Some(memo)) => memo.to_string(),
+        (None, None) => get("TRNTYPE").unwrap_or_default().to_string(),
+    };
+
+    Ok(StatementLine {
+        reference,
+        date,
+        amount,
+        currency: currency.map(str::to_string),
+        description,
+    })
+}
+
+/// `YYYYMMDD`, optionally followed by a time and zone which are ignored: statements book
+/// by day, and shifting by the zone could move a line to the neighbouring day.
+fn parse_ofx_date(value: &str) -> Option<Date> {
+    let digits = value.get(0..8)?;
+    if !digits.chars().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    let year = digits[0..4].parse().ok()?;
+    let month = Month::try_from(digits[4..6].parse::<u8>().ok()?).ok()?;
+    let day = digits[6..8].parse().ok()?;
+    Date::from_calendar_date(year, month, day).ok()
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    Open(String),
+    Close(String),
+    Leaf(String, String),
+}
+
+fn tokenize(content: &str) -> Vec<Token> {
+    let mut tokens = Vec::new();
+    let mut rest = content;
+    while let Some(start) = rest.find('<') {
+        let after = &rest[start + 1..];
+        let Some(end) = after.find('>') else {
+            break;
+        };
+        let tag = after[..end].trim();
+        rest = &after[end + 1..];
+
+        if tag.starts_with('?') || tag.starts_with('!') {
+            continue;
+        }
+        if let Some(name) = tag.strip_prefix('/') {
+            tokens.push(Token::Close(name.trim().to_ascii_uppercase()));
+            continue;
+        }
+
+        let name = tag
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .trim_end_matches('/')
+            .to_ascii_uppercase();
+        let text_end = rest.find('<').unwrap_or(rest.len());
+        let text = unescape(rest[..text_end].trim());
+        if text.is_empty() {
+            tokens.push(Token::Open(name));
+        } else {
+            tokens.push(Token::Leaf(name, text));
+        }
+    }
+    tokens
+}
+
+fn unescape(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&nbsp;", " ")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use time::macros::date;
+
+    use super::*;
+
+    #[test]
+    fn parses_sgml_ofx() {
+        let content = "OFXHEADER:100\nDATA:OFXSGML\n\n<OFX><BANKMSGSRSV1><STMTTRNRS><STMTRS>\n\
+            <CURDEF>GBP\n<BANKTRANLIST>\n\
+            <STMTTRN>\n<TRNTYPE>DEBIT\n<DTPOSTED>20240301120000[0:GMT]\n<TRNAMT>-12.99\n\
+            <FITID>202403010001\n<NAME>SPOTIFY\n<MEMO>P1234 &amp; co\n</STMTTRN>\n\
+            <STMTTRN>\n<TRNTYPE>CREDIT\n<DTPOSTED>20240302\n<TRNAMT>100.00\n\
+            <FITID>202403020001\n<NAME>REFUND\n</STMTTRN>\n\
+            </BANKTRANLIST></STMTRS></STMTTRNRS></BANKMSGSRSV1></OFX>";
+
+        let lines = parse(content).unwrap();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].date, date!(2024 - 03 - 01));
+        assert_eq!(lines[0].amount, dec!(-12.99));
+        assert_eq!(lines[0].reference.as_deref(), Some("202403010001"));
+        assert_eq!(lines[0].currency.as_deref(), Some("GBP"));
+        assert_eq!(lines[0].description, "SPOTIFY P1234 & co");
+        assert_eq!(lines[1].amount, dec!(100.00));
+    }
+
+    #[test]
+    fn parses_xml_ofx() {
+        let content = r#"<?xml version="1.0"?><?OFX OFXHEADER="200"?>
+            <OFX><CREDITCARDMSGSRSV1><CCSTMTTRNRS><CCSTMTRS><CURDEF>USD</CURDEF>
+            <BANKTRANLIST><STMTTRN><TRNTYPE>DEBIT</TRNTYPE><DTPOSTED>20240415</DTPOSTED>
+            <TRNAMT>-5.00</TRNAMT><FITID>X1</FITID><NAME>Parking</NAME></STMTTRN>
+            </BANKTRANLIST></CCSTMTRS></CCSTMTTRNRS></CREDITCARDMSGSRSV1></OFX>"#;
+
+        let lines = parse(content).unwrap();
+
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].description, "Parking");
+        assert_eq!(lines[0].currency.as_deref(), Some("USD"));
+    }
+
+    #[test]
+    fn rejects_other_documents() {
+        assert!(parse("Date,Amount\n2024-01-01,5").is_err());
+    }
+}
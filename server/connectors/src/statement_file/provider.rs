@@ -0,0 +1,42 @@
+use async_trait::async_trait;
+
+use crate::models::account::ProviderAccount;
+use crate::port::{Connector, ConnectorStore};
+use crate::provider::{CredentialSource, Provider, ProviderKind};
+use crate::Result;
+
+/// Statements are uploaded by the user, so there is nothing to fetch: connections use the
+/// client-supplied credential mode and every upload is ingested as one raw page.
+pub struct StatementFileProvider;
+
+#[async_trait]
+impl Provider for StatementFileProvider {
+    fn kind(&self) -> ProviderKind {
+        ProviderKind::StatementFile
+    }
+
+    async fn build_connector(
+        &self,
+        _provider_account_id: &str,
+        _credential: CredentialSource,
+        _store: &dyn ConnectorStore,
+    ) -> Result<Box<dyn Connector>> {
+        anyhow::bail!("statement_file connections cannot be synced — upload a statement instead")
+    }
+
+    // Raw pages are archived per provider account and projected into every binding of it,
+    // so each bank account needs its own id (e.g. its IBAN) to keep statements apart.
+    fn resolve_provider_account_id(
+        &self,
+        client_value: Option<String>,
+        _store: &dyn ConnectorStore,
+    ) -> Result<String> {
+        client_value.ok_or_else(|| {
+            anyhow::anyhow!("provider_account_id is required for statement_file bindings")
+        })
+    }
+
+    async fn list_accounts(&self, _store: &dyn ConnectorStore) -> Result<Vec<ProviderAccount>> {
+        Ok(Vec::new())
+    }
+}
@@ -0,0 +1,142 @@
+use time::{Date, Month};
+
+use super::{parse_amount, StatementLine};
+use crate::Result;
+
+/// QIF dates carry no indication of field order, so the uploader says which one the
+/// bank uses. Quicken itself writes month first.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QifDateOrder {
+    #[default]
+    MonthFirst,
+    DayFirst,
+}
+
+/// Parses the cash records of a QIF file: `D` date, `T`/`U` amount, `P` payee and
+/// `M` memo, each record ended by `^`. Investment account sections are skipped. QIF has
+/// no transaction ids, and amounts take the currency of the account they are imported into.
+pub fn parse(content: &str, date_order: QifDateOrder) -> Result<Vec<StatementLine>> {
+    let mut lines = Vec::new();
+    let mut in_transactions = false;
+    let mut date = None;
+    let mut amount = None;
+    let mut payee: Option<String> = None;
+    let mut memo: Option<String> = None;
+
+    for (index, raw) in content.lines().enumerate() {
+        let row = index + 1;
+        let raw = raw.trim_end();
+        if let Some(header) = raw.strip_prefix('!') {
+            let header = header.to_ascii_lowercase();
+            in_transactions = header.starts_with("type:")
+                && !matches!(
+                    header.as_str(),
+                    "type:cat" | "type:class" | "type:memorized" | "type:prices" | "type:invst"
+                );
+            continue;
+        }
+        if !in_transactions || raw.is_empty() {
+            continue;
+        }
+
+        let mut chars = raw.chars();
+        let code = chars.next();
+        let value = chars.as_str().trim();
+        match code {
+            Some('D') => {
+                date = Some(
+                    parse_qif_date(value, date_order)
+                        .ok_or_else(|| anyhow::anyhow!("line {row}: unparseable date '{value}'"))?,
+                )
+            }
+            Some('T' | 'U') => {
+                amount = Some(
+                    parse_amount(value, '.')
+                        .ok_or_else(|| anyhow::anyhow!("line {row}: unparseable amount"))?,
+                )
+            }
+            Some('P') => payee = Some(value.to_string()),
+            Some('M') => memo = Some(value.to_string()),
+            Some('^') => {
+                let (Some(date), Some(amount)) = (date.take(), amount.take()) else {
+                    anyhow::bail!("line {row}: record without date or amount");
+                };
+                let description = match (payee.take(), memo.take()) {
+                    (Some(payee), Some(memo)) if !memo.is_empty() => format!("{payee} {memo}"),
+                    (Some(payee), _) => payee,
+                    (None, memo) => memo.unwrap_or_default(),
+                };
+                lines.push(StatementLine {
+                    reference: None,
+                    date,
+                    amount,
+                    currency: None,
+                    description,
+                });
+            }
+            _ => {}
+        }
+    }
+    Ok(lines)
+}
+
+/// Accepts `MM/DD/YYYY`, `MM/DD'YY`, `DD.MM.YYYY`, `YYYY-MM-DD` and similar. Two-digit
+/// years before 70 are in the 2000s.
+fn parse_qif_date(value: &str, order: QifDateOrder) -> Option<Date> {
+    let parts: Vec<&str> = value
+        .split(['/', '-', '.', '\''])
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .collect();
+    let [a, b, c] = parts.as_slice() else {
+        return None;
+    };
+
+    let (year, month, day) = if a.len() == 4 {
+        (*a, *b, *c)
+    } else {
+        match order {
+            QifDateOrder::MonthFirst => (*c, *a, *b),
+            QifDateOrder::DayFirst => (*c, *b, *a),
+        }
+    };
+
+    let mut year: i32 = year.parse().ok()?;
+    if year < 100 {
+        year += if year < 70 { 2000 } else { 1900 };
+    }
+    let month = Month::try_from(month.parse::<u8>().ok()?).ok()?;
+    Date::from_calendar_date(year, month, day.parse().ok()?).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+    use time::macros::date;
+
+    use super::*;
+
+    #[test]
+    fn parses_bank_records() {
+        let content = "!Type:Bank\nD03/01'24\nT-1,200.00\nPLandlord\nMMarch rent\n^\n\
+                       D3/2/2024\nU45.10\nPInterest\n^\n";
+
+        let lines = parse(content, QifDateOrder::MonthFirst).unwrap();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].date, date!(2024 - 03 - 01));
+        assert_eq!(lines[0].amount, dec!(-1200.00));
+        assert_eq!(lines[0].description, "Landlord March rent");
+        assert_eq!(lines[1].date, date!(2024 - 03 - 02));
+    }
+
+    #[test]
+    fn honours_day_first_order_and_skips_category_lists() {
+        let content = "!Type:Cat\nNGroceries\n^\n!Type:CCard\nD02/03/2024\nT-9.99\nPBooks\n^\n";
+
+        let lines = parse(content, QifDateOrder::DayFirst).unwrap();
+
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].date, date!(2024 - 03 - 02));
+    }
+}
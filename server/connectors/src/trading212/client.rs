@@ -1,6 +1,6 @@
 use crate::models::balance::{ProviderAssetBalance, ProviderBalance, ProviderCashBalance};
 use crate::models::sync::{FetchedPage, RawPage, SyncCursor};
-use crate::models::transaction::ProviderTransaction;
+use crate::models::transaction::MappedPages;
 use crate::port::Connector;
 use crate::Result;
 use base64::Engine;
@@ -117,7 +117,7 @@ impl Connector for Trading212Client {
         Ok(ProviderBalance { quantities, cash })
     }
 
-    fn map_pages(&self, pages: &[RawPage]) -> Vec<ProviderTransaction> {
+    fn map_pages(&self, pages: &[RawPage]) -> MappedPages {
         crate::provider::map_pages(crate::provider::ProviderKind::Trading212, pages)
     }
 }
@@ -1,4 +1,6 @@
-use crate::models::{MappedTransaction, ProviderTransaction, SkippedTransaction};
+use crate::models::{
+    MappedTransaction, ProviderTransaction, ProviderTransactionKind, SkippedTransaction,
+};
 use serde_json::Value;
 
 fn skipped(external_id: impl Into<String>, reason: impl Into<String>) -> MappedTransaction {
@@ -39,7 +41,10 @@ pub fn map_transaction(raw: &Value) -> MappedTransaction {
         "fee",
     ];
     if !cash_types.contains(&tx_type.to_lowercase().as_str()) {
-        return skipped(external_id, "needs asset mapping — not imported");
+        return skipped(
+            external_id,
+            format!("{tx_type} transactions are not imported"),
+        );
     }
 
     let Some(amount) = raw.get("amount").and_then(crate::util::parse_decimal) else {
@@ -73,6 +78,8 @@ pub fn map_transaction(raw: &Value) -> MappedTransaction {
         description,
         asset_identifier: None,
         quantity: None,
+        kind: ProviderTransactionKind::Inferred,
+        counterparty: None,
     })
 }
 
@@ -160,6 +167,8 @@ pub fn map_order(raw: &Value) -> MappedTransaction {
         description: format!("{side} {quantity} {ticker}"),
         asset_identifier: Some(ticker),
         quantity: Some(quantity),
+        kind: ProviderTransactionKind::Inferred,
+        counterparty: None,
     })
 }
 
@@ -207,5 +216,7 @@ pub fn map_dividend(raw: &Value) -> MappedTransaction {
         description: format!("Dividend {ticker}"),
         asset_identifier: Some(ticker),
         quantity: None,
+        kind: ProviderTransactionKind::Inferred,
+        counterparty: None,
     })
 }
@@ -1,7 +1,7 @@
 use crate::models::account::ProviderAccount;
 use crate::models::balance::{ProviderBalance, ProviderCashBalance};
 use crate::models::sync::{FetchedPage, RawPage, SyncCursor};
-use crate::models::transaction::ProviderTransaction;
+use crate::models::transaction::MappedPages;
 use crate::port::{Connector, ProviderCapabilities};
 use crate::truelayer::auth::api_base;
 use crate::Result;
@@ -215,7 +215,7 @@ impl Connector for TrueLayerClient {
         }
     }
 
-    fn map_pages(&self, pages: &[RawPage]) -> Vec<ProviderTransaction> {
+    fn map_pages(&self, pages: &[RawPage]) -> MappedPages {
         crate::provider::map_pages(crate::provider::ProviderKind::TrueLayer, pages)
     }
 
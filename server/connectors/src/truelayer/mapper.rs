@@ -1,4 +1,6 @@
-use crate::models::{MappedTransaction, ProviderTransaction, SkippedTransaction};
+use crate::models::{
+    MappedTransaction, ProviderTransaction, ProviderTransactionKind, SkippedTransaction,
+};
 use serde_json::Value;
 
 fn parse_booking_date(s: &str) -> Option<time::OffsetDateTime> {
@@ -103,6 +105,12 @@ pub fn map_transaction(tx: &Value) -> MappedTransaction {
         .and_then(|v| v.as_str())
         .unwrap_or("")
         .to_string();
+    let counterparty = tx
+        .get("merchant_name")
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(str::to_string);
 
     // TrueLayer signs `amount` from the account-balance perspective, which inverts for cards
     // (a liability): a card payment (CREDIT) arrives negative and a purchase (DEBIT) positive.
@@ -123,5 +131,7 @@ pub fn map_transaction(tx: &Value) -> MappedTransaction {
         description,
         asset_identifier: None,
         quantity: None,
+        kind: ProviderTransactionKind::Inferred,
+        counterparty,
     })
 }
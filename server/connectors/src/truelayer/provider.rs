@@ -50,7 +50,7 @@ impl Provider for TrueLayerProvider {
         TrueLayerClient::list_accounts(&access_token).await
     }
 
-    fn begin_oauth(
+    async fn begin_oauth(
         &self,
         _store: &dyn ConnectorStore,
         state: &str,
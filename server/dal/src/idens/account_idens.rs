@@ -10,6 +10,7 @@ pub enum AccountIden {
     AccountType,
     Active,
     OwnershipShare,
+    CostBasisMethod,
 }
 
 impl Iden for AccountIden {
@@ -23,6 +24,7 @@ impl Iden for AccountIden {
             Self::LiquidityType => "liquidity_type",
             Self::Active => "active",
             Self::OwnershipShare => "ownership_share",
+            Self::CostBasisMethod => "cost_basis_method",
         }
     }
 }
@@ -0,0 +1,24 @@
+use sea_query::Iden;
+
+#[allow(dead_code)]
+pub enum AiReembedRunIden {
+    Table,
+    EmbeddingModel,
+    EmbeddingDims,
+    StartedAt,
+    UpdatedAt,
+    FinishedAt,
+}
+
+impl Iden for AiReembedRunIden {
+    fn unquoted(&self) -> &str {
+        match self {
+            Self::Table => "ai_reembed_run",
+            Self::EmbeddingModel => "embedding_model",
+            Self::EmbeddingDims => "embedding_dims",
+            Self::StartedAt => "started_at",
+            Self::UpdatedAt => "updated_at",
+            Self::FinishedAt => "finished_at",
+        }
+    }
+}
@@ -26,6 +26,8 @@ pub enum AssetsIden {
     BasePairId,
     UserId,
     Embedding,
+    EmbeddingModel,
+    EmbeddingDims,
 }
 
 #[allow(dead_code)]
@@ -108,6 +110,8 @@ impl Iden for AssetsIden {
             Self::BasePairId => "base_pair_id",
             Self::UserId => "user_id",
             Self::Embedding => "embedding",
+            Self::EmbeddingModel => "embedding_model",
+            Self::EmbeddingDims => "embedding_dims",
         }
     }
 }
@@ -0,0 +1,40 @@
+use sea_query::Iden;
+
+#[allow(dead_code)]
+pub enum BudgetIden {
+    Table,
+    Id,
+    UserId,
+    CategoryId,
+    CategoryTypeId,
+    Amount,
+    Period,
+    Rollover,
+    StartDate,
+    CreatedAt,
+    UpdatedAt,
+}
+
+impl Iden for BudgetIden {
+    fn unquoted(&self) -> &str {
+        match self {
+            Self::Table => "budget",
+            Self::Id => "id",
+            Self::UserId => "user_id",
+            Self::CategoryId => "category_id",
+            Self::CategoryTypeId => "category_type_id",
+            Self::Amount => "amount",
+            Self::Period => "period",
+            Self::Rollover => "rollover",
+            Self::StartDate => "start_date",
+            Self::CreatedAt => "created_at",
+            Self::UpdatedAt => "updated_at",
+        }
+    }
+}
+
+#[derive(Iden)]
+pub enum BudgetSpendingIden {
+    Day,
+    Quantity,
+}
@@ -122,6 +122,7 @@ pub enum ConnectorTransactionIden {
     ExternalHash,
     EditedByUser,
     ImportedAt,
+    Counterparty,
 }
 
 impl Iden for ConnectorTransactionIden {
@@ -135,6 +136,7 @@ impl Iden for ConnectorTransactionIden {
             Self::ExternalHash => "external_hash",
             Self::EditedByUser => "edited_by_user",
             Self::ImportedAt => "imported_at",
+            Self::Counterparty => "counterparty",
         }
     }
 }
@@ -187,3 +189,96 @@ impl Iden for ConnectorSecretIden {
         }
     }
 }
+
+#[allow(dead_code)]
+pub enum ConnectorImportIssueIden {
+    Table,
+    Id,
+    BindingId,
+    PageId,
+    ExternalId,
+    Stage,
+    Reason,
+    DateTransacted,
+    Amount,
+    Currency,
+    Description,
+    AssetIdentifier,
+    Quantity,
+    Dismissed,
+    FirstSeenAt,
+    LastSeenAt,
+}
+
+impl Iden for ConnectorImportIssueIden {
+    fn unquoted(&self) -> &str {
+        match self {
+            Self::Table => "connector_import_issue",
+            Self::Id => "id",
+            Self::BindingId => "binding_id",
+            Self::PageId => "page_id",
+            Self::ExternalId => "external_id",
+            Self::Stage => "stage",
+            Self::Reason => "reason",
+            Self::DateTransacted => "date_transacted",
+            Self::Amount => "amount",
+            Self::Currency => "currency",
+            Self::Description => "description",
+            Self::AssetIdentifier => "asset_identifier",
+            Self::Quantity => "quantity",
+            Self::Dismissed => "dismissed",
+            Self::FirstSeenAt => "first_seen_at",
+            Self::LastSeenAt => "last_seen_at",
+        }
+    }
+}
+
+#[allow(dead_code)]
+pub enum ConnectorDuplicateCandidateIden {
+    Table,
+    Id,
+    TransactionId,
+    CandidateTransactionId,
+    Score,
+    CreatedAt,
+}
+
+impl Iden for ConnectorDuplicateCandidateIden {
+    fn unquoted(&self) -> &str {
+        match self {
+            Self::Table => "connector_duplicate_candidate",
+            Self::Id => "id",
+            Self::TransactionId => "transaction_id",
+            Self::CandidateTransactionId => "candidate_transaction_id",
+            Self::Score => "score",
+            Self::CreatedAt => "created_at",
+        }
+    }
+}
+
+#[allow(dead_code)]
+pub enum ConnectorBalanceSnapshotIden {
+    Table,
+    Id,
+    ProviderAccountId,
+    FetchedAt,
+    Kind,
+    AssetIdentifier,
+    AssetId,
+    Quantity,
+}
+
+impl Iden for ConnectorBalanceSnapshotIden {
+    fn unquoted(&self) -> &str {
+        match self {
+            Self::Table => "connector_balance_snapshot",
+            Self::Id => "id",
+            Self::ProviderAccountId => "provider_account_id",
+            Self::FetchedAt => "fetched_at",
+            Self::Kind => "kind",
+            Self::AssetIdentifier => "asset_identifier",
+            Self::AssetId => "asset_id",
+            Self::Quantity => "quantity",
+        }
+    }
+}
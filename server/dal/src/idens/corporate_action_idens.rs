@@ -0,0 +1,34 @@
+use sea_query::Iden;
+
+#[allow(dead_code)]
+pub enum CorporateActionIden {
+    Table,
+    Id,
+    UserId,
+    AssetId,
+    ActionType,
+    EffectiveDate,
+    RatioFrom,
+    RatioTo,
+    NewAssetId,
+    CostBasisFraction,
+    CreatedAt,
+}
+
+impl Iden for CorporateActionIden {
+    fn unquoted(&self) -> &str {
+        match self {
+            Self::Table => "corporate_action",
+            Self::Id => "id",
+            Self::UserId => "user_id",
+            Self::AssetId => "asset_id",
+            Self::ActionType => "action_type",
+            Self::EffectiveDate => "effective_date",
+            Self::RatioFrom => "ratio_from",
+            Self::RatioTo => "ratio_to",
+            Self::NewAssetId => "new_asset_id",
+            Self::CostBasisFraction => "cost_basis_fraction",
+            Self::CreatedAt => "created_at",
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use sea_query::Iden;
+
+#[allow(dead_code)]
+pub enum InsightDigestIden {
+    Table,
+    Id,
+    UserId,
+    Period,
+    PeriodStart,
+    PeriodEnd,
+    ReferenceAssetId,
+    Content,
+    Narrative,
+    Model,
+    ReadAt,
+    CreatedAt,
+}
+
+impl Iden for InsightDigestIden {
+    fn unquoted(&self) -> &str {
+        match self {
+            Self::Table => "insight_digest",
+            Self::Id => "id",
+            Self::UserId => "user_id",
+            Self::Period => "period",
+            Self::PeriodStart => "period_start",
+            Self::PeriodEnd => "period_end",
+            Self::ReferenceAssetId => "reference_asset_id",
+            Self::Content => "content",
+            Self::Narrative => "narrative",
+            Self::Model => "model",
+            Self::ReadAt => "read_at",
+            Self::CreatedAt => "created_at",
+        }
+    }
+}
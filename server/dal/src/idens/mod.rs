@@ -4,11 +4,22 @@ use time::{Duration, OffsetDateTime};
 pub mod account_idens;
 pub mod account_identifier_idens;
 pub mod ai_conversation_idens;
+pub mod ai_reembed_idens;
 pub mod asset_idens;
+pub mod budget_idens;
 pub mod connector_idens;
+pub mod corporate_action_idens;
 pub mod entries_idens;
 pub(crate) mod file_idens;
+pub mod insight_digest_idens;
 pub mod rate_limit_idens;
+pub mod reconciliation_idens;
+pub mod spending_alert_idens;
+pub mod recurring_transaction_idens;
+pub mod statement_csv_mapping_idens;
+pub mod tag_idens;
+pub mod ticker_alias_idens;
+pub mod transaction_rule_idens;
 pub(crate) mod transaction_idens;
 pub(crate) mod user_idens;
 
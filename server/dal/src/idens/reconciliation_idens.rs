@@ -0,0 +1,37 @@
+use sea_query::Iden;
+
+#[allow(dead_code)]
+pub enum AccountReconciliationIden {
+    Table,
+    Id,
+    UserId,
+    AccountId,
+    AssetId,
+    StatementDate,
+    StatementBalance,
+    Source,
+    BindingId,
+    CreatedAt,
+}
+
+impl Iden for AccountReconciliationIden {
+    fn unquoted(&self) -> &str {
+        match self {
+            Self::Table => "account_reconciliation",
+            Self::Id => "id",
+            Self::UserId => "user_id",
+            Self::AccountId => "account_id",
+            Self::AssetId => "asset_id",
+            Self::StatementDate => "statement_date",
+            Self::StatementBalance => "statement_balance",
+            Self::Source => "source",
+            Self::BindingId => "binding_id",
+            Self::CreatedAt => "created_at",
+        }
+    }
+}
+
+#[derive(Iden)]
+pub enum ReconciliationMovementIden {
+    Quantity,
+}
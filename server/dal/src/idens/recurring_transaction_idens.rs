@@ -0,0 +1,42 @@
+use sea_query::Iden;
+
+#[allow(dead_code)]
+pub enum RecurringTransactionIden {
+    Table,
+    Id,
+    UserId,
+    AccountId,
+    Name,
+    Template,
+    Rrule,
+    StartDate,
+    EndDate,
+    NextOccurrence,
+    LastOccurrence,
+    CreateAsGhost,
+    Active,
+    CreatedAt,
+    UpdatedAt,
+}
+
+impl Iden for RecurringTransactionIden {
+    fn unquoted(&self) -> &str {
+        match self {
+            Self::Table => "recurring_transaction",
+            Self::Id => "id",
+            Self::UserId => "user_id",
+            Self::AccountId => "account_id",
+            Self::Name => "name",
+            Self::Template => "template",
+            Self::Rrule => "rrule",
+            Self::StartDate => "start_date",
+            Self::EndDate => "end_date",
+            Self::NextOccurrence => "next_occurrence",
+            Self::LastOccurrence => "last_occurrence",
+            Self::CreateAsGhost => "create_as_ghost",
+            Self::Active => "active",
+            Self::CreatedAt => "created_at",
+            Self::UpdatedAt => "updated_at",
+        }
+    }
+}
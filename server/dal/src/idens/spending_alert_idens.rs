@@ -0,0 +1,40 @@
+use sea_query::Iden;
+
+#[allow(dead_code)]
+pub enum SpendingAlertIden {
+    Table,
+    Id,
+    UserId,
+    TransactionId,
+    Kind,
+    AccountId,
+    AssetId,
+    Amount,
+    TypicalAmount,
+    RelatedTransactionId,
+    Description,
+    DateTransacted,
+    DismissedAt,
+    CreatedAt,
+}
+
+impl Iden for SpendingAlertIden {
+    fn unquoted(&self) -> &str {
+        match self {
+            Self::Table => "spending_alert",
+            Self::Id => "id",
+            Self::UserId => "user_id",
+            Self::TransactionId => "transaction_id",
+            Self::Kind => "kind",
+            Self::AccountId => "account_id",
+            Self::AssetId => "asset_id",
+            Self::Amount => "amount",
+            Self::TypicalAmount => "typical_amount",
+            Self::RelatedTransactionId => "related_transaction_id",
+            Self::Description => "description",
+            Self::DateTransacted => "date_transacted",
+            Self::DismissedAt => "dismissed_at",
+            Self::CreatedAt => "created_at",
+        }
+    }
+}
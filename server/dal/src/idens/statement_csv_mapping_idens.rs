@@ -0,0 +1,24 @@
+use sea_query::Iden;
+
+#[allow(dead_code)]
+pub enum StatementCsvMappingIden {
+    Table,
+    AccountId,
+    UserId,
+    Mapping,
+    CreatedAt,
+    UpdatedAt,
+}
+
+impl Iden for StatementCsvMappingIden {
+    fn unquoted(&self) -> &str {
+        match self {
+            Self::Table => "statement_csv_mapping",
+            Self::AccountId => "account_id",
+            Self::UserId => "user_id",
+            Self::Mapping => "mapping",
+            Self::CreatedAt => "created_at",
+            Self::UpdatedAt => "updated_at",
+        }
+    }
+}
@@ -0,0 +1,54 @@
+use sea_query::Iden;
+
+#[allow(dead_code)]
+pub enum TagIden {
+    Table,
+    Id,
+    UserId,
+    Name,
+    CreatedAt,
+}
+
+impl Iden for TagIden {
+    fn unquoted(&self) -> &str {
+        match self {
+            Self::Table => "tag",
+            Self::Id => "id",
+            Self::UserId => "user_id",
+            Self::Name => "name",
+            Self::CreatedAt => "created_at",
+        }
+    }
+}
+
+pub enum TransactionTagIden {
+    Table,
+    TransactionId,
+    TagId,
+}
+
+impl Iden for TransactionTagIden {
+    fn unquoted(&self) -> &str {
+        match self {
+            Self::Table => "transaction_tag",
+            Self::TransactionId => "transaction_id",
+            Self::TagId => "tag_id",
+        }
+    }
+}
+
+pub enum TransactionGroupTagIden {
+    Table,
+    GroupId,
+    TagId,
+}
+
+impl Iden for TransactionGroupTagIden {
+    fn unquoted(&self) -> &str {
+        match self {
+            Self::Table => "transaction_group_tag",
+            Self::GroupId => "group_id",
+            Self::TagId => "tag_id",
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use sea_query::Iden;
+
+#[allow(dead_code)]
+pub enum UserTickerAliasIden {
+    Table,
+    Id,
+    UserId,
+    ProviderTicker,
+    AssetId,
+    CreatedAt,
+}
+
+impl Iden for UserTickerAliasIden {
+    fn unquoted(&self) -> &str {
+        match self {
+            Self::Table => "user_ticker_alias",
+            Self::Id => "id",
+            Self::UserId => "user_id",
+            Self::ProviderTicker => "provider_ticker",
+            Self::AssetId => "asset_id",
+            Self::CreatedAt => "created_at",
+        }
+    }
+}
@@ -19,6 +19,8 @@ pub enum TransactionCategoriesIden {
     CategoryType,
     UserId,
     Embedding,
+    EmbeddingModel,
+    EmbeddingDims,
 }
 
 #[allow(dead_code)]
@@ -35,6 +37,8 @@ pub enum TransactionDescriptionsIden {
     TransactionId,
     Description,
     Embedding,
+    EmbeddingModel,
+    EmbeddingDims,
 }
 
 #[allow(dead_code)]
@@ -53,6 +57,20 @@ pub enum TransactionGroupIden {
     Description,
     DateAdded,
     DescriptionEmbedding,
+    DescriptionEmbeddingModel,
+    DescriptionEmbeddingDims,
+}
+
+#[allow(dead_code)]
+pub enum TransactionCategorySuggestionIden {
+    Table,
+    TransactionId,
+    UserId,
+    CategoryId,
+    Confidence,
+    Reason,
+    Model,
+    CreatedAt,
 }
 
 #[allow(dead_code)]
@@ -96,6 +114,8 @@ impl Iden for TransactionCategoriesIden {
             Self::CategoryType => "category_type",
             Self::UserId => "user_id",
             Self::Embedding => "embedding",
+            Self::EmbeddingModel => "embedding_model",
+            Self::EmbeddingDims => "embedding_dims",
         }
     }
 }
@@ -118,6 +138,23 @@ impl Iden for TransactionDescriptionsIden {
             Self::TransactionId => "transaction_id",
             Self::Description => "description",
             Self::Embedding => "embedding",
+            Self::EmbeddingModel => "embedding_model",
+            Self::EmbeddingDims => "embedding_dims",
+        }
+    }
+}
+
+impl Iden for TransactionCategorySuggestionIden {
+    fn unquoted(&self) -> &str {
+        match self {
+            Self::Table => "transaction_category_suggestion",
+            Self::TransactionId => "transaction_id",
+            Self::UserId => "user_id",
+            Self::CategoryId => "category_id",
+            Self::Confidence => "confidence",
+            Self::Reason => "reason",
+            Self::Model => "model",
+            Self::CreatedAt => "created_at",
         }
     }
 }
@@ -142,6 +179,8 @@ impl Iden for TransactionGroupIden {
             Self::Description => "description",
             Self::DateAdded => "date_added",
             Self::DescriptionEmbedding => "description_embedding",
+            Self::DescriptionEmbeddingModel => "description_embedding_model",
+            Self::DescriptionEmbeddingDims => "description_embedding_dims",
         }
     }
 }
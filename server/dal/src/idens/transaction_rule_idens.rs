@@ -0,0 +1,62 @@
+use sea_query::Iden;
+
+#[allow(dead_code)]
+pub enum TransactionRuleIden {
+    Table,
+    Id,
+    UserId,
+    Name,
+    Priority,
+    Enabled,
+    DescriptionContains,
+    DescriptionRegex,
+    AmountMin,
+    AmountMax,
+    AccountId,
+    Counterparty,
+    CategoryId,
+    DescriptionRewrite,
+    Visibility,
+    CreatedAt,
+    UpdatedAt,
+}
+
+impl Iden for TransactionRuleIden {
+    fn unquoted(&self) -> &str {
+        match self {
+            Self::Table => "transaction_rule",
+            Self::Id => "id",
+            Self::UserId => "user_id",
+            Self::Name => "name",
+            Self::Priority => "priority",
+            Self::Enabled => "enabled",
+            Self::DescriptionContains => "description_contains",
+            Self::DescriptionRegex => "description_regex",
+            Self::AmountMin => "amount_min",
+            Self::AmountMax => "amount_max",
+            Self::AccountId => "account_id",
+            Self::Counterparty => "counterparty",
+            Self::CategoryId => "category_id",
+            Self::DescriptionRewrite => "description_rewrite",
+            Self::Visibility => "visibility",
+            Self::CreatedAt => "created_at",
+            Self::UpdatedAt => "updated_at",
+        }
+    }
+}
+
+pub enum TransactionRuleTagIden {
+    Table,
+    RuleId,
+    TagId,
+}
+
+impl Iden for TransactionRuleTagIden {
+    fn unquoted(&self) -> &str {
+        match self {
+            Self::Table => "transaction_rule_tag",
+            Self::RuleId => "rule_id",
+            Self::TagId => "tag_id",
+        }
+    }
+}
@@ -1,8 +1,10 @@
+use async_trait::async_trait;
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use time::format_description::well_known::Rfc3339;
 use time::OffsetDateTime;
 
+use crate::market_data_provider::MarketDataProvider;
 use crate::models::asset_models::{asset_type_ids, HeldAssetPairDetailModel};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
@@ -113,7 +115,11 @@ impl MarketDataClient {
         }
         req
     }
+}
 
+/// The remote rates service configured through `MARKET_DATA_URL`.
+#[async_trait]
+impl MarketDataProvider for MarketDataClient {
     #[tracing::instrument(
         level = "debug",
         skip_all,
@@ -124,10 +130,7 @@ impl MarketDataClient {
             pairs = %pairs_label(pairs)
         )
     )]
-    pub async fn get_latest(
-        &self,
-        pairs: &[PairRequest],
-    ) -> Result<Vec<LatestRateEntry>, Box<dyn std::error::Error + Send + Sync>> {
+    async fn get_latest(&self, pairs: &[PairRequest]) -> anyhow::Result<Vec<LatestRateEntry>> {
         let url = format!("{}/rates/latest", self.base_url);
         tracing::Span::current().record("url.full", url.as_str());
         Ok(self
@@ -149,11 +152,11 @@ impl MarketDataClient {
             pairs = %pairs_label(pairs)
         )
     )]
-    pub async fn get_history(
+    async fn get_history(
         &self,
         pairs: &[PairRequest],
         from: Option<OffsetDateTime>,
-    ) -> Result<Vec<HistoryEntry>, Box<dyn std::error::Error + Send + Sync>> {
+    ) -> anyhow::Result<Vec<HistoryEntry>> {
         let url = format!("{}/rates/history", self.base_url);
         tracing::Span::current().record("url.full", url.as_str());
         let body = RatesRequestBody {
@@ -0,0 +1,356 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use time::format_description::well_known::Rfc3339;
+use time::macros::format_description;
+use time::{Date, OffsetDateTime};
+
+use crate::market_data_client::{
+    AssetClass, HistoryEntry, HistoryRateEntry, LatestRateEntry, PairRequest,
+};
+
+/// Source of exchange rates and prices for asset pairs. Pairs a provider has no data
+/// for are left out of the result rather than failing the whole request.
+#[async_trait]
+pub trait MarketDataProvider: Send + Sync {
+    async fn get_latest(&self, pairs: &[PairRequest]) -> Result<Vec<LatestRateEntry>>;
+
+    async fn get_history(
+        &self,
+        pairs: &[PairRequest],
+        from: Option<OffsetDateTime>,
+    ) -> Result<Vec<HistoryEntry>>;
+}
+
+pub mod ecb;
+pub mod price_files;
+pub mod stub;
+
+pub use ecb::EcbMarketDataProvider;
+pub use price_files::PriceFileMarketDataProvider;
+pub use stub::StubMarketDataProvider;
+
+/// Dispatches each pair to the provider configured for its asset class, falling back
+/// to the default provider for classes without an override.
+pub struct RoutingMarketDataProvider {
+    default: Arc<dyn MarketDataProvider>,
+    overrides: Vec<(AssetClass, Arc<dyn MarketDataProvider>)>,
+}
+
+impl RoutingMarketDataProvider {
+    pub fn new(default: Arc<dyn MarketDataProvider>) -> Self {
+        Self {
+            default,
+            overrides: Vec::new(),
+        }
+    }
+
+    pub fn with_class(mut self, class: AssetClass, provider: Arc<dyn MarketDataProvider>) -> Self {
+        self.overrides.retain(|(c, _)| *c != class);
+        self.overrides.push((class, provider));
+        self
+    }
+
+    fn provider_for(&self, pair: &PairRequest) -> &Arc<dyn MarketDataProvider> {
+        let class = routing_class(pair);
+        self.overrides
+            .iter()
+            .find(|(c, _)| *c == class)
+            .map(|(_, provider)| provider)
+            .unwrap_or(&self.default)
+    }
+
+    /// Groups pairs by the provider serving them, so a provider shared by several
+    /// classes is still called once.
+    fn split(
+        &self,
+        pairs: &[PairRequest],
+    ) -> Vec<(&Arc<dyn MarketDataProvider>, Vec<PairRequest>)> {
+        let mut groups: Vec<(&Arc<dyn MarketDataProvider>, Vec<PairRequest>)> = Vec::new();
+        for pair in pairs {
+            let provider = self.provider_for(pair);
+            match groups.iter_mut().find(|(p, _)| Arc::ptr_eq(p, provider)) {
+                Some((_, group)) => group.push(pair.clone()),
+                None => groups.push((provider, vec![pair.clone()])),
+            }
+        }
+        groups
+    }
+}
+
+#[async_trait]
+impl MarketDataProvider for RoutingMarketDataProvider {
+    async fn get_latest(&self, pairs: &[PairRequest]) -> Result<Vec<LatestRateEntry>> {
+        let mut results = Vec::new();
+        for (provider, group) in self.split(pairs) {
+            results.push(provider.get_latest(&group).await);
+        }
+        merge_routed(results)
+    }
+
+    async fn get_history(
+        &self,
+        pairs: &[PairRequest],
+        from: Option<OffsetDateTime>,
+    ) -> Result<Vec<HistoryEntry>> {
+        let mut results = Vec::new();
+        for (provider, group) in self.split(pairs) {
+            results.push(provider.get_history(&group, from).await);
+        }
+        merge_routed(results)
+    }
+}
+
+/// A pair is routed by the asset being priced, except that a currency priced in
+/// something else (e.g. EUR/BTC) follows the quote asset.
+fn routing_class(pair: &PairRequest) -> AssetClass {
+    match pair.base_type {
+        AssetClass::Currency => pair.quote_type,
+        class => class,
+    }
+}
+
+/// One failing source does not hide the results of the others; the call only fails
+/// when every routed provider failed.
+fn merge_routed<T>(results: Vec<Result<Vec<T>>>) -> Result<Vec<T>> {
+    let mut merged = Vec::new();
+    let mut last_error = None;
+    let mut any_ok = false;
+
+    for result in results {
+        match result {
+            Ok(entries) => {
+                any_ok = true;
+                merged.extend(entries);
+            }
+            Err(e) => {
+                tracing::warn!(
+                    error = ?e,
+                    error.type = "market_data_provider",
+                    "market data provider failed, skipping its pairs"
+                );
+                last_error = Some(e);
+            }
+        }
+    }
+
+    match last_error {
+        Some(e) if !any_ok => Err(e),
+        _ => Ok(merged),
+    }
+}
+
+/// Accepts RFC 3339 timestamps and plain `YYYY-MM-DD` dates (taken as midnight UTC).
+pub(crate) fn parse_rate_timestamp(value: &str) -> Option<OffsetDateTime> {
+    let value = value.trim();
+    OffsetDateTime::parse(value, &Rfc3339)
+        .ok()
+        .or_else(|| parse_iso_date(value).map(|d| d.midnight().assume_utc()))
+}
+
+pub(crate) fn parse_iso_date(value: &str) -> Option<Date> {
+    Date::parse(value.trim(), format_description!("[year]-[month]-[day]")).ok()
+}
+
+/// Builds the entries of a local source from its rate series. `series` maps a pair
+/// (base, quote) to its rates ordered by time.
+pub(crate) fn latest_from_series(
+    pairs: &[PairRequest],
+    series: impl Fn(&PairRequest) -> Option<BTreeMap<OffsetDateTime, Decimal>>,
+) -> Vec<LatestRateEntry> {
+    pairs
+        .iter()
+        .filter_map(|pair| {
+            let (_, rate) = series(pair)?.pop_last()?;
+            Some(LatestRateEntry {
+                base: pair.base.clone(),
+                quote: pair.quote.clone(),
+                rate,
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn history_from_series(
+    pairs: &[PairRequest],
+    from: Option<OffsetDateTime>,
+    series: impl Fn(&PairRequest) -> Option<BTreeMap<OffsetDateTime, Decimal>>,
+) -> Vec<HistoryEntry> {
+    pairs
+        .iter()
+        .filter_map(|pair| {
+            let rates: Vec<HistoryRateEntry> = series(pair)?
+                .into_iter()
+                .filter(|(recorded_at, _)| from.is_none_or(|from| *recorded_at >= from))
+                .map(|(recorded_at, rate)| HistoryRateEntry { rate, recorded_at })
+                .collect();
+            Some(HistoryEntry {
+                base: pair.base.clone(),
+                quote: pair.quote.clone(),
+                rates,
+            })
+        })
+        .collect()
+}
+
+pub(crate) fn read_error(path: &std::path::Path, e: std::io::Error) -> anyhow::Error {
+    anyhow!("failed to read {}: {}", path.display(), e)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Mutex;
+
+    use super::*;
+
+    fn pair(base: &str, base_type: AssetClass, quote: &str, quote_type: AssetClass) -> PairRequest {
+        PairRequest {
+            base: base.into(),
+            base_type,
+            quote: quote.into(),
+            quote_type,
+        }
+    }
+
+    /// Records the pairs it was asked for and answers with a fixed rate.
+    struct Recording {
+        rate: Decimal,
+        fail: bool,
+        calls: Mutex<Vec<Vec<String>>>,
+    }
+
+    impl Recording {
+        fn new(rate: Decimal) -> Arc<Self> {
+            Arc::new(Self {
+                rate,
+                fail: false,
+                calls: Mutex::new(Vec::new()),
+            })
+        }
+
+        fn failing() -> Arc<Self> {
+            Arc::new(Self {
+                rate: Decimal::ZERO,
+                fail: true,
+                calls: Mutex::new(Vec::new()),
+            })
+        }
+
+        fn calls(&self) -> Vec<Vec<String>> {
+            self.calls.lock().unwrap().clone()
+        }
+    }
+
+    #[async_trait]
+    impl MarketDataProvider for Recording {
+        async fn get_latest(&self, pairs: &[PairRequest]) -> Result<Vec<LatestRateEntry>> {
+            self.calls
+                .lock()
+                .unwrap()
+                .push(pairs.iter().map(|p| p.base.clone()).collect());
+            if self.fail {
+                return Err(anyhow!("source unavailable"));
+            }
+            Ok(pairs
+                .iter()
+                .map(|p| LatestRateEntry {
+                    base: p.base.clone(),
+                    quote: p.quote.clone(),
+                    rate: self.rate,
+                })
+                .collect())
+        }
+
+        async fn get_history(
+            &self,
+            _pairs: &[PairRequest],
+            _from: Option<OffsetDateTime>,
+        ) -> Result<Vec<HistoryEntry>> {
+            Ok(Vec::new())
+        }
+    }
+
+    #[test]
+    fn routes_currency_priced_in_other_class_by_quote() {
+        let eur_usd = pair("EUR", AssetClass::Currency, "USD", AssetClass::Currency);
+        let eur_btc = pair("EUR", AssetClass::Currency, "BTC", AssetClass::Crypto);
+        let btc_eur = pair("BTC", AssetClass::Crypto, "EUR", AssetClass::Currency);
+        let aapl_usd = pair("AAPL", AssetClass::Other, "USD", AssetClass::Currency);
+
+        assert_eq!(routing_class(&eur_usd), AssetClass::Currency);
+        assert_eq!(routing_class(&eur_btc), AssetClass::Crypto);
+        assert_eq!(routing_class(&btc_eur), AssetClass::Crypto);
+        assert_eq!(routing_class(&aapl_usd), AssetClass::Other);
+    }
+
+    #[tokio::test]
+    async fn sends_each_class_to_its_provider_once() {
+        let default = Recording::new(Decimal::from(1));
+        let fx = Recording::new(Decimal::from(2));
+        let router = RoutingMarketDataProvider::new(default.clone())
+            .with_class(AssetClass::Currency, fx.clone());
+
+        let pairs = [
+            pair("EUR", AssetClass::Currency, "USD", AssetClass::Currency),
+            pair("BTC", AssetClass::Crypto, "EUR", AssetClass::Currency),
+            pair("GBP", AssetClass::Currency, "USD", AssetClass::Currency),
+            pair("AAPL", AssetClass::Other, "USD", AssetClass::Currency),
+        ];
+        let latest = router.get_latest(&pairs).await.unwrap();
+
+        assert_eq!(latest.len(), 4);
+        assert_eq!(fx.calls(), vec![vec!["EUR".to_string(), "GBP".to_string()]]);
+        assert_eq!(
+            default.calls(),
+            vec![vec!["BTC".to_string(), "AAPL".to_string()]]
+        );
+        let btc = latest.iter().find(|e| e.base == "BTC").unwrap();
+        assert_eq!(btc.rate, Decimal::from(1));
+        let gbp = latest.iter().find(|e| e.base == "GBP").unwrap();
+        assert_eq!(gbp.rate, Decimal::from(2));
+    }
+
+    #[tokio::test]
+    async fn keeps_results_of_healthy_providers_when_one_fails() {
+        let router = RoutingMarketDataProvider::new(Recording::new(Decimal::from(1)))
+            .with_class(AssetClass::Crypto, Recording::failing());
+
+        let pairs = [
+            pair("EUR", AssetClass::Currency, "USD", AssetClass::Currency),
+            pair("BTC", AssetClass::Crypto, "EUR", AssetClass::Currency),
+        ];
+        let latest = router.get_latest(&pairs).await.unwrap();
+
+        assert_eq!(latest.len(), 1);
+        assert_eq!(latest[0].base, "EUR");
+    }
+
+    #[tokio::test]
+    async fn fails_when_every_provider_fails() {
+        let router = RoutingMarketDataProvider::new(Recording::failing());
+        let pairs = [pair(
+            "EUR",
+            AssetClass::Currency,
+            "USD",
+            AssetClass::Currency,
+        )];
+
+        assert!(router.get_latest(&pairs).await.is_err());
+    }
+
+    #[test]
+    fn parses_rfc3339_and_plain_dates() {
+        assert_eq!(
+            parse_rate_timestamp("2026-10-16T12:30:00Z").unwrap(),
+            time::macros::datetime!(2026-10-16 12:30 UTC)
+        );
+        assert_eq!(
+            parse_rate_timestamp(" 2026-10-16 ").unwrap(),
+            time::macros::datetime!(2026-10-16 0:00 UTC)
+        );
+        assert!(parse_rate_timestamp("16/10/2026").is_none());
+    }
+}
@@ -0,0 +1,236 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use time::macros::format_description;
+use time::{Date, OffsetDateTime};
+
+use crate::market_data_client::{AssetClass, HistoryEntry, LatestRateEntry, PairRequest};
+use crate::market_data_provider::{
+    history_from_series, latest_from_series, parse_iso_date, read_error, MarketDataProvider,
+};
+
+/// Reads the ECB euro foreign exchange reference rates from a directory holding the
+/// published CSV files (`eurofxref.csv`, `eurofxref-hist.csv`, ...). Only pairs of two
+/// currencies are served; cross rates are derived through EUR.
+pub struct EcbMarketDataProvider {
+    dir: PathBuf,
+}
+
+/// Units of each currency per 1 EUR, by reference date.
+type EcbRates = BTreeMap<Date, HashMap<String, Decimal>>;
+
+impl EcbMarketDataProvider {
+    pub fn new() -> Result<Self> {
+        let dir =
+            std::env::var("ECB_RATES_DIR").map_err(|_| anyhow!("ECB_RATES_DIR must be set"))?;
+        Ok(Self::with_dir(dir))
+    }
+
+    pub fn with_dir(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    async fn load(&self) -> Result<EcbRates> {
+        let mut entries = tokio::fs::read_dir(&self.dir)
+            .await
+            .map_err(|e| read_error(&self.dir, e))?;
+        let mut rates = EcbRates::new();
+
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .map_err(|e| read_error(&self.dir, e))?
+        {
+            let path = entry.path();
+            if !path
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("csv"))
+            {
+                continue;
+            }
+            let content = tokio::fs::read_to_string(&path)
+                .await
+                .map_err(|e| read_error(&path, e))?;
+            parse_ecb_csv(&content, &mut rates)
+                .map_err(|e| anyhow!("invalid ECB file {}: {}", path.display(), e))?;
+        }
+
+        Ok(rates)
+    }
+}
+
+#[async_trait]
+impl MarketDataProvider for EcbMarketDataProvider {
+    #[tracing::instrument(level = "debug", skip_all, fields(dir = %self.dir.display()))]
+    async fn get_latest(&self, pairs: &[PairRequest]) -> Result<Vec<LatestRateEntry>> {
+        let rates = self.load().await?;
+        Ok(latest_from_series(pairs, |pair| cross_series(&rates, pair)))
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(dir = %self.dir.display()))]
+    async fn get_history(
+        &self,
+        pairs: &[PairRequest],
+        from: Option<OffsetDateTime>,
+    ) -> Result<Vec<HistoryEntry>> {
+        let rates = self.load().await?;
+        Ok(history_from_series(pairs, from, |pair| {
+            cross_series(&rates, pair)
+        }))
+    }
+}
+
+/// Rates of `pair` on every reference date quoting both of its currencies.
+fn cross_series(rates: &EcbRates, pair: &PairRequest) -> Option<BTreeMap<OffsetDateTime, Decimal>> {
+    if pair.base_type != AssetClass::Currency || pair.quote_type != AssetClass::Currency {
+        return None;
+    }
+
+    let series: BTreeMap<OffsetDateTime, Decimal> = rates
+        .iter()
+        .filter_map(|(date, day)| {
+            let base = per_euro(day, &pair.base)?;
+            let quote = per_euro(day, &pair.quote)?;
+            let rate = quote.checked_div(base)?.round_dp(10).normalize();
+            Some((date.midnight().assume_utc(), rate))
+        })
+        .collect();
+
+    (!series.is_empty()).then_some(series)
+}
+
+fn per_euro(day: &HashMap<String, Decimal>, currency: &str) -> Option<Decimal> {
+    if currency.eq_ignore_ascii_case("EUR") {
+        return Some(Decimal::ONE);
+    }
+    day.get(&currency.to_ascii_uppercase()).copied()
+}
+
+/// Parses one ECB reference rate file. The header is `Date` followed by currency codes;
+/// each row is a date followed by the rates, with `N/A` for currencies not quoted that
+/// day. Both the daily (`17 October 2026`) and historical (`2026-10-17`) date styles
+/// are accepted.
+fn parse_ecb_csv(content: &str, rates: &mut EcbRates) -> Result<()> {
+    let mut lines = content.lines().filter(|line| !line.trim().is_empty());
+    let header = lines.next().ok_or_else(|| anyhow!("file is empty"))?;
+    let currencies: Vec<String> = header
+        .split(',')
+        .map(|column| column.trim().to_ascii_uppercase())
+        .collect();
+    if !currencies
+        .first()
+        .is_some_and(|first| first.eq_ignore_ascii_case("date"))
+    {
+        return Err(anyhow!("first column must be Date"));
+    }
+
+    for line in lines {
+        let mut cells = line.split(',').map(str::trim);
+        let raw_date = cells.next().unwrap_or_default();
+        let date =
+            parse_ecb_date(raw_date).ok_or_else(|| anyhow!("unparseable date '{raw_date}'"))?;
+        let day = rates.entry(date).or_default();
+
+        for (currency, cell) in currencies.iter().skip(1).zip(cells) {
+            if currency.is_empty() || cell.is_empty() || cell.eq_ignore_ascii_case("N/A") {
+                continue;
+            }
+            let rate: Decimal = cell
+                .parse()
+                .map_err(|_| anyhow!("invalid {currency} rate '{cell}' on {raw_date}"))?;
+            if !rate.is_zero() {
+                day.insert(currency.clone(), rate);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn parse_ecb_date(value: &str) -> Option<Date> {
+    parse_iso_date(value).or_else(|| {
+        Date::parse(
+            value,
+            format_description!("[day padding:none] [month repr:long] [year]"),
+        )
+        .ok()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use time::macros::date;
+
+    use super::*;
+
+    fn currency_pair(base: &str, quote: &str) -> PairRequest {
+        PairRequest {
+            base: base.into(),
+            base_type: AssetClass::Currency,
+            quote: quote.into(),
+            quote_type: AssetClass::Currency,
+        }
+    }
+
+    fn dec(value: &str) -> Decimal {
+        Decimal::from_str(value).unwrap()
+    }
+
+    const DAILY: &str = "Date, USD, JPY, GBP, \n16 October 2026, 1.0800, 161.20, 0.8400, \n";
+    const HISTORY: &str =
+        "Date,USD,JPY,GBP,\n2026-10-15,1.0750,N/A,0.8450,\n2026-10-14,1.0700,160.00,0.8400,\n";
+
+    #[test]
+    fn parses_daily_and_historical_files() {
+        let mut rates = EcbRates::new();
+        parse_ecb_csv(DAILY, &mut rates).unwrap();
+        parse_ecb_csv(HISTORY, &mut rates).unwrap();
+
+        assert_eq!(rates.len(), 3);
+        assert_eq!(rates[&date!(2026 - 10 - 16)]["USD"], dec("1.0800"));
+        assert!(!rates[&date!(2026 - 10 - 15)].contains_key("JPY"));
+        assert_eq!(rates[&date!(2026 - 10 - 14)]["JPY"], dec("160.00"));
+    }
+
+    #[test]
+    fn rejects_files_without_date_column() {
+        let mut rates = EcbRates::new();
+        assert!(parse_ecb_csv("USD,JPY\n1.0,2.0\n", &mut rates).is_err());
+    }
+
+    #[test]
+    fn derives_cross_rates_through_euro() {
+        let mut rates = EcbRates::new();
+        parse_ecb_csv(HISTORY, &mut rates).unwrap();
+
+        let usd_eur = cross_series(&rates, &currency_pair("USD", "EUR")).unwrap();
+        let gbp_usd = cross_series(&rates, &currency_pair("GBP", "USD")).unwrap();
+        let eur_jpy = cross_series(&rates, &currency_pair("EUR", "JPY")).unwrap();
+
+        let oct_14 = date!(2026 - 10 - 14).midnight().assume_utc();
+        assert_eq!(usd_eur[&oct_14], dec("0.9345794393"));
+        assert_eq!(gbp_usd[&oct_14], dec("1.2738095238"));
+        // JPY is missing on the 15th, so only the 14th is available.
+        assert_eq!(eur_jpy.len(), 1);
+        assert_eq!(eur_jpy[&oct_14], dec("160"));
+    }
+
+    #[test]
+    fn ignores_non_currency_pairs() {
+        let mut rates = EcbRates::new();
+        parse_ecb_csv(HISTORY, &mut rates).unwrap();
+        let pair = PairRequest {
+            base: "BTC".into(),
+            base_type: AssetClass::Crypto,
+            quote: "USD".into(),
+            quote_type: AssetClass::Currency,
+        };
+
+        assert!(cross_series(&rates, &pair).is_none());
+    }
+}
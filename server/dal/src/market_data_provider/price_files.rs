@@ -0,0 +1,249 @@
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use time::OffsetDateTime;
+
+use crate::market_data_client::{HistoryEntry, LatestRateEntry, PairRequest};
+use crate::market_data_provider::{
+    history_from_series, latest_from_series, parse_rate_timestamp, read_error, MarketDataProvider,
+};
+
+/// Serves prices dropped as files into a directory, for assets no online source
+/// covers. Each `.csv` file has a `base,quote,timestamp,rate` header (in any column
+/// order, `date` is accepted for `timestamp`); each `.json` file holds an array of
+/// objects with the same fields. Timestamps are RFC 3339 or `YYYY-MM-DD`.
+pub struct PriceFileMarketDataProvider {
+    dir: PathBuf,
+}
+
+/// Rates by (base, quote) ticker, ordered by time. Tickers are upper-cased.
+type PriceSeries = HashMap<(String, String), BTreeMap<OffsetDateTime, Decimal>>;
+
+#[derive(Deserialize)]
+struct PriceFileRecord {
+    base: String,
+    quote: String,
+    #[serde(alias = "date")]
+    timestamp: String,
+    rate: Decimal,
+}
+
+impl PriceFileMarketDataProvider {
+    pub fn new() -> Result<Self> {
+        let dir =
+            std::env::var("PRICE_FILES_DIR").map_err(|_| anyhow!("PRICE_FILES_DIR must be set"))?;
+        Ok(Self::with_dir(dir))
+    }
+
+    pub fn with_dir(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    async fn load(&self) -> Result<PriceSeries> {
+        let mut entries = tokio::fs::read_dir(&self.dir)
+            .await
+            .map_err(|e| read_error(&self.dir, e))?;
+        let mut series = PriceSeries::new();
+
+        while let Some(entry) = entries
+            .next_entry()
+            .await
+            .map_err(|e| read_error(&self.dir, e))?
+        {
+            let path = entry.path();
+            let extension = path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .map(str::to_ascii_lowercase);
+            let parse: fn(&str) -> Result<Vec<PriceFileRecord>> = match extension.as_deref() {
+                Some("csv") => parse_price_csv,
+                Some("json") => parse_price_json,
+                _ => continue,
+            };
+
+            let content = tokio::fs::read_to_string(&path)
+                .await
+                .map_err(|e| read_error(&path, e))?;
+            let records = parse(&content)
+                .map_err(|e| anyhow!("invalid price file {}: {}", path.display(), e))?;
+            add_records(&mut series, records);
+        }
+
+        Ok(series)
+    }
+}
+
+#[async_trait]
+impl MarketDataProvider for PriceFileMarketDataProvider {
+    #[tracing::instrument(level = "debug", skip_all, fields(dir = %self.dir.display()))]
+    async fn get_latest(&self, pairs: &[PairRequest]) -> Result<Vec<LatestRateEntry>> {
+        let series = self.load().await?;
+        Ok(latest_from_series(pairs, |pair| pair_series(&series, pair)))
+    }
+
+    #[tracing::instrument(level = "debug", skip_all, fields(dir = %self.dir.display()))]
+    async fn get_history(
+        &self,
+        pairs: &[PairRequest],
+        from: Option<OffsetDateTime>,
+    ) -> Result<Vec<HistoryEntry>> {
+        let series = self.load().await?;
+        Ok(history_from_series(pairs, from, |pair| {
+            pair_series(&series, pair)
+        }))
+    }
+}
+
+fn pair_series(
+    series: &PriceSeries,
+    pair: &PairRequest,
+) -> Option<BTreeMap<OffsetDateTime, Decimal>> {
+    series
+        .get(&(
+            pair.base.to_ascii_uppercase(),
+            pair.quote.to_ascii_uppercase(),
+        ))
+        .cloned()
+}
+
+/// Later files win when two of them price the same pair at the same moment.
+fn add_records(series: &mut PriceSeries, records: Vec<PriceFileRecord>) {
+    for record in records {
+        let Some(recorded_at) = parse_rate_timestamp(&record.timestamp) else {
+            tracing::warn!(
+                timestamp = %record.timestamp,
+                "unparseable price file timestamp, skipping"
+            );
+            continue;
+        };
+        series
+            .entry((
+                record.base.trim().to_ascii_uppercase(),
+                record.quote.trim().to_ascii_uppercase(),
+            ))
+            .or_default()
+            .insert(recorded_at, record.rate);
+    }
+}
+
+fn parse_price_json(content: &str) -> Result<Vec<PriceFileRecord>> {
+    Ok(serde_json::from_str(content)?)
+}
+
+fn parse_price_csv(content: &str) -> Result<Vec<PriceFileRecord>> {
+    let mut lines = content.lines().filter(|line| !line.trim().is_empty());
+    let header: Vec<String> = lines
+        .next()
+        .ok_or_else(|| anyhow!("file is empty"))?
+        .split(',')
+        .map(|column| column.trim().to_ascii_lowercase())
+        .collect();
+    let column = |names: &[&str]| {
+        header
+            .iter()
+            .position(|c| names.contains(&c.as_str()))
+            .ok_or_else(|| anyhow!("missing '{}' column", names[0]))
+    };
+    let base = column(&["base"])?;
+    let quote = column(&["quote"])?;
+    let timestamp = column(&["timestamp", "date"])?;
+    let rate = column(&["rate"])?;
+
+    lines
+        .enumerate()
+        .map(|(index, line)| {
+            let cells: Vec<&str> = line.split(',').map(str::trim).collect();
+            let cell = |i: usize| {
+                cells
+                    .get(i)
+                    .copied()
+                    .ok_or_else(|| anyhow!("line {} has too few columns", index + 2))
+            };
+            Ok(PriceFileRecord {
+                base: cell(base)?.to_string(),
+                quote: cell(quote)?.to_string(),
+                timestamp: cell(timestamp)?.to_string(),
+                rate: cell(rate)?
+                    .parse()
+                    .map_err(|_| anyhow!("invalid rate on line {}", index + 2))?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use time::macros::datetime;
+
+    use crate::market_data_client::AssetClass;
+
+    use super::*;
+
+    fn load(csv: &str, json: &str) -> PriceSeries {
+        let mut series = PriceSeries::new();
+        add_records(&mut series, parse_price_csv(csv).unwrap());
+        add_records(&mut series, parse_price_json(json).unwrap());
+        series
+    }
+
+    fn fund_pair() -> PairRequest {
+        PairRequest {
+            base: "vwce".into(),
+            base_type: AssetClass::Other,
+            quote: "EUR".into(),
+            quote_type: AssetClass::Currency,
+        }
+    }
+
+    #[test]
+    fn reads_csv_columns_in_any_order() {
+        let records = parse_price_csv("Date,Rate,Quote,Base\n2026-10-16,120.5,EUR,VWCE\n").unwrap();
+
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].base, "VWCE");
+        assert_eq!(records[0].timestamp, "2026-10-16");
+        assert_eq!(records[0].rate, Decimal::from_str("120.5").unwrap());
+    }
+
+    #[test]
+    fn rejects_csv_without_rate_column() {
+        assert!(parse_price_csv("base,quote,date\nVWCE,EUR,2026-10-16\n").is_err());
+    }
+
+    #[test]
+    fn merges_csv_and_json_into_one_series() {
+        let series = load(
+            "base,quote,timestamp,rate\nVWCE,EUR,2026-10-15,119\n",
+            r#"[{"base":"VWCE","quote":"EUR","timestamp":"2026-10-16T16:00:00Z","rate":"121.25"}]"#,
+        );
+
+        let pair = fund_pair();
+        let latest = latest_from_series(std::slice::from_ref(&pair), |p| pair_series(&series, p));
+        assert_eq!(latest.len(), 1);
+        assert_eq!(latest[0].base, "vwce");
+        assert_eq!(latest[0].rate, Decimal::from_str("121.25").unwrap());
+
+        let history = history_from_series(
+            std::slice::from_ref(&pair),
+            Some(datetime!(2026-10-16 0:00 UTC)),
+            |p| pair_series(&series, p),
+        );
+        assert_eq!(history[0].rates.len(), 1);
+        assert_eq!(
+            history[0].rates[0].recorded_at,
+            datetime!(2026-10-16 16:00 UTC)
+        );
+    }
+
+    #[test]
+    fn skips_unknown_pairs() {
+        let series = load("base,quote,date,rate\n", "[]");
+        assert!(pair_series(&series, &fund_pair()).is_none());
+    }
+}
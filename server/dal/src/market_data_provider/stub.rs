@@ -0,0 +1,159 @@
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use rust_decimal::Decimal;
+use time::{Date, Duration, OffsetDateTime};
+
+use crate::market_data_client::{HistoryEntry, LatestRateEntry, PairRequest};
+use crate::market_data_provider::{history_from_series, latest_from_series, MarketDataProvider};
+
+/// History served when no start date is requested.
+const DEFAULT_HISTORY_DAYS: i64 = 30;
+
+/// Offline provider answering every pair with made-up but stable daily rates, so tests
+/// and local setups get the same numbers on every run without network access.
+pub struct StubMarketDataProvider {
+    today: Option<Date>,
+}
+
+impl Default for StubMarketDataProvider {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StubMarketDataProvider {
+    pub fn new() -> Self {
+        Self { today: None }
+    }
+
+    /// Pins the last day of the generated series instead of using the current date.
+    pub fn with_today(today: Date) -> Self {
+        Self { today: Some(today) }
+    }
+
+    fn today(&self) -> Date {
+        self.today
+            .unwrap_or_else(|| OffsetDateTime::now_utc().date())
+    }
+
+    fn series(
+        &self,
+        pair: &PairRequest,
+        from: Option<OffsetDateTime>,
+    ) -> BTreeMap<OffsetDateTime, Decimal> {
+        let today = self.today();
+        let first = from
+            .map(|from| from.date())
+            .unwrap_or(today - Duration::days(DEFAULT_HISTORY_DAYS))
+            .min(today);
+
+        let mut series = BTreeMap::new();
+        let mut day = first;
+        loop {
+            series.insert(day.midnight().assume_utc(), stub_rate(pair, day));
+            if day >= today {
+                break series;
+            }
+            day += Duration::days(1);
+        }
+    }
+}
+
+#[async_trait]
+impl MarketDataProvider for StubMarketDataProvider {
+    async fn get_latest(&self, pairs: &[PairRequest]) -> Result<Vec<LatestRateEntry>> {
+        let today = self.today().midnight().assume_utc();
+        Ok(latest_from_series(pairs, |pair| {
+            Some(self.series(pair, Some(today)))
+        }))
+    }
+
+    async fn get_history(
+        &self,
+        pairs: &[PairRequest],
+        from: Option<OffsetDateTime>,
+    ) -> Result<Vec<HistoryEntry>> {
+        Ok(history_from_series(pairs, from, |pair| {
+            Some(self.series(pair, from))
+        }))
+    }
+}
+
+/// A pair's rate sits at a level derived from its tickers and drifts by up to ±5%
+/// from day to day. A pair and its inverse are not kept consistent.
+fn stub_rate(pair: &PairRequest, day: Date) -> Decimal {
+    let base = pair.base.to_ascii_uppercase();
+    let quote = pair.quote.to_ascii_uppercase();
+    if base == quote {
+        return Decimal::ONE;
+    }
+
+    let level = fnv1a(format!("{base}/{quote}").as_bytes()) % 100_000 + 1_000;
+    let drift = fnv1a(format!("{base}/{quote}/{day}").as_bytes()) % 1_001;
+    // level is in hundredths (10.00..=1009.99), the drift factor in thousandths (0.950..=1.050).
+    Decimal::from(level * (950 + drift / 10)) / Decimal::from(100_000)
+}
+
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use time::macros::{date, datetime};
+
+    use crate::market_data_client::AssetClass;
+
+    use super::*;
+
+    fn pair(base: &str, quote: &str) -> PairRequest {
+        PairRequest {
+            base: base.into(),
+            base_type: AssetClass::Crypto,
+            quote: quote.into(),
+            quote_type: AssetClass::Currency,
+        }
+    }
+
+    #[tokio::test]
+    async fn returns_the_same_rates_on_every_call() {
+        let provider = StubMarketDataProvider::with_today(date!(2026 - 10 - 16));
+        let pairs = [pair("BTC", "EUR"), pair("ETH", "EUR")];
+
+        let first = provider.get_latest(&pairs).await.unwrap();
+        let second = provider.get_latest(&pairs).await.unwrap();
+
+        assert_eq!(first.len(), 2);
+        assert_eq!(first[0].rate, second[0].rate);
+        assert_eq!(first[1].rate, second[1].rate);
+        assert_ne!(first[0].rate, first[1].rate);
+        assert!(first[0].rate > Decimal::ZERO);
+    }
+
+    #[tokio::test]
+    async fn generates_one_rate_per_day_since_from() {
+        let provider = StubMarketDataProvider::with_today(date!(2026 - 10 - 16));
+        let history = provider
+            .get_history(&[pair("BTC", "EUR")], Some(datetime!(2026-10-10 0:00 UTC)))
+            .await
+            .unwrap();
+
+        assert_eq!(history[0].rates.len(), 7);
+        assert_eq!(
+            history[0].rates.last().unwrap().recorded_at,
+            datetime!(2026-10-16 0:00 UTC)
+        );
+    }
+
+    #[test]
+    fn prices_a_pair_of_the_same_ticker_at_one() {
+        assert_eq!(
+            stub_rate(&pair("EUR", "eur"), date!(2026 - 10 - 16)),
+            Decimal::ONE
+        );
+    }
+}
@@ -7,6 +7,7 @@ pub struct Account {
     pub account_name: String,
     pub account_type: i32,
     pub ownership_share: Decimal,
+    pub cost_basis_method: String,
 }
 
 #[derive(sqlx::FromRow, Debug)]
@@ -19,6 +20,7 @@ pub struct AccountWithMetadata {
     pub liquidity_type: i32,
     pub liquidity_type_name: String,
     pub ownership_share: Decimal,
+    pub cost_basis_method: String,
     #[sqlx(default)]
     pub suggested_currency_id: Option<i32>,
     #[sqlx(default)]
@@ -48,6 +50,8 @@ pub struct AccountUpdateModel {
     pub account_type: i32,
     pub liquidity_type: i32,
     pub ownership_share: Decimal,
+    /// `None` keeps the account's current method.
+    pub cost_basis_method: Option<String>,
 }
 pub struct AccountCreationModel {
     pub user_id: Uuid,
@@ -55,6 +59,7 @@ pub struct AccountCreationModel {
     pub account_type: i32,
     pub liquidity_type: i32,
     pub ownership_share: Decimal,
+    pub cost_basis_method: String,
 }
 
 #[derive(sqlx::FromRow, Debug)]
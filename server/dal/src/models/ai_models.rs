@@ -10,6 +10,9 @@ pub struct AiTransactionSearchModel {
     pub quantity: Decimal,
     pub asset_name: String,
     pub account_name: String,
+    /// Cosine distance to the query embedding, for searches by embedding.
+    #[sqlx(default)]
+    pub distance: Option<f64>,
 }
 
 #[derive(sqlx::FromRow, Debug)]
@@ -37,3 +40,64 @@ pub struct AiAssetModel {
     pub ticker: Option<String>,
     pub asset_type: String,
 }
+
+/// A transaction or group description whose embedding the active model did not produce.
+#[derive(sqlx::FromRow, Debug)]
+pub struct AiStaleDescriptionEmbeddingModel {
+    pub id: Uuid,
+    pub description: String,
+}
+
+#[derive(sqlx::FromRow, Debug)]
+pub struct AiStaleAssetEmbeddingModel {
+    pub id: i32,
+    pub asset_name: String,
+    pub ticker: Option<String>,
+}
+
+#[derive(sqlx::FromRow, Debug)]
+pub struct AiStaleCategoryEmbeddingModel {
+    pub id: i32,
+    pub category: String,
+    pub category_type_name: String,
+}
+
+/// The category of a transaction the user has already categorised.
+#[derive(sqlx::FromRow, Debug)]
+pub struct AiTransactionCategoryModel {
+    pub transaction_id: Uuid,
+    pub category_id: i32,
+    pub category: String,
+}
+
+#[derive(Debug)]
+pub struct AddCategorySuggestionModel {
+    pub transaction_id: Uuid,
+    pub user_id: Uuid,
+    pub category_id: i32,
+    pub confidence: f32,
+    pub reason: Option<String>,
+    pub model: String,
+}
+
+#[derive(sqlx::FromRow, Debug)]
+pub struct AiCategorySuggestionModel {
+    pub transaction_id: Uuid,
+    pub category_id: i32,
+    pub confidence: f32,
+    pub reason: Option<String>,
+    pub model: String,
+    pub created_at: OffsetDateTime,
+}
+
+/// Spending well above what the user usually spends in the category.
+#[derive(sqlx::FromRow, Debug)]
+pub struct AiUnusualTransactionModel {
+    pub transaction_id: Uuid,
+    pub date_transacted: OffsetDateTime,
+    pub description: Option<String>,
+    pub category: String,
+    pub amount: Decimal,
+    /// Mean spend per transaction in the category before the period.
+    pub typical_amount: Decimal,
+}
@@ -0,0 +1,45 @@
+use sqlx::types::{Decimal, Uuid};
+use time::OffsetDateTime;
+
+#[derive(sqlx::FromRow, Debug)]
+pub struct BudgetRow {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub category_id: Option<i32>,
+    pub category_type_id: Option<i32>,
+    pub amount: Decimal,
+    pub period: String,
+    pub rollover: bool,
+    pub start_date: OffsetDateTime,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+/// Net entry quantity of one asset booked against a budget on a single day.
+#[derive(sqlx::FromRow, Debug)]
+pub struct BudgetSpendingRow {
+    pub asset_id: i32,
+    pub day: OffsetDateTime,
+    pub quantity: Decimal,
+}
+
+#[derive(Debug)]
+pub struct AddBudgetModel {
+    pub user_id: Uuid,
+    pub category_id: Option<i32>,
+    pub category_type_id: Option<i32>,
+    pub amount: Decimal,
+    pub period: String,
+    pub rollover: bool,
+    pub start_date: OffsetDateTime,
+}
+
+#[derive(Debug)]
+pub struct UpdateBudgetModel {
+    pub category_id: Option<i32>,
+    pub category_type_id: Option<i32>,
+    pub amount: Decimal,
+    pub period: String,
+    pub rollover: bool,
+    pub start_date: OffsetDateTime,
+}
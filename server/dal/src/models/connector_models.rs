@@ -1,3 +1,5 @@
+use pgvector::Vector;
+use rust_decimal::Decimal;
 use sqlx::types::{Json, Uuid};
 use time::OffsetDateTime;
 
@@ -78,6 +80,7 @@ pub struct AddConnectorTransactionModel {
     pub transaction_id: Option<Uuid>,
     pub external_id: String,
     pub external_hash: String,
+    pub counterparty: Option<String>,
 }
 
 /// Fetch outcome, written to the provider account after a walk. Clears `sync_claimed_at`.
@@ -110,3 +113,108 @@ pub struct ConnectorRawPageRow {
 pub struct RawPageCursorRow {
     pub cursor_after: Option<Json<serde_json::Value>>,
 }
+
+#[derive(sqlx::FromRow, Debug)]
+pub struct ConnectorImportIssueRow {
+    pub id: Uuid,
+    pub binding_id: Uuid,
+    pub page_id: Uuid,
+    pub external_id: String,
+    pub stage: String,
+    pub reason: String,
+    pub date_transacted: Option<OffsetDateTime>,
+    pub amount: Option<Decimal>,
+    pub currency: Option<String>,
+    pub description: Option<String>,
+    pub asset_identifier: Option<String>,
+    pub quantity: Option<Decimal>,
+    pub dismissed: bool,
+    pub first_seen_at: OffsetDateTime,
+    pub last_seen_at: OffsetDateTime,
+}
+
+/// An item a projection could not import. Upserted per binding and external id, so a
+/// re-projection refreshes the reason instead of adding another row.
+#[derive(Debug)]
+pub struct UpsertConnectorImportIssueModel {
+    pub binding_id: Uuid,
+    pub page_id: Uuid,
+    pub external_id: String,
+    pub stage: String,
+    pub reason: String,
+    pub date_transacted: Option<OffsetDateTime>,
+    pub amount: Option<Decimal>,
+    pub currency: Option<String>,
+    pub description: Option<String>,
+    pub asset_identifier: Option<String>,
+    pub quantity: Option<Decimal>,
+}
+
+/// A ghost transaction still waiting for review, with the provider item it came from.
+#[derive(sqlx::FromRow, Debug)]
+pub struct PendingReviewTransactionRow {
+    pub transaction_id: Uuid,
+    pub binding_id: Uuid,
+    pub external_id: String,
+    pub imported_at: OffsetDateTime,
+}
+
+/// Nearest neighbours of a ghost transaction's description embedding. The similar
+/// transaction is the closest one the user has already reviewed.
+#[derive(sqlx::FromRow, Debug)]
+pub struct ReviewSuggestionRow {
+    pub transaction_id: Uuid,
+    pub similar_transaction_id: Option<Uuid>,
+    pub similar_group_id: Option<Uuid>,
+    pub similar_category_id: Option<i32>,
+    pub nearest_category_id: Option<i32>,
+    pub nearest_asset_id: Option<i32>,
+}
+
+/// What an unlinked, reviewed transaction moved in one account and asset, for matching
+/// against provider imports.
+#[derive(sqlx::FromRow, Debug)]
+pub struct DuplicateCandidateRow {
+    pub transaction_id: Uuid,
+    pub date_transacted: OffsetDateTime,
+    pub description: Option<String>,
+    pub embedding: Option<Vector>,
+    pub embedding_model: Option<String>,
+    pub account_id: Uuid,
+    pub asset_id: i32,
+    pub quantity: Decimal,
+}
+
+#[derive(sqlx::FromRow, Debug)]
+pub struct ConnectorDuplicateCandidateRow {
+    pub transaction_id: Uuid,
+    pub candidate_transaction_id: Uuid,
+    pub score: f32,
+}
+
+#[derive(Debug)]
+pub struct AddConnectorDuplicateCandidateModel {
+    pub transaction_id: Uuid,
+    pub candidate_transaction_id: Uuid,
+    pub score: f32,
+}
+
+#[derive(sqlx::FromRow, Debug)]
+pub struct ConnectorBalanceSnapshotRow {
+    pub provider_account_id: Uuid,
+    pub fetched_at: OffsetDateTime,
+    pub kind: String,
+    pub asset_identifier: String,
+    pub asset_id: Option<i32>,
+    pub quantity: Decimal,
+}
+
+#[derive(Debug)]
+pub struct AddConnectorBalanceSnapshotModel {
+    pub provider_account_id: Uuid,
+    pub fetched_at: OffsetDateTime,
+    pub kind: String,
+    pub asset_identifier: String,
+    pub asset_id: Option<i32>,
+    pub quantity: Decimal,
+}
@@ -0,0 +1,28 @@
+use sqlx::types::{Decimal, Uuid};
+use time::OffsetDateTime;
+
+#[derive(sqlx::FromRow, Debug)]
+pub struct CorporateActionRow {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub asset_id: i32,
+    pub action_type: String,
+    pub effective_date: OffsetDateTime,
+    pub ratio_from: Decimal,
+    pub ratio_to: Decimal,
+    pub new_asset_id: Option<i32>,
+    pub cost_basis_fraction: Option<Decimal>,
+    pub created_at: OffsetDateTime,
+}
+
+#[derive(Debug)]
+pub struct AddCorporateActionModel {
+    pub user_id: Uuid,
+    pub asset_id: i32,
+    pub action_type: String,
+    pub effective_date: OffsetDateTime,
+    pub ratio_from: Decimal,
+    pub ratio_to: Decimal,
+    pub new_asset_id: Option<i32>,
+    pub cost_basis_fraction: Option<Decimal>,
+}
@@ -0,0 +1,43 @@
+use sqlx::types::{Json, Uuid};
+use time::OffsetDateTime;
+
+#[derive(sqlx::FromRow, Debug)]
+pub struct InsightDigestRow {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub period: String,
+    pub period_start: OffsetDateTime,
+    pub period_end: OffsetDateTime,
+    pub reference_asset_id: i32,
+    pub content: Json<serde_json::Value>,
+    pub narrative: Option<String>,
+    pub model: Option<String>,
+    pub read_at: Option<OffsetDateTime>,
+    pub created_at: OffsetDateTime,
+}
+
+#[derive(Debug)]
+pub struct AddInsightDigestModel {
+    pub user_id: Uuid,
+    pub period: String,
+    pub period_start: OffsetDateTime,
+    pub period_end: OffsetDateTime,
+    pub reference_asset_id: i32,
+    pub content: serde_json::Value,
+    pub narrative: Option<String>,
+    pub model: Option<String>,
+}
+
+/// A user still without a digest for the period, with the asset it is reported in and
+/// whether they opted in to having it narrated.
+#[derive(sqlx::FromRow, Debug)]
+pub struct InsightDigestCandidateRow {
+    pub user_id: Uuid,
+    pub reference_asset_id: i32,
+    pub narrate: bool,
+}
+
+#[derive(sqlx::FromRow, Debug)]
+pub struct InsightDigestSettingsRow {
+    pub narrate: bool,
+}
@@ -3,12 +3,25 @@ pub mod ai_conversation_models;
 pub mod ai_models;
 pub mod asset_models;
 pub mod base;
+pub mod budget_models;
 pub mod category_models;
 pub mod connector_models;
+pub mod corporate_action_models;
 pub mod entry_models;
 pub mod external_identity_models;
 pub mod file_models;
+pub mod insight_digest_models;
 pub mod portfolio_models;
 pub mod rate_limit_models;
+pub mod reconciliation_models;
+pub mod recurring_transaction_models;
+pub mod spending_alert_models;
+pub mod statement_csv_mapping_models;
+pub mod subscription_models;
+pub mod tag_models;
+pub mod ticker_alias_models;
 pub mod transaction_models;
+pub mod transaction_rule_models;
+pub mod transfer_models;
+pub mod user_data_archive_models;
 pub mod user_models;
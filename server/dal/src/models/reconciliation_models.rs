@@ -0,0 +1,34 @@
+use sqlx::types::{Decimal, Uuid};
+use time::OffsetDateTime;
+
+#[derive(sqlx::FromRow, Debug)]
+pub struct AccountReconciliationRow {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub account_id: Uuid,
+    pub asset_id: i32,
+    pub statement_date: OffsetDateTime,
+    pub statement_balance: Decimal,
+    pub source: String,
+    pub binding_id: Option<Uuid>,
+    pub created_at: OffsetDateTime,
+}
+
+/// Net quantity a single transaction moved in or out of an account for one asset.
+#[derive(sqlx::FromRow, Debug)]
+pub struct ReconciliationMovementRow {
+    pub transaction_id: Uuid,
+    pub date_transacted: OffsetDateTime,
+    pub quantity: Decimal,
+}
+
+#[derive(Debug)]
+pub struct AddAccountReconciliationModel {
+    pub user_id: Uuid,
+    pub account_id: Uuid,
+    pub asset_id: i32,
+    pub statement_date: OffsetDateTime,
+    pub statement_balance: Decimal,
+    pub source: String,
+    pub binding_id: Option<Uuid>,
+}
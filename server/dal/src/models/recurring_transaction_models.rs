@@ -0,0 +1,47 @@
+use sqlx::types::{Json, Uuid};
+use time::OffsetDateTime;
+
+#[derive(sqlx::FromRow, Debug)]
+pub struct RecurringTransactionRow {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub account_id: Uuid,
+    pub name: String,
+    pub template: Json<serde_json::Value>,
+    pub rrule: String,
+    pub start_date: OffsetDateTime,
+    pub end_date: Option<OffsetDateTime>,
+    pub next_occurrence: Option<OffsetDateTime>,
+    pub last_occurrence: Option<OffsetDateTime>,
+    pub create_as_ghost: bool,
+    pub active: bool,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+#[derive(Debug)]
+pub struct AddRecurringTransactionModel {
+    pub user_id: Uuid,
+    pub account_id: Uuid,
+    pub name: String,
+    pub template: serde_json::Value,
+    pub rrule: String,
+    pub start_date: OffsetDateTime,
+    pub end_date: Option<OffsetDateTime>,
+    pub next_occurrence: Option<OffsetDateTime>,
+    pub create_as_ghost: bool,
+    pub active: bool,
+}
+
+#[derive(Debug)]
+pub struct UpdateRecurringTransactionModel {
+    pub account_id: Uuid,
+    pub name: String,
+    pub template: serde_json::Value,
+    pub rrule: String,
+    pub start_date: OffsetDateTime,
+    pub end_date: Option<OffsetDateTime>,
+    pub next_occurrence: Option<OffsetDateTime>,
+    pub create_as_ghost: bool,
+    pub active: bool,
+}
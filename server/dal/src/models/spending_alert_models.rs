@@ -0,0 +1,47 @@
+use rust_decimal::Decimal;
+use sqlx::types::Uuid;
+use time::OffsetDateTime;
+
+#[derive(sqlx::FromRow, Debug)]
+pub struct SpendingAlertRow {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub transaction_id: Uuid,
+    pub kind: String,
+    pub account_id: Uuid,
+    pub asset_id: i32,
+    pub amount: Decimal,
+    pub typical_amount: Option<Decimal>,
+    pub related_transaction_id: Option<Uuid>,
+    pub description: Option<String>,
+    pub date_transacted: OffsetDateTime,
+    pub dismissed_at: Option<OffsetDateTime>,
+    pub created_at: OffsetDateTime,
+}
+
+#[derive(Debug)]
+pub struct AddSpendingAlertModel {
+    pub user_id: Uuid,
+    pub transaction_id: Uuid,
+    pub kind: String,
+    pub account_id: Uuid,
+    pub asset_id: i32,
+    pub amount: Decimal,
+    pub typical_amount: Option<Decimal>,
+    pub related_transaction_id: Option<Uuid>,
+    pub description: Option<String>,
+    pub date_transacted: OffsetDateTime,
+}
+
+/// Net amount of one asset moved by a regular transaction in a single account and
+/// category, with the transaction's description when it has one.
+#[derive(sqlx::FromRow, Debug)]
+pub struct SpendingAlertChargeRow {
+    pub transaction_id: Uuid,
+    pub date_transacted: OffsetDateTime,
+    pub description: Option<String>,
+    pub account_id: Uuid,
+    pub asset_id: i32,
+    pub category_id: i32,
+    pub quantity: Decimal,
+}
@@ -0,0 +1,18 @@
+use sqlx::types::{Json, Uuid};
+use time::OffsetDateTime;
+
+#[derive(sqlx::FromRow, Debug)]
+pub struct StatementCsvMappingRow {
+    pub account_id: Uuid,
+    pub user_id: Uuid,
+    pub mapping: Json<serde_json::Value>,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+#[derive(Debug)]
+pub struct UpsertStatementCsvMappingModel {
+    pub account_id: Uuid,
+    pub user_id: Uuid,
+    pub mapping: serde_json::Value,
+}
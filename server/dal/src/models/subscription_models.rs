@@ -0,0 +1,16 @@
+use pgvector::Vector;
+use sqlx::types::{Decimal, Uuid};
+use time::OffsetDateTime;
+
+/// Net amount of one asset moved by a regular transaction in a single account,
+/// together with the transaction's description and its embedding.
+#[derive(sqlx::FromRow, Debug)]
+pub struct SubscriptionChargeRow {
+    pub transaction_id: Uuid,
+    pub date_transacted: OffsetDateTime,
+    pub description: String,
+    pub embedding: Option<Vector>,
+    pub account_id: Uuid,
+    pub asset_id: i32,
+    pub quantity: Decimal,
+}
@@ -0,0 +1,16 @@
+use sqlx::types::Uuid;
+use time::OffsetDateTime;
+
+#[derive(sqlx::FromRow, Debug)]
+pub struct TagRow {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub created_at: OffsetDateTime,
+}
+
+#[derive(Debug)]
+pub struct AddTagModel {
+    pub user_id: Uuid,
+    pub name: String,
+}
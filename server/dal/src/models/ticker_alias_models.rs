@@ -0,0 +1,18 @@
+use sqlx::types::Uuid;
+use time::OffsetDateTime;
+
+#[derive(sqlx::FromRow, Debug)]
+pub struct UserTickerAliasRow {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub provider_ticker: String,
+    pub asset_id: i32,
+    pub created_at: OffsetDateTime,
+}
+
+#[derive(Debug)]
+pub struct AddUserTickerAliasModel {
+    pub user_id: Uuid,
+    pub provider_ticker: String,
+    pub asset_id: i32,
+}
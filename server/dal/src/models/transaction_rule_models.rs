@@ -0,0 +1,52 @@
+use sqlx::types::{Decimal, Uuid};
+use time::OffsetDateTime;
+
+#[derive(sqlx::FromRow, Debug)]
+pub struct TransactionRuleRow {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub priority: i32,
+    pub enabled: bool,
+    pub description_contains: Option<String>,
+    pub description_regex: Option<String>,
+    pub amount_min: Option<Decimal>,
+    pub amount_max: Option<Decimal>,
+    pub account_id: Option<Uuid>,
+    pub counterparty: Option<String>,
+    pub category_id: Option<i32>,
+    pub description_rewrite: Option<String>,
+    pub visibility: Option<String>,
+    pub created_at: OffsetDateTime,
+    pub updated_at: OffsetDateTime,
+}
+
+#[derive(sqlx::FromRow, Debug)]
+pub struct TransactionRuleTagRow {
+    pub rule_id: Uuid,
+    pub tag_id: Uuid,
+}
+
+#[derive(Debug)]
+pub struct AddUpdateTransactionRuleModel {
+    pub name: String,
+    pub priority: i32,
+    pub enabled: bool,
+    pub description_contains: Option<String>,
+    pub description_regex: Option<String>,
+    pub amount_min: Option<Decimal>,
+    pub amount_max: Option<Decimal>,
+    pub account_id: Option<Uuid>,
+    pub counterparty: Option<String>,
+    pub category_id: Option<i32>,
+    pub description_rewrite: Option<String>,
+    pub visibility: Option<String>,
+}
+
+/// A transaction rules may be applied to.
+#[derive(sqlx::FromRow, Debug)]
+pub struct RuleCandidateRow {
+    pub transaction_id: Uuid,
+    /// The other party the provider reported, for imported transactions.
+    pub counterparty: Option<String>,
+}
@@ -0,0 +1,16 @@
+use sqlx::types::{Decimal, Uuid};
+use time::OffsetDateTime;
+
+/// The only entry of a single-currency cash movement that may be one side of a transfer
+/// between the user's accounts.
+#[derive(sqlx::FromRow, Debug)]
+pub struct TransferLegRow {
+    pub transaction_id: Uuid,
+    pub date_transacted: OffsetDateTime,
+    pub visibility: String,
+    pub account_id: Uuid,
+    pub asset_id: i32,
+    pub quantity: Decimal,
+    /// Whether a connector imported the transaction.
+    pub imported: bool,
+}
@@ -0,0 +1,109 @@
+use sqlx::types::{Decimal, Uuid};
+use time::OffsetDateTime;
+
+#[derive(sqlx::FromRow, Debug)]
+pub struct ArchiveAccountRow {
+    pub id: Uuid,
+    pub account_name: String,
+    pub account_type: i32,
+    pub liquidity_type: i32,
+    pub active: bool,
+    pub ownership_share: Decimal,
+    pub cost_basis_method: String,
+}
+
+#[derive(sqlx::FromRow, Debug)]
+pub struct ArchiveAssetRow {
+    pub id: i32,
+    pub asset_type: i32,
+    pub asset_name: String,
+    pub ticker: String,
+    pub base_pair_id: Option<i32>,
+}
+
+#[derive(sqlx::FromRow, Debug)]
+pub struct ArchiveAssetPairRow {
+    pub id: i32,
+    pub pair1: i32,
+    pub pair2: i32,
+}
+
+#[derive(sqlx::FromRow, Debug)]
+pub struct ArchiveAssetRateRow {
+    pub pair_id: i32,
+    pub rate: Decimal,
+    pub recorded_at: OffsetDateTime,
+}
+
+#[derive(sqlx::FromRow, Debug)]
+pub struct ArchiveTransactionGroupRow {
+    pub id: Uuid,
+    pub category_id: i32,
+    pub description: String,
+    pub date_added: OffsetDateTime,
+}
+
+/// A transaction together with its optional description and dividend source.
+#[derive(sqlx::FromRow, Debug)]
+pub struct ArchiveTransactionRow {
+    pub id: Uuid,
+    pub group_id: Option<Uuid>,
+    pub type_id: Option<i32>,
+    pub date_transacted: OffsetDateTime,
+    pub visibility: String,
+    pub description: Option<String>,
+    pub source_asset_id: Option<i32>,
+}
+
+#[derive(sqlx::FromRow, Debug)]
+pub struct ArchiveEntryRow {
+    pub transaction_id: Uuid,
+    pub account_id: Uuid,
+    pub asset_id: i32,
+    pub quantity: Decimal,
+    pub category_id: i32,
+}
+
+#[derive(sqlx::FromRow, Debug)]
+pub struct ArchiveConnectorConnectionRow {
+    pub id: Uuid,
+    pub provider_kind: String,
+    pub credential_mode: String,
+    pub status: String,
+    pub consent_expires_at: Option<OffsetDateTime>,
+}
+
+#[derive(sqlx::FromRow, Debug)]
+pub struct ArchiveConnectorProviderAccountRow {
+    pub id: Uuid,
+    pub connection_id: Uuid,
+    pub external_account_id: String,
+}
+
+#[derive(sqlx::FromRow, Debug)]
+pub struct ArchiveConnectorBindingRow {
+    pub id: Uuid,
+    pub provider_account_id: Uuid,
+    pub sverto_account_id: Uuid,
+    pub write_mode: String,
+    pub status: String,
+}
+
+#[derive(sqlx::FromRow, Debug)]
+pub struct ArchiveConnectorTransactionRow {
+    pub binding_id: Uuid,
+    pub transaction_id: Option<Uuid>,
+    pub external_id: String,
+    pub external_hash: String,
+    pub edited_by_user: bool,
+    pub counterparty: Option<String>,
+}
+
+/// Like `AddTransactionModel`, but keeps a missing transaction type as `NULL`.
+pub struct InsertArchivedTransactionModel {
+    pub user_id: Uuid,
+    pub group_id: Option<Uuid>,
+    pub type_id: Option<i32>,
+    pub date_transacted: OffsetDateTime,
+    pub visibility: String,
+}
@@ -27,6 +27,7 @@ pub fn get_accounts(params: GetAccountsParams) -> DbQueryWithValues {
         .column((AccountIden::Table, AccountIden::AccountName))
         .column((AccountIden::Table, AccountIden::AccountType))
         .column((AccountIden::Table, AccountIden::OwnershipShare))
+        .column((AccountIden::Table, AccountIden::CostBasisMethod))
         .conditions(
             params.include_metadata,
             |q| {
@@ -162,7 +163,8 @@ fn suggested_currency_subquery() -> sea_query::SelectStatement {
 
 #[macros::named_query]
 pub fn update_account(model: AccountUpdateModel) -> DbQueryWithValues {
-    Query::update()
+    let mut query = Query::update();
+    query
         .table(AccountIden::Table)
         .value(AccountIden::AccountName, model.account_name)
         .value(AccountIden::AccountType, model.account_type)
@@ -170,9 +172,13 @@ pub fn update_account(model: AccountUpdateModel) -> DbQueryWithValues {
         .value(AccountIden::OwnershipShare, model.ownership_share)
         .and_where(Expr::col(AccountIden::Id).eq(model.account_id))
         .and_where(Expr::col(AccountIden::UserId).eq(model.user_id))
-        .and_where(Expr::col(AccountIden::Active).eq(true))
-        .build_sqlx(PostgresQueryBuilder)
-        .into()
+        .and_where(Expr::col(AccountIden::Active).eq(true));
+
+    if let Some(cost_basis_method) = model.cost_basis_method {
+        query.value(AccountIden::CostBasisMethod, cost_basis_method);
+    }
+
+    query.build_sqlx(PostgresQueryBuilder).into()
 }
 
 #[macros::named_query]
@@ -186,6 +192,7 @@ pub fn insert_account(model: AccountCreationModel) -> DbQueryWithValues {
             AccountIden::LiquidityType,
             AccountIden::Active,
             AccountIden::OwnershipShare,
+            AccountIden::CostBasisMethod,
         ])
         .values_panic([
             model.user_id.into(),
@@ -194,6 +201,7 @@ pub fn insert_account(model: AccountCreationModel) -> DbQueryWithValues {
             model.liquidity_type.into(),
             true.into(),
             model.ownership_share.into(),
+            model.cost_basis_method.into(),
         ])
         .returning_col(AccountIden::Id)
         .build_sqlx(PostgresQueryBuilder)
@@ -4,16 +4,21 @@ use sea_query::*;
 use sea_query_sqlx::SqlxBinder;
 use uuid::Uuid;
 
+use crate::enums::transaction_types::DatabaseTransactionTypes;
 use crate::idens::account_idens::{AccountIden, AccountLiquidityTypesIden, AccountTypesIden};
+use crate::idens::ai_reembed_idens::AiReembedRunIden;
 use crate::idens::asset_idens::{AssetTypesIden, AssetsIden};
+use crate::idens::connector_idens::ConnectorTransactionIden;
 use crate::idens::entries_idens::EntryIden;
 use crate::idens::transaction_idens::{
-    TransactionCategoriesIden, TransactionCategoryTypeIden, TransactionDescriptionsIden,
-    TransactionGroupIden, TransactionIden,
+    TransactionCategoriesIden, TransactionCategorySuggestionIden, TransactionCategoryTypeIden,
+    TransactionDescriptionsIden, TransactionGroupIden, TransactionIden,
 };
+use crate::models::ai_models::AddCategorySuggestionModel;
 use crate::query_params::ai_search_params::{
-    AggregateTransactionsParams, ListAccountsParams, SearchAssetsParams, SearchCategoriesParams,
-    SearchTransactionsParams,
+    AggregateTransactionsParams, EmbeddingSearch, ListAccountsParams, SearchAssetsParams,
+    SearchCategoriesParams, SearchTransactionsParams, UncategorizedTransactionsParams,
+    UnusualTransactionsParams,
 };
 
 use super::{escape_ilike_pattern, DbQueryWithValues};
@@ -183,7 +188,7 @@ pub fn count_transactions_by_description(params: &SearchTransactionsParams) -> D
 #[macros::named_query]
 pub fn search_transactions_by_embedding(
     user_id: Uuid,
-    query_vector: Vector,
+    embedding: &EmbeddingSearch,
     date_from: Option<&str>,
     date_to: Option<&str>,
     account_ids: Option<Vec<Uuid>>,
@@ -212,6 +217,16 @@ pub fn search_transactions_by_embedding(
             Expr::cust("(array_agg(DISTINCT \"account\".\"account_name\"))[1]"),
             Alias::new("account_name"),
         )
+        .expr_as(
+            cosine_distance(
+                (
+                    TransactionDescriptionsIden::Table,
+                    TransactionDescriptionsIden::Embedding,
+                ),
+                embedding,
+            ),
+            Alias::new("distance"),
+        )
         .from(TransactionIden::Table)
         .inner_join(
             TransactionDescriptionsIden::Table,
@@ -237,13 +252,17 @@ pub fn search_transactions_by_embedding(
                 .equals((EntryIden::Table, EntryIden::AccountId)),
         )
         .and_where(Expr::col((TransactionIden::Table, TransactionIden::UserId)).eq(user_id))
-        .and_where(
-            Expr::col((
+        .and_where(same_embedding_model(
+            (
                 TransactionDescriptionsIden::Table,
-                TransactionDescriptionsIden::Embedding,
-            ))
-            .is_not_null(),
-        )
+                TransactionDescriptionsIden::EmbeddingModel,
+            ),
+            (
+                TransactionDescriptionsIden::Table,
+                TransactionDescriptionsIden::EmbeddingDims,
+            ),
+            embedding,
+        ))
         .group_by_col((TransactionIden::Table, TransactionIden::Id))
         .group_by_col((
             TransactionDescriptionsIden::Table,
@@ -255,11 +274,13 @@ pub fn search_transactions_by_embedding(
             TransactionDescriptionsIden::Embedding,
         ))
         .order_by_expr(
-            Expr::col((
-                TransactionDescriptionsIden::Table,
-                TransactionDescriptionsIden::Embedding,
-            ))
-            .binary(PgBinOper::CosineDistance, Expr::val(query_vector)),
+            cosine_distance(
+                (
+                    TransactionDescriptionsIden::Table,
+                    TransactionDescriptionsIden::Embedding,
+                ),
+                embedding,
+            ),
             Order::Asc,
         )
         .limit(limit as u64);
@@ -316,6 +337,18 @@ pub fn aggregate_transactions(params: &AggregateTransactionsParams) -> DbQueryWi
             String::new(),
             "to_char(t.date_transacted, 'YYYY-MM')",
         ),
+        // A transaction carries its own tags and those of its group; one with several
+        // tags is counted under each of them.
+        "tag" => (
+            "tag.name as group_name",
+            "JOIN (SELECT tt.transaction_id, tt.tag_id FROM transaction_tag tt \
+             UNION SELECT gt_t.id, gt.tag_id FROM transaction gt_t \
+             JOIN transaction_group_tag gt ON gt.group_id = gt_t.group_id) ttag \
+             ON ttag.transaction_id = t.id \
+             JOIN tag ON tag.id = ttag.tag_id"
+                .to_string(),
+            "tag.name",
+        ),
         _ => (
             "COALESCE(td.description, tg.description, 'No description') as group_name",
             "LEFT JOIN transaction_descriptions td ON td.transaction_id = t.id \
@@ -389,6 +422,77 @@ pub fn aggregate_transactions(params: &AggregateTransactionsParams) -> DbQueryWi
     }
 }
 
+/// Each regular, visible outflow in the period, per category, that is both
+/// `deviations` standard deviations above and `min_ratio` times the category's mean
+/// outflow over the history window. Most unusual first.
+#[macros::named_query]
+pub fn get_unusual_transactions(params: &UnusualTransactionsParams) -> DbQueryWithValues {
+    let sql = r#"
+        WITH spending AS (
+            SELECT t.id AS transaction_id,
+                   t.date_transacted,
+                   e.category_id,
+                   -SUM(e.quantity) AS amount
+            FROM entry e
+            JOIN transaction t ON e.transaction_id = t.id
+            WHERE t.user_id = $1
+              AND e.asset_id = $2
+              AND t.type_id = $3
+              AND t.visibility = 'default'
+              AND t.date_transacted >= $4
+              AND t.date_transacted < $5
+            GROUP BY t.id, t.date_transacted, e.category_id
+            HAVING SUM(e.quantity) < 0
+        ),
+        history AS (
+            SELECT category_id,
+                   AVG(amount) AS mean,
+                   COALESCE(STDDEV_SAMP(amount), 0) AS stddev,
+                   COUNT(*) AS samples
+            FROM spending
+            WHERE date_transacted < $6
+            GROUP BY category_id
+        )
+        SELECT s.transaction_id,
+               s.date_transacted,
+               COALESCE(td.description, tg.description) AS description,
+               tc.category,
+               s.amount,
+               h.mean AS typical_amount
+        FROM spending s
+        JOIN history h ON h.category_id = s.category_id
+        JOIN transaction_categories tc ON tc.id = s.category_id
+        JOIN transaction t ON t.id = s.transaction_id
+        LEFT JOIN transaction_descriptions td ON td.transaction_id = t.id
+        LEFT JOIN transaction_group tg ON tg.id = t.group_id
+        WHERE s.date_transacted >= $6
+          AND h.samples >= $7
+          AND s.amount > h.mean + $8 * h.stddev
+          AND s.amount >= h.mean * $9
+        ORDER BY (s.amount - h.mean) / NULLIF(h.stddev, 0) DESC NULLS LAST, s.amount DESC
+        LIMIT $10
+    "#;
+
+    let values: Vec<sea_query::Value> = vec![
+        params.user_id.into(),
+        params.currency_asset_id.into(),
+        (DatabaseTransactionTypes::RegularTransaction as i32).into(),
+        params.history_from.into(),
+        params.date_to.into(),
+        params.date_from.into(),
+        params.min_samples.into(),
+        params.deviations.into(),
+        params.min_ratio.into(),
+        params.limit.into(),
+    ];
+
+    DbQueryWithValues {
+        query: sql.to_string(),
+        values: sea_query_sqlx::SqlxValues(sea_query::Values(values)),
+        name: None,
+    }
+}
+
 #[macros::named_query]
 pub fn get_active_accounts(params: &ListAccountsParams) -> DbQueryWithValues {
     Query::select()
@@ -435,49 +539,70 @@ pub fn get_active_accounts(params: &ListAccountsParams) -> DbQueryWithValues {
 pub fn update_transaction_description_embedding(
     transaction_id: Uuid,
     embedding: Vector,
+    model: &str,
 ) -> DbQueryWithValues {
+    let dims = embedding.as_slice().len() as i32;
     Query::update()
         .table(TransactionDescriptionsIden::Table)
         .value(
             TransactionDescriptionsIden::Embedding,
             sea_query::Value::from(embedding),
         )
+        .value(TransactionDescriptionsIden::EmbeddingModel, model)
+        .value(TransactionDescriptionsIden::EmbeddingDims, dims)
         .and_where(Expr::col(TransactionDescriptionsIden::TransactionId).eq(transaction_id))
         .build_sqlx(PostgresQueryBuilder)
         .into()
 }
 
 #[macros::named_query]
-pub fn update_transaction_group_embedding(group_id: Uuid, embedding: Vector) -> DbQueryWithValues {
+pub fn update_transaction_group_embedding(
+    group_id: Uuid,
+    embedding: Vector,
+    model: &str,
+) -> DbQueryWithValues {
+    let dims = embedding.as_slice().len() as i32;
     Query::update()
         .table(TransactionGroupIden::Table)
         .value(
             TransactionGroupIden::DescriptionEmbedding,
             sea_query::Value::from(embedding),
         )
+        .value(TransactionGroupIden::DescriptionEmbeddingModel, model)
+        .value(TransactionGroupIden::DescriptionEmbeddingDims, dims)
         .and_where(Expr::col(TransactionGroupIden::TransactionGroupId).eq(group_id))
         .build_sqlx(PostgresQueryBuilder)
         .into()
 }
 
 #[macros::named_query]
-pub fn update_asset_embedding(asset_id: i32, embedding: Vector) -> DbQueryWithValues {
+pub fn update_asset_embedding(asset_id: i32, embedding: Vector, model: &str) -> DbQueryWithValues {
+    let dims = embedding.as_slice().len() as i32;
     Query::update()
         .table(AssetsIden::Table)
         .value(AssetsIden::Embedding, sea_query::Value::from(embedding))
+        .value(AssetsIden::EmbeddingModel, model)
+        .value(AssetsIden::EmbeddingDims, dims)
         .and_where(Expr::col(AssetsIden::Id).eq(asset_id))
         .build_sqlx(PostgresQueryBuilder)
         .into()
 }
 
 #[macros::named_query]
-pub fn update_category_embedding(category_id: i32, embedding: Vector) -> DbQueryWithValues {
+pub fn update_category_embedding(
+    category_id: i32,
+    embedding: Vector,
+    model: &str,
+) -> DbQueryWithValues {
+    let dims = embedding.as_slice().len() as i32;
     Query::update()
         .table(TransactionCategoriesIden::Table)
         .value(
             TransactionCategoriesIden::Embedding,
             sea_query::Value::from(embedding),
         )
+        .value(TransactionCategoriesIden::EmbeddingModel, model)
+        .value(TransactionCategoriesIden::EmbeddingDims, dims)
         .and_where(Expr::col(TransactionCategoriesIden::Id).eq(category_id))
         .build_sqlx(PostgresQueryBuilder)
         .into()
@@ -531,24 +656,40 @@ pub fn search_categories(params: &SearchCategoriesParams) -> DbQueryWithValues {
             .is_null()),
         );
 
+    // Categories not yet embedded by the active model sort after the compared ones.
     if let Some(ref embedding) = params.embedding {
         query.order_by_expr(
-            Expr::col((
-                TransactionCategoriesIden::Table,
-                TransactionCategoriesIden::Embedding,
-            ))
-            .binary(PgBinOper::CosineDistance, Expr::val(embedding.clone())),
-            Order::Asc,
-        );
-    } else {
-        query.order_by(
-            (
-                TransactionCategoriesIden::Table,
-                TransactionCategoriesIden::Category,
-            ),
+            Expr::case(
+                same_embedding_model(
+                    (
+                        TransactionCategoriesIden::Table,
+                        TransactionCategoriesIden::EmbeddingModel,
+                    ),
+                    (
+                        TransactionCategoriesIden::Table,
+                        TransactionCategoriesIden::EmbeddingDims,
+                    ),
+                    embedding,
+                ),
+                cosine_distance(
+                    (
+                        TransactionCategoriesIden::Table,
+                        TransactionCategoriesIden::Embedding,
+                    ),
+                    embedding,
+                ),
+            )
+            .into(),
             Order::Asc,
         );
     }
+    query.order_by(
+        (
+            TransactionCategoriesIden::Table,
+            TransactionCategoriesIden::Category,
+        ),
+        Order::Asc,
+    );
 
     if let Some(limit) = params.limit {
         query.limit(limit as u64);
@@ -589,17 +730,614 @@ pub fn search_assets(params: &SearchAssetsParams) -> DbQueryWithValues {
         );
     }
 
+    // Assets not yet embedded by the active model sort after the compared ones.
     if let Some(ref embedding) = params.embedding {
         query.order_by_expr(
-            Expr::col((AssetsIden::Table, AssetsIden::Embedding))
-                .binary(PgBinOper::CosineDistance, Expr::val(embedding.clone())),
+            Expr::case(
+                same_embedding_model(
+                    (AssetsIden::Table, AssetsIden::EmbeddingModel),
+                    (AssetsIden::Table, AssetsIden::EmbeddingDims),
+                    embedding,
+                ),
+                cosine_distance((AssetsIden::Table, AssetsIden::Embedding), embedding),
+            )
+            .into(),
             Order::Asc,
         );
-    } else {
-        query.order_by((AssetsIden::Table, AssetsIden::AssetName), Order::Asc);
     }
+    query.order_by((AssetsIden::Table, AssetsIden::AssetName), Order::Asc);
 
     query.limit(params.limit.unwrap_or(100) as u64);
 
     query.build_sqlx(PostgresQueryBuilder).into()
 }
+
+/// Non-empty transaction descriptions whose embedding is missing or was not produced by
+/// `model` at `dims` dimensions, in id order after `after`.
+#[macros::named_query]
+pub fn get_stale_transaction_embeddings(
+    model: &str,
+    dims: i32,
+    after: Option<Uuid>,
+    limit: u64,
+) -> DbQueryWithValues {
+    let mut query = Query::select();
+    query
+        .expr_as(
+            Expr::col((
+                TransactionDescriptionsIden::Table,
+                TransactionDescriptionsIden::TransactionId,
+            )),
+            Alias::new("id"),
+        )
+        .column((
+            TransactionDescriptionsIden::Table,
+            TransactionDescriptionsIden::Description,
+        ))
+        .from(TransactionDescriptionsIden::Table)
+        .and_where(
+            Expr::col((
+                TransactionDescriptionsIden::Table,
+                TransactionDescriptionsIden::Description,
+            ))
+            .ne(""),
+        )
+        .and_where(
+            same_embedding_model_as(
+                (
+                    TransactionDescriptionsIden::Table,
+                    TransactionDescriptionsIden::EmbeddingModel,
+                ),
+                (
+                    TransactionDescriptionsIden::Table,
+                    TransactionDescriptionsIden::EmbeddingDims,
+                ),
+                model,
+                dims,
+            )
+            .not(),
+        )
+        .order_by(
+            (
+                TransactionDescriptionsIden::Table,
+                TransactionDescriptionsIden::TransactionId,
+            ),
+            Order::Asc,
+        )
+        .limit(limit);
+
+    if let Some(after) = after {
+        query.and_where(
+            Expr::col((
+                TransactionDescriptionsIden::Table,
+                TransactionDescriptionsIden::TransactionId,
+            ))
+            .gt(after),
+        );
+    }
+
+    query.build_sqlx(PostgresQueryBuilder).into()
+}
+
+#[macros::named_query]
+pub fn get_stale_group_embeddings(
+    model: &str,
+    dims: i32,
+    after: Option<Uuid>,
+    limit: u64,
+) -> DbQueryWithValues {
+    let mut query = Query::select();
+    query
+        .expr_as(
+            Expr::col((
+                TransactionGroupIden::Table,
+                TransactionGroupIden::TransactionGroupId,
+            )),
+            Alias::new("id"),
+        )
+        .column((
+            TransactionGroupIden::Table,
+            TransactionGroupIden::Description,
+        ))
+        .from(TransactionGroupIden::Table)
+        .and_where(
+            Expr::col((
+                TransactionGroupIden::Table,
+                TransactionGroupIden::Description,
+            ))
+            .ne(""),
+        )
+        .and_where(
+            same_embedding_model_as(
+                (
+                    TransactionGroupIden::Table,
+                    TransactionGroupIden::DescriptionEmbeddingModel,
+                ),
+                (
+                    TransactionGroupIden::Table,
+                    TransactionGroupIden::DescriptionEmbeddingDims,
+                ),
+                model,
+                dims,
+            )
+            .not(),
+        )
+        .order_by(
+            (
+                TransactionGroupIden::Table,
+                TransactionGroupIden::TransactionGroupId,
+            ),
+            Order::Asc,
+        )
+        .limit(limit);
+
+    if let Some(after) = after {
+        query.and_where(
+            Expr::col((
+                TransactionGroupIden::Table,
+                TransactionGroupIden::TransactionGroupId,
+            ))
+            .gt(after),
+        );
+    }
+
+    query.build_sqlx(PostgresQueryBuilder).into()
+}
+
+#[macros::named_query]
+pub fn get_stale_asset_embeddings(
+    model: &str,
+    dims: i32,
+    after: Option<i32>,
+    limit: u64,
+) -> DbQueryWithValues {
+    let mut query = Query::select();
+    query
+        .column((AssetsIden::Table, AssetsIden::Id))
+        .column((AssetsIden::Table, AssetsIden::AssetName))
+        .column((AssetsIden::Table, AssetsIden::Ticker))
+        .from(AssetsIden::Table)
+        .and_where(
+            same_embedding_model_as(
+                (AssetsIden::Table, AssetsIden::EmbeddingModel),
+                (AssetsIden::Table, AssetsIden::EmbeddingDims),
+                model,
+                dims,
+            )
+            .not(),
+        )
+        .order_by((AssetsIden::Table, AssetsIden::Id), Order::Asc)
+        .limit(limit);
+
+    if let Some(after) = after {
+        query.and_where(Expr::col((AssetsIden::Table, AssetsIden::Id)).gt(after));
+    }
+
+    query.build_sqlx(PostgresQueryBuilder).into()
+}
+
+/// Records that a re-embed run for the model has started, unless one is already under
+/// way. A run that has not finished a batch since `abandoned_before` is taken over.
+/// Affects a row only when the caller started the run.
+#[macros::named_query]
+pub fn claim_reembed_run(
+    model: &str,
+    dims: i32,
+    abandoned_before: time::OffsetDateTime,
+) -> DbQueryWithValues {
+    Query::insert()
+        .into_table(AiReembedRunIden::Table)
+        .columns([
+            AiReembedRunIden::EmbeddingModel,
+            AiReembedRunIden::EmbeddingDims,
+        ])
+        .values_panic([model.into(), dims.into()])
+        .on_conflict(
+            OnConflict::columns([
+                AiReembedRunIden::EmbeddingModel,
+                AiReembedRunIden::EmbeddingDims,
+            ])
+            .value(AiReembedRunIden::StartedAt, Expr::cust("NOW()"))
+            .value(AiReembedRunIden::UpdatedAt, Expr::cust("NOW()"))
+            .value(AiReembedRunIden::FinishedAt, Expr::cust("NULL"))
+            .action_and_where(
+                Expr::col((AiReembedRunIden::Table, AiReembedRunIden::FinishedAt))
+                    .is_not_null()
+                    .or(
+                        Expr::col((AiReembedRunIden::Table, AiReembedRunIden::UpdatedAt))
+                            .lt(abandoned_before),
+                    ),
+            )
+            .to_owned(),
+        )
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+/// Marks a batch of the model's re-embed run as done, and the run as finished when
+/// `finished`.
+#[macros::named_query]
+pub fn update_reembed_run(model: &str, dims: i32, finished: bool) -> DbQueryWithValues {
+    let mut query = Query::update();
+    query
+        .table(AiReembedRunIden::Table)
+        .value(AiReembedRunIden::UpdatedAt, Expr::cust("NOW()"))
+        .and_where(Expr::col(AiReembedRunIden::EmbeddingModel).eq(model))
+        .and_where(Expr::col(AiReembedRunIden::EmbeddingDims).eq(dims));
+    if finished {
+        query.value(AiReembedRunIden::FinishedAt, Expr::cust("NOW()"));
+    }
+    query.build_sqlx(PostgresQueryBuilder).into()
+}
+
+#[macros::named_query]
+pub fn get_stale_category_embeddings(
+    model: &str,
+    dims: i32,
+    after: Option<i32>,
+    limit: u64,
+) -> DbQueryWithValues {
+    let mut query = Query::select();
+    query
+        .column((
+            TransactionCategoriesIden::Table,
+            TransactionCategoriesIden::Id,
+        ))
+        .column((
+            TransactionCategoriesIden::Table,
+            TransactionCategoriesIden::Category,
+        ))
+        .column((
+            TransactionCategoryTypeIden::Table,
+            TransactionCategoryTypeIden::CategoryTypeName,
+        ))
+        .from(TransactionCategoriesIden::Table)
+        .inner_join(
+            TransactionCategoryTypeIden::Table,
+            Expr::col((
+                TransactionCategoryTypeIden::Table,
+                TransactionCategoryTypeIden::Id,
+            ))
+            .equals((
+                TransactionCategoriesIden::Table,
+                TransactionCategoriesIden::CategoryType,
+            )),
+        )
+        .and_where(
+            same_embedding_model_as(
+                (
+                    TransactionCategoriesIden::Table,
+                    TransactionCategoriesIden::EmbeddingModel,
+                ),
+                (
+                    TransactionCategoriesIden::Table,
+                    TransactionCategoriesIden::EmbeddingDims,
+                ),
+                model,
+                dims,
+            )
+            .not(),
+        )
+        .order_by(
+            (
+                TransactionCategoriesIden::Table,
+                TransactionCategoriesIden::Id,
+            ),
+            Order::Asc,
+        )
+        .limit(limit);
+
+    if let Some(after) = after {
+        query.and_where(
+            Expr::col((
+                TransactionCategoriesIden::Table,
+                TransactionCategoriesIden::Id,
+            ))
+            .gt(after),
+        );
+    }
+
+    query.build_sqlx(PostgresQueryBuilder).into()
+}
+
+/// Transactions still in the import category, newest first, with their summed amount
+/// and first asset and account name.
+#[macros::named_query]
+pub fn get_uncategorized_transactions(
+    params: &UncategorizedTransactionsParams,
+) -> DbQueryWithValues {
+    let in_category = Query::select()
+        .column((EntryIden::Table, EntryIden::TransactionId))
+        .from(EntryIden::Table)
+        .and_where(Expr::col((EntryIden::Table, EntryIden::CategoryId)).eq(params.category_id))
+        .to_owned();
+
+    let mut query = Query::select();
+    query
+        .expr_as(
+            Expr::col((TransactionIden::Table, TransactionIden::Id)),
+            Alias::new("transaction_id"),
+        )
+        .column((
+            TransactionDescriptionsIden::Table,
+            TransactionDescriptionsIden::Description,
+        ))
+        .column((TransactionIden::Table, TransactionIden::DateTransacted))
+        .expr_as(
+            Expr::cust("SUM(\"entry\".\"quantity\")"),
+            Alias::new("quantity"),
+        )
+        .expr_as(
+            Expr::cust("(array_agg(DISTINCT \"assets\".\"asset_name\"))[1]"),
+            Alias::new("asset_name"),
+        )
+        .expr_as(
+            Expr::cust("(array_agg(DISTINCT \"account\".\"account_name\"))[1]"),
+            Alias::new("account_name"),
+        )
+        .from(TransactionIden::Table)
+        .inner_join(
+            TransactionDescriptionsIden::Table,
+            Expr::col((
+                TransactionDescriptionsIden::Table,
+                TransactionDescriptionsIden::TransactionId,
+            ))
+            .equals((TransactionIden::Table, TransactionIden::Id)),
+        )
+        .inner_join(
+            EntryIden::Table,
+            Expr::col((EntryIden::Table, EntryIden::TransactionId))
+                .equals((TransactionIden::Table, TransactionIden::Id)),
+        )
+        .inner_join(
+            AssetsIden::Table,
+            Expr::col((AssetsIden::Table, AssetsIden::Id))
+                .equals((EntryIden::Table, EntryIden::AssetId)),
+        )
+        .inner_join(
+            AccountIden::Table,
+            Expr::col((AccountIden::Table, AccountIden::Id))
+                .equals((EntryIden::Table, EntryIden::AccountId)),
+        )
+        .and_where(Expr::col((TransactionIden::Table, TransactionIden::UserId)).eq(params.user_id))
+        .and_where(Expr::col((TransactionIden::Table, TransactionIden::Visibility)).ne("hidden"))
+        .and_where(
+            Expr::col((
+                TransactionDescriptionsIden::Table,
+                TransactionDescriptionsIden::Description,
+            ))
+            .ne(""),
+        )
+        .and_where(
+            Expr::col((TransactionIden::Table, TransactionIden::Id)).in_subquery(in_category),
+        )
+        .group_by_col((TransactionIden::Table, TransactionIden::Id))
+        .group_by_col((
+            TransactionDescriptionsIden::Table,
+            TransactionDescriptionsIden::Description,
+        ))
+        .group_by_col((TransactionIden::Table, TransactionIden::DateTransacted))
+        .order_by(
+            (TransactionIden::Table, TransactionIden::DateTransacted),
+            Order::Desc,
+        )
+        .limit(params.limit);
+
+    if let Some(binding_id) = params.binding_id {
+        let imported = Query::select()
+            .column((
+                ConnectorTransactionIden::Table,
+                ConnectorTransactionIden::TransactionId,
+            ))
+            .from(ConnectorTransactionIden::Table)
+            .and_where(
+                Expr::col((
+                    ConnectorTransactionIden::Table,
+                    ConnectorTransactionIden::BindingId,
+                ))
+                .eq(binding_id),
+            )
+            .and_where(
+                Expr::col((
+                    ConnectorTransactionIden::Table,
+                    ConnectorTransactionIden::TransactionId,
+                ))
+                .is_not_null(),
+            )
+            .to_owned();
+        query.and_where(
+            Expr::col((TransactionIden::Table, TransactionIden::Id)).in_subquery(imported),
+        );
+    }
+    if let Some(ref transaction_ids) = params.transaction_ids {
+        query.and_where(
+            Expr::col((TransactionIden::Table, TransactionIden::Id))
+                .is_in(transaction_ids.iter().copied()),
+        );
+    }
+    if params.skip_suggested {
+        let suggested = Query::select()
+            .column((
+                TransactionCategorySuggestionIden::Table,
+                TransactionCategorySuggestionIden::TransactionId,
+            ))
+            .from(TransactionCategorySuggestionIden::Table)
+            .and_where(
+                Expr::col((
+                    TransactionCategorySuggestionIden::Table,
+                    TransactionCategorySuggestionIden::UserId,
+                ))
+                .eq(params.user_id),
+            )
+            .to_owned();
+        query.and_where(
+            Expr::col((TransactionIden::Table, TransactionIden::Id)).not_in_subquery(suggested),
+        );
+    }
+
+    query.build_sqlx(PostgresQueryBuilder).into()
+}
+
+/// The category of each of the user's given transactions, skipping those still in
+/// `excluded_category_id`. A transaction with entries in several categories reports the
+/// category of its first entry.
+#[macros::named_query]
+pub fn get_transaction_categories(
+    user_id: Uuid,
+    transaction_ids: Vec<Uuid>,
+    excluded_category_id: i32,
+) -> DbQueryWithValues {
+    Query::select()
+        .distinct_on([(EntryIden::Table, EntryIden::TransactionId)])
+        .column((EntryIden::Table, EntryIden::TransactionId))
+        .column((EntryIden::Table, EntryIden::CategoryId))
+        .column((
+            TransactionCategoriesIden::Table,
+            TransactionCategoriesIden::Category,
+        ))
+        .from(EntryIden::Table)
+        .inner_join(
+            TransactionIden::Table,
+            Expr::col((TransactionIden::Table, TransactionIden::Id))
+                .equals((EntryIden::Table, EntryIden::TransactionId)),
+        )
+        .inner_join(
+            TransactionCategoriesIden::Table,
+            Expr::col((
+                TransactionCategoriesIden::Table,
+                TransactionCategoriesIden::Id,
+            ))
+            .equals((EntryIden::Table, EntryIden::CategoryId)),
+        )
+        .and_where(Expr::col((TransactionIden::Table, TransactionIden::UserId)).eq(user_id))
+        .and_where(Expr::col((EntryIden::Table, EntryIden::TransactionId)).is_in(transaction_ids))
+        .and_where(Expr::col((EntryIden::Table, EntryIden::CategoryId)).ne(excluded_category_id))
+        .order_by((EntryIden::Table, EntryIden::TransactionId), Order::Asc)
+        .order_by((EntryIden::Table, EntryIden::Id), Order::Asc)
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn upsert_category_suggestions(models: Vec<AddCategorySuggestionModel>) -> DbQueryWithValues {
+    let mut query = Query::insert()
+        .into_table(TransactionCategorySuggestionIden::Table)
+        .columns(vec![
+            TransactionCategorySuggestionIden::TransactionId,
+            TransactionCategorySuggestionIden::UserId,
+            TransactionCategorySuggestionIden::CategoryId,
+            TransactionCategorySuggestionIden::Confidence,
+            TransactionCategorySuggestionIden::Reason,
+            TransactionCategorySuggestionIden::Model,
+        ])
+        .on_conflict(
+            OnConflict::column(TransactionCategorySuggestionIden::TransactionId)
+                .update_columns([
+                    TransactionCategorySuggestionIden::CategoryId,
+                    TransactionCategorySuggestionIden::Confidence,
+                    TransactionCategorySuggestionIden::Reason,
+                    TransactionCategorySuggestionIden::Model,
+                ])
+                .value(
+                    TransactionCategorySuggestionIden::CreatedAt,
+                    Expr::cust("now()"),
+                )
+                .to_owned(),
+        )
+        .to_owned();
+    for model in models {
+        query.values_panic([
+            model.transaction_id.into(),
+            model.user_id.into(),
+            model.category_id.into(),
+            model.confidence.into(),
+            model.reason.into(),
+            model.model.into(),
+        ]);
+    }
+    query.build_sqlx(PostgresQueryBuilder).into()
+}
+
+/// The user's stored suggestions for transactions that are still in the import
+/// category, optionally narrowed to `transaction_ids`.
+#[macros::named_query]
+pub fn get_category_suggestions(
+    user_id: Uuid,
+    category_id: i32,
+    transaction_ids: Option<Vec<Uuid>>,
+) -> DbQueryWithValues {
+    let in_category = Query::select()
+        .column((EntryIden::Table, EntryIden::TransactionId))
+        .from(EntryIden::Table)
+        .and_where(Expr::col((EntryIden::Table, EntryIden::CategoryId)).eq(category_id))
+        .to_owned();
+
+    let mut query = Query::select();
+    query
+        .columns([
+            TransactionCategorySuggestionIden::TransactionId,
+            TransactionCategorySuggestionIden::CategoryId,
+            TransactionCategorySuggestionIden::Confidence,
+            TransactionCategorySuggestionIden::Reason,
+            TransactionCategorySuggestionIden::Model,
+            TransactionCategorySuggestionIden::CreatedAt,
+        ])
+        .from(TransactionCategorySuggestionIden::Table)
+        .and_where(Expr::col(TransactionCategorySuggestionIden::UserId).eq(user_id))
+        .and_where(
+            Expr::col(TransactionCategorySuggestionIden::TransactionId).in_subquery(in_category),
+        )
+        .order_by(TransactionCategorySuggestionIden::Confidence, Order::Desc)
+        .order_by(TransactionCategorySuggestionIden::TransactionId, Order::Asc);
+
+    if let Some(transaction_ids) = transaction_ids {
+        query.and_where(
+            Expr::col(TransactionCategorySuggestionIden::TransactionId).is_in(transaction_ids),
+        );
+    }
+
+    query.build_sqlx(PostgresQueryBuilder).into()
+}
+
+/// Cosine distance between a stored vector and the query. The column is cast to the
+/// query's length, which is what the partial HNSW indexes are built over.
+fn cosine_distance(column: impl IntoColumnRef, embedding: &EmbeddingSearch) -> SimpleExpr {
+    Expr::cust_with_expr(
+        format!("$1::vector({})", embedding.dims()),
+        Expr::col(column),
+    )
+    .binary(
+        PgBinOper::CosineDistance,
+        Expr::val(embedding.vector.clone()),
+    )
+}
+
+/// Whether the stored vector came from the model and length the query was embedded with.
+fn same_embedding_model(
+    model_column: impl IntoColumnRef,
+    dims_column: impl IntoColumnRef,
+    embedding: &EmbeddingSearch,
+) -> SimpleExpr {
+    same_embedding_model_as(
+        model_column,
+        dims_column,
+        &embedding.model,
+        embedding.dims(),
+    )
+}
+
+/// False, never NULL, for rows not embedded yet, so its negation selects them too.
+fn same_embedding_model_as(
+    model_column: impl IntoColumnRef,
+    dims_column: impl IntoColumnRef,
+    model: &str,
+    dims: i32,
+) -> SimpleExpr {
+    let model_column = model_column.into_column_ref();
+    let dims_column = dims_column.into_column_ref();
+    Expr::col(model_column.clone())
+        .is_not_null()
+        .and(Expr::col(dims_column.clone()).is_not_null())
+        .and(Expr::col(model_column).eq(model))
+        .and(Expr::col(dims_column).eq(dims))
+}
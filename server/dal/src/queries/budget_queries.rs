@@ -0,0 +1,169 @@
+use sea_query::{Expr, ExprTrait, JoinType, Order, PostgresQueryBuilder, Query};
+use sea_query_sqlx::SqlxBinder;
+use sqlx::types::Uuid;
+use time::Duration;
+
+use crate::{
+    idens::{
+        budget_idens::{BudgetIden, BudgetSpendingIden},
+        entries_idens::EntryIden,
+        transaction_idens::{TransactionCategoriesIden, TransactionIden},
+        CustomFunc,
+    },
+    models::budget_models::{AddBudgetModel, UpdateBudgetModel},
+    query_params::get_budgets_params::{
+        GetBudgetSpendingParams, GetBudgetsParams, GetBudgetsParamsSearchType,
+    },
+};
+
+use super::DbQueryWithValues;
+
+#[macros::named_query]
+pub fn get_budgets(params: GetBudgetsParams) -> DbQueryWithValues {
+    let mut query = Query::select();
+
+    query
+        .columns([
+            BudgetIden::Id,
+            BudgetIden::UserId,
+            BudgetIden::CategoryId,
+            BudgetIden::CategoryTypeId,
+            BudgetIden::Amount,
+            BudgetIden::Period,
+            BudgetIden::Rollover,
+            BudgetIden::StartDate,
+            BudgetIden::CreatedAt,
+            BudgetIden::UpdatedAt,
+        ])
+        .from(BudgetIden::Table)
+        .and_where(Expr::col(BudgetIden::UserId).eq(params.user_id));
+
+    match params.search_type {
+        GetBudgetsParamsSearchType::All => {}
+        GetBudgetsParamsSearchType::ById(id) => {
+            query.and_where(Expr::col(BudgetIden::Id).eq(id));
+        }
+    }
+
+    query
+        .order_by(BudgetIden::CreatedAt, Order::Asc)
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn insert_budget(model: AddBudgetModel) -> DbQueryWithValues {
+    Query::insert()
+        .into_table(BudgetIden::Table)
+        .columns([
+            BudgetIden::UserId,
+            BudgetIden::CategoryId,
+            BudgetIden::CategoryTypeId,
+            BudgetIden::Amount,
+            BudgetIden::Period,
+            BudgetIden::Rollover,
+            BudgetIden::StartDate,
+        ])
+        .values_panic([
+            model.user_id.into(),
+            model.category_id.into(),
+            model.category_type_id.into(),
+            model.amount.into(),
+            model.period.into(),
+            model.rollover.into(),
+            model.start_date.into(),
+        ])
+        .returning_all()
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn update_budget(id: Uuid, user_id: Uuid, updates: UpdateBudgetModel) -> DbQueryWithValues {
+    Query::update()
+        .table(BudgetIden::Table)
+        .value(BudgetIden::CategoryId, updates.category_id)
+        .value(BudgetIden::CategoryTypeId, updates.category_type_id)
+        .value(BudgetIden::Amount, updates.amount)
+        .value(BudgetIden::Period, updates.period)
+        .value(BudgetIden::Rollover, updates.rollover)
+        .value(BudgetIden::StartDate, updates.start_date)
+        .value(BudgetIden::UpdatedAt, Expr::cust("NOW()"))
+        .and_where(Expr::col(BudgetIden::Id).eq(id))
+        .and_where(Expr::col(BudgetIden::UserId).eq(user_id))
+        .returning_all()
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn delete_budget(id: Uuid, user_id: Uuid) -> DbQueryWithValues {
+    Query::delete()
+        .from_table(BudgetIden::Table)
+        .and_where(Expr::col(BudgetIden::Id).eq(id))
+        .and_where(Expr::col(BudgetIden::UserId).eq(user_id))
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+/// Sums entry quantities per asset and day for the transactions a budget tracks.
+/// Ghost and hidden transactions are left out, matching what the user sees in
+/// their transaction list.
+#[macros::named_query]
+pub fn get_budget_spending(params: GetBudgetSpendingParams) -> DbQueryWithValues {
+    let day = CustomFunc::date_bin_col(
+        Duration::days(1),
+        (TransactionIden::Table, TransactionIden::DateTransacted),
+    );
+
+    let mut query = Query::select();
+    query
+        .column((EntryIden::Table, EntryIden::AssetId))
+        .expr_as(day, BudgetSpendingIden::Day)
+        .expr_as(
+            Expr::sum(Expr::col((EntryIden::Table, EntryIden::Quantity))),
+            BudgetSpendingIden::Quantity,
+        )
+        .from(EntryIden::Table)
+        .join(
+            JoinType::Join,
+            TransactionIden::Table,
+            Expr::col((EntryIden::Table, EntryIden::TransactionId))
+                .equals((TransactionIden::Table, TransactionIden::Id)),
+        )
+        .join(
+            JoinType::Join,
+            TransactionCategoriesIden::Table,
+            Expr::col((EntryIden::Table, EntryIden::CategoryId)).equals((
+                TransactionCategoriesIden::Table,
+                TransactionCategoriesIden::Id,
+            )),
+        )
+        .and_where(Expr::col((TransactionIden::Table, TransactionIden::UserId)).eq(params.user_id))
+        .and_where(Expr::col((TransactionIden::Table, TransactionIden::Visibility)).eq("default"))
+        .and_where(
+            Expr::col((TransactionIden::Table, TransactionIden::DateTransacted)).gte(params.start),
+        )
+        .and_where(
+            Expr::col((TransactionIden::Table, TransactionIden::DateTransacted)).lt(params.end),
+        );
+
+    if let Some(category_id) = params.category_id {
+        query.and_where(Expr::col((EntryIden::Table, EntryIden::CategoryId)).eq(category_id));
+    }
+    if let Some(category_type_id) = params.category_type_id {
+        query.and_where(
+            Expr::col((
+                TransactionCategoriesIden::Table,
+                TransactionCategoriesIden::CategoryType,
+            ))
+            .eq(category_type_id),
+        );
+    }
+
+    query
+        .group_by_col((EntryIden::Table, EntryIden::AssetId))
+        .group_by_col(BudgetSpendingIden::Day)
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
@@ -1,22 +1,38 @@
-use sea_query::{Alias, Expr, ExprTrait, OnConflict, PostgresQueryBuilder, Query, SelectStatement};
+use sea_query::extension::postgres::PgBinOper;
+use sea_query::{
+    Alias, ColumnRef, Expr, ExprTrait, IntoColumnRef, JoinType, OnConflict, PostgresQueryBuilder,
+    Query, QueryStatementBuilder, SelectStatement, SimpleExpr,
+};
 use sea_query_sqlx::SqlxBinder;
 use sqlx::types::Uuid;
+use time::OffsetDateTime;
 
 use super::DbQueryWithValues;
 use crate::{
-    idens::connector_idens::{
-        ConnectorBindingIden, ConnectorConnectionIden, ConnectorProviderAccountIden,
-        ConnectorProviderIden, ConnectorRawPageIden, ConnectorTransactionIden,
+    idens::{
+        asset_idens::AssetsIden,
+        connector_idens::{
+            ConnectorBalanceSnapshotIden, ConnectorBindingIden, ConnectorConnectionIden,
+            ConnectorDuplicateCandidateIden, ConnectorImportIssueIden,
+            ConnectorProviderAccountIden, ConnectorProviderIden, ConnectorRawPageIden,
+            ConnectorTransactionIden,
+        },
+        entries_idens::EntryIden,
+        transaction_idens::{
+            TransactionCategoriesIden, TransactionDescriptionsIden, TransactionIden,
+        },
     },
     models::connector_models::{
-        AddConnectorBindingModel, AddConnectorConnectionModel, AddConnectorProviderAccountModel,
+        AddConnectorBalanceSnapshotModel, AddConnectorBindingModel, AddConnectorConnectionModel,
+        AddConnectorDuplicateCandidateModel, AddConnectorProviderAccountModel,
         AddConnectorRawPageModel, AddConnectorTransactionModel,
-        UpdateProviderAccountSyncResultModel,
+        UpdateProviderAccountSyncResultModel, UpsertConnectorImportIssueModel,
     },
     query_params::connector_params::{
         GetConnectorBindingsParams, GetConnectorBindingsParamsSearchType,
-        GetConnectorConnectionsParams, GetConnectorConnectionsParamsSearchType, GetRawPagesParams,
-        GetRawPagesParamsSearchType,
+        GetConnectorConnectionsParams, GetConnectorConnectionsParamsSearchType,
+        GetConnectorImportIssuesParams, GetConnectorImportIssuesParamsSearchType,
+        GetRawPagesParams, GetRawPagesParamsSearchType,
     },
 };
 
@@ -604,6 +620,7 @@ pub fn insert_connector_transactions(
             ConnectorTransactionIden::TransactionId,
             ConnectorTransactionIden::ExternalId,
             ConnectorTransactionIden::ExternalHash,
+            ConnectorTransactionIden::Counterparty,
         ])
         .to_owned();
     for model in models {
@@ -612,6 +629,7 @@ pub fn insert_connector_transactions(
             model.transaction_id.into(),
             model.external_id.into(),
             model.external_hash.into(),
+            model.counterparty.into(),
         ]);
     }
     query.build_sqlx(PostgresQueryBuilder).into()
@@ -732,3 +750,903 @@ pub fn get_connector_raw_pages(params: GetRawPagesParams) -> DbQueryWithValues {
 
     query.build_sqlx(PostgresQueryBuilder).into()
 }
+
+/// The latest page of the provider account archived before `page_id`. Rewinding a
+/// binding's projection checkpoint to it re-projects `page_id` and everything after.
+#[macros::named_query]
+pub fn get_previous_raw_page_id(provider_account_ref: Uuid, page_id: Uuid) -> DbQueryWithValues {
+    Query::select()
+        .expr(Expr::col((ConnectorRawPageIden::Table, ConnectorRawPageIden::Id)).max())
+        .from(ConnectorRawPageIden::Table)
+        .and_where(
+            Expr::col((
+                ConnectorRawPageIden::Table,
+                ConnectorRawPageIden::ProviderAccountId,
+            ))
+            .eq(provider_account_ref),
+        )
+        .and_where(Expr::col((ConnectorRawPageIden::Table, ConnectorRawPageIden::Id)).lt(page_id))
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn get_connector_import_issues(params: GetConnectorImportIssuesParams) -> DbQueryWithValues {
+    let mut query = Query::select();
+    query
+        .columns([
+            (
+                ConnectorImportIssueIden::Table,
+                ConnectorImportIssueIden::Id,
+            ),
+            (
+                ConnectorImportIssueIden::Table,
+                ConnectorImportIssueIden::BindingId,
+            ),
+            (
+                ConnectorImportIssueIden::Table,
+                ConnectorImportIssueIden::PageId,
+            ),
+            (
+                ConnectorImportIssueIden::Table,
+                ConnectorImportIssueIden::ExternalId,
+            ),
+            (
+                ConnectorImportIssueIden::Table,
+                ConnectorImportIssueIden::Stage,
+            ),
+            (
+                ConnectorImportIssueIden::Table,
+                ConnectorImportIssueIden::Reason,
+            ),
+            (
+                ConnectorImportIssueIden::Table,
+                ConnectorImportIssueIden::DateTransacted,
+            ),
+            (
+                ConnectorImportIssueIden::Table,
+                ConnectorImportIssueIden::Amount,
+            ),
+            (
+                ConnectorImportIssueIden::Table,
+                ConnectorImportIssueIden::Currency,
+            ),
+            (
+                ConnectorImportIssueIden::Table,
+                ConnectorImportIssueIden::Description,
+            ),
+            (
+                ConnectorImportIssueIden::Table,
+                ConnectorImportIssueIden::AssetIdentifier,
+            ),
+            (
+                ConnectorImportIssueIden::Table,
+                ConnectorImportIssueIden::Quantity,
+            ),
+            (
+                ConnectorImportIssueIden::Table,
+                ConnectorImportIssueIden::Dismissed,
+            ),
+            (
+                ConnectorImportIssueIden::Table,
+                ConnectorImportIssueIden::FirstSeenAt,
+            ),
+            (
+                ConnectorImportIssueIden::Table,
+                ConnectorImportIssueIden::LastSeenAt,
+            ),
+        ])
+        .from(ConnectorImportIssueIden::Table)
+        .and_where(
+            Expr::col((
+                ConnectorImportIssueIden::Table,
+                ConnectorImportIssueIden::BindingId,
+            ))
+            .in_subquery(owned_binding_ids_subquery(params.user_id)),
+        )
+        .and_where(
+            Expr::col((
+                ConnectorImportIssueIden::Table,
+                ConnectorImportIssueIden::Dismissed,
+            ))
+            .eq(false),
+        )
+        .order_by(
+            (
+                ConnectorImportIssueIden::Table,
+                ConnectorImportIssueIden::DateTransacted,
+            ),
+            sea_query::Order::Desc,
+        )
+        .order_by(
+            (
+                ConnectorImportIssueIden::Table,
+                ConnectorImportIssueIden::ExternalId,
+            ),
+            sea_query::Order::Asc,
+        );
+
+    match params.search_type {
+        GetConnectorImportIssuesParamsSearchType::ByBinding(binding_id) => {
+            query.and_where(
+                Expr::col((
+                    ConnectorImportIssueIden::Table,
+                    ConnectorImportIssueIden::BindingId,
+                ))
+                .eq(binding_id),
+            );
+        }
+        GetConnectorImportIssuesParamsSearchType::ById(id) => {
+            query.and_where(
+                Expr::col((
+                    ConnectorImportIssueIden::Table,
+                    ConnectorImportIssueIden::Id,
+                ))
+                .eq(id),
+            );
+        }
+        GetConnectorImportIssuesParamsSearchType::UnresolvedByIdentifier(identifier) => {
+            query
+                .and_where(
+                    Expr::col((
+                        ConnectorImportIssueIden::Table,
+                        ConnectorImportIssueIden::Stage,
+                    ))
+                    .eq("unresolved"),
+                )
+                .and_where(
+                    Expr::col((
+                        ConnectorImportIssueIden::Table,
+                        ConnectorImportIssueIden::AssetIdentifier,
+                    ))
+                    .eq(identifier.clone())
+                    .or(Expr::col((
+                        ConnectorImportIssueIden::Table,
+                        ConnectorImportIssueIden::Currency,
+                    ))
+                    .eq(identifier)),
+                );
+        }
+    }
+
+    query.build_sqlx(PostgresQueryBuilder).into()
+}
+
+#[macros::named_query]
+pub fn upsert_connector_import_issues(
+    models: Vec<UpsertConnectorImportIssueModel>,
+) -> DbQueryWithValues {
+    let mut query = Query::insert()
+        .into_table(ConnectorImportIssueIden::Table)
+        .columns(vec![
+            ConnectorImportIssueIden::BindingId,
+            ConnectorImportIssueIden::PageId,
+            ConnectorImportIssueIden::ExternalId,
+            ConnectorImportIssueIden::Stage,
+            ConnectorImportIssueIden::Reason,
+            ConnectorImportIssueIden::DateTransacted,
+            ConnectorImportIssueIden::Amount,
+            ConnectorImportIssueIden::Currency,
+            ConnectorImportIssueIden::Description,
+            ConnectorImportIssueIden::AssetIdentifier,
+            ConnectorImportIssueIden::Quantity,
+        ])
+        .on_conflict(
+            OnConflict::columns([
+                ConnectorImportIssueIden::BindingId,
+                ConnectorImportIssueIden::ExternalId,
+            ])
+            .update_columns([
+                ConnectorImportIssueIden::PageId,
+                ConnectorImportIssueIden::Stage,
+                ConnectorImportIssueIden::Reason,
+                ConnectorImportIssueIden::DateTransacted,
+                ConnectorImportIssueIden::Amount,
+                ConnectorImportIssueIden::Currency,
+                ConnectorImportIssueIden::Description,
+                ConnectorImportIssueIden::AssetIdentifier,
+                ConnectorImportIssueIden::Quantity,
+            ])
+            .value(ConnectorImportIssueIden::LastSeenAt, Expr::cust("now()"))
+            .to_owned(),
+        )
+        .to_owned();
+    for model in models {
+        query.values_panic([
+            model.binding_id.into(),
+            model.page_id.into(),
+            model.external_id.into(),
+            model.stage.into(),
+            model.reason.into(),
+            model.date_transacted.into(),
+            model.amount.into(),
+            model.currency.into(),
+            model.description.into(),
+            model.asset_identifier.into(),
+            model.quantity.into(),
+        ]);
+    }
+    query.build_sqlx(PostgresQueryBuilder).into()
+}
+
+/// Clears the issues of items that have since been imported.
+#[macros::named_query]
+pub fn delete_connector_import_issues_by_external_ids(
+    binding_id: Uuid,
+    external_ids: Vec<String>,
+) -> DbQueryWithValues {
+    Query::delete()
+        .from_table(ConnectorImportIssueIden::Table)
+        .and_where(
+            Expr::col((
+                ConnectorImportIssueIden::Table,
+                ConnectorImportIssueIden::BindingId,
+            ))
+            .eq(binding_id),
+        )
+        .and_where(
+            Expr::col((
+                ConnectorImportIssueIden::Table,
+                ConnectorImportIssueIden::ExternalId,
+            ))
+            .is_in(external_ids),
+        )
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn dismiss_connector_import_issue(user_id: Uuid, id: Uuid) -> DbQueryWithValues {
+    Query::update()
+        .table(ConnectorImportIssueIden::Table)
+        .value(ConnectorImportIssueIden::Dismissed, true)
+        .and_where(
+            Expr::col((
+                ConnectorImportIssueIden::Table,
+                ConnectorImportIssueIden::BindingId,
+            ))
+            .in_subquery(owned_binding_ids_subquery(user_id)),
+        )
+        .and_where(
+            Expr::col((
+                ConnectorImportIssueIden::Table,
+                ConnectorImportIssueIden::Id,
+            ))
+            .eq(id),
+        )
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+/// Ghost transactions linked to one of the user's bindings, newest first. Narrowed to a
+/// binding or to specific transactions when given.
+#[macros::named_query]
+pub fn get_pending_review_transactions(
+    user_id: Uuid,
+    binding_id: Option<Uuid>,
+    transaction_ids: Option<Vec<Uuid>>,
+) -> DbQueryWithValues {
+    let mut query = Query::select();
+    query
+        .column((
+            ConnectorTransactionIden::Table,
+            ConnectorTransactionIden::TransactionId,
+        ))
+        .column((
+            ConnectorTransactionIden::Table,
+            ConnectorTransactionIden::BindingId,
+        ))
+        .column((
+            ConnectorTransactionIden::Table,
+            ConnectorTransactionIden::ExternalId,
+        ))
+        .column((
+            ConnectorTransactionIden::Table,
+            ConnectorTransactionIden::ImportedAt,
+        ))
+        .from(ConnectorTransactionIden::Table)
+        .inner_join(
+            TransactionIden::Table,
+            Expr::col((TransactionIden::Table, TransactionIden::Id)).equals((
+                ConnectorTransactionIden::Table,
+                ConnectorTransactionIden::TransactionId,
+            )),
+        )
+        .and_where(Expr::col((TransactionIden::Table, TransactionIden::UserId)).eq(user_id))
+        .and_where(Expr::col((TransactionIden::Table, TransactionIden::Visibility)).eq("ghost"))
+        .and_where(
+            Expr::col((
+                ConnectorTransactionIden::Table,
+                ConnectorTransactionIden::BindingId,
+            ))
+            .in_subquery(owned_binding_ids_subquery(user_id)),
+        )
+        .order_by(
+            (TransactionIden::Table, TransactionIden::DateTransacted),
+            sea_query::Order::Desc,
+        )
+        .order_by(
+            (TransactionIden::Table, TransactionIden::Id),
+            sea_query::Order::Asc,
+        );
+
+    if let Some(binding_id) = binding_id {
+        query.and_where(
+            Expr::col((
+                ConnectorTransactionIden::Table,
+                ConnectorTransactionIden::BindingId,
+            ))
+            .eq(binding_id),
+        );
+    }
+    if let Some(transaction_ids) = transaction_ids {
+        query.and_where(
+            Expr::col((
+                ConnectorTransactionIden::Table,
+                ConnectorTransactionIden::TransactionId,
+            ))
+            .is_in(transaction_ids),
+        );
+    }
+
+    query.build_sqlx(PostgresQueryBuilder).into()
+}
+
+/// Suggestions for ghost transactions from their description embedding:
+///
+/// ```sql
+/// SELECT d.transaction_id, similar.*, nearest_category.*, nearest_asset.*
+/// FROM transaction_descriptions d
+/// LEFT JOIN LATERAL (
+///     -- closest reviewed transaction: its group and first categorised entry
+///     SELECT t.id, t.group_id, (SELECT category_id FROM entry ...) ...
+///     WHERE sd.embedding_model = d.embedding_model AND sd.embedding_dims = d.embedding_dims
+///     ORDER BY sd.embedding <=> d.embedding LIMIT 1
+/// ) similar ON TRUE
+/// LEFT JOIN LATERAL (... transaction_categories ORDER BY embedding <=> d.embedding LIMIT 1) ON TRUE
+/// LEFT JOIN LATERAL (... assets ORDER BY embedding <=> d.embedding LIMIT 1) ON TRUE
+/// WHERE d.transaction_id IN (...) AND d.embedding IS NOT NULL
+/// ```
+///
+/// Every neighbour is looked up among vectors of the ghost's own embedding model.
+#[macros::named_query]
+pub fn get_review_suggestions(user_id: Uuid, transaction_ids: Vec<Uuid>) -> DbQueryWithValues {
+    let similar_transaction = Alias::new("similar_transaction");
+    let similar_description = Alias::new("similar_description");
+    let ghost_embedding = || {
+        Expr::col((
+            TransactionDescriptionsIden::Table,
+            TransactionDescriptionsIden::Embedding,
+        ))
+    };
+    let same_model_as_ghost = |model: ColumnRef, dims: ColumnRef| {
+        Expr::col(model)
+            .equals((
+                TransactionDescriptionsIden::Table,
+                TransactionDescriptionsIden::EmbeddingModel,
+            ))
+            .and(Expr::col(dims).equals((
+                TransactionDescriptionsIden::Table,
+                TransactionDescriptionsIden::EmbeddingDims,
+            )))
+    };
+
+    let similar_category = Query::select()
+        .column((EntryIden::Table, EntryIden::CategoryId))
+        .from(EntryIden::Table)
+        .and_where(
+            Expr::col((EntryIden::Table, EntryIden::TransactionId))
+                .equals((similar_transaction.clone(), TransactionIden::Id)),
+        )
+        .and_where(Expr::col((EntryIden::Table, EntryIden::CategoryId)).is_not_null())
+        .order_by((EntryIden::Table, EntryIden::Id), sea_query::Order::Asc)
+        .limit(1)
+        .to_owned();
+
+    let similar = Query::select()
+        .expr_as(
+            Expr::col((similar_transaction.clone(), TransactionIden::Id)),
+            Alias::new("similar_transaction_id"),
+        )
+        .expr_as(
+            Expr::col((similar_transaction.clone(), TransactionIden::GroupId)),
+            Alias::new("similar_group_id"),
+        )
+        .expr_as(
+            SimpleExpr::SubQuery(None, Box::new(similar_category.into_sub_query_statement())),
+            Alias::new("similar_category_id"),
+        )
+        .from_as(TransactionIden::Table, similar_transaction.clone())
+        .join_as(
+            JoinType::InnerJoin,
+            TransactionDescriptionsIden::Table,
+            similar_description.clone(),
+            Expr::col((
+                similar_description.clone(),
+                TransactionDescriptionsIden::TransactionId,
+            ))
+            .equals((similar_transaction.clone(), TransactionIden::Id)),
+        )
+        .and_where(Expr::col((similar_transaction.clone(), TransactionIden::UserId)).eq(user_id))
+        .and_where(
+            Expr::col((similar_transaction.clone(), TransactionIden::Visibility)).eq("default"),
+        )
+        .and_where(same_model_as_ghost(
+            (
+                similar_description.clone(),
+                TransactionDescriptionsIden::EmbeddingModel,
+            )
+                .into_column_ref(),
+            (
+                similar_description.clone(),
+                TransactionDescriptionsIden::EmbeddingDims,
+            )
+                .into_column_ref(),
+        ))
+        .order_by_expr(
+            Expr::col((similar_description, TransactionDescriptionsIden::Embedding))
+                .binary(PgBinOper::CosineDistance, ghost_embedding()),
+            sea_query::Order::Asc,
+        )
+        .limit(1)
+        .to_owned();
+
+    let nearest_category = Query::select()
+        .expr_as(
+            Expr::col((
+                TransactionCategoriesIden::Table,
+                TransactionCategoriesIden::Id,
+            )),
+            Alias::new("nearest_category_id"),
+        )
+        .from(TransactionCategoriesIden::Table)
+        .and_where(
+            Expr::col((
+                TransactionCategoriesIden::Table,
+                TransactionCategoriesIden::UserId,
+            ))
+            .eq(user_id)
+            .or(Expr::col((
+                TransactionCategoriesIden::Table,
+                TransactionCategoriesIden::UserId,
+            ))
+            .is_null()),
+        )
+        .and_where(same_model_as_ghost(
+            (
+                TransactionCategoriesIden::Table,
+                TransactionCategoriesIden::EmbeddingModel,
+            )
+                .into_column_ref(),
+            (
+                TransactionCategoriesIden::Table,
+                TransactionCategoriesIden::EmbeddingDims,
+            )
+                .into_column_ref(),
+        ))
+        .order_by_expr(
+            Expr::col((
+                TransactionCategoriesIden::Table,
+                TransactionCategoriesIden::Embedding,
+            ))
+            .binary(PgBinOper::CosineDistance, ghost_embedding()),
+            sea_query::Order::Asc,
+        )
+        .limit(1)
+        .to_owned();
+
+    let nearest_asset = Query::select()
+        .expr_as(
+            Expr::col((AssetsIden::Table, AssetsIden::Id)),
+            Alias::new("nearest_asset_id"),
+        )
+        .from(AssetsIden::Table)
+        .and_where(
+            Expr::col((AssetsIden::Table, AssetsIden::UserId))
+                .eq(user_id)
+                .or(Expr::col((AssetsIden::Table, AssetsIden::UserId)).is_null()),
+        )
+        .and_where(same_model_as_ghost(
+            (AssetsIden::Table, AssetsIden::EmbeddingModel).into_column_ref(),
+            (AssetsIden::Table, AssetsIden::EmbeddingDims).into_column_ref(),
+        ))
+        .order_by_expr(
+            Expr::col((AssetsIden::Table, AssetsIden::Embedding))
+                .binary(PgBinOper::CosineDistance, ghost_embedding()),
+            sea_query::Order::Asc,
+        )
+        .limit(1)
+        .to_owned();
+
+    Query::select()
+        .column((
+            TransactionDescriptionsIden::Table,
+            TransactionDescriptionsIden::TransactionId,
+        ))
+        .column((Alias::new("similar"), Alias::new("similar_transaction_id")))
+        .column((Alias::new("similar"), Alias::new("similar_group_id")))
+        .column((Alias::new("similar"), Alias::new("similar_category_id")))
+        .column((
+            Alias::new("nearest_category"),
+            Alias::new("nearest_category_id"),
+        ))
+        .column((Alias::new("nearest_asset"), Alias::new("nearest_asset_id")))
+        .from(TransactionDescriptionsIden::Table)
+        .join_lateral(
+            JoinType::LeftJoin,
+            similar,
+            Alias::new("similar"),
+            Expr::cust("TRUE"),
+        )
+        .join_lateral(
+            JoinType::LeftJoin,
+            nearest_category,
+            Alias::new("nearest_category"),
+            Expr::cust("TRUE"),
+        )
+        .join_lateral(
+            JoinType::LeftJoin,
+            nearest_asset,
+            Alias::new("nearest_asset"),
+            Expr::cust("TRUE"),
+        )
+        .and_where(
+            Expr::col((
+                TransactionDescriptionsIden::Table,
+                TransactionDescriptionsIden::TransactionId,
+            ))
+            .is_in(transaction_ids),
+        )
+        .and_where(ghost_embedding().is_not_null())
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+/// Points the provider item behind `from_transaction_id` at another transaction and marks
+/// it edited, so later syncs leave that transaction alone.
+#[macros::named_query]
+pub fn relink_connector_transaction(
+    from_transaction_id: Uuid,
+    to_transaction_id: Uuid,
+) -> DbQueryWithValues {
+    Query::update()
+        .table(ConnectorTransactionIden::Table)
+        .value(ConnectorTransactionIden::TransactionId, to_transaction_id)
+        .value(ConnectorTransactionIden::EditedByUser, true)
+        .and_where(
+            Expr::col((
+                ConnectorTransactionIden::Table,
+                ConnectorTransactionIden::TransactionId,
+            ))
+            .eq(from_transaction_id),
+        )
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn get_connector_transactions_by_transaction_ids(
+    transaction_ids: Vec<Uuid>,
+) -> DbQueryWithValues {
+    Query::select()
+        .column((
+            ConnectorTransactionIden::Table,
+            ConnectorTransactionIden::TransactionId,
+        ))
+        .column((
+            ConnectorTransactionIden::Table,
+            ConnectorTransactionIden::ExternalId,
+        ))
+        .column((
+            ConnectorTransactionIden::Table,
+            ConnectorTransactionIden::ExternalHash,
+        ))
+        .column((
+            ConnectorTransactionIden::Table,
+            ConnectorTransactionIden::EditedByUser,
+        ))
+        .from(ConnectorTransactionIden::Table)
+        .and_where(
+            Expr::col((
+                ConnectorTransactionIden::Table,
+                ConnectorTransactionIden::TransactionId,
+            ))
+            .is_in(transaction_ids),
+        )
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+/// Reviewed transactions of the user in the given assets and date range that no provider
+/// item is linked to yet, summed per account and asset. These are the entries a provider
+/// import may turn out to duplicate.
+#[macros::named_query]
+pub fn get_duplicate_candidate_transactions(
+    user_id: Uuid,
+    asset_ids: Vec<i32>,
+    date_from: OffsetDateTime,
+    date_to: OffsetDateTime,
+) -> DbQueryWithValues {
+    Query::select()
+        .expr_as(
+            Expr::col((TransactionIden::Table, TransactionIden::Id)),
+            Alias::new("transaction_id"),
+        )
+        .column((TransactionIden::Table, TransactionIden::DateTransacted))
+        .column((
+            TransactionDescriptionsIden::Table,
+            TransactionDescriptionsIden::Description,
+        ))
+        .column((
+            TransactionDescriptionsIden::Table,
+            TransactionDescriptionsIden::Embedding,
+        ))
+        .column((
+            TransactionDescriptionsIden::Table,
+            TransactionDescriptionsIden::EmbeddingModel,
+        ))
+        .column((EntryIden::Table, EntryIden::AccountId))
+        .column((EntryIden::Table, EntryIden::AssetId))
+        .expr_as(
+            Expr::sum(Expr::col((EntryIden::Table, EntryIden::Quantity))),
+            Alias::new("quantity"),
+        )
+        .from(TransactionIden::Table)
+        .join(
+            JoinType::LeftJoin,
+            TransactionDescriptionsIden::Table,
+            Expr::col((
+                TransactionDescriptionsIden::Table,
+                TransactionDescriptionsIden::TransactionId,
+            ))
+            .equals((TransactionIden::Table, TransactionIden::Id)),
+        )
+        .join(
+            JoinType::Join,
+            EntryIden::Table,
+            Expr::col((EntryIden::Table, EntryIden::TransactionId))
+                .equals((TransactionIden::Table, TransactionIden::Id)),
+        )
+        .and_where(Expr::col((TransactionIden::Table, TransactionIden::UserId)).eq(user_id))
+        .and_where(Expr::col((TransactionIden::Table, TransactionIden::Visibility)).eq("default"))
+        .and_where(
+            Expr::col((TransactionIden::Table, TransactionIden::DateTransacted))
+                .between(date_from, date_to),
+        )
+        .and_where(Expr::col((EntryIden::Table, EntryIden::AssetId)).is_in(asset_ids))
+        .and_where(
+            Expr::exists(
+                Query::select()
+                    .expr(Expr::val(1))
+                    .from(ConnectorTransactionIden::Table)
+                    .and_where(
+                        Expr::col((
+                            ConnectorTransactionIden::Table,
+                            ConnectorTransactionIden::TransactionId,
+                        ))
+                        .equals((TransactionIden::Table, TransactionIden::Id)),
+                    )
+                    .to_owned(),
+            )
+            .not(),
+        )
+        .group_by_col((TransactionIden::Table, TransactionIden::Id))
+        .group_by_col((TransactionIden::Table, TransactionIden::DateTransacted))
+        .group_by_col((
+            TransactionDescriptionsIden::Table,
+            TransactionDescriptionsIden::Description,
+        ))
+        .group_by_col((
+            TransactionDescriptionsIden::Table,
+            TransactionDescriptionsIden::Embedding,
+        ))
+        .group_by_col((
+            TransactionDescriptionsIden::Table,
+            TransactionDescriptionsIden::EmbeddingModel,
+        ))
+        .group_by_col((EntryIden::Table, EntryIden::AccountId))
+        .group_by_col((EntryIden::Table, EntryIden::AssetId))
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn insert_connector_duplicate_candidates(
+    models: Vec<AddConnectorDuplicateCandidateModel>,
+) -> DbQueryWithValues {
+    let mut query = Query::insert()
+        .into_table(ConnectorDuplicateCandidateIden::Table)
+        .columns(vec![
+            ConnectorDuplicateCandidateIden::TransactionId,
+            ConnectorDuplicateCandidateIden::CandidateTransactionId,
+            ConnectorDuplicateCandidateIden::Score,
+        ])
+        .on_conflict(
+            OnConflict::columns([
+                ConnectorDuplicateCandidateIden::TransactionId,
+                ConnectorDuplicateCandidateIden::CandidateTransactionId,
+            ])
+            .update_column(ConnectorDuplicateCandidateIden::Score)
+            .to_owned(),
+        )
+        .to_owned();
+    for model in models {
+        query.values_panic([
+            model.transaction_id.into(),
+            model.candidate_transaction_id.into(),
+            model.score.into(),
+        ]);
+    }
+    query.build_sqlx(PostgresQueryBuilder).into()
+}
+
+/// Possible duplicates of the given imported transactions, best first.
+#[macros::named_query]
+pub fn get_connector_duplicate_candidates(transaction_ids: Vec<Uuid>) -> DbQueryWithValues {
+    Query::select()
+        .column(ConnectorDuplicateCandidateIden::TransactionId)
+        .column(ConnectorDuplicateCandidateIden::CandidateTransactionId)
+        .column(ConnectorDuplicateCandidateIden::Score)
+        .from(ConnectorDuplicateCandidateIden::Table)
+        .and_where(Expr::col(ConnectorDuplicateCandidateIden::TransactionId).is_in(transaction_ids))
+        .order_by(
+            ConnectorDuplicateCandidateIden::Score,
+            sea_query::Order::Desc,
+        )
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn delete_connector_duplicate_candidates(transaction_ids: Vec<Uuid>) -> DbQueryWithValues {
+    Query::delete()
+        .from_table(ConnectorDuplicateCandidateIden::Table)
+        .and_where(Expr::col(ConnectorDuplicateCandidateIden::TransactionId).is_in(transaction_ids))
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn insert_connector_balance_snapshots(
+    models: Vec<AddConnectorBalanceSnapshotModel>,
+) -> DbQueryWithValues {
+    let mut query = Query::insert()
+        .into_table(ConnectorBalanceSnapshotIden::Table)
+        .columns(vec![
+            ConnectorBalanceSnapshotIden::ProviderAccountId,
+            ConnectorBalanceSnapshotIden::FetchedAt,
+            ConnectorBalanceSnapshotIden::Kind,
+            ConnectorBalanceSnapshotIden::AssetIdentifier,
+            ConnectorBalanceSnapshotIden::AssetId,
+            ConnectorBalanceSnapshotIden::Quantity,
+        ])
+        .on_conflict(
+            OnConflict::columns([
+                ConnectorBalanceSnapshotIden::ProviderAccountId,
+                ConnectorBalanceSnapshotIden::FetchedAt,
+                ConnectorBalanceSnapshotIden::Kind,
+                ConnectorBalanceSnapshotIden::AssetIdentifier,
+            ])
+            .do_nothing()
+            .to_owned(),
+        )
+        .to_owned();
+    for model in models {
+        query.values_panic([
+            model.provider_account_id.into(),
+            model.fetched_at.into(),
+            model.kind.into(),
+            model.asset_identifier.into(),
+            model.asset_id.into(),
+            model.quantity.into(),
+        ]);
+    }
+    query.build_sqlx(PostgresQueryBuilder).into()
+}
+
+/// Balance snapshots of the given provider accounts, oldest first. Both bounds are
+/// inclusive.
+#[macros::named_query]
+pub fn get_connector_balance_snapshots(
+    provider_account_ids: Vec<Uuid>,
+    fetched_from: Option<OffsetDateTime>,
+    fetched_to: Option<OffsetDateTime>,
+) -> DbQueryWithValues {
+    let mut query = Query::select()
+        .columns([
+            ConnectorBalanceSnapshotIden::ProviderAccountId,
+            ConnectorBalanceSnapshotIden::FetchedAt,
+            ConnectorBalanceSnapshotIden::Kind,
+            ConnectorBalanceSnapshotIden::AssetIdentifier,
+            ConnectorBalanceSnapshotIden::AssetId,
+            ConnectorBalanceSnapshotIden::Quantity,
+        ])
+        .from(ConnectorBalanceSnapshotIden::Table)
+        .and_where(
+            Expr::col(ConnectorBalanceSnapshotIden::ProviderAccountId).is_in(provider_account_ids),
+        )
+        .to_owned();
+    if let Some(fetched_from) = fetched_from {
+        query.and_where(Expr::col(ConnectorBalanceSnapshotIden::FetchedAt).gte(fetched_from));
+    }
+    if let Some(fetched_to) = fetched_to {
+        query.and_where(Expr::col(ConnectorBalanceSnapshotIden::FetchedAt).lte(fetched_to));
+    }
+    query
+        .order_by(
+            ConnectorBalanceSnapshotIden::FetchedAt,
+            sea_query::Order::Asc,
+        )
+        .order_by(
+            ConnectorBalanceSnapshotIden::ProviderAccountId,
+            sea_query::Order::Asc,
+        )
+        .order_by(ConnectorBalanceSnapshotIden::Kind, sea_query::Order::Asc)
+        .order_by(
+            ConnectorBalanceSnapshotIden::AssetIdentifier,
+            sea_query::Order::Asc,
+        )
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+/// The last balance snapshot each of the given provider accounts reported before
+/// `fetched_before`, ordered like [`get_connector_balance_snapshots`].
+#[macros::named_query]
+pub fn get_latest_connector_balance_snapshots_before(
+    provider_account_ids: Vec<Uuid>,
+    fetched_before: OffsetDateTime,
+) -> DbQueryWithValues {
+    let earlier = Alias::new("earlier");
+    let latest_subquery = Query::select()
+        .expr(Expr::col((earlier.clone(), ConnectorBalanceSnapshotIden::FetchedAt)).max())
+        .from_as(ConnectorBalanceSnapshotIden::Table, earlier.clone())
+        .and_where(
+            Expr::col((
+                earlier.clone(),
+                ConnectorBalanceSnapshotIden::ProviderAccountId,
+            ))
+            .equals((
+                ConnectorBalanceSnapshotIden::Table,
+                ConnectorBalanceSnapshotIden::ProviderAccountId,
+            )),
+        )
+        .and_where(Expr::col((earlier, ConnectorBalanceSnapshotIden::FetchedAt)).lt(fetched_before))
+        .to_owned();
+
+    Query::select()
+        .columns([
+            ConnectorBalanceSnapshotIden::ProviderAccountId,
+            ConnectorBalanceSnapshotIden::FetchedAt,
+            ConnectorBalanceSnapshotIden::Kind,
+            ConnectorBalanceSnapshotIden::AssetIdentifier,
+            ConnectorBalanceSnapshotIden::AssetId,
+            ConnectorBalanceSnapshotIden::Quantity,
+        ])
+        .from(ConnectorBalanceSnapshotIden::Table)
+        .and_where(
+            Expr::col(ConnectorBalanceSnapshotIden::ProviderAccountId).is_in(provider_account_ids),
+        )
+        .and_where(
+            Expr::col((
+                ConnectorBalanceSnapshotIden::Table,
+                ConnectorBalanceSnapshotIden::FetchedAt,
+            ))
+            .in_subquery(latest_subquery),
+        )
+        .order_by(
+            ConnectorBalanceSnapshotIden::FetchedAt,
+            sea_query::Order::Asc,
+        )
+        .order_by(
+            ConnectorBalanceSnapshotIden::ProviderAccountId,
+            sea_query::Order::Asc,
+        )
+        .order_by(ConnectorBalanceSnapshotIden::Kind, sea_query::Order::Asc)
+        .order_by(
+            ConnectorBalanceSnapshotIden::AssetIdentifier,
+            sea_query::Order::Asc,
+        )
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
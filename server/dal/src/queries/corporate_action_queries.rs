@@ -0,0 +1,86 @@
+use sea_query::{Expr, ExprTrait, Order, PostgresQueryBuilder, Query};
+use sea_query_sqlx::SqlxBinder;
+use sqlx::types::Uuid;
+
+use crate::{
+    idens::corporate_action_idens::CorporateActionIden,
+    models::corporate_action_models::AddCorporateActionModel,
+    query_params::get_corporate_actions_params::{
+        GetCorporateActionsParams, GetCorporateActionsParamsSearchType,
+    },
+};
+
+use super::DbQueryWithValues;
+
+#[macros::named_query]
+pub fn get_corporate_actions(params: GetCorporateActionsParams) -> DbQueryWithValues {
+    let mut query = Query::select();
+
+    query
+        .columns([
+            CorporateActionIden::Id,
+            CorporateActionIden::UserId,
+            CorporateActionIden::AssetId,
+            CorporateActionIden::ActionType,
+            CorporateActionIden::EffectiveDate,
+            CorporateActionIden::RatioFrom,
+            CorporateActionIden::RatioTo,
+            CorporateActionIden::NewAssetId,
+            CorporateActionIden::CostBasisFraction,
+            CorporateActionIden::CreatedAt,
+        ])
+        .from(CorporateActionIden::Table)
+        .and_where(Expr::col(CorporateActionIden::UserId).eq(params.user_id));
+
+    match params.search_type {
+        GetCorporateActionsParamsSearchType::All => {}
+        GetCorporateActionsParamsSearchType::ById(id) => {
+            query.and_where(Expr::col(CorporateActionIden::Id).eq(id));
+        }
+    }
+
+    query
+        .order_by(CorporateActionIden::EffectiveDate, Order::Asc)
+        .order_by(CorporateActionIden::CreatedAt, Order::Asc)
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn insert_corporate_action(model: AddCorporateActionModel) -> DbQueryWithValues {
+    Query::insert()
+        .into_table(CorporateActionIden::Table)
+        .columns([
+            CorporateActionIden::UserId,
+            CorporateActionIden::AssetId,
+            CorporateActionIden::ActionType,
+            CorporateActionIden::EffectiveDate,
+            CorporateActionIden::RatioFrom,
+            CorporateActionIden::RatioTo,
+            CorporateActionIden::NewAssetId,
+            CorporateActionIden::CostBasisFraction,
+        ])
+        .values_panic([
+            model.user_id.into(),
+            model.asset_id.into(),
+            model.action_type.into(),
+            model.effective_date.into(),
+            model.ratio_from.into(),
+            model.ratio_to.into(),
+            model.new_asset_id.into(),
+            model.cost_basis_fraction.into(),
+        ])
+        .returning_all()
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn delete_corporate_action(id: Uuid, user_id: Uuid) -> DbQueryWithValues {
+    Query::delete()
+        .from_table(CorporateActionIden::Table)
+        .and_where(Expr::col(CorporateActionIden::Id).eq(id))
+        .and_where(Expr::col(CorporateActionIden::UserId).eq(user_id))
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
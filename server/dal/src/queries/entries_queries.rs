@@ -1,6 +1,7 @@
-use sea_query::{Alias, Expr, ExprTrait, JoinType, PostgresQueryBuilder, Query};
+use sea_query::{Alias, Expr, ExprTrait, JoinType, PostgresQueryBuilder, Query, SelectStatement};
 use sea_query_sqlx::SqlxBinder;
 use sqlx::types::Uuid;
+use time::OffsetDateTime;
 
 use crate::{
     idens::{
@@ -42,6 +43,34 @@ pub fn insert_entries(models: Vec<AddEntryModel>) -> DbQueryWithValues {
 
 #[macros::named_query]
 pub fn get_holdings(user_id: Uuid, apply_ownership_share: bool) -> DbQueryWithValues {
+    holdings_query(user_id, apply_ownership_share)
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+/// Holdings of `asset_id` per account from the transactions dated strictly before
+/// `before`, i.e. what a corporate action effective at `before` applies to.
+#[macros::named_query]
+pub fn get_asset_holdings_before(
+    user_id: Uuid,
+    apply_ownership_share: bool,
+    asset_id: i32,
+    before: OffsetDateTime,
+) -> DbQueryWithValues {
+    holdings_query(user_id, apply_ownership_share)
+        .join(
+            JoinType::Join,
+            TransactionIden::Table,
+            Expr::col((EntryIden::Table, EntryIden::TransactionId))
+                .equals((TransactionIden::Table, TransactionIden::Id)),
+        )
+        .and_where(Expr::col((EntryIden::Table, EntryIden::AssetId)).eq(asset_id))
+        .and_where(Expr::col((TransactionIden::Table, TransactionIden::DateTransacted)).lt(before))
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+fn holdings_query(user_id: Uuid, apply_ownership_share: bool) -> SelectStatement {
     let quantity_sum = || {
         if apply_ownership_share {
             Expr::sum(
@@ -68,8 +97,7 @@ pub fn get_holdings(user_id: Uuid, apply_ownership_share: bool) -> DbQueryWithVa
         .group_by_col((EntryIden::Table, EntryIden::AccountId))
         .group_by_col((EntryIden::Table, EntryIden::AssetId))
         .and_having(quantity_sum().ne(0))
-        .build_sqlx(PostgresQueryBuilder)
-        .into()
+        .to_owned()
 }
 
 /// This query takes start time, interval and user id. It then queries the database
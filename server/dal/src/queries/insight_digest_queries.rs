@@ -0,0 +1,205 @@
+use sea_query::{Alias, Expr, ExprTrait, OnConflict, Order, PostgresQueryBuilder, Query};
+use sea_query_sqlx::SqlxBinder;
+use sqlx::types::Uuid;
+
+use crate::{
+    idens::{
+        insight_digest_idens::InsightDigestIden, transaction_idens::TransactionIden,
+        user_idens::UsersIden,
+    },
+    models::insight_digest_models::AddInsightDigestModel,
+    query_params::get_insight_digests_params::{
+        GetInsightDigestCandidatesParams, GetInsightDigestsParams,
+        GetInsightDigestsParamsSearchType,
+    },
+};
+
+use super::DbQueryWithValues;
+
+/// Digests newest period first; a month and the week ending with it share an end,
+/// the month comes first.
+#[macros::named_query]
+pub fn get_insight_digests(params: GetInsightDigestsParams) -> DbQueryWithValues {
+    let mut query = Query::select();
+
+    query
+        .columns([
+            InsightDigestIden::Id,
+            InsightDigestIden::UserId,
+            InsightDigestIden::Period,
+            InsightDigestIden::PeriodStart,
+            InsightDigestIden::PeriodEnd,
+            InsightDigestIden::ReferenceAssetId,
+            InsightDigestIden::Content,
+            InsightDigestIden::Narrative,
+            InsightDigestIden::Model,
+            InsightDigestIden::ReadAt,
+            InsightDigestIden::CreatedAt,
+        ])
+        .from(InsightDigestIden::Table)
+        .and_where(Expr::col(InsightDigestIden::UserId).eq(params.user_id));
+
+    match params.search_type {
+        GetInsightDigestsParamsSearchType::All { period } => {
+            if let Some(period) = period {
+                query.and_where(Expr::col(InsightDigestIden::Period).eq(period));
+            }
+        }
+        GetInsightDigestsParamsSearchType::ById(id) => {
+            query.and_where(Expr::col(InsightDigestIden::Id).eq(id));
+        }
+    }
+
+    if let Some(paging) = params.paging {
+        query.limit(paging.count).offset(paging.start);
+    }
+
+    query
+        .order_by(InsightDigestIden::PeriodEnd, Order::Desc)
+        .order_by(InsightDigestIden::Period, Order::Asc)
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn get_insight_digest_candidates(
+    params: GetInsightDigestCandidatesParams,
+) -> DbQueryWithValues {
+    let mut query = Query::select();
+
+    query
+        .expr_as(
+            Expr::col((UsersIden::Table, UsersIden::Id)),
+            Alias::new("user_id"),
+        )
+        .expr_as(
+            Expr::col((UsersIden::Table, UsersIden::DefaultAsset)),
+            Alias::new("reference_asset_id"),
+        )
+        .expr_as(
+            Expr::col((UsersIden::Table, UsersIden::NarrateInsightDigests)),
+            Alias::new("narrate"),
+        )
+        .from(UsersIden::Table)
+        .and_where(Expr::col((UsersIden::Table, UsersIden::DefaultAsset)).is_not_null())
+        .and_where(Expr::exists(
+            Query::select()
+                .expr(Expr::val(1))
+                .from(TransactionIden::Table)
+                .and_where(
+                    Expr::col((TransactionIden::Table, TransactionIden::UserId))
+                        .equals((UsersIden::Table, UsersIden::Id)),
+                )
+                .and_where(
+                    Expr::col((TransactionIden::Table, TransactionIden::DateTransacted))
+                        .lt(params.period_end),
+                )
+                .to_owned(),
+        ))
+        .and_where(
+            Expr::exists(
+                Query::select()
+                    .expr(Expr::val(1))
+                    .from(InsightDigestIden::Table)
+                    .and_where(
+                        Expr::col((InsightDigestIden::Table, InsightDigestIden::UserId))
+                            .equals((UsersIden::Table, UsersIden::Id)),
+                    )
+                    .and_where(
+                        Expr::col((InsightDigestIden::Table, InsightDigestIden::Period))
+                            .eq(params.period),
+                    )
+                    .and_where(
+                        Expr::col((InsightDigestIden::Table, InsightDigestIden::PeriodStart))
+                            .eq(params.period_start),
+                    )
+                    .to_owned(),
+            )
+            .not(),
+        );
+
+    if let Some(after) = params.after {
+        query.and_where(Expr::col((UsersIden::Table, UsersIden::Id)).gt(after));
+    }
+
+    query
+        .order_by((UsersIden::Table, UsersIden::Id), Order::Asc)
+        .limit(params.limit)
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+/// Stores a digest unless the user already has one for the period. Returns the new
+/// digest, no row when one already existed.
+#[macros::named_query]
+pub fn insert_insight_digest(model: AddInsightDigestModel) -> DbQueryWithValues {
+    Query::insert()
+        .into_table(InsightDigestIden::Table)
+        .columns([
+            InsightDigestIden::UserId,
+            InsightDigestIden::Period,
+            InsightDigestIden::PeriodStart,
+            InsightDigestIden::PeriodEnd,
+            InsightDigestIden::ReferenceAssetId,
+            InsightDigestIden::Content,
+            InsightDigestIden::Narrative,
+            InsightDigestIden::Model,
+        ])
+        .values_panic([
+            model.user_id.into(),
+            model.period.into(),
+            model.period_start.into(),
+            model.period_end.into(),
+            model.reference_asset_id.into(),
+            model.content.into(),
+            model.narrative.into(),
+            model.model.into(),
+        ])
+        .on_conflict(
+            OnConflict::columns([
+                InsightDigestIden::UserId,
+                InsightDigestIden::Period,
+                InsightDigestIden::PeriodStart,
+            ])
+            .do_nothing()
+            .to_owned(),
+        )
+        .returning_all()
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn mark_insight_digest_read(user_id: Uuid, id: Uuid) -> DbQueryWithValues {
+    Query::update()
+        .table(InsightDigestIden::Table)
+        .value(InsightDigestIden::ReadAt, Expr::cust("NOW()"))
+        .and_where(Expr::col(InsightDigestIden::Id).eq(id))
+        .and_where(Expr::col(InsightDigestIden::UserId).eq(user_id))
+        .and_where(Expr::col(InsightDigestIden::ReadAt).is_null())
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn get_insight_digest_settings(user_id: Uuid) -> DbQueryWithValues {
+    Query::select()
+        .expr_as(
+            Expr::col(UsersIden::NarrateInsightDigests),
+            Alias::new("narrate"),
+        )
+        .from(UsersIden::Table)
+        .and_where(Expr::col(UsersIden::Id).eq(user_id))
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn update_insight_digest_settings(user_id: Uuid, narrate: bool) -> DbQueryWithValues {
+    Query::update()
+        .table(UsersIden::Table)
+        .value(UsersIden::NarrateInsightDigests, narrate)
+        .and_where(Expr::col(UsersIden::Id).eq(user_id))
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
@@ -8,17 +8,30 @@ pub mod ai_conversation_queries;
 pub mod ai_queries;
 pub mod ai_quick_upload_queries;
 pub mod asset_queries;
+pub mod budget_queries;
 pub mod category_queries;
 pub mod category_type_queries;
 pub mod connector_queries;
+pub mod corporate_action_queries;
 pub mod entries_queries;
 pub mod file_queries;
+pub mod insight_digest_queries;
 pub mod rate_limit_queries;
 pub mod rate_limit_redis_queries;
+pub mod reconciliation_queries;
+pub mod recurring_transaction_queries;
+pub mod spending_alert_queries;
+pub mod statement_csv_mapping_queries;
+pub mod subscription_queries;
+pub mod tag_queries;
+pub mod ticker_alias_queries;
 pub mod transaction_categories_queries;
 pub mod transaction_data_queries;
 pub mod transaction_group_queries;
 pub mod transaction_queries;
+pub mod transaction_rule_queries;
+pub mod transfer_queries;
+pub mod user_data_archive_queries;
 pub mod user_queries;
 
 pub struct DbQueryWithValues {
@@ -0,0 +1,153 @@
+use sea_query::{Expr, ExprTrait, JoinType, Order, PostgresQueryBuilder, Query};
+use sea_query_sqlx::SqlxBinder;
+use sqlx::types::Uuid;
+use time::OffsetDateTime;
+
+use crate::{
+    idens::{
+        entries_idens::EntryIden,
+        reconciliation_idens::{AccountReconciliationIden, ReconciliationMovementIden},
+        transaction_idens::TransactionIden,
+    },
+    models::reconciliation_models::AddAccountReconciliationModel,
+    query_params::get_account_reconciliations_params::{
+        GetAccountReconciliationsParams, GetAccountReconciliationsParamsSearchType,
+    },
+};
+
+use super::DbQueryWithValues;
+
+#[macros::named_query]
+pub fn get_account_reconciliations(params: GetAccountReconciliationsParams) -> DbQueryWithValues {
+    let mut query = Query::select();
+
+    query
+        .columns([
+            AccountReconciliationIden::Id,
+            AccountReconciliationIden::UserId,
+            AccountReconciliationIden::AccountId,
+            AccountReconciliationIden::AssetId,
+            AccountReconciliationIden::StatementDate,
+            AccountReconciliationIden::StatementBalance,
+            AccountReconciliationIden::Source,
+            AccountReconciliationIden::BindingId,
+            AccountReconciliationIden::CreatedAt,
+        ])
+        .from(AccountReconciliationIden::Table)
+        .and_where(Expr::col(AccountReconciliationIden::UserId).eq(params.user_id))
+        .and_where(Expr::col(AccountReconciliationIden::AccountId).eq(params.account_id));
+
+    match params.search_type {
+        GetAccountReconciliationsParamsSearchType::All => {}
+        GetAccountReconciliationsParamsSearchType::ById(id) => {
+            query.and_where(Expr::col(AccountReconciliationIden::Id).eq(id));
+        }
+        GetAccountReconciliationsParamsSearchType::ByAsset(asset_id) => {
+            query.and_where(Expr::col(AccountReconciliationIden::AssetId).eq(asset_id));
+        }
+    }
+
+    query
+        .order_by(AccountReconciliationIden::StatementDate, Order::Desc)
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn insert_account_reconciliations(
+    models: Vec<AddAccountReconciliationModel>,
+) -> DbQueryWithValues {
+    let mut builder = Query::insert()
+        .into_table(AccountReconciliationIden::Table)
+        .columns([
+            AccountReconciliationIden::UserId,
+            AccountReconciliationIden::AccountId,
+            AccountReconciliationIden::AssetId,
+            AccountReconciliationIden::StatementDate,
+            AccountReconciliationIden::StatementBalance,
+            AccountReconciliationIden::Source,
+            AccountReconciliationIden::BindingId,
+        ])
+        .to_owned();
+
+    for model in models {
+        builder.values_panic([
+            model.user_id.into(),
+            model.account_id.into(),
+            model.asset_id.into(),
+            model.statement_date.into(),
+            model.statement_balance.into(),
+            model.source.into(),
+            model.binding_id.into(),
+        ]);
+    }
+
+    builder
+        .returning_all()
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+/// Moves a checkpoint to a later date, used when a provider reports the same balance again.
+#[macros::named_query]
+pub fn update_reconciliation_statement_date(
+    id: Uuid,
+    statement_date: OffsetDateTime,
+) -> DbQueryWithValues {
+    Query::update()
+        .table(AccountReconciliationIden::Table)
+        .value(AccountReconciliationIden::StatementDate, statement_date)
+        .and_where(Expr::col(AccountReconciliationIden::Id).eq(id))
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn delete_account_reconciliation(id: Uuid, user_id: Uuid) -> DbQueryWithValues {
+    Query::delete()
+        .from_table(AccountReconciliationIden::Table)
+        .and_where(Expr::col(AccountReconciliationIden::Id).eq(id))
+        .and_where(Expr::col(AccountReconciliationIden::UserId).eq(user_id))
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+/// Net quantity of `asset_id` each transaction moved through the account, up to and
+/// including `up_to`, oldest first. Ghost and hidden transactions are left out, as they
+/// are not part of the ledger the user has confirmed.
+#[macros::named_query]
+pub fn get_reconciliation_movements(
+    user_id: Uuid,
+    account_id: Uuid,
+    asset_id: i32,
+    up_to: OffsetDateTime,
+) -> DbQueryWithValues {
+    Query::select()
+        .column((EntryIden::Table, EntryIden::TransactionId))
+        .column((TransactionIden::Table, TransactionIden::DateTransacted))
+        .expr_as(
+            Expr::sum(Expr::col((EntryIden::Table, EntryIden::Quantity))),
+            ReconciliationMovementIden::Quantity,
+        )
+        .from(EntryIden::Table)
+        .join(
+            JoinType::Join,
+            TransactionIden::Table,
+            Expr::col((EntryIden::Table, EntryIden::TransactionId))
+                .equals((TransactionIden::Table, TransactionIden::Id)),
+        )
+        .and_where(Expr::col((TransactionIden::Table, TransactionIden::UserId)).eq(user_id))
+        .and_where(Expr::col((TransactionIden::Table, TransactionIden::Visibility)).eq("default"))
+        .and_where(Expr::col((EntryIden::Table, EntryIden::AccountId)).eq(account_id))
+        .and_where(Expr::col((EntryIden::Table, EntryIden::AssetId)).eq(asset_id))
+        .and_where(Expr::col((TransactionIden::Table, TransactionIden::DateTransacted)).lte(up_to))
+        .group_by_col((EntryIden::Table, EntryIden::TransactionId))
+        .group_by_col((TransactionIden::Table, TransactionIden::DateTransacted))
+        .order_by(
+            (TransactionIden::Table, TransactionIden::DateTransacted),
+            Order::Asc,
+        )
+        .order_by((EntryIden::Table, EntryIden::TransactionId), Order::Asc)
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
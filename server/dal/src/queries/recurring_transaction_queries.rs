@@ -0,0 +1,164 @@
+use sea_query::{Expr, ExprTrait, Order, PostgresQueryBuilder, Query};
+use sea_query_sqlx::SqlxBinder;
+use sqlx::types::Uuid;
+
+use crate::{
+    idens::recurring_transaction_idens::RecurringTransactionIden,
+    models::recurring_transaction_models::{
+        AddRecurringTransactionModel, UpdateRecurringTransactionModel,
+    },
+    query_params::get_recurring_transactions_params::{
+        AdvanceRecurringTransactionParams, GetRecurringTransactionsParams,
+        GetRecurringTransactionsParamsSearchType,
+    },
+};
+
+use super::DbQueryWithValues;
+
+#[macros::named_query]
+pub fn get_recurring_transactions(params: GetRecurringTransactionsParams) -> DbQueryWithValues {
+    let mut query = Query::select();
+
+    query
+        .columns([
+            RecurringTransactionIden::Id,
+            RecurringTransactionIden::UserId,
+            RecurringTransactionIden::AccountId,
+            RecurringTransactionIden::Name,
+            RecurringTransactionIden::Template,
+            RecurringTransactionIden::Rrule,
+            RecurringTransactionIden::StartDate,
+            RecurringTransactionIden::EndDate,
+            RecurringTransactionIden::NextOccurrence,
+            RecurringTransactionIden::LastOccurrence,
+            RecurringTransactionIden::CreateAsGhost,
+            RecurringTransactionIden::Active,
+            RecurringTransactionIden::CreatedAt,
+            RecurringTransactionIden::UpdatedAt,
+        ])
+        .from(RecurringTransactionIden::Table);
+
+    match params.search_type {
+        GetRecurringTransactionsParamsSearchType::All(user_id) => {
+            query
+                .and_where(Expr::col(RecurringTransactionIden::UserId).eq(user_id))
+                .order_by(RecurringTransactionIden::CreatedAt, Order::Asc);
+        }
+        GetRecurringTransactionsParamsSearchType::ById { user_id, id } => {
+            query
+                .and_where(Expr::col(RecurringTransactionIden::UserId).eq(user_id))
+                .and_where(Expr::col(RecurringTransactionIden::Id).eq(id));
+        }
+        GetRecurringTransactionsParamsSearchType::Due { now, after, limit } => {
+            query
+                .and_where(Expr::col(RecurringTransactionIden::Active).eq(true))
+                .and_where(Expr::col(RecurringTransactionIden::NextOccurrence).lte(now))
+                .order_by(RecurringTransactionIden::Id, Order::Asc)
+                .limit(limit);
+            if let Some(after) = after {
+                query.and_where(Expr::col(RecurringTransactionIden::Id).gt(after));
+            }
+        }
+    }
+
+    query.build_sqlx(PostgresQueryBuilder).into()
+}
+
+#[macros::named_query]
+pub fn insert_recurring_transaction(model: AddRecurringTransactionModel) -> DbQueryWithValues {
+    Query::insert()
+        .into_table(RecurringTransactionIden::Table)
+        .columns([
+            RecurringTransactionIden::UserId,
+            RecurringTransactionIden::AccountId,
+            RecurringTransactionIden::Name,
+            RecurringTransactionIden::Template,
+            RecurringTransactionIden::Rrule,
+            RecurringTransactionIden::StartDate,
+            RecurringTransactionIden::EndDate,
+            RecurringTransactionIden::NextOccurrence,
+            RecurringTransactionIden::CreateAsGhost,
+            RecurringTransactionIden::Active,
+        ])
+        .values_panic([
+            model.user_id.into(),
+            model.account_id.into(),
+            model.name.into(),
+            model.template.into(),
+            model.rrule.into(),
+            model.start_date.into(),
+            model.end_date.into(),
+            model.next_occurrence.into(),
+            model.create_as_ghost.into(),
+            model.active.into(),
+        ])
+        .returning_all()
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn update_recurring_transaction(
+    id: Uuid,
+    user_id: Uuid,
+    updates: UpdateRecurringTransactionModel,
+) -> DbQueryWithValues {
+    Query::update()
+        .table(RecurringTransactionIden::Table)
+        .value(RecurringTransactionIden::AccountId, updates.account_id)
+        .value(RecurringTransactionIden::Name, updates.name)
+        .value(RecurringTransactionIden::Template, updates.template)
+        .value(RecurringTransactionIden::Rrule, updates.rrule)
+        .value(RecurringTransactionIden::StartDate, updates.start_date)
+        .value(RecurringTransactionIden::EndDate, updates.end_date)
+        .value(
+            RecurringTransactionIden::NextOccurrence,
+            updates.next_occurrence,
+        )
+        .value(
+            RecurringTransactionIden::CreateAsGhost,
+            updates.create_as_ghost,
+        )
+        .value(RecurringTransactionIden::Active, updates.active)
+        .value(RecurringTransactionIden::UpdatedAt, Expr::cust("NOW()"))
+        .and_where(Expr::col(RecurringTransactionIden::Id).eq(id))
+        .and_where(Expr::col(RecurringTransactionIden::UserId).eq(user_id))
+        .returning_all()
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn delete_recurring_transaction(id: Uuid, user_id: Uuid) -> DbQueryWithValues {
+    Query::delete()
+        .from_table(RecurringTransactionIden::Table)
+        .and_where(Expr::col(RecurringTransactionIden::Id).eq(id))
+        .and_where(Expr::col(RecurringTransactionIden::UserId).eq(user_id))
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+/// Affects no rows when the schedule already moved past the expected occurrence,
+/// meaning another worker got there first.
+#[macros::named_query]
+pub fn advance_recurring_transaction(
+    params: AdvanceRecurringTransactionParams,
+) -> DbQueryWithValues {
+    Query::update()
+        .table(RecurringTransactionIden::Table)
+        .value(
+            RecurringTransactionIden::NextOccurrence,
+            params.next_occurrence,
+        )
+        .value(
+            RecurringTransactionIden::LastOccurrence,
+            params.last_occurrence,
+        )
+        .value(RecurringTransactionIden::UpdatedAt, Expr::cust("NOW()"))
+        .and_where(Expr::col(RecurringTransactionIden::Id).eq(params.id))
+        .and_where(
+            Expr::col(RecurringTransactionIden::NextOccurrence).eq(params.expected_next_occurrence),
+        )
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
@@ -0,0 +1,198 @@
+use sea_query::{Alias, Expr, ExprTrait, JoinType, OnConflict, Order, PostgresQueryBuilder, Query};
+use sea_query_sqlx::SqlxBinder;
+use sqlx::types::Uuid;
+
+use crate::{
+    enums::transaction_types::DatabaseTransactionTypes,
+    idens::{
+        entries_idens::EntryIden,
+        spending_alert_idens::SpendingAlertIden,
+        transaction_idens::{TransactionDescriptionsIden, TransactionIden},
+    },
+    models::spending_alert_models::AddSpendingAlertModel,
+    query_params::get_spending_alerts_params::{
+        GetSpendingAlertChargesParams, GetSpendingAlertChargesParamsSearchType,
+        GetSpendingAlertsParams, GetSpendingAlertsParamsSearchType,
+    },
+};
+
+use super::DbQueryWithValues;
+
+#[macros::named_query]
+pub fn get_spending_alerts(params: GetSpendingAlertsParams) -> DbQueryWithValues {
+    let mut query = Query::select();
+
+    query
+        .columns([
+            SpendingAlertIden::Id,
+            SpendingAlertIden::UserId,
+            SpendingAlertIden::TransactionId,
+            SpendingAlertIden::Kind,
+            SpendingAlertIden::AccountId,
+            SpendingAlertIden::AssetId,
+            SpendingAlertIden::Amount,
+            SpendingAlertIden::TypicalAmount,
+            SpendingAlertIden::RelatedTransactionId,
+            SpendingAlertIden::Description,
+            SpendingAlertIden::DateTransacted,
+            SpendingAlertIden::DismissedAt,
+            SpendingAlertIden::CreatedAt,
+        ])
+        .from(SpendingAlertIden::Table)
+        .and_where(Expr::col(SpendingAlertIden::UserId).eq(params.user_id));
+
+    match params.search_type {
+        GetSpendingAlertsParamsSearchType::All { include_dismissed } => {
+            if !include_dismissed {
+                query.and_where(Expr::col(SpendingAlertIden::DismissedAt).is_null());
+            }
+        }
+        GetSpendingAlertsParamsSearchType::ById(id) => {
+            query.and_where(Expr::col(SpendingAlertIden::Id).eq(id));
+        }
+    }
+
+    if let Some(paging) = params.paging {
+        query.limit(paging.count).offset(paging.start);
+    }
+
+    query
+        .order_by(SpendingAlertIden::CreatedAt, Order::Desc)
+        .order_by(SpendingAlertIden::DateTransacted, Order::Desc)
+        .order_by(SpendingAlertIden::Id, Order::Asc)
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+/// Stores the alerts a transaction does not have yet. Returns only the new ones, so a
+/// transaction checked twice is not reported twice.
+#[macros::named_query]
+pub fn insert_spending_alerts(models: Vec<AddSpendingAlertModel>) -> DbQueryWithValues {
+    let mut query = Query::insert()
+        .into_table(SpendingAlertIden::Table)
+        .columns([
+            SpendingAlertIden::UserId,
+            SpendingAlertIden::TransactionId,
+            SpendingAlertIden::Kind,
+            SpendingAlertIden::AccountId,
+            SpendingAlertIden::AssetId,
+            SpendingAlertIden::Amount,
+            SpendingAlertIden::TypicalAmount,
+            SpendingAlertIden::RelatedTransactionId,
+            SpendingAlertIden::Description,
+            SpendingAlertIden::DateTransacted,
+        ])
+        .on_conflict(
+            OnConflict::columns([SpendingAlertIden::TransactionId, SpendingAlertIden::Kind])
+                .do_nothing()
+                .to_owned(),
+        )
+        .returning_all()
+        .to_owned();
+    for model in models {
+        query.values_panic([
+            model.user_id.into(),
+            model.transaction_id.into(),
+            model.kind.into(),
+            model.account_id.into(),
+            model.asset_id.into(),
+            model.amount.into(),
+            model.typical_amount.into(),
+            model.related_transaction_id.into(),
+            model.description.into(),
+            model.date_transacted.into(),
+        ]);
+    }
+    query.build_sqlx(PostgresQueryBuilder).into()
+}
+
+#[macros::named_query]
+pub fn dismiss_spending_alert(user_id: Uuid, id: Uuid) -> DbQueryWithValues {
+    Query::update()
+        .table(SpendingAlertIden::Table)
+        .value(SpendingAlertIden::DismissedAt, Expr::cust("NOW()"))
+        .and_where(Expr::col(SpendingAlertIden::Id).eq(id))
+        .and_where(Expr::col(SpendingAlertIden::UserId).eq(user_id))
+        .and_where(Expr::col(SpendingAlertIden::DismissedAt).is_null())
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+/// Every visible regular transaction matching the search, summed per account, asset
+/// and category, oldest first.
+#[macros::named_query]
+pub fn get_spending_alert_charges(params: GetSpendingAlertChargesParams) -> DbQueryWithValues {
+    let mut query = Query::select();
+
+    query
+        .expr_as(
+            Expr::col((TransactionIden::Table, TransactionIden::Id)),
+            Alias::new("transaction_id"),
+        )
+        .column((TransactionIden::Table, TransactionIden::DateTransacted))
+        .column((
+            TransactionDescriptionsIden::Table,
+            TransactionDescriptionsIden::Description,
+        ))
+        .column((EntryIden::Table, EntryIden::AccountId))
+        .column((EntryIden::Table, EntryIden::AssetId))
+        .column((EntryIden::Table, EntryIden::CategoryId))
+        .expr_as(
+            Expr::sum(Expr::col((EntryIden::Table, EntryIden::Quantity))),
+            Alias::new("quantity"),
+        )
+        .from(TransactionIden::Table)
+        .join(
+            JoinType::LeftJoin,
+            TransactionDescriptionsIden::Table,
+            Expr::col((
+                TransactionDescriptionsIden::Table,
+                TransactionDescriptionsIden::TransactionId,
+            ))
+            .equals((TransactionIden::Table, TransactionIden::Id)),
+        )
+        .join(
+            JoinType::Join,
+            EntryIden::Table,
+            Expr::col((EntryIden::Table, EntryIden::TransactionId))
+                .equals((TransactionIden::Table, TransactionIden::Id)),
+        )
+        .and_where(Expr::col((TransactionIden::Table, TransactionIden::UserId)).eq(params.user_id))
+        .and_where(
+            Expr::col((TransactionIden::Table, TransactionIden::TypeId))
+                .eq(DatabaseTransactionTypes::RegularTransaction as i32),
+        )
+        .and_where(Expr::col((TransactionIden::Table, TransactionIden::Visibility)).eq("default"));
+
+    match params.search_type {
+        GetSpendingAlertChargesParamsSearchType::ByIds(ids) => {
+            query.and_where(Expr::col((TransactionIden::Table, TransactionIden::Id)).is_in(ids));
+        }
+        GetSpendingAlertChargesParamsSearchType::Range { from, to } => {
+            query
+                .and_where(
+                    Expr::col((TransactionIden::Table, TransactionIden::DateTransacted)).gte(from),
+                )
+                .and_where(
+                    Expr::col((TransactionIden::Table, TransactionIden::DateTransacted)).lt(to),
+                );
+        }
+    }
+
+    query
+        .group_by_col((TransactionIden::Table, TransactionIden::Id))
+        .group_by_col((TransactionIden::Table, TransactionIden::DateTransacted))
+        .group_by_col((
+            TransactionDescriptionsIden::Table,
+            TransactionDescriptionsIden::Description,
+        ))
+        .group_by_col((EntryIden::Table, EntryIden::AccountId))
+        .group_by_col((EntryIden::Table, EntryIden::AssetId))
+        .group_by_col((EntryIden::Table, EntryIden::CategoryId))
+        .order_by(
+            (TransactionIden::Table, TransactionIden::DateTransacted),
+            Order::Asc,
+        )
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
@@ -0,0 +1,65 @@
+use sea_query::{Expr, ExprTrait, OnConflict, PostgresQueryBuilder, Query};
+use sea_query_sqlx::SqlxBinder;
+use sqlx::types::Uuid;
+
+use crate::{
+    idens::{statement_csv_mapping_idens::StatementCsvMappingIden, CommonsIden},
+    models::statement_csv_mapping_models::UpsertStatementCsvMappingModel,
+};
+
+use super::DbQueryWithValues;
+
+#[macros::named_query]
+pub fn get_statement_csv_mapping(user_id: Uuid, account_id: Uuid) -> DbQueryWithValues {
+    Query::select()
+        .columns([
+            StatementCsvMappingIden::AccountId,
+            StatementCsvMappingIden::UserId,
+            StatementCsvMappingIden::Mapping,
+            StatementCsvMappingIden::CreatedAt,
+            StatementCsvMappingIden::UpdatedAt,
+        ])
+        .from(StatementCsvMappingIden::Table)
+        .and_where(Expr::col(StatementCsvMappingIden::UserId).eq(user_id))
+        .and_where(Expr::col(StatementCsvMappingIden::AccountId).eq(account_id))
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn upsert_statement_csv_mapping(model: UpsertStatementCsvMappingModel) -> DbQueryWithValues {
+    Query::insert()
+        .into_table(StatementCsvMappingIden::Table)
+        .columns([
+            StatementCsvMappingIden::AccountId,
+            StatementCsvMappingIden::UserId,
+            StatementCsvMappingIden::Mapping,
+        ])
+        .values_panic([
+            model.account_id.into(),
+            model.user_id.into(),
+            model.mapping.into(),
+        ])
+        .on_conflict(
+            OnConflict::column(StatementCsvMappingIden::AccountId)
+                .value(
+                    StatementCsvMappingIden::Mapping,
+                    Expr::col((CommonsIden::Excluded, StatementCsvMappingIden::Mapping)),
+                )
+                .value(StatementCsvMappingIden::UpdatedAt, Expr::cust("NOW()"))
+                .to_owned(),
+        )
+        .returning_all()
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn delete_statement_csv_mapping(user_id: Uuid, account_id: Uuid) -> DbQueryWithValues {
+    Query::delete()
+        .from_table(StatementCsvMappingIden::Table)
+        .and_where(Expr::col(StatementCsvMappingIden::UserId).eq(user_id))
+        .and_where(Expr::col(StatementCsvMappingIden::AccountId).eq(account_id))
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
@@ -0,0 +1,82 @@
+use sea_query::{Alias, Expr, ExprTrait, JoinType, Order, PostgresQueryBuilder, Query};
+use sea_query_sqlx::SqlxBinder;
+
+use crate::{
+    enums::transaction_types::DatabaseTransactionTypes,
+    idens::{
+        entries_idens::EntryIden,
+        transaction_idens::{TransactionDescriptionsIden, TransactionIden},
+    },
+    query_params::get_subscription_charges_params::GetSubscriptionChargesParams,
+};
+
+use super::DbQueryWithValues;
+
+/// Outflows and inflows of every visible regular transaction with a description,
+/// summed per account and asset, oldest first.
+#[macros::named_query]
+pub fn get_subscription_charges(params: GetSubscriptionChargesParams) -> DbQueryWithValues {
+    Query::select()
+        .expr_as(
+            Expr::col((TransactionIden::Table, TransactionIden::Id)),
+            Alias::new("transaction_id"),
+        )
+        .column((TransactionIden::Table, TransactionIden::DateTransacted))
+        .column((
+            TransactionDescriptionsIden::Table,
+            TransactionDescriptionsIden::Description,
+        ))
+        .column((
+            TransactionDescriptionsIden::Table,
+            TransactionDescriptionsIden::Embedding,
+        ))
+        .column((EntryIden::Table, EntryIden::AccountId))
+        .column((EntryIden::Table, EntryIden::AssetId))
+        .expr_as(
+            Expr::sum(Expr::col((EntryIden::Table, EntryIden::Quantity))),
+            Alias::new("quantity"),
+        )
+        .from(TransactionIden::Table)
+        .join(
+            JoinType::Join,
+            TransactionDescriptionsIden::Table,
+            Expr::col((
+                TransactionDescriptionsIden::Table,
+                TransactionDescriptionsIden::TransactionId,
+            ))
+            .equals((TransactionIden::Table, TransactionIden::Id)),
+        )
+        .join(
+            JoinType::Join,
+            EntryIden::Table,
+            Expr::col((EntryIden::Table, EntryIden::TransactionId))
+                .equals((TransactionIden::Table, TransactionIden::Id)),
+        )
+        .and_where(Expr::col((TransactionIden::Table, TransactionIden::UserId)).eq(params.user_id))
+        .and_where(
+            Expr::col((TransactionIden::Table, TransactionIden::TypeId))
+                .eq(DatabaseTransactionTypes::RegularTransaction as i32),
+        )
+        .and_where(Expr::col((TransactionIden::Table, TransactionIden::Visibility)).eq("default"))
+        .and_where(
+            Expr::col((TransactionIden::Table, TransactionIden::DateTransacted)).gte(params.since),
+        )
+        .group_by_col((TransactionIden::Table, TransactionIden::Id))
+        .group_by_col((TransactionIden::Table, TransactionIden::DateTransacted))
+        .group_by_col((
+            TransactionDescriptionsIden::Table,
+            TransactionDescriptionsIden::Description,
+        ))
+        .group_by_col((
+            TransactionDescriptionsIden::Table,
+            TransactionDescriptionsIden::Embedding,
+        ))
+        .group_by_col((EntryIden::Table, EntryIden::AccountId))
+        .group_by_col((EntryIden::Table, EntryIden::AssetId))
+        .order_by(
+            (TransactionIden::Table, TransactionIden::DateTransacted),
+            Order::Asc,
+        )
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
@@ -0,0 +1,236 @@
+use sea_query::{
+    Expr, ExprTrait, OnConflict, Order, PostgresQueryBuilder, Query, SelectStatement, UnionType,
+};
+use sea_query_sqlx::SqlxBinder;
+use sqlx::types::Uuid;
+
+use crate::{
+    idens::{
+        tag_idens::{TagIden, TransactionGroupTagIden, TransactionTagIden},
+        transaction_idens::TransactionIden,
+    },
+    models::tag_models::AddTagModel,
+    query_params::get_tags_params::{GetTagsParams, GetTagsParamsSearchType},
+};
+
+use super::DbQueryWithValues;
+
+#[macros::named_query]
+pub fn get_tags(params: GetTagsParams) -> DbQueryWithValues {
+    let mut query = Query::select();
+
+    query
+        .columns([
+            (TagIden::Table, TagIden::Id),
+            (TagIden::Table, TagIden::UserId),
+            (TagIden::Table, TagIden::Name),
+            (TagIden::Table, TagIden::CreatedAt),
+        ])
+        .from(TagIden::Table)
+        .and_where(Expr::col((TagIden::Table, TagIden::UserId)).eq(params.user_id));
+
+    match params.search_type {
+        GetTagsParamsSearchType::All => {}
+        GetTagsParamsSearchType::ByIds(ids) => {
+            query.and_where(Expr::col((TagIden::Table, TagIden::Id)).is_in(ids));
+        }
+        GetTagsParamsSearchType::ByTransaction(transaction_id) => {
+            query
+                .inner_join(
+                    TransactionTagIden::Table,
+                    Expr::col((TransactionTagIden::Table, TransactionTagIden::TagId))
+                        .equals((TagIden::Table, TagIden::Id)),
+                )
+                .and_where(
+                    Expr::col((TransactionTagIden::Table, TransactionTagIden::TransactionId))
+                        .eq(transaction_id),
+                );
+        }
+        GetTagsParamsSearchType::ByGroup(group_id) => {
+            query
+                .inner_join(
+                    TransactionGroupTagIden::Table,
+                    Expr::col((
+                        TransactionGroupTagIden::Table,
+                        TransactionGroupTagIden::TagId,
+                    ))
+                    .equals((TagIden::Table, TagIden::Id)),
+                )
+                .and_where(
+                    Expr::col((
+                        TransactionGroupTagIden::Table,
+                        TransactionGroupTagIden::GroupId,
+                    ))
+                    .eq(group_id),
+                );
+        }
+    }
+
+    query
+        .order_by((TagIden::Table, TagIden::Name), Order::Asc)
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn insert_tag(model: AddTagModel) -> DbQueryWithValues {
+    Query::insert()
+        .into_table(TagIden::Table)
+        .columns([TagIden::UserId, TagIden::Name])
+        .values_panic([model.user_id.into(), model.name.into()])
+        .returning_all()
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn update_tag(id: Uuid, user_id: Uuid, name: String) -> DbQueryWithValues {
+    Query::update()
+        .table(TagIden::Table)
+        .value(TagIden::Name, name)
+        .and_where(Expr::col(TagIden::Id).eq(id))
+        .and_where(Expr::col(TagIden::UserId).eq(user_id))
+        .returning_all()
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn delete_tag(id: Uuid, user_id: Uuid) -> DbQueryWithValues {
+    Query::delete()
+        .from_table(TagIden::Table)
+        .and_where(Expr::col(TagIden::Id).eq(id))
+        .and_where(Expr::col(TagIden::UserId).eq(user_id))
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn transaction_exists_for_user(transaction_id: Uuid, user_id: Uuid) -> DbQueryWithValues {
+    Query::select()
+        .expr(Expr::exists(
+            Query::select()
+                .from(TransactionIden::Table)
+                .and_where(Expr::col(TransactionIden::Id).eq(transaction_id))
+                .and_where(Expr::col(TransactionIden::UserId).eq(user_id))
+                .take(),
+        ))
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+/// Groups have no owner column of their own; a group belongs to the user owning its
+/// transactions.
+#[macros::named_query]
+pub fn transaction_group_exists_for_user(group_id: Uuid, user_id: Uuid) -> DbQueryWithValues {
+    Query::select()
+        .expr(Expr::exists(
+            Query::select()
+                .from(TransactionIden::Table)
+                .and_where(Expr::col(TransactionIden::GroupId).eq(group_id))
+                .and_where(Expr::col(TransactionIden::UserId).eq(user_id))
+                .take(),
+        ))
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn delete_transaction_tags(transaction_id: Uuid) -> DbQueryWithValues {
+    Query::delete()
+        .from_table(TransactionTagIden::Table)
+        .and_where(Expr::col(TransactionTagIden::TransactionId).eq(transaction_id))
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn insert_transaction_tags(transaction_id: Uuid, tag_ids: Vec<Uuid>) -> DbQueryWithValues {
+    let mut builder = Query::insert()
+        .into_table(TransactionTagIden::Table)
+        .columns([TransactionTagIden::TransactionId, TransactionTagIden::TagId])
+        .to_owned();
+
+    for tag_id in tag_ids {
+        builder.values_panic([transaction_id.into(), tag_id.into()]);
+    }
+
+    builder.build_sqlx(PostgresQueryBuilder).into()
+}
+
+/// Adds tags to a transaction, keeping the ones it already carries.
+#[macros::named_query]
+pub fn add_transaction_tags(transaction_id: Uuid, tag_ids: Vec<Uuid>) -> DbQueryWithValues {
+    let mut builder = Query::insert()
+        .into_table(TransactionTagIden::Table)
+        .columns([TransactionTagIden::TransactionId, TransactionTagIden::TagId])
+        .on_conflict(
+            OnConflict::columns([TransactionTagIden::TransactionId, TransactionTagIden::TagId])
+                .do_nothing()
+                .to_owned(),
+        )
+        .to_owned();
+
+    for tag_id in tag_ids {
+        builder.values_panic([transaction_id.into(), tag_id.into()]);
+    }
+
+    builder.build_sqlx(PostgresQueryBuilder).into()
+}
+
+#[macros::named_query]
+pub fn delete_transaction_group_tags(group_id: Uuid) -> DbQueryWithValues {
+    Query::delete()
+        .from_table(TransactionGroupTagIden::Table)
+        .and_where(Expr::col(TransactionGroupTagIden::GroupId).eq(group_id))
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn insert_transaction_group_tags(group_id: Uuid, tag_ids: Vec<Uuid>) -> DbQueryWithValues {
+    let mut builder = Query::insert()
+        .into_table(TransactionGroupTagIden::Table)
+        .columns([
+            TransactionGroupTagIden::GroupId,
+            TransactionGroupTagIden::TagId,
+        ])
+        .to_owned();
+
+    for tag_id in tag_ids {
+        builder.values_panic([group_id.into(), tag_id.into()]);
+    }
+
+    builder.build_sqlx(PostgresQueryBuilder).into()
+}
+
+/// Ids of the transactions carrying any of `tag_ids`, either directly or through the
+/// group they belong to.
+pub(crate) fn tagged_transaction_ids(tag_ids: &[Uuid]) -> SelectStatement {
+    let group_tagged = Query::select()
+        .column((TransactionIden::Table, TransactionIden::Id))
+        .from(TransactionIden::Table)
+        .inner_join(
+            TransactionGroupTagIden::Table,
+            Expr::col((
+                TransactionGroupTagIden::Table,
+                TransactionGroupTagIden::GroupId,
+            ))
+            .equals((TransactionIden::Table, TransactionIden::GroupId)),
+        )
+        .and_where(
+            Expr::col((
+                TransactionGroupTagIden::Table,
+                TransactionGroupTagIden::TagId,
+            ))
+            .is_in(tag_ids.iter().copied()),
+        )
+        .to_owned();
+
+    Query::select()
+        .column(TransactionTagIden::TransactionId)
+        .from(TransactionTagIden::Table)
+        .and_where(Expr::col(TransactionTagIden::TagId).is_in(tag_ids.iter().copied()))
+        .union(UnionType::Distinct, group_tagged)
+        .to_owned()
+}
@@ -0,0 +1,73 @@
+use sea_query::{Expr, ExprTrait, Order, PostgresQueryBuilder, Query};
+use sea_query_sqlx::SqlxBinder;
+use sqlx::types::Uuid;
+
+use crate::{
+    idens::ticker_alias_idens::UserTickerAliasIden,
+    models::ticker_alias_models::AddUserTickerAliasModel,
+};
+
+use super::DbQueryWithValues;
+
+#[macros::named_query]
+pub fn get_user_ticker_aliases(user_id: Uuid) -> DbQueryWithValues {
+    Query::select()
+        .columns([
+            UserTickerAliasIden::Id,
+            UserTickerAliasIden::UserId,
+            UserTickerAliasIden::ProviderTicker,
+            UserTickerAliasIden::AssetId,
+            UserTickerAliasIden::CreatedAt,
+        ])
+        .from(UserTickerAliasIden::Table)
+        .and_where(Expr::col(UserTickerAliasIden::UserId).eq(user_id))
+        .order_by(UserTickerAliasIden::ProviderTicker, Order::Asc)
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn get_user_ticker_alias(id: Uuid, user_id: Uuid) -> DbQueryWithValues {
+    Query::select()
+        .columns([
+            UserTickerAliasIden::Id,
+            UserTickerAliasIden::UserId,
+            UserTickerAliasIden::ProviderTicker,
+            UserTickerAliasIden::AssetId,
+            UserTickerAliasIden::CreatedAt,
+        ])
+        .from(UserTickerAliasIden::Table)
+        .and_where(Expr::col(UserTickerAliasIden::Id).eq(id))
+        .and_where(Expr::col(UserTickerAliasIden::UserId).eq(user_id))
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn insert_user_ticker_alias(model: AddUserTickerAliasModel) -> DbQueryWithValues {
+    Query::insert()
+        .into_table(UserTickerAliasIden::Table)
+        .columns([
+            UserTickerAliasIden::UserId,
+            UserTickerAliasIden::ProviderTicker,
+            UserTickerAliasIden::AssetId,
+        ])
+        .values_panic([
+            model.user_id.into(),
+            model.provider_ticker.into(),
+            model.asset_id.into(),
+        ])
+        .returning_all()
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn delete_user_ticker_alias(id: Uuid, user_id: Uuid) -> DbQueryWithValues {
+    Query::delete()
+        .from_table(UserTickerAliasIden::Table)
+        .and_where(Expr::col(UserTickerAliasIden::Id).eq(id))
+        .and_where(Expr::col(UserTickerAliasIden::UserId).eq(user_id))
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
@@ -93,6 +93,14 @@ pub fn get_transaction_with_entries(params: GetTransactionWithEntriesParams) ->
             Expr::col((TransactionIden::Table, TransactionIden::DateTransacted)).lte(date_to),
         );
     }
+    if let Some(ref tag_ids) = params.tag_ids {
+        if !tag_ids.is_empty() {
+            eligible_transactions_builder.and_where(
+                Expr::col((TransactionIden::Table, TransactionIden::Id))
+                    .in_subquery(super::tag_queries::tagged_transaction_ids(tag_ids)),
+            );
+        }
+    }
 
     match params.group_filter {
         GroupFilter::IndividualOnly => {
@@ -260,7 +268,7 @@ pub fn get_combined_transaction_ids_for_user(
         .to_owned();
 
     // --- CTE: group transactions half (DISTINCT ON tg.id) ---
-    let group_query = Query::select()
+    let mut group_query = Query::select()
         .distinct_on([(
             TransactionGroupIden::Table,
             TransactionGroupIden::TransactionGroupId,
@@ -311,6 +319,20 @@ pub fn get_combined_transaction_ids_for_user(
         .and_where(Expr::col((TransactionIden::Table, TransactionIden::UserId)).eq(params.user_id))
         .to_owned();
 
+    // A group matches when the group itself or any of its transactions carries a tag.
+    if let Some(ref tag_ids) = params.tag_ids {
+        if !tag_ids.is_empty() {
+            individual_query.and_where(
+                Expr::col((TransactionIden::Table, TransactionIden::Id))
+                    .in_subquery(super::tag_queries::tagged_transaction_ids(tag_ids)),
+            );
+            group_query.and_where(
+                Expr::col((TransactionIden::Table, TransactionIden::Id))
+                    .in_subquery(super::tag_queries::tagged_transaction_ids(tag_ids)),
+            );
+        }
+    }
+
     // --- CTE: UNION ALL ---
     let combined_cte_query = individual_query
         .union(sea_query::UnionType::All, group_query)
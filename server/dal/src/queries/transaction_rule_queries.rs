@@ -0,0 +1,261 @@
+use sea_query::{Alias, Expr, ExprTrait, Order, PostgresQueryBuilder, Query, SimpleExpr};
+use sea_query_sqlx::SqlxBinder;
+use sqlx::types::Uuid;
+
+use crate::{
+    enums::transaction_types::DatabaseTransactionTypes,
+    idens::{
+        connector_idens::ConnectorTransactionIden,
+        transaction_idens::TransactionIden,
+        transaction_rule_idens::{TransactionRuleIden, TransactionRuleTagIden},
+    },
+    models::transaction_rule_models::AddUpdateTransactionRuleModel,
+    query_params::get_transaction_rules_params::{
+        GetTransactionRulesParams, GetTransactionRulesParamsSearchType,
+    },
+};
+
+use super::DbQueryWithValues;
+
+/// Rules in the order they run: ascending priority, then oldest first.
+#[macros::named_query]
+pub fn get_transaction_rules(params: GetTransactionRulesParams) -> DbQueryWithValues {
+    let mut query = Query::select();
+
+    query
+        .columns([
+            TransactionRuleIden::Id,
+            TransactionRuleIden::UserId,
+            TransactionRuleIden::Name,
+            TransactionRuleIden::Priority,
+            TransactionRuleIden::Enabled,
+            TransactionRuleIden::DescriptionContains,
+            TransactionRuleIden::DescriptionRegex,
+            TransactionRuleIden::AmountMin,
+            TransactionRuleIden::AmountMax,
+            TransactionRuleIden::AccountId,
+            TransactionRuleIden::Counterparty,
+            TransactionRuleIden::CategoryId,
+            TransactionRuleIden::DescriptionRewrite,
+            TransactionRuleIden::Visibility,
+            TransactionRuleIden::CreatedAt,
+            TransactionRuleIden::UpdatedAt,
+        ])
+        .from(TransactionRuleIden::Table)
+        .and_where(Expr::col(TransactionRuleIden::UserId).eq(params.user_id));
+
+    match params.search_type {
+        GetTransactionRulesParamsSearchType::All => {}
+        GetTransactionRulesParamsSearchType::ById(id) => {
+            query.and_where(Expr::col(TransactionRuleIden::Id).eq(id));
+        }
+        GetTransactionRulesParamsSearchType::Enabled => {
+            query.and_where(Expr::col(TransactionRuleIden::Enabled).eq(true));
+        }
+    }
+
+    query
+        .order_by(TransactionRuleIden::Priority, Order::Asc)
+        .order_by(TransactionRuleIden::CreatedAt, Order::Asc)
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+/// Tags added by each of the user's rules.
+#[macros::named_query]
+pub fn get_transaction_rule_tags(user_id: Uuid) -> DbQueryWithValues {
+    Query::select()
+        .columns([
+            (
+                TransactionRuleTagIden::Table,
+                TransactionRuleTagIden::RuleId,
+            ),
+            (TransactionRuleTagIden::Table, TransactionRuleTagIden::TagId),
+        ])
+        .from(TransactionRuleTagIden::Table)
+        .inner_join(
+            TransactionRuleIden::Table,
+            Expr::col((TransactionRuleIden::Table, TransactionRuleIden::Id)).equals((
+                TransactionRuleTagIden::Table,
+                TransactionRuleTagIden::RuleId,
+            )),
+        )
+        .and_where(Expr::col((TransactionRuleIden::Table, TransactionRuleIden::UserId)).eq(user_id))
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn insert_transaction_rule(
+    user_id: Uuid,
+    model: AddUpdateTransactionRuleModel,
+) -> DbQueryWithValues {
+    Query::insert()
+        .into_table(TransactionRuleIden::Table)
+        .columns([
+            TransactionRuleIden::UserId,
+            TransactionRuleIden::Name,
+            TransactionRuleIden::Priority,
+            TransactionRuleIden::Enabled,
+            TransactionRuleIden::DescriptionContains,
+            TransactionRuleIden::DescriptionRegex,
+            TransactionRuleIden::AmountMin,
+            TransactionRuleIden::AmountMax,
+            TransactionRuleIden::AccountId,
+            TransactionRuleIden::Counterparty,
+            TransactionRuleIden::CategoryId,
+            TransactionRuleIden::DescriptionRewrite,
+            TransactionRuleIden::Visibility,
+        ])
+        .values_panic([
+            user_id.into(),
+            model.name.into(),
+            model.priority.into(),
+            model.enabled.into(),
+            model.description_contains.into(),
+            model.description_regex.into(),
+            model.amount_min.into(),
+            model.amount_max.into(),
+            model.account_id.into(),
+            model.counterparty.into(),
+            model.category_id.into(),
+            model.description_rewrite.into(),
+            model.visibility.into(),
+        ])
+        .returning_all()
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn update_transaction_rule(
+    id: Uuid,
+    user_id: Uuid,
+    updates: AddUpdateTransactionRuleModel,
+) -> DbQueryWithValues {
+    Query::update()
+        .table(TransactionRuleIden::Table)
+        .value(TransactionRuleIden::Name, updates.name)
+        .value(TransactionRuleIden::Priority, updates.priority)
+        .value(TransactionRuleIden::Enabled, updates.enabled)
+        .value(
+            TransactionRuleIden::DescriptionContains,
+            updates.description_contains,
+        )
+        .value(
+            TransactionRuleIden::DescriptionRegex,
+            updates.description_regex,
+        )
+        .value(TransactionRuleIden::AmountMin, updates.amount_min)
+        .value(TransactionRuleIden::AmountMax, updates.amount_max)
+        .value(TransactionRuleIden::AccountId, updates.account_id)
+        .value(TransactionRuleIden::Counterparty, updates.counterparty)
+        .value(TransactionRuleIden::CategoryId, updates.category_id)
+        .value(
+            TransactionRuleIden::DescriptionRewrite,
+            updates.description_rewrite,
+        )
+        .value(TransactionRuleIden::Visibility, updates.visibility)
+        .value(TransactionRuleIden::UpdatedAt, Expr::cust("NOW()"))
+        .and_where(Expr::col(TransactionRuleIden::Id).eq(id))
+        .and_where(Expr::col(TransactionRuleIden::UserId).eq(user_id))
+        .returning_all()
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn delete_transaction_rule(id: Uuid, user_id: Uuid) -> DbQueryWithValues {
+    Query::delete()
+        .from_table(TransactionRuleIden::Table)
+        .and_where(Expr::col(TransactionRuleIden::Id).eq(id))
+        .and_where(Expr::col(TransactionRuleIden::UserId).eq(user_id))
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn delete_transaction_rule_tags(rule_id: Uuid) -> DbQueryWithValues {
+    Query::delete()
+        .from_table(TransactionRuleTagIden::Table)
+        .and_where(Expr::col(TransactionRuleTagIden::RuleId).eq(rule_id))
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn insert_transaction_rule_tags(rule_id: Uuid, tag_ids: Vec<Uuid>) -> DbQueryWithValues {
+    let mut builder = Query::insert()
+        .into_table(TransactionRuleTagIden::Table)
+        .columns([
+            TransactionRuleTagIden::RuleId,
+            TransactionRuleTagIden::TagId,
+        ])
+        .to_owned();
+
+    for tag_id in tag_ids {
+        builder.values_panic([rule_id.into(), tag_id.into()]);
+    }
+
+    builder.build_sqlx(PostgresQueryBuilder).into()
+}
+
+/// A page of the user's regular transactions, which are the ones rules apply to, ordered
+/// by id and starting after `after`. Imports come with the counterparty their provider
+/// reported.
+#[macros::named_query]
+pub fn get_rule_candidates(user_id: Uuid, after: Option<Uuid>, limit: u64) -> DbQueryWithValues {
+    let counterparty_subquery = Query::select()
+        .column((
+            ConnectorTransactionIden::Table,
+            ConnectorTransactionIden::Counterparty,
+        ))
+        .from(ConnectorTransactionIden::Table)
+        .and_where(
+            Expr::col((
+                ConnectorTransactionIden::Table,
+                ConnectorTransactionIden::TransactionId,
+            ))
+            .equals((TransactionIden::Table, TransactionIden::Id)),
+        )
+        .and_where(
+            Expr::col((
+                ConnectorTransactionIden::Table,
+                ConnectorTransactionIden::Counterparty,
+            ))
+            .is_not_null(),
+        )
+        .limit(1)
+        .to_owned();
+
+    let mut query = Query::select();
+
+    query
+        .expr_as(
+            Expr::col((TransactionIden::Table, TransactionIden::Id)),
+            Alias::new("transaction_id"),
+        )
+        .expr_as(
+            SimpleExpr::SubQuery(
+                None,
+                Box::new(counterparty_subquery.into_sub_query_statement()),
+            ),
+            Alias::new("counterparty"),
+        )
+        .from(TransactionIden::Table)
+        .and_where(Expr::col((TransactionIden::Table, TransactionIden::UserId)).eq(user_id))
+        .and_where(
+            Expr::col((TransactionIden::Table, TransactionIden::TypeId))
+                .eq(DatabaseTransactionTypes::RegularTransaction as i32),
+        );
+
+    if let Some(after) = after {
+        query.and_where(Expr::col((TransactionIden::Table, TransactionIden::Id)).gt(after));
+    }
+
+    query
+        .order_by((TransactionIden::Table, TransactionIden::Id), Order::Asc)
+        .limit(limit)
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
@@ -0,0 +1,120 @@
+use sea_query::{Alias, Expr, ExprTrait, JoinType, Order, PostgresQueryBuilder, Query};
+use sea_query_sqlx::SqlxBinder;
+
+use crate::{
+    enums::transaction_types::DatabaseTransactionTypes,
+    idens::{
+        asset_idens::AssetsIden, connector_idens::ConnectorTransactionIden,
+        entries_idens::EntryIden, transaction_idens::TransactionIden,
+    },
+    models::asset_models::asset_type_ids,
+    query_params::get_transfer_legs_params::{
+        GetTransferLegsParams, GetTransferLegsParamsSearchType,
+    },
+};
+
+use super::DbQueryWithValues;
+
+/// Ungrouped regular transactions and cash transfers in or out that move a single currency
+/// in a single entry, oldest first. Rejected (hidden) transactions are left out; ghosts
+/// awaiting review are included.
+#[macros::named_query]
+pub fn get_transfer_legs(params: GetTransferLegsParams) -> DbQueryWithValues {
+    let other_entry = Alias::new("other_entry");
+
+    let mut query = Query::select()
+        .expr_as(
+            Expr::col((TransactionIden::Table, TransactionIden::Id)),
+            Alias::new("transaction_id"),
+        )
+        .column((TransactionIden::Table, TransactionIden::DateTransacted))
+        .column((TransactionIden::Table, TransactionIden::Visibility))
+        .column((EntryIden::Table, EntryIden::AccountId))
+        .column((EntryIden::Table, EntryIden::AssetId))
+        .column((EntryIden::Table, EntryIden::Quantity))
+        .expr_as(
+            Expr::exists(
+                Query::select()
+                    .expr(Expr::val(1))
+                    .from(ConnectorTransactionIden::Table)
+                    .and_where(
+                        Expr::col((
+                            ConnectorTransactionIden::Table,
+                            ConnectorTransactionIden::TransactionId,
+                        ))
+                        .equals((TransactionIden::Table, TransactionIden::Id)),
+                    )
+                    .to_owned(),
+            ),
+            Alias::new("imported"),
+        )
+        .from(TransactionIden::Table)
+        .join(
+            JoinType::Join,
+            EntryIden::Table,
+            Expr::col((EntryIden::Table, EntryIden::TransactionId))
+                .equals((TransactionIden::Table, TransactionIden::Id)),
+        )
+        .join(
+            JoinType::Join,
+            AssetsIden::Table,
+            Expr::col((AssetsIden::Table, AssetsIden::Id))
+                .equals((EntryIden::Table, EntryIden::AssetId)),
+        )
+        .and_where(Expr::col((TransactionIden::Table, TransactionIden::UserId)).eq(params.user_id))
+        .and_where(
+            Expr::col((TransactionIden::Table, TransactionIden::TypeId)).is_in([
+                DatabaseTransactionTypes::RegularTransaction as i32,
+                DatabaseTransactionTypes::CashTransferOut as i32,
+                DatabaseTransactionTypes::CashTransferIn as i32,
+            ]),
+        )
+        .and_where(
+            Expr::col((TransactionIden::Table, TransactionIden::Visibility))
+                .is_in(["default", "ghost"]),
+        )
+        .and_where(Expr::col((TransactionIden::Table, TransactionIden::GroupId)).is_null())
+        .and_where(
+            Expr::col((AssetsIden::Table, AssetsIden::AssetType)).eq(asset_type_ids::CURRENCY),
+        )
+        .and_where(
+            Expr::exists(
+                Query::select()
+                    .expr(Expr::val(1))
+                    .from_as(EntryIden::Table, other_entry.clone())
+                    .and_where(
+                        Expr::col((other_entry.clone(), EntryIden::TransactionId))
+                            .equals((TransactionIden::Table, TransactionIden::Id)),
+                    )
+                    .and_where(
+                        Expr::col((other_entry.clone(), EntryIden::Id))
+                            .ne(Expr::col((EntryIden::Table, EntryIden::Id))),
+                    )
+                    .to_owned(),
+            )
+            .not(),
+        )
+        .to_owned();
+
+    match params.search_type {
+        GetTransferLegsParamsSearchType::ByDateRange { from, to } => {
+            query.and_where(
+                Expr::col((TransactionIden::Table, TransactionIden::DateTransacted))
+                    .between(from, to),
+            );
+        }
+        GetTransferLegsParamsSearchType::ByTransactionIds(transaction_ids) => {
+            query.and_where(
+                Expr::col((TransactionIden::Table, TransactionIden::Id)).is_in(transaction_ids),
+            );
+        }
+    }
+
+    query
+        .order_by(
+            (TransactionIden::Table, TransactionIden::DateTransacted),
+            Order::Asc,
+        )
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
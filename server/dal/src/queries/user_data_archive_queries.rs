@@ -0,0 +1,446 @@
+use sea_query::{Alias, Expr, ExprTrait, JoinType, Order, PostgresQueryBuilder, Query};
+use sea_query_sqlx::SqlxBinder;
+use sqlx::types::Uuid;
+
+use super::DbQueryWithValues;
+use crate::idens::{
+    account_idens::AccountIden,
+    asset_idens::{AssetHistoryIden, AssetPairsIden, AssetsIden},
+    connector_idens::{
+        ConnectorBindingIden, ConnectorConnectionIden, ConnectorProviderAccountIden,
+        ConnectorProviderIden, ConnectorTransactionIden,
+    },
+    entries_idens::EntryIden,
+    transaction_idens::{
+        TransactionDescriptionsIden, TransactionDividendsIden, TransactionGroupIden,
+        TransactionIden,
+    },
+};
+use crate::models::user_data_archive_models::InsertArchivedTransactionModel;
+
+/// Whether the user already has accounts or transactions, i.e. is not a fresh user.
+#[macros::named_query]
+pub fn user_has_ledger_data(user_id: Uuid) -> DbQueryWithValues {
+    Query::select()
+        .expr_as(
+            Expr::exists(
+                Query::select()
+                    .expr(Expr::val(1))
+                    .from(AccountIden::Table)
+                    .and_where(Expr::col(AccountIden::UserId).eq(user_id))
+                    .to_owned(),
+            )
+            .or(Expr::exists(
+                Query::select()
+                    .expr(Expr::val(1))
+                    .from(TransactionIden::Table)
+                    .and_where(Expr::col(TransactionIden::UserId).eq(user_id))
+                    .to_owned(),
+            )),
+            Alias::new("exists"),
+        )
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn get_archive_accounts(user_id: Uuid) -> DbQueryWithValues {
+    Query::select()
+        .columns([
+            AccountIden::Id,
+            AccountIden::AccountName,
+            AccountIden::AccountType,
+            AccountIden::LiquidityType,
+            AccountIden::Active,
+            AccountIden::OwnershipShare,
+            AccountIden::CostBasisMethod,
+        ])
+        .from(AccountIden::Table)
+        .and_where(Expr::col(AccountIden::UserId).eq(user_id))
+        .order_by(AccountIden::Id, Order::Asc)
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn get_archive_custom_assets(user_id: Uuid) -> DbQueryWithValues {
+    Query::select()
+        .columns([
+            AssetsIden::Id,
+            AssetsIden::AssetType,
+            AssetsIden::AssetName,
+            AssetsIden::Ticker,
+            AssetsIden::BasePairId,
+        ])
+        .from(AssetsIden::Table)
+        .and_where(Expr::col(AssetsIden::UserId).eq(user_id))
+        .order_by(AssetsIden::Id, Order::Asc)
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+/// Pairs with at least one side among `asset_ids`.
+#[macros::named_query]
+pub fn get_archive_asset_pairs(asset_ids: Vec<i32>) -> DbQueryWithValues {
+    Query::select()
+        .columns([
+            AssetPairsIden::Id,
+            AssetPairsIden::Pair1,
+            AssetPairsIden::Pair2,
+        ])
+        .from(AssetPairsIden::Table)
+        .cond_where(
+            Expr::col(AssetPairsIden::Pair1)
+                .is_in(asset_ids.clone())
+                .or(Expr::col(AssetPairsIden::Pair2).is_in(asset_ids)),
+        )
+        .order_by(AssetPairsIden::Id, Order::Asc)
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn get_archive_asset_rates(pair_ids: Vec<i32>) -> DbQueryWithValues {
+    Query::select()
+        .columns([
+            AssetHistoryIden::PairId,
+            AssetHistoryIden::Rate,
+            AssetHistoryIden::RecordedAt,
+        ])
+        .from(AssetHistoryIden::Table)
+        .and_where(Expr::col(AssetHistoryIden::PairId).is_in(pair_ids))
+        .order_by(AssetHistoryIden::PairId, Order::Asc)
+        .order_by(AssetHistoryIden::RecordedAt, Order::Asc)
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+/// Groups that hold at least one of the user's transactions.
+#[macros::named_query]
+pub fn get_archive_transaction_groups(user_id: Uuid) -> DbQueryWithValues {
+    Query::select()
+        .expr_as(
+            Expr::col(TransactionGroupIden::TransactionGroupId),
+            Alias::new("id"),
+        )
+        .columns([
+            TransactionGroupIden::CategoryId,
+            TransactionGroupIden::Description,
+            TransactionGroupIden::DateAdded,
+        ])
+        .from(TransactionGroupIden::Table)
+        .and_where(
+            Expr::col(TransactionGroupIden::TransactionGroupId).in_subquery(
+                Query::select()
+                    .column(TransactionIden::GroupId)
+                    .from(TransactionIden::Table)
+                    .and_where(Expr::col(TransactionIden::UserId).eq(user_id))
+                    .and_where(Expr::col(TransactionIden::GroupId).is_not_null())
+                    .to_owned(),
+            ),
+        )
+        .order_by(TransactionGroupIden::DateAdded, Order::Asc)
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn get_archive_transactions(user_id: Uuid) -> DbQueryWithValues {
+    Query::select()
+        .column((TransactionIden::Table, TransactionIden::Id))
+        .column((TransactionIden::Table, TransactionIden::GroupId))
+        .column((TransactionIden::Table, TransactionIden::TypeId))
+        .column((TransactionIden::Table, TransactionIden::DateTransacted))
+        .column((TransactionIden::Table, TransactionIden::Visibility))
+        .column((
+            TransactionDescriptionsIden::Table,
+            TransactionDescriptionsIden::Description,
+        ))
+        .column((
+            TransactionDividendsIden::Table,
+            TransactionDividendsIden::SourceAssetId,
+        ))
+        .from(TransactionIden::Table)
+        .join(
+            JoinType::LeftJoin,
+            TransactionDescriptionsIden::Table,
+            Expr::col((
+                TransactionDescriptionsIden::Table,
+                TransactionDescriptionsIden::TransactionId,
+            ))
+            .equals((TransactionIden::Table, TransactionIden::Id)),
+        )
+        .join(
+            JoinType::LeftJoin,
+            TransactionDividendsIden::Table,
+            Expr::col((
+                TransactionDividendsIden::Table,
+                TransactionDividendsIden::TransactionId,
+            ))
+            .equals((TransactionIden::Table, TransactionIden::Id)),
+        )
+        .and_where(Expr::col((TransactionIden::Table, TransactionIden::UserId)).eq(user_id))
+        .order_by(
+            (TransactionIden::Table, TransactionIden::DateTransacted),
+            Order::Asc,
+        )
+        .order_by((TransactionIden::Table, TransactionIden::Id), Order::Asc)
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn get_archive_entries(user_id: Uuid) -> DbQueryWithValues {
+    Query::select()
+        .column((EntryIden::Table, EntryIden::TransactionId))
+        .column((EntryIden::Table, EntryIden::AccountId))
+        .column((EntryIden::Table, EntryIden::AssetId))
+        .column((EntryIden::Table, EntryIden::Quantity))
+        .column((EntryIden::Table, EntryIden::CategoryId))
+        .from(EntryIden::Table)
+        .inner_join(
+            TransactionIden::Table,
+            Expr::col((EntryIden::Table, EntryIden::TransactionId))
+                .equals((TransactionIden::Table, TransactionIden::Id)),
+        )
+        .and_where(Expr::col((TransactionIden::Table, TransactionIden::UserId)).eq(user_id))
+        .order_by((EntryIden::Table, EntryIden::Id), Order::Asc)
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn get_archive_connector_connections(user_id: Uuid) -> DbQueryWithValues {
+    Query::select()
+        .column((ConnectorConnectionIden::Table, ConnectorConnectionIden::Id))
+        .expr_as(
+            Expr::col((ConnectorProviderIden::Table, ConnectorProviderIden::Kind)),
+            Alias::new("provider_kind"),
+        )
+        .column((
+            ConnectorConnectionIden::Table,
+            ConnectorConnectionIden::CredentialMode,
+        ))
+        .column((
+            ConnectorConnectionIden::Table,
+            ConnectorConnectionIden::Status,
+        ))
+        .column((
+            ConnectorConnectionIden::Table,
+            ConnectorConnectionIden::ConsentExpiresAt,
+        ))
+        .from(ConnectorConnectionIden::Table)
+        .inner_join(
+            ConnectorProviderIden::Table,
+            Expr::col((ConnectorProviderIden::Table, ConnectorProviderIden::Id)).equals((
+                ConnectorConnectionIden::Table,
+                ConnectorConnectionIden::ProviderId,
+            )),
+        )
+        .and_where(
+            Expr::col((
+                ConnectorConnectionIden::Table,
+                ConnectorConnectionIden::UserId,
+            ))
+            .eq(user_id),
+        )
+        .order_by(
+            (
+                ConnectorConnectionIden::Table,
+                ConnectorConnectionIden::CreatedAt,
+            ),
+            Order::Asc,
+        )
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn get_archive_connector_provider_accounts(user_id: Uuid) -> DbQueryWithValues {
+    Query::select()
+        .column((
+            ConnectorProviderAccountIden::Table,
+            ConnectorProviderAccountIden::Id,
+        ))
+        .column((
+            ConnectorProviderAccountIden::Table,
+            ConnectorProviderAccountIden::ConnectionId,
+        ))
+        .column((
+            ConnectorProviderAccountIden::Table,
+            ConnectorProviderAccountIden::ExternalAccountId,
+        ))
+        .from(ConnectorProviderAccountIden::Table)
+        .inner_join(
+            ConnectorConnectionIden::Table,
+            Expr::col((ConnectorConnectionIden::Table, ConnectorConnectionIden::Id)).equals((
+                ConnectorProviderAccountIden::Table,
+                ConnectorProviderAccountIden::ConnectionId,
+            )),
+        )
+        .and_where(
+            Expr::col((
+                ConnectorConnectionIden::Table,
+                ConnectorConnectionIden::UserId,
+            ))
+            .eq(user_id),
+        )
+        .order_by(
+            (
+                ConnectorProviderAccountIden::Table,
+                ConnectorProviderAccountIden::CreatedAt,
+            ),
+            Order::Asc,
+        )
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+#[macros::named_query]
+pub fn get_archive_connector_bindings(user_id: Uuid) -> DbQueryWithValues {
+    Query::select()
+        .column((ConnectorBindingIden::Table, ConnectorBindingIden::Id))
+        .column((
+            ConnectorBindingIden::Table,
+            ConnectorBindingIden::ProviderAccountId,
+        ))
+        .column((
+            ConnectorBindingIden::Table,
+            ConnectorBindingIden::SvertoAccountId,
+        ))
+        .column((ConnectorBindingIden::Table, ConnectorBindingIden::WriteMode))
+        .column((ConnectorBindingIden::Table, ConnectorBindingIden::Status))
+        .from(ConnectorBindingIden::Table)
+        .inner_join(
+            ConnectorProviderAccountIden::Table,
+            Expr::col((
+                ConnectorProviderAccountIden::Table,
+                ConnectorProviderAccountIden::Id,
+            ))
+            .equals((
+                ConnectorBindingIden::Table,
+                ConnectorBindingIden::ProviderAccountId,
+            )),
+        )
+        .inner_join(
+            ConnectorConnectionIden::Table,
+            Expr::col((ConnectorConnectionIden::Table, ConnectorConnectionIden::Id)).equals((
+                ConnectorProviderAccountIden::Table,
+                ConnectorProviderAccountIden::ConnectionId,
+            )),
+        )
+        .and_where(
+            Expr::col((
+                ConnectorConnectionIden::Table,
+                ConnectorConnectionIden::UserId,
+            ))
+            .eq(user_id),
+        )
+        .order_by(
+            (ConnectorBindingIden::Table, ConnectorBindingIden::CreatedAt),
+            Order::Asc,
+        )
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+/// The import ledger of every binding the user owns, which lets a re-created binding
+/// recognise transactions it imported before the move.
+#[macros::named_query]
+pub fn get_archive_connector_transactions(user_id: Uuid) -> DbQueryWithValues {
+    Query::select()
+        .column((
+            ConnectorTransactionIden::Table,
+            ConnectorTransactionIden::BindingId,
+        ))
+        .column((
+            ConnectorTransactionIden::Table,
+            ConnectorTransactionIden::TransactionId,
+        ))
+        .column((
+            ConnectorTransactionIden::Table,
+            ConnectorTransactionIden::ExternalId,
+        ))
+        .column((
+            ConnectorTransactionIden::Table,
+            ConnectorTransactionIden::ExternalHash,
+        ))
+        .column((
+            ConnectorTransactionIden::Table,
+            ConnectorTransactionIden::EditedByUser,
+        ))
+        .column((
+            ConnectorTransactionIden::Table,
+            ConnectorTransactionIden::Counterparty,
+        ))
+        .from(ConnectorTransactionIden::Table)
+        .inner_join(
+            ConnectorBindingIden::Table,
+            Expr::col((ConnectorBindingIden::Table, ConnectorBindingIden::Id)).equals((
+                ConnectorTransactionIden::Table,
+                ConnectorTransactionIden::BindingId,
+            )),
+        )
+        .inner_join(
+            ConnectorProviderAccountIden::Table,
+            Expr::col((
+                ConnectorProviderAccountIden::Table,
+                ConnectorProviderAccountIden::Id,
+            ))
+            .equals((
+                ConnectorBindingIden::Table,
+                ConnectorBindingIden::ProviderAccountId,
+            )),
+        )
+        .inner_join(
+            ConnectorConnectionIden::Table,
+            Expr::col((ConnectorConnectionIden::Table, ConnectorConnectionIden::Id)).equals((
+                ConnectorProviderAccountIden::Table,
+                ConnectorProviderAccountIden::ConnectionId,
+            )),
+        )
+        .and_where(
+            Expr::col((
+                ConnectorConnectionIden::Table,
+                ConnectorConnectionIden::UserId,
+            ))
+            .eq(user_id),
+        )
+        .order_by(
+            (
+                ConnectorTransactionIden::Table,
+                ConnectorTransactionIden::ImportedAt,
+            ),
+            Order::Asc,
+        )
+        .build_sqlx(PostgresQueryBuilder)
+        .into()
+}
+
+/// Inserts transactions and returns their new ids in input order.
+#[macros::named_query]
+pub fn insert_archived_transactions(
+    models: Vec<InsertArchivedTransactionModel>,
+) -> DbQueryWithValues {
+    let mut builder = Query::insert()
+        .into_table(TransactionIden::Table)
+        .columns([
+            TransactionIden::GroupId,
+            TransactionIden::UserId,
+            TransactionIden::TypeId,
+            TransactionIden::DateTransacted,
+            TransactionIden::Visibility,
+        ])
+        .returning_col(TransactionIden::Id)
+        .to_owned();
+    for model in models {
+        builder.values_panic([
+            model.group_id.into(),
+            model.user_id.into(),
+            model.type_id.into(),
+            model.date_transacted.into(),
+            model.visibility.into(),
+        ]);
+    }
+    builder.build_sqlx(PostgresQueryBuilder).into()
+}
@@ -1,5 +1,7 @@
 use pgvector::Vector;
+use rust_decimal::Decimal;
 use sqlx::types::Uuid;
+use time::OffsetDateTime;
 
 pub struct SearchTransactionsParams {
     pub user_id: Uuid,
@@ -25,15 +27,57 @@ pub struct ListAccountsParams {
     pub user_id: Uuid,
 }
 
+/// A query vector and the model that produced it. Searches compare it only with vectors
+/// stored by the same model and of the same length.
+pub struct EmbeddingSearch {
+    pub model: String,
+    pub vector: Vector,
+}
+
+impl EmbeddingSearch {
+    pub fn dims(&self) -> i32 {
+        self.vector.as_slice().len() as i32
+    }
+}
+
 pub struct SearchCategoriesParams {
     pub user_id: Uuid,
-    pub embedding: Option<Vector>,
+    pub embedding: Option<EmbeddingSearch>,
     pub limit: Option<i64>,
 }
 
 pub struct SearchAssetsParams {
     pub user_id: Uuid,
     pub query: Option<String>,
-    pub embedding: Option<Vector>,
+    pub embedding: Option<EmbeddingSearch>,
     pub limit: Option<i64>,
 }
+
+/// Transactions whose entries still carry `category_id`, the category imports land in.
+pub struct UncategorizedTransactionsParams {
+    pub user_id: Uuid,
+    pub category_id: i32,
+    /// Only transactions imported by this connector binding.
+    pub binding_id: Option<Uuid>,
+    pub transaction_ids: Option<Vec<Uuid>>,
+    /// Leave out transactions that already have a suggestion.
+    pub skip_suggested: bool,
+    pub limit: u64,
+}
+
+/// Outflows in `date_from..date_to` far above the user's usual spend in the same
+/// category, judged against their outflows in `history_from..date_from`.
+pub struct UnusualTransactionsParams {
+    pub user_id: Uuid,
+    pub currency_asset_id: i32,
+    pub date_from: OffsetDateTime,
+    pub date_to: OffsetDateTime,
+    pub history_from: OffsetDateTime,
+    /// Fewest past transactions a category needs before it is judged.
+    pub min_samples: i64,
+    /// Standard deviations above the mean an amount must be.
+    pub deviations: Decimal,
+    /// Multiple of the mean an amount must reach as well.
+    pub min_ratio: Decimal,
+    pub limit: i64,
+}
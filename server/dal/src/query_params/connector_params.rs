@@ -89,3 +89,41 @@ impl GetConnectorBindingsParams {
         }
     }
 }
+
+/// Open (not dismissed) import issues on the user's bindings.
+#[derive(Debug)]
+pub struct GetConnectorImportIssuesParams {
+    pub user_id: Uuid,
+    pub search_type: GetConnectorImportIssuesParamsSearchType,
+}
+
+#[derive(Debug)]
+pub enum GetConnectorImportIssuesParamsSearchType {
+    ByBinding(Uuid),
+    ById(Uuid),
+    /// Unresolved transactions whose instrument or currency is the identifier.
+    UnresolvedByIdentifier(String),
+}
+
+impl GetConnectorImportIssuesParams {
+    pub fn by_binding(user_id: Uuid, binding_id: Uuid) -> Self {
+        Self {
+            user_id,
+            search_type: GetConnectorImportIssuesParamsSearchType::ByBinding(binding_id),
+        }
+    }
+    pub fn by_id(user_id: Uuid, id: Uuid) -> Self {
+        Self {
+            user_id,
+            search_type: GetConnectorImportIssuesParamsSearchType::ById(id),
+        }
+    }
+    pub fn unresolved_by_identifier(user_id: Uuid, identifier: String) -> Self {
+        Self {
+            user_id,
+            search_type: GetConnectorImportIssuesParamsSearchType::UnresolvedByIdentifier(
+                identifier,
+            ),
+        }
+    }
+}
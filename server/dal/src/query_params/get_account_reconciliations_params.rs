@@ -0,0 +1,39 @@
+use sqlx::types::Uuid;
+
+pub struct GetAccountReconciliationsParams {
+    pub user_id: Uuid,
+    pub account_id: Uuid,
+    pub search_type: GetAccountReconciliationsParamsSearchType,
+}
+
+impl GetAccountReconciliationsParams {
+    pub fn by_id(user_id: Uuid, account_id: Uuid, id: Uuid) -> Self {
+        Self {
+            user_id,
+            account_id,
+            search_type: GetAccountReconciliationsParamsSearchType::ById(id),
+        }
+    }
+
+    pub fn by_account(user_id: Uuid, account_id: Uuid) -> Self {
+        Self {
+            user_id,
+            account_id,
+            search_type: GetAccountReconciliationsParamsSearchType::All,
+        }
+    }
+
+    pub fn by_asset(user_id: Uuid, account_id: Uuid, asset_id: i32) -> Self {
+        Self {
+            user_id,
+            account_id,
+            search_type: GetAccountReconciliationsParamsSearchType::ByAsset(asset_id),
+        }
+    }
+}
+
+pub enum GetAccountReconciliationsParamsSearchType {
+    All,
+    ById(Uuid),
+    ByAsset(i32),
+}
@@ -31,6 +31,17 @@ impl GetAccountsParams {
         }
     }
 
+    /// Includes deactivated accounts, whose history still feeds the portfolio.
+    pub fn all_by_user_id(user_id: Uuid) -> Self {
+        Self {
+            search_type: GetAccountsParamsSeachType::ByUserId(user_id),
+            include_metadata: false,
+            include_inactive: true,
+            include_suggested_currency: false,
+            user_id: None,
+        }
+    }
+
     pub fn by_ids(ids: HashSet<Uuid>) -> Self {
         Self {
             search_type: GetAccountsParamsSeachType::ByIds(ids),
@@ -0,0 +1,38 @@
+use sqlx::types::Uuid;
+use time::OffsetDateTime;
+
+pub struct GetBudgetsParams {
+    pub user_id: Uuid,
+    pub search_type: GetBudgetsParamsSearchType,
+}
+
+impl GetBudgetsParams {
+    pub fn by_id(user_id: Uuid, id: Uuid) -> Self {
+        Self {
+            user_id,
+            search_type: GetBudgetsParamsSearchType::ById(id),
+        }
+    }
+
+    pub fn all(user_id: Uuid) -> Self {
+        Self {
+            user_id,
+            search_type: GetBudgetsParamsSearchType::All,
+        }
+    }
+}
+
+pub enum GetBudgetsParamsSearchType {
+    All,
+    ById(Uuid),
+}
+
+/// Selects the entries counted against a budget: either a single category or
+/// every category of a category type, within `[start, end)`.
+pub struct GetBudgetSpendingParams {
+    pub user_id: Uuid,
+    pub category_id: Option<i32>,
+    pub category_type_id: Option<i32>,
+    pub start: OffsetDateTime,
+    pub end: OffsetDateTime,
+}
@@ -6,4 +6,5 @@ pub struct GetCombinedTransactionsParams {
     pub user_id: Uuid,
     pub pagination: PaginationMode,
     pub search_query: Option<String>,
+    pub tag_ids: Option<Vec<Uuid>>,
 }
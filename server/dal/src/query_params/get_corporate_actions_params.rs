@@ -0,0 +1,27 @@
+use sqlx::types::Uuid;
+
+pub struct GetCorporateActionsParams {
+    pub user_id: Uuid,
+    pub search_type: GetCorporateActionsParamsSearchType,
+}
+
+impl GetCorporateActionsParams {
+    pub fn all(user_id: Uuid) -> Self {
+        Self {
+            user_id,
+            search_type: GetCorporateActionsParamsSearchType::All,
+        }
+    }
+
+    pub fn by_id(user_id: Uuid, id: Uuid) -> Self {
+        Self {
+            user_id,
+            search_type: GetCorporateActionsParamsSearchType::ById(id),
+        }
+    }
+}
+
+pub enum GetCorporateActionsParamsSearchType {
+    All,
+    ById(Uuid),
+}
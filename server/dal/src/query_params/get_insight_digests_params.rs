@@ -0,0 +1,47 @@
+use sqlx::types::Uuid;
+use time::OffsetDateTime;
+
+use super::paging_params::PagingParams;
+
+pub struct GetInsightDigestsParams {
+    pub user_id: Uuid,
+    pub search_type: GetInsightDigestsParamsSearchType,
+    pub paging: Option<PagingParams>,
+}
+
+impl GetInsightDigestsParams {
+    pub fn all(user_id: Uuid, period: Option<String>, start: u64, count: u64) -> Self {
+        Self {
+            user_id,
+            search_type: GetInsightDigestsParamsSearchType::All { period },
+            paging: Some(PagingParams { start, count }),
+        }
+    }
+
+    pub fn by_id(user_id: Uuid, id: Uuid) -> Self {
+        Self {
+            user_id,
+            search_type: GetInsightDigestsParamsSearchType::ById(id),
+            paging: None,
+        }
+    }
+}
+
+pub enum GetInsightDigestsParamsSearchType {
+    /// Newest period first, optionally of one period kind only.
+    All {
+        period: Option<String>,
+    },
+    ById(Uuid),
+}
+
+/// Users with a base currency and transactions before `period_end` that have no
+/// digest yet for the period starting at `period_start`, ordered by id and starting
+/// after `after`.
+pub struct GetInsightDigestCandidatesParams {
+    pub period: String,
+    pub period_start: OffsetDateTime,
+    pub period_end: OffsetDateTime,
+    pub after: Option<Uuid>,
+    pub limit: u64,
+}
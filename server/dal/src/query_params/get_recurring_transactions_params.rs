@@ -0,0 +1,51 @@
+use sqlx::types::Uuid;
+use time::OffsetDateTime;
+
+pub struct GetRecurringTransactionsParams {
+    pub search_type: GetRecurringTransactionsParamsSearchType,
+}
+
+impl GetRecurringTransactionsParams {
+    pub fn by_id(user_id: Uuid, id: Uuid) -> Self {
+        Self {
+            search_type: GetRecurringTransactionsParamsSearchType::ById { user_id, id },
+        }
+    }
+
+    pub fn all(user_id: Uuid) -> Self {
+        Self {
+            search_type: GetRecurringTransactionsParamsSearchType::All(user_id),
+        }
+    }
+
+    /// Active templates of every user whose next occurrence is at or before `now`,
+    /// by id and starting after `after`, so callers can page past templates that fail.
+    pub fn due(now: OffsetDateTime, after: Option<Uuid>, limit: u64) -> Self {
+        Self {
+            search_type: GetRecurringTransactionsParamsSearchType::Due { now, after, limit },
+        }
+    }
+}
+
+pub enum GetRecurringTransactionsParamsSearchType {
+    All(Uuid),
+    ById {
+        user_id: Uuid,
+        id: Uuid,
+    },
+    Due {
+        now: OffsetDateTime,
+        after: Option<Uuid>,
+        limit: u64,
+    },
+}
+
+/// Moves a template's schedule forward after its occurrences were materialized.
+/// Only applies while `next_occurrence` still equals `expected_next_occurrence`,
+/// so two workers cannot materialize the same occurrence.
+pub struct AdvanceRecurringTransactionParams {
+    pub id: Uuid,
+    pub expected_next_occurrence: OffsetDateTime,
+    pub next_occurrence: Option<OffsetDateTime>,
+    pub last_occurrence: OffsetDateTime,
+}
@@ -0,0 +1,52 @@
+use sqlx::types::Uuid;
+use time::OffsetDateTime;
+
+use super::paging_params::PagingParams;
+
+pub struct GetSpendingAlertsParams {
+    pub user_id: Uuid,
+    pub search_type: GetSpendingAlertsParamsSearchType,
+    pub paging: Option<PagingParams>,
+}
+
+impl GetSpendingAlertsParams {
+    pub fn all(user_id: Uuid, include_dismissed: bool, start: u64, count: u64) -> Self {
+        Self {
+            user_id,
+            search_type: GetSpendingAlertsParamsSearchType::All { include_dismissed },
+            paging: Some(PagingParams { start, count }),
+        }
+    }
+
+    pub fn by_id(user_id: Uuid, id: Uuid) -> Self {
+        Self {
+            user_id,
+            search_type: GetSpendingAlertsParamsSearchType::ById(id),
+            paging: None,
+        }
+    }
+}
+
+pub enum GetSpendingAlertsParamsSearchType {
+    /// Newest first.
+    All {
+        include_dismissed: bool,
+    },
+    ById(Uuid),
+}
+
+/// Visible regular transactions of a user the detector looks at.
+pub struct GetSpendingAlertChargesParams {
+    pub user_id: Uuid,
+    pub search_type: GetSpendingAlertChargesParamsSearchType,
+}
+
+pub enum GetSpendingAlertChargesParamsSearchType {
+    /// The transactions to check.
+    ByIds(Vec<Uuid>),
+    /// The history they are checked against, dated within `from..to`.
+    Range {
+        from: OffsetDateTime,
+        to: OffsetDateTime,
+    },
+}
@@ -0,0 +1,8 @@
+use sqlx::types::Uuid;
+use time::OffsetDateTime;
+
+/// Regular transactions of a user dated on or after `since`.
+pub struct GetSubscriptionChargesParams {
+    pub user_id: Uuid,
+    pub since: OffsetDateTime,
+}
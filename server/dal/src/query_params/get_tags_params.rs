@@ -0,0 +1,50 @@
+use sqlx::types::Uuid;
+
+pub struct GetTagsParams {
+    pub user_id: Uuid,
+    pub search_type: GetTagsParamsSearchType,
+}
+
+impl GetTagsParams {
+    pub fn all(user_id: Uuid) -> Self {
+        Self {
+            user_id,
+            search_type: GetTagsParamsSearchType::All,
+        }
+    }
+
+    pub fn by_id(user_id: Uuid, id: Uuid) -> Self {
+        Self {
+            user_id,
+            search_type: GetTagsParamsSearchType::ByIds(vec![id]),
+        }
+    }
+
+    pub fn by_ids(user_id: Uuid, ids: Vec<Uuid>) -> Self {
+        Self {
+            user_id,
+            search_type: GetTagsParamsSearchType::ByIds(ids),
+        }
+    }
+
+    pub fn by_transaction(user_id: Uuid, transaction_id: Uuid) -> Self {
+        Self {
+            user_id,
+            search_type: GetTagsParamsSearchType::ByTransaction(transaction_id),
+        }
+    }
+
+    pub fn by_group(user_id: Uuid, group_id: Uuid) -> Self {
+        Self {
+            user_id,
+            search_type: GetTagsParamsSearchType::ByGroup(group_id),
+        }
+    }
+}
+
+pub enum GetTagsParamsSearchType {
+    All,
+    ByIds(Vec<Uuid>),
+    ByTransaction(Uuid),
+    ByGroup(Uuid),
+}
@@ -0,0 +1,36 @@
+use sqlx::types::Uuid;
+
+pub struct GetTransactionRulesParams {
+    pub user_id: Uuid,
+    pub search_type: GetTransactionRulesParamsSearchType,
+}
+
+impl GetTransactionRulesParams {
+    pub fn all(user_id: Uuid) -> Self {
+        Self {
+            user_id,
+            search_type: GetTransactionRulesParamsSearchType::All,
+        }
+    }
+
+    pub fn by_id(user_id: Uuid, id: Uuid) -> Self {
+        Self {
+            user_id,
+            search_type: GetTransactionRulesParamsSearchType::ById(id),
+        }
+    }
+
+    pub fn enabled(user_id: Uuid) -> Self {
+        Self {
+            user_id,
+            search_type: GetTransactionRulesParamsSearchType::Enabled,
+        }
+    }
+}
+
+pub enum GetTransactionRulesParamsSearchType {
+    All,
+    ById(Uuid),
+    /// Only rules that are switched on.
+    Enabled,
+}
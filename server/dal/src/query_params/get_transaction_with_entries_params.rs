@@ -21,6 +21,7 @@ pub struct GetTransactionWithEntriesParams {
     pub transaction_type_ids: Option<Vec<i32>>,
     pub date_from: Option<OffsetDateTime>,
     pub date_to: Option<OffsetDateTime>,
+    pub tag_ids: Option<Vec<Uuid>>,
 }
 
 impl GetTransactionWithEntriesParams {
@@ -36,6 +37,7 @@ impl GetTransactionWithEntriesParams {
             transaction_type_ids: None,
             date_from: None,
             date_to: None,
+            tag_ids: None,
         }
     }
 
@@ -53,6 +55,7 @@ impl GetTransactionWithEntriesParams {
             transaction_type_ids: None,
             date_from: None,
             date_to: None,
+            tag_ids: None,
         }
     }
 
@@ -68,6 +71,7 @@ impl GetTransactionWithEntriesParams {
             transaction_type_ids: None,
             date_from: None,
             date_to: None,
+            tag_ids: None,
         }
     }
 
@@ -83,6 +87,7 @@ impl GetTransactionWithEntriesParams {
             transaction_type_ids: None,
             date_from: None,
             date_to: None,
+            tag_ids: None,
         }
     }
 
@@ -98,6 +103,7 @@ impl GetTransactionWithEntriesParams {
             transaction_type_ids: None,
             date_from: None,
             date_to: None,
+            tag_ids: None,
         }
     }
 
@@ -113,6 +119,7 @@ impl GetTransactionWithEntriesParams {
             transaction_type_ids: None,
             date_from: None,
             date_to: None,
+            tag_ids: None,
         }
     }
 
@@ -132,6 +139,7 @@ impl GetTransactionWithEntriesParams {
             transaction_type_ids: None,
             date_from: None,
             date_to: None,
+            tag_ids: None,
         }
     }
 
@@ -147,6 +155,7 @@ impl GetTransactionWithEntriesParams {
             transaction_type_ids: None,
             date_from: None,
             date_to: None,
+            tag_ids: None,
         }
     }
 }
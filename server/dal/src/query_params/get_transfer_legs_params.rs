@@ -0,0 +1,32 @@
+use sqlx::types::Uuid;
+use time::OffsetDateTime;
+
+/// Possible transfer legs of a user.
+pub struct GetTransferLegsParams {
+    pub user_id: Uuid,
+    pub search_type: GetTransferLegsParamsSearchType,
+}
+
+pub enum GetTransferLegsParamsSearchType {
+    /// Dated within `from..=to`.
+    ByDateRange {
+        from: OffsetDateTime,
+        to: OffsetDateTime,
+    },
+    ByTransactionIds(Vec<Uuid>),
+}
+
+impl GetTransferLegsParams {
+    pub fn by_date_range(user_id: Uuid, from: OffsetDateTime, to: OffsetDateTime) -> Self {
+        Self {
+            user_id,
+            search_type: GetTransferLegsParamsSearchType::ByDateRange { from, to },
+        }
+    }
+    pub fn by_transaction_ids(user_id: Uuid, transaction_ids: Vec<Uuid>) -> Self {
+        Self {
+            user_id,
+            search_type: GetTransferLegsParamsSearchType::ByTransactionIds(transaction_ids),
+        }
+    }
+}
@@ -1,14 +1,24 @@
 pub mod ai_conversation_params;
 pub mod ai_search_params;
 pub mod connector_params;
+pub mod get_account_reconciliations_params;
 pub mod get_accounts_params;
 pub mod get_assets_params;
 pub mod get_binned_entries_params;
+pub mod get_budgets_params;
 pub mod get_categories_params;
 pub mod get_category_count_params;
 pub mod get_category_types_params;
 pub mod get_combined_transactions_params;
+pub mod get_corporate_actions_params;
+pub mod get_insight_digests_params;
 pub mod get_rates_params;
+pub mod get_recurring_transactions_params;
+pub mod get_spending_alerts_params;
+pub mod get_subscription_charges_params;
+pub mod get_tags_params;
 pub mod get_transaction_groups_params;
+pub mod get_transaction_rules_params;
 pub mod get_transaction_with_entries_params;
+pub mod get_transfer_legs_params;
 pub mod paging_params;
@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 use crate::view_models::accounts::base_models::account_identifier::AccountIdentifierViewModel;
+use crate::view_models::accounts::base_models::cost_basis_method::CostBasisMethod;
 use crate::view_models::accounts::base_models::ownership_share::OwnershipShare;
 
 use super::base_models::account::{AccountViewModel, IdentifiableAccountViewModel};
@@ -21,6 +22,8 @@ pub struct AddAccountRequestViewModel {
     pub liquidity_type: RequiredLiquidityTypeId,
     #[serde(default)]
     pub identifiers: Vec<AccountIdentifierViewModel>,
+    #[serde(default)]
+    pub cost_basis_method: CostBasisMethod,
 }
 
 #[cfg(feature = "backend")]
@@ -40,6 +43,7 @@ impl From<AddAccountRequestViewModel>
             account_type: body.account.account_type.0,
             account_liquidity_type: body.liquidity_type.0,
             ownership_share: body.ownership_share.as_decimal(),
+            cost_basis_method: Some(body.cost_basis_method.to_business()),
             identifiers: identifiers_to_dtos(&body.identifiers),
         }
     }
@@ -53,4 +57,6 @@ pub struct AddAccountResponseViewModel {
     pub liquidity_type: RequiredLiquidityTypeId,
     #[serde(default)]
     pub identifiers: Vec<AccountIdentifierViewModel>,
+    #[serde(default)]
+    pub cost_basis_method: CostBasisMethod,
 }
@@ -0,0 +1,39 @@
+#[cfg(feature = "backend")]
+use business::dtos::accounts::cost_basis_method_dto::CostBasisMethodDto;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Order in which sales and transfers out consume the account's lots.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CostBasisMethod {
+    #[default]
+    Fifo,
+    Lifo,
+    Hifo,
+    AverageCost,
+}
+
+#[cfg(feature = "backend")]
+impl From<CostBasisMethodDto> for CostBasisMethod {
+    fn from(value: CostBasisMethodDto) -> Self {
+        match value {
+            CostBasisMethodDto::Fifo => Self::Fifo,
+            CostBasisMethodDto::Lifo => Self::Lifo,
+            CostBasisMethodDto::Hifo => Self::Hifo,
+            CostBasisMethodDto::AverageCost => Self::AverageCost,
+        }
+    }
+}
+
+#[cfg(feature = "backend")]
+impl CostBasisMethod {
+    pub fn to_business(self) -> CostBasisMethodDto {
+        match self {
+            Self::Fifo => CostBasisMethodDto::Fifo,
+            Self::Lifo => CostBasisMethodDto::Lifo,
+            Self::Hifo => CostBasisMethodDto::Hifo,
+            Self::AverageCost => CostBasisMethodDto::AverageCost,
+        }
+    }
+}
@@ -5,6 +5,7 @@ pub mod account_liquidity_type;
 pub mod account_name;
 pub mod account_type;
 pub mod account_type_id;
+pub mod cost_basis_method;
 pub mod liquidity_type_id;
 pub mod metadata_lookup;
 pub mod ownership_share;
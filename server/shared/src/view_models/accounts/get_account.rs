@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 use crate::view_models::accounts::base_models::account_identifier::AccountIdentifierViewModel;
+use crate::view_models::accounts::base_models::cost_basis_method::CostBasisMethod;
 use crate::view_models::accounts::base_models::ownership_share::OwnershipShare;
 
 use super::base_models::{
@@ -16,4 +17,6 @@ pub struct GetAccountResponseViewModel {
     pub liquidity_type: IdentifiableAccountLiquidityTypeViewModel,
     #[serde(default)]
     pub identifiers: Vec<AccountIdentifierViewModel>,
+    #[serde(default)]
+    pub cost_basis_method: CostBasisMethod,
 }
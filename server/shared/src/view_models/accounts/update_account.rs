@@ -1,6 +1,7 @@
 use serde::{Deserialize, Serialize};
 
 use crate::view_models::accounts::base_models::account_identifier::AccountIdentifierViewModel;
+use crate::view_models::accounts::base_models::cost_basis_method::CostBasisMethod;
 use crate::view_models::accounts::base_models::ownership_share::OwnershipShare;
 
 use super::base_models::account::AccountViewModel;
@@ -21,6 +22,9 @@ pub struct UpdateAccountViewModel {
     pub liquidity_type: RequiredLiquidityTypeId,
     #[serde(default)]
     pub identifiers: Vec<AccountIdentifierViewModel>,
+    /// Left out, the account keeps its current cost-basis method.
+    #[serde(default)]
+    pub cost_basis_method: Option<CostBasisMethod>,
 }
 
 #[cfg(feature = "backend")]
@@ -40,6 +44,7 @@ impl From<UpdateAccountViewModel>
             account_type: body.account.account_type.0,
             account_liquidity_type: body.liquidity_type.0,
             ownership_share: body.ownership_share.as_decimal(),
+            cost_basis_method: body.cost_basis_method.map(CostBasisMethod::to_business),
             identifiers: identifiers_to_dtos(&body.identifiers),
         }
     }
@@ -0,0 +1,50 @@
+#[cfg(feature = "backend")]
+use business::dtos::category_suggestion_dto::CategorySuggestionDto;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use uuid::Uuid;
+
+use crate::view_models::categories::base_models::category::IdentifiableCategoryViewModel;
+
+/// Transactions one suggestion request may name.
+pub const MAX_SUGGEST_TRANSACTIONS: usize = 25;
+
+#[derive(Debug, Default, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct SuggestCategoriesRequestViewModel {
+    /// Transactions to suggest categories for again. When omitted, the next
+    /// transactions without a suggestion are picked.
+    #[serde(default)]
+    pub transaction_ids: Option<Vec<Uuid>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct CategorySuggestionViewModel {
+    pub transaction_id: Uuid,
+    pub category_id: i32,
+    /// From 0 to 1.
+    pub confidence: f32,
+    pub reason: Option<String>,
+    pub model: String,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+#[derive(Debug, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct CategorySuggestionsResponseViewModel {
+    pub suggestions: Vec<CategorySuggestionViewModel>,
+    pub categories: Vec<IdentifiableCategoryViewModel>,
+}
+
+#[cfg(feature = "backend")]
+impl From<CategorySuggestionDto> for CategorySuggestionViewModel {
+    fn from(dto: CategorySuggestionDto) -> Self {
+        Self {
+            transaction_id: dto.transaction_id,
+            category_id: dto.category_id,
+            confidence: dto.confidence,
+            reason: dto.reason,
+            model: dto.model,
+            created_at: dto.created_at,
+        }
+    }
+}
@@ -1,3 +1,4 @@
+pub mod category_suggestions;
 pub mod chat;
 pub mod conversations;
 pub mod errors;
@@ -1,4 +1,7 @@
 use crate::errors::FieldError;
+use crate::view_models::ai::category_suggestions::{
+    SuggestCategoriesRequestViewModel, MAX_SUGGEST_TRANSACTIONS,
+};
 use crate::view_models::ai::conversations::SendMessageRequestViewModel;
 use crate::view_models::transactions::validation::Validatable;
 
@@ -23,3 +26,26 @@ impl Validatable for SendMessageRequestViewModel {
         Ok(())
     }
 }
+
+impl Validatable for SuggestCategoriesRequestViewModel {
+    fn validate(&self) -> Result<(), Vec<FieldError>> {
+        let Some(ids) = &self.transaction_ids else {
+            return Ok(());
+        };
+
+        if ids.is_empty() {
+            return Err(vec![FieldError {
+                field: "transaction_ids".to_string(),
+                message: "Omit transaction_ids or provide at least one.".to_string(),
+            }]);
+        }
+        if ids.len() > MAX_SUGGEST_TRANSACTIONS {
+            return Err(vec![FieldError {
+                field: "transaction_ids".to_string(),
+                message: format!("At most {MAX_SUGGEST_TRANSACTIONS} transactions at a time."),
+            }]);
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,118 @@
+#[cfg(feature = "backend")]
+use business::dtos::budgets::{AddUpdateBudgetDto, BudgetDto, BudgetPeriodDto, BudgetTargetDto};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use time::serde::timestamp;
+use utoipa::ToSchema;
+
+use crate::view_models::categories::base_models::{
+    category_id::RequiredCategoryId, category_type_id::RequiredCategoryTypeId,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetPeriod {
+    Weekly,
+    Monthly,
+    Yearly,
+}
+
+#[cfg(feature = "backend")]
+impl From<BudgetPeriodDto> for BudgetPeriod {
+    fn from(period: BudgetPeriodDto) -> Self {
+        match period {
+            BudgetPeriodDto::Weekly => Self::Weekly,
+            BudgetPeriodDto::Monthly => Self::Monthly,
+            BudgetPeriodDto::Yearly => Self::Yearly,
+        }
+    }
+}
+
+#[cfg(feature = "backend")]
+impl BudgetPeriod {
+    pub fn to_business(self) -> BudgetPeriodDto {
+        match self {
+            Self::Weekly => BudgetPeriodDto::Weekly,
+            Self::Monthly => BudgetPeriodDto::Monthly,
+            Self::Yearly => BudgetPeriodDto::Yearly,
+        }
+    }
+}
+
+/// Either a single category or every category of a category type.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BudgetTarget {
+    Category(RequiredCategoryId),
+    CategoryType(RequiredCategoryTypeId),
+}
+
+#[cfg(feature = "backend")]
+impl From<BudgetTargetDto> for BudgetTarget {
+    fn from(target: BudgetTargetDto) -> Self {
+        match target {
+            BudgetTargetDto::Category(id) => Self::Category(RequiredCategoryId(id)),
+            BudgetTargetDto::CategoryType(id) => Self::CategoryType(RequiredCategoryTypeId(id)),
+        }
+    }
+}
+
+#[cfg(feature = "backend")]
+impl BudgetTarget {
+    pub fn to_business(self) -> BudgetTargetDto {
+        match self {
+            Self::Category(id) => BudgetTargetDto::Category(id.0),
+            Self::CategoryType(id) => BudgetTargetDto::CategoryType(id.0),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct BudgetViewModel {
+    pub target: BudgetTarget,
+    /// Limit per period, in the user's default asset.
+    pub amount: Decimal,
+    pub period: BudgetPeriod,
+    /// Carry unspent amounts over into the next period.
+    pub rollover: bool,
+    /// The budget is tracked from the period containing this date.
+    #[serde(with = "timestamp")]
+    #[schema(value_type = i64)]
+    pub start_date: time::OffsetDateTime,
+}
+
+#[cfg(feature = "backend")]
+impl BudgetViewModel {
+    pub fn to_business(self) -> AddUpdateBudgetDto {
+        AddUpdateBudgetDto {
+            target: self.target.to_business(),
+            amount: self.amount,
+            period: self.period.to_business(),
+            rollover: self.rollover,
+            start_date: self.start_date,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct IdentifiableBudgetViewModel {
+    pub id: uuid::Uuid,
+    #[serde(flatten)]
+    pub budget: BudgetViewModel,
+}
+
+#[cfg(feature = "backend")]
+impl From<BudgetDto> for IdentifiableBudgetViewModel {
+    fn from(dto: BudgetDto) -> Self {
+        Self {
+            id: dto.id,
+            budget: BudgetViewModel {
+                target: dto.target.into(),
+                amount: dto.amount,
+                period: dto.period.into(),
+                rollover: dto.rollover,
+                start_date: dto.start_date,
+            },
+        }
+    }
+}
@@ -0,0 +1,62 @@
+#[cfg(feature = "backend")]
+use business::dtos::budgets::BudgetActualDto;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use time::serde::timestamp;
+use utoipa::ToSchema;
+
+use super::base_models::IdentifiableBudgetViewModel;
+use crate::view_models::assets::base_models::asset_id::RequiredAssetId;
+
+#[derive(Clone, Debug, Serialize, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct GetBudgetActualsQueryParams {
+    /// Report on the budget periods containing this date. Defaults to now.
+    #[serde(default, with = "timestamp::option")]
+    #[param(value_type = Option<i64>)]
+    pub date: Option<time::OffsetDateTime>,
+
+    /// The asset to report amounts in. If not provided, the default asset id from the user will be used
+    #[serde(default)]
+    pub default_asset_id: Option<i32>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct BudgetActualViewModel {
+    #[serde(flatten)]
+    pub budget: IdentifiableBudgetViewModel,
+    #[schema(example = "2024-06-01")]
+    pub period_start: String,
+    #[schema(example = "2024-06-30")]
+    pub period_end: String,
+    pub budgeted: Decimal,
+    /// Unspent amount brought forward from the previous period.
+    pub carried_over: Decimal,
+    pub available: Decimal,
+    /// Net outflow booked against the budget this period.
+    pub spent: Decimal,
+    /// Negative when the budget is overspent.
+    pub remaining: Decimal,
+}
+
+#[cfg(feature = "backend")]
+impl From<BudgetActualDto> for BudgetActualViewModel {
+    fn from(dto: BudgetActualDto) -> Self {
+        Self {
+            budget: dto.budget.into(),
+            period_start: dto.period.start_date.to_string(),
+            period_end: dto.period.end_date.to_string(),
+            budgeted: dto.period.budgeted,
+            carried_over: dto.period.carried_over,
+            available: dto.period.available(),
+            spent: dto.period.spent,
+            remaining: dto.period.remaining(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct GetBudgetActualsResponseViewModel {
+    pub reference_asset_id: RequiredAssetId,
+    pub budgets: Vec<BudgetActualViewModel>,
+}
@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::base_models::IdentifiableBudgetViewModel;
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct GetBudgetsResponseViewModel {
+    pub budgets: Vec<IdentifiableBudgetViewModel>,
+}
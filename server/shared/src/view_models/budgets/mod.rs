@@ -0,0 +1,3 @@
+pub mod base_models;
+pub mod get_budget_actuals;
+pub mod get_budgets;
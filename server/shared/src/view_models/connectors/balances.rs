@@ -0,0 +1,87 @@
+#[cfg(feature = "backend")]
+use business::dtos::connectors::{
+    ProviderBalanceKindDto, ProviderBalanceLineDto, ProviderBalanceSnapshotDto,
+};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use time::serde::timestamp;
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+#[serde(default)]
+pub struct GetBindingBalancesQueryParams {
+    /// Only return balances fetched at or after this time.
+    #[serde(with = "timestamp::option")]
+    #[param(value_type = Option<i64>)]
+    pub from: Option<time::OffsetDateTime>,
+
+    /// Only return balances fetched at or before this time.
+    #[serde(with = "timestamp::option")]
+    #[param(value_type = Option<i64>)]
+    pub to: Option<time::OffsetDateTime>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProviderBalanceKind {
+    Cash,
+    Holding,
+}
+
+#[cfg(feature = "backend")]
+impl From<ProviderBalanceKindDto> for ProviderBalanceKind {
+    fn from(kind: ProviderBalanceKindDto) -> Self {
+        match kind {
+            ProviderBalanceKindDto::Cash => Self::Cash,
+            ProviderBalanceKindDto::Holding => Self::Holding,
+        }
+    }
+}
+
+/// One currency or instrument of a reported balance.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct ProviderBalanceLineViewModel {
+    pub kind: ProviderBalanceKind,
+    /// Currency code or instrument ticker as the provider reported it.
+    pub asset_identifier: String,
+    /// Asset the identifier resolved to when the balance was fetched.
+    pub asset_id: Option<i32>,
+    pub quantity: Decimal,
+}
+
+#[cfg(feature = "backend")]
+impl From<ProviderBalanceLineDto> for ProviderBalanceLineViewModel {
+    fn from(dto: ProviderBalanceLineDto) -> Self {
+        Self {
+            kind: dto.kind.into(),
+            asset_identifier: dto.asset_identifier,
+            asset_id: dto.asset_id,
+            quantity: dto.quantity,
+        }
+    }
+}
+
+/// The balance the provider reported on one sync.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct ProviderBalanceSnapshotViewModel {
+    #[serde(with = "timestamp")]
+    #[schema(value_type = i64)]
+    pub fetched_at: time::OffsetDateTime,
+    pub balances: Vec<ProviderBalanceLineViewModel>,
+}
+
+#[cfg(feature = "backend")]
+impl From<ProviderBalanceSnapshotDto> for ProviderBalanceSnapshotViewModel {
+    fn from(dto: ProviderBalanceSnapshotDto) -> Self {
+        Self {
+            fetched_at: dto.fetched_at,
+            balances: dto.lines.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct GetBindingBalancesResponseViewModel {
+    pub snapshots: Vec<ProviderBalanceSnapshotViewModel>,
+}
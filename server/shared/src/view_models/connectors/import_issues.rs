@@ -0,0 +1,77 @@
+#[cfg(feature = "backend")]
+use business::dtos::connectors::{ConnectorImportIssueDto, ImportIssueStageDto};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use time::serde::timestamp;
+use utoipa::ToSchema;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ImportIssueStage {
+    /// The provider item is not a transaction Sverto imports (fees, FX, non-cash rows).
+    Skipped,
+    /// The transaction's currency or instrument matches no asset. A ticker alias resolves it.
+    Unresolved,
+}
+
+#[cfg(feature = "backend")]
+impl From<ImportIssueStageDto> for ImportIssueStage {
+    fn from(stage: ImportIssueStageDto) -> Self {
+        match stage {
+            ImportIssueStageDto::Skipped => Self::Skipped,
+            ImportIssueStageDto::Unresolved => Self::Unresolved,
+        }
+    }
+}
+
+/// A provider item that was not imported. Transaction details are only known for
+/// unresolved items.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct ConnectorImportIssueViewModel {
+    pub id: uuid::Uuid,
+    pub binding_id: uuid::Uuid,
+    pub external_id: String,
+    pub stage: ImportIssueStage,
+    pub reason: String,
+    #[serde(with = "timestamp::option")]
+    #[schema(value_type = Option<i64>)]
+    pub date_transacted: Option<time::OffsetDateTime>,
+    pub amount: Option<Decimal>,
+    pub currency: Option<String>,
+    pub description: Option<String>,
+    /// Provider ticker to map with a ticker alias.
+    pub asset_identifier: Option<String>,
+    pub quantity: Option<Decimal>,
+    #[serde(with = "timestamp")]
+    #[schema(value_type = i64)]
+    pub first_seen_at: time::OffsetDateTime,
+    #[serde(with = "timestamp")]
+    #[schema(value_type = i64)]
+    pub last_seen_at: time::OffsetDateTime,
+}
+
+#[cfg(feature = "backend")]
+impl From<ConnectorImportIssueDto> for ConnectorImportIssueViewModel {
+    fn from(dto: ConnectorImportIssueDto) -> Self {
+        Self {
+            id: dto.id,
+            binding_id: dto.binding_id,
+            external_id: dto.external_id,
+            stage: dto.stage.into(),
+            reason: dto.reason,
+            date_transacted: dto.date_transacted,
+            amount: dto.amount,
+            currency: dto.currency,
+            description: dto.description,
+            asset_identifier: dto.asset_identifier,
+            quantity: dto.quantity,
+            first_seen_at: dto.first_seen_at,
+            last_seen_at: dto.last_seen_at,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct GetImportIssuesResponseViewModel {
+    pub issues: Vec<ConnectorImportIssueViewModel>,
+}
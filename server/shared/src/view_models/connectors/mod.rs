@@ -1,12 +1,16 @@
+pub mod balances;
 pub mod base_models;
 pub mod create_binding;
 pub mod create_connection;
 pub mod get_bindings;
 pub mod get_connections;
+pub mod import_issues;
 pub mod ingest;
 pub mod list_provider_account_transactions;
 pub mod list_provider_accounts;
 pub mod oauth;
+pub mod review_inbox;
+pub mod statement_import;
 pub mod sync_binding;
 pub mod sync_checkpoint;
 pub mod update_binding;
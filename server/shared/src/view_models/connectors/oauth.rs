@@ -32,6 +32,10 @@ pub struct OAuthCallbackQuery {
     pub state: Option<String>,
     pub error: Option<String>,
     pub error_description: Option<String>,
+    /// Set by aggregators that redirect with the requisition reference instead of an
+    /// authorization code (GoCardless).
+    #[serde(rename = "ref")]
+    pub reference: Option<String>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
@@ -0,0 +1,152 @@
+#[cfg(feature = "backend")]
+use business::dtos::connectors::{
+    DuplicateCandidateDto, PendingReviewTransactionDto, ReviewEditDto, ReviewMergeDto,
+    ReviewSuggestionDto,
+};
+use serde::{Deserialize, Serialize};
+use time::serde::timestamp;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::view_models::transactions::{
+    base_models::metadata_lookup::MetadataLookupTables,
+    transaction_types::{
+        RequiredTransactionWithIdentifiableEntries, TransactionWithIdentifiableEntries,
+    },
+};
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+#[serde(default)]
+pub struct ReviewInboxQuery {
+    /// Only list transactions imported by this binding.
+    pub binding_id: Option<Uuid>,
+}
+
+/// Suggestions borrowed from the closest transaction the user has already reviewed, with
+/// the nearest category and asset by embedding. All empty until the description is
+/// embedded.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct ReviewSuggestionsViewModel {
+    pub similar_transaction_id: Option<Uuid>,
+    pub category_id: Option<i32>,
+    pub group_id: Option<Uuid>,
+    pub asset_id: Option<i32>,
+}
+
+#[cfg(feature = "backend")]
+impl From<ReviewSuggestionDto> for ReviewSuggestionsViewModel {
+    fn from(dto: ReviewSuggestionDto) -> Self {
+        Self {
+            similar_transaction_id: dto.similar_transaction_id,
+            category_id: dto.category_id,
+            group_id: dto.group_id,
+            asset_id: dto.asset_id,
+        }
+    }
+}
+
+/// A transaction the user entered by hand that the import may duplicate. Merging the
+/// two keeps the manual transaction.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, ToSchema)]
+pub struct DuplicateCandidateViewModel {
+    pub transaction_id: Uuid,
+    /// Match confidence between 0 and 1.
+    pub score: f32,
+}
+
+#[cfg(feature = "backend")]
+impl From<DuplicateCandidateDto> for DuplicateCandidateViewModel {
+    fn from(dto: DuplicateCandidateDto) -> Self {
+        Self {
+            transaction_id: dto.transaction_id,
+            score: dto.score,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct ReviewInboxItemViewModel {
+    pub binding_id: Uuid,
+    pub external_id: String,
+    #[serde(with = "timestamp")]
+    #[schema(value_type = i64)]
+    pub imported_at: time::OffsetDateTime,
+    pub transaction: RequiredTransactionWithIdentifiableEntries,
+    pub suggestions: ReviewSuggestionsViewModel,
+    /// Best first; empty unless the import looked like a manual entry.
+    pub duplicate_candidates: Vec<DuplicateCandidateViewModel>,
+}
+
+#[cfg(feature = "backend")]
+impl From<PendingReviewTransactionDto> for ReviewInboxItemViewModel {
+    fn from(dto: PendingReviewTransactionDto) -> Self {
+        Self {
+            binding_id: dto.binding_id,
+            external_id: dto.external_id,
+            imported_at: dto.imported_at,
+            transaction: dto.transaction.into(),
+            suggestions: dto.suggestions.into(),
+            duplicate_candidates: dto
+                .duplicate_candidates
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct GetReviewInboxResponseViewModel {
+    pub items: Vec<ReviewInboxItemViewModel>,
+    /// Accounts, assets and categories referenced by the transactions and suggestions.
+    pub lookup_tables: MetadataLookupTables,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct ReviewTransactionsRequestViewModel {
+    pub transaction_ids: Vec<Uuid>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct ReviewEditViewModel {
+    pub transaction_id: Uuid,
+    pub transaction: TransactionWithIdentifiableEntries,
+}
+
+#[cfg(feature = "backend")]
+impl From<ReviewEditViewModel> for ReviewEditDto {
+    fn from(view_model: ReviewEditViewModel) -> Self {
+        Self {
+            transaction_id: view_model.transaction_id,
+            transaction: view_model.transaction.into(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct EditAndAcceptRequestViewModel {
+    pub edits: Vec<ReviewEditViewModel>,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, ToSchema)]
+pub struct ReviewMergeViewModel {
+    pub ghost_transaction_id: Uuid,
+    /// The transaction the user entered by hand for the same movement.
+    pub manual_transaction_id: Uuid,
+}
+
+#[cfg(feature = "backend")]
+impl From<ReviewMergeViewModel> for ReviewMergeDto {
+    fn from(view_model: ReviewMergeViewModel) -> Self {
+        Self {
+            ghost_transaction_id: view_model.ghost_transaction_id,
+            manual_transaction_id: view_model.manual_transaction_id,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct MergeReviewTransactionsRequestViewModel {
+    pub merges: Vec<ReviewMergeViewModel>,
+}
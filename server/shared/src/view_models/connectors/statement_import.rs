@@ -0,0 +1,154 @@
+#[cfg(feature = "backend")]
+use business::dtos::connectors::{
+    CsvAmountColumnsDto, CsvColumnMappingDto, ImportStatementDto, StatementFormatDto,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum StatementFormat {
+    Csv,
+    Ofx,
+    /// Quicken's flavour of OFX; parsed as OFX.
+    Qfx,
+    Qif,
+    Camt053,
+}
+
+#[cfg(feature = "backend")]
+impl StatementFormat {
+    pub fn to_business(self) -> StatementFormatDto {
+        match self {
+            Self::Csv => StatementFormatDto::Csv,
+            Self::Ofx | Self::Qfx => StatementFormatDto::Ofx,
+            Self::Qif => StatementFormatDto::Qif,
+            Self::Camt053 => StatementFormatDto::Camt053,
+        }
+    }
+}
+
+/// Where a CSV line's amount comes from. Columns are zero-based.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum CsvAmountColumnsViewModel {
+    /// One signed column. `negate` flips the sign for banks that print money going out as positive.
+    Signed {
+        column: usize,
+        #[serde(default)]
+        negate: bool,
+    },
+    /// Separate money-out and money-in columns.
+    DebitCredit { debit: usize, credit: usize },
+}
+
+/// How to read a bank's CSV export. Columns are zero-based.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct CsvColumnMappingViewModel {
+    #[serde(default = "default_delimiter")]
+    pub delimiter: char,
+    /// Rows before the first transaction, including the header row.
+    #[serde(default = "default_skip_rows")]
+    pub skip_rows: usize,
+    pub date_column: usize,
+    /// A `time` format description, e.g. `[day]/[month]/[year]`.
+    pub date_format: String,
+    pub description_columns: Vec<usize>,
+    pub amount: CsvAmountColumnsViewModel,
+    #[serde(default = "default_decimal_separator")]
+    pub decimal_separator: char,
+    pub currency_column: Option<usize>,
+    pub reference_column: Option<usize>,
+}
+
+fn default_delimiter() -> char {
+    ','
+}
+
+fn default_skip_rows() -> usize {
+    1
+}
+
+fn default_decimal_separator() -> char {
+    '.'
+}
+
+#[cfg(feature = "backend")]
+impl From<CsvColumnMappingDto> for CsvColumnMappingViewModel {
+    fn from(dto: CsvColumnMappingDto) -> Self {
+        Self {
+            delimiter: dto.delimiter,
+            skip_rows: dto.skip_rows,
+            date_column: dto.date_column,
+            date_format: dto.date_format,
+            description_columns: dto.description_columns,
+            amount: match dto.amount {
+                CsvAmountColumnsDto::Signed { column, negate } => {
+                    CsvAmountColumnsViewModel::Signed { column, negate }
+                }
+                CsvAmountColumnsDto::DebitCredit { debit, credit } => {
+                    CsvAmountColumnsViewModel::DebitCredit { debit, credit }
+                }
+            },
+            decimal_separator: dto.decimal_separator,
+            currency_column: dto.currency_column,
+            reference_column: dto.reference_column,
+        }
+    }
+}
+
+#[cfg(feature = "backend")]
+impl CsvColumnMappingViewModel {
+    pub fn to_business(self) -> CsvColumnMappingDto {
+        CsvColumnMappingDto {
+            delimiter: self.delimiter,
+            skip_rows: self.skip_rows,
+            date_column: self.date_column,
+            date_format: self.date_format,
+            description_columns: self.description_columns,
+            amount: match self.amount {
+                CsvAmountColumnsViewModel::Signed { column, negate } => {
+                    CsvAmountColumnsDto::Signed { column, negate }
+                }
+                CsvAmountColumnsViewModel::DebitCredit { debit, credit } => {
+                    CsvAmountColumnsDto::DebitCredit { debit, credit }
+                }
+            },
+            decimal_separator: self.decimal_separator,
+            currency_column: self.currency_column,
+            reference_column: self.reference_column,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct ImportStatementRequestViewModel {
+    pub format: StatementFormat,
+    /// The statement file's text content.
+    pub content: String,
+    /// Currency code for statements that do not state one (CSV without a currency
+    /// column, QIF). Defaults to the user's default currency.
+    pub currency: Option<String>,
+    /// CSV only. Overrides the account's saved mapping for this upload.
+    pub csv_mapping: Option<CsvColumnMappingViewModel>,
+    /// Save `csv_mapping` as the account's mapping for later uploads.
+    #[serde(default)]
+    pub save_csv_mapping: bool,
+    /// QIF only. Dates are written day first rather than month first.
+    #[serde(default)]
+    pub qif_day_first: bool,
+}
+
+#[cfg(feature = "backend")]
+impl ImportStatementRequestViewModel {
+    pub fn to_business(self) -> ImportStatementDto {
+        ImportStatementDto {
+            format: self.format.to_business(),
+            content: self.content,
+            currency: self.currency,
+            csv_mapping: self.csv_mapping.map(CsvColumnMappingViewModel::to_business),
+            save_csv_mapping: self.save_csv_mapping,
+            qif_day_first: self.qif_day_first,
+        }
+    }
+}
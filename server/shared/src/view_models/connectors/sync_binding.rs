@@ -17,7 +17,16 @@ pub struct SyncReportViewModel {
     pub amended: i64,
     pub conflicts: i64,
     pub unresolved: i64,
+    pub skipped: i64,
     pub duplicates: i64,
+    /// Imports linked to a transaction the user had already entered.
+    pub linked_existing: i64,
+    /// Imports waiting in the review inbox as possible duplicates of a manual entry.
+    pub possible_duplicates: i64,
+    /// Imports folded into a balance transfer with the other side of the transfer.
+    pub transfers_matched: i64,
+    /// Imports at least one of the user's transaction rules matched.
+    pub rules_applied: i64,
     pub pages_projected: i64,
 }
 
@@ -30,7 +39,12 @@ impl From<SyncReportDto> for SyncReportViewModel {
             amended: report.amended as i64,
             conflicts: report.conflicts as i64,
             unresolved: report.unresolved as i64,
+            skipped: report.skipped as i64,
             duplicates: report.duplicates as i64,
+            linked_existing: report.linked_existing as i64,
+            possible_duplicates: report.possible_duplicates as i64,
+            transfers_matched: report.transfers_matched as i64,
+            rules_applied: report.rules_applied as i64,
             pages_projected: report.pages_projected as i64,
         }
     }
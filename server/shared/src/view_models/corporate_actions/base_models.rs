@@ -0,0 +1,109 @@
+#[cfg(feature = "backend")]
+use business::dtos::corporate_action_dto::{
+    AddCorporateActionDto, CorporateActionDto, CorporateActionTypeDto,
+};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use time::serde::timestamp;
+use utoipa::ToSchema;
+
+use crate::view_models::assets::base_models::asset_id::RequiredAssetId;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum CorporateActionType {
+    /// Every `ratio_from` units become `ratio_to` units, with `ratio_to > ratio_from`.
+    Split,
+    /// Every `ratio_from` units become `ratio_to` units, with `ratio_to < ratio_from`.
+    ReverseSplit,
+    /// Holders receive `ratio_to` units of `new_asset_id` for every `ratio_from` units
+    /// held, and `cost_basis_fraction` of the cost basis moves to them.
+    SpinOff,
+    /// The asset is replaced by `new_asset_id`, `ratio_from` old units becoming
+    /// `ratio_to` new ones.
+    TickerChange,
+}
+
+#[cfg(feature = "backend")]
+impl From<CorporateActionTypeDto> for CorporateActionType {
+    fn from(action_type: CorporateActionTypeDto) -> Self {
+        match action_type {
+            CorporateActionTypeDto::Split => Self::Split,
+            CorporateActionTypeDto::ReverseSplit => Self::ReverseSplit,
+            CorporateActionTypeDto::SpinOff => Self::SpinOff,
+            CorporateActionTypeDto::TickerChange => Self::TickerChange,
+        }
+    }
+}
+
+#[cfg(feature = "backend")]
+impl From<CorporateActionType> for CorporateActionTypeDto {
+    fn from(action_type: CorporateActionType) -> Self {
+        match action_type {
+            CorporateActionType::Split => Self::Split,
+            CorporateActionType::ReverseSplit => Self::ReverseSplit,
+            CorporateActionType::SpinOff => Self::SpinOff,
+            CorporateActionType::TickerChange => Self::TickerChange,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct CorporateActionViewModel {
+    pub asset_id: RequiredAssetId,
+    pub action_type: CorporateActionType,
+    /// Transactions from this date on are already in post-action units.
+    #[serde(with = "timestamp")]
+    #[schema(value_type = i64)]
+    pub effective_date: time::OffsetDateTime,
+    pub ratio_from: Decimal,
+    pub ratio_to: Decimal,
+    /// Asset received in a spin-off or replacing the asset in a ticker change.
+    pub new_asset_id: Option<i32>,
+    /// Share of the cost basis moved to the spun-off asset, between 0 and 1.
+    pub cost_basis_fraction: Option<Decimal>,
+}
+
+#[cfg(feature = "backend")]
+impl CorporateActionViewModel {
+    pub fn to_business(self) -> AddCorporateActionDto {
+        AddCorporateActionDto {
+            asset_id: self.asset_id.0,
+            action_type: self.action_type.into(),
+            effective_date: self.effective_date,
+            ratio_from: self.ratio_from,
+            ratio_to: self.ratio_to,
+            new_asset_id: self.new_asset_id,
+            cost_basis_fraction: self.cost_basis_fraction,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct IdentifiableCorporateActionViewModel {
+    pub id: uuid::Uuid,
+    #[serde(flatten)]
+    pub corporate_action: CorporateActionViewModel,
+    #[serde(with = "timestamp")]
+    #[schema(value_type = i64)]
+    pub created_at: time::OffsetDateTime,
+}
+
+#[cfg(feature = "backend")]
+impl From<CorporateActionDto> for IdentifiableCorporateActionViewModel {
+    fn from(dto: CorporateActionDto) -> Self {
+        Self {
+            id: dto.id,
+            corporate_action: CorporateActionViewModel {
+                asset_id: RequiredAssetId(dto.asset_id),
+                action_type: dto.action_type.into(),
+                effective_date: dto.effective_date,
+                ratio_from: dto.ratio_from,
+                ratio_to: dto.ratio_to,
+                new_asset_id: dto.new_asset_id,
+                cost_basis_fraction: dto.cost_basis_fraction,
+            },
+            created_at: dto.created_at,
+        }
+    }
+}
@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::base_models::IdentifiableCorporateActionViewModel;
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct GetCorporateActionsResponseViewModel {
+    pub corporate_actions: Vec<IdentifiableCorporateActionViewModel>,
+}
@@ -0,0 +1,114 @@
+#[cfg(feature = "backend")]
+use business::dtos::insight_digest_dto::{InsightDigestDto, InsightPeriodDto};
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::view_models::assets::base_models::asset_id::RequiredAssetId;
+
+pub const MAX_DIGESTS_PAGE_SIZE: u64 = 50;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum InsightPeriod {
+    Weekly,
+    Monthly,
+}
+
+#[cfg(feature = "backend")]
+impl From<InsightPeriodDto> for InsightPeriod {
+    fn from(period: InsightPeriodDto) -> Self {
+        match period {
+            InsightPeriodDto::Weekly => Self::Weekly,
+            InsightPeriodDto::Monthly => Self::Monthly,
+        }
+    }
+}
+
+#[cfg(feature = "backend")]
+impl From<InsightPeriod> for InsightPeriodDto {
+    fn from(period: InsightPeriod) -> Self {
+        match period {
+            InsightPeriod::Weekly => Self::Weekly,
+            InsightPeriod::Monthly => Self::Monthly,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+#[serde(default)]
+pub struct GetInsightDigestsQuery {
+    /// Only list weekly or monthly digests.
+    pub period: Option<InsightPeriod>,
+
+    #[param(maximum = 50, minimum = 1, example = 10)]
+    /// How many digests to return in a single page
+    pub count: u64,
+
+    /// The index in the list of the fist digest of the page.
+    #[param(minimum = 0, example = 0)]
+    pub start: u64,
+}
+
+impl Default for GetInsightDigestsQuery {
+    fn default() -> Self {
+        Self {
+            period: None,
+            count: 10,
+            start: 0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct InsightDigestViewModel {
+    pub id: Uuid,
+    pub period: InsightPeriod,
+    #[schema(example = "2024-03-04")]
+    pub period_start: String,
+    /// Last day of the period.
+    #[schema(example = "2024-03-10")]
+    pub period_end: String,
+    /// Asset the amounts are reported in.
+    pub reference_asset_id: RequiredAssetId,
+    /// The figures behind the digest: spending by category against the period before,
+    /// unusual transactions, the change in net worth, the portfolio's biggest movers and
+    /// the charges due in the coming period.
+    pub content: serde_json::Value,
+    /// The digest in words. Missing when the AI usage limit was reached or no AI
+    /// provider is configured.
+    pub narrative: Option<String>,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub read_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+#[cfg(feature = "backend")]
+impl From<InsightDigestDto> for InsightDigestViewModel {
+    fn from(dto: InsightDigestDto) -> Self {
+        Self {
+            id: dto.id,
+            period: dto.period.into(),
+            period_start: dto.period_start.date().to_string(),
+            period_end: dto
+                .period_end
+                .date()
+                .previous_day()
+                .unwrap_or(dto.period_end.date())
+                .to_string(),
+            reference_asset_id: RequiredAssetId(dto.reference_asset_id),
+            content: dto.content,
+            narrative: dto.narrative,
+            read_at: dto.read_at,
+            created_at: dto.created_at,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct GetInsightDigestsResponseViewModel {
+    pub digests: Vec<InsightDigestViewModel>,
+}
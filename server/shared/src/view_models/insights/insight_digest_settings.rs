@@ -0,0 +1,29 @@
+#[cfg(feature = "backend")]
+use business::dtos::insight_digest_dto::InsightDigestSettingsDto;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, ToSchema)]
+pub struct InsightDigestSettingsViewModel {
+    /// Whether Myra writes a narrative for the user's digests. Narrating sends the
+    /// digest's figures to the AI provider, so it is off until the user turns it on.
+    pub narrate: bool,
+}
+
+#[cfg(feature = "backend")]
+impl From<InsightDigestSettingsDto> for InsightDigestSettingsViewModel {
+    fn from(settings: InsightDigestSettingsDto) -> Self {
+        Self {
+            narrate: settings.narrate,
+        }
+    }
+}
+
+#[cfg(feature = "backend")]
+impl From<InsightDigestSettingsViewModel> for InsightDigestSettingsDto {
+    fn from(settings: InsightDigestSettingsViewModel) -> Self {
+        Self {
+            narrate: settings.narrate,
+        }
+    }
+}
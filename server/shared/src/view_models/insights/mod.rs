@@ -0,0 +1,2 @@
+pub mod get_insight_digests;
+pub mod insight_digest_settings;
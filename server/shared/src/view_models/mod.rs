@@ -5,10 +5,22 @@ pub mod accounts;
 pub mod ai;
 pub mod assets;
 pub mod base_models;
+pub mod budgets;
 pub mod categories;
 pub mod connectors;
+pub mod corporate_actions;
 pub mod errors;
 pub mod files;
+pub mod insights;
 pub mod portfolio;
+pub mod reconciliations;
+pub mod recurring_transactions;
+pub mod reports;
+pub mod spending_alerts;
+pub mod subscriptions;
+pub mod tags;
+pub mod ticker_aliases;
+pub mod transaction_rules;
 pub mod transactions;
+pub mod transfers;
 pub mod users;
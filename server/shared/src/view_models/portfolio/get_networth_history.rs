@@ -32,4 +32,9 @@ pub struct GetNetWorthHistoryResponseViewModel {
     pub range: String,
 
     pub sums: Vec<NetWorthPointViewModel>,
+
+    /// Value of the balance the account's connectors reported, on the dates of `sums`
+    /// from the first reported balance on. Only returned for account history.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub provider_sums: Vec<NetWorthPointViewModel>,
 }
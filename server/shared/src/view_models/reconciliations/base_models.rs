@@ -0,0 +1,134 @@
+#[cfg(feature = "backend")]
+use business::dtos::reconciliation_dto::{
+    AccountReconciliationDto, AddAccountReconciliationDto, LedgerMovementDto,
+    ReconciliationReportDto, ReconciliationSourceDto,
+};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use time::serde::timestamp;
+use utoipa::ToSchema;
+
+use crate::view_models::assets::base_models::asset_id::RequiredAssetId;
+use crate::view_models::transactions::base_models::transaction_id::RequiredTransactionId;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReconciliationSource {
+    Manual,
+    Connector,
+}
+
+#[cfg(feature = "backend")]
+impl From<ReconciliationSourceDto> for ReconciliationSource {
+    fn from(source: ReconciliationSourceDto) -> Self {
+        match source {
+            ReconciliationSourceDto::Manual => Self::Manual,
+            ReconciliationSourceDto::Connector => Self::Connector,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct ReconciliationViewModel {
+    pub asset_id: RequiredAssetId,
+    /// Date the balance was stated for. Transactions on this date are included.
+    #[serde(with = "timestamp")]
+    #[schema(value_type = i64)]
+    pub statement_date: time::OffsetDateTime,
+    pub statement_balance: Decimal,
+}
+
+#[cfg(feature = "backend")]
+impl ReconciliationViewModel {
+    pub fn to_business(self) -> AddAccountReconciliationDto {
+        AddAccountReconciliationDto {
+            asset_id: self.asset_id.0,
+            statement_date: self.statement_date,
+            statement_balance: self.statement_balance,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct IdentifiableReconciliationViewModel {
+    pub id: uuid::Uuid,
+    #[serde(flatten)]
+    pub reconciliation: ReconciliationViewModel,
+    pub source: ReconciliationSource,
+    /// Connector binding that reported the balance, for connector checkpoints.
+    pub binding_id: Option<uuid::Uuid>,
+    #[serde(with = "timestamp")]
+    #[schema(value_type = i64)]
+    pub created_at: time::OffsetDateTime,
+}
+
+#[cfg(feature = "backend")]
+impl From<AccountReconciliationDto> for IdentifiableReconciliationViewModel {
+    fn from(dto: AccountReconciliationDto) -> Self {
+        Self {
+            id: dto.id,
+            reconciliation: ReconciliationViewModel {
+                asset_id: RequiredAssetId(dto.asset_id),
+                statement_date: dto.statement_date,
+                statement_balance: dto.statement_balance,
+            },
+            source: dto.source.into(),
+            binding_id: dto.binding_id,
+            created_at: dto.created_at,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct UnreconciledTransactionViewModel {
+    pub transaction_id: RequiredTransactionId,
+    #[serde(with = "timestamp")]
+    #[schema(value_type = i64)]
+    pub date_transacted: time::OffsetDateTime,
+    /// Net quantity the transaction moved through the account.
+    pub quantity: Decimal,
+}
+
+#[cfg(feature = "backend")]
+impl From<LedgerMovementDto> for UnreconciledTransactionViewModel {
+    fn from(dto: LedgerMovementDto) -> Self {
+        Self {
+            transaction_id: RequiredTransactionId(dto.transaction_id),
+            date_transacted: dto.date_transacted,
+            quantity: dto.quantity,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct ReconciliationReportViewModel {
+    #[serde(flatten)]
+    pub reconciliation: IdentifiableReconciliationViewModel,
+    /// Sum of the account's entries in the asset up to the statement date.
+    pub ledger_balance: Decimal,
+    /// Statement balance minus ledger balance.
+    pub discrepancy: Decimal,
+    pub reconciled: bool,
+    /// Latest earlier checkpoint for the same asset that still matches the ledger.
+    pub last_reconciled: Option<IdentifiableReconciliationViewModel>,
+    /// Transactions booked since `last_reconciled`, oldest first.
+    pub unreconciled_transactions: Vec<UnreconciledTransactionViewModel>,
+}
+
+#[cfg(feature = "backend")]
+impl From<ReconciliationReportDto> for ReconciliationReportViewModel {
+    fn from(dto: ReconciliationReportDto) -> Self {
+        Self {
+            reconciled: dto.is_reconciled(),
+            reconciliation: dto.reconciliation.into(),
+            ledger_balance: dto.ledger_balance,
+            discrepancy: dto.discrepancy,
+            last_reconciled: dto.last_reconciled.map(Into::into),
+            unreconciled_transactions: dto
+                .unreconciled_transactions
+                .into_iter()
+                .map(Into::into)
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::base_models::ReconciliationReportViewModel;
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct GetReconciliationsResponseViewModel {
+    pub reconciliations: Vec<ReconciliationReportViewModel>,
+}
@@ -0,0 +1,93 @@
+#[cfg(feature = "backend")]
+use business::dtos::recurring_transactions::{
+    AddUpdateRecurringTransactionDto, RecurringTransactionDto,
+};
+use serde::{Deserialize, Serialize};
+use time::serde::timestamp;
+use utoipa::ToSchema;
+
+use crate::view_models::{
+    accounts::base_models::account_id::RequiredAccountId,
+    transactions::transaction_types::TransactionWithEntries,
+};
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct RecurringTransactionViewModel {
+    pub name: String,
+    /// Account the template belongs to. The transaction must have an entry in it.
+    pub account_id: RequiredAccountId,
+    /// Transaction created on every occurrence. Its date is replaced by the occurrence date.
+    pub transaction: TransactionWithEntries,
+    /// Schedule in iCalendar RRULE form, e.g. `FREQ=MONTHLY;BYMONTHDAY=1`.
+    /// Supports `FREQ`, `INTERVAL`, `BYDAY` for weekly and `BYMONTHDAY` for monthly rules.
+    pub rrule: String,
+    /// First occurrence. Past occurrences are not created.
+    #[serde(with = "timestamp")]
+    #[schema(value_type = i64)]
+    pub start_date: time::OffsetDateTime,
+    /// Last date an occurrence may fall on. Repeats indefinitely when omitted.
+    #[serde(default, with = "timestamp::option")]
+    #[schema(value_type = Option<i64>)]
+    pub end_date: Option<time::OffsetDateTime>,
+    /// Create occurrences as ghost transactions that show up for review before
+    /// counting towards balances.
+    #[serde(default)]
+    pub create_as_ghost: bool,
+    #[serde(default = "default_active")]
+    pub active: bool,
+}
+
+fn default_active() -> bool {
+    true
+}
+
+#[cfg(feature = "backend")]
+impl RecurringTransactionViewModel {
+    pub fn to_business(self) -> AddUpdateRecurringTransactionDto {
+        AddUpdateRecurringTransactionDto {
+            account_id: self.account_id.0,
+            name: self.name,
+            template: self.transaction.into(),
+            rrule: self.rrule,
+            start_date: self.start_date,
+            end_date: self.end_date,
+            create_as_ghost: self.create_as_ghost,
+            active: self.active,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct IdentifiableRecurringTransactionViewModel {
+    pub id: uuid::Uuid,
+    #[serde(flatten)]
+    pub recurring_transaction: RecurringTransactionViewModel,
+    /// Date of the next transaction to be created, if any are left.
+    #[serde(with = "timestamp::option")]
+    #[schema(value_type = Option<i64>)]
+    pub next_occurrence: Option<time::OffsetDateTime>,
+    #[serde(with = "timestamp::option")]
+    #[schema(value_type = Option<i64>)]
+    pub last_occurrence: Option<time::OffsetDateTime>,
+}
+
+#[cfg(feature = "backend")]
+impl From<RecurringTransactionDto> for IdentifiableRecurringTransactionViewModel {
+    fn from(dto: RecurringTransactionDto) -> Self {
+        Self {
+            id: dto.id,
+            recurring_transaction: RecurringTransactionViewModel {
+                name: dto.name,
+                account_id: RequiredAccountId(dto.account_id),
+                transaction: dto.template.into(),
+                rrule: dto.rrule,
+                start_date: dto.start_date,
+                end_date: dto.end_date,
+                create_as_ghost: dto.create_as_ghost,
+                active: dto.active,
+            },
+            next_occurrence: dto.next_occurrence,
+            last_occurrence: dto.last_occurrence,
+        }
+    }
+}
@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::base_models::IdentifiableRecurringTransactionViewModel;
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct GetRecurringTransactionsResponseViewModel {
+    pub recurring_transactions: Vec<IdentifiableRecurringTransactionViewModel>,
+}
@@ -0,0 +1,2 @@
+pub mod base_models;
+pub mod get_recurring_transactions;
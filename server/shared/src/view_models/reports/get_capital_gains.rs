@@ -0,0 +1,324 @@
+#[cfg(feature = "backend")]
+use business::dtos::portfolio::capital_gains::{
+    disposal_dto::{CapitalGainsDisposalDto, DisposalMatchDto, ShareMatchingRuleDto},
+    CapitalGainsTaxYearDto,
+};
+#[cfg(feature = "backend")]
+use itertools::Itertools;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::view_models::assets::base_models::{
+    asset::IdentifiableAssetViewModel, asset_id::RequiredAssetId,
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ReportFormat {
+    #[default]
+    Json,
+    Csv,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct GetCapitalGainsReportQueryParams {
+    /// UK tax year to report on, labelled by the year it starts in (2024 covers 2024-04-06 to 2025-04-05). All years are returned when omitted.
+    #[serde(default)]
+    pub year: Option<i32>,
+
+    /// Response format. Defaults to JSON.
+    #[serde(default)]
+    pub format: ReportFormat,
+
+    /// The asset to report amounts in. If not provided, the default asset id from the user will be used
+    #[serde(default)]
+    pub default_asset_id: Option<i32>,
+}
+
+/// Which HMRC share identification rule matched part of a disposal.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ShareMatchingRule {
+    SameDay,
+    BedAndBreakfast,
+    #[serde(rename = "section_104")]
+    Section104,
+}
+
+#[cfg(feature = "backend")]
+impl From<ShareMatchingRuleDto> for ShareMatchingRule {
+    fn from(value: ShareMatchingRuleDto) -> Self {
+        match value {
+            ShareMatchingRuleDto::SameDay => Self::SameDay,
+            ShareMatchingRuleDto::BedAndBreakfast => Self::BedAndBreakfast,
+            ShareMatchingRuleDto::Section104 => Self::Section104,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct DisposalMatchViewModel {
+    pub rule: ShareMatchingRule,
+    /// Absent for Section 104 matches, which draw from the pooled holding.
+    #[schema(example = "2024-05-01")]
+    pub acquisition_date: Option<String>,
+    pub quantity: Decimal,
+    pub cost: Decimal,
+}
+
+#[cfg(feature = "backend")]
+impl From<DisposalMatchDto> for DisposalMatchViewModel {
+    fn from(dto: DisposalMatchDto) -> Self {
+        Self {
+            rule: dto.rule.into(),
+            acquisition_date: dto.acquisition_date.map(|x| x.to_string()),
+            quantity: dto.quantity,
+            cost: dto.cost,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct CapitalGainsDisposalViewModel {
+    pub asset_id: RequiredAssetId,
+    #[schema(example = "2024-06-01")]
+    pub disposal_date: String,
+    pub quantity: Decimal,
+    pub cost_basis: Decimal,
+    pub proceeds: Decimal,
+    pub fees: Decimal,
+    pub gain: Decimal,
+    pub matches: Vec<DisposalMatchViewModel>,
+}
+
+#[cfg(feature = "backend")]
+impl From<CapitalGainsDisposalDto> for CapitalGainsDisposalViewModel {
+    fn from(dto: CapitalGainsDisposalDto) -> Self {
+        Self {
+            asset_id: RequiredAssetId(dto.asset_id),
+            disposal_date: dto.date.to_string(),
+            quantity: dto.quantity,
+            cost_basis: dto.cost_basis,
+            proceeds: dto.proceeds,
+            fees: dto.fees,
+            gain: dto.gain(),
+            matches: dto.matches.into_iter().map_into().collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct CapitalGainsTaxYearViewModel {
+    #[schema(example = 2024)]
+    pub tax_year: i32,
+    #[schema(example = "2024-04-06")]
+    pub start_date: String,
+    #[schema(example = "2025-04-05")]
+    pub end_date: String,
+    pub proceeds: Decimal,
+    pub allowable_cost: Decimal,
+    pub gain: Decimal,
+    pub dividends: Decimal,
+    pub withholding_tax: Decimal,
+    pub disposals: Vec<CapitalGainsDisposalViewModel>,
+}
+
+#[cfg(feature = "backend")]
+impl From<CapitalGainsTaxYearDto> for CapitalGainsTaxYearViewModel {
+    fn from(dto: CapitalGainsTaxYearDto) -> Self {
+        Self {
+            tax_year: dto.tax_year,
+            start_date: dto.start_date.to_string(),
+            end_date: dto.end_date.to_string(),
+            proceeds: dto.proceeds,
+            allowable_cost: dto.allowable_cost,
+            gain: dto.gain,
+            dividends: dto.dividends,
+            withholding_tax: dto.withholding_tax,
+            disposals: dto.disposals.into_iter().map_into().collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct CapitalGainsReportLookupTables {
+    pub assets: Vec<IdentifiableAssetViewModel>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct GetCapitalGainsReportResponseViewModel {
+    pub reference_asset_id: RequiredAssetId,
+    pub tax_years: Vec<CapitalGainsTaxYearViewModel>,
+    pub lookup_tables: CapitalGainsReportLookupTables,
+}
+
+impl GetCapitalGainsReportResponseViewModel {
+    const CSV_HEADER: &'static str = "tax_year,record,disposal_date,asset,quantity,acquisition_dates,matching_rules,cost_basis,proceeds,fees,gain,dividends,withholding_tax";
+
+    /// One row per disposal followed by a totals row for each tax year, in the
+    /// shape accountants usually paste into a self-assessment worksheet.
+    pub fn to_csv(&self) -> String {
+        let mut lines = vec![Self::CSV_HEADER.to_string()];
+
+        for year in &self.tax_years {
+            for disposal in &year.disposals {
+                let ticker = self
+                    .lookup_tables
+                    .assets
+                    .iter()
+                    .find(|x| x.asset_id.0 == disposal.asset_id.0)
+                    .map(|x| x.asset.ticker.as_str().to_string())
+                    .unwrap_or_else(|| disposal.asset_id.0.to_string());
+
+                let acquisition_dates = disposal
+                    .matches
+                    .iter()
+                    .filter_map(|x| x.acquisition_date.clone())
+                    .collect::<Vec<_>>()
+                    .join(";");
+
+                let matching_rules = disposal
+                    .matches
+                    .iter()
+                    .map(|x| match x.rule {
+                        ShareMatchingRule::SameDay => "same_day",
+                        ShareMatchingRule::BedAndBreakfast => "bed_and_breakfast",
+                        ShareMatchingRule::Section104 => "section_104",
+                    })
+                    .collect::<Vec<_>>()
+                    .join(";");
+
+                lines.push(csv_row(&[
+                    year.tax_year.to_string(),
+                    "disposal".to_string(),
+                    disposal.disposal_date.clone(),
+                    ticker,
+                    disposal.quantity.to_string(),
+                    acquisition_dates,
+                    matching_rules,
+                    disposal.cost_basis.to_string(),
+                    disposal.proceeds.to_string(),
+                    disposal.fees.to_string(),
+                    disposal.gain.to_string(),
+                    String::new(),
+                    String::new(),
+                ]));
+            }
+
+            let fees: Decimal = year.disposals.iter().map(|x| x.fees).sum();
+            lines.push(csv_row(&[
+                year.tax_year.to_string(),
+                "total".to_string(),
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+                String::new(),
+                (year.allowable_cost - fees).to_string(),
+                year.proceeds.to_string(),
+                fees.to_string(),
+                year.gain.to_string(),
+                year.dividends.to_string(),
+                year.withholding_tax.to_string(),
+            ]));
+        }
+
+        let mut csv = lines.join("\n");
+        csv.push('\n');
+        csv
+    }
+}
+
+fn csv_row(fields: &[String]) -> String {
+    fields
+        .iter()
+        .map(|field| {
+            if field.contains([',', '"', '\n', '\r']) {
+                format!("\"{}\"", field.replace('"', "\"\""))
+            } else {
+                field.clone()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+    use crate::view_models::assets::base_models::{
+        asset::AssetViewModel, asset_name::AssetName, asset_ticker::AssetTicker,
+        asset_type_id::RequiredAssetTypeId,
+    };
+
+    #[test]
+    fn csv_lists_disposals_and_year_totals() {
+        let report = GetCapitalGainsReportResponseViewModel {
+            reference_asset_id: RequiredAssetId(1),
+            tax_years: vec![CapitalGainsTaxYearViewModel {
+                tax_year: 2024,
+                start_date: "2024-04-06".to_string(),
+                end_date: "2025-04-05".to_string(),
+                proceeds: dec!(100),
+                allowable_cost: dec!(62),
+                gain: dec!(38),
+                dividends: dec!(12.5),
+                withholding_tax: dec!(1.5),
+                disposals: vec![CapitalGainsDisposalViewModel {
+                    asset_id: RequiredAssetId(5),
+                    disposal_date: "2024-06-01".to_string(),
+                    quantity: dec!(10),
+                    cost_basis: dec!(60),
+                    proceeds: dec!(100),
+                    fees: dec!(2),
+                    gain: dec!(38),
+                    matches: vec![
+                        DisposalMatchViewModel {
+                            rule: ShareMatchingRule::BedAndBreakfast,
+                            acquisition_date: Some("2024-06-10".to_string()),
+                            quantity: dec!(4),
+                            cost: dec!(30),
+                        },
+                        DisposalMatchViewModel {
+                            rule: ShareMatchingRule::Section104,
+                            acquisition_date: None,
+                            quantity: dec!(6),
+                            cost: dec!(30),
+                        },
+                    ],
+                }],
+            }],
+            lookup_tables: CapitalGainsReportLookupTables {
+                assets: vec![IdentifiableAssetViewModel {
+                    asset_id: RequiredAssetId(5),
+                    asset: AssetViewModel {
+                        ticker: AssetTicker::from_trusted("VWRL".to_string()),
+                        name: AssetName::from_trusted("Vanguard, All-World".to_string()),
+                        asset_type: RequiredAssetTypeId(1),
+                    },
+                }],
+            },
+        };
+
+        let csv = report.to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+
+        assert_eq!(lines.len(), 3);
+        assert_eq!(
+            lines[1],
+            "2024,disposal,2024-06-01,VWRL,10,2024-06-10,bed_and_breakfast;section_104,60,100,2,38,,"
+        );
+        assert_eq!(lines[2], "2024,total,,,,,,60,100,2,38,12.5,1.5");
+    }
+
+    #[test]
+    fn csv_quotes_fields_with_separators() {
+        assert_eq!(
+            csv_row(&["a,b".to_string(), "say \"hi\"".to_string()]),
+            "\"a,b\",\"say \"\"hi\"\"\""
+        );
+    }
+}
@@ -0,0 +1,114 @@
+#[cfg(feature = "backend")]
+use business::dtos::spending_alert_dto::{SpendingAlertDto, SpendingAlertKindDto};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use time::OffsetDateTime;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::view_models::{
+    accounts::base_models::account_id::RequiredAccountId,
+    assets::base_models::asset_id::RequiredAssetId,
+    transactions::base_models::transaction_id::RequiredTransactionId,
+};
+
+pub const MAX_ALERTS_PAGE_SIZE: u64 = 100;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SpendingAlertKind {
+    /// Far above what the user usually pays the merchant, or in the category.
+    UnusualAmount,
+    /// The same amount charged by the same merchant on the same account within a day.
+    DuplicateCharge,
+    /// First purchase from a merchant, in a currency other than the user's base currency.
+    NewMerchantAbroad,
+    /// Charged in a currency the account has not been charged in before.
+    UnexpectedCurrency,
+}
+
+#[cfg(feature = "backend")]
+impl From<SpendingAlertKindDto> for SpendingAlertKind {
+    fn from(kind: SpendingAlertKindDto) -> Self {
+        match kind {
+            SpendingAlertKindDto::UnusualAmount => Self::UnusualAmount,
+            SpendingAlertKindDto::DuplicateCharge => Self::DuplicateCharge,
+            SpendingAlertKindDto::NewMerchantAbroad => Self::NewMerchantAbroad,
+            SpendingAlertKindDto::UnexpectedCurrency => Self::UnexpectedCurrency,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+#[serde(default)]
+pub struct GetSpendingAlertsQuery {
+    /// Also list alerts the user dismissed.
+    pub include_dismissed: bool,
+
+    #[param(maximum = 100, minimum = 1, example = 20)]
+    /// How many alerts to return in a single page
+    pub count: u64,
+
+    /// The index in the list of the fist alert of the page.
+    #[param(minimum = 0, example = 0)]
+    pub start: u64,
+}
+
+impl Default for GetSpendingAlertsQuery {
+    fn default() -> Self {
+        Self {
+            include_dismissed: false,
+            count: 20,
+            start: 0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct SpendingAlertViewModel {
+    pub id: Uuid,
+    pub kind: SpendingAlertKind,
+    pub transaction_id: RequiredTransactionId,
+    pub account_id: RequiredAccountId,
+    /// Asset the transaction was paid in.
+    pub asset_id: RequiredAssetId,
+    /// Amount paid, as a positive number.
+    pub amount: Decimal,
+    /// What the user usually pays the merchant or in the category, for unusual amounts.
+    pub typical_amount: Option<Decimal>,
+    /// The earlier charge this one repeats, for duplicate charges.
+    pub related_transaction_id: Option<RequiredTransactionId>,
+    pub description: Option<String>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub date_transacted: OffsetDateTime,
+    #[serde(default, with = "time::serde::rfc3339::option")]
+    pub dismissed_at: Option<OffsetDateTime>,
+    #[serde(with = "time::serde::rfc3339")]
+    pub created_at: OffsetDateTime,
+}
+
+#[cfg(feature = "backend")]
+impl From<SpendingAlertDto> for SpendingAlertViewModel {
+    fn from(dto: SpendingAlertDto) -> Self {
+        Self {
+            id: dto.id,
+            kind: dto.kind.into(),
+            transaction_id: RequiredTransactionId(dto.transaction_id),
+            account_id: RequiredAccountId(dto.account_id),
+            asset_id: RequiredAssetId(dto.asset_id),
+            amount: dto.amount,
+            typical_amount: dto.typical_amount,
+            related_transaction_id: dto.related_transaction_id.map(RequiredTransactionId),
+            description: dto.description,
+            date_transacted: dto.date_transacted,
+            dismissed_at: dto.dismissed_at,
+            created_at: dto.created_at,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct GetSpendingAlertsResponseViewModel {
+    pub alerts: Vec<SpendingAlertViewModel>,
+}
@@ -0,0 +1,84 @@
+#[cfg(feature = "backend")]
+use business::dtos::subscription_dto::{DetectedSubscriptionDto, SubscriptionPeriodDto};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::view_models::{
+    accounts::base_models::account_id::RequiredAccountId,
+    assets::base_models::asset_id::RequiredAssetId,
+    transactions::base_models::transaction_id::RequiredTransactionId,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SubscriptionPeriod {
+    Weekly,
+    Monthly,
+    Quarterly,
+    Yearly,
+}
+
+#[cfg(feature = "backend")]
+impl From<SubscriptionPeriodDto> for SubscriptionPeriod {
+    fn from(period: SubscriptionPeriodDto) -> Self {
+        match period {
+            SubscriptionPeriodDto::Weekly => Self::Weekly,
+            SubscriptionPeriodDto::Monthly => Self::Monthly,
+            SubscriptionPeriodDto::Quarterly => Self::Quarterly,
+            SubscriptionPeriodDto::Yearly => Self::Yearly,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct DetectedSubscriptionViewModel {
+    /// Description of the most recent charge.
+    pub description: String,
+    pub account_id: RequiredAccountId,
+    /// Asset the subscription is paid in.
+    pub asset_id: RequiredAssetId,
+    pub period: SubscriptionPeriod,
+    /// Typical amount charged, as a positive number.
+    pub amount: Decimal,
+    pub last_amount: Decimal,
+    #[schema(example = "2024-01-15")]
+    pub first_charge_date: String,
+    #[schema(example = "2024-06-15")]
+    pub last_charge_date: String,
+    #[schema(example = "2024-07-15")]
+    pub next_charge_date: String,
+    pub charge_count: usize,
+    /// Between 0 and 1; how regular the dates and amounts of past charges are.
+    pub confidence: f64,
+    pub transaction_ids: Vec<RequiredTransactionId>,
+}
+
+#[cfg(feature = "backend")]
+impl From<DetectedSubscriptionDto> for DetectedSubscriptionViewModel {
+    fn from(dto: DetectedSubscriptionDto) -> Self {
+        Self {
+            description: dto.description,
+            account_id: RequiredAccountId(dto.account_id),
+            asset_id: RequiredAssetId(dto.asset_id),
+            period: dto.period.into(),
+            amount: dto.amount,
+            last_amount: dto.last_amount,
+            first_charge_date: dto.first_charge_date.to_string(),
+            last_charge_date: dto.last_charge_date.to_string(),
+            next_charge_date: dto.next_charge_date.to_string(),
+            charge_count: dto.charge_count,
+            confidence: dto.confidence,
+            transaction_ids: dto
+                .transaction_ids
+                .into_iter()
+                .map(RequiredTransactionId)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct GetSubscriptionsResponseViewModel {
+    pub subscriptions: Vec<DetectedSubscriptionViewModel>,
+}
@@ -0,0 +1,45 @@
+#[cfg(feature = "backend")]
+use business::dtos::tag_dto::TagDto;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use time::serde::timestamp;
+use utoipa::ToSchema;
+
+validated_string_type!(TagName, max_len = 50, description = "Tag name");
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct TagViewModel {
+    #[schema(example = "holiday-2026")]
+    pub name: TagName,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct IdentifiableTagViewModel {
+    pub id: uuid::Uuid,
+    #[serde(flatten)]
+    pub tag: TagViewModel,
+    #[serde(with = "timestamp")]
+    #[schema(value_type = i64)]
+    pub created_at: time::OffsetDateTime,
+}
+
+#[cfg(feature = "backend")]
+impl From<TagDto> for IdentifiableTagViewModel {
+    fn from(dto: TagDto) -> Self {
+        Self {
+            id: dto.id,
+            tag: TagViewModel {
+                name: TagName::from_trusted(dto.name),
+            },
+            created_at: dto.created_at,
+        }
+    }
+}
+
+/// Restricts a transaction listing to transactions carrying a tag, either directly
+/// or through their group.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+#[serde(default)]
+pub struct TagFilterQuery {
+    pub tag_id: Option<uuid::Uuid>,
+}
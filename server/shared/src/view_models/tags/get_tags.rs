@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::base_models::IdentifiableTagViewModel;
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct GetTagsResponseViewModel {
+    pub tags: Vec<IdentifiableTagViewModel>,
+}
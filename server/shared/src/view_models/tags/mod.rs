@@ -0,0 +1,3 @@
+pub mod base_models;
+pub mod get_tags;
+pub mod set_tags;
@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+/// Replaces every tag on the transaction or group with this set.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct SetTagsRequestViewModel {
+    pub tag_ids: Vec<Uuid>,
+}
@@ -0,0 +1,50 @@
+#[cfg(feature = "backend")]
+use business::dtos::ticker_alias_dto::{AddUserTickerAliasDto, UserTickerAliasDto};
+use serde::{Deserialize, Serialize};
+use time::serde::timestamp;
+use utoipa::ToSchema;
+
+use crate::view_models::assets::base_models::asset_id::RequiredAssetId;
+
+/// Maps a provider's instrument identifier or currency code to an asset when importing
+/// connector transactions.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct TickerAliasViewModel {
+    /// Identifier exactly as the provider reports it, e.g. `VUSAl_EQ`.
+    pub provider_ticker: String,
+    pub asset_id: RequiredAssetId,
+}
+
+#[cfg(feature = "backend")]
+impl TickerAliasViewModel {
+    pub fn to_business(self) -> AddUserTickerAliasDto {
+        AddUserTickerAliasDto {
+            provider_ticker: self.provider_ticker,
+            asset_id: self.asset_id.0,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct IdentifiableTickerAliasViewModel {
+    pub id: uuid::Uuid,
+    #[serde(flatten)]
+    pub alias: TickerAliasViewModel,
+    #[serde(with = "timestamp")]
+    #[schema(value_type = i64)]
+    pub created_at: time::OffsetDateTime,
+}
+
+#[cfg(feature = "backend")]
+impl From<UserTickerAliasDto> for IdentifiableTickerAliasViewModel {
+    fn from(dto: UserTickerAliasDto) -> Self {
+        Self {
+            id: dto.id,
+            alias: TickerAliasViewModel {
+                provider_ticker: dto.provider_ticker,
+                asset_id: RequiredAssetId(dto.asset_id),
+            },
+            created_at: dto.created_at,
+        }
+    }
+}
@@ -0,0 +1,27 @@
+#[cfg(feature = "backend")]
+use business::dtos::ticker_alias_dto::CreatedUserTickerAliasDto;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::base_models::IdentifiableTickerAliasViewModel;
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct CreateTickerAliasResponseViewModel {
+    #[serde(flatten)]
+    pub ticker_alias: IdentifiableTickerAliasViewModel,
+    /// Bindings re-projected to import the transactions the alias resolves.
+    pub reprojected_binding_ids: Vec<uuid::Uuid>,
+    /// Bindings that were busy syncing; re-project them once the sync finishes.
+    pub pending_binding_ids: Vec<uuid::Uuid>,
+}
+
+#[cfg(feature = "backend")]
+impl From<CreatedUserTickerAliasDto> for CreateTickerAliasResponseViewModel {
+    fn from(dto: CreatedUserTickerAliasDto) -> Self {
+        Self {
+            ticker_alias: dto.alias.into(),
+            reprojected_binding_ids: dto.reprojected_bindings,
+            pending_binding_ids: dto.pending_bindings,
+        }
+    }
+}
@@ -0,0 +1,9 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::base_models::IdentifiableTickerAliasViewModel;
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct GetTickerAliasesResponseViewModel {
+    pub ticker_aliases: Vec<IdentifiableTickerAliasViewModel>,
+}
@@ -0,0 +1,3 @@
+pub mod base_models;
+pub mod create_ticker_alias;
+pub mod get_ticker_aliases;
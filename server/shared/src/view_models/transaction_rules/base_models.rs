@@ -0,0 +1,171 @@
+#[cfg(feature = "backend")]
+use business::dtos::transaction_rule_dto::{
+    AddUpdateTransactionRuleDto, RuleActionsDto, RuleConditionsDto, TransactionRuleDto,
+};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use time::serde::timestamp;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::view_models::transactions::base_models::visibility::TransactionVisibility;
+
+validated_string_type!(
+    TransactionRuleName,
+    max_len = 100,
+    description = "Transaction rule name"
+);
+
+/// Every condition that is set must hold for the rule to apply; at least one is required.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+#[serde(default)]
+pub struct TransactionRuleConditions {
+    /// Matched case-insensitively anywhere in the description.
+    #[schema(example = "tesco")]
+    pub description_contains: Option<String>,
+    /// Regular expression the description must match. Its capture groups can be used in
+    /// `description_rewrite`.
+    #[schema(example = "^CARD PAYMENT TO (?<merchant>.+?),")]
+    pub description_regex: Option<String>,
+    /// Inclusive bounds on the signed amount; spending is negative.
+    pub amount_min: Option<Decimal>,
+    pub amount_max: Option<Decimal>,
+    pub account_id: Option<Uuid>,
+    /// Matched case-insensitively against the other party named by a bank connector. Only
+    /// imported transactions carry one.
+    pub counterparty: Option<String>,
+}
+
+#[cfg(feature = "backend")]
+impl From<RuleConditionsDto> for TransactionRuleConditions {
+    fn from(dto: RuleConditionsDto) -> Self {
+        Self {
+            description_contains: dto.description_contains,
+            description_regex: dto.description_regex,
+            amount_min: dto.amount_min,
+            amount_max: dto.amount_max,
+            account_id: dto.account_id,
+            counterparty: dto.counterparty,
+        }
+    }
+}
+
+#[cfg(feature = "backend")]
+impl TransactionRuleConditions {
+    pub fn to_business(self) -> RuleConditionsDto {
+        RuleConditionsDto {
+            description_contains: non_empty(self.description_contains),
+            description_regex: non_empty(self.description_regex),
+            amount_min: self.amount_min,
+            amount_max: self.amount_max,
+            account_id: self.account_id,
+            counterparty: non_empty(self.counterparty),
+        }
+    }
+}
+
+/// What the rule does to a matching transaction; at least one action is required.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, ToSchema)]
+#[serde(default)]
+pub struct TransactionRuleActions {
+    pub category_id: Option<i32>,
+    /// Tags added to the transaction. Tags it already carries are kept.
+    pub tag_ids: Vec<Uuid>,
+    /// Replaces the description. `$1` or `$name` expand to groups captured by
+    /// `description_regex`.
+    #[schema(example = "$merchant")]
+    pub description_rewrite: Option<String>,
+    pub visibility: Option<TransactionVisibility>,
+}
+
+#[cfg(feature = "backend")]
+impl From<RuleActionsDto> for TransactionRuleActions {
+    fn from(dto: RuleActionsDto) -> Self {
+        Self {
+            category_id: dto.category_id,
+            tag_ids: dto.tag_ids,
+            description_rewrite: dto.description_rewrite,
+            visibility: dto.visibility.map(Into::into),
+        }
+    }
+}
+
+#[cfg(feature = "backend")]
+impl TransactionRuleActions {
+    pub fn to_business(self) -> RuleActionsDto {
+        RuleActionsDto {
+            category_id: self.category_id,
+            tag_ids: self.tag_ids,
+            description_rewrite: non_empty(self.description_rewrite),
+            visibility: self.visibility.map(TransactionVisibility::to_business),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct TransactionRuleViewModel {
+    #[schema(example = "Groceries")]
+    pub name: TransactionRuleName,
+    /// Rules run in ascending priority. For the category, description and visibility the
+    /// first matching rule that sets one wins; tags from every matching rule are added.
+    #[serde(default)]
+    pub priority: i32,
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+    pub conditions: TransactionRuleConditions,
+    pub actions: TransactionRuleActions,
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+#[cfg(feature = "backend")]
+impl TransactionRuleViewModel {
+    pub fn to_business(self) -> AddUpdateTransactionRuleDto {
+        AddUpdateTransactionRuleDto {
+            name: self.name.into_inner(),
+            priority: self.priority,
+            enabled: self.enabled,
+            conditions: self.conditions.to_business(),
+            actions: self.actions.to_business(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct IdentifiableTransactionRuleViewModel {
+    pub id: Uuid,
+    #[serde(flatten)]
+    pub rule: TransactionRuleViewModel,
+    #[serde(with = "timestamp")]
+    #[schema(value_type = i64)]
+    pub created_at: time::OffsetDateTime,
+    #[serde(with = "timestamp")]
+    #[schema(value_type = i64)]
+    pub updated_at: time::OffsetDateTime,
+}
+
+#[cfg(feature = "backend")]
+impl From<TransactionRuleDto> for IdentifiableTransactionRuleViewModel {
+    fn from(dto: TransactionRuleDto) -> Self {
+        Self {
+            id: dto.id,
+            rule: TransactionRuleViewModel {
+                name: TransactionRuleName::from_trusted(dto.name),
+                priority: dto.priority,
+                enabled: dto.enabled,
+                conditions: dto.conditions.into(),
+                actions: dto.actions.into(),
+            },
+            created_at: dto.created_at,
+            updated_at: dto.updated_at,
+        }
+    }
+}
+
+/// Blank strings from a form mean the field is not set.
+#[cfg(feature = "backend")]
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.filter(|value| !value.trim().is_empty())
+}
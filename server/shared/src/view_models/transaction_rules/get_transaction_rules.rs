@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use super::base_models::IdentifiableTransactionRuleViewModel;
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct GetTransactionRulesResponseViewModel {
+    /// In the order the rules run.
+    pub rules: Vec<IdentifiableTransactionRuleViewModel>,
+}
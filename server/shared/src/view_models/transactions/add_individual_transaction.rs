@@ -8,6 +8,9 @@ use super::transaction_types::{
 pub struct AddIndividualTransactionRequestViewModel {
     /// Individual transaction to be added
     pub transaction: TransactionWithEntries,
+    /// Run the user's transaction rules over the transaction before it is saved.
+    #[serde(default)]
+    pub apply_rules: bool,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, utoipa::ToSchema)]
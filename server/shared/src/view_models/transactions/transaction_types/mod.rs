@@ -291,6 +291,57 @@ impl From<TransactionDto> for RequiredTransactionWithIdentifiableEntries {
     }
 }
 
+#[cfg(feature = "backend")]
+impl From<TransactionDto> for TransactionWithEntries {
+    fn from(value: TransactionDto) -> Self {
+        match value.transaction_type {
+            TransactionTypeDto::Regular(_) => TransactionWithEntries::RegularTransaction(
+                RegularTransactionInputViewModel::from(value),
+            ),
+            TransactionTypeDto::AssetPurchase(_) => {
+                TransactionWithEntries::AssetPurchase(AssetPurchaseInputViewModel::from(value))
+            }
+            TransactionTypeDto::AssetSale(_) => {
+                TransactionWithEntries::AssetSale(AssetSaleInputViewModel::from(value))
+            }
+            TransactionTypeDto::CashTransferIn(_) => {
+                TransactionWithEntries::CashTransferIn(CashTransferInInputViewModel::from(value))
+            }
+            TransactionTypeDto::CashTransferOut(_) => {
+                TransactionWithEntries::CashTransferOut(CashTransferOutInputViewModel::from(value))
+            }
+            TransactionTypeDto::CashDividend(_) => {
+                TransactionWithEntries::CashDividend(CashDividendInputViewModel::from(value))
+            }
+            TransactionTypeDto::AssetDividend(_) => {
+                TransactionWithEntries::AssetDividend(AssetDividendInputViewModel::from(value))
+            }
+            TransactionTypeDto::AssetTransferOut(_) => TransactionWithEntries::AssetTransferOut(
+                AssetTransferOutInputViewModel::from(value),
+            ),
+            TransactionTypeDto::AssetTransferIn(_) => {
+                TransactionWithEntries::AssetTransferIn(AssetTransferInInputViewModel::from(value))
+            }
+            TransactionTypeDto::AssetTrade(_) => {
+                TransactionWithEntries::AssetTrade(AssetTradeInputViewModel::from(value))
+            }
+            TransactionTypeDto::AssetBalanceTransfer(_) => {
+                TransactionWithEntries::AssetBalanceTransfer(
+                    AssetBalanceTransferInputViewModel::from(value),
+                )
+            }
+            TransactionTypeDto::AccountFees(_) => {
+                TransactionWithEntries::AccountFees(AccountFeesInputViewModel::from(value))
+            }
+            TransactionTypeDto::CashBalanceTransfer(_) => {
+                TransactionWithEntries::CashBalanceTransfer(
+                    CashBalanceTransferInputViewModel::from(value),
+                )
+            }
+        }
+    }
+}
+
 #[cfg(feature = "backend")]
 fn extract_base_no_id(
     base: TransactionBaseWithIdentifiableEntries,
@@ -0,0 +1,110 @@
+#[cfg(feature = "backend")]
+use business::dtos::transfer_match_dto::TransferPairDto;
+#[cfg(feature = "backend")]
+use business::entities::transfers::{TransferLeg, TransferMatch};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use time::serde::timestamp;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::view_models::transactions::base_models::visibility::TransactionVisibility;
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+#[serde(default)]
+pub struct GetTransferMatchesQueryParams {
+    /// Only match transactions dated at or after this time. Defaults to 90 days before `to`.
+    #[serde(with = "timestamp::option")]
+    #[param(value_type = Option<i64>)]
+    pub from: Option<time::OffsetDateTime>,
+
+    /// Only match transactions dated at or before this time. Defaults to now.
+    #[serde(with = "timestamp::option")]
+    #[param(value_type = Option<i64>)]
+    pub to: Option<time::OffsetDateTime>,
+}
+
+/// One side of a transfer, still recorded as a transaction of its own.
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct TransferLegViewModel {
+    pub transaction_id: Uuid,
+    pub account_id: Uuid,
+    pub asset_id: i32,
+    /// Negative on the outgoing side.
+    pub amount: Decimal,
+    #[serde(with = "timestamp")]
+    #[schema(value_type = i64)]
+    pub date: time::OffsetDateTime,
+    pub visibility: TransactionVisibility,
+    pub imported: bool,
+}
+
+#[cfg(feature = "backend")]
+impl From<TransferLeg> for TransferLegViewModel {
+    fn from(leg: TransferLeg) -> Self {
+        Self {
+            transaction_id: leg.transaction_id,
+            account_id: leg.account_id,
+            asset_id: leg.asset_id,
+            amount: leg.amount,
+            date: leg.date,
+            visibility: leg.visibility.into(),
+            imported: leg.imported,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct TransferMatchViewModel {
+    pub outgoing: TransferLegViewModel,
+    pub incoming: TransferLegViewModel,
+    /// The money changed currency on the way.
+    pub is_exchange: bool,
+    /// Neither side could have been paired with another transaction.
+    pub unambiguous: bool,
+}
+
+#[cfg(feature = "backend")]
+impl From<TransferMatch> for TransferMatchViewModel {
+    fn from(transfer: TransferMatch) -> Self {
+        Self {
+            is_exchange: transfer.is_exchange(),
+            unambiguous: transfer.unambiguous,
+            outgoing: transfer.outgoing.into(),
+            incoming: transfer.incoming.into(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct GetTransferMatchesResponseViewModel {
+    pub matches: Vec<TransferMatchViewModel>,
+}
+
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, ToSchema)]
+pub struct TransferPairViewModel {
+    pub outgoing_transaction_id: Uuid,
+    pub incoming_transaction_id: Uuid,
+}
+
+#[cfg(feature = "backend")]
+impl From<TransferPairViewModel> for TransferPairDto {
+    fn from(view_model: TransferPairViewModel) -> Self {
+        Self {
+            outgoing_transaction_id: view_model.outgoing_transaction_id,
+            incoming_transaction_id: view_model.incoming_transaction_id,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct ConfirmTransfersRequestViewModel {
+    pub transfers: Vec<TransferPairViewModel>,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct ConfirmTransfersResponseViewModel {
+    /// The balance transfers that replaced the pairs, in request order.
+    pub transaction_ids: Vec<Uuid>,
+}
@@ -0,0 +1,28 @@
+#[cfg(feature = "backend")]
+use business::dtos::user_data_archive_dto::UserDataImportSummaryDto;
+use serde::{Deserialize, Serialize};
+
+/// Number of records created by an archive import.
+#[derive(Clone, Debug, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ImportUserDataResponseViewModel {
+    pub accounts: usize,
+    pub custom_assets: usize,
+    pub categories: usize,
+    pub transaction_groups: usize,
+    pub transactions: usize,
+    pub connector_bindings: usize,
+}
+
+#[cfg(feature = "backend")]
+impl From<UserDataImportSummaryDto> for ImportUserDataResponseViewModel {
+    fn from(summary: UserDataImportSummaryDto) -> Self {
+        Self {
+            accounts: summary.accounts,
+            custom_assets: summary.custom_assets,
+            categories: summary.categories,
+            transaction_groups: summary.transaction_groups,
+            transactions: summary.transactions,
+            connector_bindings: summary.connector_bindings,
+        }
+    }
+}
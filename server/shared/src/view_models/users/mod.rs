@@ -1,4 +1,5 @@
 pub mod add_user_view_model;
 pub mod base_models;
+pub mod import_user_data_view_model;
 pub mod set_base_asset_view_model;
 pub mod set_onboarding_view_model;
@@ -1,10 +1,10 @@
 use apalis::prelude::{Attempt, BoxDynError, Data};
-use business::jobs::{EmbeddingJob, FileProcessingJob};
+use business::jobs::{CategorizeTransactionsJob, EmbeddingJob, FileProcessingJob, ReembedJob};
 use business::service_collection::Services;
 use clap::{Parser, Subcommand};
 use uuid::Uuid;
 
-use worker::jobs::cron::{RefreshAssetsJob, SeedAssetHistoryJob};
+use worker::jobs::cron::{GenerateInsightDigestsJob, RefreshAssetsJob, SeedAssetHistoryJob};
 use worker::jobs::{run_job, CronJob};
 
 #[derive(Parser)]
@@ -18,6 +18,8 @@ struct Cli {
 enum Jobs {
     RefreshAssets,
     SeedAssetHistory,
+    /// Build the weekly and monthly digests due for the last completed periods.
+    GenerateInsightDigests,
     EmbedTransaction {
         #[arg(long)]
         transaction_id: Uuid,
@@ -42,6 +44,16 @@ enum Jobs {
         #[arg(long)]
         text: String,
     },
+    /// Re-embed everything the active embedding model did not produce. Runs the first
+    /// batch here; the worker picks up the rest.
+    Reembed,
+    /// Suggest categories for the user's transactions still in the import category.
+    Categorize {
+        #[arg(long)]
+        user_id: Uuid,
+        #[arg(long)]
+        binding_id: Option<Uuid>,
+    },
     ProcessUploadedFile {
         #[arg(long)]
         file_id: Uuid,
@@ -65,6 +77,11 @@ async fn main() -> anyhow::Result<()> {
         Jobs::SeedAssetHistory => SeedAssetHistoryJob::tick(&services.create_providers())
             .await
             .map_err(Into::into),
+        Jobs::GenerateInsightDigests => {
+            GenerateInsightDigestsJob::tick(&services.create_providers())
+                .await
+                .map_err(Into::into)
+        }
         Jobs::EmbedTransaction {
             transaction_id,
             text,
@@ -103,6 +120,28 @@ async fn main() -> anyhow::Result<()> {
             )
             .await
         }
+        Jobs::Reembed => {
+            run_job(
+                ReembedJob::Categories { after: None },
+                Data::new(services),
+                Attempt::new_with_value(1),
+            )
+            .await
+        }
+        Jobs::Categorize {
+            user_id,
+            binding_id,
+        } => {
+            run_job(
+                CategorizeTransactionsJob {
+                    user_id,
+                    binding_id,
+                },
+                Data::new(services),
+                Attempt::new_with_value(1),
+            )
+            .await
+        }
         Jobs::ProcessUploadedFile { file_id, user_id } => {
             run_job(
                 FileProcessingJob { file_id, user_id },
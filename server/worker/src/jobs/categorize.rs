@@ -0,0 +1,25 @@
+use async_trait::async_trait;
+use business::jobs::CategorizeTransactionsJob;
+use business::service_collection::ai_categorizer_service::AiCategorizerService;
+use business::service_collection::ServiceProviders;
+
+use crate::jobs::WorkerJob;
+use crate::retry::RetryPolicy;
+
+#[async_trait]
+impl WorkerJob for CategorizeTransactionsJob {
+    const NAME: &'static str = "categorize_transactions";
+
+    fn retry_policy() -> RetryPolicy {
+        RetryPolicy::standard()
+    }
+
+    #[tracing::instrument(level = "info", skip_all, fields(user_id = %self.user_id))]
+    async fn run(&self, providers: &ServiceProviders) -> anyhow::Result<()> {
+        let suggested = AiCategorizerService::new(providers)
+            .categorize_pending(self.user_id, self.binding_id)
+            .await?;
+        tracing::info!(suggested, "category suggestions stored");
+        Ok(())
+    }
+}
@@ -0,0 +1,76 @@
+use async_trait::async_trait;
+use business::dtos::insight_digest_dto::InsightPeriodDto;
+use business::service_collection::insight_digest_service::InsightDigestService;
+use business::service_collection::ServiceProviders;
+use time::OffsetDateTime;
+
+use crate::jobs::CronJob;
+
+const DIGEST_BATCH_LIMIT: u64 = 50;
+
+pub struct GenerateInsightDigestsJob;
+
+#[async_trait]
+impl CronJob for GenerateInsightDigestsJob {
+    const NAME: &'static str = "generate-insight-digests";
+    const SCHEDULE: &'static str = "0 20 * * * *";
+
+    #[tracing::instrument(level = "info", name = "generate_insight_digests", skip_all)]
+    async fn tick(providers: &ServiceProviders) -> anyhow::Result<()> {
+        let digest_svc = InsightDigestService::new(providers);
+        let today = OffsetDateTime::now_utc().date();
+
+        for period in InsightPeriodDto::ALL {
+            let mut generated = 0;
+            // Users whose digest fails stay candidates, so later pages start after them
+            // instead of from the top.
+            let mut after = None;
+            loop {
+                let candidates = digest_svc
+                    .get_candidates(period, today, after, DIGEST_BATCH_LIMIT)
+                    .await?;
+                let Some(last) = candidates.last().map(|candidate| candidate.user_id) else {
+                    break;
+                };
+                after = Some(last);
+                let page_len = candidates.len();
+
+                for candidate in candidates {
+                    let Ok(digest) = digest_svc
+                        .generate_digest(&candidate, period, today)
+                        .await
+                        .inspect_err(|e| {
+                            tracing::warn!(
+                                user_id = %candidate.user_id,
+                                period = period.as_str(),
+                                error = ?e,
+                                error.type = "generate_insight_digest",
+                                "failed to generate insight digest"
+                            );
+                        })
+                    else {
+                        continue;
+                    };
+
+                    if digest.is_some() {
+                        generated += 1;
+                    }
+                }
+
+                if (page_len as u64) < DIGEST_BATCH_LIMIT {
+                    break;
+                }
+            }
+
+            if after.is_some() {
+                tracing::info!(
+                    period = period.as_str(),
+                    count = generated,
+                    "generated insight digests"
+                );
+            }
+        }
+
+        Ok(())
+    }
+}
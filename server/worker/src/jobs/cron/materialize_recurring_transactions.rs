@@ -0,0 +1,67 @@
+use crate::jobs::CronJob;
+use async_trait::async_trait;
+use business::service_collection::recurring_transaction_service::RecurringTransactionService;
+use business::service_collection::ServiceProviders;
+use time::OffsetDateTime;
+
+const MATERIALIZE_BATCH_LIMIT: u64 = 200;
+
+pub struct MaterializeRecurringTransactionsJob;
+
+#[async_trait]
+impl CronJob for MaterializeRecurringTransactionsJob {
+    const NAME: &'static str = "materialize_recurring_transactions";
+    const SCHEDULE: &'static str = "0 5 * * * *";
+
+    #[tracing::instrument(level = "info", name = "materialize_recurring_transactions", skip_all)]
+    async fn tick(providers: &ServiceProviders) -> anyhow::Result<()> {
+        let recurring_svc = RecurringTransactionService::new(providers);
+        let now = OffsetDateTime::now_utc();
+
+        let mut created = 0;
+        let mut total = 0;
+        // Templates that fail stay due, so later pages start after them instead of from
+        // the top.
+        let mut after = None;
+        loop {
+            let due = recurring_svc
+                .get_due_recurring_transactions(now, after, MATERIALIZE_BATCH_LIMIT)
+                .await?;
+            let Some(last) = due.last().map(|recurring| recurring.id) else {
+                break;
+            };
+            after = Some(last);
+            let page_len = due.len();
+
+            for recurring in due {
+                total += 1;
+                match recurring_svc
+                    .materialize_recurring_transaction(&recurring, now)
+                    .await
+                {
+                    Ok(count) => created += count,
+                    Err(e) => {
+                        tracing::warn!(
+                            recurring_transaction_id = %recurring.id,
+                            error = ?e,
+                            "failed to materialize recurring transaction"
+                        );
+                    }
+                }
+            }
+
+            if (page_len as u64) < MATERIALIZE_BATCH_LIMIT {
+                break;
+            }
+        }
+
+        if total > 0 {
+            tracing::info!(
+                count = created,
+                attempted = total,
+                "materialized recurring transactions"
+            );
+        }
+        Ok(())
+    }
+}
@@ -1,10 +1,14 @@
 pub mod generate_chat_titles;
+pub mod generate_insight_digests;
+pub mod materialize_recurring_transactions;
 pub mod refresh_assets;
 pub mod refresh_oauth_tokens;
 pub mod seed_asset_history;
 pub mod sync_connectors;
 
 pub use generate_chat_titles::GenerateChatTitlesJob;
+pub use generate_insight_digests::GenerateInsightDigestsJob;
+pub use materialize_recurring_transactions::MaterializeRecurringTransactionsJob;
 pub use refresh_assets::RefreshAssetsJob;
 pub use refresh_oauth_tokens::RefreshOauthTokensJob;
 pub use seed_asset_history::SeedAssetHistoryJob;
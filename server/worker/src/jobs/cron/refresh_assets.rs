@@ -2,7 +2,6 @@ use async_trait::async_trait;
 use business::dtos::asset_pair_rate_insert_dto::AssetPairRateInsertDto;
 use business::service_collection::asset_rates_service::AssetRatesService;
 use business::service_collection::ServiceProviders;
-use dal::market_data_client::MarketDataClient;
 use time::OffsetDateTime;
 
 use crate::jobs::cron::collect_market_pairs;
@@ -25,10 +24,7 @@ impl CronJob for RefreshAssetsJob {
             return Ok(());
         }
 
-        let response = MarketDataClient::new()
-            .get_latest(&requests)
-            .await
-            .map_err(anyhow::Error::msg)?;
+        let response = providers.market_data.get_latest(&requests).await?;
 
         let now = OffsetDateTime::now_utc();
         let recorded_at = now.replace_time(time::Time::from_hms(now.hour(), now.minute(), 0)?);
@@ -2,7 +2,6 @@ use async_trait::async_trait;
 use business::dtos::asset_pair_rate_insert_dto::AssetPairRateInsertDto;
 use business::service_collection::asset_rates_service::AssetRatesService;
 use business::service_collection::ServiceProviders;
-use dal::market_data_client::MarketDataClient;
 
 use crate::jobs::cron::collect_market_pairs;
 use crate::jobs::CronJob;
@@ -24,10 +23,7 @@ impl CronJob for SeedAssetHistoryJob {
             return Ok(());
         }
 
-        let response = MarketDataClient::new()
-            .get_history(&requests, None)
-            .await
-            .map_err(anyhow::Error::msg)?;
+        let response = providers.market_data.get_history(&requests, None).await?;
 
         let mut all_inserts: Vec<AssetPairRateInsertDto> = Vec::new();
 
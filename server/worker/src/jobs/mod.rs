@@ -1,8 +1,11 @@
+pub mod categorize;
 pub mod cron;
 pub mod embeddings;
 pub mod file_processing;
 pub mod quick_upload;
+pub mod spending_alerts;
 pub mod sync_connector;
+pub mod transaction_rules;
 
 use std::panic::AssertUnwindSafe;
 use std::str::FromStr;
@@ -0,0 +1,25 @@
+use async_trait::async_trait;
+use business::jobs::CheckSpendingAlertsJob;
+use business::service_collection::spending_alert_service::SpendingAlertService;
+use business::service_collection::ServiceProviders;
+
+use crate::jobs::WorkerJob;
+use crate::retry::RetryPolicy;
+
+#[async_trait]
+impl WorkerJob for CheckSpendingAlertsJob {
+    const NAME: &'static str = "check_spending_alerts";
+
+    fn retry_policy() -> RetryPolicy {
+        RetryPolicy::standard()
+    }
+
+    #[tracing::instrument(level = "info", skip_all, fields(user_id = %self.user_id))]
+    async fn run(&self, providers: &ServiceProviders) -> anyhow::Result<()> {
+        let alerts = SpendingAlertService::new(providers)
+            .check_transactions(self.user_id, self.transaction_ids.clone())
+            .await?;
+        tracing::info!(alerts = alerts.len(), "spending alerts raised");
+        Ok(())
+    }
+}
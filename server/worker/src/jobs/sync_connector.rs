@@ -1,5 +1,6 @@
 use async_trait::async_trait;
 use business::jobs::SyncConnectorBindingJob;
+use business::service_collection::ai_categorizer_service::AiCategorizerService;
 use business::service_collection::connector_sync_service::ConnectorSyncService;
 use business::service_collection::ServiceProviders;
 
@@ -17,7 +18,20 @@ impl WorkerJob for SyncConnectorBindingJob {
     #[tracing::instrument(level = "info", skip_all, fields(binding_id = %self.binding_id, user_id = %self.user_id))]
     async fn run(&self, providers: &ServiceProviders) -> anyhow::Result<()> {
         let sync_svc = ConnectorSyncService::new(providers);
-        sync_svc.sync_binding(self.user_id, self.binding_id).await?;
+        let report = sync_svc.sync_binding(self.user_id, self.binding_id).await?;
+        if report.new_transactions > 0 {
+            // Categorizing is a follow-up; failing to enqueue it must not fail the sync.
+            if let Err(e) = AiCategorizerService::new(providers)
+                .enqueue_categorize(self.user_id, Some(self.binding_id))
+                .await
+            {
+                tracing::warn!(
+                    error = ?e,
+                    error.type = "enqueue_categorize",
+                    "failed to enqueue categorization after sync"
+                );
+            }
+        }
         Ok(())
     }
 
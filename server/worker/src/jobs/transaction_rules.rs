@@ -0,0 +1,29 @@
+use async_trait::async_trait;
+use business::jobs::ApplyTransactionRulesJob;
+use business::service_collection::transaction_rule_service::TransactionRuleService;
+use business::service_collection::ServiceProviders;
+
+use crate::jobs::WorkerJob;
+use crate::retry::RetryPolicy;
+
+#[async_trait]
+impl WorkerJob for ApplyTransactionRulesJob {
+    const NAME: &'static str = "apply_transaction_rules";
+
+    fn retry_policy() -> RetryPolicy {
+        RetryPolicy::standard()
+    }
+
+    #[tracing::instrument(level = "info", skip_all, fields(user_id = %self.user_id))]
+    async fn run(&self, providers: &ServiceProviders) -> anyhow::Result<()> {
+        let report = TransactionRuleService::new(providers)
+            .apply_to_history(self.user_id)
+            .await?;
+        tracing::info!(
+            examined = report.examined,
+            updated = report.updated,
+            "transaction rules applied to history"
+        );
+        Ok(())
+    }
+}
@@ -1,10 +1,14 @@
 use apalis::prelude::{Monitor, WorkerError};
-use business::jobs::{EmbeddingJob, FileProcessingJob, QuickUploadJob, SyncConnectorBindingJob};
+use business::jobs::{
+    ApplyTransactionRulesJob, CategorizeTransactionsJob, CheckSpendingAlertsJob, EmbeddingJob,
+    FileProcessingJob, QuickUploadJob, ReembedJob, SyncConnectorBindingJob,
+};
 use business::loader::StartupLoader;
+use business::service_collection::ai_embedding_service::AiEmbeddingService;
 use business::service_collection::Services;
 use worker::jobs::cron::{
-    GenerateChatTitlesJob, RefreshAssetsJob, RefreshOauthTokensJob, SeedAssetHistoryJob,
-    SyncConnectorsJob,
+    GenerateChatTitlesJob, GenerateInsightDigestsJob, MaterializeRecurringTransactionsJob,
+    RefreshAssetsJob, RefreshOauthTokensJob, SeedAssetHistoryJob, SyncConnectorsJob,
 };
 use worker::jobs::MonitorExt;
 
@@ -20,6 +24,14 @@ async fn main() -> anyhow::Result<()> {
 
     StartupLoader::load_all().await?;
 
+    // Vectors left behind by a previous embedding model are replaced in the background.
+    if let Err(e) = AiEmbeddingService::new(&services.create_providers())
+        .enqueue_reembed_if_stale()
+        .await
+    {
+        tracing::warn!(error = %e, "could not check for stale embeddings");
+    }
+
     tracing::info!("worker starting");
 
     Monitor::new()
@@ -27,11 +39,17 @@ async fn main() -> anyhow::Result<()> {
         .register_job::<FileProcessingJob>(&services)
         .register_job::<QuickUploadJob>(&services)
         .register_job::<SyncConnectorBindingJob>(&services)
+        .register_job::<ApplyTransactionRulesJob>(&services)
+        .register_job::<ReembedJob>(&services)
+        .register_job::<CategorizeTransactionsJob>(&services)
+        .register_job::<CheckSpendingAlertsJob>(&services)
         .register_cron::<RefreshAssetsJob>(&services)
         .register_cron::<SeedAssetHistoryJob>(&services)
         .register_cron::<GenerateChatTitlesJob>(&services)
         .register_cron::<SyncConnectorsJob>(&services)
         .register_cron::<RefreshOauthTokensJob>(&services)
+        .register_cron::<MaterializeRecurringTransactionsJob>(&services)
+        .register_cron::<GenerateInsightDigestsJob>(&services)
         .should_restart(|ctx, error, attempt| {
             if matches!(error, WorkerError::GracefulExit) {
                 return false;